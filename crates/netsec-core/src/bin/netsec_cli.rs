@@ -0,0 +1,47 @@
+//! `netsec-cli` — headless front end for the netsec engine.
+//!
+//! All parsing and formatting lives in [`netsec_core::cli`]; this binary only
+//! builds the engine, prints the result, and maps errors to exit codes.
+
+use std::process::ExitCode;
+
+use netsec_core::cli::{self, CliCommand, USAGE};
+use netsec_core::NetsecEngine;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(e.exit_code() as u8);
+        }
+    };
+
+    if args.command == CliCommand::Help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let engine = match NetsecEngine::new(args.config_dir.as_deref()).await {
+        Ok(engine) => engine,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let code = match cli::run(&engine, &args).await {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(e.exit_code() as u8)
+        }
+    };
+
+    engine.shutdown().await;
+    code
+}
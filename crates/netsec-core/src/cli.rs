@@ -0,0 +1,661 @@
+//! Headless command-line front end for the [`NetsecEngine`].
+//!
+//! Argument parsing, command execution, and output formatting live here so
+//! they can be unit-tested against an in-memory engine; the `netsec-cli`
+//! binary is a thin wrapper that prints the result and sets the exit code.
+//!
+//! Supported commands:
+//! - `scan <target> [--type discovery|port|vulnerability|full|udp|tcp_udp] [--ports SPEC] [--exclude IP,CIDR,...] [--dry-run]`
+//! - `ingest <file> [--format nmap|suricata|zeek]`
+//! - `alerts list [--severity LEVEL] [--limit N]`
+//! - `devices list [--limit N]`
+//!
+//! Global flags: `--json` (machine-readable output), `--config DIR`.
+
+use std::path::{Path, PathBuf};
+
+use netsec_db::repo::alerts::AlertFilter;
+use netsec_models::alert::{Alert, ParseSeverityError, Severity};
+use netsec_models::device::Device;
+use netsec_models::scan::ScanType;
+use netsec_pipeline::normalization::{normalize, ParserOutput};
+use netsec_scanner::active::{format_scan_command, ScanConfig};
+use thiserror::Error;
+
+use crate::engine::{EngineError, NetsecEngine};
+
+/// Default number of rows returned by the `list` commands.
+pub const DEFAULT_LIST_LIMIT: i64 = 100;
+
+/// Usage text printed for `--help` and on argument errors.
+pub const USAGE: &str = "\
+usage: netsec-cli [--json] [--config DIR] <command>
+
+commands:
+  scan <target> [--type discovery|port|vulnerability|full|udp|tcp_udp] [--ports SPEC] [--exclude IP,CIDR,...] [--dry-run]
+  ingest <file> [--format nmap|suricata|zeek]
+  alerts list [--severity info|low|medium|high|critical] [--limit N]
+  devices list [--limit N]";
+
+/// Errors produced by the CLI layer.
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+}
+
+impl CliError {
+    /// Process exit code for this error: `2` for usage errors, `1` otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+pub type CliResult<T> = Result<T, CliError>;
+
+/// Input format accepted by the `ingest` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestFormat {
+    Nmap,
+    Suricata,
+    Zeek,
+}
+
+impl IngestFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nmap => "nmap",
+            Self::Suricata => "suricata",
+            Self::Zeek => "zeek",
+        }
+    }
+
+    fn parse(s: &str) -> CliResult<Self> {
+        match s {
+            "nmap" => Ok(Self::Nmap),
+            "suricata" | "eve" => Ok(Self::Suricata),
+            "zeek" => Ok(Self::Zeek),
+            other => Err(CliError::Usage(format!("unknown ingest format: {other}"))),
+        }
+    }
+
+    /// Guess the format from a file extension (`.xml`, `.json`, `.log`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "xml" => Some(Self::Nmap),
+            "json" | "jsonl" => Some(Self::Suricata),
+            "log" | "tsv" => Some(Self::Zeek),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed CLI subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Scan {
+        target: String,
+        scan_type: ScanType,
+        ports: Option<String>,
+        dry_run: bool,
+        exclude: Vec<String>,
+    },
+    Ingest {
+        path: PathBuf,
+        format: Option<IngestFormat>,
+    },
+    AlertsList {
+        min_severity: Option<Severity>,
+        limit: i64,
+    },
+    DevicesList {
+        limit: i64,
+    },
+    Help,
+}
+
+/// Fully parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    pub command: CliCommand,
+    pub json: bool,
+    pub config_dir: Option<PathBuf>,
+}
+
+/// Parse command-line arguments (excluding the program name).
+pub fn parse_args<I, S>(args: I) -> CliResult<CliArgs>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut json = false;
+    let mut config_dir = None;
+    let mut positional: Vec<String> = Vec::new();
+    let mut scan_type = None;
+    let mut ports = None;
+    let mut dry_run = false;
+    let mut exclude = Vec::new();
+    let mut format = None;
+    let mut min_severity = None;
+    let mut limit = None;
+
+    let mut iter = args.into_iter().map(Into::into);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .ok_or_else(|| CliError::Usage(format!("{flag} requires a value")))
+        };
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => positional.insert(0, "help".to_string()),
+            "--config" => config_dir = Some(PathBuf::from(value("--config")?)),
+            "--type" => scan_type = Some(parse_scan_type(&value("--type")?)?),
+            "--ports" => ports = Some(value("--ports")?),
+            "--dry-run" => dry_run = true,
+            "--exclude" => exclude = value("--exclude")?.split(',').map(String::from).collect(),
+            "--format" => format = Some(IngestFormat::parse(&value("--format")?)?),
+            "--severity" => min_severity = Some(parse_severity(&value("--severity")?)?),
+            "--limit" => {
+                let raw = value("--limit")?;
+                let n = raw
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| CliError::Usage(format!("invalid --limit: {raw}")))?;
+                limit = Some(n);
+            }
+            flag if flag.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown flag: {flag}")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    let words: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match words.as_slice() {
+        ["help", ..] => CliCommand::Help,
+        ["scan", target] => CliCommand::Scan {
+            target: target.to_string(),
+            scan_type: scan_type.unwrap_or(ScanType::Discovery),
+            ports,
+            dry_run,
+            exclude,
+        },
+        ["ingest", path] => CliCommand::Ingest {
+            path: PathBuf::from(path),
+            format,
+        },
+        ["alerts", "list"] => CliCommand::AlertsList {
+            min_severity,
+            limit,
+        },
+        ["devices", "list"] => CliCommand::DevicesList { limit },
+        [] => return Err(CliError::Usage("missing command".to_string())),
+        other => {
+            return Err(CliError::Usage(format!(
+                "unrecognized command: {}",
+                other.join(" ")
+            )))
+        }
+    };
+
+    Ok(CliArgs {
+        command,
+        json,
+        config_dir,
+    })
+}
+
+fn parse_scan_type(s: &str) -> CliResult<ScanType> {
+    match s {
+        "discovery" => Ok(ScanType::Discovery),
+        "port" => Ok(ScanType::Port),
+        "vulnerability" => Ok(ScanType::Vulnerability),
+        "full" => Ok(ScanType::Full),
+        "udp" => Ok(ScanType::Udp),
+        "tcp_udp" => Ok(ScanType::TcpUdp),
+        other => Err(CliError::Usage(format!("unknown scan type: {other}"))),
+    }
+}
+
+fn parse_severity(s: &str) -> CliResult<Severity> {
+    s.parse().map_err(|e: ParseSeverityError| CliError::Usage(e.to_string()))
+}
+
+/// Execute a parsed command against the engine and return the text to print.
+pub async fn run(engine: &NetsecEngine, args: &CliArgs) -> CliResult<String> {
+    match &args.command {
+        CliCommand::Help => Ok(USAGE.to_string()),
+        CliCommand::Scan {
+            target,
+            scan_type,
+            ports,
+            dry_run,
+            exclude,
+        } => {
+            let config = ScanConfig {
+                target: target.clone(),
+                scan_type: scan_type.clone(),
+                timing: 4,
+                ports: ports.clone(),
+                dry_run: *dry_run,
+                exclude: exclude.clone(),
+                ..Default::default()
+            };
+            let devices = engine.scan(&config).await?;
+            if *dry_run {
+                let command = format_scan_command(&config).map_err(EngineError::from)?;
+                return Ok(if args.json {
+                    serde_json::json!({ "dry_run": true, "command": command }).to_string()
+                } else {
+                    format!("dry run (not executed): {command}")
+                });
+            }
+            Ok(format_devices(&devices, args.json))
+        }
+        CliCommand::Ingest { path, format } => {
+            let format = format
+                .or_else(|| IngestFormat::from_path(path))
+                .ok_or_else(|| {
+                    CliError::Usage(format!(
+                        "cannot infer format of {}; pass --format",
+                        path.display()
+                    ))
+                })?;
+            let data = std::fs::read_to_string(path)?;
+            let summary = ingest(engine, format, &data).await?;
+            Ok(format_ingest_summary(&summary, args.json))
+        }
+        CliCommand::AlertsList {
+            min_severity,
+            limit,
+        } => {
+            let filter = AlertFilter {
+                min_severity: *min_severity,
+                limit: Some(*limit),
+                ..Default::default()
+            };
+            let alerts = netsec_db::repo::alerts::query(engine.pool(), &filter)
+                .await
+                .map_err(EngineError::from)?;
+            Ok(format_alerts(&alerts, args.json))
+        }
+        CliCommand::DevicesList { limit } => {
+            let devices = netsec_db::repo::devices::list(engine.pool(), *limit, 0)
+                .await
+                .map_err(EngineError::from)?;
+            Ok(format_devices(&devices, args.json))
+        }
+    }
+}
+
+/// Result of an `ingest` run.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct IngestSummary {
+    pub format: &'static str,
+    /// Records produced by the parser.
+    pub records: usize,
+    /// Alerts pushed through the pipeline.
+    pub alerts: usize,
+}
+
+/// Parse `data` in the given format and push every normalized alert
+/// through the engine's pipeline.
+pub async fn ingest(
+    engine: &NetsecEngine,
+    format: IngestFormat,
+    data: &str,
+) -> CliResult<IngestSummary> {
+    let outputs: Vec<ParserOutput> = match format {
+        IngestFormat::Nmap => netsec_parsers::nmap::parse_nmap_xml(data)
+            .map_err(CliError::Parse)?
+            .hosts
+            .into_iter()
+            .map(ParserOutput::Nmap)
+            .collect(),
+        IngestFormat::Suricata => netsec_parsers::suricata::parse_eve_batch(data, true)
+            .into_iter()
+            .map(ParserOutput::Suricata)
+            .collect(),
+        IngestFormat::Zeek => netsec_parsers::zeek::parse_zeek_log(data)
+            .into_iter()
+            .map(ParserOutput::Zeek)
+            .collect(),
+    };
+
+    let records = outputs.len();
+    let mut alerts = 0;
+    for output in outputs {
+        let normalized = normalize(output).map_err(EngineError::from)?;
+        for alert in normalized {
+            engine.process_alert(alert).await?;
+            alerts += 1;
+        }
+    }
+
+    Ok(IngestSummary {
+        format: format.as_str(),
+        records,
+        alerts,
+    })
+}
+
+/// Render alerts as a JSON array or a fixed-width table.
+pub fn format_alerts(alerts: &[Alert], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(alerts).unwrap_or_else(|_| "[]".to_string());
+    }
+    let rows: Vec<Vec<String>> = alerts
+        .iter()
+        .map(|a| {
+            vec![
+                a.severity.clone(),
+                a.status.clone(),
+                a.source_tool.clone(),
+                a.device_ip.clone().unwrap_or_else(|| "-".to_string()),
+                a.count.to_string(),
+                a.title.clone(),
+            ]
+        })
+        .collect();
+    render_table(&["SEVERITY", "STATUS", "SOURCE", "DEVICE", "COUNT", "TITLE"], &rows)
+}
+
+/// Render devices as a JSON array or a fixed-width table.
+pub fn format_devices(devices: &[Device], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(devices).unwrap_or_else(|_| "[]".to_string());
+    }
+    let dash = || "-".to_string();
+    let rows: Vec<Vec<String>> = devices
+        .iter()
+        .map(|d| {
+            vec![
+                d.ip.clone(),
+                d.mac.clone().unwrap_or_else(dash),
+                d.hostname.clone().unwrap_or_else(dash),
+                d.vendor.clone().unwrap_or_else(dash),
+                d.device_type.clone(),
+                d.status.clone(),
+            ]
+        })
+        .collect();
+    render_table(&["IP", "MAC", "HOSTNAME", "VENDOR", "TYPE", "STATUS"], &rows)
+}
+
+/// Render an ingest summary as a JSON object or a single line.
+pub fn format_ingest_summary(summary: &IngestSummary, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(summary).unwrap_or_else(|_| "{}".to_string());
+    }
+    format!(
+        "ingested {} {} record(s), {} alert(s) processed",
+        summary.records, summary.format, summary.alerts
+    )
+}
+
+/// Render rows as a left-aligned, space-padded table with a header line.
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if let Some(w) = widths.get_mut(i) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = vec![line(headers.to_vec())];
+    for row in rows {
+        out.push(line(row.iter().map(String::as_str).collect()));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use netsec_models::alert::{AlertCategory, NormalizedAlert};
+
+    fn config_dir() -> PathBuf {
+        let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dir.pop(); // crates/
+        dir.pop(); // workspace root
+        dir.join("config")
+    }
+
+    async fn test_engine() -> NetsecEngine {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        NetsecEngine::new_with_pool(Some(&config_dir()), pool)
+            .await
+            .unwrap()
+    }
+
+    fn parse(args: &[&str]) -> CliResult<CliArgs> {
+        parse_args(args.iter().copied())
+    }
+
+    fn normalized(title: &str, severity: Severity) -> NormalizedAlert {
+        NormalizedAlert {
+            source_tool: "suricata".into(),
+            severity,
+            category: AlertCategory::Intrusion,
+            title: title.into(),
+            description: String::new(),
+            device_ip: Some("10.0.0.5".into()),
+            fingerprint: format!("fp-{title}"),
+            raw_data: serde_json::json!({}),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_scan_with_type() {
+        let args = parse(&["--json", "scan", "10.0.0.0/24", "--type", "port"]).unwrap();
+        assert!(args.json);
+        assert_eq!(
+            args.command,
+            CliCommand::Scan {
+                target: "10.0.0.0/24".into(),
+                scan_type: ScanType::Port,
+                ports: None,
+                dry_run: false,
+                exclude: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_alerts_list_severity() {
+        let args = parse(&["alerts", "list", "--severity", "high"]).unwrap();
+        assert!(!args.json);
+        assert_eq!(
+            args.command,
+            CliCommand::AlertsList {
+                min_severity: Some(Severity::High),
+                limit: DEFAULT_LIST_LIMIT,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_are_usage() {
+        for bad in [
+            &[][..],
+            &["frobnicate"][..],
+            &["alerts", "list", "--severity", "extreme"][..],
+            &["devices", "list", "--limit", "0"][..],
+            &["scan"][..],
+            &["scan", "10.0.0.1", "--bogus"][..],
+        ] {
+            let err = parse(bad).unwrap_err();
+            assert_eq!(err.exit_code(), 2, "args {bad:?} should be a usage error");
+        }
+    }
+
+    #[test]
+    fn test_ingest_format_from_path() {
+        assert_eq!(IngestFormat::from_path(Path::new("scan.xml")), Some(IngestFormat::Nmap));
+        assert_eq!(IngestFormat::from_path(Path::new("eve.json")), Some(IngestFormat::Suricata));
+        assert_eq!(IngestFormat::from_path(Path::new("conn.log")), Some(IngestFormat::Zeek));
+        assert_eq!(IngestFormat::from_path(Path::new("capture.pcap")), None);
+    }
+
+    #[test]
+    fn test_render_table_alignment() {
+        let table = render_table(
+            &["A", "BB"],
+            &[vec!["long-value".into(), "x".into()], vec!["y".into(), "z".into()]],
+        );
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "A           BB");
+        assert_eq!(lines[1], "long-value  x");
+        assert_eq!(lines[2], "y           z");
+    }
+
+    #[tokio::test]
+    async fn test_run_devices_list_json_shape() {
+        let engine = test_engine().await;
+        let mut device = Device::new("192.168.1.10".into());
+        device.hostname = Some("nas".into());
+        netsec_db::repo::devices::insert(engine.pool(), &device)
+            .await
+            .unwrap();
+
+        let args = parse(&["--json", "devices", "list"]).unwrap();
+        let out = run(&engine, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let arr = value.as_array().unwrap();
+        assert_eq!(arr.len(), 1);
+        assert_eq!(arr[0]["ip"], "192.168.1.10");
+        assert_eq!(arr[0]["hostname"], "nas");
+    }
+
+    #[tokio::test]
+    async fn test_run_alerts_list_filters_by_severity() {
+        let engine = test_engine().await;
+        engine
+            .process_alert(normalized("low-one", Severity::Low))
+            .await
+            .unwrap();
+        engine
+            .process_alert(normalized("crit-one", Severity::Critical))
+            .await
+            .unwrap();
+
+        let args = parse(&["alerts", "list", "--severity", "high", "--json"]).unwrap();
+        let out = run(&engine, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let arr = value.as_array().unwrap();
+        assert_eq!(arr.len(), 1);
+        assert_eq!(arr[0]["title"], "crit-one");
+        assert!(arr[0]["severity"].is_string());
+
+        let table = run(&engine, &parse(&["alerts", "list"]).unwrap())
+            .await
+            .unwrap();
+        assert!(table.starts_with("SEVERITY"));
+        assert_eq!(table.lines().count(), 3);
+
+        // The limit applies after the severity filter
+        engine
+            .process_alert(normalized("low-two", Severity::Low))
+            .await
+            .unwrap();
+        let args = parse(&["alerts", "list", "--severity", "high", "--limit", "1"]).unwrap();
+        let table = run(&engine, &args).await.unwrap();
+        assert_eq!(table.lines().count(), 2);
+        assert!(table.contains("crit-one"));
+    }
+
+    #[tokio::test]
+    async fn test_run_ingest_suricata_file() {
+        let engine = test_engine().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("eve.json");
+        std::fs::write(
+            &path,
+            r#"{"event_type":"alert","src_ip":"10.0.0.1","dest_ip":"10.0.0.2","alert":{"signature":"ET SCAN","signature_id":2000001,"severity":2,"category":"Attempted Information Leak"}}
+{"event_type":"flow","src_ip":"10.0.0.1"}"#,
+        )
+        .unwrap();
+
+        let args = parse(&["--json", "ingest", path.to_str().unwrap()]).unwrap();
+        let out = run(&engine, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["format"], "suricata");
+        assert_eq!(value["records"], 1);
+        assert_eq!(value["alerts"], 1);
+        assert_eq!(netsec_db::repo::alerts::count(engine.pool()).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_ingest_missing_file_exit_code() {
+        let engine = test_engine().await;
+        let args = parse(&["ingest", "/nonexistent/netsec/eve.json"]).unwrap();
+        let err = run(&engine, &args).await.unwrap_err();
+        assert!(matches!(err, CliError::Io(_)));
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_ingest_unknown_extension_is_usage_error() {
+        let engine = test_engine().await;
+        let args = parse(&["ingest", "capture.bin"]).unwrap();
+        let err = run(&engine, &args).await.unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_invalid_target_fails() {
+        let engine = test_engine().await;
+        let args = parse(&["scan", "not a target;rm"]).unwrap();
+        let err = run(&engine, &args).await.unwrap_err();
+        assert_eq!(err.exit_code(), 1);
+        assert!(err.to_string().contains("invalid character"));
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_dry_run_prints_command() {
+        let engine = test_engine().await;
+        let args = parse(&["--json", "scan", "10.0.0.0/24", "--type", "port", "--ports", "22", "--dry-run"])
+            .unwrap();
+        let out = run(&engine, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["command"], "nmap -sS -T4 -p 22 -oX - 10.0.0.0/24");
+
+        // Dry runs don't trip the per-target cooldown
+        let again = run(&engine, &args).await.unwrap();
+        assert_eq!(again, out);
+
+        let args = parse(&[
+            "--json", "scan", "10.0.0.0/24", "--exclude", "10.0.0.1,10.0.0.64/26", "--dry-run",
+        ])
+        .unwrap();
+        let out = run(&engine, &args).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["command"], "nmap -sn -T4 --exclude 10.0.0.1,10.0.0.64/26 10.0.0.0/24");
+    }
+}
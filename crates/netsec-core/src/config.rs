@@ -1,9 +1,20 @@
 //! Layered TOML configuration loading via the `config` crate.
 //!
 //! Load order: `default.toml` (required) -> `local.toml` (optional) -> `NETSEC_` env vars.
+//! Loaded configs are bounds-checked by [`NetsecConfig::validate`];
+//! [`watch_config`] reloads the files when they change.
 
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use netsec_pipeline::PipelineConfig;
+use tokio::sync::{mpsc, watch};
+
+/// How long file events are collected before a reload, so an editor's
+/// write-rename-chmod burst triggers one reload.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
 
 /// Top-level configuration for the netsec platform.
 #[derive(Debug, Clone, Deserialize)]
@@ -17,6 +28,150 @@ pub struct NetsecConfig {
     pub tools: ToolsConfig,
 }
 
+/// Errors loading or validating the configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to load configuration: {0}")]
+    Load(#[from] config::ConfigError),
+    #[error("invalid {field}: {message}")]
+    Invalid {
+        /// Dotted path of the offending setting, e.g. `server.port`.
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl ConfigError {
+    /// The setting that failed validation, if any.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            Self::Load(_) => None,
+            Self::Invalid { field, .. } => Some(field),
+        }
+    }
+}
+
+fn invalid(field: &'static str, message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        field,
+        message: message.into(),
+    }
+}
+
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "warning", "error", "critical"];
+
+impl NetsecConfig {
+    /// The alert pipeline settings derived from this config.
+    pub fn pipeline_config(&self) -> PipelineConfig {
+        PipelineConfig {
+            correlation_window_secs: i64::try_from(self.alerts.dedup_window_seconds)
+                .unwrap_or(i64::MAX),
+            ..PipelineConfig::default()
+        }
+    }
+
+    /// Check settings that deserialize fine but can't work: out-of-range
+    /// numbers, malformed URLs, and features enabled without the settings
+    /// they need. Returns the first failing field.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.server.host.trim().is_empty() {
+            return Err(invalid("server.host", "must not be empty"));
+        }
+        if self.server.port == 0 {
+            return Err(invalid("server.port", "must be 1-65535"));
+        }
+        if self.server.workers == 0 {
+            return Err(invalid("server.workers", "must be at least 1"));
+        }
+
+        validate_database_url(&self.database.url)?;
+
+        if !LOG_LEVELS.contains(&self.logging.level.to_ascii_lowercase().as_str()) {
+            return Err(invalid(
+                "logging.level",
+                format!("unknown level {:?}", self.logging.level),
+            ));
+        }
+
+        if self.auth.enabled && self.auth.api_key.trim().is_empty() {
+            return Err(invalid("auth.api_key", "must be set when auth is enabled"));
+        }
+
+        // Only the correlation window comes from this config
+        self.pipeline_config()
+            .validate()
+            .map_err(|e| invalid("alerts.dedup_window_seconds", e.to_string()))?;
+        if self.alerts.max_alerts_per_minute == 0 {
+            return Err(invalid("alerts.max_alerts_per_minute", "must be at least 1"));
+        }
+
+        let dispatch = &self.alerts.dispatch;
+        if !dispatch.webhook_url.is_empty() {
+            validate_http_url(&dispatch.webhook_url)
+                .map_err(|message| invalid("alerts.dispatch.webhook_url", message))?;
+        }
+        if dispatch.email_enabled {
+            if dispatch.email_smtp_host.trim().is_empty() {
+                return Err(invalid(
+                    "alerts.dispatch.email_smtp_host",
+                    "must be set when email is enabled",
+                ));
+            }
+            if dispatch.email_smtp_port == 0 {
+                return Err(invalid("alerts.dispatch.email_smtp_port", "must be 1-65535"));
+            }
+            for (field, addr) in [
+                ("alerts.dispatch.email_from", &dispatch.email_from),
+                ("alerts.dispatch.email_to", &dispatch.email_to),
+            ] {
+                if !addr.contains('@') {
+                    return Err(invalid(field, format!("not an email address: {addr:?}")));
+                }
+            }
+        }
+
+        if self.tools.scan_timeout == 0 {
+            return Err(invalid("tools.scan_timeout", "must be a positive number of seconds"));
+        }
+        if self.tools.max_concurrent_scans == 0 {
+            return Err(invalid("tools.max_concurrent_scans", "must be at least 1"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Accept `sqlite:` URLs (optionally with a `+driver` suffix, as the Python
+/// backend writes them) and `postgres://` / `postgresql://` URLs.
+fn validate_database_url(url: &str) -> Result<(), ConfigError> {
+    let field = "database.url";
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return Err(invalid(field, format!("missing scheme in {url:?}")));
+    };
+    let dialect = scheme.split('+').next().unwrap_or_default();
+    match dialect {
+        "sqlite" if !rest.trim_start_matches('/').is_empty() => Ok(()),
+        "postgres" | "postgresql" if rest.len() > 2 && rest.starts_with("//") => Ok(()),
+        "sqlite" | "postgres" | "postgresql" => {
+            Err(invalid(field, format!("incomplete URL {url:?}")))
+        }
+        _ => Err(invalid(field, format!("unsupported database scheme {scheme:?}"))),
+    }
+}
+
+/// Check an `http(s)://host[...]` URL.
+fn validate_http_url(url: &str) -> Result<(), String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| format!("must be an http(s) URL, got {url:?}"))?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || url.contains(char::is_whitespace) {
+        return Err(format!("malformed URL {url:?}"));
+    }
+    Ok(())
+}
+
 /// Server configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
@@ -29,6 +184,7 @@ pub struct ServerConfig {
 /// Database configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DatabaseConfig {
+    /// `sqlite:` path, or a `postgres://` URL with the `postgres` feature.
     pub url: String,
     pub echo: bool,
 }
@@ -45,6 +201,12 @@ pub struct LoggingConfig {
 pub struct SchedulerConfig {
     pub enabled: bool,
     pub timezone: String,
+    /// Spread job dispatch over up to this many seconds (0 disables).
+    #[serde(default)]
+    pub jitter_secs: u64,
+    /// Mark devices unseen for this many seconds offline (0 disables).
+    #[serde(default)]
+    pub offline_after_secs: u64,
 }
 
 /// Authentication configuration.
@@ -59,13 +221,29 @@ pub struct AuthConfig {
 pub struct AlertsConfig {
     pub dedup_window_seconds: u64,
     pub max_alerts_per_minute: u64,
+    /// Resolve open alerts not seen for this many hours (0 disables).
+    #[serde(default)]
+    pub auto_resolve_after_hours: u64,
     pub dispatch: DispatchConfig,
 }
 
+impl AlertsConfig {
+    /// How long an alert may go unseen before it is auto-resolved, if aging
+    /// is enabled.
+    pub fn auto_resolve_after(&self) -> Option<Duration> {
+        (self.auto_resolve_after_hours > 0)
+            .then(|| Duration::from_secs(self.auto_resolve_after_hours.saturating_mul(3600)))
+    }
+}
+
 /// Alert dispatch configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DispatchConfig {
+    /// Alerts are POSTed here as JSON when non-empty.
     pub webhook_url: String,
+    /// Bearer token sent to the webhook; empty sends no `Authorization` header.
+    #[serde(default)]
+    pub webhook_token: String,
     pub email_enabled: bool,
     pub email_smtp_host: String,
     pub email_smtp_port: u16,
@@ -77,7 +255,18 @@ pub struct DispatchConfig {
 #[derive(Debug, Clone, Deserialize)]
 pub struct ToolsConfig {
     pub scan_timeout: u64,
+    /// Scans executing at once; more wait for a free slot.
     pub max_concurrent_scans: u32,
+    /// MACs allowed on multiple IPs without raising a duplicate MAC alert.
+    #[serde(default)]
+    pub mac_allowlist: Vec<String>,
+    /// Minimum seconds between scans of the same target (0 disables).
+    #[serde(default = "default_scan_cooldown_secs")]
+    pub scan_cooldown_secs: u64,
+}
+
+fn default_scan_cooldown_secs() -> u64 {
+    crate::rate_limit::DEFAULT_SCAN_COOLDOWN.as_secs()
 }
 
 /// Load configuration from TOML files and environment variables.
@@ -88,7 +277,8 @@ pub struct ToolsConfig {
 /// 3. Environment variables with prefix `NETSEC` and separator `__`
 ///
 /// If `config_dir` is `None`, defaults to `"config"` relative to the current directory.
-pub fn load_config(config_dir: Option<&Path>) -> Result<NetsecConfig, config::ConfigError> {
+/// The result is checked with [`NetsecConfig::validate`].
+pub fn load_config(config_dir: Option<&Path>) -> Result<NetsecConfig, ConfigError> {
     let dir = config_dir
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| std::path::PathBuf::from("config"));
@@ -105,14 +295,105 @@ pub fn load_config(config_dir: Option<&Path>) -> Result<NetsecConfig, config::Co
                 .try_parsing(true),
         );
 
-    let config = builder.build()?;
-    config.try_deserialize()
+    let config: NetsecConfig = builder.build()?.try_deserialize()?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Errors starting a config watch.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigWatchError {
+    #[error("initial config load failed: {0}")]
+    Load(#[from] ConfigError),
+    #[error("watching {path} failed: {source}")]
+    Watch {
+        path: PathBuf,
+        source: notify::Error,
+    },
+}
+
+/// Load the configuration in `config_dir` and keep it up to date.
+///
+/// The returned receiver holds the current config; whenever `default.toml` or
+/// `local.toml` changes the files are reloaded and, if they still load and
+/// validate, the new config is sent. A reload that fails is logged and ignored, keeping the
+/// last good config. The initial load must succeed.
+///
+/// Must be called inside a Tokio runtime. Watching stops once every receiver
+/// is dropped.
+pub fn watch_config(
+    config_dir: &Path,
+) -> Result<watch::Receiver<Arc<NetsecConfig>>, ConfigWatchError> {
+    let dir = config_dir.to_path_buf();
+    let initial = load_config(Some(&dir))?;
+    let (tx, rx) = watch::channel(Arc::new(initial));
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let watch_err = |source| ConfigWatchError::Watch {
+        path: dir.clone(),
+        source,
+    };
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        match event {
+            Ok(event) if touches_config_file(&event) => {
+                let _ = event_tx.send(());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Config watch error: {e}"),
+        }
+    })
+    .map_err(watch_err)?;
+    notify::Watcher::watch(&mut watcher, &dir, notify::RecursiveMode::NonRecursive)
+        .map_err(watch_err)?;
+
+    tokio::spawn(async move {
+        // Dropping the watcher stops the notifications
+        let _watcher = watcher;
+        loop {
+            tokio::select! {
+                _ = tx.closed() => break,
+                event = event_rx.recv() => {
+                    if event.is_none() {
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(RELOAD_DEBOUNCE).await;
+            while event_rx.try_recv().is_ok() {}
+
+            match load_config(Some(&dir)) {
+                Ok(config) => {
+                    tracing::info!("Reloaded configuration from {}", dir.display());
+                    if tx.send(Arc::new(config)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid configuration in {}: {e}", dir.display());
+                }
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Whether a file event concerns `default.toml` or `local.toml`.
+fn touches_config_file(event: &notify::Event) -> bool {
+    if event.kind.is_access() {
+        return false;
+    }
+    event.paths.iter().any(|p| {
+        matches!(
+            p.file_name().and_then(|n| n.to_str()),
+            Some("default.toml" | "local.toml")
+        )
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     fn config_dir() -> PathBuf {
         // Navigate from crate root to workspace root config/
@@ -149,6 +430,8 @@ mod tests {
         let cfg = load_config(Some(&config_dir())).unwrap();
         assert!(cfg.scheduler.enabled);
         assert_eq!(cfg.scheduler.timezone, "UTC");
+        assert_eq!(cfg.scheduler.jitter_secs, 0);
+        assert_eq!(cfg.scheduler.offline_after_secs, 0);
     }
 
     #[test]
@@ -156,12 +439,15 @@ mod tests {
         let cfg = load_config(Some(&config_dir())).unwrap();
         assert_eq!(cfg.alerts.dedup_window_seconds, 300);
         assert_eq!(cfg.alerts.max_alerts_per_minute, 100);
+        assert_eq!(cfg.alerts.auto_resolve_after_hours, 0);
+        assert_eq!(cfg.alerts.auto_resolve_after(), None);
     }
 
     #[test]
     fn test_config_tools_values() {
         let cfg = load_config(Some(&config_dir())).unwrap();
         assert_eq!(cfg.tools.scan_timeout, 300);
+        assert_eq!(cfg.tools.scan_cooldown_secs, 60);
         assert_eq!(cfg.tools.max_concurrent_scans, 3);
     }
 
@@ -170,4 +456,101 @@ mod tests {
         let result = load_config(Some(Path::new("/nonexistent/path/to/config")));
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_watch_config_reloads_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let original = std::fs::read_to_string(config_dir().join("default.toml")).unwrap();
+        std::fs::write(tmp.path().join("default.toml"), &original).unwrap();
+
+        let mut rx = watch_config(tmp.path()).unwrap();
+        assert_eq!(rx.borrow().scheduler.jitter_secs, 0);
+
+        // An invalid file is ignored and the last good config kept
+        std::fs::write(tmp.path().join("default.toml"), "[server]\nport = \"nope\"\n").unwrap();
+        tokio::time::sleep(RELOAD_DEBOUNCE * 4).await;
+        assert!(!rx.has_changed().unwrap());
+        assert_eq!(rx.borrow().server.port, 8420);
+
+        // An override in local.toml is picked up
+        std::fs::write(tmp.path().join("default.toml"), &original).unwrap();
+        std::fs::write(tmp.path().join("local.toml"), "[scheduler]\njitter_secs = 7\n").unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                rx.changed().await.unwrap();
+                if rx.borrow_and_update().scheduler.jitter_secs == 7 {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("reloaded config not observed");
+        assert_eq!(rx.borrow().server.port, 8420);
+    }
+
+    fn assert_invalid(mutate: impl FnOnce(&mut NetsecConfig), field: &str) {
+        let mut cfg = load_config(Some(&config_dir())).unwrap();
+        mutate(&mut cfg);
+        let err = cfg.validate().unwrap_err();
+        assert_eq!(err.field(), Some(field), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_values() {
+        assert_invalid(|c| c.server.port = 0, "server.port");
+        assert_invalid(|c| c.server.workers = 0, "server.workers");
+        assert_invalid(|c| c.logging.level = "loud".into(), "logging.level");
+        assert_invalid(|c| c.auth.enabled = true, "auth.api_key");
+        assert_invalid(
+            |c| c.alerts.dedup_window_seconds = 1_000_000,
+            "alerts.dedup_window_seconds",
+        );
+        assert_invalid(|c| c.alerts.max_alerts_per_minute = 0, "alerts.max_alerts_per_minute");
+        assert_invalid(|c| c.tools.scan_timeout = 0, "tools.scan_timeout");
+        assert_invalid(|c| c.tools.max_concurrent_scans = 0, "tools.max_concurrent_scans");
+        assert_invalid(
+            |c| {
+                c.alerts.dispatch.email_enabled = true;
+                c.alerts.dispatch.email_smtp_host = "smtp.example.com".into();
+                c.alerts.dispatch.email_from = "netsec@example.com".into();
+            },
+            "alerts.dispatch.email_to",
+        );
+    }
+
+    #[test]
+    fn test_validate_urls() {
+        for url in ["", "netsec.db", "mysql://db/netsec", "postgres://", "sqlite:"] {
+            assert_invalid(|c| c.database.url = url.into(), "database.url");
+        }
+        for url in ["hooks.example.com/x", "ftp://example.com", "https://", "https://a b/"] {
+            assert_invalid(
+                |c| c.alerts.dispatch.webhook_url = url.into(),
+                "alerts.dispatch.webhook_url",
+            );
+        }
+
+        let mut cfg = load_config(Some(&config_dir())).unwrap();
+        for url in [
+            "sqlite:netsec.db",
+            "sqlite+aiosqlite:///./netsec.db",
+            "postgres://u@db/netsec",
+        ] {
+            cfg.database.url = url.into();
+            cfg.validate().unwrap();
+        }
+        cfg.alerts.dispatch.webhook_url = "https://hooks.example.com/netsec?x=1".into();
+        cfg.validate().unwrap();
+    }
+
+    #[test]
+    fn test_load_config_validates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let original = std::fs::read_to_string(config_dir().join("default.toml")).unwrap();
+        std::fs::write(tmp.path().join("default.toml"), original).unwrap();
+        let local = "[tools]\nmax_concurrent_scans = 0\n";
+        std::fs::write(tmp.path().join("local.toml"), local).unwrap();
+        let err = load_config(Some(tmp.path())).unwrap_err();
+        assert_eq!(err.field(), Some("tools.max_concurrent_scans"));
+    }
 }
@@ -3,23 +3,40 @@
 //! Provides a single entry point for initializing the database, event bus,
 //! pipeline, scanner, scheduler, and plugin registry.
 
+use std::future::Future;
 use std::path::Path;
-use std::time::Duration;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use netsec_db::DbPool;
 use netsec_events::EventBus;
 use netsec_models::alert::NormalizedAlert;
 use netsec_models::device::Device;
 use netsec_models::event::{EventType, NetsecEvent};
 use netsec_models::plugin::TriggerType;
-use netsec_pipeline::{Pipeline, PipelineConfig};
+use netsec_pipeline::dispatch::WebhookTarget;
+use netsec_pipeline::Pipeline;
 use netsec_scanner::active::{ActiveScanner, ScanConfig};
 use netsec_scheduler::Scheduler;
-use sqlx::SqlitePool;
 use thiserror::Error;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use crate::config::{load_config, NetsecConfig};
 use crate::plugin_registry::PluginRegistry;
+use crate::rate_limit::{allow_scan, normalize_target, Decision, ScanHistory};
+
+/// Interval between scheduler ticks.
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Interval between checks for stale alerts when aging is enabled.
+const ALERT_AGING_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// How long [`NetsecEngine::shutdown`] waits for subscribers and tasks.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long [`NetsecEngine::status`] waits for the database check.
+pub const STATUS_DB_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Normalize a SQLite URL from Python-style to sqlx-compatible format.
 ///
@@ -54,26 +71,60 @@ pub enum EngineError {
     Pipeline(#[from] netsec_pipeline::PipelineError),
     #[error("scheduler error: {0}")]
     Scheduler(#[from] netsec_scheduler::SchedulerError),
+    #[error("scan of {target} rate limited; retry in {retry_after_secs}s")]
+    RateLimited {
+        target: String,
+        retry_after_secs: u64,
+    },
     #[error("{0}")]
     Other(String),
 }
 
 pub type EngineResult<T> = Result<T, EngineError>;
 
+/// Component status snapshot for a health endpoint.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EngineStatus {
+    /// Whether `SELECT 1` succeeded within [`STATUS_DB_TIMEOUT`].
+    pub db_ok: bool,
+    /// How long the database check took (capped at the timeout).
+    pub db_latency_ms: u64,
+    /// Why the database check failed.
+    pub db_error: Option<String>,
+    /// Live event bus subscriptions.
+    pub event_subscribers: usize,
+    /// Whether the scheduler tick loop is running.
+    pub scheduler_running: bool,
+    /// Scans running or waiting for a slot.
+    pub active_scans: usize,
+}
+
+impl EngineStatus {
+    /// Whether the engine can serve requests: currently, whether the
+    /// database answered.
+    pub fn is_healthy(&self) -> bool {
+        self.db_ok
+    }
+}
+
 /// Central orchestration engine for the netsec platform.
 ///
 /// Holds references to all subsystems and provides high-level operations
 /// (scan, process alert, schedule job, etc.).
 pub struct NetsecEngine {
     config: NetsecConfig,
-    pool: SqlitePool,
+    pool: DbPool,
     event_bus: EventBus,
     pipeline: Pipeline,
     scanner: ActiveScanner,
     scheduler: Scheduler,
     plugin_registry: PluginRegistry,
     metadata_engine: netsec_metadata::MetadataEngine,
+    scan_history: Mutex<ScanHistory>,
     scheduler_handle: Option<JoinHandle<()>>,
+    /// Background tasks started with [`NetsecEngine::spawn_task`].
+    tasks: Vec<(String, JoinHandle<()>)>,
+    shutdown_token: CancellationToken,
 }
 
 impl NetsecEngine {
@@ -91,24 +142,23 @@ impl NetsecEngine {
 
         let event_bus = EventBus::new();
 
-        let pipeline_config = PipelineConfig {
-            correlation_window_secs: config.alerts.dedup_window_seconds as i64,
-            ..PipelineConfig::default()
-        };
-        let pipeline = Pipeline::with_config(pool.clone(), event_bus.clone(), pipeline_config)?;
-
-        let scanner = ActiveScanner::new(pool.clone(), event_bus.clone());
+        let pipeline = build_pipeline(&config, &pool, &event_bus)?;
 
-        let scheduler = Scheduler::new(
+        let scanner = ActiveScanner::with_max_concurrency(
             pool.clone(),
             event_bus.clone(),
-            Duration::from_secs(60),
-        );
+            config.tools.max_concurrent_scans as usize,
+        )
+        .with_mac_allowlist(config.tools.mac_allowlist.clone());
+
+        let scheduler = Scheduler::new(pool.clone(), event_bus.clone(), scheduler_config(&config));
 
         let plugin_registry = PluginRegistry::new();
 
         let metadata_engine = netsec_metadata::MetadataEngine::with_defaults();
 
+        let scan_cooldown = Duration::from_secs(config.tools.scan_cooldown_secs);
+
         Ok(Self {
             config,
             pool,
@@ -118,14 +168,17 @@ impl NetsecEngine {
             scheduler,
             plugin_registry,
             metadata_engine,
+            scan_history: Mutex::new(ScanHistory::new(scan_cooldown)),
             scheduler_handle: None,
+            tasks: Vec::new(),
+            shutdown_token: CancellationToken::new(),
         })
     }
 
     /// Create an engine with a pre-existing pool (useful for tests with in-memory SQLite).
     pub async fn new_with_pool(
         config_dir: Option<&Path>,
-        pool: SqlitePool,
+        pool: DbPool,
     ) -> EngineResult<Self> {
         let config = load_config(config_dir).map_err(|e| EngineError::Config(e.to_string()))?;
 
@@ -133,24 +186,23 @@ impl NetsecEngine {
 
         let event_bus = EventBus::new();
 
-        let pipeline_config = PipelineConfig {
-            correlation_window_secs: config.alerts.dedup_window_seconds as i64,
-            ..PipelineConfig::default()
-        };
-        let pipeline = Pipeline::with_config(pool.clone(), event_bus.clone(), pipeline_config)?;
-
-        let scanner = ActiveScanner::new(pool.clone(), event_bus.clone());
+        let pipeline = build_pipeline(&config, &pool, &event_bus)?;
 
-        let scheduler = Scheduler::new(
+        let scanner = ActiveScanner::with_max_concurrency(
             pool.clone(),
             event_bus.clone(),
-            Duration::from_secs(60),
-        );
+            config.tools.max_concurrent_scans as usize,
+        )
+        .with_mac_allowlist(config.tools.mac_allowlist.clone());
+
+        let scheduler = Scheduler::new(pool.clone(), event_bus.clone(), scheduler_config(&config));
 
         let plugin_registry = PluginRegistry::new();
 
         let metadata_engine = netsec_metadata::MetadataEngine::with_defaults();
 
+        let scan_cooldown = Duration::from_secs(config.tools.scan_cooldown_secs);
+
         Ok(Self {
             config,
             pool,
@@ -160,17 +212,30 @@ impl NetsecEngine {
             scheduler,
             plugin_registry,
             metadata_engine,
+            scan_history: Mutex::new(ScanHistory::new(scan_cooldown)),
             scheduler_handle: None,
+            tasks: Vec::new(),
+            shutdown_token: CancellationToken::new(),
         })
     }
 
-    /// Start the engine: launches the scheduler tick loop and publishes
-    /// a startup event on the event bus.
+    /// Start the engine: launches the scheduler tick loop and, if
+    /// `alerts.auto_resolve_after_hours` is set, the alert aging task, then
+    /// publishes a startup event on the event bus.
     pub async fn start(&mut self) -> EngineResult<()> {
         if self.config.scheduler.enabled {
             let handle = self.scheduler.start();
             self.scheduler_handle = Some(handle);
         }
+        if let Some(threshold) = self.config.alerts.auto_resolve_after() {
+            let task = age_alerts(
+                self.pool.clone(),
+                threshold,
+                ALERT_AGING_INTERVAL,
+                self.shutdown_token(),
+            );
+            self.spawn_task("alert-aging", task);
+        }
 
         let event = NetsecEvent::new(
             EventType::ScanStarted, // reuse as "engine started" event
@@ -181,26 +246,208 @@ impl NetsecEngine {
         Ok(())
     }
 
-    /// Gracefully shut down the engine: stop scheduler, close pool.
-    pub async fn shutdown(&mut self) -> EngineResult<()> {
+    /// Report component status.
+    ///
+    /// The database check is bounded by [`STATUS_DB_TIMEOUT`], so an
+    /// unresponsive database reports `db_ok: false` instead of hanging the
+    /// call; the other checks are in-memory.
+    pub async fn status(&self) -> EngineStatus {
+        let started = Instant::now();
+        let check = tokio::time::timeout(
+            STATUS_DB_TIMEOUT,
+            sqlx::query("SELECT 1").execute(&self.pool),
+        )
+        .await;
+        let db_latency_ms = started.elapsed().as_millis() as u64;
+        let db_error = match check {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("no response within {STATUS_DB_TIMEOUT:?}")),
+        };
+
+        EngineStatus {
+            db_ok: db_error.is_none(),
+            db_latency_ms,
+            db_error,
+            event_subscribers: self.event_bus.subscriber_count(),
+            scheduler_running: self
+                .scheduler_handle
+                .as_ref()
+                .is_some_and(|handle| !handle.is_finished()),
+            active_scans: self.scanner.running_count(),
+        }
+    }
+
+    /// Run `task` in the background until the engine shuts down.
+    ///
+    /// The task should finish once [`shutdown_token`](Self::shutdown_token)
+    /// is cancelled; [`shutdown`](Self::shutdown) waits for it and aborts it
+    /// if it overruns the timeout.
+    pub fn spawn_task<F>(&mut self, name: &str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.retain(|(_, handle)| !handle.is_finished());
+        self.tasks.push((name.to_string(), tokio::spawn(task)));
+    }
+
+    /// Token cancelled when the engine starts shutting down.
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown_token.clone()
+    }
+
+    /// Gracefully shut down the engine with [`SHUTDOWN_TIMEOUT`].
+    pub fn shutdown(self) -> impl Future<Output = ()> {
+        self.shutdown_with_timeout(SHUTDOWN_TIMEOUT)
+    }
+
+    /// Gracefully shut down the engine:
+    ///
+    /// 1. Signal the scheduler and every task from [`spawn_task`](Self::spawn_task).
+    /// 2. Cancel in-flight scans.
+    /// 3. Publish a `SystemHealth` shutdown event and wait for subscribers to
+    ///    drain the event bus.
+    /// 4. Await the scheduler loop and tasks; any still running when
+    ///    `timeout` (shared by steps 3 to 5) runs out are aborted.
+    /// 5. Wait for the cancelled scans to record their status.
+    /// 6. Close the database pool.
+    pub async fn shutdown_with_timeout(mut self, timeout: Duration) {
+        let deadline = tokio::time::Instant::now() + timeout;
+
         self.scheduler.shutdown();
+        self.shutdown_token.cancel();
+        let cancelled = self.scanner.cancel_all();
+        if cancelled > 0 {
+            tracing::info!("Cancelled {cancelled} running scans");
+        }
+
+        self.publish_subsystem_event("engine", "shutting_down");
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if !self.event_bus.drain(remaining).await {
+            tracing::warn!("Event subscribers did not drain before shutdown");
+        }
 
+        let mut tasks = std::mem::take(&mut self.tasks);
         if let Some(handle) = self.scheduler_handle.take() {
-            let _ = handle.await;
+            tasks.push(("scheduler".to_string(), handle));
+        }
+        for (name, mut handle) in tasks {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!("Task {name} ended abnormally: {e}"),
+                Err(_) => {
+                    tracing::warn!("Task {name} did not stop within {timeout:?}; aborting");
+                    handle.abort();
+                }
+            }
+        }
+
+        if tokio::time::timeout_at(deadline, self.scanner.wait_idle()).await.is_err() {
+            tracing::warn!("Cancelled scans did not finish within {timeout:?}");
         }
 
         self.pool.close().await;
+        tracing::info!("Engine shut down");
+    }
+
+    /// Stop the scheduler tick loop and start a fresh scheduler.
+    ///
+    /// Waits for the old loop to exit (an in-progress tick finishes first),
+    /// then builds a new scheduler on the shared pool and event bus and starts
+    /// it if scheduling is enabled. Run history is not carried over, so
+    /// interval jobs are re-evaluated from scratch. Publishes `SystemHealth`
+    /// events before and after the restart.
+    pub async fn restart_scheduler(&mut self) -> EngineResult<()> {
+        self.publish_subsystem_event("scheduler", "restarting");
 
+        self.scheduler.shutdown();
+        if let Some(handle) = self.scheduler_handle.take() {
+            if let Err(e) = handle.await {
+                tracing::warn!("Scheduler task ended abnormally: {e}");
+            }
+        }
+
+        self.scheduler = Scheduler::new(
+            self.pool.clone(),
+            self.event_bus.clone(),
+            scheduler_config(&self.config),
+        );
+        if self.config.scheduler.enabled {
+            self.scheduler_handle = Some(self.scheduler.start());
+        }
+
+        tracing::info!("Scheduler restarted");
+        self.publish_subsystem_event("scheduler", "restarted");
         Ok(())
     }
 
+    /// Rebuild the alert pipeline from the current configuration.
+    ///
+    /// Taking `&mut self` means no `process_alert` call can be in flight, so
+    /// the old pipeline is simply dropped. Dispatch targets added after
+    /// construction are not carried over. Publishes `SystemHealth` events
+    /// before and after the restart.
+    pub fn restart_pipeline(&mut self) -> EngineResult<()> {
+        self.publish_subsystem_event("pipeline", "restarting");
+        self.pipeline = build_pipeline(&self.config, &self.pool, &self.event_bus)?;
+        tracing::info!("Pipeline restarted");
+        self.publish_subsystem_event("pipeline", "restarted");
+        Ok(())
+    }
+
+    fn publish_subsystem_event(&self, subsystem: &str, action: &str) {
+        let event = NetsecEvent::new(
+            EventType::SystemHealth,
+            serde_json::json!({"subsystem": subsystem, "action": action}),
+        );
+        let _ = self.event_bus.publish(event);
+    }
+
     /// Run a scan after validating the configuration.
+    ///
+    /// Scans of a target already scanned within the configured cooldown
+    /// (`tools.scan_cooldown_secs`) are refused with
+    /// [`EngineError::RateLimited`].
     pub async fn scan(&self, config: &ScanConfig) -> EngineResult<Vec<Device>> {
         config.validate()?;
+        // Dry runs never touch the target, so they don't consume its cooldown
+        if !config.dry_run {
+            self.reserve_scan(&config.target, Instant::now())?;
+        }
         let devices = self.scanner.run_scan(config).await?;
         Ok(devices)
     }
 
+    /// Cancel a scan started by [`scan`](Self::scan) that is still running.
+    /// Returns `false` if no such scan is executing.
+    pub fn cancel_scan(&self, scan_id: &str) -> bool {
+        self.scanner.cancel(scan_id)
+    }
+
+    /// Check the per-target cooldown and, if allowed, record the scan start.
+    ///
+    /// Recording before the scan runs means concurrent scans of the same
+    /// target are refused too.
+    fn reserve_scan(&self, target: &str, now: Instant) -> EngineResult<()> {
+        let mut history = self
+            .scan_history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match allow_scan(target, now, &history) {
+            Decision::Allow => {
+                history.record(target, now);
+                Ok(())
+            }
+            Decision::Deny { retry_after } => {
+                tracing::warn!(target, ?retry_after, "Scan refused by per-target rate limit");
+                Err(EngineError::RateLimited {
+                    target: normalize_target(target),
+                    retry_after_secs: retry_after.as_secs_f64().ceil() as u64,
+                })
+            }
+        }
+    }
+
     /// Push a normalized alert through the pipeline.
     pub async fn process_alert(
         &self,
@@ -210,6 +457,12 @@ impl NetsecEngine {
         Ok(alert)
     }
 
+    /// Ask the scheduler to re-read the jobs table now, e.g. after a job was
+    /// added, edited, or deleted.
+    pub fn reload_scheduler(&self) {
+        self.scheduler.reload();
+    }
+
     /// Insert a scheduled job into the database.
     pub async fn schedule_job(
         &self,
@@ -224,6 +477,7 @@ impl NetsecEngine {
         job.task_params = task_params.to_string();
 
         netsec_db::repo::scheduled_jobs::insert(&self.pool, &job).await?;
+        self.scheduler.reload();
         Ok(job)
     }
 
@@ -235,7 +489,7 @@ impl NetsecEngine {
     }
 
     /// Reference to the SQLite pool.
-    pub fn pool(&self) -> &SqlitePool {
+    pub fn pool(&self) -> &DbPool {
         &self.pool
     }
 
@@ -293,9 +547,64 @@ impl NetsecEngine {
     }
 }
 
+/// Scheduler settings derived from the platform config.
+fn scheduler_config(config: &NetsecConfig) -> netsec_scheduler::SchedulerConfig {
+    netsec_scheduler::SchedulerConfig {
+        tick_interval: SCHEDULER_TICK,
+        jitter_secs: config.scheduler.jitter_secs,
+        offline_after: (config.scheduler.offline_after_secs > 0)
+            .then(|| Duration::from_secs(config.scheduler.offline_after_secs)),
+    }
+}
+
+/// Auto-resolve alerts unseen for `threshold`, every `interval`, until
+/// `shutdown` is cancelled.
+async fn age_alerts(
+    pool: DbPool,
+    threshold: Duration,
+    interval: Duration,
+    shutdown: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        match netsec_db::repo::alerts::auto_resolve_stale(&pool, threshold).await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Auto-resolved {n} stale alerts"),
+            Err(e) => tracing::warn!("Alert aging failed: {e}"),
+        }
+    }
+}
+
+/// Build the alert pipeline for the given config on shared resources.
+fn build_pipeline(
+    config: &NetsecConfig,
+    pool: &DbPool,
+    event_bus: &EventBus,
+) -> EngineResult<Pipeline> {
+    let pipeline_config = config.pipeline_config();
+    let mut pipeline = Pipeline::with_config(pool.clone(), event_bus.clone(), pipeline_config)?;
+
+    let dispatch = &config.alerts.dispatch;
+    if !dispatch.webhook_url.is_empty() {
+        let mut webhook = WebhookTarget::new(dispatch.webhook_url.clone());
+        if !dispatch.webhook_token.is_empty() {
+            webhook = webhook.with_bearer_token(dispatch.webhook_token.clone());
+        }
+        pipeline.add_dispatch_target(Box::new(webhook));
+    }
+
+    Ok(pipeline)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use netsec_models::alert::Alert;
     use std::path::PathBuf;
 
     fn config_dir() -> PathBuf {
@@ -326,7 +635,176 @@ mod tests {
 
         // Verify event bus has a subscriber (the scheduler created one internally)
         // Just ensure no panic on start/shutdown cycle
-        engine.shutdown().await.unwrap();
+        engine.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_status() {
+        let mut engine = test_engine().await;
+        let status = engine.status().await;
+        assert!(status.db_ok, "{:?}", status.db_error);
+        assert!(status.is_healthy());
+        assert!(!status.scheduler_running);
+        assert_eq!(status.active_scans, 0);
+        let baseline = status.event_subscribers;
+
+        engine.start().await.unwrap();
+        let rx1 = engine.event_bus().subscribe();
+        let _rx2 = engine.event_bus().subscribe_filtered(vec![EventType::AlertCreated]);
+        let status = engine.status().await;
+        assert!(status.scheduler_running);
+        assert_eq!(status.event_subscribers, engine.event_bus().subscriber_count());
+        assert!(status.event_subscribers >= baseline + 2);
+
+        drop(rx1);
+        assert_eq!(engine.status().await.event_subscribers, status.event_subscribers - 1);
+
+        // A closed pool degrades the database check instead of failing
+        engine.pool().close().await;
+        let status = engine.status().await;
+        assert!(!status.db_ok);
+        assert!(status.db_error.is_some());
+        assert!(status.scheduler_running);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_tasks() {
+        let mut engine = test_engine().await;
+        engine.start().await.unwrap();
+        let scheduler = engine.scheduler_handle.as_ref().unwrap().abort_handle();
+
+        let token = engine.shutdown_token();
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        engine.spawn_task("cooperative", async move {
+            token.cancelled().await;
+            let _ = done_tx.send(());
+        });
+        let (stuck_tx, stuck_rx) = tokio::sync::oneshot::channel::<()>();
+        engine.spawn_task("stuck", async move {
+            let _keep = stuck_tx;
+            std::future::pending::<()>().await;
+        });
+        let pool = engine.pool().clone();
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            engine.shutdown_with_timeout(Duration::from_millis(500)),
+        )
+        .await
+        .expect("shutdown overran its timeout");
+
+        assert!(scheduler.is_finished());
+        done_rx.await.expect("cooperative task finished");
+        // The stuck task was aborted, dropping its sender
+        assert!(stuck_rx.await.is_err());
+        assert!(pool.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_not_held_by_idle_subscriber() {
+        let mut engine = test_engine().await;
+        engine.start().await.unwrap();
+        // Subscribed but never read, like an unread Python subscription
+        let _idle = engine.event_bus().subscribe();
+
+        tokio::time::timeout(
+            Duration::from_secs(3),
+            engine.shutdown_with_timeout(Duration::from_secs(10)),
+        )
+        .await
+        .expect("an idle subscriber held up shutdown");
+    }
+
+    #[tokio::test]
+    async fn test_age_alerts_resolves_until_shutdown() {
+        let engine = test_engine().await;
+        let pool = engine.pool().clone();
+        let mut stale = Alert::new("Stale".into(), "zeek".into(), "fp-stale".into());
+        stale.last_seen = "2020-01-01T00:00:00+00:00".into();
+        netsec_db::repo::alerts::insert(&pool, &stale).await.unwrap();
+
+        let token = CancellationToken::new();
+        let task = tokio::spawn(age_alerts(
+            pool.clone(),
+            Duration::from_secs(3600),
+            Duration::from_millis(10),
+            token.clone(),
+        ));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let alert = netsec_db::repo::alerts::get_by_id(&pool, &stale.id).await.unwrap();
+                if alert.unwrap().status == "resolved" {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("stale alert was not resolved");
+
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .expect("aging task did not stop")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restart_scheduler_replaces_tick_loop() {
+        let mut engine = test_engine().await;
+        engine.start().await.unwrap();
+        let old = engine.scheduler_handle.as_ref().unwrap().abort_handle();
+
+        let mut rx = engine.event_bus().subscribe();
+        engine.restart_scheduler().await.unwrap();
+        assert!(old.is_finished(), "old tick loop should have exited");
+        assert!(engine.scheduler_handle.is_some());
+
+        let restarting = rx.recv().await.unwrap();
+        assert_eq!(restarting.event_type, EventType::SystemHealth);
+        assert_eq!(restarting.payload["subsystem"], "scheduler");
+        assert_eq!(restarting.payload["action"], "restarting");
+        assert_eq!(rx.recv().await.unwrap().payload["action"], "restarted");
+
+        // The new scheduler picks up and dispatches a due job
+        let job = engine
+            .schedule_job(TriggerType::Interval, r#"{"interval_secs": 0}"#, "discovery_scan", "{}")
+            .await
+            .unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("new scheduler did not dispatch")
+            .unwrap();
+        assert_eq!(event.event_type, EventType::ScanStarted);
+        assert_eq!(event.payload["job_id"], job.id.as_str());
+
+        // An idle subscriber would hold up the event bus drain
+        drop(rx);
+        engine.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_restart_pipeline_keeps_processing() {
+        let mut engine = test_engine().await;
+        let mut rx = engine.event_bus().subscribe();
+
+        engine.restart_pipeline().unwrap();
+        assert_eq!(rx.recv().await.unwrap().payload["action"], "restarting");
+        assert_eq!(rx.recv().await.unwrap().payload["action"], "restarted");
+
+        let normalized = NormalizedAlert {
+            source_tool: "suricata".into(),
+            severity: netsec_models::alert::Severity::High,
+            category: netsec_models::alert::AlertCategory::Intrusion,
+            title: "after restart".into(),
+            description: String::new(),
+            device_ip: Some("10.0.0.9".into()),
+            fingerprint: "restart-test".into(),
+            raw_data: serde_json::json!({}),
+            timestamp: chrono::Utc::now(),
+        };
+        let alert = engine.process_alert(normalized).await.unwrap();
+        assert_eq!(alert.title, "after restart");
     }
 
     #[tokio::test]
@@ -338,7 +816,7 @@ mod tests {
             target: "".to_string(),
             scan_type: netsec_models::scan::ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         let result = engine.scan(&bad_config).await;
         assert!(result.is_err());
@@ -346,6 +824,33 @@ mod tests {
         assert!(err.contains("target must not be empty"));
     }
 
+    #[tokio::test]
+    async fn test_engine_rate_limits_repeat_target() {
+        let engine = test_engine().await;
+        let start = Instant::now();
+
+        engine.reserve_scan("10.0.0.1", start).unwrap();
+        let err = engine
+            .reserve_scan("10.0.0.1/32", start + Duration::from_secs(15))
+            .unwrap_err();
+        match err {
+            EngineError::RateLimited {
+                target,
+                retry_after_secs,
+            } => {
+                assert_eq!(target, "10.0.0.1");
+                assert_eq!(retry_after_secs, 45);
+            }
+            other => panic!("expected RateLimited, got {other}"),
+        }
+
+        // A different target, and the same target after the cooldown, are allowed
+        engine.reserve_scan("10.0.0.2", start).unwrap();
+        engine
+            .reserve_scan("10.0.0.1", start + Duration::from_secs(60))
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_engine_accessors() {
         let mut engine = test_engine().await;
@@ -1,5 +1,6 @@
 //! Facade crate: NetsecEngine wiring all sub-crates together,
-//! plus the unified plugin registry and configuration loading.
+//! plus the unified plugin registry, configuration loading, per-target scan
+//! rate limiting, and the headless `netsec-cli` command handlers.
 
 pub use netsec_models as models;
 pub use netsec_db as db;
@@ -12,11 +13,15 @@ pub use netsec_platform as platform;
 pub use netsec_threat as threat;
 pub use netsec_metadata as metadata;
 
+pub mod cli;
 pub mod engine;
 pub mod config;
 pub mod plugin_registry;
+pub mod rate_limit;
 
 // Re-export key types for convenience.
-pub use engine::{EngineError, EngineResult, NetsecEngine};
-pub use plugin_registry::{Plugin, PluginInfo, PluginKey, PluginRegistry};
-pub use config::{load_config, NetsecConfig};
+pub use engine::{EngineError, EngineResult, EngineStatus, NetsecEngine};
+pub use plugin_registry::{
+    ApiVersion, Capability, Plugin, PluginInfo, PluginKey, PluginRegistry, PLUGIN_API_VERSION,
+};
+pub use config::{load_config, watch_config, ConfigError, NetsecConfig};
@@ -1,7 +1,9 @@
 //! Unified plugin registry supporting all plugin categories.
 //!
 //! Provides a `Plugin` trait for lifecycle management and a `PluginRegistry`
-//! for registering, querying, and controlling plugins at runtime.
+//! for registering, querying, and controlling plugins at runtime. Plugins
+//! declare the plugin API version they were built against and the
+//! capabilities they provide; registration rejects incompatible versions.
 
 use std::collections::HashMap;
 use std::fmt;
@@ -30,6 +32,50 @@ impl fmt::Display for PluginKey {
     }
 }
 
+/// A plugin API version. Compatible versions share the major version; a
+/// plugin may target an older minor version than the host, not a newer one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Whether a plugin built against `self` can run on a `host`.
+    pub fn is_compatible_with(&self, host: ApiVersion) -> bool {
+        self.major == host.major && self.minor <= host.minor
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// The plugin API version this build implements.
+pub const PLUGIN_API_VERSION: ApiVersion = ApiVersion::new(1, 0);
+
+/// Something a plugin can do, independent of its category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    HostDiscovery,
+    PortScan,
+    ServiceDetection,
+    OsDetection,
+    VulnerabilityScan,
+    PacketCapture,
+    IntrusionDetection,
+    MalwareScan,
+    LogIngest,
+    MetadataExtraction,
+    Reporting,
+}
+
 /// Metadata describing a plugin.
 #[derive(Debug, Clone)]
 pub struct PluginInfo {
@@ -38,6 +84,9 @@ pub struct PluginInfo {
     pub category: PluginCategory,
     pub status: PluginStatus,
     pub description: String,
+    /// Plugin API version the plugin was built against.
+    pub api_version: ApiVersion,
+    pub capabilities: Vec<Capability>,
 }
 
 /// Trait that all plugins must implement.
@@ -62,20 +111,40 @@ pub trait Plugin: Send + Sync {
 /// Central registry for all plugins.
 pub struct PluginRegistry {
     plugins: HashMap<PluginKey, Box<dyn Plugin>>,
+    api_version: ApiVersion,
 }
 
 impl PluginRegistry {
-    /// Create an empty registry.
+    /// Create an empty registry for [`PLUGIN_API_VERSION`].
     pub fn new() -> Self {
+        Self::with_api_version(PLUGIN_API_VERSION)
+    }
+
+    /// Create an empty registry hosting plugin API `api_version`.
+    pub fn with_api_version(api_version: ApiVersion) -> Self {
         Self {
             plugins: HashMap::new(),
+            api_version,
         }
     }
 
-    /// Register a plugin. Returns an error if a plugin with the same key already exists.
+    /// The plugin API version this registry hosts.
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    /// Register a plugin. Returns an error if a plugin with the same key already exists
+    /// or the plugin's API version is incompatible with the registry's.
     pub fn register(&mut self, plugin: Box<dyn Plugin>) -> Result<(), String> {
         let info = plugin.info();
         let key = PluginKey::new(info.category.clone(), &info.name);
+        if !info.api_version.is_compatible_with(self.api_version) {
+            return Err(format!(
+                "plugin {key} requires plugin API {}, but this host provides {} \
+                 (compatible: {}.0 to {})",
+                info.api_version, self.api_version, self.api_version.major, self.api_version
+            ));
+        }
         if self.plugins.contains_key(&key) {
             return Err(format!("plugin already registered: {key}"));
         }
@@ -110,6 +179,18 @@ impl PluginRegistry {
             .collect()
     }
 
+    /// List info for all plugins that declare `capability`, sorted by key.
+    pub fn find_by_capability(&self, capability: Capability) -> Vec<PluginInfo> {
+        let mut matches: Vec<(String, PluginInfo)> = self
+            .plugins
+            .iter()
+            .map(|(key, p)| (key.to_string(), p.info()))
+            .filter(|(_, info)| info.capabilities.contains(&capability))
+            .collect();
+        matches.sort_by(|a, b| a.0.cmp(&b.0));
+        matches.into_iter().map(|(_, info)| info).collect()
+    }
+
     /// Run health checks on all plugins and return their statuses.
     pub fn health_check_all(&self) -> Vec<(PluginKey, PluginStatus)> {
         self.plugins
@@ -170,6 +251,8 @@ mod tests {
         #[allow(dead_code)]
         stopped: Arc<AtomicBool>,
         health: PluginStatus,
+        api_version: ApiVersion,
+        capabilities: Vec<Capability>,
     }
 
     impl MockPlugin {
@@ -180,6 +263,8 @@ mod tests {
                 started: Arc::new(AtomicBool::new(false)),
                 stopped: Arc::new(AtomicBool::new(false)),
                 health: PluginStatus::Available,
+                api_version: PLUGIN_API_VERSION,
+                capabilities: Vec::new(),
             }
         }
 
@@ -187,6 +272,16 @@ mod tests {
             self.health = status;
             self
         }
+
+        fn with_api_version(mut self, major: u32, minor: u32) -> Self {
+            self.api_version = ApiVersion::new(major, minor);
+            self
+        }
+
+        fn with_capabilities(mut self, capabilities: &[Capability]) -> Self {
+            self.capabilities = capabilities.to_vec();
+            self
+        }
     }
 
     impl Plugin for MockPlugin {
@@ -201,6 +296,8 @@ mod tests {
                     PluginStatus::Available
                 },
                 description: format!("Mock {} plugin", self.name),
+                api_version: self.api_version,
+                capabilities: self.capabilities.clone(),
             }
         }
 
@@ -231,6 +328,8 @@ mod tests {
                 category: PluginCategory::NetworkScanner,
                 status: PluginStatus::Error,
                 description: "Always fails".to_string(),
+                api_version: PLUGIN_API_VERSION,
+                capabilities: Vec::new(),
             }
         }
 
@@ -455,6 +554,58 @@ mod tests {
         assert_eq!(failures, 1);
     }
 
+    #[test]
+    fn test_register_rejects_incompatible_api_version() {
+        let mut registry = PluginRegistry::with_api_version(ApiVersion::new(2, 3));
+
+        for (major, minor) in [(1, 9), (3, 0), (2, 4)] {
+            let plugin = MockPlugin::new("nmap", PluginCategory::NetworkScanner)
+                .with_api_version(major, minor);
+            let err = registry.register(Box::new(plugin)).unwrap_err();
+            assert!(err.contains(&format!("requires plugin API {major}.{minor}")), "{err}");
+            assert!(err.contains("host provides 2.3"), "{err}");
+        }
+        assert_eq!(registry.count(), 0);
+
+        // Same major, same or older minor
+        for (name, minor) in [("a", 0), ("b", 3)] {
+            let plugin =
+                MockPlugin::new(name, PluginCategory::NetworkScanner).with_api_version(2, minor);
+            registry.register(Box::new(plugin)).unwrap();
+        }
+        assert_eq!(registry.count(), 2);
+    }
+
+    #[test]
+    fn test_find_by_capability() {
+        let mut registry = PluginRegistry::new();
+        let plugins: [(&str, PluginCategory, &[Capability]); 3] = [
+            (
+                "nmap",
+                PluginCategory::NetworkScanner,
+                &[Capability::HostDiscovery, Capability::PortScan, Capability::OsDetection],
+            ),
+            ("masscan", PluginCategory::NetworkScanner, &[Capability::PortScan]),
+            (
+                "suricata",
+                PluginCategory::IdsIps,
+                &[Capability::PacketCapture, Capability::IntrusionDetection],
+            ),
+        ];
+        for (name, category, caps) in plugins {
+            let plugin = MockPlugin::new(name, category).with_capabilities(caps);
+            registry.register(Box::new(plugin)).unwrap();
+        }
+
+        let names = |cap| -> Vec<String> {
+            registry.find_by_capability(cap).into_iter().map(|i| i.name).collect()
+        };
+        assert_eq!(names(Capability::PortScan), ["masscan", "nmap"]);
+        assert_eq!(names(Capability::OsDetection), ["nmap"]);
+        assert_eq!(names(Capability::IntrusionDetection), ["suricata"]);
+        assert!(names(Capability::MalwareScan).is_empty());
+    }
+
     #[test]
     fn test_plugin_key_display() {
         let key = PluginKey::new(PluginCategory::NetworkScanner, "nmap");
@@ -0,0 +1,172 @@
+//! Per-target scan rate limiting.
+//!
+//! Refuses a new scan of a target that was scanned within a cooldown window,
+//! protecting fragile devices and avoiding IDS triggers when many jobs or
+//! concurrent scans hit the same host.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default cooldown between scans of the same target.
+pub const DEFAULT_SCAN_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Outcome of a rate-limit check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// The scan may proceed.
+    Allow,
+    /// The target was scanned too recently; retry after the given delay.
+    Deny { retry_after: Duration },
+}
+
+/// Recent scan start times per normalized target.
+#[derive(Debug, Clone)]
+pub struct ScanHistory {
+    cooldown: Duration,
+    last_scan: HashMap<String, Instant>,
+}
+
+impl ScanHistory {
+    /// Create an empty history. A zero cooldown disables rate limiting.
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last_scan: HashMap::new(),
+        }
+    }
+
+    /// The configured cooldown.
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// When the target was last scanned, if at all.
+    pub fn last_scan(&self, target: &str) -> Option<Instant> {
+        self.last_scan.get(&normalize_target(target)).copied()
+    }
+
+    /// Record a scan of `target` starting at `now`, dropping expired entries.
+    pub fn record(&mut self, target: &str, now: Instant) {
+        let cooldown = self.cooldown;
+        self.last_scan
+            .retain(|_, at| now.saturating_duration_since(*at) < cooldown);
+        self.last_scan.insert(normalize_target(target), now);
+    }
+
+    /// Number of targets still inside their cooldown window.
+    pub fn len(&self) -> usize {
+        self.last_scan.len()
+    }
+
+    /// Whether no targets are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.last_scan.is_empty()
+    }
+}
+
+/// Normalize a scan target so equivalent spellings share a cooldown.
+///
+/// Lowercases, trims whitespace, and drops single-host prefixes
+/// (`/32` for IPv4, `/128` for IPv6).
+pub fn normalize_target(target: &str) -> String {
+    let target = target.trim().to_ascii_lowercase();
+    let single_host = if target.contains(':') { "/128" } else { "/32" };
+    match target.strip_suffix(single_host) {
+        Some(host) => host.to_string(),
+        None => target,
+    }
+}
+
+/// Decide whether `target` may be scanned at `now` given recent history.
+pub fn allow_scan(target: &str, now: Instant, history: &ScanHistory) -> Decision {
+    let Some(last) = history.last_scan(target) else {
+        return Decision::Allow;
+    };
+    let elapsed = now.saturating_duration_since(last);
+    if elapsed >= history.cooldown {
+        Decision::Allow
+    } else {
+        Decision::Deny {
+            retry_after: history.cooldown - elapsed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_scan_allowed() {
+        let history = ScanHistory::new(Duration::from_secs(60));
+        assert_eq!(allow_scan("10.0.0.1", Instant::now(), &history), Decision::Allow);
+    }
+
+    #[test]
+    fn test_within_cooldown_denied() {
+        let start = Instant::now();
+        let mut history = ScanHistory::new(Duration::from_secs(60));
+        history.record("10.0.0.1", start);
+
+        let decision = allow_scan("10.0.0.1", start + Duration::from_secs(20), &history);
+        assert_eq!(
+            decision,
+            Decision::Deny {
+                retry_after: Duration::from_secs(40)
+            }
+        );
+        // Other targets are unaffected
+        assert_eq!(
+            allow_scan("10.0.0.2", start + Duration::from_secs(20), &history),
+            Decision::Allow
+        );
+    }
+
+    #[test]
+    fn test_expired_cooldown_allowed() {
+        let start = Instant::now();
+        let mut history = ScanHistory::new(Duration::from_secs(60));
+        history.record("10.0.0.1", start);
+
+        assert_eq!(
+            allow_scan("10.0.0.1", start + Duration::from_secs(60), &history),
+            Decision::Allow
+        );
+        assert_eq!(
+            allow_scan("10.0.0.1", start + Duration::from_secs(300), &history),
+            Decision::Allow
+        );
+    }
+
+    #[test]
+    fn test_equivalent_targets_share_cooldown() {
+        let start = Instant::now();
+        let mut history = ScanHistory::new(Duration::from_secs(60));
+        history.record("10.0.0.1/32", start);
+
+        assert!(matches!(
+            allow_scan(" 10.0.0.1 ", start, &history),
+            Decision::Deny { .. }
+        ));
+        assert_eq!(normalize_target("FE80::1/128"), "fe80::1");
+        assert_eq!(normalize_target("10.0.0.0/24"), "10.0.0.0/24");
+    }
+
+    #[test]
+    fn test_zero_cooldown_disables_limit() {
+        let start = Instant::now();
+        let mut history = ScanHistory::new(Duration::ZERO);
+        history.record("10.0.0.1", start);
+        assert_eq!(allow_scan("10.0.0.1", start, &history), Decision::Allow);
+    }
+
+    #[test]
+    fn test_record_prunes_expired_entries() {
+        let start = Instant::now();
+        let mut history = ScanHistory::new(Duration::from_secs(60));
+        history.record("10.0.0.1", start);
+        history.record("10.0.0.2", start + Duration::from_secs(120));
+        assert_eq!(history.len(), 1);
+        assert!(history.last_scan("10.0.0.1").is_none());
+    }
+}
@@ -1,4 +1,5 @@
-//! SQLite database layer via sqlx with async support.
+//! Database layer via sqlx with async support: SQLite by default, PostgreSQL
+//! with the `postgres` feature.
 //!
 //! Provides:
 //! - Connection pool initialization
@@ -9,5 +10,5 @@ pub mod pool;
 pub mod migrate;
 pub mod repo;
 
-pub use pool::create_pool;
+pub use pool::{create_pool, Backend, DbPool};
 pub use migrate::run_migrations;
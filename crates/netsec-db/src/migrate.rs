@@ -1,36 +1,77 @@
 //! Schema migration from SQL files.
 
-use sqlx::SqlitePool;
+use crate::pool::{Backend, DbPool};
 
-/// SQL statements for all tables and schema migrations, in order.
-const MIGRATIONS: &[(&str, &str)] = &[
-    ("001_create_devices", include_str!("../../../migrations/sql/001_create_devices.sql")),
-    ("002_create_ports", include_str!("../../../migrations/sql/002_create_ports.sql")),
-    ("003_create_alerts", include_str!("../../../migrations/sql/003_create_alerts.sql")),
-    ("004_create_scans", include_str!("../../../migrations/sql/004_create_scans.sql")),
-    ("005_create_vulnerabilities", include_str!("../../../migrations/sql/005_create_vulnerabilities.sql")),
-    ("006_create_traffic_flows", include_str!("../../../migrations/sql/006_create_traffic_flows.sql")),
-    ("007_create_device_events", include_str!("../../../migrations/sql/007_create_device_events.sql")),
-    ("008_create_observations", include_str!("../../../migrations/sql/008_create_observations.sql")),
-    ("009_create_scheduled_jobs", include_str!("../../../migrations/sql/009_create_scheduled_jobs.sql")),
-    ("010_add_alert_notes", include_str!("../../../migrations/sql/010_add_alert_notes.sql")),
-    ("011_add_device_fields", include_str!("../../../migrations/sql/011_add_device_fields.sql")),
-    ("012_add_alert_fields", include_str!("../../../migrations/sql/012_add_alert_fields.sql")),
-    ("013_add_vuln_fields", include_str!("../../../migrations/sql/013_add_vuln_fields.sql")),
-    ("014_create_sentinel_snapshots", include_str!("../../../migrations/sql/014_create_sentinel_snapshots.sql")),
-    ("015_create_sentinel_file_hashes", include_str!("../../../migrations/sql/015_create_sentinel_file_hashes.sql")),
-    ("016_create_sentinel_auth_events", include_str!("../../../migrations/sql/016_create_sentinel_auth_events.sql")),
-    ("017_create_sentinel_persistence", include_str!("../../../migrations/sql/017_create_sentinel_persistence.sql")),
-    ("018_create_sentinel_baselines", include_str!("../../../migrations/sql/018_create_sentinel_baselines.sql")),
-    ("019_create_sentinel_osint", include_str!("../../../migrations/sql/019_create_sentinel_osint.sql")),
-];
+/// Expand migration names into `(name, sql)` pairs read from a dialect
+/// directory under `migrations/sql/`.
+macro_rules! migrations {
+    ($dialect:literal: $($name:literal),* $(,)?) => {
+        &[$((
+            $name,
+            include_str!(concat!("../../../migrations/sql/", $dialect, "/", $name, ".sql")),
+        )),*]
+    };
+}
+
+/// Every migration name, in order; each dialect directory has one file per name.
+macro_rules! all_migrations {
+    ($dialect:literal) => {
+        migrations!($dialect:
+            "001_create_devices",
+            "002_create_ports",
+            "003_create_alerts",
+            "004_create_scans",
+            "005_create_vulnerabilities",
+            "006_create_traffic_flows",
+            "007_create_device_events",
+            "008_create_observations",
+            "009_create_scheduled_jobs",
+            "010_add_alert_notes",
+            "011_add_device_fields",
+            "012_add_alert_fields",
+            "013_add_vuln_fields",
+            "014_create_sentinel_snapshots",
+            "015_create_sentinel_file_hashes",
+            "016_create_sentinel_auth_events",
+            "017_create_sentinel_persistence",
+            "018_create_sentinel_baselines",
+            "019_create_sentinel_osint",
+            "020_add_port_http_fields",
+            "021_add_job_last_run",
+            "022_add_job_timezone",
+            "023_add_traffic_country",
+            "024_add_device_discovery_method",
+            "025_create_alert_notes",
+        )
+    };
+}
+
+/// SQLite schema migrations, in order.
+const SQLITE_MIGRATIONS: &[(&str, &str)] = all_migrations!("sqlite");
+
+/// PostgreSQL schema migrations, in order.
+#[cfg(feature = "postgres")]
+const POSTGRES_MIGRATIONS: &[(&str, &str)] = all_migrations!("postgres");
+
+fn migrations_for(backend: Backend) -> &'static [(&'static str, &'static str)] {
+    match backend {
+        Backend::Sqlite => SQLITE_MIGRATIONS,
+        #[cfg(feature = "postgres")]
+        Backend::Postgres => POSTGRES_MIGRATIONS,
+        // `create_pool` can't connect to Postgres without the feature
+        #[cfg(not(feature = "postgres"))]
+        Backend::Postgres => &[],
+    }
+}
 
-/// Run all migrations against the database.
+/// Run all migrations against the database, using the SQL dialect of the
+/// pool's backend.
 ///
 /// Uses `CREATE TABLE IF NOT EXISTS` for table creation and gracefully handles
 /// `ALTER TABLE ADD COLUMN` for columns that already exist (idempotent).
-pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    for (name, sql) in MIGRATIONS {
+pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
+    let migrations = migrations_for(Backend::of(pool));
+    for (name, sql) in migrations {
         tracing::debug!("Running migration: {name}");
         // Split multi-statement migrations and execute each statement separately
         // so we can handle "duplicate column" errors from ALTER TABLE.
@@ -50,6 +91,6 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             }
         }
     }
-    tracing::info!("All {} migrations applied", MIGRATIONS.len());
+    tracing::info!("All {} migrations applied", migrations.len());
     Ok(())
 }
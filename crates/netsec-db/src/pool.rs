@@ -1,25 +1,155 @@
 //! Database connection pool management.
+//!
+//! Pools are `sqlx::Any` pools so the same repository code runs against
+//! SQLite (the default) or PostgreSQL (with the `postgres` feature); the
+//! backend is chosen from the connection URL scheme.
 
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::SqlitePool;
-use std::str::FromStr;
+use sqlx::any::AnyPoolOptions;
+use sqlx::{AnyConnection, AnyPool, Connection, Executor};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-/// Create a SQLite connection pool.
+/// A connection pool for any supported backend.
+pub type DbPool = AnyPool;
+
+/// Distinguishes each in-memory test database so pools don't share state.
+static TEST_DB_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// A supported database backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Sqlite,
+    Postgres,
+}
+
+impl Backend {
+    /// Backend for a connection URL, by scheme (`sqlite:`, `postgres://`, `postgresql://`).
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.split(':').next()?;
+        match scheme {
+            "sqlite" => Some(Self::Sqlite),
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            _ => None,
+        }
+    }
+
+    /// Backend a connected pool is talking to.
+    pub fn of(pool: &DbPool) -> Self {
+        match pool.connect_options().database_url.scheme() {
+            "postgres" | "postgresql" => Self::Postgres,
+            _ => Self::Sqlite,
+        }
+    }
+}
+
+/// Create a connection pool for `url`.
 ///
-/// `url` should be a SQLite path like `sqlite:netsec.db` or `:memory:` for testing.
-pub async fn create_pool(url: &str) -> Result<SqlitePool, sqlx::Error> {
-    let opts = SqliteConnectOptions::from_str(url)?
-        .create_if_missing(true)
-        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-        .foreign_keys(true);
-
-    SqlitePoolOptions::new()
-        .max_connections(5)
-        .connect_with(opts)
-        .await
+/// `url` is either a SQLite path like `sqlite:netsec.db` / `sqlite::memory:`,
+/// or a `postgres://` URL when built with the `postgres` feature. SQLite
+/// databases are created if missing and use WAL journaling with foreign keys on.
+pub async fn create_pool(url: &str) -> Result<DbPool, sqlx::Error> {
+    sqlx::any::install_default_drivers();
+
+    let backend = Backend::from_url(url).ok_or_else(|| {
+        sqlx::Error::Configuration(format!("unsupported database URL: {url}").into())
+    })?;
+
+    match backend {
+        Backend::Sqlite => {
+            let url = sqlite_url(url);
+            let file_backed = !url.contains("mode=memory");
+            AnyPoolOptions::new()
+                .max_connections(5)
+                .after_connect(move |conn, _meta| {
+                    Box::pin(async move { configure_sqlite(conn, file_backed).await })
+                })
+                .connect(&url)
+                .await
+        }
+        #[cfg(feature = "postgres")]
+        Backend::Postgres => AnyPoolOptions::new().max_connections(5).connect(url).await,
+        #[cfg(not(feature = "postgres"))]
+        Backend::Postgres => Err(sqlx::Error::Configuration(
+            "PostgreSQL support requires netsec-db's `postgres` feature".into(),
+        )),
+    }
 }
 
 /// Create an in-memory pool for testing.
-pub async fn create_test_pool() -> Result<SqlitePool, sqlx::Error> {
+pub async fn create_test_pool() -> Result<DbPool, sqlx::Error> {
     create_pool("sqlite::memory:").await
 }
+
+/// Rewrite a SQLite URL into a form that survives the `Any` driver, which
+/// re-parses the URL for every new connection: `:memory:` becomes a named
+/// shared-cache database (so all pool connections see the same data) and
+/// file databases are opened with `mode=rwc` so they're created if missing.
+fn sqlite_url(url: &str) -> String {
+    let path = url.trim_start_matches("sqlite:").trim_start_matches("//");
+    if path == ":memory:" {
+        let seq = TEST_DB_SEQ.fetch_add(1, Ordering::Relaxed);
+        return format!("sqlite:file:netsec-memory-{}-{seq}?mode=memory", std::process::id());
+    }
+    if path.contains("mode=") {
+        url.to_string()
+    } else if path.contains('?') {
+        format!("{url}&mode=rwc")
+    } else {
+        format!("{url}?mode=rwc")
+    }
+}
+
+async fn configure_sqlite(conn: &mut AnyConnection, file_backed: bool) -> Result<(), sqlx::Error> {
+    conn.execute("PRAGMA foreign_keys = ON").await?;
+    if file_backed {
+        conn.execute("PRAGMA journal_mode = WAL").await?;
+    }
+    conn.ping().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_from_url() {
+        assert_eq!(Backend::from_url("sqlite:netsec.db"), Some(Backend::Sqlite));
+        assert_eq!(Backend::from_url("sqlite::memory:"), Some(Backend::Sqlite));
+        assert_eq!(
+            Backend::from_url("postgres://netsec@localhost/netsec"),
+            Some(Backend::Postgres)
+        );
+        assert_eq!(Backend::from_url("postgresql://localhost/db"), Some(Backend::Postgres));
+        assert_eq!(Backend::from_url("mysql://localhost/db"), None);
+        assert_eq!(Backend::from_url("netsec.db"), None);
+    }
+
+    #[test]
+    fn test_sqlite_url_rewrite() {
+        assert_eq!(sqlite_url("sqlite:netsec.db"), "sqlite:netsec.db?mode=rwc");
+        assert_eq!(sqlite_url("sqlite:a.db?cache=shared"), "sqlite:a.db?cache=shared&mode=rwc");
+        assert_eq!(sqlite_url("sqlite:a.db?mode=ro"), "sqlite:a.db?mode=ro");
+        let a = sqlite_url("sqlite::memory:");
+        let b = sqlite_url("sqlite::memory:");
+        assert!(a.ends_with("?mode=memory"));
+        assert_ne!(a, b);
+    }
+
+    #[tokio::test]
+    async fn test_memory_pool_shares_one_database() {
+        let pool = create_test_pool().await.unwrap();
+        assert_eq!(Backend::of(&pool), Backend::Sqlite);
+        sqlx::raw_sql("CREATE TABLE t (x INTEGER)").execute(&pool).await.unwrap();
+
+        // A second connection must see the table created on the first
+        let mut a = pool.acquire().await.unwrap();
+        let mut b = pool.acquire().await.unwrap();
+        sqlx::query("INSERT INTO t (x) VALUES ($1)").bind(1_i64).execute(&mut *a).await.unwrap();
+        let row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM t").fetch_one(&mut *b).await.unwrap();
+        assert_eq!(row.0, 1);
+
+        // ...but a separate test pool is a separate database
+        let other = create_test_pool().await.unwrap();
+        assert!(sqlx::query("SELECT * FROM t").fetch_all(&other).await.is_err());
+    }
+}
@@ -9,3 +9,63 @@ pub mod traffic;
 pub mod device_events;
 pub mod observations;
 pub mod scheduled_jobs;
+
+use serde::Serialize;
+
+/// One page of a cursor-paginated listing.
+///
+/// Pass `next_cursor` back as the `after` argument of the same `list_paged`
+/// function to fetch the following page; it is `None` on the last page.
+/// Unlike `LIMIT`/`OFFSET`, rows inserted while paging never shift later pages.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from up to `limit + 1` rows: the extra row only signals
+    /// that another page exists and is dropped.
+    pub(crate) fn from_rows(mut rows: Vec<T>, limit: i64, id: impl Fn(&T) -> &str) -> Self {
+        let limit = page_limit(limit) as usize;
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| id(row).to_string())
+        } else {
+            None
+        };
+        Self { items: rows, next_cursor }
+    }
+}
+
+/// Page size clamped to at least one row.
+pub(crate) fn page_limit(limit: i64) -> i64 {
+    limit.max(1)
+}
+
+/// Bind parameters per statement for multi-row inserts; SQLite builds before
+/// 3.32 cap a statement at 999.
+pub(crate) const MAX_BIND_PARAMS: usize = 999;
+
+/// Numbered placeholders `$1, $2, ..., $n`.
+pub(crate) fn numbered_params(n: usize) -> String {
+    params_from(1, n)
+}
+
+/// Numbered `VALUES` tuples for `rows` rows of `cols` columns:
+/// `($1, $2), ($3, $4)`.
+pub(crate) fn values_placeholders(rows: usize, cols: usize) -> String {
+    (0..rows)
+        .map(|row| format!("({})", params_from(row * cols + 1, cols)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Numbered placeholders `$first, ..., $(first + n - 1)`, for lists bound after
+/// other parameters.
+pub(crate) fn params_from(first: usize, n: usize) -> String {
+    (first..first + n)
+        .map(|i| format!("${i}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
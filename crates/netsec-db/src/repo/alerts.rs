@@ -1,12 +1,61 @@
 //! Alert repository.
 
-use netsec_models::alert::Alert;
-use sqlx::SqlitePool;
+use std::time::Duration;
 
-pub async fn insert(pool: &SqlitePool, alert: &Alert) -> Result<(), sqlx::Error> {
+use chrono::Utc;
+use netsec_models::alert::{Alert, AlertNote, AlertStatus, Severity};
+use netsec_models::time::{now_rfc3339, to_rfc3339};
+use sqlx::any::{AnyArguments, AnyExecutor};
+use sqlx::query::Query;
+use sqlx::Any;
+use sqlx::AnyConnection;
+
+use crate::pool::DbPool;
+use crate::repo::{numbered_params, page_limit, params_from, Page};
+
+/// Criteria for [`query`]. Unset fields don't filter.
+#[derive(Debug, Clone, Default)]
+pub struct AlertFilter {
+    pub severity: Option<Severity>,
+    /// Only alerts at or above this severity.
+    pub min_severity: Option<Severity>,
+    pub status: Option<AlertStatus>,
+    pub device_ip: Option<String>,
+    /// Inclusive lower bound on `created_at` (RFC 3339).
+    pub since: Option<String>,
+    /// Exclusive upper bound on `created_at` (RFC 3339).
+    pub until: Option<String>,
+    /// Maximum number of alerts to return; all matches when `None`.
+    pub limit: Option<i64>,
+}
+
+/// Author recorded on notes the system adds on its own.
+pub const SYSTEM_AUTHOR: &str = "system";
+
+/// Note added to alerts closed by [`auto_resolve_stale`].
+pub const AUTO_RESOLVE_NOTE: &str = "auto-resolved (stale)";
+
+/// Values per `IN (...)` query, well under SQLite's parameter limit.
+const IN_CHUNK: usize = 500;
+
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, alert: &Alert) -> Result<(), sqlx::Error> {
+    insert_query(alert).execute(pool).await?;
+    Ok(())
+}
+
+/// Insert all alerts in one transaction; either every row is written or none.
+pub async fn insert_many(pool: &DbPool, alerts: &[Alert]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for alert in alerts {
+        insert_query(alert).execute(&mut *tx).await?;
+    }
+    tx.commit().await
+}
+
+fn insert_query(alert: &Alert) -> Query<'_, Any, AnyArguments<'_>> {
     sqlx::query(
         "INSERT INTO alerts (id, severity, status, source_tool, category, title, description, device_ip, fingerprint, correlation_id, count, created_at, updated_at, notes, source_event_id, device_id, raw_data, first_seen, last_seen)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)"
     )
     .bind(&alert.id)
     .bind(&alert.severity)
@@ -27,35 +76,51 @@ pub async fn insert(pool: &SqlitePool, alert: &Alert) -> Result<(), sqlx::Error>
     .bind(&alert.raw_data)
     .bind(&alert.first_seen)
     .bind(&alert.last_seen)
-    .execute(pool)
-    .await?;
-    Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Alert>, sqlx::Error> {
-    sqlx::query_as::<_, Alert>("SELECT * FROM alerts WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<Alert>, sqlx::Error> {
+    sqlx::query_as::<_, Alert>("SELECT * FROM alerts WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn get_by_fingerprint(pool: &SqlitePool, fingerprint: &str) -> Result<Option<Alert>, sqlx::Error> {
-    sqlx::query_as::<_, Alert>("SELECT * FROM alerts WHERE fingerprint = ? ORDER BY created_at DESC LIMIT 1")
+pub async fn get_by_fingerprint<'e>(pool: impl AnyExecutor<'e>, fingerprint: &str) -> Result<Option<Alert>, sqlx::Error> {
+    sqlx::query_as::<_, Alert>("SELECT * FROM alerts WHERE fingerprint = $1 ORDER BY created_at DESC LIMIT 1")
         .bind(fingerprint)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<Alert>, sqlx::Error> {
-    sqlx::query_as::<_, Alert>("SELECT * FROM alerts ORDER BY created_at DESC LIMIT ? OFFSET ?")
+/// Alerts having any of `fingerprints`, newest first.
+pub async fn list_by_fingerprints(
+    pool: &DbPool,
+    fingerprints: &[String],
+) -> Result<Vec<Alert>, sqlx::Error> {
+    let mut found = Vec::new();
+    for chunk in fingerprints.chunks(IN_CHUNK) {
+        let placeholders = numbered_params(chunk.len());
+        let sql = format!("SELECT * FROM alerts WHERE fingerprint IN ({placeholders})");
+        let mut query = sqlx::query_as::<_, Alert>(&sql);
+        for fingerprint in chunk {
+            query = query.bind(fingerprint);
+        }
+        found.extend(query.fetch_all(pool).await?);
+    }
+    found.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(found)
+}
+
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<Alert>, sqlx::Error> {
+    sqlx::query_as::<_, Alert>("SELECT * FROM alerts ORDER BY created_at DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn update_status(pool: &SqlitePool, id: &str, status: &str, updated_at: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("UPDATE alerts SET status=?, updated_at=? WHERE id=?")
+pub async fn update_status<'e>(pool: impl AnyExecutor<'e>, id: &str, status: &str, updated_at: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE alerts SET status=$1, updated_at=$2 WHERE id=$3")
         .bind(status)
         .bind(updated_at)
         .bind(id)
@@ -64,8 +129,162 @@ pub async fn update_status(pool: &SqlitePool, id: &str, status: &str, updated_at
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn increment_count(pool: &SqlitePool, id: &str, updated_at: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("UPDATE alerts SET count = count + 1, updated_at=? WHERE id=?")
+/// Write the analyst-editable fields of `alert` (severity, status, category,
+/// title, description, notes and `updated_at`). Returns `false` if no alert
+/// has its id.
+pub async fn update<'e>(pool: impl AnyExecutor<'e>, alert: &Alert) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE alerts SET severity=$1, status=$2, category=$3, title=$4, description=$5,
+             notes=$6, updated_at=$7
+         WHERE id=$8",
+    )
+    .bind(&alert.severity)
+    .bind(&alert.status)
+    .bind(&alert.category)
+    .bind(&alert.title)
+    .bind(&alert.description)
+    .bind(&alert.notes)
+    .bind(&alert.updated_at)
+    .bind(&alert.id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Append a note by `author` to the alert's history and return the alert with
+/// its full [`note_history`](Alert::note_history), or `None` if no alert has
+/// the id.
+pub async fn add_note(
+    pool: &DbPool,
+    alert_id: &str,
+    author: &str,
+    text: &str,
+) -> Result<Option<Alert>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    if get_by_id(&mut *tx, alert_id).await?.is_none() {
+        return Ok(None);
+    }
+    append_note(&mut tx, alert_id, author, text).await?;
+    sqlx::query("UPDATE alerts SET updated_at=$1 WHERE id=$2")
+        .bind(now_rfc3339())
+        .bind(alert_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    get_with_notes(pool, alert_id).await
+}
+
+/// Insert a note after the alert's last one. Run it in a transaction so
+/// concurrent notes can't take the same `seq`.
+async fn append_note(
+    conn: &mut AnyConnection,
+    alert_id: &str,
+    author: &str,
+    text: &str,
+) -> Result<(), sqlx::Error> {
+    let (last,): (i64,) =
+        sqlx::query_as("SELECT COALESCE(MAX(seq), 0) FROM alert_notes WHERE alert_id = $1")
+            .bind(alert_id)
+            .fetch_one(&mut *conn)
+            .await?;
+    let note = AlertNote::new(alert_id.to_string(), last + 1, author.to_string(), text.to_string());
+    sqlx::query(
+        "INSERT INTO alert_notes (id, alert_id, seq, author, text, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&note.id)
+    .bind(&note.alert_id)
+    .bind(note.seq)
+    .bind(&note.author)
+    .bind(&note.text)
+    .bind(&note.created_at)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Resolve open (`new` or `acknowledged`) alerts whose `last_seen` is more
+/// than `older_than` ago, noting [`AUTO_RESOLVE_NOTE`] on each. Alerts with no
+/// `last_seen` age from `created_at`. Returns the number resolved.
+pub async fn auto_resolve_stale(pool: &DbPool, older_than: Duration) -> Result<u64, sqlx::Error> {
+    let cutoff = chrono::Duration::from_std(older_than)
+        .ok()
+        .and_then(|age| Utc::now().checked_sub_signed(age));
+    let Some(cutoff) = cutoff else {
+        // Older than any representable time: nothing is that stale
+        return Ok(0);
+    };
+
+    let mut tx = pool.begin().await?;
+    let stale: Vec<(String,)> = sqlx::query_as(
+        "SELECT id FROM alerts
+         WHERE status IN ($1, $2) AND COALESCE(last_seen, created_at) < $3",
+    )
+    .bind(AlertStatus::New.as_str())
+    .bind(AlertStatus::Acknowledged.as_str())
+    .bind(to_rfc3339(&cutoff))
+    .fetch_all(&mut *tx)
+    .await?;
+    let now = now_rfc3339();
+    for (id,) in &stale {
+        sqlx::query("UPDATE alerts SET status=$1, updated_at=$2 WHERE id=$3")
+            .bind(AlertStatus::Resolved.as_str())
+            .bind(&now)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        append_note(&mut tx, id, SYSTEM_AUTHOR, AUTO_RESOLVE_NOTE).await?;
+    }
+    tx.commit().await?;
+    Ok(stale.len() as u64)
+}
+
+/// Notes on an alert, oldest first.
+pub async fn list_notes<'e>(
+    pool: impl AnyExecutor<'e>,
+    alert_id: &str,
+) -> Result<Vec<AlertNote>, sqlx::Error> {
+    sqlx::query_as::<_, AlertNote>("SELECT * FROM alert_notes WHERE alert_id = $1 ORDER BY seq")
+        .bind(alert_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Like [`get_by_id`], with [`note_history`](Alert::note_history) loaded.
+pub async fn get_with_notes(pool: &DbPool, id: &str) -> Result<Option<Alert>, sqlx::Error> {
+    let Some(mut alert) = get_by_id(pool, id).await? else {
+        return Ok(None);
+    };
+    alert.note_history = list_notes(pool, id).await?;
+    Ok(Some(alert))
+}
+
+/// Set the status of every alert in `ids` in one transaction. Returns the
+/// number of rows changed; unknown ids are skipped.
+pub async fn bulk_update_status(
+    pool: &DbPool,
+    ids: &[String],
+    status: &str,
+) -> Result<u64, sqlx::Error> {
+    let now = now_rfc3339();
+    let mut changed = 0;
+    let mut tx = pool.begin().await?;
+    for chunk in ids.chunks(IN_CHUNK) {
+        let placeholders = params_from(3, chunk.len());
+        let sql =
+            format!("UPDATE alerts SET status=$1, updated_at=$2 WHERE id IN ({placeholders})");
+        let mut query = sqlx::query(&sql).bind(status).bind(&now);
+        for id in chunk {
+            query = query.bind(id);
+        }
+        changed += query.execute(&mut *tx).await?.rows_affected();
+    }
+    tx.commit().await?;
+    Ok(changed)
+}
+
+pub async fn increment_count<'e>(pool: impl AnyExecutor<'e>, id: &str, updated_at: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE alerts SET count = count + 1, updated_at=$1 WHERE id=$2")
         .bind(updated_at)
         .bind(id)
         .execute(pool)
@@ -73,21 +292,21 @@ pub async fn increment_count(pool: &SqlitePool, id: &str, updated_at: &str) -> R
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM alerts WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM alerts WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn list_by_device_ip_since(
-    pool: &SqlitePool,
+pub async fn list_by_device_ip_since<'e>(
+    pool: impl AnyExecutor<'e>,
     device_ip: &str,
     since: &str,
 ) -> Result<Vec<Alert>, sqlx::Error> {
     sqlx::query_as::<_, Alert>(
-        "SELECT * FROM alerts WHERE device_ip = ? AND created_at >= ? ORDER BY created_at DESC",
+        "SELECT * FROM alerts WHERE device_ip = $1 AND created_at >= $2 ORDER BY created_at DESC",
     )
     .bind(device_ip)
     .bind(since)
@@ -95,9 +314,94 @@ pub async fn list_by_device_ip_since(
     .await
 }
 
-pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+pub async fn list_since<'e>(pool: impl AnyExecutor<'e>, since: &str) -> Result<Vec<Alert>, sqlx::Error> {
+    sqlx::query_as::<_, Alert>(
+        "SELECT * FROM alerts WHERE created_at >= $1 ORDER BY created_at DESC",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count<'e>(pool: impl AnyExecutor<'e>) -> Result<i64, sqlx::Error> {
     let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM alerts")
         .fetch_one(pool)
         .await?;
     Ok(row.0)
 }
+
+/// Alerts, newest first, one page at a time.
+///
+/// `after` is the `next_cursor` of the previous page (`None` for the first).
+/// Rows are ordered by `(created_at, id)`, so pages stay stable while new rows are
+/// inserted. A cursor whose row has since been deleted yields an empty page.
+pub async fn list_paged<'e>(
+    pool: impl AnyExecutor<'e>,
+    after: Option<&str>,
+    limit: i64,
+) -> Result<Page<Alert>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, Alert>(
+        "SELECT * FROM alerts
+         WHERE $1 IS NULL
+            OR (created_at, id) < (SELECT created_at, id FROM alerts WHERE id = $1)
+         ORDER BY created_at DESC, id DESC
+         LIMIT $2",
+    )
+    .bind(after)
+    .bind(page_limit(limit) + 1)
+    .fetch_all(pool)
+    .await?;
+    Ok(Page::from_rows(rows, limit, |alert| &alert.id))
+}
+
+/// Alerts matching every criterion set in `filter`, newest first.
+pub async fn query<'e>(
+    pool: impl AnyExecutor<'e>,
+    filter: &AlertFilter,
+) -> Result<Vec<Alert>, sqlx::Error> {
+    let mut conditions = Vec::new();
+    let mut params: Vec<&str> = Vec::new();
+    let criteria = [
+        ("severity = ", filter.severity.as_ref().map(Severity::as_str)),
+        ("status = ", filter.status.as_ref().map(AlertStatus::as_str)),
+        ("device_ip = ", filter.device_ip.as_deref()),
+        ("created_at >= ", filter.since.as_deref()),
+        ("created_at < ", filter.until.as_deref()),
+    ];
+    for (condition, value) in criteria {
+        if let Some(value) = value {
+            params.push(value);
+            conditions.push(format!("{condition}${}", params.len()));
+        }
+    }
+    if let Some(min) = filter.min_severity {
+        let start = params.len() + 1;
+        params.extend(
+            [Severity::Info, Severity::Low, Severity::Medium, Severity::High, Severity::Critical]
+                .into_iter()
+                .filter(|severity| *severity >= min)
+                .map(|severity| severity.as_str()),
+        );
+        let placeholders = params_from(start, params.len() + 1 - start);
+        conditions.push(format!("severity IN ({placeholders})"));
+    }
+
+    let mut sql = String::from("SELECT * FROM alerts");
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+    sql.push_str(" ORDER BY created_at DESC, id DESC");
+    if filter.limit.is_some() {
+        sql.push_str(&format!(" LIMIT ${}", params.len() + 1));
+    }
+
+    let mut query = sqlx::query_as::<_, Alert>(&sql);
+    for param in params {
+        query = query.bind(param);
+    }
+    if let Some(limit) = filter.limit {
+        query = query.bind(limit.max(0));
+    }
+    query.fetch_all(pool).await
+}
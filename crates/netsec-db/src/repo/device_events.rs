@@ -1,12 +1,12 @@
 //! Device event repository.
 
 use netsec_models::event::DeviceEvent;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, event: &DeviceEvent) -> Result<(), sqlx::Error> {
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, event: &DeviceEvent) -> Result<(), sqlx::Error> {
     sqlx::query(
         "INSERT INTO device_events (id, device_id, event_type, details, created_at)
-         VALUES (?, ?, ?, ?, ?)"
+         VALUES ($1, $2, $3, $4, $5)"
     )
     .bind(&event.id)
     .bind(&event.device_id)
@@ -18,8 +18,8 @@ pub async fn insert(pool: &SqlitePool, event: &DeviceEvent) -> Result<(), sqlx::
     Ok(())
 }
 
-pub async fn list_by_device(pool: &SqlitePool, device_id: &str, limit: i64) -> Result<Vec<DeviceEvent>, sqlx::Error> {
-    sqlx::query_as::<_, DeviceEvent>("SELECT * FROM device_events WHERE device_id = ? ORDER BY created_at DESC LIMIT ?")
+pub async fn list_by_device<'e>(pool: impl AnyExecutor<'e>, device_id: &str, limit: i64) -> Result<Vec<DeviceEvent>, sqlx::Error> {
+    sqlx::query_as::<_, DeviceEvent>("SELECT * FROM device_events WHERE device_id = $1 ORDER BY created_at DESC LIMIT $2")
         .bind(device_id)
         .bind(limit)
         .fetch_all(pool)
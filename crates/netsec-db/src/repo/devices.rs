@@ -1,12 +1,21 @@
 //! Device repository.
 
-use netsec_models::device::Device;
-use sqlx::SqlitePool;
+use netsec_models::device::{Device, DeviceStatus};
+use std::collections::HashMap;
 
-pub async fn insert(pool: &SqlitePool, device: &Device) -> Result<(), sqlx::Error> {
+use sqlx::any::AnyExecutor;
+use sqlx::{Acquire, Any};
+
+use crate::repo::{
+    numbered_params, page_limit, values_placeholders, Page, MAX_BIND_PARAMS,
+};
+
+const DEVICE_COLUMNS: usize = 14;
+
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, device: &Device) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO devices (id, ip, mac, hostname, vendor, os_family, os_version, device_type, classification_confidence, status, notes, first_seen, last_seen)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO devices (id, ip, mac, hostname, vendor, os_family, os_version, device_type, classification_confidence, status, notes, first_seen, last_seen, discovery_method)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)"
     )
     .bind(&device.id)
     .bind(&device.ip)
@@ -21,37 +30,39 @@ pub async fn insert(pool: &SqlitePool, device: &Device) -> Result<(), sqlx::Erro
     .bind(&device.notes)
     .bind(&device.first_seen)
     .bind(&device.last_seen)
+    .bind(&device.discovery_method)
     .execute(pool)
     .await?;
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Device>, sqlx::Error> {
-    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<Device>, sqlx::Error> {
+    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn get_by_ip(pool: &SqlitePool, ip: &str) -> Result<Option<Device>, sqlx::Error> {
-    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE ip = ?")
+pub async fn get_by_ip<'e>(pool: impl AnyExecutor<'e>, ip: &str) -> Result<Option<Device>, sqlx::Error> {
+    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE ip = $1")
         .bind(ip)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<Device>, sqlx::Error> {
-    sqlx::query_as::<_, Device>("SELECT * FROM devices ORDER BY last_seen DESC LIMIT ? OFFSET ?")
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<Device>, sqlx::Error> {
+    sqlx::query_as::<_, Device>("SELECT * FROM devices ORDER BY last_seen DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn update(pool: &SqlitePool, device: &Device) -> Result<bool, sqlx::Error> {
+pub async fn update<'e>(pool: impl AnyExecutor<'e>, device: &Device) -> Result<bool, sqlx::Error> {
     let result = sqlx::query(
-        "UPDATE devices SET ip=?, mac=?, hostname=?, vendor=?, os_family=?, os_version=?, device_type=?, classification_confidence=?, status=?, notes=?, last_seen=?
-         WHERE id=?"
+        "UPDATE devices SET ip=$1, mac=$2, hostname=$3, vendor=$4, os_family=$5, os_version=$6, device_type=$7, classification_confidence=$8, status=$9, notes=$10, last_seen=$11,
+             discovery_method=COALESCE(discovery_method, $12)
+         WHERE id=$13"
     )
     .bind(&device.ip)
     .bind(&device.mac)
@@ -64,30 +75,185 @@ pub async fn update(pool: &SqlitePool, device: &Device) -> Result<bool, sqlx::Er
     .bind(&device.status)
     .bind(&device.notes)
     .bind(&device.last_seen)
+    .bind(&device.discovery_method)
     .bind(&device.id)
     .execute(pool)
     .await?;
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM devices WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM devices WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn get_by_mac(pool: &SqlitePool, mac: &str) -> Result<Option<Device>, sqlx::Error> {
-    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE mac = ?")
+pub async fn get_by_mac<'e>(pool: impl AnyExecutor<'e>, mac: &str) -> Result<Option<Device>, sqlx::Error> {
+    sqlx::query_as::<_, Device>("SELECT * FROM devices WHERE mac = $1")
         .bind(mac)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+/// List all devices bound to a MAC, matching case- and separator-insensitively.
+pub async fn list_by_mac<'e>(pool: impl AnyExecutor<'e>, mac: &str) -> Result<Vec<Device>, sqlx::Error> {
+    let normalized = mac.trim().to_ascii_lowercase().replace('-', ":");
+    sqlx::query_as::<_, Device>(
+        "SELECT * FROM devices WHERE lower(replace(mac, '-', ':')) = $1 ORDER BY ip",
+    )
+    .bind(normalized)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count<'e>(pool: impl AnyExecutor<'e>) -> Result<i64, sqlx::Error> {
     let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM devices")
         .fetch_one(pool)
         .await?;
     Ok(row.0)
 }
+
+/// Devices, most recently discovered first, one page at a time.
+///
+/// `after` is the `next_cursor` of the previous page (`None` for the first).
+/// Rows are ordered by `(first_seen, id)`, so pages stay stable while new rows are
+/// inserted. A cursor whose row has since been deleted yields an empty page.
+pub async fn list_paged<'e>(
+    pool: impl AnyExecutor<'e>,
+    after: Option<&str>,
+    limit: i64,
+) -> Result<Page<Device>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, Device>(
+        "SELECT * FROM devices
+         WHERE $1 IS NULL
+            OR (first_seen, id) < (SELECT first_seen, id FROM devices WHERE id = $1)
+         ORDER BY first_seen DESC, id DESC
+         LIMIT $2",
+    )
+    .bind(after)
+    .bind(page_limit(limit) + 1)
+    .fetch_all(pool)
+    .await?;
+    Ok(Page::from_rows(rows, limit, |device| &device.id))
+}
+
+/// Devices with any of the given IPs.
+pub async fn list_by_ips<'a>(
+    conn: impl Acquire<'a, Database = Any>,
+    ips: &[String],
+) -> Result<Vec<Device>, sqlx::Error> {
+    let mut conn = conn.acquire().await?;
+    let mut found = Vec::new();
+    for chunk in ips.chunks(MAX_BIND_PARAMS) {
+        let sql = format!(
+            "SELECT * FROM devices WHERE ip IN ({})",
+            numbered_params(chunk.len())
+        );
+        let mut query = sqlx::query_as::<_, Device>(&sql);
+        for ip in chunk {
+            query = query.bind(ip);
+        }
+        found.extend(query.fetch_all(&mut *conn).await?);
+    }
+    Ok(found)
+}
+
+/// Set `online` devices last seen before `seen_before` (RFC 3339) to
+/// `offline`, in one transaction. Returns the devices that changed, with
+/// their new status.
+pub async fn mark_offline_before<'a>(
+    conn: impl Acquire<'a, Database = Any>,
+    seen_before: &str,
+) -> Result<Vec<Device>, sqlx::Error> {
+    let offline = DeviceStatus::Offline.as_str();
+    let mut tx = conn.begin().await?;
+    let mut stale = sqlx::query_as::<_, Device>(
+        "SELECT * FROM devices WHERE status = $1 AND last_seen < $2 ORDER BY last_seen",
+    )
+    .bind(DeviceStatus::Online.as_str())
+    .bind(seen_before)
+    .fetch_all(&mut *tx)
+    .await?;
+    for device in &mut stale {
+        sqlx::query("UPDATE devices SET status = $1 WHERE id = $2")
+            .bind(offline)
+            .bind(&device.id)
+            .execute(&mut *tx)
+            .await?;
+        device.status = offline.to_string();
+    }
+    tx.commit().await?;
+    Ok(stale)
+}
+
+/// Insert or update many devices, keyed by id, with multi-row
+/// `INSERT ... ON CONFLICT` statements in a single transaction.
+///
+/// Existing rows get the same columns [`update`] writes; `first_seen` is
+/// kept. Pass a `&DbPool` or a connection/transaction to join an outer
+/// transaction. When `devices` repeats an id the last entry wins.
+pub async fn bulk_upsert<'a>(
+    conn: impl Acquire<'a, Database = Any>,
+    devices: &[Device],
+) -> Result<(), sqlx::Error> {
+    if devices.is_empty() {
+        return Ok(());
+    }
+
+    // One row per id: Postgres rejects a statement that updates a row twice
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut unique: Vec<&Device> = Vec::new();
+    for device in devices {
+        match index.get(device.id.as_str()) {
+            Some(&i) => unique[i] = device,
+            None => {
+                index.insert(&device.id, unique.len());
+                unique.push(device);
+            }
+        }
+    }
+
+    let mut tx = conn.begin().await?;
+    for chunk in unique.chunks(MAX_BIND_PARAMS / DEVICE_COLUMNS) {
+        let sql = format!(
+            "INSERT INTO devices (id, ip, mac, hostname, vendor, os_family, os_version, device_type, classification_confidence, status, notes, first_seen, last_seen, discovery_method)
+             VALUES {}
+             ON CONFLICT(id) DO UPDATE SET
+               ip = excluded.ip,
+               mac = excluded.mac,
+               hostname = excluded.hostname,
+               vendor = excluded.vendor,
+               os_family = excluded.os_family,
+               os_version = excluded.os_version,
+               device_type = excluded.device_type,
+               classification_confidence = excluded.classification_confidence,
+               status = excluded.status,
+               notes = excluded.notes,
+               last_seen = excluded.last_seen,
+               discovery_method = COALESCE(devices.discovery_method, excluded.discovery_method)",
+            values_placeholders(chunk.len(), DEVICE_COLUMNS)
+        );
+        let mut query = sqlx::query(&sql);
+        for device in chunk {
+            query = query
+                .bind(&device.id)
+                .bind(&device.ip)
+                .bind(&device.mac)
+                .bind(&device.hostname)
+                .bind(&device.vendor)
+                .bind(&device.os_family)
+                .bind(&device.os_version)
+                .bind(&device.device_type)
+                .bind(device.classification_confidence)
+                .bind(&device.status)
+                .bind(&device.notes)
+                .bind(&device.first_seen)
+                .bind(&device.last_seen)
+                .bind(&device.discovery_method);
+        }
+        query.execute(&mut *tx).await?;
+    }
+    tx.commit().await
+}
@@ -1,12 +1,12 @@
 //! Observation repository.
 
 use netsec_models::event::Observation;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, obs: &Observation) -> Result<(), sqlx::Error> {
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, obs: &Observation) -> Result<(), sqlx::Error> {
     sqlx::query(
         "INSERT INTO observations (id, device_id, protocol, source_data, created_at)
-         VALUES (?, ?, ?, ?, ?)"
+         VALUES ($1, $2, $3, $4, $5)"
     )
     .bind(&obs.id)
     .bind(&obs.device_id)
@@ -18,8 +18,8 @@ pub async fn insert(pool: &SqlitePool, obs: &Observation) -> Result<(), sqlx::Er
     Ok(())
 }
 
-pub async fn list_by_device(pool: &SqlitePool, device_id: &str, limit: i64) -> Result<Vec<Observation>, sqlx::Error> {
-    sqlx::query_as::<_, Observation>("SELECT * FROM observations WHERE device_id = ? ORDER BY created_at DESC LIMIT ?")
+pub async fn list_by_device<'e>(pool: impl AnyExecutor<'e>, device_id: &str, limit: i64) -> Result<Vec<Observation>, sqlx::Error> {
+    sqlx::query_as::<_, Observation>("SELECT * FROM observations WHERE device_id = $1 ORDER BY created_at DESC LIMIT $2")
         .bind(device_id)
         .bind(limit)
         .fetch_all(pool)
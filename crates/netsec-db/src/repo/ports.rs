@@ -1,12 +1,19 @@
 //! Port repository.
 
 use netsec_models::port::Port;
-use sqlx::SqlitePool;
+use std::collections::HashMap;
 
-pub async fn insert(pool: &SqlitePool, port: &Port) -> Result<(), sqlx::Error> {
+use sqlx::any::AnyExecutor;
+use sqlx::{Acquire, Any};
+
+use crate::repo::{numbered_params, values_placeholders, MAX_BIND_PARAMS};
+
+const PORT_COLUMNS: usize = 12;
+
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, port: &Port) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO ports (id, device_id, port_number, protocol, state, service_name, service_version, banner, first_seen, last_seen)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO ports (id, device_id, port_number, protocol, state, service_name, service_version, banner, first_seen, last_seen, http_title, server_header)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"
     )
     .bind(&port.id)
     .bind(&port.device_id)
@@ -18,35 +25,39 @@ pub async fn insert(pool: &SqlitePool, port: &Port) -> Result<(), sqlx::Error> {
     .bind(&port.banner)
     .bind(&port.first_seen)
     .bind(&port.last_seen)
+    .bind(&port.http_title)
+    .bind(&port.server_header)
     .execute(pool)
     .await?;
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Port>, sqlx::Error> {
-    sqlx::query_as::<_, Port>("SELECT * FROM ports WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<Port>, sqlx::Error> {
+    sqlx::query_as::<_, Port>("SELECT * FROM ports WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list_by_device(pool: &SqlitePool, device_id: &str) -> Result<Vec<Port>, sqlx::Error> {
-    sqlx::query_as::<_, Port>("SELECT * FROM ports WHERE device_id = ? ORDER BY port_number")
+pub async fn list_by_device<'e>(pool: impl AnyExecutor<'e>, device_id: &str) -> Result<Vec<Port>, sqlx::Error> {
+    sqlx::query_as::<_, Port>("SELECT * FROM ports WHERE device_id = $1 ORDER BY port_number")
         .bind(device_id)
         .fetch_all(pool)
         .await
 }
 
-pub async fn upsert(pool: &SqlitePool, port: &Port) -> Result<(), sqlx::Error> {
+pub async fn upsert<'e>(pool: impl AnyExecutor<'e>, port: &Port) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO ports (id, device_id, port_number, protocol, state, service_name, service_version, banner, first_seen, last_seen)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "INSERT INTO ports (id, device_id, port_number, protocol, state, service_name, service_version, banner, first_seen, last_seen, http_title, server_header)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
          ON CONFLICT(device_id, port_number, protocol) DO UPDATE SET
            state = excluded.state,
            service_name = excluded.service_name,
            service_version = excluded.service_version,
            banner = excluded.banner,
-           last_seen = excluded.last_seen"
+           last_seen = excluded.last_seen,
+           http_title = COALESCE(excluded.http_title, ports.http_title),
+           server_header = COALESCE(excluded.server_header, ports.server_header)"
     )
     .bind(&port.id)
     .bind(&port.device_id)
@@ -58,19 +69,21 @@ pub async fn upsert(pool: &SqlitePool, port: &Port) -> Result<(), sqlx::Error> {
     .bind(&port.banner)
     .bind(&port.first_seen)
     .bind(&port.last_seen)
+    .bind(&port.http_title)
+    .bind(&port.server_header)
     .execute(pool)
     .await?;
     Ok(())
 }
 
-pub async fn get_by_device_port_proto(
-    pool: &SqlitePool,
+pub async fn get_by_device_port_proto<'e>(
+    pool: impl AnyExecutor<'e>,
     device_id: &str,
     port_number: i64,
     protocol: &str,
 ) -> Result<Option<Port>, sqlx::Error> {
     sqlx::query_as::<_, Port>(
-        "SELECT * FROM ports WHERE device_id = ? AND port_number = ? AND protocol = ?",
+        "SELECT * FROM ports WHERE device_id = $1 AND port_number = $2 AND protocol = $3",
     )
     .bind(device_id)
     .bind(port_number)
@@ -79,10 +92,95 @@ pub async fn get_by_device_port_proto(
     .await
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM ports WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM ports WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
     Ok(result.rows_affected() > 0)
 }
+
+/// Upsert many ports with multi-row `INSERT ... ON CONFLICT` statements in a
+/// single transaction, using the same conflict rules as [`upsert`].
+///
+/// Pass a `&DbPool` or a connection/transaction to join an outer transaction.
+/// When `ports` repeats a `(device_id, port_number, protocol)` key the last
+/// entry wins.
+pub async fn bulk_upsert<'a>(
+    conn: impl Acquire<'a, Database = Any>,
+    ports: &[Port],
+) -> Result<(), sqlx::Error> {
+    if ports.is_empty() {
+        return Ok(());
+    }
+
+    // One row per key: Postgres rejects a statement that updates a row twice
+    let mut index: HashMap<(&str, i64, &str), usize> = HashMap::new();
+    let mut unique: Vec<&Port> = Vec::new();
+    for port in ports {
+        let key = (port.device_id.as_str(), port.port_number, port.protocol.as_str());
+        match index.get(&key) {
+            Some(&i) => unique[i] = port,
+            None => {
+                index.insert(key, unique.len());
+                unique.push(port);
+            }
+        }
+    }
+
+    let mut tx = conn.begin().await?;
+    for chunk in unique.chunks(MAX_BIND_PARAMS / PORT_COLUMNS) {
+        let sql = format!(
+            "INSERT INTO ports (id, device_id, port_number, protocol, state, service_name, service_version, banner, first_seen, last_seen, http_title, server_header)
+             VALUES {}
+             ON CONFLICT(device_id, port_number, protocol) DO UPDATE SET
+               state = excluded.state,
+               service_name = excluded.service_name,
+               service_version = excluded.service_version,
+               banner = excluded.banner,
+               last_seen = excluded.last_seen,
+               http_title = COALESCE(excluded.http_title, ports.http_title),
+               server_header = COALESCE(excluded.server_header, ports.server_header)",
+            values_placeholders(chunk.len(), PORT_COLUMNS)
+        );
+        let mut query = sqlx::query(&sql);
+        for port in chunk {
+            query = query
+                .bind(&port.id)
+                .bind(&port.device_id)
+                .bind(port.port_number)
+                .bind(&port.protocol)
+                .bind(&port.state)
+                .bind(&port.service_name)
+                .bind(&port.service_version)
+                .bind(&port.banner)
+                .bind(&port.first_seen)
+                .bind(&port.last_seen)
+                .bind(&port.http_title)
+                .bind(&port.server_header);
+        }
+        query.execute(&mut *tx).await?;
+    }
+    tx.commit().await
+}
+
+/// Ports of all the given devices, ordered by device then port number.
+pub async fn list_by_devices<'a>(
+    conn: impl Acquire<'a, Database = Any>,
+    device_ids: &[String],
+) -> Result<Vec<Port>, sqlx::Error> {
+    let mut conn = conn.acquire().await?;
+    let mut found = Vec::new();
+    for chunk in device_ids.chunks(MAX_BIND_PARAMS) {
+        let sql = format!(
+            "SELECT * FROM ports WHERE device_id IN ({}) ORDER BY device_id, port_number",
+            numbered_params(chunk.len())
+        );
+        let mut query = sqlx::query_as::<_, Port>(&sql);
+        for id in chunk {
+            query = query.bind(id);
+        }
+        found.extend(query.fetch_all(&mut *conn).await?);
+    }
+    Ok(found)
+}
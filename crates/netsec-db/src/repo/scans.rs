@@ -1,12 +1,14 @@
 //! Scan repository.
 
 use netsec_models::scan::Scan;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, scan: &Scan) -> Result<(), sqlx::Error> {
+use crate::repo::{page_limit, Page};
+
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, scan: &Scan) -> Result<(), sqlx::Error> {
     sqlx::query(
         "INSERT INTO scans (id, scan_type, tool, target, status, progress, parameters, results, started_at, completed_at, created_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
     )
     .bind(&scan.id)
     .bind(&scan.scan_type)
@@ -24,23 +26,23 @@ pub async fn insert(pool: &SqlitePool, scan: &Scan) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Scan>, sqlx::Error> {
-    sqlx::query_as::<_, Scan>("SELECT * FROM scans WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<Scan>, sqlx::Error> {
+    sqlx::query_as::<_, Scan>("SELECT * FROM scans WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<Scan>, sqlx::Error> {
-    sqlx::query_as::<_, Scan>("SELECT * FROM scans ORDER BY created_at DESC LIMIT ? OFFSET ?")
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<Scan>, sqlx::Error> {
+    sqlx::query_as::<_, Scan>("SELECT * FROM scans ORDER BY created_at DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn update_status(pool: &SqlitePool, id: &str, status: &str, progress: f64) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("UPDATE scans SET status=?, progress=? WHERE id=?")
+pub async fn update_status<'e>(pool: impl AnyExecutor<'e>, id: &str, status: &str, progress: f64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE scans SET status=$1, progress=$2 WHERE id=$3")
         .bind(status)
         .bind(progress)
         .bind(id)
@@ -49,8 +51,8 @@ pub async fn update_status(pool: &SqlitePool, id: &str, status: &str, progress:
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn set_results(pool: &SqlitePool, id: &str, results: &str, completed_at: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("UPDATE scans SET results=?, status='completed', progress=1.0, completed_at=? WHERE id=?")
+pub async fn set_results<'e>(pool: impl AnyExecutor<'e>, id: &str, results: &str, completed_at: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE scans SET results=$1, status='completed', progress=1.0, completed_at=$2 WHERE id=$3")
         .bind(results)
         .bind(completed_at)
         .bind(id)
@@ -59,10 +61,34 @@ pub async fn set_results(pool: &SqlitePool, id: &str, results: &str, completed_a
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM scans WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM scans WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
     Ok(result.rows_affected() > 0)
 }
+
+/// Scans, newest first, one page at a time.
+///
+/// `after` is the `next_cursor` of the previous page (`None` for the first).
+/// Rows are ordered by `(created_at, id)`, so pages stay stable while new rows are
+/// inserted. A cursor whose row has since been deleted yields an empty page.
+pub async fn list_paged<'e>(
+    pool: impl AnyExecutor<'e>,
+    after: Option<&str>,
+    limit: i64,
+) -> Result<Page<Scan>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, Scan>(
+        "SELECT * FROM scans
+         WHERE $1 IS NULL
+            OR (created_at, id) < (SELECT created_at, id FROM scans WHERE id = $1)
+         ORDER BY created_at DESC, id DESC
+         LIMIT $2",
+    )
+    .bind(after)
+    .bind(page_limit(limit) + 1)
+    .fetch_all(pool)
+    .await?;
+    Ok(Page::from_rows(rows, limit, |scan| &scan.id))
+}
@@ -1,12 +1,12 @@
 //! Scheduled job repository.
 
 use netsec_models::plugin::ScheduledJob;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, job: &ScheduledJob) -> Result<(), sqlx::Error> {
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, job: &ScheduledJob) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO scheduled_jobs (id, trigger_type, trigger_args, task_type, task_params, enabled, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO scheduled_jobs (id, trigger_type, trigger_args, task_type, task_params, enabled, created_at, updated_at, last_run, timezone)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
     )
     .bind(&job.id)
     .bind(&job.trigger_type)
@@ -16,34 +16,37 @@ pub async fn insert(pool: &SqlitePool, job: &ScheduledJob) -> Result<(), sqlx::E
     .bind(job.enabled)
     .bind(&job.created_at)
     .bind(&job.updated_at)
+    .bind(&job.last_run)
+    .bind(&job.timezone)
     .execute(pool)
     .await?;
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<ScheduledJob>, sqlx::Error> {
-    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<ScheduledJob>, sqlx::Error> {
+    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list_enabled(pool: &SqlitePool) -> Result<Vec<ScheduledJob>, sqlx::Error> {
-    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs WHERE enabled = 1")
+pub async fn list_enabled<'e>(pool: impl AnyExecutor<'e>) -> Result<Vec<ScheduledJob>, sqlx::Error> {
+    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs WHERE enabled = $1")
+        .bind(true)
         .fetch_all(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<ScheduledJob>, sqlx::Error> {
-    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs ORDER BY created_at DESC LIMIT ? OFFSET ?")
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<ScheduledJob>, sqlx::Error> {
+    sqlx::query_as::<_, ScheduledJob>("SELECT * FROM scheduled_jobs ORDER BY created_at DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn set_enabled(pool: &SqlitePool, id: &str, enabled: bool, updated_at: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("UPDATE scheduled_jobs SET enabled=?, updated_at=? WHERE id=?")
+pub async fn set_enabled<'e>(pool: impl AnyExecutor<'e>, id: &str, enabled: bool, updated_at: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE scheduled_jobs SET enabled=$1, updated_at=$2 WHERE id=$3")
         .bind(enabled)
         .bind(updated_at)
         .bind(id)
@@ -52,8 +55,18 @@ pub async fn set_enabled(pool: &SqlitePool, id: &str, enabled: bool, updated_at:
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM scheduled_jobs WHERE id = ?")
+/// Record when a job was last dispatched. Returns `false` if the job does not exist.
+pub async fn update_last_run<'e>(pool: impl AnyExecutor<'e>, id: &str, last_run: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE scheduled_jobs SET last_run=$1 WHERE id=$2")
+        .bind(last_run)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM scheduled_jobs WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
@@ -1,12 +1,12 @@
 //! Traffic flow repository.
 
 use netsec_models::traffic::TrafficFlow;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, flow: &TrafficFlow) -> Result<(), sqlx::Error> {
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, flow: &TrafficFlow) -> Result<(), sqlx::Error> {
     sqlx::query(
-        "INSERT INTO traffic_flows (id, src_ip, src_port, dst_ip, dst_port, protocol, bytes_sent, bytes_received, packets_sent, packets_received, first_seen, last_seen)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO traffic_flows (id, src_ip, src_port, dst_ip, dst_port, protocol, bytes_sent, bytes_received, packets_sent, packets_received, first_seen, last_seen, country_src, country_dst)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)"
     )
     .bind(&flow.id)
     .bind(&flow.src_ip)
@@ -20,28 +20,30 @@ pub async fn insert(pool: &SqlitePool, flow: &TrafficFlow) -> Result<(), sqlx::E
     .bind(flow.packets_received)
     .bind(&flow.first_seen)
     .bind(&flow.last_seen)
+    .bind(&flow.country_src)
+    .bind(&flow.country_dst)
     .execute(pool)
     .await?;
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<TrafficFlow>, sqlx::Error> {
-    sqlx::query_as::<_, TrafficFlow>("SELECT * FROM traffic_flows WHERE id = ?")
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<TrafficFlow>, sqlx::Error> {
+    sqlx::query_as::<_, TrafficFlow>("SELECT * FROM traffic_flows WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<TrafficFlow>, sqlx::Error> {
-    sqlx::query_as::<_, TrafficFlow>("SELECT * FROM traffic_flows ORDER BY last_seen DESC LIMIT ? OFFSET ?")
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<TrafficFlow>, sqlx::Error> {
+    sqlx::query_as::<_, TrafficFlow>("SELECT * FROM traffic_flows ORDER BY last_seen DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM traffic_flows WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM traffic_flows WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
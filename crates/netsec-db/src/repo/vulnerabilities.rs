@@ -1,12 +1,14 @@
 //! Vulnerability repository.
 
 use netsec_models::vulnerability::Vulnerability;
-use sqlx::SqlitePool;
+use sqlx::any::AnyExecutor;
 
-pub async fn insert(pool: &SqlitePool, vuln: &Vulnerability) -> Result<(), sqlx::Error> {
+use crate::pool::DbPool;
+
+pub async fn insert<'e>(pool: impl AnyExecutor<'e>, vuln: &Vulnerability) -> Result<(), sqlx::Error> {
     sqlx::query(
         "INSERT INTO vulnerabilities (id, cve_id, cvss_score, severity, title, description, device_id, port, source_tool, solution, created_at, updated_at, service, device_ip, status, references_json)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)"
     )
     .bind(&vuln.id)
     .bind(&vuln.cve_id)
@@ -29,30 +31,74 @@ pub async fn insert(pool: &SqlitePool, vuln: &Vulnerability) -> Result<(), sqlx:
     Ok(())
 }
 
-pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Vulnerability>, sqlx::Error> {
-    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities WHERE id = ?")
+/// Insert a vulnerability, or refresh the existing row for the same CVE on
+/// the same device IP and port.
+///
+/// Re-running a vuln scan updates score, severity, title, service and
+/// references in place instead of duplicating rows; the existing row keeps
+/// its id, status and creation time. Returns the id of the stored row.
+pub async fn upsert(pool: &DbPool, vuln: &Vulnerability) -> Result<String, sqlx::Error> {
+    let existing: Option<(String,)> = sqlx::query_as(
+        "SELECT id FROM vulnerabilities
+         WHERE cve_id IS NOT DISTINCT FROM $1
+           AND device_ip IS NOT DISTINCT FROM $2
+           AND port IS NOT DISTINCT FROM $3
+         LIMIT 1"
+    )
+    .bind(&vuln.cve_id)
+    .bind(&vuln.device_ip)
+    .bind(vuln.port)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((id,)) = existing else {
+        insert(pool, vuln).await?;
+        return Ok(vuln.id.clone());
+    };
+
+    sqlx::query(
+        "UPDATE vulnerabilities
+         SET cvss_score = $1, severity = $2, title = $3, description = $4, service = $5,
+             references_json = $6, updated_at = $7
+         WHERE id = $8"
+    )
+    .bind(vuln.cvss_score)
+    .bind(&vuln.severity)
+    .bind(&vuln.title)
+    .bind(&vuln.description)
+    .bind(&vuln.service)
+    .bind(&vuln.references_json)
+    .bind(&vuln.updated_at)
+    .bind(&id)
+    .execute(pool)
+    .await?;
+    Ok(id)
+}
+
+pub async fn get_by_id<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<Option<Vulnerability>, sqlx::Error> {
+    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities WHERE id = $1")
         .bind(id)
         .fetch_optional(pool)
         .await
 }
 
-pub async fn list(pool: &SqlitePool, limit: i64, offset: i64) -> Result<Vec<Vulnerability>, sqlx::Error> {
-    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities ORDER BY created_at DESC LIMIT ? OFFSET ?")
+pub async fn list<'e>(pool: impl AnyExecutor<'e>, limit: i64, offset: i64) -> Result<Vec<Vulnerability>, sqlx::Error> {
+    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities ORDER BY created_at DESC LIMIT $1 OFFSET $2")
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
         .await
 }
 
-pub async fn list_by_device(pool: &SqlitePool, device_id: &str) -> Result<Vec<Vulnerability>, sqlx::Error> {
-    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities WHERE device_id = ? ORDER BY cvss_score DESC")
+pub async fn list_by_device<'e>(pool: impl AnyExecutor<'e>, device_id: &str) -> Result<Vec<Vulnerability>, sqlx::Error> {
+    sqlx::query_as::<_, Vulnerability>("SELECT * FROM vulnerabilities WHERE device_id = $1 ORDER BY cvss_score DESC")
         .bind(device_id)
         .fetch_all(pool)
         .await
 }
 
-pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query("DELETE FROM vulnerabilities WHERE id = ?")
+pub async fn delete<'e>(pool: impl AnyExecutor<'e>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM vulnerabilities WHERE id = $1")
         .bind(id)
         .execute(pool)
         .await?;
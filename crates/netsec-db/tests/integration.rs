@@ -1,8 +1,9 @@
 //! Integration tests for netsec-db against in-memory SQLite.
 
-use netsec_db::{create_pool, run_migrations};
+use netsec_db::{create_pool, run_migrations, DbPool};
 use netsec_db::repo::{devices, ports, alerts, scans, vulnerabilities, traffic, device_events, observations, scheduled_jobs};
-use netsec_models::alert::{Alert, Severity};
+use netsec_db::repo::alerts::AlertFilter;
+use netsec_models::alert::{Alert, AlertStatus, Severity};
 use netsec_models::device::Device;
 use netsec_models::event::{DeviceEvent, DeviceEventType, Observation};
 use netsec_models::plugin::{ScheduledJob, TriggerType};
@@ -11,7 +12,7 @@ use netsec_models::scan::{Scan, ScanType};
 use netsec_models::traffic::TrafficFlow;
 use netsec_models::vulnerability::Vulnerability;
 
-async fn setup() -> sqlx::SqlitePool {
+async fn setup() -> DbPool {
     let pool = create_pool("sqlite::memory:").await.expect("pool creation failed");
     run_migrations(&pool).await.expect("migrations failed");
     pool
@@ -146,16 +147,52 @@ async fn test_vulnerability_crud() {
     assert_eq!(fetched.severity, "high");
 }
 
+#[tokio::test]
+async fn test_vulnerability_upsert_by_cve_and_port() {
+    let pool = setup().await;
+
+    let mut first = Vulnerability::new("CVE-2023-38408".into(), "nmap".into(), Severity::High);
+    first.cve_id = Some("CVE-2023-38408".into());
+    first.device_ip = Some("10.0.0.5".into());
+    first.port = Some(22);
+    first.cvss_score = Some(7.5);
+    let id = vulnerabilities::upsert(&pool, &first).await.unwrap();
+    assert_eq!(id, first.id);
+
+    // Same CVE on the same port updates in place
+    let mut rescan = Vulnerability::new("CVE-2023-38408".into(), "nmap".into(), Severity::Critical);
+    rescan.cve_id = first.cve_id.clone();
+    rescan.device_ip = first.device_ip.clone();
+    rescan.port = Some(22);
+    rescan.cvss_score = Some(9.8);
+    let id = vulnerabilities::upsert(&pool, &rescan).await.unwrap();
+    assert_eq!(id, first.id);
+
+    let stored = vulnerabilities::get_by_id(&pool, &first.id).await.unwrap().unwrap();
+    assert_eq!(stored.cvss_score, Some(9.8));
+    assert_eq!(stored.severity, "critical");
+
+    // Different port is a separate finding
+    let mut other_port = rescan.clone();
+    other_port.id = "other".into();
+    other_port.port = Some(2222);
+    vulnerabilities::upsert(&pool, &other_port).await.unwrap();
+    assert_eq!(vulnerabilities::list(&pool, 100, 0).await.unwrap().len(), 2);
+}
+
 #[tokio::test]
 async fn test_traffic_crud() {
     let pool = setup().await;
 
-    let flow = TrafficFlow::new("10.0.0.1".into(), 12345, "10.0.0.2".into(), 80, "tcp".into());
+    let mut flow = TrafficFlow::new("10.0.0.1".into(), 12345, "8.8.8.8".into(), 80, "tcp".into());
+    flow.country_dst = Some("US".into());
     traffic::insert(&pool, &flow).await.unwrap();
 
     let fetched = traffic::get_by_id(&pool, &flow.id).await.unwrap().unwrap();
     assert_eq!(fetched.src_ip, "10.0.0.1");
     assert_eq!(fetched.dst_port, 80);
+    assert_eq!(fetched.country_src, None);
+    assert_eq!(fetched.country_dst.as_deref(), Some("US"));
 
     let all = traffic::list(&pool, 100, 0).await.unwrap();
     assert_eq!(all.len(), 1);
@@ -209,6 +246,7 @@ async fn test_scheduled_jobs_crud() {
     let fetched = scheduled_jobs::get_by_id(&pool, &job.id).await.unwrap().unwrap();
     assert_eq!(fetched.task_type, "full_scan");
     assert!(fetched.enabled);
+    assert_eq!(fetched.timezone, "UTC");
 
     // List enabled
     let enabled = scheduled_jobs::list_enabled(&pool).await.unwrap();
@@ -223,6 +261,21 @@ async fn test_scheduled_jobs_crud() {
     assert_eq!(enabled_after.len(), 0);
 }
 
+#[tokio::test]
+async fn test_scheduled_jobs_update_last_run() {
+    let pool = setup().await;
+
+    let job = ScheduledJob::new(TriggerType::Interval, "discovery_scan".into());
+    scheduled_jobs::insert(&pool, &job).await.unwrap();
+    assert!(scheduled_jobs::get_by_id(&pool, &job.id).await.unwrap().unwrap().last_run.is_none());
+
+    assert!(scheduled_jobs::update_last_run(&pool, &job.id, "2024-01-15T10:00:00+00:00").await.unwrap());
+    let fetched = scheduled_jobs::get_by_id(&pool, &job.id).await.unwrap().unwrap();
+    assert_eq!(fetched.last_run.as_deref(), Some("2024-01-15T10:00:00+00:00"));
+
+    assert!(!scheduled_jobs::update_last_run(&pool, "missing", "2024-01-15T10:00:00+00:00").await.unwrap());
+}
+
 #[tokio::test]
 async fn test_cascade_delete_device() {
     let pool = setup().await;
@@ -291,6 +344,36 @@ async fn test_alert_list_and_pagination() {
     assert_eq!(page2.len(), 1);
 }
 
+#[tokio::test]
+async fn test_alert_insert_many_and_list_by_fingerprints() {
+    let pool = setup().await;
+
+    let batch: Vec<Alert> = (0..3)
+        .map(|i| Alert::new(format!("Batch {i}"), "suricata".into(), format!("fp-batch-{i}")))
+        .collect();
+    alerts::insert_many(&pool, &batch).await.unwrap();
+    assert_eq!(alerts::count(&pool).await.unwrap(), 3);
+
+    let fingerprints: Vec<String> = ["fp-batch-0", "fp-batch-2", "fp-none"]
+        .iter()
+        .map(|fp| fp.to_string())
+        .collect();
+    let found = alerts::list_by_fingerprints(&pool, &fingerprints).await.unwrap();
+    let mut titles: Vec<&str> = found.iter().map(|a| a.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["Batch 0", "Batch 2"]);
+
+    // A failing row rolls back the whole batch
+    let dup_id = Alert {
+        title: "Conflicting".into(),
+        ..batch[0].clone()
+    };
+    let fresh = Alert::new("Fresh".into(), "suricata".into(), "fp-fresh".into());
+    assert!(alerts::insert_many(&pool, &[fresh.clone(), dup_id]).await.is_err());
+    assert!(alerts::get_by_id(&pool, &fresh.id).await.unwrap().is_none());
+    assert_eq!(alerts::count(&pool).await.unwrap(), 3);
+}
+
 #[tokio::test]
 async fn test_alert_update_status() {
     let pool = setup().await;
@@ -304,6 +387,139 @@ async fn test_alert_update_status() {
     assert_eq!(fetched.status, "acknowledged");
 }
 
+#[tokio::test]
+async fn test_alert_bulk_update_status() {
+    let pool = setup().await;
+    let mut ids = Vec::new();
+    for i in 0..4 {
+        let alert = Alert::new(format!("Bulk {i}"), "suricata".into(), format!("fp-bulk-{i}"));
+        alerts::insert(&pool, &alert).await.unwrap();
+        ids.push(alert.id);
+    }
+
+    let targets = vec![ids[0].clone(), ids[1].clone(), ids[2].clone(), "missing".to_string()];
+    let changed = alerts::bulk_update_status(&pool, &targets, "resolved").await.unwrap();
+    assert_eq!(changed, 3);
+
+    for (i, id) in ids.iter().enumerate() {
+        let alert = alerts::get_by_id(&pool, id).await.unwrap().unwrap();
+        let expected = if i < 3 { "resolved" } else { "new" };
+        assert_eq!(alert.status, expected, "alert {i}");
+    }
+    assert_eq!(alerts::bulk_update_status(&pool, &[], "resolved").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_alert_auto_resolve_stale() {
+    let pool = setup().await;
+    let mut old = Alert::new("Old".into(), "suricata".into(), "fp-old".into());
+    old.last_seen = "2020-01-01T00:00:00+00:00".into();
+    alerts::insert(&pool, &old).await.unwrap();
+    let recent = Alert::new("Recent".into(), "suricata".into(), "fp-recent".into());
+    alerts::insert(&pool, &recent).await.unwrap();
+    // Acknowledged alerts are still open
+    let mut acked = Alert::new("Acked".into(), "suricata".into(), "fp-acked".into());
+    acked.last_seen = old.last_seen.clone();
+    acked.status = AlertStatus::Acknowledged.as_str().into();
+    alerts::insert(&pool, &acked).await.unwrap();
+    // Closed alerts are left alone
+    let mut dismissed = Alert::new("Dismissed".into(), "suricata".into(), "fp-fp".into());
+    dismissed.last_seen = old.last_seen.clone();
+    dismissed.status = AlertStatus::FalsePositive.as_str().into();
+    alerts::insert(&pool, &dismissed).await.unwrap();
+    // No last_seen (rows predating the column): aged from created_at
+    let mut unseen = Alert::new("Unseen".into(), "suricata".into(), "fp-unseen".into());
+    unseen.created_at = old.last_seen.clone();
+    alerts::insert(&pool, &unseen).await.unwrap();
+    let fresh = Alert::new("Unseen recent".into(), "suricata".into(), "fp-fresh".into());
+    alerts::insert(&pool, &fresh).await.unwrap();
+    sqlx::query("UPDATE alerts SET last_seen = NULL WHERE id IN ($1, $2)")
+        .bind(&unseen.id)
+        .bind(&fresh.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let day = std::time::Duration::from_secs(86_400);
+    assert_eq!(alerts::auto_resolve_stale(&pool, day).await.unwrap(), 3);
+
+    let old = alerts::get_with_notes(&pool, &old.id).await.unwrap().unwrap();
+    assert_eq!(old.status, "resolved");
+    assert_eq!(old.note_history.len(), 1);
+    assert_eq!(old.note_history[0].text, alerts::AUTO_RESOLVE_NOTE);
+    assert_eq!(old.note_history[0].author, alerts::SYSTEM_AUTHOR);
+    let recent = alerts::get_with_notes(&pool, &recent.id).await.unwrap().unwrap();
+    assert_eq!(recent.status, "new");
+    assert!(recent.note_history.is_empty());
+    let acked = alerts::get_by_id(&pool, &acked.id).await.unwrap().unwrap();
+    assert_eq!(acked.status, "resolved");
+    // Rows with a NULL last_seen don't decode into an Alert; read the status directly
+    for (id, expected) in [(&unseen.id, "resolved"), (&fresh.id, "new")] {
+        let (status,): (String,) = sqlx::query_as("SELECT status FROM alerts WHERE id = $1")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status, expected);
+    }
+    let dismissed = alerts::get_by_id(&pool, &dismissed.id).await.unwrap().unwrap();
+    assert_eq!(dismissed.status, "false_positive");
+
+    // Already resolved; a huge threshold matches nothing
+    assert_eq!(alerts::auto_resolve_stale(&pool, day).await.unwrap(), 0);
+    let forever = std::time::Duration::from_secs(u64::MAX);
+    assert_eq!(alerts::auto_resolve_stale(&pool, forever).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_alert_update_notes() {
+    let pool = setup().await;
+    let mut alert = Alert::new("Notes test".into(), "suricata".into(), "fp-notes".into());
+    alerts::insert(&pool, &alert).await.unwrap();
+
+    alert.notes = Some("Benign scanner on the guest VLAN".into());
+    alert.status = AlertStatus::FalsePositive.as_str().into();
+    assert!(alerts::update(&pool, &alert).await.unwrap());
+
+    let fetched = alerts::get_by_id(&pool, &alert.id).await.unwrap().unwrap();
+    assert_eq!(fetched.notes.as_deref(), Some("Benign scanner on the guest VLAN"));
+    assert_eq!(fetched.status, "false_positive");
+}
+
+#[tokio::test]
+async fn test_alert_add_notes_in_order() {
+    let pool = setup().await;
+    let alert = Alert::new("History test".into(), "suricata".into(), "fp-history".into());
+    alerts::insert(&pool, &alert).await.unwrap();
+
+    let first = alerts::add_note(&pool, &alert.id, "alice", "Looking into it").await.unwrap().unwrap();
+    assert_eq!(first.note_history.len(), 1);
+    let updated = alerts::add_note(&pool, &alert.id, "bob", "Confirmed, host isolated")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.id, alert.id);
+    let history: Vec<(i64, &str, &str)> = updated
+        .note_history
+        .iter()
+        .map(|n| (n.seq, n.author.as_str(), n.text.as_str()))
+        .collect();
+    assert_eq!(
+        history,
+        vec![(1, "alice", "Looking into it"), (2, "bob", "Confirmed, host isolated")]
+    );
+
+    let listed = alerts::list_notes(&pool, &alert.id).await.unwrap();
+    assert_eq!(listed, updated.note_history);
+    let loaded = alerts::get_with_notes(&pool, &alert.id).await.unwrap().unwrap();
+    assert_eq!(loaded.note_history, updated.note_history);
+
+    assert!(alerts::add_note(&pool, "missing", "alice", "x").await.unwrap().is_none());
+    // Notes go with their alert
+    alerts::delete(&pool, &alert.id).await.unwrap();
+    assert!(alerts::list_notes(&pool, &alert.id).await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_alert_delete() {
     let pool = setup().await;
@@ -472,6 +688,11 @@ async fn test_update_nonexistent_returns_false() {
     fake_device.id = fake_id.clone();
     assert!(!devices::update(&pool, &fake_device).await.unwrap());
 
+    // alerts::update with non-existent ID
+    let mut fake_alert = Alert::new("Fake".into(), "suricata".into(), "fp-fake".into());
+    fake_alert.id = fake_id.clone();
+    assert!(!alerts::update(&pool, &fake_alert).await.unwrap());
+
     // alerts::update_status with non-existent ID
     assert!(!alerts::update_status(&pool, &fake_id, "resolved", "2025-01-01T00:00:00Z").await.unwrap());
 
@@ -554,3 +775,291 @@ async fn test_vulnerability_device_set_null() {
     assert!(fetched.device_id.is_none(), "device_id should be NULL after device deletion (ON DELETE SET NULL)");
     assert_eq!(fetched.title, "FK test");
 }
+
+// ============================================================
+// Repo functions run on any executor, including transactions
+// ============================================================
+
+#[tokio::test]
+async fn test_repo_in_transaction_rolls_back() {
+    let pool = setup().await;
+
+    let device = Device::new("10.0.0.80".into());
+    let mut tx = pool.begin().await.unwrap();
+    devices::insert(&mut *tx, &device).await.unwrap();
+    assert!(devices::get_by_id(&mut *tx, &device.id).await.unwrap().is_some());
+    tx.rollback().await.unwrap();
+
+    assert!(devices::get_by_id(&pool, &device.id).await.unwrap().is_none());
+}
+
+// ============================================================
+// Cursor pagination
+// ============================================================
+
+/// Follow `next_cursor` until the last page, collecting ids in page order.
+async fn walk_device_pages(pool: &DbPool, limit: i64) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = devices::list_paged(pool, cursor.as_deref(), limit).await.unwrap();
+        assert!(page.items.len() as i64 <= limit);
+        ids.extend(page.items.into_iter().map(|d| d.id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return ids,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_devices_paged_walk_without_gaps() {
+    let pool = setup().await;
+
+    // Several devices share a first_seen so the id tiebreak is exercised
+    let mut expected = Vec::new();
+    for i in 0..23 {
+        let mut device = Device::new(format!("10.1.0.{i}"));
+        device.first_seen = format!("2024-01-15T10:00:{:02}Z", i / 3);
+        devices::insert(&pool, &device).await.unwrap();
+        expected.push(device.id);
+    }
+
+    for limit in [1, 5, 10, 23, 50] {
+        let ids = walk_device_pages(&pool, limit).await;
+        assert_eq!(ids.len(), 23, "limit {limit}");
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), 23, "duplicates at limit {limit}");
+        assert!(expected.iter().all(|id| unique.contains(id)));
+    }
+
+    // Newest first
+    let first = devices::list_paged(&pool, None, 2).await.unwrap();
+    assert!(first.items.iter().all(|d| d.first_seen == "2024-01-15T10:00:07Z"));
+}
+
+#[tokio::test]
+async fn test_paged_stable_under_concurrent_inserts() {
+    let pool = setup().await;
+
+    for i in 0..10 {
+        let mut alert = Alert::new(format!("Alert {i}"), "suricata".into(), format!("fp-{i}"));
+        alert.created_at = format!("2024-01-15T10:00:{i:02}Z");
+        alerts::insert(&pool, &alert).await.unwrap();
+    }
+
+    let page1 = alerts::list_paged(&pool, None, 4).await.unwrap();
+    assert_eq!(page1.items.len(), 4);
+
+    // A newer alert arriving mid-walk must not shift the remaining pages
+    let mut newer = Alert::new("Newer".into(), "suricata".into(), "fp-new".into());
+    newer.created_at = "2024-01-15T11:00:00Z".into();
+    alerts::insert(&pool, &newer).await.unwrap();
+
+    let mut seen: Vec<String> = page1.items.iter().map(|a| a.title.clone()).collect();
+    let mut cursor = page1.next_cursor;
+    while let Some(after) = cursor {
+        let page = alerts::list_paged(&pool, Some(&after), 4).await.unwrap();
+        seen.extend(page.items.iter().map(|a| a.title.clone()));
+        cursor = page.next_cursor;
+    }
+    let expected: Vec<String> = (0..10).rev().map(|i| format!("Alert {i}")).collect();
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn test_scans_paged() {
+    let pool = setup().await;
+
+    for i in 0..5 {
+        let mut scan = Scan::new("nmap".into(), format!("10.2.0.{i}"), ScanType::Discovery);
+        scan.created_at = format!("2024-01-15T10:00:0{i}Z");
+        scans::insert(&pool, &scan).await.unwrap();
+    }
+
+    let page1 = scans::list_paged(&pool, None, 3).await.unwrap();
+    let targets: Vec<_> = page1.items.iter().map(|s| s.target.as_str()).collect();
+    assert_eq!(targets, ["10.2.0.4", "10.2.0.3", "10.2.0.2"]);
+    let page2 = scans::list_paged(&pool, page1.next_cursor.as_deref(), 3).await.unwrap();
+    let targets: Vec<_> = page2.items.iter().map(|s| s.target.as_str()).collect();
+    assert_eq!(targets, ["10.2.0.1", "10.2.0.0"]);
+    assert!(page2.next_cursor.is_none());
+
+    // An exact final page has no cursor either
+    let all = scans::list_paged(&pool, None, 5).await.unwrap();
+    assert_eq!(all.items.len(), 5);
+    assert!(all.next_cursor.is_none());
+
+    let empty = scans::list_paged(&pool, Some("missing"), 3).await.unwrap();
+    assert!(empty.items.is_empty());
+    assert!(empty.next_cursor.is_none());
+}
+
+// ============================================================
+// Bulk upserts
+// ============================================================
+
+#[tokio::test]
+async fn test_ports_bulk_upsert_many_rows() {
+    let pool = setup().await;
+    let device = Device::new("10.3.0.1".into());
+    devices::insert(&pool, &device).await.unwrap();
+
+    let mut batch: Vec<Port> = (1..=500)
+        .map(|n| Port::new(device.id.clone(), n, "tcp".into()))
+        .collect();
+    batch[0].state = "open".into();
+    batch[0].http_title = Some("Admin".into());
+    ports::bulk_upsert(&pool, &batch).await.unwrap();
+
+    let stored = ports::list_by_device(&pool, &device.id).await.unwrap();
+    assert_eq!(stored.len(), 500);
+
+    // A conflicting row updates in place; a missing title keeps the old one
+    let mut changed = Port::new(device.id.clone(), 1, "tcp".into());
+    changed.state = "closed".into();
+    changed.service_name = Some("tcpmux".into());
+    let udp = Port::new(device.id.clone(), 1, "udp".into());
+    ports::bulk_upsert(&pool, &[changed, udp]).await.unwrap();
+
+    let port1 = ports::get_by_device_port_proto(&pool, &device.id, 1, "tcp")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(port1.id, batch[0].id);
+    assert_eq!(port1.state, "closed");
+    assert_eq!(port1.service_name.as_deref(), Some("tcpmux"));
+    assert_eq!(port1.http_title.as_deref(), Some("Admin"));
+    assert_eq!(ports::list_by_device(&pool, &device.id).await.unwrap().len(), 501);
+}
+
+#[tokio::test]
+async fn test_ports_bulk_upsert_repeated_key_last_wins() {
+    let pool = setup().await;
+    let device = Device::new("10.3.0.2".into());
+    devices::insert(&pool, &device).await.unwrap();
+
+    let mut first = Port::new(device.id.clone(), 22, "tcp".into());
+    first.state = "filtered".into();
+    let mut second = Port::new(device.id.clone(), 22, "tcp".into());
+    second.state = "open".into();
+    ports::bulk_upsert(&pool, &[first, second]).await.unwrap();
+
+    let stored = ports::list_by_device(&pool, &device.id).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].state, "open");
+}
+
+#[tokio::test]
+async fn test_devices_bulk_upsert() {
+    let pool = setup().await;
+
+    let mut batch: Vec<Device> = (0..300)
+        .map(|i| Device::new(format!("10.4.{}.{}", i / 256, i % 256)))
+        .collect();
+    devices::bulk_upsert(&pool, &batch).await.unwrap();
+    assert_eq!(devices::count(&pool).await.unwrap(), 300);
+
+    let first_seen = batch[7].first_seen.clone();
+    batch[7].hostname = Some("nas.local".into());
+    batch[7].first_seen = "2099-01-01T00:00:00Z".into();
+    batch[7].last_seen = "2099-01-01T00:00:00Z".into();
+    devices::bulk_upsert(&pool, &batch[7..8]).await.unwrap();
+
+    let updated = devices::get_by_id(&pool, &batch[7].id).await.unwrap().unwrap();
+    assert_eq!(updated.hostname.as_deref(), Some("nas.local"));
+    assert_eq!(updated.last_seen, "2099-01-01T00:00:00Z");
+    assert_eq!(updated.first_seen, first_seen);
+    assert_eq!(devices::count(&pool).await.unwrap(), 300);
+
+    let ips = vec!["10.4.0.7".to_string(), "10.4.1.0".to_string(), "192.0.2.1".to_string()];
+    let found = devices::list_by_ips(&pool, &ips).await.unwrap();
+    assert_eq!(found.len(), 2);
+}
+
+// ============================================================
+// Filtered alert queries
+// ============================================================
+
+/// Alerts `a0`..`a5` over three devices, severities, statuses and times.
+async fn seed_filter_alerts(pool: &DbPool) {
+    let rows = [
+        ("a0", "critical", "new", "10.5.0.1", "2024-01-01T00:00:00Z"),
+        ("a1", "high", "new", "10.5.0.1", "2024-01-02T00:00:00Z"),
+        ("a2", "high", "acknowledged", "10.5.0.2", "2024-01-03T00:00:00Z"),
+        ("a3", "low", "resolved", "10.5.0.2", "2024-01-04T00:00:00Z"),
+        ("a4", "high", "new", "10.5.0.3", "2024-01-05T00:00:00Z"),
+        ("a5", "info", "false_positive", "10.5.0.1", "2024-01-06T00:00:00Z"),
+    ];
+    for (title, severity, status, ip, created_at) in rows {
+        let mut alert = Alert::new(title.into(), "suricata".into(), format!("fp-{title}"));
+        alert.severity = severity.into();
+        alert.status = status.into();
+        alert.device_ip = Some(ip.into());
+        alert.created_at = created_at.into();
+        alerts::insert(pool, &alert).await.unwrap();
+    }
+}
+
+async fn titles(pool: &DbPool, filter: AlertFilter) -> Vec<String> {
+    alerts::query(pool, &filter).await.unwrap().into_iter().map(|a| a.title).collect()
+}
+
+#[tokio::test]
+async fn test_alert_query_single_filters() {
+    let pool = setup().await;
+    seed_filter_alerts(&pool).await;
+
+    assert_eq!(titles(&pool, AlertFilter::default()).await.len(), 6);
+
+    let by_severity = AlertFilter { severity: Some(Severity::High), ..Default::default() };
+    assert_eq!(titles(&pool, by_severity).await, ["a4", "a2", "a1"]);
+
+    let at_least = AlertFilter { min_severity: Some(Severity::High), ..Default::default() };
+    assert_eq!(titles(&pool, at_least).await, ["a4", "a2", "a1", "a0"]);
+
+    let by_status = AlertFilter { status: Some(AlertStatus::New), ..Default::default() };
+    assert_eq!(titles(&pool, by_status).await, ["a4", "a1", "a0"]);
+
+    let by_ip = AlertFilter { device_ip: Some("10.5.0.2".into()), ..Default::default() };
+    assert_eq!(titles(&pool, by_ip).await, ["a3", "a2"]);
+
+    let since = AlertFilter { since: Some("2024-01-05T00:00:00Z".into()), ..Default::default() };
+    assert_eq!(titles(&pool, since).await, ["a5", "a4"]);
+
+    let until = AlertFilter { until: Some("2024-01-02T00:00:00Z".into()), ..Default::default() };
+    assert_eq!(titles(&pool, until).await, ["a0"]);
+
+    let limited = AlertFilter { limit: Some(2), ..Default::default() };
+    assert_eq!(titles(&pool, limited).await, ["a5", "a4"]);
+}
+
+#[tokio::test]
+async fn test_alert_query_combined_filters() {
+    let pool = setup().await;
+    seed_filter_alerts(&pool).await;
+
+    let filter = AlertFilter {
+        severity: Some(Severity::High),
+        status: Some(AlertStatus::New),
+        since: Some("2024-01-02T00:00:00Z".into()),
+        until: Some("2024-01-05T00:00:00Z".into()),
+        ..Default::default()
+    };
+    assert_eq!(titles(&pool, filter).await, ["a1"]);
+
+    let filter = AlertFilter {
+        device_ip: Some("10.5.0.1".into()),
+        status: Some(AlertStatus::New),
+        limit: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(titles(&pool, filter).await, ["a1"]);
+
+    let none = AlertFilter {
+        severity: Some(Severity::Critical),
+        device_ip: Some("10.5.0.3".into()),
+        ..Default::default()
+    };
+    assert!(titles(&pool, none).await.is_empty());
+}
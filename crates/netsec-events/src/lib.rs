@@ -1,51 +1,311 @@
 //! Event bus built on tokio::broadcast for inter-component communication.
 //!
-//! Supports typed events, filtered subscriptions, and async consumption.
+//! Supports typed events, filtered subscriptions, and async consumption,
+//! either through `recv` or as a [`Stream`].
+//!
+//! Every published event gets the next sequence number of its bus, so a
+//! consumer can order events and spot gaps after reconnecting.
 
 use netsec_models::event::{EventType, NetsecEvent};
-use std::collections::HashSet;
+use netsec_models::time::now_rfc3339;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 
 /// Default capacity of the broadcast channel.
 const DEFAULT_CAPACITY: usize = 1024;
 
+/// Default number of events [`EventBus::publish_or_buffer`] holds while
+/// nobody is subscribed.
+const DEFAULT_PENDING_LIMIT: usize = 256;
+
+/// How long [`EventBus::drain`] waits for the backlog to shrink before it
+/// treats the remaining subscribers as idle.
+const DRAIN_STALL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Bounded buffer of the most recently published events.
+struct ReplayBuffer {
+    events: VecDeque<NetsecEvent>,
+    len: usize,
+}
+
 /// The central event bus for the netsec platform.
 #[derive(Clone)]
 pub struct EventBus {
     sender: broadcast::Sender<NetsecEvent>,
+    replay: Option<Arc<Mutex<ReplayBuffer>>>,
+    pending: Arc<Mutex<PendingQueue>>,
+    stats: Arc<Mutex<HashMap<EventType, u64>>>,
+    /// Sequence number of the last published event. Held while an event is
+    /// numbered and sent, so receivers see numbers in order.
+    sequence: Arc<Mutex<u64>>,
+}
+
+/// Events published with no subscribers, waiting for the next one.
+struct PendingQueue {
+    events: VecDeque<NetsecEvent>,
+    limit: usize,
+}
+
+impl PendingQueue {
+    fn new(limit: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            limit,
+        }
+    }
 }
 
 impl EventBus {
     /// Create a new event bus with default capacity.
     pub fn new() -> Self {
-        let (sender, _) = broadcast::channel(DEFAULT_CAPACITY);
-        Self { sender }
+        Self::with_capacity(DEFAULT_CAPACITY)
     }
 
     /// Create a new event bus with a custom channel capacity.
     pub fn with_capacity(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { sender }
+        Self {
+            sender,
+            replay: None,
+            pending: Arc::new(Mutex::new(PendingQueue::new(DEFAULT_PENDING_LIMIT))),
+            stats: Arc::default(),
+            sequence: Arc::default(),
+        }
+    }
+
+    /// Create a new event bus that also keeps the last `replay_len` events
+    /// for late subscribers (see [`subscribe_with_history`](Self::subscribe_with_history)).
+    pub fn with_replay(capacity: usize, replay_len: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            replay: Some(Arc::new(Mutex::new(ReplayBuffer {
+                events: VecDeque::with_capacity(replay_len),
+                len: replay_len,
+            }))),
+            pending: Arc::new(Mutex::new(PendingQueue::new(DEFAULT_PENDING_LIMIT))),
+            stats: Arc::default(),
+            sequence: Arc::default(),
+        }
+    }
+
+    /// Set how many events [`publish_or_buffer`](Self::publish_or_buffer)
+    /// holds while nobody is subscribed; the oldest are dropped beyond that.
+    pub fn with_pending_limit(self, limit: usize) -> Self {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).limit = limit;
+        self
     }
 
     /// Publish an event to all subscribers.
+    ///
+    /// The event is given the bus's next sequence number, replacing any it
+    /// had, and the current time if its timestamp is empty. On a replay bus
+    /// the event is buffered even when nobody is subscribed.
     pub fn publish(
         &self,
         event: NetsecEvent,
     ) -> Result<usize, broadcast::error::SendError<NetsecEvent>> {
-        self.sender.send(event)
+        self.send(event, true)
+    }
+
+    /// Number and send an event. Unless `keep_unsent`, an event nobody
+    /// received leaves no trace: it isn't counted, numbered or replayed.
+    fn send(
+        &self,
+        mut event: NetsecEvent,
+        keep_unsent: bool,
+    ) -> Result<usize, broadcast::error::SendError<NetsecEvent>> {
+        let mut sequence = self.sequence.lock().unwrap_or_else(|e| e.into_inner());
+        event.sequence = *sequence + 1;
+        if event.timestamp.is_empty() {
+            event.timestamp = now_rfc3339();
+        }
+
+        // Buffer and send under the lock so a concurrent
+        // `subscribe_with_history` sees each event exactly once.
+        let mut buffer = self
+            .replay
+            .as_ref()
+            .map(|replay| replay.lock().unwrap_or_else(|e| e.into_inner()));
+        let replayed = buffer.as_ref().is_some_and(|b| b.len > 0).then(|| event.clone());
+        let event_type = event.event_type.clone();
+        let result = self.sender.send(event);
+        if result.is_err() && !keep_unsent {
+            return result;
+        }
+
+        *sequence += 1;
+        *self
+            .stats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(event_type)
+            .or_insert(0) += 1;
+        if let (Some(buffer), Some(event)) = (buffer.as_mut(), replayed) {
+            if buffer.events.len() == buffer.len {
+                buffer.events.pop_front();
+            }
+            buffer.events.push_back(event);
+        }
+        result
+    }
+
+    /// Sequence number of the last event published on this bus (or any of
+    /// its clones), or 0 if there was none yet.
+    pub fn last_sequence(&self) -> u64 {
+        *self.sequence.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Publish an event, holding it for the next subscriber if there are none.
+    ///
+    /// Returns the number of receivers reached, or 0 if the event was
+    /// buffered. Buffered events are delivered when the next subscription of
+    /// any kind is created, and only counted and numbered then.
+    pub fn publish_or_buffer(&self, event: NetsecEvent) -> usize {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let event = match self.send(event, false) {
+            Ok(count) => return count,
+            Err(broadcast::error::SendError(event)) => event,
+        };
+        if pending.limit > 0 {
+            if pending.events.len() >= pending.limit {
+                pending.events.pop_front();
+            }
+            pending.events.push_back(event);
+        }
+        0
+    }
+
+    /// Number of events published so far, per event type.
+    ///
+    /// Counts every call to [`publish`](Self::publish), whether or not any
+    /// subscriber received the event. Shared by all clones of the bus.
+    pub fn stats(&self) -> HashMap<EventType, u64> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Reset all publish counters to zero.
+    pub fn reset_stats(&self) {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Number of events waiting for a subscriber.
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).events.len()
+    }
+
+    /// Wait until every subscriber has received the events already sent, for
+    /// at most `timeout`, then discard events still waiting for a
+    /// subscriber. Returns `true` if the subscribers caught up in time.
+    ///
+    /// Meant for shutdown: publishing can continue meanwhile, but the wait
+    /// only ends once the channel is momentarily empty. Only subscribers that
+    /// are consuming are waited for: once the backlog stops shrinking for a
+    /// short while (e.g. a subscription nobody reads), the wait gives up.
+    pub async fn drain(&self, timeout: std::time::Duration) -> bool {
+        let caught_up = tokio::time::timeout(timeout, async {
+            let mut backlog = self.sender.len();
+            let mut progressed = tokio::time::Instant::now();
+            while backlog > 0 && self.sender.receiver_count() > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                let remaining = self.sender.len();
+                if remaining < backlog {
+                    progressed = tokio::time::Instant::now();
+                } else if progressed.elapsed() >= DRAIN_STALL {
+                    return false;
+                }
+                backlog = remaining;
+            }
+            true
+        })
+        .await
+        .unwrap_or(false);
+        let dropped = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            std::mem::take(&mut pending.events).len()
+        };
+        if dropped > 0 {
+            tracing::debug!("Discarded {dropped} events that never had a subscriber");
+        }
+        caught_up
+    }
+
+    /// Create a receiver and flush pending events to it.
+    fn receiver(&self) -> broadcast::Receiver<NetsecEvent> {
+        let receiver = self.sender.subscribe();
+        self.flush_pending();
+        receiver
+    }
+
+    fn flush_pending(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        while let Some(event) = pending.events.pop_front() {
+            let _ = self.publish(event);
+        }
+    }
+
+    /// Subscribe to all events, also returning the buffered recent events,
+    /// oldest first.
+    ///
+    /// Every event is in exactly one of the snapshot or the live receiver.
+    /// Without a replay buffer the snapshot is empty.
+    pub fn subscribe_with_history(
+        &self,
+    ) -> (Vec<NetsecEvent>, broadcast::Receiver<NetsecEvent>) {
+        let Some(replay) = &self.replay else {
+            return (Vec::new(), self.receiver());
+        };
+
+        let (history, receiver) = {
+            let buffer = replay.lock().unwrap_or_else(|e| e.into_inner());
+            (buffer.events.iter().cloned().collect(), self.sender.subscribe())
+        };
+        // Pending events go out live, after the snapshot was taken
+        self.flush_pending();
+        (history, receiver)
     }
 
     /// Subscribe to all events.
     pub fn subscribe(&self) -> broadcast::Receiver<NetsecEvent> {
-        self.sender.subscribe()
+        self.receiver()
     }
 
     /// Subscribe to only specific event types.
     pub fn subscribe_filtered(&self, types: Vec<EventType>) -> FilteredSubscriber {
         FilteredSubscriber {
-            receiver: self.sender.subscribe(),
-            filter: types.into_iter().collect(),
+            receiver: self.receiver(),
+            filter: FilterMode::Include(types.into_iter().collect()),
+        }
+    }
+
+    /// Subscribe to every event type except the given ones.
+    pub fn subscribe_excluding(&self, types: Vec<EventType>) -> FilteredSubscriber {
+        FilteredSubscriber {
+            receiver: self.receiver(),
+            filter: FilterMode::Exclude(types.into_iter().collect()),
+        }
+    }
+
+    /// Subscribe to all events as a [`Stream`].
+    ///
+    /// Events dropped because the consumer lagged are skipped. The stream ends
+    /// when every sender (all clones of the bus) has been dropped.
+    pub fn subscribe_stream(&self) -> impl Stream<Item = NetsecEvent> {
+        BroadcastStream::new(self.receiver()).filter_map(Result::ok)
+    }
+
+    /// Subscribe to events accepted by an arbitrary predicate, e.g. on payload
+    /// contents.
+    pub fn subscribe_where<F>(&self, predicate: F) -> PredicateSubscriber
+    where
+        F: Fn(&NetsecEvent) -> bool + Send + 'static,
+    {
+        PredicateSubscriber {
+            receiver: self.receiver(),
+            predicate: Box::new(predicate),
         }
     }
 
@@ -61,10 +321,29 @@ impl Default for EventBus {
     }
 }
 
-/// A subscriber that only yields events matching a set of [`EventType`]s.
+/// How a [`FilteredSubscriber`] selects events by [`EventType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterMode {
+    /// Only events whose type is in the set.
+    Include(HashSet<EventType>),
+    /// All events whose type is not in the set.
+    Exclude(HashSet<EventType>),
+}
+
+impl FilterMode {
+    /// Whether an event of the given type passes the filter.
+    pub fn matches(&self, event_type: &EventType) -> bool {
+        match self {
+            Self::Include(types) => types.contains(event_type),
+            Self::Exclude(types) => !types.contains(event_type),
+        }
+    }
+}
+
+/// A subscriber that only yields events matching a [`FilterMode`].
 pub struct FilteredSubscriber {
     receiver: broadcast::Receiver<NetsecEvent>,
-    filter: HashSet<EventType>,
+    filter: FilterMode,
 }
 
 impl FilteredSubscriber {
@@ -74,7 +353,62 @@ impl FilteredSubscriber {
     pub async fn recv(&mut self) -> Result<NetsecEvent, broadcast::error::RecvError> {
         loop {
             let event = self.receiver.recv().await?;
-            if self.filter.contains(&event.event_type) {
+            if self.filter.matches(&event.event_type) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Receive the next matching event together with the number of events
+    /// the channel dropped because this subscriber lagged behind.
+    ///
+    /// Lag is accumulated while waiting, so drops reported by the channel
+    /// before the next match are not lost. The count covers all dropped
+    /// events, matching or not, since their types are unknown.
+    pub async fn recv_with_lag(
+        &mut self,
+    ) -> Result<(NetsecEvent, u64), broadcast::error::RecvError> {
+        let mut lagged = 0;
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event.event_type) => {
+                    return Ok((event, lagged));
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => lagged += n,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Convert into a [`Stream`] of matching events.
+    ///
+    /// Events dropped because the consumer lagged are skipped. The stream ends
+    /// when every sender (all clones of the bus) has been dropped.
+    pub fn into_stream(self) -> impl Stream<Item = NetsecEvent> {
+        let filter = self.filter;
+        BroadcastStream::new(self.receiver)
+            .filter_map(move |result| result.ok().filter(|e| filter.matches(&e.event_type)))
+    }
+}
+
+/// Boxed event predicate used by [`PredicateSubscriber`].
+pub type EventPredicate = Box<dyn Fn(&NetsecEvent) -> bool + Send>;
+
+/// A subscriber that only yields events accepted by a predicate.
+pub struct PredicateSubscriber {
+    receiver: broadcast::Receiver<NetsecEvent>,
+    predicate: EventPredicate,
+}
+
+impl PredicateSubscriber {
+    /// Receive the next event accepted by the predicate.
+    ///
+    /// Events that are rejected are silently skipped.
+    pub async fn recv(&mut self) -> Result<NetsecEvent, broadcast::error::RecvError> {
+        loop {
+            let event = self.receiver.recv().await?;
+            if (self.predicate)(&event) {
                 return Ok(event);
             }
         }
@@ -141,6 +475,335 @@ mod tests {
         assert_eq!(received.id, match_event.id);
     }
 
+    #[tokio::test]
+    async fn test_excluding_subscriber_skips_excluded_types() {
+        let bus = EventBus::new();
+        let mut filtered = bus.subscribe_excluding(vec![EventType::SystemHealth]);
+
+        bus.publish(NetsecEvent::new(
+            EventType::SystemHealth,
+            serde_json::json!({"status": "ok"}),
+        ))
+        .unwrap();
+        let unlisted = NetsecEvent::new(
+            EventType::DeviceDiscovered,
+            serde_json::json!({"ip": "10.0.0.1"}),
+        );
+        bus.publish(unlisted.clone()).unwrap();
+
+        let received = filtered.recv().await.unwrap();
+        assert_eq!(received.event_type, EventType::DeviceDiscovered);
+        assert_eq!(received.id, unlisted.id);
+    }
+
+    #[tokio::test]
+    async fn test_predicate_subscriber_filters_on_payload() {
+        let bus = EventBus::new();
+        let mut critical = bus.subscribe_where(|event| {
+            event.event_type == EventType::AlertCreated
+                && event.payload["severity"] == "critical"
+        });
+
+        bus.publish(NetsecEvent::new(
+            EventType::AlertCreated,
+            serde_json::json!({"severity": "low"}),
+        ))
+        .unwrap();
+        bus.publish(NetsecEvent::new(
+            EventType::AlertUpdated,
+            serde_json::json!({"severity": "critical"}),
+        ))
+        .unwrap();
+        let target = NetsecEvent::new(
+            EventType::AlertCreated,
+            serde_json::json!({"severity": "critical"}),
+        );
+        bus.publish(target.clone()).unwrap();
+
+        let received = critical.recv().await.unwrap();
+        assert_eq!(received.id, target.id);
+
+        // Nothing else was accepted
+        drop(bus);
+        assert!(critical.recv().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recv_with_lag_reports_dropped_events() {
+        let bus = EventBus::with_capacity(2);
+        let mut filtered = bus.subscribe_filtered(vec![EventType::ScanCompleted]);
+
+        // Overflow the channel before the subscriber reads anything
+        for _ in 0..5 {
+            bus.publish(NetsecEvent::new(
+                EventType::ScanStarted,
+                serde_json::json!({}),
+            ))
+            .unwrap();
+        }
+        let last = NetsecEvent::new(EventType::ScanCompleted, serde_json::json!({}));
+        bus.publish(last.clone()).unwrap();
+
+        let (received, lagged) = filtered.recv_with_lag().await.unwrap();
+        assert_eq!(received.id, last.id);
+        assert!(lagged > 0);
+
+        // Caught up: no further lag reported
+        bus.publish(NetsecEvent::new(
+            EventType::ScanCompleted,
+            serde_json::json!({}),
+        ))
+        .unwrap();
+        let (_, lagged) = filtered.recv_with_lag().await.unwrap();
+        assert_eq!(lagged, 0);
+    }
+
+    #[tokio::test]
+    async fn test_history_snapshot_then_live() {
+        let bus = EventBus::with_replay(16, 8);
+        let first = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+        let second = NetsecEvent::new(EventType::ScanCompleted, serde_json::json!({}));
+        // Buffered even with no subscribers
+        assert!(bus.publish(first.clone()).is_err());
+        assert!(bus.publish(second.clone()).is_err());
+
+        let (history, mut rx) = bus.subscribe_with_history();
+        let ids: Vec<&str> = history.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec![first.id.as_str(), second.id.as_str()]);
+
+        // Later events arrive live only
+        let third = NetsecEvent::new(EventType::AlertCreated, serde_json::json!({}));
+        bus.publish(third.clone()).unwrap();
+        assert_eq!(rx.recv().await.unwrap().id, third.id);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_history_evicts_oldest() {
+        let bus = EventBus::with_replay(16, 3);
+        let events: Vec<NetsecEvent> = (0..5)
+            .map(|i| NetsecEvent::new(EventType::DeviceDiscovered, serde_json::json!({"n": i})))
+            .collect();
+        for event in &events {
+            let _ = bus.publish(event.clone());
+        }
+
+        let (history, _rx) = bus.subscribe_with_history();
+        let ns: Vec<i64> = history.iter().map(|e| e.payload["n"].as_i64().unwrap()).collect();
+        assert_eq!(ns, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_history_empty_without_replay() {
+        let bus = EventBus::new();
+        let _ = bus.publish(NetsecEvent::new(EventType::ScanStarted, serde_json::json!({})));
+        let (history, _rx) = bus.subscribe_with_history();
+        assert!(history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_stream_take() {
+        let bus = EventBus::new();
+        let stream = bus
+            .subscribe_filtered(vec![EventType::DeviceDiscovered])
+            .into_stream();
+
+        for i in 0..4 {
+            bus.publish(NetsecEvent::new(
+                EventType::DeviceDiscovered,
+                serde_json::json!({"n": i}),
+            ))
+            .unwrap();
+            bus.publish(NetsecEvent::new(
+                EventType::ScanStarted,
+                serde_json::json!({}),
+            ))
+            .unwrap();
+        }
+
+        let events: Vec<NetsecEvent> = stream.take(3).collect().await;
+        let ns: Vec<i64> = events.iter().map(|e| e.payload["n"].as_i64().unwrap()).collect();
+        assert_eq!(ns, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_when_bus_dropped() {
+        let bus = EventBus::new();
+        let stream = bus.subscribe_stream();
+        bus.publish(NetsecEvent::new(EventType::ScanStarted, serde_json::json!({})))
+            .unwrap();
+        drop(bus);
+
+        let events: Vec<NetsecEvent> = stream.collect().await;
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_or_buffer_flushes_to_next_subscriber() {
+        let bus = EventBus::new();
+        let first = NetsecEvent::new(EventType::DeviceDiscovered, serde_json::json!({"n": 1}));
+        let second = NetsecEvent::new(EventType::DeviceDiscovered, serde_json::json!({"n": 2}));
+        assert_eq!(bus.publish_or_buffer(first.clone()), 0);
+        assert_eq!(bus.publish_or_buffer(second.clone()), 0);
+        assert_eq!(bus.pending_count(), 2);
+
+        let mut rx = bus.subscribe();
+        assert_eq!(bus.pending_count(), 0);
+        assert_eq!(rx.recv().await.unwrap().id, first.id);
+        assert_eq!(rx.recv().await.unwrap().id, second.id);
+
+        // With a subscriber, events go straight out
+        let third = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+        assert_eq!(bus.publish_or_buffer(third.clone()), 1);
+        assert_eq!(rx.recv().await.unwrap().id, third.id);
+    }
+
+    #[tokio::test]
+    async fn test_publish_or_buffer_counts_buffered_event_once() {
+        let bus = EventBus::with_replay(16, 16);
+        // A subscriber that goes away leaves the send to fail
+        drop(bus.subscribe());
+        let event = NetsecEvent::new(EventType::DeviceDiscovered, serde_json::json!({}));
+        assert_eq!(bus.publish_or_buffer(event.clone()), 0);
+        assert_eq!(bus.pending_count(), 1);
+        assert_eq!(bus.last_sequence(), 0);
+        assert!(bus.stats().is_empty());
+
+        let (history, mut rx) = bus.subscribe_with_history();
+        assert!(history.is_empty());
+        let received = rx.recv().await.unwrap();
+        assert_eq!(received.id, event.id);
+        assert_eq!(received.sequence, 1);
+        assert_eq!(bus.last_sequence(), 1);
+        assert_eq!(bus.stats()[&EventType::DeviceDiscovered], 1);
+        assert_eq!(bus.subscribe_with_history().0.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_or_buffer_limit_drops_oldest() {
+        let bus = EventBus::new().with_pending_limit(2);
+        for i in 0..4 {
+            bus.publish_or_buffer(NetsecEvent::new(
+                EventType::DeviceDiscovered,
+                serde_json::json!({"n": i}),
+            ));
+        }
+        assert_eq!(bus.pending_count(), 2);
+
+        let mut filtered = bus.subscribe_filtered(vec![EventType::DeviceDiscovered]);
+        assert_eq!(filtered.recv().await.unwrap().payload["n"], 2);
+        assert_eq!(filtered.recv().await.unwrap().payload["n"], 3);
+    }
+
+    #[test]
+    fn test_publish_stats() {
+        let bus = EventBus::new();
+        let _rx = bus.subscribe();
+        let clone = bus.clone();
+        for event_type in [
+            EventType::AlertCreated,
+            EventType::ScanStarted,
+            EventType::AlertCreated,
+            EventType::DeviceDiscovered,
+            EventType::AlertCreated,
+        ] {
+            clone
+                .publish(NetsecEvent::new(event_type, serde_json::json!({})))
+                .unwrap();
+        }
+
+        // Clones share the counters
+        let stats = bus.stats();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[&EventType::AlertCreated], 3);
+        assert_eq!(stats[&EventType::ScanStarted], 1);
+        assert_eq!(stats[&EventType::DeviceDiscovered], 1);
+
+        clone.reset_stats();
+        assert!(bus.stats().is_empty());
+    }
+
+    #[test]
+    fn test_publish_assigns_sequence() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        assert_eq!(bus.last_sequence(), 0);
+
+        let clone = bus.clone();
+        for n in 1..=3 {
+            let event = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({"n": n}));
+            clone.publish(event).unwrap();
+            // Clones share the counter
+            assert_eq!(bus.last_sequence(), n);
+        }
+        let sequences: Vec<u64> = (0..3).map(|_| rx.try_recv().unwrap().sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3]);
+
+        // Republishing an event gives it a new number
+        let mut event = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+        event.sequence = 1;
+        bus.publish(event).unwrap();
+        assert_eq!(rx.try_recv().unwrap().sequence, 4);
+        assert_eq!(bus.last_sequence(), 4);
+    }
+
+    #[test]
+    fn test_concurrent_publishers_keep_sequence_order() {
+        let bus = EventBus::with_capacity(4096);
+        let mut rx = bus.subscribe();
+
+        let publishers: Vec<_> = (0..4)
+            .map(|_| {
+                let bus = bus.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..500 {
+                        let event = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+                        bus.publish(event).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for publisher in publishers {
+            publisher.join().unwrap();
+        }
+
+        let sequences: Vec<u64> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|event| event.sequence)
+            .collect();
+        assert_eq!(sequences, (1..=2000).collect::<Vec<u64>>());
+        assert_eq!(bus.last_sequence(), 2000);
+    }
+
+    #[test]
+    fn test_publish_fills_missing_timestamp() {
+        let bus = EventBus::with_replay(16, 4);
+        let mut event = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+        event.timestamp = String::new();
+        let _ = bus.publish(event);
+        let kept = NetsecEvent::new(EventType::ScanStarted, serde_json::json!({}));
+        let _ = bus.publish(kept.clone());
+
+        let (history, _rx) = bus.subscribe_with_history();
+        assert!(!history[0].timestamp.is_empty());
+        assert_eq!(history[0].sequence, 1);
+        assert_eq!(history[1].timestamp, kept.timestamp);
+        assert_eq!(history[1].sequence, 2);
+    }
+
+    #[test]
+    fn test_filter_mode_matches() {
+        let include = FilterMode::Include([EventType::AlertCreated].into_iter().collect());
+        assert!(include.matches(&EventType::AlertCreated));
+        assert!(!include.matches(&EventType::ScanStarted));
+
+        let exclude = FilterMode::Exclude([EventType::AlertCreated].into_iter().collect());
+        assert!(!exclude.matches(&EventType::AlertCreated));
+        assert!(exclude.matches(&EventType::ScanStarted));
+
+        // Excluding nothing passes everything
+        assert!(FilterMode::Exclude(HashSet::new()).matches(&EventType::SystemHealth));
+    }
+
     #[test]
     fn test_subscriber_count() {
         let bus = EventBus::new();
@@ -177,4 +840,35 @@ mod tests {
         let e2 = rx.recv().await.unwrap();
         assert_eq!(e2.event_type, EventType::ScanCompleted);
     }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_subscribers() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        for _ in 0..3 {
+            bus.publish(NetsecEvent::new(EventType::SystemHealth, serde_json::json!({})))
+                .unwrap();
+        }
+
+        // Nobody is reading yet: the stalled subscriber is given up on well
+        // before the timeout
+        let started = std::time::Instant::now();
+        assert!(!bus.drain(std::time::Duration::from_secs(10)).await);
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+
+        let reader = tokio::spawn(async move {
+            for _ in 0..3 {
+                rx.recv().await.unwrap();
+            }
+            rx
+        });
+        assert!(bus.drain(std::time::Duration::from_secs(5)).await);
+        drop(reader.await.unwrap());
+
+        // Events that never had a subscriber are discarded
+        bus.publish_or_buffer(NetsecEvent::new(EventType::SystemHealth, serde_json::json!({})));
+        assert_eq!(bus.pending_count(), 1);
+        assert!(bus.drain(std::time::Duration::from_millis(50)).await);
+        assert_eq!(bus.pending_count(), 0);
+    }
 }
@@ -10,6 +10,10 @@ pub struct ApiConfig {
     pub base_url: String,
     pub api_key: Option<String>,
     pub timeout_secs: u64,
+    /// Times a failed GET is retried; other methods are never retried.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further one.
+    pub retry_base_delay_ms: u64,
 }
 
 impl Default for ApiConfig {
@@ -18,10 +22,18 @@ impl Default for ApiConfig {
             base_url: "http://127.0.0.1:8420".to_string(),
             api_key: None,
             timeout_secs: 30,
+            max_retries: 2,
+            retry_base_delay_ms: 500,
         }
     }
 }
 
+/// Whether a request error is worth retrying: the request timed out or
+/// never reached the server.
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect()
+}
+
 /// HTTP client for the NetSec backend API.
 #[derive(Clone)]
 pub struct ApiClient {
@@ -58,26 +70,84 @@ impl ApiClient {
         format!("{}/api{}", self.config.base_url, path)
     }
 
+    /// Send an idempotent GET, retrying timeouts, connection failures and
+    /// 5xx responses with exponential backoff (`retry_base_delay_ms`, doubled
+    /// per attempt) up to `max_retries` times. Other 4xx responses and
+    /// non-transient errors are returned immediately.
+    ///
+    /// Only for GETs: retrying a POST or DELETE could start a scan or create a
+    /// job twice.
+    async fn send_get(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, ApiError> {
+        let mut attempt = 0;
+        loop {
+            let Some(req) = request.try_clone() else {
+                // Streaming bodies can't be replayed; GETs never have one
+                return Ok(request.send().await?);
+            };
+            let retryable = match req.send().await {
+                Ok(resp) if resp.status().is_server_error() => {
+                    if attempt >= self.config.max_retries {
+                        return Ok(resp);
+                    }
+                    format!("HTTP {}", resp.status())
+                }
+                Ok(resp) => return Ok(resp),
+                Err(e) if is_transient(&e) && attempt < self.config.max_retries => e.to_string(),
+                Err(e) => return Err(e.into()),
+            };
+            let delay = self.retry_delay(attempt);
+            attempt += 1;
+            tracing::debug!(
+                "Retrying request ({}/{}) in {:?}: {}",
+                attempt,
+                self.config.max_retries,
+                delay,
+                retryable
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Delay before retry number `attempt + 1`.
+    fn retry_delay(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+        std::time::Duration::from_millis(self.config.retry_base_delay_ms)
+            .saturating_mul(factor)
+    }
+
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
         response: reqwest::Response,
     ) -> Result<T, ApiError> {
-        let status = response.status();
-        if status.is_success() {
+        if response.status().is_success() {
             response
                 .json()
                 .await
                 .map_err(|e| ApiError::Deserialize(e.to_string()))
         } else {
-            let message = response
-                .json::<ApiErrorDetail>()
-                .await
-                .map(|e| e.detail)
-                .unwrap_or_else(|_| format!("HTTP {}", status));
-            Err(ApiError::Api {
+            Err(Self::error_from_response(response).await)
+        }
+    }
+
+    /// Turn a non-success response into an error, using the backend's
+    /// `detail` message when it sends one. 401 maps to `Unauthorized`.
+    async fn error_from_response(response: reqwest::Response) -> ApiError {
+        let status = response.status();
+        let message = response
+            .json::<ApiErrorDetail>()
+            .await
+            .map(|e| e.detail)
+            .unwrap_or_else(|_| format!("HTTP {}", status));
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            ApiError::Unauthorized(message)
+        } else {
+            ApiError::Api {
                 status: status.as_u16(),
                 message,
-            })
+            }
         }
     }
 
@@ -87,13 +157,13 @@ impl ApiClient {
 
     /// Check API health.
     pub async fn health(&self) -> Result<HealthResponse, ApiError> {
-        let resp = self.client.get(self.url("/system/health")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/system/health"))).await?;
         self.handle_response(resp).await
     }
 
     /// Get system information.
     pub async fn system_info(&self) -> Result<SystemInfo, ApiError> {
-        let resp = self.client.get(self.url("/system/info")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/system/info"))).await?;
         self.handle_response(resp).await
     }
 
@@ -118,17 +188,14 @@ impl ApiClient {
         if let Some(s) = status {
             req = req.query(&[("status", s)]);
         }
-        let resp = req.send().await?;
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Get a device by ID.
     pub async fn get_device(&self, device_id: &str) -> Result<Device, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/devices/{}", device_id)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/devices/{}", device_id)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
@@ -157,16 +224,7 @@ impl ApiClient {
         if resp.status().is_success() {
             Ok(())
         } else {
-            let status = resp.status();
-            let message = resp
-                .json::<ApiErrorDetail>()
-                .await
-                .map(|e| e.detail)
-                .unwrap_or_else(|_| format!("HTTP {}", status));
-            Err(ApiError::Api {
-                status: status.as_u16(),
-                message,
-            })
+            Err(Self::error_from_response(resp).await)
         }
     }
 
@@ -202,17 +260,14 @@ impl ApiClient {
         if let Some(s) = status {
             req = req.query(&[("status", s)]);
         }
-        let resp = req.send().await?;
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Get a scan by ID.
     pub async fn get_scan(&self, scan_id: &str) -> Result<Scan, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/scans/{}", scan_id)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/scans/{}", scan_id)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
@@ -255,23 +310,20 @@ impl ApiClient {
         if let Some(t) = source_tool {
             req = req.query(&[("source_tool", t)]);
         }
-        let resp = req.send().await?;
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Get alert statistics.
     pub async fn alert_stats(&self) -> Result<AlertStats, ApiError> {
-        let resp = self.client.get(self.url("/alerts/stats")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/alerts/stats"))).await?;
         self.handle_response(resp).await
     }
 
     /// Get an alert by ID.
     pub async fn get_alert(&self, alert_id: &str) -> Result<Alert, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/alerts/{}", alert_id)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/alerts/{}", alert_id)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
@@ -315,17 +367,14 @@ impl ApiClient {
         if let Some(s) = status {
             req = req.query(&[("status", s)]);
         }
-        let resp = req.send().await?;
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Get a vulnerability by ID.
     pub async fn get_vulnerability(&self, vuln_id: &str) -> Result<Vulnerability, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/vulnerabilities/{}", vuln_id)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/vulnerabilities/{}", vuln_id)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
@@ -373,7 +422,7 @@ impl ApiClient {
         if let Some(p) = protocol {
             req = req.query(&[("protocol", p)]);
         }
-        let resp = req.send().await?;
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
@@ -383,33 +432,27 @@ impl ApiClient {
 
     /// List all tools.
     pub async fn list_tools(&self) -> Result<Vec<Tool>, ApiError> {
-        let resp = self.client.get(self.url("/tools")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/tools"))).await?;
         self.handle_response(resp).await
     }
 
     /// Get a tool by name.
     pub async fn get_tool(&self, tool_name: &str) -> Result<Tool, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/tools/{}", tool_name)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/tools/{}", tool_name)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Check tool health.
     pub async fn tool_health(&self, tool_name: &str) -> Result<ToolHealth, ApiError> {
-        let resp = self
-            .client
-            .get(self.url(&format!("/tools/{}/health", tool_name)))
-            .send()
-            .await?;
+        let req = self.client.get(self.url(&format!("/tools/{}/health", tool_name)));
+        let resp = self.send_get(req).await?;
         self.handle_response(resp).await
     }
 
     /// Check all tools health.
     pub async fn all_tools_health(&self) -> Result<Vec<ToolHealth>, ApiError> {
-        let resp = self.client.get(self.url("/tools/health")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/tools/health"))).await?;
         self.handle_response(resp).await
     }
 
@@ -419,7 +462,7 @@ impl ApiClient {
 
     /// List scheduled jobs.
     pub async fn list_jobs(&self) -> Result<Vec<ScheduledJob>, ApiError> {
-        let resp = self.client.get(self.url("/scheduler/jobs")).send().await?;
+        let resp = self.send_get(self.client.get(self.url("/scheduler/jobs"))).await?;
         self.handle_response(resp).await
     }
 
@@ -444,16 +487,7 @@ impl ApiClient {
         if resp.status().is_success() {
             Ok(())
         } else {
-            let status = resp.status();
-            let message = resp
-                .json::<ApiErrorDetail>()
-                .await
-                .map(|e| e.detail)
-                .unwrap_or_else(|_| format!("HTTP {}", status));
-            Err(ApiError::Api {
-                status: status.as_u16(),
-                message,
-            })
+            Err(Self::error_from_response(resp).await)
         }
     }
 
@@ -488,5 +522,114 @@ mod tests {
         assert_eq!(config.base_url, "http://127.0.0.1:8420");
         assert!(config.api_key.is_none());
         assert_eq!(config.timeout_secs, 30);
+        assert_eq!(config.max_retries, 2);
+        assert_eq!(config.retry_base_delay_ms, 500);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_sent_as_bearer_header() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/system/health"))
+            .and(header("authorization", "Bearer secret-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "ok",
+                "timestamp": "2024-01-15T10:30:00Z",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_json(serde_json::json!({"detail": "Invalid API key"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(ApiConfig {
+            base_url: server.uri(),
+            api_key: Some("secret-key".to_string()),
+            ..ApiConfig::default()
+        })
+        .unwrap();
+        client.health().await.unwrap();
+
+        let anonymous = ApiClient::new(ApiConfig {
+            base_url: server.uri(),
+            ..ApiConfig::default()
+        })
+        .unwrap();
+        match anonymous.health().await {
+            Err(ApiError::Unauthorized(message)) => assert_eq!(message, "Invalid API key"),
+            other => panic!("expected Unauthorized, got {:?}", other.map(|_| ())),
+        }
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].headers.contains_key("authorization"));
+        assert!(!requests[1].headers.contains_key("authorization"));
+    }
+
+    fn health_body() -> serde_json::Value {
+        serde_json::json!({"status": "ok", "timestamp": "2024-01-15T10:30:00Z"})
+    }
+
+    fn retrying_client(server: &wiremock::MockServer) -> ApiClient {
+        ApiClient::new(ApiConfig {
+            base_url: server.uri(),
+            max_retries: 2,
+            retry_base_delay_ms: 10,
+            ..ApiConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_retried_after_server_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(health_body()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let health = retrying_client(&server).health().await.unwrap();
+        assert_eq!(health.status, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_and_posts_not_retried() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = retrying_client(&server);
+        assert!(client.get_device("missing").await.unwrap_err().is_not_found());
+        let err = client.pause_job("job-1").await.unwrap_err();
+        assert!(matches!(err, ApiError::Api { status: 503, .. }));
+        // `expect` counts are verified when the server is dropped
     }
 }
@@ -1,6 +1,7 @@
 //! API response models matching Python backend schemas.
 
 use chrono::{DateTime, NaiveDateTime, Utc};
+use netsec_models::time::parse_rfc3339;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
@@ -13,8 +14,8 @@ where
 {
     let s = String::deserialize(deserializer)?;
     // Try RFC 3339 / ISO 8601 with timezone first
-    if let Ok(dt) = DateTime::parse_from_rfc3339(&s) {
-        return Ok(dt.with_timezone(&Utc));
+    if let Ok(dt) = parse_rfc3339(&s) {
+        return Ok(dt);
     }
     // Try naive datetime (no timezone) and assume UTC
     if let Ok(naive) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f") {
@@ -35,8 +36,8 @@ where
     match opt {
         None => Ok(None),
         Some(s) => {
-            if let Ok(dt) = DateTime::parse_from_rfc3339(&s) {
-                return Ok(Some(dt.with_timezone(&Utc)));
+            if let Ok(dt) = parse_rfc3339(&s) {
+                return Ok(Some(dt));
             }
             if let Ok(naive) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f") {
                 return Ok(Some(naive.and_utc()));
@@ -82,6 +83,10 @@ pub struct Port {
     pub service_name: Option<String>,
     pub service_version: Option<String>,
     pub banner: Option<String>,
+    #[serde(default)]
+    pub http_title: Option<String>,
+    #[serde(default)]
+    pub server_header: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -335,6 +340,9 @@ pub enum ApiError {
     #[error("API error ({status}): {message}")]
     Api { status: u16, message: String },
 
+    #[error("Unauthorized: {0} (check the API key in Settings)")]
+    Unauthorized(String),
+
     #[error("Deserialization error: {0}")]
     Deserialize(String),
 
@@ -1,6 +1,7 @@
 //! WebSocket client for real-time event streaming.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
@@ -102,8 +103,10 @@ pub enum WsState {
     Disconnected,
     Connecting,
     Connected,
-    Reconnecting(u32), // reconnect attempt number
-    Error(String),
+    /// Waiting to retry; `attempt` counts from 1 since the last good connection.
+    Reconnecting { attempt: u32 },
+    /// Gave up after `max_reconnect_attempts`; the stream has ended.
+    Failed,
 }
 
 /// Messages from the WebSocket connection.
@@ -121,7 +124,10 @@ pub enum WsMessage2 {
 #[derive(Debug, Clone)]
 pub struct WsConfig {
     pub url: String,
+    /// Delay before the first reconnect attempt; doubled on each further attempt.
     pub reconnect_delay_ms: u64,
+    /// Upper bound on the reconnect delay.
+    pub max_reconnect_delay_ms: u64,
     pub max_reconnect_attempts: u32,
 }
 
@@ -130,17 +136,67 @@ impl Default for WsConfig {
         Self {
             url: "ws://127.0.0.1:8420/ws".to_string(),
             reconnect_delay_ms: 2000,
+            max_reconnect_delay_ms: 60_000,
             max_reconnect_attempts: 10,
         }
     }
 }
 
+/// Exponential reconnect backoff.
+///
+/// Yields the delay before each reconnect attempt: the base delay, then
+/// double the previous one up to the cap, and ends after the configured
+/// number of attempts. [`reset`](Self::reset) starts over after a successful
+/// connect.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    max_attempts: u32,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(config: &WsConfig) -> Self {
+        Self {
+            base: Duration::from_millis(config.reconnect_delay_ms),
+            max: Duration::from_millis(config.max_reconnect_delay_ms),
+            max_attempts: config.max_reconnect_attempts,
+            attempt: 0,
+        }
+    }
+
+    /// Attempts made since the last reset.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.attempt >= self.max_attempts {
+            return None;
+        }
+        let factor = 2u32.checked_pow(self.attempt).unwrap_or(u32::MAX);
+        self.attempt += 1;
+        Some(self.base.saturating_mul(factor).min(self.max))
+    }
+}
+
 /// Create a subscription for WebSocket events.
 ///
 /// This returns an iced Subscription that:
 /// 1. Connects to the WebSocket endpoint
 /// 2. Streams events as they arrive
-/// 3. Automatically reconnects on disconnect
+/// 3. Reconnects on disconnect with exponential [`Backoff`], reporting
+///    `WsState::Reconnecting` per attempt and `WsState::Failed` once the
+///    attempts run out
 pub fn connect(config: WsConfig) -> Subscription<WsMessage2> {
     struct WsConnection;
 
@@ -154,13 +210,13 @@ fn ws_stream(config: WsConfig) -> impl futures_util::Stream<Item = WsMessage2> {
     iced::stream::channel(100, move |mut output| {
         let config = config.clone();
         async move {
-            let mut reconnect_attempts = 0u32;
+            let mut backoff = Backoff::new(&config);
 
             loop {
                 // Report connecting state
                 let _ = output.send(WsMessage2::StateChanged(
-                    if reconnect_attempts > 0 {
-                        WsState::Reconnecting(reconnect_attempts)
+                    if backoff.attempt() > 0 {
+                        WsState::Reconnecting { attempt: backoff.attempt() }
                     } else {
                         WsState::Connecting
                     }
@@ -169,7 +225,7 @@ fn ws_stream(config: WsConfig) -> impl futures_util::Stream<Item = WsMessage2> {
                 // Try to connect (pass URL as string - tokio-tungstenite accepts &str)
                 match connect_async(&config.url).await {
                     Ok((ws_stream, _)) => {
-                        reconnect_attempts = 0;
+                        backoff.reset();
                         let _ = output.send(WsMessage2::StateChanged(WsState::Connected)).await;
 
                         let (mut write, mut read) = ws_stream.split();
@@ -215,18 +271,17 @@ fn ws_stream(config: WsConfig) -> impl futures_util::Stream<Item = WsMessage2> {
                 // Connection lost - report and maybe reconnect
                 let _ = output.send(WsMessage2::StateChanged(WsState::Disconnected)).await;
 
-                reconnect_attempts += 1;
-                if reconnect_attempts > config.max_reconnect_attempts {
-                    let _ = output.send(WsMessage2::StateChanged(
-                        WsState::Error("Max reconnect attempts exceeded".to_string())
-                    )).await;
+                let Some(delay) = backoff.next() else {
+                    tracing::warn!(
+                        "WebSocket giving up after {} reconnect attempts",
+                        config.max_reconnect_attempts
+                    );
+                    let _ = output.send(WsMessage2::StateChanged(WsState::Failed)).await;
                     return;
-                }
+                };
 
                 // Wait before reconnecting
-                tokio::time::sleep(std::time::Duration::from_millis(
-                    config.reconnect_delay_ms * reconnect_attempts as u64
-                )).await;
+                tokio::time::sleep(delay).await;
             }
         }
     })
@@ -251,6 +306,39 @@ mod tests {
         assert_eq!(config.max_reconnect_attempts, 10);
     }
 
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = WsConfig {
+            reconnect_delay_ms: 500,
+            max_reconnect_delay_ms: 5000,
+            max_reconnect_attempts: 6,
+            ..WsConfig::default()
+        };
+        let delays: Vec<u64> = Backoff::new(&config).map(|d| d.as_millis() as u64).collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 5000, 5000]);
+    }
+
+    #[test]
+    fn test_backoff_reset_and_large_attempt_counts() {
+        let config = WsConfig {
+            reconnect_delay_ms: 1000,
+            max_reconnect_delay_ms: 8000,
+            max_reconnect_attempts: 100,
+            ..WsConfig::default()
+        };
+        let mut backoff = Backoff::new(&config);
+        assert_eq!(backoff.next(), Some(Duration::from_secs(1)));
+        assert_eq!(backoff.next(), Some(Duration::from_secs(2)));
+        assert_eq!(backoff.attempt(), 2);
+        backoff.reset();
+        assert_eq!(backoff.attempt(), 0);
+        assert_eq!(backoff.next(), Some(Duration::from_secs(1)));
+
+        // Doubling never overflows past the cap
+        assert_eq!(backoff.clone().last(), Some(Duration::from_secs(8)));
+        assert_eq!(backoff.count(), 99);
+    }
+
     #[test]
     fn test_event_deserialize() {
         let json = r#"{
@@ -7,22 +7,34 @@ use crate::api::{
     self, ApiClient, ApiConfig, WsConfig, WsState,
     websocket::{self, WsMessage2},
 };
-use crate::desktop::{notifications, persistence};
-use crate::message::{InspectorTab, Message, Severity, ToastLevel, ToolMode};
+use crate::desktop::{export, notifications, persistence, project, shortcuts};
+use crate::message::{InspectorTab, LayoutKind, Message, Severity, ToastLevel, ToolMode};
 use crate::webview::{CanvasWebview, NetworkStateJson, WebviewEvent, parse_node_id, parse_connection_id};
 use crate::views::settings::Settings;
+use crate::views::theme_editor::ThemeEditor;
 use crate::views::ui_components::{ConfirmDialog, Toast};
-use crate::state::network::NetworkState;
+use crate::state::connection::ConnectionNotifier;
+use crate::state::device_diff;
+use crate::state::favorites::Favorites;
+use crate::state::network::{layout, NetworkState};
+use crate::state::notifications::{
+    NotificationCenter, NotificationFilter, NotificationKind, NotificationTarget,
+};
 use crate::state::terminal::TerminalState;
-use crate::theme;
+use crate::state::traffic::TrafficHistory;
+use crate::theme::{self, Palette};
 use crate::views;
 
+use chrono::Utc;
 use reqwest::Url;
 use std::net::{Ipv4Addr, TcpStream, ToSocketAddrs};
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::time::{Duration, Instant};
 
+/// How long a canvas node stays highlighted after a refresh changed it.
+const DEVICE_HIGHLIGHT_DURATION: Duration = Duration::from_secs(4);
+
 /// API connection state.
 #[derive(Debug, Clone, Default)]
 pub struct ApiState {
@@ -64,8 +76,14 @@ pub struct NetWatch {
     terminal: TerminalState,
     /// Network canvas state
     network: NetworkState,
+    /// Device state as of the last canvas sync, for diffing refreshes
+    device_snapshot: device_diff::Snapshot,
+    /// Decides which WebSocket state changes are toasted
+    connection_notifier: ConnectionNotifier,
     /// Current tool mode
     tool_mode: ToolMode,
+    /// Layout last applied to the canvas
+    layout_kind: LayoutKind,
     /// Active inspector tab
     inspector_tab: InspectorTab,
     /// Whether the terminal panel is visible
@@ -74,6 +92,10 @@ pub struct NetWatch {
     inspector_visible: bool,
     /// Whether the toolbar is visible
     toolbar_visible: bool,
+    /// Pinned devices shown in the favorites sidebar
+    favorites: Favorites,
+    /// Whether the favorites sidebar is visible
+    favorites_visible: bool,
     /// Whether the vulnerability dashboard modal is visible
     show_vuln_dashboard: bool,
     /// Vulnerability filter by severity
@@ -84,12 +106,10 @@ pub struct NetWatch {
     show_alerts_dashboard: bool,
     /// Selected alert ID in the alerts dashboard
     selected_alert_id: Option<String>,
-    /// Alert filter by severity
-    alert_filter_severity: Option<String>,
-    /// Alert filter by status
-    alert_filter_status: Option<String>,
-    /// Alert search query
-    alert_search_query: String,
+    /// Alert list filter (severity/status multi-select and search)
+    alert_filter: views::alerts::FilterSet,
+    /// Alert IDs checked for a bulk action
+    checked_alert_ids: std::collections::BTreeSet<String>,
     /// Whether the scans dashboard modal is visible
     show_scans_dashboard: bool,
     /// Selected scan ID in the scans dashboard
@@ -102,6 +122,8 @@ pub struct NetWatch {
     selected_traffic_id: Option<String>,
     /// Traffic filter by protocol
     traffic_filter_protocol: Option<String>,
+    /// Rolling per-device traffic history for inspector anomaly highlighting
+    traffic_history: TrafficHistory,
     /// Whether the tools dashboard modal is visible
     show_tools_dashboard: bool,
     /// Selected tool name
@@ -114,6 +136,18 @@ pub struct NetWatch {
     show_settings: bool,
     /// Application settings
     settings: Settings,
+    /// Whether the theme editor is visible
+    show_theme_editor: bool,
+    /// Whether the notification center modal is visible
+    show_notification_center: bool,
+    /// Session notification history
+    notifications: NotificationCenter,
+    /// Notification center filter
+    notification_filter: NotificationFilter,
+    /// Theme editor inputs
+    theme_editor: ThemeEditor,
+    /// Last saved palette, restored when the editor is cancelled
+    saved_palette: Palette,
     /// Toast notifications
     toasts: Vec<Toast>,
     /// Next toast ID
@@ -166,6 +200,14 @@ impl NetWatch {
         let settings = persistence::load_settings().unwrap_or_default();
         tracing::info!("Settings loaded: API URL = {}", settings.api_url);
 
+        // Apply a persisted custom theme over the built-in palette
+        theme::set_dark_mode(settings.dark_mode);
+        let saved_palette = persistence::load_theme(settings.dark_mode)
+            .unwrap_or_else(|| Palette::builtin(settings.dark_mode));
+        theme::set_palette(saved_palette);
+
+        let favorites = persistence::load_favorites().unwrap_or_default();
+
         if let Some((message, level, backend_started)) =
             Self::ensure_local_backend_running(&settings.api_url)
         {
@@ -183,8 +225,8 @@ impl NetWatch {
         // Initialize API client with settings
         let api_config = ApiConfig {
             base_url: settings.api_url.clone(),
-            api_key: None,
-            timeout_secs: 30,
+            api_key: settings.api_key(),
+            ..ApiConfig::default()
         };
         let (api_client, api_client_error) = match ApiClient::new(api_config) {
             Ok(client) => (Some(client), None),
@@ -202,6 +244,7 @@ impl NetWatch {
         let ws_config = WsConfig {
             url: settings.ws_url.clone(),
             reconnect_delay_ms: 5000,
+            max_reconnect_delay_ms: 60_000,
             max_reconnect_attempts: 10,
         };
 
@@ -224,31 +267,42 @@ impl NetWatch {
             Self {
                 terminal,
                 network,
+                device_snapshot: device_diff::Snapshot::new(),
+                connection_notifier: ConnectionNotifier::new(),
                 tool_mode: ToolMode::Select,
+                layout_kind: LayoutKind::default(),
                 inspector_tab: InspectorTab::Details,
                 terminal_visible: true,
                 inspector_visible: true,
                 toolbar_visible: true,
+                favorites_visible: !favorites.is_empty(),
+                favorites,
                 show_vuln_dashboard: false,
                 vuln_filter_severity: None,
                 vuln_search_query: String::new(),
                 show_alerts_dashboard: false,
                 selected_alert_id: None,
-                alert_filter_severity: None,
-                alert_filter_status: None,
-                alert_search_query: String::new(),
+                alert_filter: views::alerts::FilterSet::default(),
+                checked_alert_ids: Default::default(),
                 show_scans_dashboard: false,
                 selected_scan_id: None,
                 scan_filter_status: None,
                 show_traffic_dashboard: false,
                 selected_traffic_id: None,
                 traffic_filter_protocol: None,
+                traffic_history: TrafficHistory::new(),
                 show_tools_dashboard: false,
                 selected_tool: None,
                 show_scheduler_dashboard: false,
                 selected_job_id: None,
                 show_settings: false,
                 settings,
+                show_theme_editor: false,
+                show_notification_center: false,
+                notifications: NotificationCenter::new(),
+                notification_filter: NotificationFilter::default(),
+                theme_editor: ThemeEditor::new(&saved_palette),
+                saved_palette,
                 toasts: Vec::new(),
                 next_toast_id: 0,
                 confirm_dialog: None,
@@ -588,7 +642,25 @@ impl NetWatch {
                 Task::none()
             }
             Message::GroupSelected => {
-                // TODO: Implement grouping
+                let label = format!("Group {}", self.network.groups.len() + 1);
+                if self.network.group_selected(label).is_some() {
+                    self.sync_state_to_webview();
+                    Task::none()
+                } else {
+                    Task::done(Message::ShowToast(
+                        "Select at least two nodes to group".to_string(),
+                        ToastLevel::Info,
+                    ))
+                }
+            }
+            Message::SetGroupCollapsed(id, collapsed) => {
+                self.network.set_group_collapsed(id, collapsed);
+                self.sync_state_to_webview();
+                Task::none()
+            }
+            Message::DeleteGroup(id, keep_members) => {
+                self.network.remove_group(id, keep_members);
+                self.sync_state_to_webview();
                 Task::none()
             }
             Message::NodeMoved(id, x, y) => {
@@ -614,6 +686,17 @@ impl NetWatch {
                 self.network.hovered_connection = id;
                 Task::none()
             }
+            Message::SetLayout(kind) => {
+                self.layout_kind = kind;
+                match kind {
+                    LayoutKind::Radial => self.network.apply_radial_layout(),
+                    LayoutKind::Force => {
+                        self.network.apply_force_layout(layout::FORCE_ITERATIONS)
+                    }
+                }
+                self.sync_state_to_webview();
+                Task::none()
+            }
 
             // === Tool Mode ===
             Message::SetToolMode(mode) => {
@@ -740,15 +823,69 @@ impl NetWatch {
             }
 
             // === File Operations ===
-            Message::SaveProject => {
-                // TODO: Implement save dialog
-                tracing::info!("Save project requested");
-                Task::none()
-            }
+            Message::SaveProject => match project::to_json(&self.network) {
+                Ok(contents) => {
+                    Task::perform(project::save_with_dialog(contents), Message::ProjectSaved)
+                }
+                Err(e) => {
+                    tracing::error!("Project serialization failed: {}", e);
+                    Task::done(Message::ShowToast(
+                        format!("Failed to save project: {}", e),
+                        ToastLevel::Error,
+                    ))
+                }
+            },
             Message::LoadProject => {
-                // TODO: Implement load dialog
-                tracing::info!("Load project requested");
-                Task::none()
+                Task::perform(project::open_with_dialog(), Message::ProjectOpened)
+            }
+            Message::ProjectSaved(result) => match result {
+                Ok(Some(path)) => Task::done(Message::ShowToast(
+                    format!("Project saved to {}", path.display()),
+                    ToastLevel::Success,
+                )),
+                Ok(None) => Task::none(),
+                Err(e) => {
+                    tracing::error!("Project save failed: {}", e);
+                    Task::done(Message::ShowToast(
+                        format!("Failed to save project: {}", e),
+                        ToastLevel::Error,
+                    ))
+                }
+            },
+            Message::ProjectOpened(result) => {
+                let loaded = result.and_then(|opened| {
+                    opened
+                        .map(|(path, contents)| {
+                            project::from_json(&contents)
+                                .map(|network| (path, network))
+                                .map_err(|e| e.to_string())
+                        })
+                        .transpose()
+                });
+                match loaded {
+                    Ok(Some((path, network))) => {
+                        tracing::info!(
+                            "Loaded project {:?}: {} nodes, {} connections",
+                            path,
+                            network.nodes.len(),
+                            network.connections.len()
+                        );
+                        self.network = network;
+                        self.sync_state_to_webview();
+                        Task::done(Message::ShowToast(
+                            format!("Project loaded from {}", path.display()),
+                            ToastLevel::Success,
+                        ))
+                    }
+                    Ok(None) => Task::none(),
+                    Err(e) => {
+                        tracing::error!("Project load failed: {}", e);
+                        Task::done(Message::ShowToast(
+                            format!("Failed to load project: {}", e),
+                            ToastLevel::Error,
+                        ))
+                    }
+                }
             }
 
             // === UI Panels ===
@@ -767,6 +904,96 @@ impl NetWatch {
                 self.update_webview_bounds();
                 Task::none()
             }
+            Message::CloseTopOverlay => match self.top_overlay_close_message() {
+                Some(message) => Task::done(message),
+                None => Task::none(),
+            },
+            Message::ShowNotificationCenter => {
+                self.show_notification_center = true;
+                Task::none()
+            }
+            Message::HideNotificationCenter => {
+                self.show_notification_center = false;
+                Task::none()
+            }
+            Message::NotificationOpened(id) => {
+                self.notifications.mark_read(id);
+                let Some(target) = self.notifications.get(id).map(|e| e.target.clone()) else {
+                    return Task::none();
+                };
+                match target {
+                    NotificationTarget::Alert(alert_id) => {
+                        self.show_notification_center = false;
+                        self.selected_alert_id = Some(alert_id);
+                        Task::done(Message::ShowAlertsDashboard)
+                    }
+                    NotificationTarget::Scan(scan_id) => {
+                        self.show_notification_center = false;
+                        self.selected_scan_id = Some(scan_id);
+                        Task::done(Message::ShowScansDashboard)
+                    }
+                    NotificationTarget::Device(ip) => {
+                        match self.network.nodes.iter().find(|n| n.ip == ip) {
+                            Some(node) => {
+                                self.show_notification_center = false;
+                                Task::done(Message::NodeSelected(node.id))
+                            }
+                            None => Task::done(Message::ShowToast(
+                                format!("Device {} is no longer in the inventory", ip),
+                                ToastLevel::Info,
+                            )),
+                        }
+                    }
+                    NotificationTarget::None => Task::none(),
+                }
+            }
+            Message::NotificationFilterKind(kind) => {
+                self.notification_filter.kind = kind;
+                Task::none()
+            }
+            Message::NotificationToggleUnreadOnly => {
+                self.notification_filter.unread_only = !self.notification_filter.unread_only;
+                Task::none()
+            }
+            Message::NotificationsMarkAllRead => {
+                self.notifications.mark_all_read();
+                Task::none()
+            }
+            Message::NotificationsClear => {
+                self.notifications.clear();
+                Task::none()
+            }
+            Message::ToggleFavoritesPanel => {
+                self.favorites_visible = !self.favorites_visible;
+                self.update_webview_bounds();
+                Task::none()
+            }
+            Message::TogglePinDevice(ip) => {
+                let was_pinned = self.favorites.contains(&ip);
+                if !was_pinned && self.favorites.is_full() {
+                    return Task::done(Message::ShowToast(
+                        format!("Unpin a device first (limit {})", crate::state::favorites::MAX_FAVORITES),
+                        ToastLevel::Warning,
+                    ));
+                }
+                self.favorites.toggle(&ip);
+                if !was_pinned && !self.favorites_visible {
+                    self.favorites_visible = true;
+                    self.update_webview_bounds();
+                }
+                if let Err(e) = persistence::save_favorites(&self.favorites) {
+                    tracing::warn!("Failed to save favorites: {}", e);
+                }
+                Task::none()
+            }
+            Message::FavoriteMove(ip, offset) => {
+                if self.favorites.shift(&ip, offset) {
+                    if let Err(e) = persistence::save_favorites(&self.favorites) {
+                        tracing::warn!("Failed to save favorites: {}", e);
+                    }
+                }
+                Task::none()
+            }
             Message::ShowVulnDashboard => {
                 self.show_vuln_dashboard = true;
                 // Fetch latest vulnerabilities from API
@@ -807,6 +1034,7 @@ impl NetWatch {
             Message::HideAlertsDashboard => {
                 self.show_alerts_dashboard = false;
                 self.selected_alert_id = None;
+                self.checked_alert_ids.clear();
                 Task::none()
             }
             Message::AlertSelected(id) => {
@@ -814,15 +1042,21 @@ impl NetWatch {
                 Task::none()
             }
             Message::AlertFilterSeverity(severity) => {
-                self.alert_filter_severity = severity;
+                match severity {
+                    Some(severity) => self.alert_filter.toggle_severity(&severity),
+                    None => self.alert_filter.severities.clear(),
+                }
                 Task::none()
             }
             Message::AlertFilterStatus(status) => {
-                self.alert_filter_status = status;
+                match status {
+                    Some(status) => self.alert_filter.toggle_status(&status),
+                    None => self.alert_filter.statuses.clear(),
+                }
                 Task::none()
             }
             Message::AlertSearch(query) => {
-                self.alert_search_query = query;
+                self.alert_filter.search = query;
                 Task::none()
             }
             Message::AcknowledgeAlert(id) => {
@@ -849,6 +1083,12 @@ impl NetWatch {
                 };
                 Task::done(Message::UpdateAlert(id, update))
             }
+            Message::AlertChecked(id) => {
+                if !self.checked_alert_ids.remove(&id) {
+                    self.checked_alert_ids.insert(id);
+                }
+                Task::none()
+            }
 
             // === Scans Dashboard ===
             Message::ShowScansDashboard => {
@@ -957,6 +1197,70 @@ impl NetWatch {
                 }
             }
 
+            // === Export ===
+            Message::ExportCanvasSvg => {
+                let result = export::export_network_svg(&self.network, self.settings.export_dir());
+                let (message, level) = match result {
+                    Ok(path) => (
+                        format!("Canvas exported to {}", path.display()),
+                        ToastLevel::Success,
+                    ),
+                    Err(e) => {
+                        tracing::error!("Canvas export failed: {}", e);
+                        (format!("Canvas export failed: {}", e), ToastLevel::Error)
+                    }
+                };
+                Task::done(Message::ShowToast(message, level))
+            }
+            Message::ExportAlertsCsv => {
+                let alerts: Vec<&api::Alert> = self
+                    .api_state
+                    .alerts
+                    .iter()
+                    .filter(|a| views::alerts::matches_filter(a, &self.alert_filter))
+                    .collect();
+                let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+                let file_name = format!("alerts-{stamp}.csv");
+                Task::perform(
+                    export::save_csv_with_dialog(
+                        file_name,
+                        export::alerts_csv(&alerts),
+                        self.settings.export_dir(),
+                    ),
+                    Message::CsvExported,
+                )
+            }
+            Message::ExportScansCsv => {
+                let scans = views::scans::filtered_scans(
+                    &self.api_state.scans,
+                    self.scan_filter_status.as_deref(),
+                );
+                let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+                let file_name = format!("scans-{stamp}.csv");
+                Task::perform(
+                    export::save_csv_with_dialog(
+                        file_name,
+                        export::scans_csv(&scans),
+                        self.settings.export_dir(),
+                    ),
+                    Message::CsvExported,
+                )
+            }
+            Message::CsvExported(result) => match result {
+                Ok(Some(path)) => Task::done(Message::ShowToast(
+                    format!("Exported to {}", path.display()),
+                    ToastLevel::Success,
+                )),
+                Ok(None) => Task::none(),
+                Err(e) => {
+                    tracing::error!("CSV export failed: {}", e);
+                    Task::done(Message::ShowToast(
+                        format!("CSV export failed: {}", e),
+                        ToastLevel::Error,
+                    ))
+                }
+            },
+
             // === Settings ===
             Message::ShowSettings => {
                 self.show_settings = true;
@@ -974,8 +1278,17 @@ impl NetWatch {
                 self.settings.ws_url = url;
                 Task::none()
             }
+            Message::SettingsUpdateApiKey(key) => {
+                self.settings.api_key = key;
+                Task::none()
+            }
             Message::SettingsToggleDarkMode => {
                 self.settings.dark_mode = !self.settings.dark_mode;
+                let dark_mode = self.settings.dark_mode;
+                theme::set_dark_mode(dark_mode);
+                self.saved_palette = persistence::load_theme(dark_mode)
+                    .unwrap_or_else(|| Palette::builtin(dark_mode));
+                theme::set_palette(self.saved_palette);
                 Task::none()
             }
             Message::SettingsToggleNotifications => {
@@ -990,6 +1303,14 @@ impl NetWatch {
                 self.settings.refresh_interval_secs = secs;
                 Task::none()
             }
+            Message::SettingsUpdateAnomalyThreshold(threshold) => {
+                self.settings.traffic_anomaly_threshold = threshold;
+                Task::none()
+            }
+            Message::SettingsUpdateExportDir(dir) => {
+                self.settings.export_dir = dir;
+                Task::none()
+            }
             Message::SettingsSave => {
                 // Persist settings to file
                 if let Err(e) = persistence::save_settings(&self.settings) {
@@ -1004,8 +1325,8 @@ impl NetWatch {
                 // Update API client with new URL if changed
                 let config = ApiConfig {
                     base_url: self.settings.api_url.clone(),
-                    api_key: None,
-                    timeout_secs: 30,
+                    api_key: self.settings.api_key(),
+                    ..ApiConfig::default()
                 };
                 let client = match ApiClient::new(config) {
                     Ok(client) => client,
@@ -1042,6 +1363,50 @@ impl NetWatch {
                 Task::batch(tasks)
             }
 
+            // === Theme Editor ===
+            Message::ShowThemeEditor => {
+                self.saved_palette = theme::palette();
+                self.theme_editor = ThemeEditor::new(&self.saved_palette);
+                self.show_settings = false;
+                self.show_theme_editor = true;
+                Task::none()
+            }
+            Message::HideThemeEditor => {
+                // Revert any unsaved live-preview changes
+                theme::set_palette(self.saved_palette);
+                self.show_theme_editor = false;
+                Task::none()
+            }
+            Message::ThemeEditorColorChanged(key, value) => {
+                self.theme_editor.set_input(&key, value);
+                // Live preview: apply every valid input immediately
+                theme::set_palette(self.theme_editor.preview(&self.saved_palette));
+                Task::none()
+            }
+            Message::ThemeEditorReset => {
+                let builtin = Palette::builtin(self.settings.dark_mode);
+                self.theme_editor = ThemeEditor::new(&builtin);
+                theme::set_palette(builtin);
+                Task::none()
+            }
+            Message::ThemeEditorSave => {
+                let palette = self.theme_editor.preview(&self.saved_palette);
+                if let Err(e) = persistence::save_theme(&palette, self.settings.dark_mode) {
+                    tracing::error!("Failed to save theme: {}", e);
+                    return Task::done(Message::ShowToast(
+                        format!("Failed to save theme: {}", e),
+                        ToastLevel::Error,
+                    ));
+                }
+                theme::set_palette(palette);
+                self.saved_palette = palette;
+                self.show_theme_editor = false;
+                Task::done(Message::ShowToast(
+                    "Theme saved".to_string(),
+                    ToastLevel::Success,
+                ))
+            }
+
             // === Notifications ===
             Message::ShowToast(message, level) => {
                 let toast = Toast::new(self.next_toast_id, message, level);
@@ -1082,6 +1447,11 @@ impl NetWatch {
                     tracing::debug!("Auto-dismissed {} toasts", old_count - self.toasts.len());
                 }
 
+                // Fade out change highlights on the canvas
+                if self.network.clear_expired_highlights(now) {
+                    self.sync_state_to_webview();
+                }
+
                 // Auto-refresh if enabled
                 if self.settings.auto_refresh {
                     let refresh_interval = Duration::from_secs(self.settings.refresh_interval_secs as u64);
@@ -1147,6 +1517,8 @@ impl NetWatch {
                     Task::done(Message::FetchAlerts),
                     Task::done(Message::FetchAlertStats),
                     Task::done(Message::FetchVulnerabilities),
+                    // Keeps the inspector's traffic baselines current
+                    Task::done(Message::FetchTraffic),
                     Task::done(Message::FetchTools),
                 ])
             }
@@ -1457,6 +1829,61 @@ impl NetWatch {
                     Task::none()
                 }
             }
+            Message::BulkUpdateAlerts(ids, status) => {
+                if let Some(client) = self.api_client.clone() {
+                    Task::perform(
+                        async move {
+                            let mut results = Vec::with_capacity(ids.len());
+                            for id in ids {
+                                let update = api::AlertUpdate {
+                                    status: Some(status.clone()),
+                                    severity: None,
+                                    notes: None,
+                                };
+                                let result = client
+                                    .update_alert(&id, update)
+                                    .await
+                                    .map_err(|e| e.to_string());
+                                results.push((id, result));
+                            }
+                            results
+                        },
+                        Message::AlertsBulkUpdated,
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Message::AlertsBulkUpdated(results) => {
+                let total = results.len();
+                let mut failures = Vec::new();
+                for (id, result) in results {
+                    match result {
+                        Ok(alert) => {
+                            self.checked_alert_ids.remove(&alert.id);
+                            let cached = self.api_state.alerts.iter_mut().find(|a| a.id == alert.id);
+                            if let Some(cached) = cached {
+                                *cached = alert;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to update alert {}: {}", id, e);
+                            failures.push(format!("{id}: {e}"));
+                        }
+                    }
+                }
+                tracing::info!("Bulk-updated {} of {} alerts", total - failures.len(), total);
+                if !failures.is_empty() {
+                    // Failed alerts stay checked so the update can be retried.
+                    self.api_state.last_error = Some(format!(
+                        "Failed to update {} of {} alerts: {}",
+                        failures.len(),
+                        total,
+                        failures.join("; ")
+                    ));
+                }
+                Task::none()
+            }
             Message::AlertUpdated(result) => {
                 match result {
                     Ok(alert) => {
@@ -1490,8 +1917,9 @@ impl NetWatch {
                     Ok(vulns) => {
                         tracing::info!("Fetched {} vulnerabilities", vulns.len());
                         self.api_state.vulnerabilities = vulns;
-                        // Sync to canvas nodes
-                        self.sync_vulnerabilities_to_canvas();
+                        // Sync to canvas nodes, highlighting devices with new findings
+                        self.sync_devices_to_canvas();
+                        self.sync_state_to_webview();
                     }
                     Err(e) => {
                         tracing::error!("Failed to fetch vulnerabilities: {}", e);
@@ -1541,6 +1969,7 @@ impl NetWatch {
                 match result {
                     Ok(traffic) => {
                         tracing::info!("Fetched {} traffic flows", traffic.len());
+                        self.traffic_history.record(&traffic);
                         self.api_state.traffic = traffic;
                     }
                     Err(e) => {
@@ -1757,8 +2186,12 @@ impl NetWatch {
             Message::WsStateChanged(state) => {
                 tracing::info!("WebSocket state: {:?}", state);
                 self.api_state.ws_connected = matches!(state, WsState::Connected);
+                let toast = self.connection_notifier.on_state(&state);
                 self.api_state.ws_state = Some(state);
-                Task::none()
+                match toast {
+                    Some((message, level)) => Task::done(Message::ShowToast(message, level)),
+                    None => Task::none(),
+                }
             }
             Message::WsEventReceived(event) => {
                 self.handle_ws_event(event)
@@ -1965,21 +2398,43 @@ impl NetWatch {
         }
     }
 
+    /// The message that closes the overlay `view` currently shows, following
+    /// the same precedence: only the first open overlay is drawn.
+    fn top_overlay_close_message(&self) -> Option<Message> {
+        let overlays = [
+            (self.show_vuln_dashboard, Message::HideVulnDashboard),
+            (self.show_alerts_dashboard, Message::HideAlertsDashboard),
+            (self.show_scans_dashboard, Message::HideScansDashboard),
+            (self.show_traffic_dashboard, Message::HideTrafficDashboard),
+            (self.show_tools_dashboard, Message::HideToolsDashboard),
+            (self.show_scheduler_dashboard, Message::HideSchedulerDashboard),
+            (self.show_settings, Message::HideSettings),
+            (self.show_notification_center, Message::HideNotificationCenter),
+            (self.show_theme_editor, Message::HideThemeEditor),
+            (self.confirm_dialog.is_some(), Message::ConfirmDialogCancel),
+        ];
+        overlays.into_iter().find(|(open, _)| *open).map(|(_, message)| message)
+    }
+
     /// Recompute and apply webview bounds based on current layout state.
     fn update_webview_bounds(&mut self) {
         // Layout constants (must match the view code)
         const HEADER_HEIGHT: f64 = 56.0; // header container height
         const RULE_HEIGHT: f64 = 1.0;    // horizontal_rule(1)
         const TOOLBAR_WIDTH: f64 = 72.0; // toolbar container width
+        const FAVORITES_WIDTH: f64 = views::favorites::FAVORITES_WIDTH as f64;
         const INSPECTOR_WIDTH: f64 = 280.0; // inspector panel width
         const ROW_SPACING: f64 = 1.0;    // main_content row spacing
 
         let top = HEADER_HEIGHT + RULE_HEIGHT;
-        let left = if self.toolbar_visible {
+        let mut left = if self.toolbar_visible {
             TOOLBAR_WIDTH + ROW_SPACING
         } else {
             0.0
         };
+        if self.favorites_visible {
+            left += FAVORITES_WIDTH + ROW_SPACING;
+        }
         let right_inset = if self.inspector_visible {
             INSPECTOR_WIDTH + ROW_SPACING
         } else {
@@ -2039,10 +2494,19 @@ impl NetWatch {
                 self.network.scan_progress = 100;
                 self.active_scan_id = None;
 
+                let scan_type = event.get_string("scan_type").unwrap_or_else(|| "Network".to_string());
+                let target = event.get_string("target").unwrap_or_else(|| "unknown".to_string());
+                self.notifications.push(
+                    NotificationKind::Scan,
+                    format!("Scan completed: {}", scan_type),
+                    format!("Target: {}", target),
+                    None,
+                    event.get_string("scan_id").map_or(NotificationTarget::None, NotificationTarget::Scan),
+                    Utc::now(),
+                );
+
                 // Show native notification if enabled
                 if self.settings.notifications_enabled {
-                    let scan_type = event.get_string("scan_type").unwrap_or_else(|| "Network".to_string());
-                    let target = event.get_string("target").unwrap_or_else(|| "unknown".to_string());
                     notifications::notify_scan_completed(&scan_type, &target, "completed");
                 }
 
@@ -2061,10 +2525,19 @@ impl NetWatch {
                     self.api_state.last_error = Some(error);
                 }
 
+                let scan_type = event.get_string("scan_type").unwrap_or_else(|| "Network".to_string());
+                let target = event.get_string("target").unwrap_or_else(|| "unknown".to_string());
+                self.notifications.push(
+                    NotificationKind::Scan,
+                    format!("Scan failed: {}", scan_type),
+                    format!("Target: {}", target),
+                    None,
+                    event.get_string("scan_id").map_or(NotificationTarget::None, NotificationTarget::Scan),
+                    Utc::now(),
+                );
+
                 // Show native notification if enabled
                 if self.settings.notifications_enabled {
-                    let scan_type = event.get_string("scan_type").unwrap_or_else(|| "Network".to_string());
-                    let target = event.get_string("target").unwrap_or_else(|| "unknown".to_string());
                     notifications::notify_scan_completed(&scan_type, &target, "failed");
                 }
 
@@ -2073,10 +2546,22 @@ impl NetWatch {
 
             // Device events
             WsEventType::DeviceDiscovered => {
+                let ip = event.get_string("ip_address").unwrap_or_else(|| "unknown".to_string());
+                let hostname = event.get_string("hostname");
+                self.notifications.push(
+                    NotificationKind::Device,
+                    "New device discovered".to_string(),
+                    match hostname.as_deref() {
+                        Some(host) => format!("{} ({})", host, ip),
+                        None => ip.clone(),
+                    },
+                    None,
+                    NotificationTarget::Device(ip.clone()),
+                    Utc::now(),
+                );
+
                 // Show native notification if enabled
                 if self.settings.notifications_enabled {
-                    let ip = event.get_string("ip_address").unwrap_or_else(|| "unknown".to_string());
-                    let hostname = event.get_string("hostname");
                     notifications::notify_device_discovered(&ip, hostname.as_deref());
                 }
 
@@ -2103,11 +2588,23 @@ impl NetWatch {
 
             // Alert events
             WsEventType::AlertCreated => {
+                let severity = event.get_string("severity").unwrap_or_else(|| "info".to_string());
+                let title = event.get_string("title").unwrap_or_else(|| "New Alert".to_string());
+                let message = event.get_string("message").unwrap_or_else(|| "A new alert was created".to_string());
+                self.notifications.push(
+                    NotificationKind::Alert,
+                    title.clone(),
+                    message.clone(),
+                    Some(severity.to_lowercase()),
+                    event
+                        .get_string("alert_id")
+                        .or_else(|| event.get_string("id"))
+                        .map_or(NotificationTarget::None, NotificationTarget::Alert),
+                    Utc::now(),
+                );
+
                 // Show native notification if enabled
                 if self.settings.notifications_enabled {
-                    let severity = event.get_string("severity").unwrap_or_else(|| "info".to_string());
-                    let title = event.get_string("title").unwrap_or_else(|| "New Alert".to_string());
-                    let message = event.get_string("message").unwrap_or_else(|| "A new alert was created".to_string());
                     notifications::notify_alert(&severity, &title, &message);
                 }
 
@@ -2149,9 +2646,37 @@ impl NetWatch {
 
     /// Sync devices from API to the network canvas.
     /// This creates/updates nodes on the canvas based on API device data.
+    ///
+    /// The cached devices are diffed against the previous sync: nodes of
+    /// removed devices are dropped, known nodes keep their positions, only new
+    /// nodes are laid out, and nodes whose status, ports or vulnerabilities
+    /// changed are briefly highlighted.
     pub fn sync_devices_to_canvas(&mut self) {
         use crate::message::{NodeStatus, NodeType};
-        use crate::state::network::{Node, Port as CanvasPort, Vulnerability as CanvasVuln};
+        use crate::state::network::{Node, Port as CanvasPort};
+
+        let snapshot = device_diff::snapshot(&self.api_state.devices, &self.api_state.vulnerabilities);
+        let diff = device_diff::diff(&self.device_snapshot, &snapshot);
+        self.device_snapshot = snapshot;
+        if !diff.is_empty() {
+            tracing::debug!(
+                "Device changes: {} added, {} changed, {} removed",
+                diff.added.len(),
+                diff.changed.len(),
+                diff.removed.len()
+            );
+        }
+
+        let removed: Vec<_> = self.network.nodes
+            .iter()
+            .filter(|n| diff.removed.contains(&n.ip))
+            .map(|n| n.id)
+            .collect();
+        for id in removed {
+            self.network.remove_node(id);
+        }
+
+        let mut new_ids = Vec::new();
 
         for api_device in &self.api_state.devices {
             // Check if device already exists on canvas (by IP)
@@ -2176,6 +2701,8 @@ impl NetWatch {
                     state: p.state.clone(),
                     service_name: p.service_name.clone(),
                     service_version: p.service_version.clone(),
+                    http_title: p.http_title.clone(),
+                    server_header: p.server_header.clone(),
                 }).collect();
             } else {
                 // Create new node
@@ -2192,7 +2719,7 @@ impl NetWatch {
                     _ => NodeType::Workstation, // Default
                 };
 
-                // Temporary position - radial layout applied after all nodes added
+                // Temporary position - new nodes are placed after all are added
                 let x = 0.0;
                 let y = 0.0;
 
@@ -2216,20 +2743,31 @@ impl NetWatch {
                     state: p.state.clone(),
                     service_name: p.service_name.clone(),
                     service_version: p.service_version.clone(),
+                    http_title: p.http_title.clone(),
+                    server_header: p.server_header.clone(),
                 }).collect();
 
+                new_ids.push(node.id);
                 self.network.add_node(node);
             }
         }
 
-        // Apply radial layout to position all nodes
-        self.network.apply_radial_layout();
+        // Position only the new nodes; existing ones stay where they are
+        self.network.place_new_nodes(&new_ids);
 
         // Create hub-to-spoke connections
         self.network.create_hub_connections();
 
         // Sync vulnerabilities to nodes
         self.sync_vulnerabilities_to_canvas();
+
+        // Highlight devices that changed since the last sync
+        let until = Instant::now() + DEVICE_HIGHLIGHT_DURATION;
+        for change in diff.changed.iter().filter(|c| c.is_notable()) {
+            if let Some(id) = self.network.nodes.iter().find(|n| n.ip == change.ip).map(|n| n.id) {
+                self.network.highlight_node(id, until);
+            }
+        }
     }
 
     /// Sync vulnerabilities from API to canvas nodes.
@@ -2276,15 +2814,23 @@ impl NetWatch {
         let header = views::header::view(
             self.network.is_scanning,
             vuln_count,
-            self.api_state.ws_connected,
+            self.api_state.ws_state.as_ref(),
             self.api_state.devices.len(),
             self.api_state.alerts.len(),
             self.api_state.scans.len(),
+            self.notifications.unread_count(),
         );
 
         // Left toolbar
         let toolbar = if self.toolbar_visible {
-            views::toolbar::view(self.tool_mode)
+            views::toolbar::view(self.tool_mode, self.layout_kind)
+        } else {
+            container(column![]).width(Length::Shrink).into()
+        };
+
+        // Pinned devices sidebar
+        let favorites = if self.favorites_visible {
+            views::favorites::view(&self.favorites, &self.network)
         } else {
             container(column![]).width(Length::Shrink).into()
         };
@@ -2294,14 +2840,21 @@ impl NetWatch {
 
         // Inspector panel
         let inspector = if self.inspector_visible {
-            views::inspector::view(&self.network, self.inspector_tab)
+            views::inspector::view(
+                &self.network,
+                self.inspector_tab,
+                &self.traffic_history,
+                self.settings.traffic_anomaly_threshold,
+                &self.favorites,
+            )
         } else {
             container(column![]).width(Length::Shrink).into()
         };
 
-        // Main row: toolbar | canvas | inspector
+        // Main row: toolbar | favorites | canvas | inspector
         let main_content = row![
             toolbar,
+            favorites,
             container(canvas)
                 .width(Length::Fill)
                 .height(Length::Fill),
@@ -2352,9 +2905,8 @@ impl NetWatch {
                 &self.api_state.alerts,
                 self.api_state.alert_stats.as_ref(),
                 self.selected_alert_id.as_deref(),
-                self.alert_filter_severity.as_deref(),
-                self.alert_filter_status.as_deref(),
-                &self.alert_search_query,
+                &self.alert_filter,
+                &self.checked_alert_ids,
             );
 
             Stack::new()
@@ -2411,6 +2963,21 @@ impl NetWatch {
                 .push(main_layout)
                 .push(settings_panel)
                 .into()
+        } else if self.show_notification_center {
+            let notification_center =
+                views::notification_center::view(&self.notifications, self.notification_filter);
+
+            Stack::new()
+                .push(main_layout)
+                .push(notification_center)
+                .into()
+        } else if self.show_theme_editor {
+            let theme_editor = views::theme_editor::view(&self.theme_editor);
+
+            Stack::new()
+                .push(main_layout)
+                .push(theme_editor)
+                .into()
         } else if let Some(ref dialog) = self.confirm_dialog {
             let dialog_view = views::ui_components::confirm_dialog_view(dialog);
 
@@ -2439,6 +3006,8 @@ impl NetWatch {
         let mut subs = vec![
             // Terminal output subscriptions
             self.terminal.subscription(),
+            // In-window keyboard shortcuts
+            shortcuts::subscription(),
             // Tick every second for toasts and auto-refresh
             time::every(Duration::from_secs(1)).map(|_| Message::Tick),
             // Window size events for webview bounds (Opened + Resized)
@@ -0,0 +1,434 @@
+//! Canvas export to SVG for reports, and dashboard tables to CSV.
+//!
+//! The live canvas is drawn by the embedded webview, which window screenshots
+//! do not capture reliably, so the export is generated from `NetworkState`
+//! directly. Output is deterministic for a given state.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use directories::UserDirs;
+use iced::Color;
+
+use crate::api::{Alert, Scan};
+use crate::message::{ConnectionType, NodeStatus, NodeType};
+use crate::state::network::{NetworkState, Node};
+use crate::theme::{color_to_hex, colors};
+
+/// Radius of a device node in the exported diagram.
+const NODE_RADIUS: f32 = 20.0;
+
+/// Margin around the diagram bounds.
+const MARGIN: f32 = 60.0;
+
+/// Status color for a node, matching the canvas legend.
+fn status_color(status: NodeStatus) -> Color {
+    match status {
+        NodeStatus::Online => colors::status_online(),
+        NodeStatus::Offline => colors::status_offline(),
+        NodeStatus::Warning => colors::status_warning(),
+        NodeStatus::Compromised => colors::status_critical(),
+    }
+}
+
+/// Escape text for use in SVG content and attribute values.
+fn escape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Bounding box of a node: (min_x, min_y, max_x, max_y).
+fn node_bounds(node: &Node) -> (f32, f32, f32, f32) {
+    match (node.node_type, node.width, node.height) {
+        (NodeType::Group, Some(w), Some(h)) => (node.x, node.y, node.x + w, node.y + h),
+        _ => (
+            node.x - NODE_RADIUS,
+            node.y - NODE_RADIUS,
+            node.x + NODE_RADIUS,
+            // Leave room for the label and IP below the node
+            node.y + NODE_RADIUS + 30.0,
+        ),
+    }
+}
+
+/// Render the network diagram as a standalone SVG document.
+///
+/// Emits one `<line class="edge">` per connection whose endpoints both exist,
+/// and one `<g class="node">` per node with its label and IP.
+pub fn render_network_svg(network: &NetworkState) -> String {
+    let (min_x, min_y, max_x, max_y) = network
+        .nodes
+        .iter()
+        .map(node_bounds)
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+        .unwrap_or((0.0, 0.0, 0.0, 0.0));
+    let (x, y) = (min_x - MARGIN, min_y - MARGIN);
+    let (width, height) = (max_x - min_x + 2.0 * MARGIN, max_y - min_y + 2.0 * MARGIN);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{x:.1} {y:.1} {width:.1} {height:.1}" width="{width:.0}" height="{height:.0}" font-family="sans-serif">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <rect x="{x:.1}" y="{y:.1}" width="{width:.1}" height="{height:.1}" fill="{}"/>"#,
+        color_to_hex(colors::bg_primary())
+    );
+
+    // Edges first so nodes draw on top
+    let edge_color = color_to_hex(colors::text_muted());
+    svg.push_str("  <g id=\"edges\">\n");
+    for conn in &network.connections {
+        let (Some(from), Some(to)) = (network.get_node(conn.from), network.get_node(conn.to))
+        else {
+            continue;
+        };
+        let dash = match conn.connection_type {
+            ConnectionType::Wired => "",
+            ConnectionType::Wireless => r#" stroke-dasharray="6 4""#,
+        };
+        let _ = writeln!(
+            svg,
+            r#"    <line class="edge" x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{edge_color}" stroke-width="2"{dash}/>"#,
+            from.x, from.y, to.x, to.y
+        );
+    }
+    svg.push_str("  </g>\n");
+
+    let text_primary = color_to_hex(colors::text_primary());
+    let text_muted = color_to_hex(colors::text_muted());
+    let surface = color_to_hex(colors::surface());
+    svg.push_str("  <g id=\"nodes\">\n");
+    for node in &network.nodes {
+        let label = escape_xml(&node.label);
+        let stroke = color_to_hex(status_color(node.status));
+        let _ = writeln!(
+            svg,
+            r#"    <g class="node" data-type="{}">"#,
+            escape_xml(node.node_type.label())
+        );
+        match (node.node_type, node.width, node.height) {
+            (NodeType::Group, Some(w), Some(h)) => {
+                let _ = writeln!(
+                    svg,
+                    r#"      <rect x="{:.1}" y="{:.1}" width="{w:.1}" height="{h:.1}" rx="8" fill="none" stroke="{stroke}" stroke-dasharray="4 4"/>"#,
+                    node.x, node.y
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"      <text x="{:.1}" y="{:.1}" font-size="12" fill="{text_primary}">{label}</text>"#,
+                    node.x + 8.0,
+                    node.y + 18.0
+                );
+            }
+            _ => {
+                let _ = writeln!(
+                    svg,
+                    r#"      <circle cx="{:.1}" cy="{:.1}" r="{NODE_RADIUS:.1}" fill="{surface}" stroke="{stroke}" stroke-width="2"/>"#,
+                    node.x, node.y
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"      <text x="{:.1}" y="{:.1}" font-size="12" text-anchor="middle" fill="{text_primary}">{label}</text>"#,
+                    node.x,
+                    node.y + NODE_RADIUS + 14.0
+                );
+                if !node.ip.is_empty() {
+                    let _ = writeln!(
+                        svg,
+                        r#"      <text x="{:.1}" y="{:.1}" font-size="10" text-anchor="middle" fill="{text_muted}">{}</text>"#,
+                        node.x,
+                        node.y + NODE_RADIUS + 27.0,
+                        escape_xml(&node.ip)
+                    );
+                }
+            }
+        }
+        svg.push_str("    </g>\n");
+    }
+    svg.push_str("  </g>\n");
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Directory exports are written to: `configured` (the
+/// [export directory setting](crate::views::settings::Settings::export_dir)) if
+/// set, otherwise `NetWatch` in the user's documents directory, falling back to
+/// the home directory.
+fn export_dir(configured: Option<PathBuf>) -> Option<PathBuf> {
+    if configured.is_some() {
+        return configured;
+    }
+    let dirs = UserDirs::new()?;
+    let base = dirs
+        .document_dir()
+        .map(|d| d.to_path_buf())
+        .unwrap_or_else(|| dirs.home_dir().to_path_buf());
+    Some(base.join("NetWatch"))
+}
+
+/// Render the network to SVG and write it to a timestamped file in the
+/// [export directory](export_dir).
+///
+/// Returns the path written.
+pub fn export_network_svg(
+    network: &NetworkState,
+    configured_dir: Option<PathBuf>,
+) -> Result<PathBuf, String> {
+    let dir = export_dir(configured_dir)
+        .ok_or_else(|| "Could not determine export directory".to_string())?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create export directory: {}", e))?;
+
+    let filename = format!(
+        "network-{}.svg",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = dir.join(filename);
+
+    fs::write(&path, render_network_svg(network))
+        .map_err(|e| format!("Failed to write SVG: {}", e))?;
+
+    tracing::info!("Exported canvas to {:?}", path);
+    Ok(path)
+}
+
+/// Quote a CSV field if it contains a comma, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One CSV record, CRLF-terminated.
+fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|f| csv_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Alerts as CSV with the dashboard's columns. Times are RFC 3339.
+pub fn alerts_csv(alerts: &[&Alert]) -> String {
+    let mut csv = csv_row(&[
+        "severity", "status", "source_tool", "device_ip", "first_seen", "last_seen", "count",
+    ]);
+    for alert in alerts {
+        csv.push_str(&csv_row(&[
+            alert.severity.clone(),
+            alert.status.clone(),
+            alert.source_tool.clone(),
+            alert.device_ip.clone().unwrap_or_default(),
+            alert.first_seen.to_rfc3339(),
+            alert.last_seen.to_rfc3339(),
+            alert.count.to_string(),
+        ]));
+    }
+    csv
+}
+
+/// Scans as CSV with the dashboard's columns. `duration` is in seconds and
+/// empty until the scan has both started and completed.
+pub fn scans_csv(scans: &[&Scan]) -> String {
+    let mut csv = csv_row(&["tool", "type", "target", "status", "duration", "devices_found"]);
+    for scan in scans {
+        let duration = match (scan.started_at, scan.completed_at) {
+            (Some(started), Some(completed)) => {
+                completed.signed_duration_since(started).num_seconds().to_string()
+            }
+            _ => String::new(),
+        };
+        csv.push_str(&csv_row(&[
+            scan.tool.clone(),
+            scan.scan_type.clone(),
+            scan.target.clone(),
+            scan.status.clone(),
+            duration,
+            scan.devices_found.to_string(),
+        ]));
+    }
+    csv
+}
+
+/// Ask for a destination, starting in the [export directory](export_dir), and
+/// write a CSV export there.
+///
+/// Returns `Ok(None)` if the dialog was cancelled.
+pub async fn save_csv_with_dialog(
+    file_name: String,
+    contents: String,
+    configured_dir: Option<PathBuf>,
+) -> Result<Option<PathBuf>, String> {
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .set_title("Export CSV")
+        .add_filter("CSV", &["csv"])
+        .set_file_name(file_name);
+    if let Some(dir) = export_dir(configured_dir) {
+        dialog = dialog.set_directory(dir);
+    }
+    let Some(handle) = dialog.save_file().await else {
+        return Ok(None);
+    };
+    let path = handle.path().to_path_buf();
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    tracing::info!("Exported CSV to {:?}", path);
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::NodeType;
+
+    fn sample() -> NetworkState {
+        let mut network = NetworkState::new();
+        let router = Node::new(
+            NodeType::Router,
+            400.0,
+            100.0,
+            "Main Router".to_string(),
+            "192.168.1.1".to_string(),
+        );
+        let mut laptop = Node::new(
+            NodeType::Workstation,
+            200.0,
+            250.0,
+            "Bob's <Laptop>".to_string(),
+            "192.168.1.20".to_string(),
+        );
+        laptop.status = NodeStatus::Compromised;
+        let phone = Node::new(
+            NodeType::Mobile,
+            600.0,
+            250.0,
+            "Phone".to_string(),
+            "192.168.1.30".to_string(),
+        );
+        let (r, l, p) = (router.id, laptop.id, phone.id);
+        network.add_node(router);
+        network.add_node(laptop);
+        network.add_node(phone);
+        network.add_connection(r, l, ConnectionType::Wired);
+        network.add_connection(r, p, ConnectionType::Wireless);
+        network
+    }
+
+    #[test]
+    fn test_svg_contains_nodes_and_edges() {
+        let network = sample();
+        let svg = render_network_svg(&network);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r#"class="node""#).count(), 3);
+        assert_eq!(svg.matches(r#"class="edge""#).count(), 2);
+        assert_eq!(svg.matches("stroke-dasharray=\"6 4\"").count(), 1);
+        assert!(svg.contains(">Main Router</text>"));
+        assert!(svg.contains(">192.168.1.30</text>"));
+    }
+
+    #[test]
+    fn test_svg_escapes_labels() {
+        let svg = render_network_svg(&sample());
+        assert!(svg.contains("Bob&apos;s &lt;Laptop&gt;"));
+        assert!(!svg.contains("<Laptop>"));
+    }
+
+    #[test]
+    fn test_svg_is_deterministic() {
+        let network = sample();
+        assert_eq!(render_network_svg(&network), render_network_svg(&network));
+    }
+
+    #[test]
+    fn test_svg_skips_dangling_edges() {
+        let mut network = sample();
+        let orphan = network.nodes[2].id;
+        network.nodes.retain(|n| n.id != orphan);
+        let svg = render_network_svg(&network);
+        assert_eq!(svg.matches(r#"class="node""#).count(), 2);
+        assert_eq!(svg.matches(r#"class="edge""#).count(), 1);
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_row(&["x", "y,z", ""]), "x,\"y,z\",\r\n");
+    }
+
+    #[test]
+    fn test_alerts_csv() {
+        let alert: Alert = serde_json::from_value(serde_json::json!({
+            "id": "a1",
+            "title": "Port scan",
+            "description": null,
+            "severity": "high",
+            "status": "open",
+            "source_tool": "zeek, suricata",
+            "source_event_id": null,
+            "category": null,
+            "device_ip": "10.0.0.9",
+            "device_id": null,
+            "fingerprint": null,
+            "count": 3,
+            "first_seen": "2024-06-15T12:00:00Z",
+            "last_seen": "2024-06-15T12:05:00Z",
+            "raw_data": null,
+            "correlation_id": null,
+            "notes": null,
+            "created_at": "2024-06-15T12:00:00Z",
+            "updated_at": "2024-06-15T12:05:00Z"
+        }))
+        .unwrap();
+        let csv = alerts_csv(&[&alert]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "severity,status,source_tool,device_ip,first_seen,last_seen,count"
+        );
+        assert_eq!(
+            lines[1],
+            "high,open,\"zeek, suricata\",10.0.0.9,\
+             2024-06-15T12:00:00+00:00,2024-06-15T12:05:00+00:00,3"
+        );
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_empty_network_renders() {
+        let svg = render_network_svg(&NetworkState::new());
+        assert_eq!(svg.matches(r#"class="node""#).count(), 0);
+        assert!(svg.contains("viewBox="));
+    }
+
+    #[test]
+    fn test_export_network_svg_to_configured_dir() {
+        let dir = std::env::temp_dir().join(format!("netwatch-export-{}", std::process::id()));
+        let path = export_network_svg(&sample(), Some(dir.clone())).unwrap();
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), render_network_svg(&sample()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
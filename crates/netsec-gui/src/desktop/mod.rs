@@ -1,5 +1,10 @@
-//! Desktop integration features: notifications, settings persistence, hotkeys.
+//! Desktop integration features: notifications, settings persistence, global
+//! hotkeys and in-window shortcuts, canvas export and project files.
+
+pub mod export;
 
 pub mod hotkeys;
 pub mod notifications;
 pub mod persistence;
+pub mod project;
+pub mod shortcuts;
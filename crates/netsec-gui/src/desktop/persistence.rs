@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::state::favorites::Favorites;
+use crate::state::traffic::DEFAULT_ANOMALY_THRESHOLD;
+use crate::theme::Palette;
 use crate::views::settings::Settings;
 
 /// Serializable settings format.
@@ -12,10 +15,20 @@ use crate::views::settings::Settings;
 pub struct PersistedSettings {
     pub api_url: String,
     pub ws_url: String,
+    #[serde(default)]
+    pub api_key: String,
     pub dark_mode: bool,
     pub notifications_enabled: bool,
     pub auto_refresh: bool,
     pub refresh_interval_secs: u32,
+    #[serde(default = "default_anomaly_threshold")]
+    pub traffic_anomaly_threshold: f32,
+    #[serde(default)]
+    pub export_dir: String,
+}
+
+fn default_anomaly_threshold() -> f32 {
+    DEFAULT_ANOMALY_THRESHOLD
 }
 
 impl From<&Settings> for PersistedSettings {
@@ -23,10 +36,13 @@ impl From<&Settings> for PersistedSettings {
         Self {
             api_url: s.api_url.clone(),
             ws_url: s.ws_url.clone(),
+            api_key: s.api_key.clone(),
             dark_mode: s.dark_mode,
             notifications_enabled: s.notifications_enabled,
             auto_refresh: s.auto_refresh,
             refresh_interval_secs: s.refresh_interval_secs,
+            traffic_anomaly_threshold: s.traffic_anomaly_threshold,
+            export_dir: s.export_dir.clone(),
         }
     }
 }
@@ -36,10 +52,13 @@ impl From<PersistedSettings> for Settings {
         Self {
             api_url: p.api_url,
             ws_url: p.ws_url,
+            api_key: p.api_key,
             dark_mode: p.dark_mode,
             notifications_enabled: p.notifications_enabled,
             auto_refresh: p.auto_refresh,
             refresh_interval_secs: p.refresh_interval_secs,
+            traffic_anomaly_threshold: p.traffic_anomaly_threshold,
+            export_dir: p.export_dir,
         }
     }
 }
@@ -100,6 +119,122 @@ pub fn save_settings(settings: &Settings) -> Result<(), String> {
     Ok(())
 }
 
+/// Get the custom theme file path. Dark and light mode each keep their own
+/// custom theme.
+fn theme_path(dark_mode: bool) -> Option<PathBuf> {
+    let file = if dark_mode { "theme.toml" } else { "theme-light.toml" };
+    ProjectDirs::from("com", "netwatch", "NetWatch").map(|dirs| {
+        dirs.config_dir().join(file)
+    })
+}
+
+/// Load the custom theme for dark or light mode from disk.
+///
+/// Returns `None` (keeping the built-in palette) when no theme was saved or
+/// the saved theme is unreadable or fails contrast validation.
+pub fn load_theme(dark_mode: bool) -> Option<Palette> {
+    let path = theme_path(dark_mode)?;
+
+    if !path.exists() {
+        tracing::debug!("Theme file not found at {:?}", path);
+        return None;
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(content) => match Palette::from_toml(&content) {
+            Ok(palette) => {
+                tracing::info!("Loaded custom theme from {:?}", path);
+                Some(palette)
+            }
+            Err(e) => {
+                tracing::error!("Ignoring custom theme: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to read theme file: {}", e);
+            None
+        }
+    }
+}
+
+/// Validate and save the custom theme for dark or light mode to disk.
+pub fn save_theme(palette: &Palette, dark_mode: bool) -> Result<(), String> {
+    palette.validate().map_err(|e| e.to_string())?;
+
+    let path = theme_path(dark_mode)
+        .ok_or_else(|| "Could not determine theme path".to_string())?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let content = palette.to_toml().map_err(|e| e.to_string())?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write theme file: {}", e))?;
+
+    tracing::info!("Saved custom theme to {:?}", path);
+    Ok(())
+}
+
+/// Get the pinned devices file path.
+fn favorites_path() -> Option<PathBuf> {
+    ProjectDirs::from("com", "netwatch", "NetWatch").map(|dirs| {
+        dirs.config_dir().join("favorites.toml")
+    })
+}
+
+/// Load pinned devices from disk.
+///
+/// The stored list is deduplicated and capped on load.
+pub fn load_favorites() -> Option<Favorites> {
+    let path = favorites_path()?;
+
+    if !path.exists() {
+        tracing::debug!("Favorites file not found at {:?}", path);
+        return None;
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(content) => match toml::from_str::<Favorites>(&content) {
+            Ok(stored) => {
+                tracing::info!("Loaded {} pinned devices from {:?}", stored.len(), path);
+                Some(Favorites::from_ips(stored.ips().to_vec()))
+            }
+            Err(e) => {
+                tracing::error!("Failed to parse favorites: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to read favorites file: {}", e);
+            None
+        }
+    }
+}
+
+/// Save pinned devices to disk.
+pub fn save_favorites(favorites: &Favorites) -> Result<(), String> {
+    let path = favorites_path()
+        .ok_or_else(|| "Could not determine favorites path".to_string())?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let content = toml::to_string_pretty(favorites)
+        .map_err(|e| format!("Failed to serialize favorites: {}", e))?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write favorites file: {}", e))?;
+
+    tracing::debug!("Saved pinned devices to {:?}", path);
+    Ok(())
+}
+
 /// Get the config directory path (for display to user).
 pub fn config_dir() -> Option<PathBuf> {
     ProjectDirs::from("com", "netwatch", "NetWatch").map(|dirs| {
@@ -0,0 +1,234 @@
+//! Project save/load: the network canvas persisted as versioned JSON.
+//!
+//! A project holds the nodes, connections, groups and viewport (pan/zoom) of a
+//! `NetworkState`. Selection, hover and scan progress are session state and
+//! are not saved.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::network::{Connection, Group, NetworkState, Node};
+
+/// Project file format version written by this build.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// File extension for project files.
+pub const PROJECT_EXTENSION: &str = "netwatch";
+
+/// Errors reading a project file.
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectError {
+    #[error("not a valid project file: {0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("project file version {found} is not supported (expected {PROJECT_VERSION})")]
+    UnsupportedVersion { found: u64 },
+    #[error("project file has no version")]
+    MissingVersion,
+}
+
+/// On-disk project layout.
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectFile {
+    version: u32,
+    nodes: Vec<Node>,
+    connections: Vec<Connection>,
+    #[serde(default)]
+    groups: Vec<Group>,
+    pan: (f32, f32),
+    zoom: f32,
+}
+
+/// Serialize the canvas of `network` as a project document.
+pub fn to_json(network: &NetworkState) -> Result<String, ProjectError> {
+    let file = ProjectFile {
+        version: PROJECT_VERSION,
+        nodes: network.nodes.clone(),
+        connections: network.connections.clone(),
+        groups: network.groups.clone(),
+        pan: network.pan,
+        zoom: network.zoom,
+    };
+    Ok(serde_json::to_string_pretty(&file)?)
+}
+
+/// Rebuild a `NetworkState` from a project document.
+///
+/// The version is checked before the rest of the document is parsed, so a
+/// file from a newer build reports a version mismatch rather than whatever
+/// field happened to change. Connections to nodes missing from the file are
+/// dropped.
+pub fn from_json(data: &str) -> Result<NetworkState, ProjectError> {
+    let value: serde_json::Value = serde_json::from_str(data)?;
+    let version = value
+        .get("version")
+        .ok_or(ProjectError::MissingVersion)?
+        .as_u64()
+        .ok_or(ProjectError::MissingVersion)?;
+    if version != u64::from(PROJECT_VERSION) {
+        return Err(ProjectError::UnsupportedVersion { found: version });
+    }
+    let file: ProjectFile = serde_json::from_value(value)?;
+
+    let mut network = NetworkState::new();
+    network.nodes = file.nodes;
+    let has_node = |id| network.nodes.iter().any(|n| n.id == id);
+    let connections = file
+        .connections
+        .into_iter()
+        .filter(|c| has_node(c.from) && has_node(c.to))
+        .collect();
+    network.connections = connections;
+    network.groups = file.groups;
+    network.pan = file.pan;
+    network.set_zoom(file.zoom);
+    Ok(network)
+}
+
+/// Ask for a destination and write `contents` there.
+///
+/// Returns `Ok(None)` if the dialog was cancelled.
+pub async fn save_with_dialog(contents: String) -> Result<Option<PathBuf>, String> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .set_title("Save Project")
+        .add_filter("NetWatch project", &[PROJECT_EXTENSION])
+        .set_file_name(format!("network.{PROJECT_EXTENSION}"))
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    let path = handle.path().to_path_buf();
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    tracing::info!("Saved project to {:?}", path);
+    Ok(Some(path))
+}
+
+/// Ask for a project file and read it.
+///
+/// Returns `Ok(None)` if the dialog was cancelled.
+pub async fn open_with_dialog() -> Result<Option<(PathBuf, String)>, String> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .set_title("Open Project")
+        .add_filter("NetWatch project", &[PROJECT_EXTENSION])
+        .pick_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    let path = handle.path().to_path_buf();
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(Some((path, contents)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{ConnectionType, NodeStatus, NodeType, Severity};
+    use crate::state::network::{Port, Vulnerability};
+
+    fn sample() -> NetworkState {
+        let mut network = NetworkState::new();
+        let router = Node::new(
+            NodeType::Router,
+            400.0,
+            100.0,
+            "Main Router".to_string(),
+            "192.168.1.1".to_string(),
+        );
+        let mut camera = Node::new(
+            NodeType::IoT,
+            620.5,
+            250.25,
+            "Camera".to_string(),
+            "192.168.1.100".to_string(),
+        );
+        camera.status = NodeStatus::Warning;
+        camera.mac = Some("aa:bb:cc:dd:ee:ff".to_string());
+        camera.ports.push(Port {
+            number: 554,
+            protocol: "tcp".to_string(),
+            state: "open".to_string(),
+            service_name: Some("rtsp".to_string()),
+            service_version: None,
+            http_title: None,
+            server_header: None,
+        });
+        camera.vulnerabilities.push(Vulnerability {
+            cve: "CVE-2023-12345".to_string(),
+            cvss: 8.5,
+            severity: Severity::High,
+            description: "RCE".to_string(),
+            references: Vec::new(),
+        });
+        let (r, c) = (router.id, camera.id);
+        network.add_node(router);
+        network.add_node(camera);
+        network.add_connection(r, c, ConnectionType::Wireless);
+        network.pan = (-35.0, 12.5);
+        network.zoom = 1.5;
+        network.select_node(c);
+        network
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let network = sample();
+        let loaded = from_json(&to_json(&network).unwrap()).unwrap();
+
+        assert_eq!(loaded.nodes.len(), 2);
+        for (a, b) in network.nodes.iter().zip(&loaded.nodes) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.node_type, b.node_type);
+            assert_eq!((a.x, a.y), (b.x, b.y));
+            assert_eq!(a.label, b.label);
+            assert_eq!(a.status, b.status);
+            assert_eq!(a.mac, b.mac);
+            assert_eq!(a.ports.len(), b.ports.len());
+            assert_eq!(a.vulnerabilities.len(), b.vulnerabilities.len());
+        }
+        assert_eq!(loaded.nodes[1].vulnerabilities[0].severity, Severity::High);
+        assert_eq!(loaded.connections.len(), 1);
+        assert_eq!(loaded.connections[0].id, network.connections[0].id);
+        assert_eq!(loaded.connections[0].connection_type, ConnectionType::Wireless);
+        assert_eq!(loaded.pan, (-35.0, 12.5));
+        assert_eq!(loaded.zoom, 1.5);
+        // Selection is session state
+        assert!(loaded.selected_ids.is_empty());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let mut value: serde_json::Value =
+            serde_json::from_str(&to_json(&sample()).unwrap()).unwrap();
+        value["version"] = serde_json::json!(PROJECT_VERSION + 1);
+        let err = from_json(&value.to_string()).unwrap_err();
+        assert!(matches!(err, ProjectError::UnsupportedVersion { found } if found == 2));
+
+        value.as_object_mut().unwrap().remove("version");
+        assert!(matches!(from_json(&value.to_string()), Err(ProjectError::MissingVersion)));
+    }
+
+    #[test]
+    fn test_malformed_file() {
+        assert!(matches!(from_json("not json"), Err(ProjectError::Malformed(_))));
+        assert!(matches!(
+            from_json(r#"{"version": 1, "nodes": "oops"}"#),
+            Err(ProjectError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_dangling_connections_dropped() {
+        let mut value: serde_json::Value =
+            serde_json::from_str(&to_json(&sample()).unwrap()).unwrap();
+        value["nodes"].as_array_mut().unwrap().truncate(1);
+        let loaded = from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.nodes.len(), 1);
+        assert!(loaded.connections.is_empty());
+    }
+}
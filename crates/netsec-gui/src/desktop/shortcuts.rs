@@ -0,0 +1,83 @@
+//! In-window keyboard shortcuts.
+//!
+//! Unlike the global hotkeys in [`super::hotkeys`], these only fire while the
+//! window is focused and no widget (such as a text input) consumed the key.
+//! `Ctrl` means `Cmd` on macOS.
+
+use iced::keyboard::{self, key::Named, Key, Modifiers};
+use iced::Subscription;
+
+use crate::message::Message;
+
+/// Map a key press to its shortcut message, if any.
+///
+/// | Shortcut | Action |
+/// |----------|--------|
+/// | `Ctrl+T` | Toggle the terminal panel |
+/// | `Ctrl+I` | Toggle the inspector panel |
+/// | `Ctrl+A` | Open the alerts dashboard |
+/// | `Ctrl+S` | Open the scans dashboard |
+/// | `Esc`    | Close the frontmost overlay |
+pub fn shortcut_message(key: &Key, modifiers: Modifiers) -> Option<Message> {
+    match key.as_ref() {
+        Key::Named(Named::Escape) if modifiers.is_empty() => Some(Message::CloseTopOverlay),
+        Key::Character(c) if modifiers.command() && !modifiers.shift() && !modifiers.alt() => {
+            match c.to_ascii_lowercase().as_str() {
+                "t" => Some(Message::ToggleTerminalPanel),
+                "i" => Some(Message::ToggleInspectorPanel),
+                "a" => Some(Message::ShowAlertsDashboard),
+                "s" => Some(Message::ShowScansDashboard),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Subscription delivering shortcut messages.
+pub fn subscription() -> Subscription<Message> {
+    keyboard::on_key_press(|key, modifiers| shortcut_message(&key, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn char_key(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn test_shortcut_message() {
+        let ctrl = Modifiers::COMMAND;
+        assert!(matches!(
+            shortcut_message(&char_key("t"), ctrl),
+            Some(Message::ToggleTerminalPanel)
+        ));
+        assert!(matches!(
+            shortcut_message(&char_key("I"), ctrl),
+            Some(Message::ToggleInspectorPanel)
+        ));
+        assert!(matches!(
+            shortcut_message(&char_key("a"), ctrl),
+            Some(Message::ShowAlertsDashboard)
+        ));
+        assert!(matches!(
+            shortcut_message(&char_key("s"), ctrl),
+            Some(Message::ShowScansDashboard)
+        ));
+        assert!(matches!(
+            shortcut_message(&Key::Named(Named::Escape), Modifiers::empty()),
+            Some(Message::CloseTopOverlay)
+        ));
+    }
+
+    #[test]
+    fn test_unmapped_keys() {
+        assert!(shortcut_message(&char_key("t"), Modifiers::empty()).is_none());
+        assert!(shortcut_message(&char_key("t"), Modifiers::COMMAND | Modifiers::SHIFT).is_none());
+        assert!(shortcut_message(&char_key("x"), Modifiers::COMMAND).is_none());
+        assert!(shortcut_message(&Key::Named(Named::Escape), Modifiers::SHIFT).is_none());
+        assert!(shortcut_message(&Key::Named(Named::Enter), Modifiers::empty()).is_none());
+    }
+}
@@ -4,12 +4,14 @@
 //! following the Elm architecture.
 
 use netsec_pty::ShellInfo;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::api::{
     self, Alert, AlertStats, Device, Scan, ScheduledJob, Tool, ToolHealth,
     TrafficFlow, Vulnerability, WsEvent, WsState,
 };
+use crate::state::notifications::NotificationKind;
 
 /// Unique identifier for a terminal tab.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -28,7 +30,7 @@ impl Default for TabId {
 }
 
 /// Unique identifier for a device/node.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeId(pub Uuid);
 
 impl NodeId {
@@ -44,7 +46,7 @@ impl Default for NodeId {
 }
 
 /// Unique identifier for a connection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ConnectionId(pub Uuid);
 
 impl ConnectionId {
@@ -54,7 +56,7 @@ impl ConnectionId {
 }
 
 /// Node types for the network canvas.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NodeType {
     Server,
     Firewall,
@@ -86,7 +88,7 @@ impl NodeType {
 }
 
 /// Node status on the network.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum NodeStatus {
     #[default]
     Online,
@@ -96,7 +98,7 @@ pub enum NodeStatus {
 }
 
 /// Vulnerability severity levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Severity {
     Low,
     Medium,
@@ -116,7 +118,7 @@ impl Severity {
 }
 
 /// Connection type between nodes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConnectionType {
     Wired,
     Wireless,
@@ -131,6 +133,16 @@ pub enum ToolMode {
     Pan,
 }
 
+/// Canvas layout algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutKind {
+    /// Hub-and-spoke around the router.
+    #[default]
+    Radial,
+    /// Force-directed (Fruchterman-Reingold).
+    Force,
+}
+
 /// Nmap scan types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NmapScanType {
@@ -216,6 +228,10 @@ pub enum Message {
     DeleteSelected,
     /// Group selected nodes
     GroupSelected,
+    /// Collapse (`true`) or expand a group
+    SetGroupCollapsed(NodeId, bool),
+    /// Delete a group, keeping (`true`) or deleting its member nodes
+    DeleteGroup(NodeId, bool),
     /// Node position changed
     NodeMoved(NodeId, f32, f32),
     /// Start connecting nodes
@@ -226,6 +242,8 @@ pub enum Message {
     CancelConnection,
     /// Hover over a connection
     ConnectionHovered(Option<ConnectionId>),
+    /// Re-lay out the canvas with the given algorithm
+    SetLayout(LayoutKind),
 
     // === Tool Mode ===
     /// Change the current tool mode
@@ -248,6 +266,10 @@ pub enum Message {
     SaveProject,
     /// Load a project
     LoadProject,
+    /// Project written (`None` if the dialog was cancelled)
+    ProjectSaved(Result<Option<std::path::PathBuf>, String>),
+    /// Project file read as (path, contents); `None` if the dialog was cancelled
+    ProjectOpened(Result<Option<(std::path::PathBuf, String)>, String>),
 
     // === UI Panels ===
     /// Toggle the terminal panel visibility
@@ -256,6 +278,28 @@ pub enum Message {
     ToggleInspectorPanel,
     /// Toggle the toolbar visibility
     ToggleToolbar,
+    /// Close the frontmost overlay (dashboard, panel or dialog)
+    CloseTopOverlay,
+    /// Show the notification center
+    ShowNotificationCenter,
+    /// Hide the notification center
+    HideNotificationCenter,
+    /// Open a notification: mark it read and jump to its detail
+    NotificationOpened(u64),
+    /// Filter notifications by kind (`None` shows all)
+    NotificationFilterKind(Option<NotificationKind>),
+    /// Toggle showing only unread notifications
+    NotificationToggleUnreadOnly,
+    /// Mark every notification read
+    NotificationsMarkAllRead,
+    /// Clear the notification history
+    NotificationsClear,
+    /// Toggle the pinned devices sidebar visibility
+    ToggleFavoritesPanel,
+    /// Pin or unpin a device by IP
+    TogglePinDevice(String),
+    /// Move a pinned device up (negative) or down (positive) in the list
+    FavoriteMove(String, isize),
     /// Show the vulnerability dashboard
     ShowVulnDashboard,
     /// Hide the vulnerability dashboard
@@ -278,9 +322,9 @@ pub enum Message {
     HideAlertsDashboard,
     /// Select an alert in the list
     AlertSelected(String),
-    /// Filter alerts by severity
+    /// Toggle a severity in the alert filter (`None` clears the selection)
     AlertFilterSeverity(Option<String>),
-    /// Filter alerts by status
+    /// Toggle a status in the alert filter (`None` clears the selection)
     AlertFilterStatus(Option<String>),
     /// Search alerts
     AlertSearch(String),
@@ -290,6 +334,10 @@ pub enum Message {
     ResolveAlert(String),
     /// Dismiss an alert
     DismissAlert(String),
+    /// Check or uncheck an alert for a bulk action
+    AlertChecked(String),
+    /// Set the status of several alerts at once: (alert IDs, status)
+    BulkUpdateAlerts(Vec<String>, String),
 
     // === Scans Dashboard ===
     /// Show the scans dashboard
@@ -335,6 +383,16 @@ pub enum Message {
     /// Toggle job enabled state
     ToggleJobEnabled(String),
 
+    // === Export ===
+    /// Export the network canvas as an SVG file
+    ExportCanvasSvg,
+    /// Export the filtered alerts list as CSV
+    ExportAlertsCsv,
+    /// Export the filtered scans list as CSV
+    ExportScansCsv,
+    /// CSV written (`None` if the dialog was cancelled)
+    CsvExported(Result<Option<std::path::PathBuf>, String>),
+
     // === Settings ===
     /// Show the settings panel
     ShowSettings,
@@ -344,6 +402,8 @@ pub enum Message {
     SettingsUpdateApiUrl(String),
     /// Update WebSocket URL setting
     SettingsUpdateWsUrl(String),
+    /// Update API key setting
+    SettingsUpdateApiKey(String),
     /// Toggle dark mode
     SettingsToggleDarkMode,
     /// Toggle notifications
@@ -352,9 +412,25 @@ pub enum Message {
     SettingsToggleAutoRefresh,
     /// Update refresh interval
     SettingsUpdateRefreshInterval(u32),
+    /// Update traffic anomaly threshold (factor over baseline)
+    SettingsUpdateAnomalyThreshold(f32),
+    /// Update export directory setting
+    SettingsUpdateExportDir(String),
     /// Save settings
     SettingsSave,
 
+    // === Theme Editor ===
+    /// Show the theme editor (closes settings)
+    ShowThemeEditor,
+    /// Hide the theme editor, reverting unsaved changes
+    HideThemeEditor,
+    /// Update a palette color (key, hex input)
+    ThemeEditorColorChanged(String, String),
+    /// Reset the editor to the built-in palette
+    ThemeEditorReset,
+    /// Validate and save the custom theme
+    ThemeEditorSave,
+
     // === Notifications ===
     /// Show a toast notification
     ShowToast(String, ToastLevel),
@@ -444,6 +520,8 @@ pub enum Message {
     UpdateAlert(String, api::AlertUpdate),
     /// Alert updated
     AlertUpdated(Result<Alert, String>),
+    /// Bulk status update finished, with each alert's ID and outcome
+    AlertsBulkUpdated(Vec<(String, Result<Alert, String>)>),
 
     // === Vulnerabilities ===
     /// Request to fetch vulnerabilities
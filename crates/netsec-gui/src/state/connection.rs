@@ -0,0 +1,98 @@
+//! User-facing notices for WebSocket connection changes.
+//!
+//! The event stream reports every connect attempt, so a backend that is down
+//! or flapping produces a burst of state changes. Only the first loss, the
+//! final failure and the recovery after a loss are worth a toast; the
+//! last-notified state filters out the repeats.
+
+use crate::api::WsState;
+use crate::message::ToastLevel;
+
+/// The last connection state the user was told about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notified {
+    Connected,
+    Lost,
+    Failed,
+}
+
+/// Decides which WebSocket state changes raise a toast.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionNotifier {
+    last: Option<Notified>,
+}
+
+impl ConnectionNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `state` and return the toast to show for it, if any.
+    ///
+    /// The first successful connect is silent; a connect after a loss or
+    /// failure is announced. Losses are announced once until the connection
+    /// comes back.
+    pub fn on_state(&mut self, state: &WsState) -> Option<(String, ToastLevel)> {
+        let (next, toast) = match (self.last, state) {
+            (None, WsState::Connected) => (Notified::Connected, None),
+            (Some(Notified::Lost | Notified::Failed), WsState::Connected) => (
+                Notified::Connected,
+                Some(("Backend connection restored".to_string(), ToastLevel::Success)),
+            ),
+            (None | Some(Notified::Connected), WsState::Disconnected) => (
+                Notified::Lost,
+                Some((
+                    "Lost connection to backend; reconnecting...".to_string(),
+                    ToastLevel::Warning,
+                )),
+            ),
+            (None | Some(Notified::Connected | Notified::Lost), WsState::Failed) => (
+                Notified::Failed,
+                Some((
+                    "Backend unreachable; live updates stopped".to_string(),
+                    ToastLevel::Warning,
+                )),
+            ),
+            _ => return None,
+        };
+        self.last = Some(next);
+        toast
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toast(notifier: &mut ConnectionNotifier, state: WsState) -> Option<ToastLevel> {
+        notifier.on_state(&state).map(|(_, level)| level)
+    }
+
+    #[test]
+    fn test_connect_disconnect_reconnect() {
+        let mut notifier = ConnectionNotifier::new();
+        assert_eq!(toast(&mut notifier, WsState::Connecting), None);
+        assert_eq!(toast(&mut notifier, WsState::Connected), None);
+
+        assert_eq!(toast(&mut notifier, WsState::Disconnected), Some(ToastLevel::Warning));
+        // Failed reconnect attempts don't repeat the warning
+        for attempt in 1..4 {
+            assert_eq!(toast(&mut notifier, WsState::Reconnecting { attempt }), None);
+            assert_eq!(toast(&mut notifier, WsState::Disconnected), None);
+        }
+
+        assert_eq!(toast(&mut notifier, WsState::Connected), Some(ToastLevel::Success));
+        assert_eq!(toast(&mut notifier, WsState::Connected), None);
+        assert_eq!(toast(&mut notifier, WsState::Disconnected), Some(ToastLevel::Warning));
+    }
+
+    #[test]
+    fn test_failure_announced_once() {
+        let mut notifier = ConnectionNotifier::new();
+        assert_eq!(toast(&mut notifier, WsState::Disconnected), Some(ToastLevel::Warning));
+        assert_eq!(toast(&mut notifier, WsState::Failed), Some(ToastLevel::Warning));
+        assert_eq!(toast(&mut notifier, WsState::Failed), None);
+        assert_eq!(toast(&mut notifier, WsState::Disconnected), None);
+        assert_eq!(toast(&mut notifier, WsState::Connected), Some(ToastLevel::Success));
+    }
+}
@@ -0,0 +1,327 @@
+//! Diffing of device inventory refreshes.
+//!
+//! Every device fetch replaces the whole inventory. Comparing a compact
+//! snapshot of the previous and current sets lets the canvas keep known nodes
+//! where they are, place only genuinely new ones, and briefly highlight the
+//! devices whose status, ports or vulnerabilities actually changed.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::api::{Device, Vulnerability};
+
+/// The fields of a device that the canvas cares about, keyed by IP in a
+/// [`Snapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceState {
+    pub status: String,
+    pub hostname: Option<String>,
+    pub vendor: Option<String>,
+    pub os_family: Option<String>,
+    /// `(port, protocol, state)` triples.
+    pub ports: BTreeSet<(u16, String, String)>,
+    /// CVE ids (or vulnerability ids when no CVE is known).
+    pub vulnerabilities: BTreeSet<String>,
+}
+
+/// Device states keyed by IP address.
+pub type Snapshot = BTreeMap<String, DeviceState>;
+
+/// A field group that differs between two snapshots of one device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangedField {
+    Status,
+    Ports,
+    Vulnerabilities,
+    /// Hostname, vendor or OS.
+    Details,
+}
+
+/// A device present in both snapshots whose state differs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceChange {
+    pub ip: String,
+    pub fields: Vec<ChangedField>,
+}
+
+impl DeviceChange {
+    /// Whether the change is worth drawing attention to on the canvas.
+    pub fn is_notable(&self) -> bool {
+        self.fields.iter().any(|f| {
+            matches!(
+                f,
+                ChangedField::Status | ChangedField::Ports | ChangedField::Vulnerabilities
+            )
+        })
+    }
+}
+
+/// Result of comparing two snapshots. IPs are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<DeviceChange>,
+    pub removed: Vec<String>,
+}
+
+impl DeviceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Build a snapshot from the cached devices and vulnerabilities.
+///
+/// Vulnerabilities are attached through their `device_id`; ones without a
+/// known device are ignored.
+pub fn snapshot(devices: &[Device], vulnerabilities: &[Vulnerability]) -> Snapshot {
+    let ip_by_id: HashMap<&str, &str> = devices
+        .iter()
+        .map(|d| (d.id.as_str(), d.ip_address.as_str()))
+        .collect();
+
+    let mut snapshot: Snapshot = devices
+        .iter()
+        .map(|d| {
+            let state = DeviceState {
+                status: d.status.clone(),
+                hostname: d.hostname.clone(),
+                vendor: d.vendor.clone(),
+                os_family: d.os_family.clone(),
+                ports: d
+                    .ports
+                    .iter()
+                    .map(|p| (p.port_number, p.protocol.clone(), p.state.clone()))
+                    .collect(),
+                vulnerabilities: BTreeSet::new(),
+            };
+            (d.ip_address.clone(), state)
+        })
+        .collect();
+
+    for vuln in vulnerabilities {
+        let Some(ip) = vuln.device_id.as_deref().and_then(|id| ip_by_id.get(id)) else {
+            continue;
+        };
+        if let Some(state) = snapshot.get_mut(*ip) {
+            state
+                .vulnerabilities
+                .insert(vuln.cve_id.clone().unwrap_or_else(|| vuln.id.clone()));
+        }
+    }
+
+    snapshot
+}
+
+/// Compare two snapshots.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> DeviceDiff {
+    let mut result = DeviceDiff::default();
+
+    for (ip, new) in after {
+        let Some(old) = before.get(ip) else {
+            result.added.push(ip.clone());
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        if old.status != new.status {
+            fields.push(ChangedField::Status);
+        }
+        if old.ports != new.ports {
+            fields.push(ChangedField::Ports);
+        }
+        if old.vulnerabilities != new.vulnerabilities {
+            fields.push(ChangedField::Vulnerabilities);
+        }
+        if old.hostname != new.hostname || old.vendor != new.vendor || old.os_family != new.os_family
+        {
+            fields.push(ChangedField::Details);
+        }
+        if !fields.is_empty() {
+            result.changed.push(DeviceChange {
+                ip: ip.clone(),
+                fields,
+            });
+        }
+    }
+
+    result.removed = before
+        .keys()
+        .filter(|ip| !after.contains_key(*ip))
+        .cloned()
+        .collect();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Port;
+    use chrono::Utc;
+
+    fn device(id: &str, ip: &str, status: &str, ports: &[u16]) -> Device {
+        let now = Utc::now();
+        Device {
+            id: id.to_string(),
+            ip_address: ip.to_string(),
+            mac_address: None,
+            hostname: None,
+            vendor: None,
+            os_family: None,
+            os_version: None,
+            device_type: None,
+            status: status.to_string(),
+            first_seen: now,
+            last_seen: now,
+            notes: None,
+            ports: ports
+                .iter()
+                .map(|&n| Port {
+                    id: format!("{id}-{n}"),
+                    port_number: n,
+                    protocol: "tcp".to_string(),
+                    state: "open".to_string(),
+                    service_name: None,
+                    service_version: None,
+                    banner: None,
+                    http_title: None,
+                    server_header: None,
+                })
+                .collect(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn vuln(device_id: &str, cve: &str) -> Vulnerability {
+        let now = Utc::now();
+        Vulnerability {
+            id: format!("v-{cve}"),
+            title: cve.to_string(),
+            description: None,
+            cve_id: Some(cve.to_string()),
+            cvss_score: Some(7.5),
+            severity: "high".to_string(),
+            status: "open".to_string(),
+            device_id: Some(device_id.to_string()),
+            port: None,
+            service: None,
+            source_tool: "nmap".to_string(),
+            solution: None,
+            references: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_identical_sets_produce_empty_diff() {
+        let devices = vec![
+            device("a", "10.0.0.1", "online", &[22, 80]),
+            device("b", "10.0.0.2", "online", &[]),
+        ];
+        let before = snapshot(&devices, &[]);
+        // Fresh fetch: same content, new timestamps
+        let after = snapshot(&devices.clone(), &[]);
+        assert!(diff(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed() {
+        let before = snapshot(
+            &[
+                device("a", "10.0.0.1", "online", &[]),
+                device("b", "10.0.0.2", "online", &[]),
+            ],
+            &[],
+        );
+        let after = snapshot(
+            &[
+                device("a", "10.0.0.1", "online", &[]),
+                device("c", "10.0.0.3", "online", &[]),
+            ],
+            &[],
+        );
+
+        let result = diff(&before, &after);
+        assert_eq!(result.added, vec!["10.0.0.3"]);
+        assert_eq!(result.removed, vec!["10.0.0.2"]);
+        assert!(result.changed.is_empty());
+    }
+
+    #[test]
+    fn test_first_load_is_all_added() {
+        let after = snapshot(&[device("a", "10.0.0.1", "online", &[])], &[]);
+        let result = diff(&Snapshot::new(), &after);
+        assert_eq!(result.added, vec!["10.0.0.1"]);
+        assert!(result.changed.is_empty() && result.removed.is_empty());
+    }
+
+    #[test]
+    fn test_status_and_port_changes() {
+        let before = snapshot(
+            &[
+                device("a", "10.0.0.1", "online", &[22]),
+                device("b", "10.0.0.2", "online", &[80]),
+            ],
+            &[],
+        );
+        let after = snapshot(
+            &[
+                device("a", "10.0.0.1", "offline", &[22]),
+                device("b", "10.0.0.2", "online", &[80, 443]),
+            ],
+            &[],
+        );
+
+        let result = diff(&before, &after);
+        assert_eq!(
+            result.changed,
+            vec![
+                DeviceChange {
+                    ip: "10.0.0.1".to_string(),
+                    fields: vec![ChangedField::Status],
+                },
+                DeviceChange {
+                    ip: "10.0.0.2".to_string(),
+                    fields: vec![ChangedField::Ports],
+                },
+            ]
+        );
+        assert!(result.changed.iter().all(DeviceChange::is_notable));
+    }
+
+    #[test]
+    fn test_vulnerability_changes_by_device_id() {
+        let devices = vec![
+            device("a", "10.0.0.1", "online", &[]),
+            device("b", "10.0.0.2", "online", &[]),
+        ];
+        let before = snapshot(&devices, &[vuln("a", "CVE-2024-0001")]);
+        let after = snapshot(
+            &devices,
+            &[
+                vuln("a", "CVE-2024-0001"),
+                vuln("b", "CVE-2024-0002"),
+                vuln("unknown", "CVE-2024-0003"),
+            ],
+        );
+
+        let result = diff(&before, &after);
+        assert_eq!(result.changed.len(), 1);
+        assert_eq!(result.changed[0].ip, "10.0.0.2");
+        assert_eq!(result.changed[0].fields, vec![ChangedField::Vulnerabilities]);
+    }
+
+    #[test]
+    fn test_detail_only_change_is_not_notable() {
+        let before = snapshot(&[device("a", "10.0.0.1", "online", &[])], &[]);
+        let mut renamed = device("a", "10.0.0.1", "online", &[]);
+        renamed.hostname = Some("nas".to_string());
+        let after = snapshot(&[renamed], &[]);
+
+        let result = diff(&before, &after);
+        assert_eq!(result.changed[0].fields, vec![ChangedField::Details]);
+        assert!(!result.changed[0].is_notable());
+    }
+}
@@ -0,0 +1,167 @@
+//! Pinned (favorite) devices for the quick-access sidebar.
+//!
+//! Favorites are keyed by device IP, which is how canvas nodes are matched to
+//! backend devices, so pins survive restarts and node re-creation. The list
+//! is ordered, deduplicated, and capped at [`MAX_FAVORITES`].
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of pinned devices.
+pub const MAX_FAVORITES: usize = 12;
+
+/// Ordered list of pinned device IPs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Favorites {
+    #[serde(default)]
+    devices: Vec<String>,
+}
+
+impl Favorites {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from a stored list, dropping blanks and duplicates and
+    /// truncating to the cap.
+    pub fn from_ips<I: IntoIterator<Item = String>>(ips: I) -> Self {
+        let mut favorites = Self::new();
+        for ip in ips {
+            favorites.add(&ip);
+        }
+        favorites
+    }
+
+    /// Pinned IPs in display order.
+    pub fn ips(&self) -> &[String] {
+        &self.devices
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.devices.len() >= MAX_FAVORITES
+    }
+
+    pub fn contains(&self, ip: &str) -> bool {
+        self.position(ip).is_some()
+    }
+
+    fn position(&self, ip: &str) -> Option<usize> {
+        let ip = ip.trim();
+        self.devices.iter().position(|d| d == ip)
+    }
+
+    /// Pin a device at the end of the list.
+    ///
+    /// Returns `false` if the IP is blank, already pinned, or the list is full.
+    pub fn add(&mut self, ip: &str) -> bool {
+        let ip = ip.trim();
+        if ip.is_empty() || self.contains(ip) || self.is_full() {
+            return false;
+        }
+        self.devices.push(ip.to_string());
+        true
+    }
+
+    /// Unpin a device. Returns `false` if it was not pinned.
+    pub fn remove(&mut self, ip: &str) -> bool {
+        match self.position(ip) {
+            Some(index) => {
+                self.devices.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pin if unpinned, unpin if pinned. Returns whether the device is now pinned.
+    pub fn toggle(&mut self, ip: &str) -> bool {
+        if self.remove(ip) {
+            false
+        } else {
+            self.add(ip)
+        }
+    }
+
+    /// Move a pinned device by `offset` positions, clamped to the list bounds.
+    ///
+    /// Returns `false` if the device is not pinned or did not move.
+    pub fn shift(&mut self, ip: &str, offset: isize) -> bool {
+        let Some(from) = self.position(ip) else {
+            return false;
+        };
+        let last = self.devices.len() - 1;
+        let to = from.saturating_add_signed(offset).min(last);
+        if to == from {
+            return false;
+        }
+        let item = self.devices.remove(from);
+        self.devices.insert(to, item);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ips(favorites: &Favorites) -> Vec<&str> {
+        favorites.ips().iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_add_dedups_and_trims() {
+        let mut favorites = Favorites::new();
+        assert!(favorites.add("10.0.0.1"));
+        assert!(!favorites.add(" 10.0.0.1 "));
+        assert!(!favorites.add("  "));
+        assert!(favorites.add("10.0.0.2"));
+        assert_eq!(ips(&favorites), vec!["10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    fn test_cap() {
+        let mut favorites = Favorites::from_ips((0..MAX_FAVORITES + 3).map(|i| format!("10.0.0.{i}")));
+        assert_eq!(favorites.len(), MAX_FAVORITES);
+        assert!(favorites.is_full());
+        assert!(!favorites.add("192.168.1.1"));
+
+        // Removing one frees a slot
+        assert!(favorites.remove("10.0.0.0"));
+        assert!(favorites.add("192.168.1.1"));
+        assert_eq!(favorites.ips().last().unwrap(), "192.168.1.1");
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut favorites = Favorites::new();
+        assert!(favorites.toggle("10.0.0.1"));
+        assert!(favorites.contains("10.0.0.1"));
+        assert!(!favorites.toggle("10.0.0.1"));
+        assert!(favorites.is_empty());
+        assert!(!favorites.remove("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_shift_reorders_and_clamps() {
+        let mut favorites = Favorites::from_ips(["a", "b", "c"].map(String::from));
+        assert!(favorites.shift("c", -1));
+        assert_eq!(ips(&favorites), vec!["a", "c", "b"]);
+        assert!(favorites.shift("a", 10));
+        assert_eq!(ips(&favorites), vec!["c", "b", "a"]);
+        assert!(!favorites.shift("c", -1));
+        assert!(!favorites.shift("missing", 1));
+    }
+
+    #[test]
+    fn test_from_ips_dedups() {
+        let favorites = Favorites::from_ips(["a", "b", "a", ""].map(String::from));
+        assert_eq!(ips(&favorites), vec!["a", "b"]);
+    }
+}
@@ -1,4 +1,9 @@
 //! Application state modules.
 
+pub mod connection;
+pub mod device_diff;
+pub mod favorites;
 pub mod network;
+pub mod notifications;
 pub mod terminal;
+pub mod traffic;
@@ -4,6 +4,8 @@ use crate::message::{
     ConnectionId, ConnectionType, NodeId, NodeStatus, NodeType, Severity,
 };
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
 /// Layout configuration for radial positioning
 pub mod layout {
@@ -12,10 +14,22 @@ pub mod layout {
     pub const SPOKE_Y_OFFSET: f32 = 150.0;
     pub const BASE_RADIUS: f32 = 180.0;
     pub const RADIUS_VARIATION: f32 = 60.0;
+    /// Space between a group's members and its container edge.
+    pub const GROUP_PADDING: f32 = 40.0;
+    /// Area the force-directed layout keeps nodes inside.
+    pub const FORCE_AREA_WIDTH: f32 = 800.0;
+    pub const FORCE_AREA_HEIGHT: f32 = 700.0;
+    /// Iterations run by the canvas when switching to the force layout.
+    pub const FORCE_ITERATIONS: usize = 200;
+    /// Upper bound on force layout iterations, to keep the UI responsive.
+    pub const MAX_FORCE_ITERATIONS: usize = 500;
+    /// Size of a collapsed group's super-node.
+    pub const COLLAPSED_GROUP_WIDTH: f32 = 120.0;
+    pub const COLLAPSED_GROUP_HEIGHT: f32 = 60.0;
 }
 
 /// A vulnerability detected on a node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
     pub cve: String,
     pub cvss: f32,
@@ -25,17 +39,19 @@ pub struct Vulnerability {
 }
 
 /// An open port on a node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Port {
     pub number: u16,
     pub protocol: String,
     pub state: String,
     pub service_name: Option<String>,
     pub service_version: Option<String>,
+    pub http_title: Option<String>,
+    pub server_header: Option<String>,
 }
 
 /// A node on the network canvas.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Node {
     pub id: NodeId,
     pub node_type: NodeType,
@@ -57,6 +73,10 @@ pub struct Node {
     /// For group nodes
     pub width: Option<f32>,
     pub height: Option<f32>,
+    /// Set when a refresh changed this device; the canvas highlights the
+    /// node until this instant.
+    #[serde(skip)]
+    pub highlight_until: Option<Instant>,
 }
 
 impl Node {
@@ -81,6 +101,7 @@ impl Node {
             parent_id: None,
             width: None,
             height: None,
+            highlight_until: None,
         }
     }
 
@@ -101,10 +122,14 @@ impl Node {
             .filter(|v| matches!(v.severity, Severity::High))
             .count()
     }
+
+    pub fn is_highlighted(&self) -> bool {
+        self.highlight_until.is_some()
+    }
 }
 
 /// A connection between two nodes.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
     pub id: ConnectionId,
     pub from: NodeId,
@@ -129,11 +154,25 @@ impl Connection {
     }
 }
 
+/// A named set of nodes drawn inside one container on the canvas.
+///
+/// Groups share the node ID space so the canvas can select and move them
+/// like nodes. A node belongs to at most one group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Group {
+    pub id: NodeId,
+    pub label: String,
+    pub members: Vec<NodeId>,
+    /// Drawn as a single super-node instead of a container.
+    pub collapsed: bool,
+}
+
 /// Network canvas state.
 #[derive(Debug, Default)]
 pub struct NetworkState {
     pub nodes: Vec<Node>,
     pub connections: Vec<Connection>,
+    pub groups: Vec<Group>,
     pub selected_ids: Vec<NodeId>,
     pub hovered_connection: Option<ConnectionId>,
     pub pan: (f32, f32),
@@ -148,6 +187,7 @@ impl NetworkState {
         Self {
             nodes: Vec::new(),
             connections: Vec::new(),
+            groups: Vec::new(),
             selected_ids: Vec::new(),
             hovered_connection: None,
             pan: (0.0, 0.0),
@@ -163,11 +203,22 @@ impl NetworkState {
         self.nodes.push(node);
     }
 
-    /// Remove a node and its connections.
+    /// Remove a node and its connections. A group left without members is
+    /// removed too.
     pub fn remove_node(&mut self, id: NodeId) {
         self.nodes.retain(|n| n.id != id);
         self.connections.retain(|c| c.from != id && c.to != id);
         self.selected_ids.retain(|&sid| sid != id);
+        for group in &mut self.groups {
+            group.members.retain(|&m| m != id);
+        }
+        let emptied: Vec<NodeId> = self.groups
+            .iter()
+            .filter(|g| g.members.is_empty())
+            .map(|g| g.id)
+            .collect();
+        self.groups.retain(|g| !g.members.is_empty());
+        self.selected_ids.retain(|sid| !emptied.contains(sid));
     }
 
     /// Get a node by ID.
@@ -205,11 +256,111 @@ impl NetworkState {
         self.selected_ids.first().and_then(|&id| self.get_node(id))
     }
 
-    /// Delete all selected nodes.
+    /// Delete all selected nodes. Selected groups are dissolved, keeping
+    /// their members.
     pub fn delete_selected(&mut self) {
         let ids: Vec<_> = self.selected_ids.clone();
         for id in ids {
-            self.remove_node(id);
+            if self.get_group(id).is_some() {
+                self.remove_group(id, true);
+            } else {
+                self.remove_node(id);
+            }
+        }
+    }
+
+    /// Get a group by ID.
+    pub fn get_group(&self, id: NodeId) -> Option<&Group> {
+        self.groups.iter().find(|g| g.id == id)
+    }
+
+    /// The group a node belongs to, if any.
+    pub fn group_of(&self, node_id: NodeId) -> Option<&Group> {
+        self.groups.iter().find(|g| g.members.contains(&node_id))
+    }
+
+    /// Get the first selected group.
+    pub fn selected_group(&self) -> Option<&Group> {
+        self.selected_ids.first().and_then(|&id| self.get_group(id))
+    }
+
+    /// Group the selected nodes and select the new group.
+    ///
+    /// Selected nodes already in another group are moved out of it. Returns
+    /// `None` without changing anything unless at least two nodes are
+    /// selected.
+    pub fn group_selected(&mut self, label: String) -> Option<NodeId> {
+        let members: Vec<NodeId> = self.selected_ids
+            .iter()
+            .copied()
+            .filter(|&id| self.get_node(id).is_some())
+            .collect();
+        if members.len() < 2 {
+            return None;
+        }
+
+        for group in &mut self.groups {
+            group.members.retain(|m| !members.contains(m));
+        }
+        self.groups.retain(|g| !g.members.is_empty());
+
+        let id = NodeId::new();
+        for node in self.nodes.iter_mut().filter(|n| members.contains(&n.id)) {
+            node.parent_id = Some(id);
+        }
+        self.groups.push(Group {
+            id,
+            label,
+            members,
+            collapsed: false,
+        });
+        self.select_node(id);
+        Some(id)
+    }
+
+    /// Collapse or expand a group.
+    pub fn set_group_collapsed(&mut self, id: NodeId, collapsed: bool) {
+        if let Some(group) = self.groups.iter_mut().find(|g| g.id == id) {
+            group.collapsed = collapsed;
+        }
+    }
+
+    /// Remove a group, either releasing its members onto the canvas
+    /// (`keep_members`) or deleting them along with it.
+    pub fn remove_group(&mut self, id: NodeId, keep_members: bool) {
+        let Some(index) = self.groups.iter().position(|g| g.id == id) else {
+            return;
+        };
+        let group = self.groups.remove(index);
+        self.selected_ids.retain(|&sid| sid != id);
+        for member in group.members {
+            if keep_members {
+                if let Some(node) = self.get_node_mut(member) {
+                    node.parent_id = None;
+                }
+            } else {
+                self.remove_node(member);
+            }
+        }
+    }
+
+    /// Canvas rectangle of a group as `(x, y, width, height)`: the padded
+    /// bounds of its members, or a fixed-size box centred on them when
+    /// collapsed.
+    pub fn group_bounds(&self, group: &Group) -> Option<(f32, f32, f32, f32)> {
+        let (min_x, min_y, max_x, max_y) = group
+            .members
+            .iter()
+            .filter_map(|&id| self.get_node(id))
+            .map(|n| (n.x, n.y, n.x, n.y))
+            .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))?;
+        if group.collapsed {
+            let (w, h) = (layout::COLLAPSED_GROUP_WIDTH, layout::COLLAPSED_GROUP_HEIGHT);
+            let (cx, cy) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+            Some((cx - w / 2.0, cy - h / 2.0, w, h))
+        } else {
+            let pad = layout::GROUP_PADDING;
+            Some((min_x - pad, min_y - pad, max_x - min_x + 2.0 * pad, max_y - min_y + 2.0 * pad))
         }
     }
 
@@ -260,11 +411,25 @@ impl NetworkState {
         (critical, high, medium, low)
     }
 
-    /// Move a node to a new position.
+    /// Move a node to a new position. Moving a group moves its members so
+    /// the group's rectangle starts at `(x, y)`.
     pub fn move_node(&mut self, id: NodeId, x: f32, y: f32) {
         if let Some(node) = self.get_node_mut(id) {
             node.x = x;
             node.y = y;
+            return;
+        }
+        let Some(group) = self.get_group(id) else {
+            return;
+        };
+        let Some((gx, gy, _, _)) = self.group_bounds(group) else {
+            return;
+        };
+        let (dx, dy) = (x - gx, y - gy);
+        let members = group.members.clone();
+        for node in self.nodes.iter_mut().filter(|n| members.contains(&n.id)) {
+            node.x += dx;
+            node.y += dy;
         }
     }
 
@@ -339,6 +504,161 @@ impl NetworkState {
         }
     }
 
+    /// Apply a force-directed layout (Fruchterman-Reingold).
+    ///
+    /// Every pair of nodes repels, connected nodes attract, and each step's
+    /// movement is limited by a temperature that cools linearly to zero.
+    /// Starts from the current positions and keeps nodes within the layout
+    /// area. Deterministic: nodes sharing a position are pushed apart along
+    /// fixed directions. `iterations` is capped at
+    /// [`MAX_FORCE_ITERATIONS`](layout::MAX_FORCE_ITERATIONS).
+    pub fn apply_force_layout(&mut self, iterations: usize) {
+        let n = self.nodes.len();
+        if n < 2 {
+            return;
+        }
+        let iterations = iterations.min(layout::MAX_FORCE_ITERATIONS);
+        let (width, height) = (layout::FORCE_AREA_WIDTH, layout::FORCE_AREA_HEIGHT);
+        // Ideal edge length for the area shared between all nodes
+        let k = (width * height / n as f32).sqrt();
+
+        let index: std::collections::HashMap<NodeId, usize> =
+            self.nodes.iter().enumerate().map(|(i, n)| (n.id, i)).collect();
+        let edges: Vec<(usize, usize)> = self.connections
+            .iter()
+            .filter_map(|c| Some((*index.get(&c.from)?, *index.get(&c.to)?)))
+            .filter(|(a, b)| a != b)
+            .collect();
+
+        let mut pos: Vec<(f32, f32)> = self.nodes
+            .iter()
+            .map(|n| (n.x.clamp(0.0, width), n.y.clamp(0.0, height)))
+            .collect();
+        let initial_temperature = width / 10.0;
+
+        for step in 0..iterations {
+            let mut disp = vec![(0.0_f32, 0.0_f32); n];
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let (mut dx, mut dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                    let mut dist = (dx * dx + dy * dy).sqrt();
+                    if dist < 0.01 {
+                        // Coincident nodes: separate along a fixed per-pair angle
+                        let angle = (i * n + j) as f32;
+                        (dx, dy, dist) = (angle.cos(), angle.sin(), 1.0);
+                    }
+                    let force = k * k / dist;
+                    let (fx, fy) = (dx / dist * force, dy / dist * force);
+                    disp[i].0 += fx;
+                    disp[i].1 += fy;
+                    disp[j].0 -= fx;
+                    disp[j].1 -= fy;
+                }
+            }
+
+            for &(a, b) in &edges {
+                let (dx, dy) = (pos[a].0 - pos[b].0, pos[a].1 - pos[b].1);
+                let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                let force = dist * dist / k;
+                let (fx, fy) = (dx / dist * force, dy / dist * force);
+                disp[a].0 -= fx;
+                disp[a].1 -= fy;
+                disp[b].0 += fx;
+                disp[b].1 += fy;
+            }
+
+            let temperature = initial_temperature * (1.0 - step as f32 / iterations as f32);
+            for (p, d) in pos.iter_mut().zip(&disp) {
+                let len = (d.0 * d.0 + d.1 * d.1).sqrt();
+                if len > 0.0 {
+                    let limited = len.min(temperature);
+                    p.0 = (p.0 + d.0 / len * limited).clamp(0.0, width);
+                    p.1 = (p.1 + d.1 / len * limited).clamp(0.0, height);
+                }
+            }
+        }
+
+        for (node, (x, y)) in self.nodes.iter_mut().zip(pos) {
+            node.x = x;
+            node.y = y;
+        }
+    }
+
+    /// Position newly added nodes without moving existing ones.
+    ///
+    /// Each new spoke goes into the middle of the widest angular gap around
+    /// the hub. Falls back to a full [`apply_radial_layout`](Self::apply_radial_layout)
+    /// when the hub itself is new or there are no placed spokes yet.
+    pub fn place_new_nodes(&mut self, new_ids: &[NodeId]) {
+        use std::f32::consts::TAU;
+
+        if new_ids.is_empty() {
+            return;
+        }
+        let hub_id = match self.find_hub_node() {
+            Some(id) => id,
+            None => return,
+        };
+        let has_placed_spokes = self.nodes
+            .iter()
+            .any(|n| n.id != hub_id && !new_ids.contains(&n.id));
+        if new_ids.contains(&hub_id) || !has_placed_spokes {
+            self.apply_radial_layout();
+            return;
+        }
+
+        let (center_x, center_y) = match self.get_node(hub_id) {
+            Some(hub) => (hub.x, hub.y + layout::SPOKE_Y_OFFSET),
+            None => return,
+        };
+
+        let mut angles: Vec<f32> = self.nodes
+            .iter()
+            .filter(|n| n.id != hub_id && !new_ids.contains(&n.id))
+            .map(|n| (n.y - center_y).atan2(n.x - center_x).rem_euclid(TAU))
+            .collect();
+
+        let radius = layout::BASE_RADIUS + layout::RADIUS_VARIATION / 2.0;
+        for id in new_ids {
+            angles.sort_by(|a, b| a.total_cmp(b));
+            // Widest gap, including the wrap-around from last back to first
+            let mut best = (angles[angles.len() - 1], angles[0] + TAU - angles[angles.len() - 1]);
+            for pair in angles.windows(2) {
+                let gap = pair[1] - pair[0];
+                if gap > best.1 {
+                    best = (pair[0], gap);
+                }
+            }
+            let angle = (best.0 + best.1 / 2.0).rem_euclid(TAU);
+            angles.push(angle);
+
+            if let Some(node) = self.get_node_mut(*id) {
+                node.x = center_x + angle.cos() * radius;
+                node.y = center_y + angle.sin() * radius;
+            }
+        }
+    }
+
+    /// Highlight a node until `until`.
+    pub fn highlight_node(&mut self, id: NodeId, until: Instant) {
+        if let Some(node) = self.get_node_mut(id) {
+            node.highlight_until = Some(until);
+        }
+    }
+
+    /// Clear highlights that have expired. Returns `true` if any were cleared.
+    pub fn clear_expired_highlights(&mut self, now: Instant) -> bool {
+        let mut cleared = false;
+        for node in &mut self.nodes {
+            if node.highlight_until.is_some_and(|until| until <= now) {
+                node.highlight_until = None;
+                cleared = true;
+            }
+        }
+        cleared
+    }
+
     /// Create connections from hub to all spoke nodes.
     pub fn create_hub_connections(&mut self) {
         let hub_id = match self.find_hub_node() {
@@ -401,6 +721,8 @@ impl NetworkState {
                 state: "open".to_string(),
                 service_name: Some("ssh".to_string()),
                 service_version: Some("OpenSSH 8.9".to_string()),
+                http_title: None,
+                server_header: None,
             },
             Port {
                 number: 445,
@@ -408,6 +730,8 @@ impl NetworkState {
                 state: "open".to_string(),
                 service_name: Some("smb".to_string()),
                 service_version: None,
+                http_title: None,
+                server_header: None,
             },
         ];
         let server_id = server.id;
@@ -454,3 +778,214 @@ impl NetworkState {
         self.add_connection(router_id, iot_id, ConnectionType::Wireless);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn node(network: &mut NetworkState, node_type: NodeType, ip: &str) -> NodeId {
+        let node = Node::new(node_type, 0.0, 0.0, ip.to_string(), ip.to_string());
+        let id = node.id;
+        network.add_node(node);
+        id
+    }
+
+    #[test]
+    fn test_place_new_nodes_keeps_existing_positions() {
+        let mut network = NetworkState::new();
+        node(&mut network, NodeType::Router, "10.0.0.1");
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        network.apply_radial_layout();
+        let before: Vec<(f32, f32)> = network.nodes.iter().map(|n| (n.x, n.y)).collect();
+
+        let c = node(&mut network, NodeType::Server, "10.0.0.4");
+        network.place_new_nodes(&[c]);
+
+        let after: Vec<(f32, f32)> = network.nodes.iter().take(3).map(|n| (n.x, n.y)).collect();
+        assert_eq!(before, after);
+
+        // The new node is placed on the ring, away from both existing spokes
+        let new = network.get_node(c).unwrap();
+        assert!((new.x, new.y) != (0.0, 0.0));
+        for id in [a, b] {
+            let old = network.get_node(id).unwrap();
+            let distance = ((old.x - new.x).powi(2) + (old.y - new.y).powi(2)).sqrt();
+            assert!(distance > layout::BASE_RADIUS / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_place_new_nodes_lays_out_everything_on_first_load() {
+        let mut network = NetworkState::new();
+        let hub = node(&mut network, NodeType::Router, "10.0.0.1");
+        let spoke = node(&mut network, NodeType::Server, "10.0.0.2");
+        network.place_new_nodes(&[hub, spoke]);
+
+        let hub = network.get_node(hub).unwrap();
+        assert_eq!((hub.x, hub.y), (layout::CENTER_X, layout::CENTER_Y));
+        let spoke = network.get_node(spoke).unwrap();
+        assert!((spoke.x, spoke.y) != (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_group_selected() {
+        let mut network = NetworkState::new();
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        let c = node(&mut network, NodeType::Server, "10.0.0.4");
+
+        network.select_node(a);
+        assert_eq!(network.group_selected("Servers".to_string()), None);
+
+        network.add_to_selection(&[b, c]);
+        let group_id = network.group_selected("Servers".to_string()).unwrap();
+        let group = network.get_group(group_id).unwrap();
+        assert_eq!(group.members, vec![a, b, c]);
+        assert!(!group.collapsed);
+        assert_eq!(network.get_node(b).unwrap().parent_id, Some(group_id));
+        assert_eq!(network.selected_group().unwrap().id, group_id);
+
+        // Regrouping moves nodes out of their old group
+        network.select_node(b);
+        network.add_to_selection(&[c]);
+        let inner = network.group_selected("Inner".to_string()).unwrap();
+        assert_eq!(network.get_group(group_id).unwrap().members, vec![a]);
+        assert_eq!(network.group_of(c).unwrap().id, inner);
+    }
+
+    #[test]
+    fn test_collapse_group() {
+        let mut network = NetworkState::new();
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        network.move_node(a, 100.0, 100.0);
+        network.move_node(b, 300.0, 200.0);
+        network.select_node(a);
+        network.add_to_selection(&[b]);
+        let id = network.group_selected("Pair".to_string()).unwrap();
+
+        let pad = layout::GROUP_PADDING;
+        let expanded = network.group_bounds(network.get_group(id).unwrap()).unwrap();
+        assert_eq!(expanded, (100.0 - pad, 100.0 - pad, 200.0 + 2.0 * pad, 100.0 + 2.0 * pad));
+
+        network.set_group_collapsed(id, true);
+        let group = network.get_group(id).unwrap();
+        assert!(group.collapsed);
+        let (x, y, w, h) = network.group_bounds(group).unwrap();
+        assert_eq!((x + w / 2.0, y + h / 2.0), (200.0, 150.0));
+        assert_eq!((w, h), (layout::COLLAPSED_GROUP_WIDTH, layout::COLLAPSED_GROUP_HEIGHT));
+
+        // Moving the super-node carries its members along
+        network.move_node(id, x + 10.0, y - 20.0);
+        assert_eq!(network.get_node(a).unwrap().x, 110.0);
+        assert_eq!(network.get_node(b).unwrap().y, 180.0);
+    }
+
+    #[test]
+    fn test_group_membership_follows_node_deletion() {
+        let mut network = NetworkState::new();
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        network.select_node(a);
+        network.add_to_selection(&[b]);
+        let id = network.group_selected("Pair".to_string()).unwrap();
+
+        network.remove_node(a);
+        assert_eq!(network.get_group(id).unwrap().members, vec![b]);
+        network.remove_node(b);
+        assert!(network.get_group(id).is_none());
+        assert!(network.selected_ids.is_empty());
+    }
+
+    #[test]
+    fn test_remove_group_keeps_or_removes_members() {
+        let mut network = NetworkState::new();
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        network.select_node(a);
+        network.add_to_selection(&[b]);
+        let id = network.group_selected("Pair".to_string()).unwrap();
+
+        network.remove_group(id, true);
+        assert!(network.groups.is_empty());
+        assert_eq!(network.nodes.len(), 2);
+        assert_eq!(network.get_node(a).unwrap().parent_id, None);
+
+        network.select_node(a);
+        network.add_to_selection(&[b]);
+        let id = network.group_selected("Pair".to_string()).unwrap();
+        network.remove_group(id, false);
+        assert!(network.groups.is_empty());
+        assert!(network.nodes.is_empty());
+    }
+
+    fn distance(network: &NetworkState, a: NodeId, b: NodeId) -> f32 {
+        let (a, b) = (network.get_node(a).unwrap(), network.get_node(b).unwrap());
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_force_layout_pulls_connected_nodes_together() {
+        let mut network = NetworkState::new();
+        let a = node(&mut network, NodeType::Server, "10.0.0.2");
+        let b = node(&mut network, NodeType::Server, "10.0.0.3");
+        let c = node(&mut network, NodeType::Server, "10.0.0.4");
+        let d = node(&mut network, NodeType::Server, "10.0.0.5");
+        network.move_node(a, 50.0, 50.0);
+        network.move_node(b, 750.0, 650.0);
+        network.move_node(c, 60.0, 640.0);
+        network.move_node(d, 740.0, 60.0);
+        network.add_connection(a, b, ConnectionType::Wired);
+
+        let before = distance(&network, a, b);
+        network.apply_force_layout(50);
+        assert!(distance(&network, a, b) < before);
+        // The connected pair ends up closer than the unconnected ones
+        assert!(distance(&network, a, b) < distance(&network, c, d));
+
+        for n in &network.nodes {
+            assert!((0.0..=layout::FORCE_AREA_WIDTH).contains(&n.x));
+            assert!((0.0..=layout::FORCE_AREA_HEIGHT).contains(&n.y));
+        }
+    }
+
+    #[test]
+    fn test_force_layout_is_deterministic_and_separates_stacked_nodes() {
+        let build = || {
+            let mut network = NetworkState::new();
+            let hub = node(&mut network, NodeType::Router, "10.0.0.1");
+            for i in 2..6 {
+                let id = node(&mut network, NodeType::Server, &format!("10.0.0.{i}"));
+                network.add_connection(hub, id, ConnectionType::Wired);
+            }
+            network
+        };
+        let (mut first, mut second) = (build(), build());
+        // All nodes start stacked at the origin
+        first.apply_force_layout(100);
+        second.apply_force_layout(100);
+
+        let positions = |n: &NetworkState| n.nodes.iter().map(|n| (n.x, n.y)).collect::<Vec<_>>();
+        assert_eq!(positions(&first), positions(&second));
+        for (i, a) in first.nodes.iter().enumerate() {
+            for b in &first.nodes[i + 1..] {
+                assert!(distance(&first, a.id, b.id) > 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_expired_highlights_are_cleared() {
+        let mut network = NetworkState::new();
+        let id = node(&mut network, NodeType::Server, "10.0.0.2");
+        let now = Instant::now();
+        network.highlight_node(id, now + Duration::from_secs(3));
+
+        assert!(!network.clear_expired_highlights(now));
+        assert!(network.get_node(id).unwrap().is_highlighted());
+        assert!(network.clear_expired_highlights(now + Duration::from_secs(3)));
+        assert!(!network.get_node(id).unwrap().is_highlighted());
+    }
+}
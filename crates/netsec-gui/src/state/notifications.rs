@@ -0,0 +1,253 @@
+//! In-app notification history with read/unread state.
+//!
+//! Desktop notifications and toasts disappear; every alert, scan and device
+//! event is also recorded here so it can be reviewed later. History is kept
+//! for the session, newest first, capped at [`MAX_HISTORY`] entries.
+
+use chrono::{DateTime, Utc};
+
+/// Maximum number of entries kept; the oldest are dropped first.
+pub const MAX_HISTORY: usize = 200;
+
+/// What kind of event produced a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Alert,
+    Scan,
+    Device,
+}
+
+impl NotificationKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Alert => "ALERT",
+            Self::Scan => "SCAN",
+            Self::Device => "DEVICE",
+        }
+    }
+}
+
+/// Where clicking a notification should take the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationTarget {
+    Alert(String),
+    Scan(String),
+    /// Device IP.
+    Device(String),
+    None,
+}
+
+/// A recorded notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationEntry {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    /// Severity label for alerts (`critical`, `high`, ...).
+    pub severity: Option<String>,
+    pub target: NotificationTarget,
+    pub timestamp: DateTime<Utc>,
+    pub read: bool,
+}
+
+/// Which entries to list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NotificationFilter {
+    /// Only entries of this kind; `None` lists all kinds.
+    pub kind: Option<NotificationKind>,
+    pub unread_only: bool,
+}
+
+impl NotificationFilter {
+    pub fn matches(&self, entry: &NotificationEntry) -> bool {
+        self.kind.is_none_or(|kind| entry.kind == kind) && !(self.unread_only && entry.read)
+    }
+}
+
+/// Session notification history.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationCenter {
+    /// Newest first.
+    entries: Vec<NotificationEntry>,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new, unread notification. Returns its id.
+    pub fn push(
+        &mut self,
+        kind: NotificationKind,
+        title: String,
+        body: String,
+        severity: Option<String>,
+        target: NotificationTarget,
+        timestamp: DateTime<Utc>,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(
+            0,
+            NotificationEntry {
+                id,
+                kind,
+                title,
+                body,
+                severity,
+                target,
+                timestamp,
+                read: false,
+            },
+        );
+        self.entries.truncate(MAX_HISTORY);
+        id
+    }
+
+    /// All entries, newest first.
+    pub fn entries(&self) -> &[NotificationEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, id: u64) -> Option<&NotificationEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Entries passing the filter, newest first.
+    pub fn filtered(&self, filter: NotificationFilter) -> impl Iterator<Item = &NotificationEntry> {
+        self.entries.iter().filter(move |e| filter.matches(e))
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.entries.iter().filter(|e| !e.read).count()
+    }
+
+    /// Mark one entry read. Returns `true` if it was unread.
+    pub fn mark_read(&mut self, id: u64) -> bool {
+        match self.entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) if !entry.read => {
+                entry.read = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark every entry read. Returns how many changed.
+    pub fn mark_all_read(&mut self) -> usize {
+        let mut changed = 0;
+        for entry in self.entries.iter_mut().filter(|e| !e.read) {
+            entry.read = true;
+            changed += 1;
+        }
+        changed
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(center: &mut NotificationCenter, kind: NotificationKind, title: &str) -> u64 {
+        center.push(
+            kind,
+            title.to_string(),
+            String::new(),
+            None,
+            NotificationTarget::None,
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_new_entries_are_unread() {
+        let mut center = NotificationCenter::new();
+        assert_eq!(center.unread_count(), 0);
+
+        let a = push(&mut center, NotificationKind::Alert, "a");
+        let b = push(&mut center, NotificationKind::Scan, "b");
+        assert_ne!(a, b);
+        assert_eq!(center.unread_count(), 2);
+        // Newest first
+        assert_eq!(center.entries()[0].id, b);
+    }
+
+    #[test]
+    fn test_mark_read_transitions() {
+        let mut center = NotificationCenter::new();
+        let a = push(&mut center, NotificationKind::Alert, "a");
+        push(&mut center, NotificationKind::Alert, "b");
+
+        assert!(center.mark_read(a));
+        assert!(center.get(a).unwrap().read);
+        assert_eq!(center.unread_count(), 1);
+
+        // Viewing again changes nothing
+        assert!(!center.mark_read(a));
+        assert_eq!(center.unread_count(), 1);
+        assert!(!center.mark_read(999));
+    }
+
+    #[test]
+    fn test_mark_all_read() {
+        let mut center = NotificationCenter::new();
+        let a = push(&mut center, NotificationKind::Alert, "a");
+        push(&mut center, NotificationKind::Scan, "b");
+        push(&mut center, NotificationKind::Device, "c");
+        center.mark_read(a);
+
+        assert_eq!(center.mark_all_read(), 2);
+        assert_eq!(center.unread_count(), 0);
+
+        // A later event is unread again
+        push(&mut center, NotificationKind::Alert, "d");
+        assert_eq!(center.unread_count(), 1);
+    }
+
+    #[test]
+    fn test_filter_by_kind_and_unread() {
+        let mut center = NotificationCenter::new();
+        let a = push(&mut center, NotificationKind::Alert, "a");
+        push(&mut center, NotificationKind::Alert, "b");
+        push(&mut center, NotificationKind::Scan, "c");
+        center.mark_read(a);
+
+        let alerts = NotificationFilter {
+            kind: Some(NotificationKind::Alert),
+            unread_only: false,
+        };
+        assert_eq!(center.filtered(alerts).count(), 2);
+
+        let unread_alerts = NotificationFilter {
+            unread_only: true,
+            ..alerts
+        };
+        let titles: Vec<&str> = center.filtered(unread_alerts).map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["b"]);
+
+        assert_eq!(center.filtered(NotificationFilter::default()).count(), 3);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut center = NotificationCenter::new();
+        for i in 0..MAX_HISTORY + 5 {
+            push(&mut center, NotificationKind::Device, &i.to_string());
+        }
+        assert_eq!(center.entries().len(), MAX_HISTORY);
+        assert_eq!(center.unread_count(), MAX_HISTORY);
+        // Oldest were dropped
+        assert_eq!(center.entries().last().unwrap().title, "5");
+    }
+}
@@ -198,8 +198,10 @@ impl TerminalState {
     pub fn resize_tab(&mut self, tab_id: TabId, cols: u16, rows: u16) {
         if let Some(tab) = self.tabs.iter_mut().find(|t| t.id == tab_id) {
             tab.parser.set_size(rows, cols);
-            if let Some(ref mut session) = tab.session {
-                let _ = session.resize(cols, rows);
+            if let Some(ref session) = tab.session {
+                if let Err(e) = session.resize(cols, rows) {
+                    tracing::warn!("Failed to resize PTY: {}", e);
+                }
             }
         }
     }
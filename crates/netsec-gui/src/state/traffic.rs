@@ -0,0 +1,251 @@
+//! Per-device traffic history and anomaly detection.
+//!
+//! Each traffic refresh is summarized per (device, peer) pair and appended to
+//! a short rolling history. The mean of earlier samples is the peer's
+//! baseline; the inspector highlights peers whose current throughput or
+//! connection count exceeds that baseline by a configurable factor.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::api::TrafficFlow;
+
+/// Number of refreshes kept per (device, peer) pair.
+pub const HISTORY_LEN: usize = 20;
+
+/// Earlier samples required before a baseline is trusted.
+pub const MIN_BASELINE_SAMPLES: usize = 3;
+
+/// Default factor over baseline at which a peer is flagged.
+pub const DEFAULT_ANOMALY_THRESHOLD: f32 = 2.0;
+
+/// Traffic between a device and one peer in a single refresh.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TrafficSample {
+    /// Total bytes in both directions.
+    pub bytes: f64,
+    /// Number of flows.
+    pub connections: f64,
+}
+
+/// How far a peer's current traffic deviates from its baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyLevel {
+    Normal,
+    /// Exceeds the threshold (amber).
+    Elevated,
+    /// Exceeds twice the threshold (red).
+    Anomalous,
+}
+
+/// A peer's current traffic and its baseline, if enough history exists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerTraffic {
+    pub peer_ip: String,
+    pub current: TrafficSample,
+    pub baseline: Option<TrafficSample>,
+}
+
+impl PeerTraffic {
+    /// Classify this peer against its baseline.
+    pub fn anomaly_level(&self, threshold: f32) -> AnomalyLevel {
+        let Some(baseline) = self.baseline else {
+            return AnomalyLevel::Normal;
+        };
+        let exceeds = |factor: f64| {
+            is_anomalous(self.current.bytes, baseline.bytes, factor)
+                || is_anomalous(self.current.connections, baseline.connections, factor)
+        };
+        let threshold = f64::from(threshold);
+        if exceeds(threshold * 2.0) {
+            AnomalyLevel::Anomalous
+        } else if exceeds(threshold) {
+            AnomalyLevel::Elevated
+        } else {
+            AnomalyLevel::Normal
+        }
+    }
+}
+
+/// Whether `current` exceeds `baseline` by more than `threshold` times.
+pub fn is_anomalous(current: f64, baseline: f64, threshold: f64) -> bool {
+    current > baseline * threshold
+}
+
+/// Mean of all samples except the newest, once enough history exists.
+pub fn baseline(history: &VecDeque<TrafficSample>) -> Option<TrafficSample> {
+    let earlier = history.len().checked_sub(1)?;
+    if earlier < MIN_BASELINE_SAMPLES {
+        return None;
+    }
+    let n = earlier as f64;
+    let (bytes, connections) = history
+        .iter()
+        .take(earlier)
+        .fold((0.0, 0.0), |(b, c), s| (b + s.bytes, c + s.connections));
+    Some(TrafficSample {
+        bytes: bytes / n,
+        connections: connections / n,
+    })
+}
+
+/// Summarize flows per device IP, then per peer IP (both directions).
+pub fn summarize_flows(flows: &[TrafficFlow]) -> HashMap<String, HashMap<String, TrafficSample>> {
+    let mut summary: HashMap<String, HashMap<String, TrafficSample>> = HashMap::new();
+    for flow in flows {
+        let bytes = (flow.bytes_sent + flow.bytes_received) as f64;
+        for (device, peer) in [(&flow.src_ip, &flow.dst_ip), (&flow.dst_ip, &flow.src_ip)] {
+            let sample = summary
+                .entry(device.clone())
+                .or_default()
+                .entry(peer.clone())
+                .or_default();
+            sample.bytes += bytes;
+            sample.connections += 1.0;
+        }
+    }
+    summary
+}
+
+/// Rolling per-device, per-peer traffic history.
+#[derive(Debug, Clone, Default)]
+pub struct TrafficHistory {
+    samples: HashMap<String, HashMap<String, VecDeque<TrafficSample>>>,
+    latest: HashMap<String, HashMap<String, TrafficSample>>,
+}
+
+impl TrafficHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a traffic refresh.
+    pub fn record(&mut self, flows: &[TrafficFlow]) {
+        let summary = summarize_flows(flows);
+        for (device, peers) in &summary {
+            let device_history = self.samples.entry(device.clone()).or_default();
+            for (peer, sample) in peers {
+                let history = device_history.entry(peer.clone()).or_default();
+                history.push_back(*sample);
+                while history.len() > HISTORY_LEN {
+                    history.pop_front();
+                }
+            }
+        }
+        self.latest = summary;
+    }
+
+    /// Peers of a device in the latest refresh, busiest first.
+    pub fn peers(&self, device_ip: &str) -> Vec<PeerTraffic> {
+        let Some(current) = self.latest.get(device_ip) else {
+            return Vec::new();
+        };
+        let history = self.samples.get(device_ip);
+
+        let mut peers: Vec<PeerTraffic> = current
+            .iter()
+            .map(|(peer, sample)| PeerTraffic {
+                peer_ip: peer.clone(),
+                current: *sample,
+                baseline: history.and_then(|h| h.get(peer)).and_then(baseline),
+            })
+            .collect();
+        peers.sort_by(|a, b| {
+            b.current
+                .bytes
+                .total_cmp(&a.current.bytes)
+                .then_with(|| a.peer_ip.cmp(&b.peer_ip))
+        });
+        peers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn flow(src: &str, dst: &str, bytes: i64) -> TrafficFlow {
+        TrafficFlow {
+            id: format!("{src}-{dst}-{bytes}"),
+            src_ip: src.to_string(),
+            src_port: None,
+            dst_ip: dst.to_string(),
+            dst_port: Some(443),
+            protocol: "tcp".to_string(),
+            bytes_sent: bytes,
+            bytes_received: 0,
+            packets_sent: 1,
+            packets_received: 0,
+            started_at: Utc::now(),
+            ended_at: None,
+            application: None,
+            country_src: None,
+            country_dst: None,
+        }
+    }
+
+    #[test]
+    fn test_is_anomalous() {
+        assert!(is_anomalous(300.0, 100.0, 2.0));
+        assert!(!is_anomalous(200.0, 100.0, 2.0));
+        assert!(!is_anomalous(150.0, 100.0, 2.0));
+        // Any traffic over a silent baseline is anomalous
+        assert!(is_anomalous(1.0, 0.0, 2.0));
+        assert!(!is_anomalous(0.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn test_baseline_requires_history() {
+        let mut history: VecDeque<TrafficSample> = VecDeque::new();
+        for bytes in [100.0, 200.0, 300.0] {
+            history.push_back(TrafficSample { bytes, connections: 1.0 });
+        }
+        // Three samples = two earlier ones, not enough
+        assert_eq!(baseline(&history), None);
+
+        history.push_back(TrafficSample { bytes: 5000.0, connections: 9.0 });
+        let b = baseline(&history).unwrap();
+        assert_eq!(b.bytes, 200.0);
+        assert_eq!(b.connections, 1.0);
+    }
+
+    #[test]
+    fn test_summarize_flows_both_directions() {
+        let flows = vec![
+            flow("10.0.0.5", "1.1.1.1", 100),
+            flow("10.0.0.5", "1.1.1.1", 50),
+            flow("8.8.8.8", "10.0.0.5", 10),
+        ];
+        let summary = summarize_flows(&flows);
+        let device = &summary["10.0.0.5"];
+        assert_eq!(device["1.1.1.1"], TrafficSample { bytes: 150.0, connections: 2.0 });
+        assert_eq!(device["8.8.8.8"], TrafficSample { bytes: 10.0, connections: 1.0 });
+        assert_eq!(summary["1.1.1.1"]["10.0.0.5"].bytes, 150.0);
+    }
+
+    #[test]
+    fn test_history_flags_spike() {
+        let mut history = TrafficHistory::new();
+        for _ in 0..4 {
+            history.record(&[flow("10.0.0.5", "1.1.1.1", 100), flow("10.0.0.5", "9.9.9.9", 100)]);
+        }
+        history.record(&[flow("10.0.0.5", "1.1.1.1", 1000), flow("10.0.0.5", "9.9.9.9", 250)]);
+
+        let peers = history.peers("10.0.0.5");
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].peer_ip, "1.1.1.1");
+        assert_eq!(peers[0].anomaly_level(2.0), AnomalyLevel::Anomalous);
+        assert_eq!(peers[1].anomaly_level(2.0), AnomalyLevel::Elevated);
+        assert_eq!(peers[1].anomaly_level(3.0), AnomalyLevel::Normal);
+    }
+
+    #[test]
+    fn test_new_peer_is_not_flagged() {
+        let mut history = TrafficHistory::new();
+        history.record(&[flow("10.0.0.5", "1.1.1.1", 1_000_000)]);
+        let peers = history.peers("10.0.0.5");
+        assert_eq!(peers[0].baseline, None);
+        assert_eq!(peers[0].anomaly_level(2.0), AnomalyLevel::Normal);
+        assert!(history.peers("10.0.0.99").is_empty());
+    }
+}
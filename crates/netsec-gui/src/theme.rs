@@ -1,56 +1,493 @@
-//! Dark theme styling for NetWatch.
+//! Light and dark theme styling for NetWatch.
+//!
+//! The color palette is runtime-configurable: the built-in palette for the
+//! selected mode (dark or light) can be overridden by a custom theme persisted
+//! to disk or edited live in the theme editor. Views read the active values
+//! through [`colors`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
 
 use iced::widget::{button, container};
 use iced::{Background, Border, Color, Theme};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Minimum contrast ratio for primary text (WCAG AA, normal text).
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Minimum contrast ratio for secondary text (WCAG AA, large text).
+pub const MIN_SECONDARY_TEXT_CONTRAST: f32 = 3.0;
+
+/// Text/background pairs that must stay readable, with their minimum ratio.
+const CONTRAST_RULES: &[(&str, &str, f32)] = &[
+    ("text_primary", "bg_primary", MIN_TEXT_CONTRAST),
+    ("text_primary", "bg_secondary", MIN_TEXT_CONTRAST),
+    ("text_primary", "bg_tertiary", MIN_TEXT_CONTRAST),
+    ("text_primary", "surface", MIN_TEXT_CONTRAST),
+    ("text_secondary", "bg_primary", MIN_SECONDARY_TEXT_CONTRAST),
+    (
+        "text_secondary",
+        "bg_secondary",
+        MIN_SECONDARY_TEXT_CONTRAST,
+    ),
+];
+
+/// Errors from loading, saving, or validating a theme.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ThemeError {
+    #[error("failed to parse theme: {0}")]
+    Parse(String),
+
+    #[error("failed to serialize theme: {0}")]
+    Serialize(String),
+
+    #[error("{foreground} on {background} has contrast {ratio:.2}:1 (minimum {minimum}:1)")]
+    LowContrast {
+        foreground: &'static str,
+        background: &'static str,
+        ratio: f32,
+        minimum: f32,
+    },
+}
+
+/// A complete color palette.
+///
+/// Serialized as TOML with `#rrggbb` / `#rrggbbaa` hex strings. Keys missing
+/// from a custom theme fall back to [`Palette::BUILTIN`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    #[serde(with = "hex_color")]
+    pub bg_primary: Color,
+    #[serde(with = "hex_color")]
+    pub bg_secondary: Color,
+    #[serde(with = "hex_color")]
+    pub bg_tertiary: Color,
+    #[serde(with = "hex_color")]
+    pub surface: Color,
+    #[serde(with = "hex_color")]
+    pub surface_hover: Color,
+    #[serde(with = "hex_color")]
+    pub border: Color,
+    #[serde(with = "hex_color")]
+    pub border_focus: Color,
+    #[serde(with = "hex_color")]
+    pub text_primary: Color,
+    #[serde(with = "hex_color")]
+    pub text_secondary: Color,
+    #[serde(with = "hex_color")]
+    pub text_muted: Color,
+    #[serde(with = "hex_color")]
+    pub cyan: Color,
+    #[serde(with = "hex_color")]
+    pub green: Color,
+    #[serde(with = "hex_color")]
+    pub yellow: Color,
+    #[serde(with = "hex_color")]
+    pub red: Color,
+    #[serde(with = "hex_color")]
+    pub orange: Color,
+    #[serde(with = "hex_color")]
+    pub purple: Color,
+    #[serde(with = "hex_color")]
+    pub status_offline: Color,
+}
+
+impl Palette {
+    /// The built-in dark palette.
+    pub const BUILTIN: Palette = Palette {
+        // Base colors
+        bg_primary: Color::from_rgb(0.04, 0.04, 0.05), // #0a0a0d
+        bg_secondary: Color::from_rgb(0.07, 0.09, 0.11), // #121719
+        bg_tertiary: Color::from_rgb(0.10, 0.12, 0.14), // #1a1f24
+
+        // Surface colors
+        surface: Color::from_rgb(0.12, 0.14, 0.16), // #1e2429
+        surface_hover: Color::from_rgb(0.16, 0.18, 0.20), // #292e33
+
+        // Border colors
+        border: Color::from_rgba(1.0, 1.0, 1.0, 0.1),
+        border_focus: Color::from_rgb(0.13, 0.83, 0.93), // #22d3ee (cyan)
+
+        // Text colors
+        text_primary: Color::from_rgb(0.89, 0.91, 0.94), // #e2e8f0
+        text_secondary: Color::from_rgb(0.58, 0.64, 0.69), // #94a3b0
+        text_muted: Color::from_rgb(0.39, 0.45, 0.51),   // #647282
+
+        // Accent colors
+        cyan: Color::from_rgb(0.13, 0.83, 0.93),   // #22d3ee
+        green: Color::from_rgb(0.13, 0.77, 0.37),  // #22c55e
+        yellow: Color::from_rgb(0.92, 0.70, 0.03), // #eab308
+        red: Color::from_rgb(0.94, 0.27, 0.27),    // #ef4444
+        orange: Color::from_rgb(0.97, 0.53, 0.15), // #f88725
+        purple: Color::from_rgb(0.57, 0.36, 0.90), // #9158e5
+
+        // Status colors
+        status_offline: Color::from_rgb(0.39, 0.45, 0.51),
+    };
+
+    /// The built-in light palette.
+    pub const LIGHT: Palette = Palette {
+        // Base colors
+        bg_primary: Color::from_rgb(0.97, 0.98, 0.99), // #f8fafc
+        bg_secondary: Color::from_rgb(0.95, 0.96, 0.98), // #f1f5f9
+        bg_tertiary: Color::from_rgb(0.89, 0.91, 0.94), // #e2e8f0
+
+        // Surface colors
+        surface: Color::from_rgb(1.0, 1.0, 1.0), // #ffffff
+        surface_hover: Color::from_rgb(0.91, 0.93, 0.95), // #e8edf2
+
+        // Border colors
+        border: Color::from_rgba(0.0, 0.0, 0.0, 0.12),
+        border_focus: Color::from_rgb(0.03, 0.57, 0.70), // #0891b2 (cyan)
+
+        // Text colors
+        text_primary: Color::from_rgb(0.06, 0.09, 0.16), // #0f172a
+        text_secondary: Color::from_rgb(0.28, 0.33, 0.41), // #475569
+        text_muted: Color::from_rgb(0.39, 0.45, 0.55),   // #64748b
+
+        // Accent colors, darkened to stay readable on light backgrounds
+        cyan: Color::from_rgb(0.03, 0.57, 0.70),   // #0891b2
+        green: Color::from_rgb(0.09, 0.64, 0.29),  // #16a34a
+        yellow: Color::from_rgb(0.79, 0.54, 0.02), // #ca8a04
+        red: Color::from_rgb(0.86, 0.15, 0.15),    // #dc2626
+        orange: Color::from_rgb(0.92, 0.35, 0.05), // #ea580c
+        purple: Color::from_rgb(0.49, 0.23, 0.93), // #7c3aed
+
+        // Status colors
+        status_offline: Color::from_rgb(0.58, 0.64, 0.72),
+    };
+
+    /// The built-in palette for dark or light mode.
+    pub const fn builtin(dark_mode: bool) -> Palette {
+        if dark_mode {
+            Self::BUILTIN
+        } else {
+            Self::LIGHT
+        }
+    }
+
+    /// Editable keys, in display order.
+    pub const KEYS: [&'static str; 17] = [
+        "bg_primary",
+        "bg_secondary",
+        "bg_tertiary",
+        "surface",
+        "surface_hover",
+        "border",
+        "border_focus",
+        "text_primary",
+        "text_secondary",
+        "text_muted",
+        "cyan",
+        "green",
+        "yellow",
+        "red",
+        "orange",
+        "purple",
+        "status_offline",
+    ];
+
+    /// Get a color by key.
+    pub fn get(&self, key: &str) -> Option<Color> {
+        let mut copy = *self;
+        copy.slot_mut(key).map(|c| *c)
+    }
+
+    /// Set a color by key. Returns `false` for an unknown key.
+    pub fn set(&mut self, key: &str, color: Color) -> bool {
+        match self.slot_mut(key) {
+            Some(slot) => {
+                *slot = color;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn slot_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "bg_primary" => &mut self.bg_primary,
+            "bg_secondary" => &mut self.bg_secondary,
+            "bg_tertiary" => &mut self.bg_tertiary,
+            "surface" => &mut self.surface,
+            "surface_hover" => &mut self.surface_hover,
+            "border" => &mut self.border,
+            "border_focus" => &mut self.border_focus,
+            "text_primary" => &mut self.text_primary,
+            "text_secondary" => &mut self.text_secondary,
+            "text_muted" => &mut self.text_muted,
+            "cyan" => &mut self.cyan,
+            "green" => &mut self.green,
+            "yellow" => &mut self.yellow,
+            "red" => &mut self.red,
+            "orange" => &mut self.orange,
+            "purple" => &mut self.purple,
+            "status_offline" => &mut self.status_offline,
+            _ => return None,
+        })
+    }
+
+    /// All text/background pairs that fall below their minimum contrast.
+    pub fn contrast_issues(&self) -> Vec<ThemeError> {
+        CONTRAST_RULES
+            .iter()
+            .filter_map(|&(fg, bg, minimum)| {
+                let ratio = contrast_ratio(self.get(fg)?, self.get(bg)?);
+                (ratio < minimum).then_some(ThemeError::LowContrast {
+                    foreground: fg,
+                    background: bg,
+                    ratio,
+                    minimum,
+                })
+            })
+            .collect()
+    }
+
+    /// Reject palettes with unreadable text/background pairs.
+    pub fn validate(&self) -> Result<(), ThemeError> {
+        match self.contrast_issues().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse and validate a palette from TOML.
+    pub fn from_toml(content: &str) -> Result<Self, ThemeError> {
+        let palette: Palette =
+            toml::from_str(content).map_err(|e| ThemeError::Parse(e.to_string()))?;
+        palette.validate()?;
+        Ok(palette)
+    }
+
+    /// Serialize the palette to TOML.
+    pub fn to_toml(&self) -> Result<String, ThemeError> {
+        toml::to_string_pretty(self).map_err(|e| ThemeError::Serialize(e.to_string()))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::BUILTIN
+    }
+}
+
+/// Parse a `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa` hex color.
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Color::parse(hex)
+}
+
+/// Format a color as `#rrggbb`, or `#rrggbbaa` when not fully opaque.
+pub fn color_to_hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == u8::MAX {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+/// WCAG relative luminance of a color (alpha is ignored).
+pub fn relative_luminance(color: Color) -> f32 {
+    fn channel(c: f32) -> f32 {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la >= lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Serde adapter storing colors as hex strings.
+mod hex_color {
+    use iced::Color;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::color_to_hex(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        super::parse_hex_color(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid color: {}", s)))
+    }
+}
+
+/// The palette the UI currently renders with.
+static ACTIVE_PALETTE: RwLock<Palette> = RwLock::new(Palette::BUILTIN);
+
+/// Get a copy of the active palette.
+pub fn palette() -> Palette {
+    *ACTIVE_PALETTE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Replace the active palette. Takes effect on the next redraw.
+pub fn set_palette(palette: Palette) {
+    *ACTIVE_PALETTE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = palette;
+}
+
+/// Whether the UI renders in dark mode; selects the base iced theme.
+static DARK_MODE: AtomicBool = AtomicBool::new(true);
 
-/// Color palette for the dark theme.
+/// Switch between dark and light mode. The palette is set separately with
+/// [`set_palette`], since a custom theme may replace the built-in one.
+pub fn set_dark_mode(dark_mode: bool) {
+    DARK_MODE.store(dark_mode, Ordering::Relaxed);
+}
+
+/// Whether dark mode is active.
+pub fn is_dark_mode() -> bool {
+    DARK_MODE.load(Ordering::Relaxed)
+}
+
+/// Accessors for the active palette colors.
 pub mod colors {
+    use std::sync::PoisonError;
+
     use iced::Color;
 
+    use super::{Palette, ACTIVE_PALETTE};
+
+    fn read(pick: impl FnOnce(&Palette) -> Color) -> Color {
+        pick(
+            &ACTIVE_PALETTE
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
     // Base colors
-    pub const BG_PRIMARY: Color = Color::from_rgb(0.04, 0.04, 0.05); // #0a0a0d
-    pub const BG_SECONDARY: Color = Color::from_rgb(0.07, 0.09, 0.11); // #121719
-    pub const BG_TERTIARY: Color = Color::from_rgb(0.10, 0.12, 0.14); // #1a1f24
+    pub fn bg_primary() -> Color {
+        read(|p| p.bg_primary)
+    }
+
+    pub fn bg_secondary() -> Color {
+        read(|p| p.bg_secondary)
+    }
+
+    pub fn bg_tertiary() -> Color {
+        read(|p| p.bg_tertiary)
+    }
 
     // Surface colors
-    pub const SURFACE: Color = Color::from_rgb(0.12, 0.14, 0.16); // #1e2429
-    pub const SURFACE_HOVER: Color = Color::from_rgb(0.16, 0.18, 0.20); // #292e33
+    pub fn surface() -> Color {
+        read(|p| p.surface)
+    }
+
+    pub fn surface_hover() -> Color {
+        read(|p| p.surface_hover)
+    }
 
     // Border colors
-    pub const BORDER: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.1);
-    pub const BORDER_FOCUS: Color = Color::from_rgb(0.13, 0.83, 0.93); // #22d3ee (cyan)
+    pub fn border() -> Color {
+        read(|p| p.border)
+    }
+
+    pub fn border_focus() -> Color {
+        read(|p| p.border_focus)
+    }
 
     // Text colors
-    pub const TEXT_PRIMARY: Color = Color::from_rgb(0.89, 0.91, 0.94); // #e2e8f0
-    pub const TEXT_SECONDARY: Color = Color::from_rgb(0.58, 0.64, 0.69); // #94a3b0
-    pub const TEXT_MUTED: Color = Color::from_rgb(0.39, 0.45, 0.51); // #647282
+    pub fn text_primary() -> Color {
+        read(|p| p.text_primary)
+    }
+
+    pub fn text_secondary() -> Color {
+        read(|p| p.text_secondary)
+    }
+
+    pub fn text_muted() -> Color {
+        read(|p| p.text_muted)
+    }
 
     // Accent colors
-    pub const CYAN: Color = Color::from_rgb(0.13, 0.83, 0.93); // #22d3ee
-    pub const GREEN: Color = Color::from_rgb(0.13, 0.77, 0.37); // #22c55e
-    pub const YELLOW: Color = Color::from_rgb(0.92, 0.70, 0.03); // #eab308
-    pub const RED: Color = Color::from_rgb(0.94, 0.27, 0.27); // #ef4444
-    pub const ORANGE: Color = Color::from_rgb(0.97, 0.53, 0.15); // #f88725
-    pub const PURPLE: Color = Color::from_rgb(0.57, 0.36, 0.90); // #9158e5
+    pub fn cyan() -> Color {
+        read(|p| p.cyan)
+    }
+
+    pub fn green() -> Color {
+        read(|p| p.green)
+    }
+
+    pub fn yellow() -> Color {
+        read(|p| p.yellow)
+    }
+
+    pub fn red() -> Color {
+        read(|p| p.red)
+    }
+
+    pub fn orange() -> Color {
+        read(|p| p.orange)
+    }
+
+    pub fn purple() -> Color {
+        read(|p| p.purple)
+    }
+
+    /// `color` at the given opacity, for tinted backgrounds and borders.
+    pub fn tint(color: Color, alpha: f32) -> Color {
+        Color { a: alpha, ..color }
+    }
+
+    /// A translucent wash of the text color: lightens surfaces in dark mode
+    /// and darkens them in light mode.
+    pub fn overlay(alpha: f32) -> Color {
+        tint(text_primary(), alpha)
+    }
 
     // Status colors
-    pub const STATUS_ONLINE: Color = GREEN;
-    pub const STATUS_OFFLINE: Color = Color::from_rgb(0.39, 0.45, 0.51);
-    pub const STATUS_WARNING: Color = YELLOW;
-    pub const STATUS_CRITICAL: Color = RED;
+    pub fn status_online() -> Color {
+        green()
+    }
+
+    pub fn status_offline() -> Color {
+        read(|p| p.status_offline)
+    }
+
+    pub fn status_warning() -> Color {
+        yellow()
+    }
+
+    pub fn status_critical() -> Color {
+        red()
+    }
 }
 
-/// Get the application theme.
+/// Get the application theme for the active mode.
 pub fn get_theme() -> Theme {
-    Theme::Dark
+    if is_dark_mode() {
+        Theme::Dark
+    } else {
+        Theme::Light
+    }
 }
 
 /// Container style for panels.
 pub fn panel_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_SECONDARY)),
+        background: Some(Background::Color(colors::bg_secondary())),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 4.0.into(),
         },
@@ -61,7 +498,7 @@ pub fn panel_style(_theme: &Theme) -> container::Style {
 /// Container style for the main content area.
 pub fn content_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_PRIMARY)),
+        background: Some(Background::Color(colors::bg_primary())),
         ..Default::default()
     }
 }
@@ -71,7 +508,7 @@ pub fn terminal_style(_theme: &Theme) -> container::Style {
     container::Style {
         background: Some(Background::Color(Color::from_rgb(0.04, 0.04, 0.04))),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 0.0.into(),
         },
@@ -82,8 +519,8 @@ pub fn terminal_style(_theme: &Theme) -> container::Style {
 /// Button style for primary actions.
 pub fn primary_button_style(_theme: &Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(colors::CYAN)),
-        text_color: colors::BG_PRIMARY,
+        background: Some(Background::Color(colors::cyan())),
+        text_color: colors::bg_primary(),
         border: Border {
             radius: 4.0.into(),
             ..Default::default()
@@ -102,8 +539,8 @@ pub fn primary_button_style(_theme: &Theme, status: button::Status) -> button::S
             ..base
         },
         button::Status::Disabled => button::Style {
-            background: Some(Background::Color(colors::SURFACE)),
-            text_color: colors::TEXT_MUTED,
+            background: Some(Background::Color(colors::surface())),
+            text_color: colors::text_muted(),
             ..base
         },
     }
@@ -112,10 +549,10 @@ pub fn primary_button_style(_theme: &Theme, status: button::Status) -> button::S
 /// Button style for secondary/subtle actions.
 pub fn secondary_button_style(_theme: &Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(colors::SURFACE)),
-        text_color: colors::TEXT_PRIMARY,
+        background: Some(Background::Color(colors::surface())),
+        text_color: colors::text_primary(),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 4.0.into(),
         },
@@ -125,19 +562,19 @@ pub fn secondary_button_style(_theme: &Theme, status: button::Status) -> button:
     match status {
         button::Status::Active => base,
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(colors::SURFACE_HOVER)),
+            background: Some(Background::Color(colors::surface_hover())),
             border: Border {
-                color: colors::CYAN,
+                color: colors::cyan(),
                 ..base.border
             },
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(colors::BG_TERTIARY)),
+            background: Some(Background::Color(colors::bg_tertiary())),
             ..base
         },
         button::Status::Disabled => button::Style {
-            text_color: colors::TEXT_MUTED,
+            text_color: colors::text_muted(),
             ..base
         },
     }
@@ -147,10 +584,10 @@ pub fn secondary_button_style(_theme: &Theme, status: button::Status) -> button:
 pub fn tab_button_style(_theme: &Theme, status: button::Status, active: bool) -> button::Style {
     let base = if active {
         button::Style {
-            background: Some(Background::Color(colors::BG_TERTIARY)),
-            text_color: colors::TEXT_PRIMARY,
+            background: Some(Background::Color(colors::bg_tertiary())),
+            text_color: colors::text_primary(),
             border: Border {
-                color: colors::CYAN,
+                color: colors::cyan(),
                 width: 0.0,
                 radius: 4.0.into(),
             },
@@ -159,7 +596,7 @@ pub fn tab_button_style(_theme: &Theme, status: button::Status, active: bool) ->
     } else {
         button::Style {
             background: Some(Background::Color(Color::TRANSPARENT)),
-            text_color: colors::TEXT_SECONDARY,
+            text_color: colors::text_secondary(),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -170,10 +607,115 @@ pub fn tab_button_style(_theme: &Theme, status: button::Status, active: bool) ->
 
     match status {
         button::Status::Hovered if !active => button::Style {
-            background: Some(Background::Color(colors::SURFACE)),
-            text_color: colors::TEXT_PRIMARY,
+            background: Some(Background::Color(colors::surface())),
+            text_color: colors::text_primary(),
             ..base
         },
         _ => base,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_palette() -> Palette {
+        Palette {
+            bg_primary: Color::from_rgb8(0xfa, 0xfa, 0xf9),
+            bg_secondary: Color::from_rgb8(0xf0, 0xf0, 0xee),
+            bg_tertiary: Color::from_rgb8(0xe7, 0xe5, 0xe4),
+            surface: Color::from_rgb8(0xff, 0xff, 0xff),
+            text_primary: Color::from_rgb8(0x1c, 0x19, 0x17),
+            text_secondary: Color::from_rgb8(0x57, 0x53, 0x4e),
+            border: Color::from_rgba8(0x00, 0x00, 0x00, 0.2),
+            cyan: Color::from_rgb8(0x08, 0x91, 0xb2),
+            ..Palette::BUILTIN
+        }
+    }
+
+    #[test]
+    fn test_builtin_palette_passes_contrast() {
+        assert!(Palette::BUILTIN.validate().is_ok());
+        assert!(Palette::LIGHT.validate().is_ok());
+    }
+
+    #[test]
+    fn test_light_and_dark_backgrounds_differ() {
+        let (dark, light) = (Palette::builtin(true), Palette::builtin(false));
+        assert_ne!(dark.bg_primary, light.bg_primary);
+        assert!(relative_luminance(light.bg_primary) > relative_luminance(dark.bg_primary));
+        assert!(relative_luminance(light.text_primary) < relative_luminance(dark.text_primary));
+    }
+
+    #[test]
+    fn test_custom_theme_roundtrip() {
+        let palette = custom_palette();
+        let toml = palette.to_toml().unwrap();
+        assert!(toml.contains("bg_primary = \"#fafaf9\""));
+        assert!(toml.contains("border = \"#00000033\""));
+
+        let loaded = Palette::from_toml(&toml).unwrap();
+        for key in Palette::KEYS {
+            assert_eq!(
+                color_to_hex(loaded.get(key).unwrap()),
+                color_to_hex(palette.get(key).unwrap()),
+                "{key}"
+            );
+        }
+        assert_eq!(loaded.bg_primary, palette.bg_primary);
+    }
+
+    #[test]
+    fn test_partial_theme_falls_back_to_builtin() {
+        let loaded = Palette::from_toml("cyan = \"#ff00ff\"\n").unwrap();
+        assert_eq!(loaded.cyan, Color::from_rgb8(0xff, 0x00, 0xff));
+        assert_eq!(loaded.bg_primary, Palette::BUILTIN.bg_primary);
+    }
+
+    #[test]
+    fn test_rejects_low_contrast_text() {
+        // Dark grey text on the near-black background is unreadable
+        let toml = "text_primary = \"#333333\"\n";
+        match Palette::from_toml(toml) {
+            Err(ThemeError::LowContrast { foreground, background, ratio, minimum }) => {
+                assert_eq!(foreground, "text_primary");
+                assert_eq!(background, "bg_primary");
+                assert!(ratio < minimum);
+            }
+            other => panic!("expected LowContrast, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_color() {
+        let err = Palette::from_toml("cyan = \"teal\"\n").unwrap_err();
+        assert!(matches!(err, ThemeError::Parse(_)));
+    }
+
+    #[test]
+    fn test_hex_color_parsing() {
+        assert_eq!(parse_hex_color("#22d3ee"), Some(Color::from_rgb8(0x22, 0xd3, 0xee)));
+        assert_eq!(parse_hex_color(" 22D3EE "), Some(Color::from_rgb8(0x22, 0xd3, 0xee)));
+        assert_eq!(parse_hex_color("#fff"), Some(Color::WHITE));
+        assert_eq!(parse_hex_color("#gggggg"), None);
+        assert_eq!(parse_hex_color("#é1234"), None);
+        assert_eq!(parse_hex_color(""), None);
+        assert_eq!(color_to_hex(Color::from_rgb8(0x22, 0xd3, 0xee)), "#22d3ee");
+    }
+
+    #[test]
+    fn test_contrast_ratio_bounds() {
+        let max = contrast_ratio(Color::BLACK, Color::WHITE);
+        assert!((max - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_palette_get_set() {
+        let mut palette = Palette::BUILTIN;
+        assert!(palette.set("red", Color::WHITE));
+        assert_eq!(palette.get("red"), Some(Color::WHITE));
+        assert!(!palette.set("nope", Color::WHITE));
+        assert_eq!(palette.get("nope"), None);
+    }
+}
@@ -1,6 +1,10 @@
 //! Alerts dashboard view with filtering and details.
 
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use std::collections::BTreeSet;
+
+use iced::widget::{
+    button, checkbox, column, container, row, scrollable, text, text_input, Space,
+};
 use iced::{Alignment, Background, Border, Color, Element, Length};
 
 use crate::api::{Alert, AlertStats};
@@ -40,11 +44,11 @@ impl AlertSeverity {
 
     pub fn color(&self) -> Color {
         match self {
-            Self::Critical => colors::RED,
-            Self::High => colors::ORANGE,
-            Self::Medium => colors::YELLOW,
+            Self::Critical => colors::red(),
+            Self::High => colors::orange(),
+            Self::Medium => colors::yellow(),
             Self::Low => Color::from_rgb(0.23, 0.51, 0.95),
-            Self::Info => colors::CYAN,
+            Self::Info => colors::cyan(),
         }
     }
 }
@@ -80,10 +84,10 @@ impl AlertStatus {
 
     pub fn color(&self) -> Color {
         match self {
-            Self::Open => colors::RED,
-            Self::Acknowledged => colors::YELLOW,
-            Self::Resolved => colors::GREEN,
-            Self::Dismissed => colors::TEXT_MUTED,
+            Self::Open => colors::red(),
+            Self::Acknowledged => colors::yellow(),
+            Self::Resolved => colors::green(),
+            Self::Dismissed => colors::text_muted(),
         }
     }
 }
@@ -163,7 +167,7 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
         Space::with_width(8),
         text(alert.title.clone())
             .size(11)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(Length::Fill),
         status_badge(status),
     ]
@@ -173,7 +177,7 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
     let description = if let Some(ref desc) = alert.description {
         text(desc.clone())
             .size(9)
-            .color(colors::TEXT_SECONDARY)
+            .color(colors::text_secondary())
     } else {
         text("")
             .size(9)
@@ -185,11 +189,11 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
     let count = alert.count;
 
     let mut meta_row_content = row![
-        text("\u{2699}").size(9).color(colors::TEXT_MUTED), // ⚙
+        text("\u{2699}").size(9).color(colors::text_muted()), // ⚙
         Space::with_width(4),
         text(source_tool)
             .size(8)
-            .color(colors::CYAN),
+            .color(colors::cyan()),
     ]
     .align_y(Alignment::Center);
 
@@ -198,11 +202,11 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
         meta_row_content = meta_row_content.push(Space::with_width(12));
         meta_row_content = meta_row_content.push(
             row![
-                text("\u{1F4BB}").size(9).color(colors::TEXT_MUTED), // 💻
+                text("\u{1F4BB}").size(9).color(colors::text_muted()), // 💻
                 Space::with_width(4),
                 text(ip)
                     .size(8)
-                    .color(colors::TEXT_SECONDARY),
+                    .color(colors::text_secondary()),
             ]
             .align_y(Alignment::Center)
         );
@@ -215,7 +219,7 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
             container(
                 text(format!("x{}", count))
                     .size(8)
-                    .color(colors::ORANGE)
+                    .color(colors::orange())
             )
             .padding([2, 4])
             .style(|_| container::Style {
@@ -233,7 +237,7 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
     meta_row_content = meta_row_content.push(
         text(time_ago)
             .size(8)
-            .color(colors::TEXT_MUTED)
+            .color(colors::text_muted())
     );
 
     let meta_row = meta_row_content;
@@ -255,9 +259,9 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
     };
 
     let border_color = if is_selected {
-        colors::CYAN
+        colors::cyan()
     } else {
-        colors::BORDER
+        colors::border()
     };
 
     button(
@@ -276,7 +280,7 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
         };
         iced::widget::button::Style {
             background: Some(Background::Color(bg)),
-            text_color: colors::TEXT_PRIMARY,
+            text_color: colors::text_primary(),
             border: Border {
                 color: border_color,
                 width: 1.0,
@@ -288,8 +292,199 @@ fn alert_card(alert: &Alert, is_selected: bool) -> Element<'_, Message> {
     .into()
 }
 
+/// Alert list filter: AND across categories, OR within a category.
+///
+/// An empty category places no constraint, so the default filter matches
+/// every alert. Values are stored lowercase.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterSet {
+    /// Selected severities; an alert must have one of them.
+    pub severities: BTreeSet<String>,
+    /// Selected statuses; an alert must have one of them.
+    pub statuses: BTreeSet<String>,
+    /// Free-text query over title, source tool, and device IP.
+    pub search: String,
+}
+
+impl FilterSet {
+    /// Add a severity to the selection, or remove it if already selected.
+    pub fn toggle_severity(&mut self, severity: &str) {
+        toggle(&mut self.severities, severity);
+    }
+
+    /// Add a status to the selection, or remove it if already selected.
+    pub fn toggle_status(&mut self, status: &str) {
+        toggle(&mut self.statuses, status);
+    }
+
+    pub fn has_severity(&self, severity: &str) -> bool {
+        self.severities.contains(&severity.to_lowercase())
+    }
+
+    pub fn has_status(&self, status: &str) -> bool {
+        self.statuses.contains(&status.to_lowercase())
+    }
+}
+
+fn toggle(set: &mut BTreeSet<String>, value: &str) {
+    let value = value.to_lowercase();
+    if !set.remove(&value) {
+        set.insert(value);
+    }
+}
+
+/// Whether an alert passes the filter.
+pub fn matches_filter(alert: &Alert, filter: &FilterSet) -> bool {
+    if !filter.severities.is_empty() && !filter.has_severity(&alert.severity) {
+        return false;
+    }
+    if !filter.statuses.is_empty() && !filter.has_status(&alert.status) {
+        return false;
+    }
+    if !filter.search.is_empty() {
+        let query = filter.search.to_lowercase();
+        if !alert.title.to_lowercase().contains(&query)
+            && !alert.source_tool.to_lowercase().contains(&query)
+            && !alert.device_ip.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false)
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// IDs of the checked alerts that pass the filter, in list order.
+///
+/// Checked alerts hidden by the filter are left out, so a bulk action only
+/// touches what the analyst can see.
+pub fn bulk_targets(
+    alerts: &[Alert],
+    filter: &FilterSet,
+    checked: &BTreeSet<String>,
+) -> Vec<String> {
+    alerts
+        .iter()
+        .filter(|alert| checked.contains(&alert.id) && matches_filter(alert, filter))
+        .map(|alert| alert.id.clone())
+        .collect()
+}
+
+/// Find the other alerts that share the selected alert's incident.
+///
+/// Alerts are linked through `correlation_id`; an alert without one (or with
+/// an empty one) has no siblings. The selected alert itself is excluded and
+/// the result is ordered most-recently-seen first.
+pub fn find_related_alerts<'a>(alerts: &'a [Alert], selected: &Alert) -> Vec<&'a Alert> {
+    let Some(incident) = selected.correlation_id.as_deref().filter(|c| !c.is_empty()) else {
+        return Vec::new();
+    };
+
+    let mut related: Vec<&Alert> = alerts
+        .iter()
+        .filter(|a| a.id != selected.id && a.correlation_id.as_deref() == Some(incident))
+        .collect();
+    related.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    related
+}
+
+/// Render a compact, clickable row for a related alert.
+fn related_alert_row(alert: &Alert) -> Element<'_, Message> {
+    let severity = AlertSeverity::from_str(&alert.severity);
+
+    button(
+        row![
+            container(Space::with_width(6))
+                .height(Length::Fixed(6.0))
+                .style(move |_| container::Style {
+                    background: Some(Background::Color(severity.color())),
+                    border: Border {
+                        radius: 3.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            Space::with_width(8),
+            text(alert.title.clone())
+                .size(9)
+                .color(colors::text_primary()),
+            Space::with_width(Length::Fill),
+            text(alert.last_seen.format("%m-%d %H:%M").to_string())
+                .size(8)
+                .color(colors::text_muted()),
+        ]
+        .align_y(Alignment::Center)
+    )
+    .on_press(Message::AlertSelected(alert.id.clone()))
+    .padding([6, 8])
+    .width(Length::Fill)
+    .style(|_, status| {
+        let bg = if matches!(status, iced::widget::button::Status::Hovered) {
+            Color::from_rgba(1.0, 1.0, 1.0, 0.05)
+        } else {
+            Color::TRANSPARENT
+        };
+        iced::widget::button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: colors::text_primary(),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .into()
+}
+
+/// Render the "related alerts" section of the detail panel.
+fn related_alerts_section<'a>(alert: &'a Alert, alerts: &'a [Alert]) -> Element<'a, Message> {
+    let title = text("Related Alerts")
+        .size(9)
+        .color(colors::text_muted());
+
+    let body: Element<'a, Message> = if alert.correlation_id.as_deref().is_none_or(str::is_empty) {
+        text("Not part of an incident")
+            .size(9)
+            .color(colors::text_muted())
+            .into()
+    } else {
+        let related = find_related_alerts(alerts, alert);
+        if related.is_empty() {
+            text("No other alerts in this incident")
+                .size(9)
+                .color(colors::text_muted())
+                .into()
+        } else {
+            let mut rows = column![].spacing(2);
+            for sibling in related {
+                rows = rows.push(related_alert_row(sibling));
+            }
+            rows.into()
+        }
+    };
+
+    column![
+        Space::with_height(16),
+        title,
+        Space::with_height(8),
+        container(body)
+            .padding(8)
+            .width(Length::Fill)
+            .style(|_| container::Style {
+                background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.02))),
+                border: Border {
+                    color: colors::border(),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }),
+    ]
+    .into()
+}
+
 /// Render the alert detail panel.
-fn alert_detail(alert: &Alert) -> Element<'_, Message> {
+fn alert_detail<'a>(alert: &'a Alert, alerts: &'a [Alert]) -> Element<'a, Message> {
     let severity = AlertSeverity::from_str(&alert.severity);
     let status = AlertStatus::from_str(&alert.status);
 
@@ -303,7 +498,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
         Space::with_height(12),
         text(alert.title.clone())
             .size(16)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
     ];
 
     // Description
@@ -312,7 +507,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
             Space::with_height(12),
             text(desc.clone())
                 .size(10)
-                .color(colors::TEXT_SECONDARY),
+                .color(colors::text_secondary()),
         ]
     } else {
         column![]
@@ -323,7 +518,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
         row![
             text(label)
                 .size(9)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
                 .width(Length::Fixed(100.0)),
             text(value)
                 .size(10)
@@ -334,32 +529,32 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
 
     let mut info_section = column![].spacing(8);
 
-    info_section = info_section.push(info_row_owned("Source Tool", alert.source_tool.clone(), colors::CYAN));
+    info_section = info_section.push(info_row_owned("Source Tool", alert.source_tool.clone(), colors::cyan()));
 
     if let Some(ref ip) = alert.device_ip {
-        info_section = info_section.push(info_row_owned("Device IP", ip.clone(), colors::TEXT_PRIMARY));
+        info_section = info_section.push(info_row_owned("Device IP", ip.clone(), colors::text_primary()));
     }
 
     if let Some(ref category) = alert.category {
-        info_section = info_section.push(info_row_owned("Category", category.clone(), colors::TEXT_SECONDARY));
+        info_section = info_section.push(info_row_owned("Category", category.clone(), colors::text_secondary()));
     }
 
     info_section = info_section.push(info_row_owned(
         "First Seen",
         alert.first_seen.format("%Y-%m-%d %H:%M").to_string(),
-        colors::TEXT_SECONDARY,
+        colors::text_secondary(),
     ));
 
     info_section = info_section.push(info_row_owned(
         "Last Seen",
         alert.last_seen.format("%Y-%m-%d %H:%M").to_string(),
-        colors::TEXT_SECONDARY,
+        colors::text_secondary(),
     ));
 
     info_section = info_section.push(info_row_owned(
         "Count",
         alert.count.to_string(),
-        if alert.count > 1 { colors::ORANGE } else { colors::TEXT_SECONDARY },
+        if alert.count > 1 { colors::orange() } else { colors::text_secondary() },
     ));
 
     // Action buttons
@@ -377,7 +572,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::YELLOW,
+                text_color: colors::yellow(),
                 border: Border {
                     color: Color::from_rgba(1.0, 0.8, 0.0, 0.3),
                     width: 1.0,
@@ -400,7 +595,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::GREEN,
+                text_color: colors::green(),
                 border: Border {
                     color: Color::from_rgba(0.13, 0.77, 0.37, 0.3),
                     width: 1.0,
@@ -423,9 +618,9 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -443,19 +638,19 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
                 Space::with_height(16),
                 text("Raw Data")
                     .size(9)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_height(8),
                 container(
                     text(format!("{} data fields available", key_count))
                         .size(9)
-                        .color(colors::TEXT_SECONDARY)
+                        .color(colors::text_secondary())
                 )
                 .padding(12)
                 .width(Length::Fill)
                 .style(|_| container::Style {
                     background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.3))),
                     border: Border {
-                        color: colors::BORDER,
+                        color: colors::border(),
                         width: 1.0,
                         radius: 4.0.into(),
                     },
@@ -481,7 +676,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
                     .style(|_| container::Style {
                         background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.02))),
                         border: Border {
-                            color: colors::BORDER,
+                            color: colors::border(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -489,6 +684,7 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
                     }),
                 Space::with_height(16),
                 action_buttons,
+                related_alerts_section(alert, alerts),
                 raw_data_section,
             ]
             .padding(16)
@@ -498,9 +694,9 @@ fn alert_detail(alert: &Alert) -> Element<'_, Message> {
     .width(Length::Fixed(350.0))
     .height(Length::Fill)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SECONDARY)),
+        background: Some(Background::Color(colors::bg_secondary())),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             ..Default::default()
         },
@@ -530,7 +726,7 @@ fn stats_bar<'a>(stats: Option<&'a AlertStats>, total: usize) -> Element<'a, Mes
                     .color(color),
                 text(label)
                     .size(8)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -547,17 +743,17 @@ fn stats_bar<'a>(stats: Option<&'a AlertStats>, total: usize) -> Element<'a, Mes
     };
 
     row![
-        stat_box("CRITICAL", critical, colors::RED),
+        stat_box("CRITICAL", critical, colors::red()),
         Space::with_width(8),
-        stat_box("HIGH", high, colors::ORANGE),
+        stat_box("HIGH", high, colors::orange()),
         Space::with_width(8),
-        stat_box("MEDIUM", medium, colors::YELLOW),
+        stat_box("MEDIUM", medium, colors::yellow()),
         Space::with_width(8),
         stat_box("LOW", low, Color::from_rgb(0.23, 0.51, 0.95)),
         Space::with_width(Length::Fill),
         text(format!("{} total alerts", total))
             .size(10)
-            .color(colors::TEXT_MUTED),
+            .color(colors::text_muted()),
     ]
     .align_y(Alignment::Center)
     .into()
@@ -568,16 +764,15 @@ pub fn view<'a>(
     alerts: &'a [Alert],
     stats: Option<&'a AlertStats>,
     selected_id: Option<&'a str>,
-    filter_severity: Option<&'a str>,
-    filter_status: Option<&'a str>,
-    search_query: &'a str,
+    filter: &'a FilterSet,
+    checked: &'a BTreeSet<String>,
 ) -> Element<'a, Message> {
     // Header
     let header = row![
         container(
             text("\u{1F514}")  // 🔔
                 .size(20)
-                .color(colors::ORANGE)
+                .color(colors::orange())
         )
         .padding([8, 12])
         .style(|_| container::Style {
@@ -592,14 +787,19 @@ pub fn view<'a>(
         column![
             text("SECURITY ALERTS")
                 .size(18)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             text("Monitor and manage security events")
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ],
         Space::with_width(Length::Fill),
+        button(text("Export CSV").size(10))
+            .on_press(Message::ExportAlertsCsv)
+            .padding([4, 8])
+            .style(crate::theme::secondary_button_style),
+        Space::with_width(8),
         button(
-            text("\u{21BB}").size(14).color(colors::TEXT_MUTED) // ↻
+            text("\u{21BB}").size(14).color(colors::text_muted()) // ↻
         )
         .on_press(Message::FetchAlerts)
         .padding([8, 12])
@@ -611,14 +811,14 @@ pub fn view<'a>(
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 border: Border::default(),
                 ..Default::default()
             }
         }),
         Space::with_width(8),
         button(
-            text("\u{2716}").size(18).color(colors::TEXT_MUTED) // ✖
+            text("\u{2716}").size(18).color(colors::text_muted()) // ✖
         )
         .on_press(Message::HideAlertsDashboard)
         .padding([8, 12])
@@ -630,7 +830,7 @@ pub fn view<'a>(
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 border: Border::default(),
                 ..Default::default()
             }
@@ -668,31 +868,39 @@ pub fn view<'a>(
         .into()
     };
 
+    // Multi-select chips: ALL clears the category, others toggle
+    let severity_chip = |label: &'a str, value: &str, color: Color| {
+        filter_btn(label, filter.has_severity(value), color, Message::AlertFilterSeverity(Some(value.to_string())))
+    };
+    let status_chip = |label: &'a str, value: &str, color: Color| {
+        filter_btn(label, filter.has_status(value), color, Message::AlertFilterStatus(Some(value.to_string())))
+    };
+
     let severity_filters = row![
-        text("Severity:").size(9).color(colors::TEXT_MUTED),
+        text("Severity:").size(9).color(colors::text_muted()),
         Space::with_width(8),
-        filter_btn("ALL", filter_severity.is_none(), colors::CYAN, Message::AlertFilterSeverity(None)),
+        filter_btn("ALL", filter.severities.is_empty(), colors::cyan(), Message::AlertFilterSeverity(None)),
         Space::with_width(4),
-        filter_btn("CRITICAL", filter_severity == Some("critical"), colors::RED, Message::AlertFilterSeverity(Some("critical".to_string()))),
+        severity_chip("CRITICAL", "critical", colors::red()),
         Space::with_width(4),
-        filter_btn("HIGH", filter_severity == Some("high"), colors::ORANGE, Message::AlertFilterSeverity(Some("high".to_string()))),
+        severity_chip("HIGH", "high", colors::orange()),
         Space::with_width(4),
-        filter_btn("MEDIUM", filter_severity == Some("medium"), colors::YELLOW, Message::AlertFilterSeverity(Some("medium".to_string()))),
+        severity_chip("MEDIUM", "medium", colors::yellow()),
         Space::with_width(4),
-        filter_btn("LOW", filter_severity == Some("low"), Color::from_rgb(0.23, 0.51, 0.95), Message::AlertFilterSeverity(Some("low".to_string()))),
+        severity_chip("LOW", "low", Color::from_rgb(0.23, 0.51, 0.95)),
     ]
     .align_y(Alignment::Center);
 
     let status_filters = row![
-        text("Status:").size(9).color(colors::TEXT_MUTED),
+        text("Status:").size(9).color(colors::text_muted()),
         Space::with_width(8),
-        filter_btn("ALL", filter_status.is_none(), colors::CYAN, Message::AlertFilterStatus(None)),
+        filter_btn("ALL", filter.statuses.is_empty(), colors::cyan(), Message::AlertFilterStatus(None)),
         Space::with_width(4),
-        filter_btn("OPEN", filter_status == Some("open"), colors::RED, Message::AlertFilterStatus(Some("open".to_string()))),
+        status_chip("OPEN", "open", colors::red()),
         Space::with_width(4),
-        filter_btn("ACK", filter_status == Some("acknowledged"), colors::YELLOW, Message::AlertFilterStatus(Some("acknowledged".to_string()))),
+        status_chip("ACK", "acknowledged", colors::yellow()),
         Space::with_width(4),
-        filter_btn("RESOLVED", filter_status == Some("resolved"), colors::GREEN, Message::AlertFilterStatus(Some("resolved".to_string()))),
+        status_chip("RESOLVED", "resolved", colors::green()),
     ]
     .align_y(Alignment::Center);
 
@@ -700,7 +908,7 @@ pub fn view<'a>(
         row![
             severity_filters,
             Space::with_width(Length::Fill),
-            text_input("Search alerts...", search_query)
+            text_input("Search alerts...", &filter.search)
                 .on_input(Message::AlertSearch)
                 .padding([6, 12])
                 .width(Length::Fixed(200.0))
@@ -712,34 +920,32 @@ pub fn view<'a>(
     ]
     .padding([12, 24]);
 
+    // Bulk actions over the checked alerts still visible
+    let targets = bulk_targets(alerts, filter, checked);
+    let filters: Element<'a, Message> = if targets.is_empty() {
+        filters.into()
+    } else {
+        let bulk_btn = |label: &'a str, color: Color, status: &str| {
+            let msg = Message::BulkUpdateAlerts(targets.clone(), status.to_string());
+            filter_btn(label, false, color, msg)
+        };
+        let bulk_actions = row![
+            text(format!("{} selected:", targets.len()))
+                .size(9)
+                .color(colors::text_muted()),
+            Space::with_width(8),
+            bulk_btn("ACKNOWLEDGE", colors::yellow(), "acknowledged"),
+            Space::with_width(4),
+            bulk_btn("RESOLVE", colors::green(), "resolved"),
+        ]
+        .align_y(Alignment::Center);
+        column![filters, container(bulk_actions).padding([0, 24]), Space::with_height(12)].into()
+    };
+
     // Filter alerts
     let filtered_alerts: Vec<&Alert> = alerts
         .iter()
-        .filter(|alert| {
-            // Filter by severity
-            if let Some(sev) = filter_severity {
-                if alert.severity.to_lowercase() != sev.to_lowercase() {
-                    return false;
-                }
-            }
-            // Filter by status
-            if let Some(st) = filter_status {
-                if alert.status.to_lowercase() != st.to_lowercase() {
-                    return false;
-                }
-            }
-            // Filter by search
-            if !search_query.is_empty() {
-                let query = search_query.to_lowercase();
-                if !alert.title.to_lowercase().contains(&query)
-                    && !alert.source_tool.to_lowercase().contains(&query)
-                    && !alert.device_ip.as_ref().map(|s| s.to_lowercase().contains(&query)).unwrap_or(false)
-                {
-                    return false;
-                }
-            }
-            true
-        })
+        .filter(|alert| matches_filter(alert, filter))
         .collect();
 
     // Alert list
@@ -750,7 +956,7 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No alerts match your filters")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -763,7 +969,17 @@ pub fn view<'a>(
         let mut cards = column![].spacing(8);
         for alert in &filtered_alerts {
             let is_selected = selected_id == Some(&alert.id);
-            cards = cards.push(alert_card(alert, is_selected));
+            let id = alert.id.clone();
+            cards = cards.push(
+                row![
+                    checkbox("", checked.contains(&alert.id))
+                        .on_toggle(move |_| Message::AlertChecked(id.clone()))
+                        .size(14),
+                    alert_card(alert, is_selected),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
         }
         scrollable(
             container(cards)
@@ -783,7 +999,7 @@ pub fn view<'a>(
             container(alert_list)
                 .width(Length::Fill)
                 .height(Length::Fill),
-            alert_detail(alert),
+            alert_detail(alert, alerts),
         ]
         .into()
     } else {
@@ -799,7 +1015,7 @@ pub fn view<'a>(
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         container(stats_section)
@@ -812,14 +1028,14 @@ pub fn view<'a>(
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         filters,
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         container(main_content)
@@ -834,9 +1050,9 @@ pub fn view<'a>(
             .width(Length::Fixed(1100.0))
             .height(Length::Fixed(700.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
@@ -853,3 +1069,140 @@ pub fn view<'a>(
     })
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn alert(id: &str, correlation_id: Option<&str>, minutes_ago: i64) -> Alert {
+        let seen = Utc::now() - Duration::minutes(minutes_ago);
+        Alert {
+            id: id.to_string(),
+            title: format!("alert {id}"),
+            description: None,
+            severity: "high".to_string(),
+            status: "new".to_string(),
+            source_tool: "suricata".to_string(),
+            source_event_id: None,
+            category: None,
+            device_ip: None,
+            device_id: None,
+            fingerprint: None,
+            count: 1,
+            first_seen: seen,
+            last_seen: seen,
+            raw_data: None,
+            correlation_id: correlation_id.map(str::to_string),
+            notes: None,
+            created_at: seen,
+            updated_at: seen,
+        }
+    }
+
+    #[test]
+    fn test_find_related_only_same_incident() {
+        let alerts = vec![
+            alert("a", Some("inc-1"), 5),
+            alert("b", Some("inc-1"), 1),
+            alert("c", Some("inc-2"), 2),
+            alert("d", None, 3),
+            alert("e", Some("inc-1"), 10),
+        ];
+
+        let related = find_related_alerts(&alerts, &alerts[0]);
+        let ids: Vec<&str> = related.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "e"]);
+    }
+
+    #[test]
+    fn test_find_related_without_incident_is_empty() {
+        let alerts = vec![
+            alert("a", None, 1),
+            alert("b", None, 2),
+            alert("c", Some(""), 3),
+            alert("d", Some(""), 4),
+        ];
+
+        assert!(find_related_alerts(&alerts, &alerts[0]).is_empty());
+        assert!(find_related_alerts(&alerts, &alerts[2]).is_empty());
+    }
+
+    #[test]
+    fn test_find_related_single_alert_incident() {
+        let alerts = vec![alert("a", Some("inc-1"), 1), alert("b", Some("inc-2"), 1)];
+        assert!(find_related_alerts(&alerts, &alerts[0]).is_empty());
+    }
+
+    fn with(severity: &str, status: &str) -> Alert {
+        let mut a = alert(&format!("{severity}-{status}"), None, 1);
+        a.severity = severity.to_string();
+        a.status = status.to_string();
+        a
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = FilterSet::default();
+        assert!(matches_filter(&with("low", "resolved"), &filter));
+        assert!(matches_filter(&with("critical", "open"), &filter));
+    }
+
+    #[test]
+    fn test_or_within_category() {
+        let mut filter = FilterSet::default();
+        filter.toggle_severity("critical");
+        filter.toggle_severity("HIGH");
+
+        assert!(matches_filter(&with("critical", "open"), &filter));
+        assert!(matches_filter(&with("High", "resolved"), &filter));
+        assert!(!matches_filter(&with("medium", "open"), &filter));
+    }
+
+    #[test]
+    fn test_and_across_categories() {
+        // (critical OR high) AND open
+        let mut filter = FilterSet::default();
+        filter.toggle_severity("critical");
+        filter.toggle_severity("high");
+        filter.toggle_status("open");
+
+        assert!(matches_filter(&with("critical", "open"), &filter));
+        assert!(matches_filter(&with("high", "open"), &filter));
+        assert!(!matches_filter(&with("high", "acknowledged"), &filter));
+        assert!(!matches_filter(&with("low", "open"), &filter));
+
+        filter.search = "suricata".to_string();
+        assert!(matches_filter(&with("high", "open"), &filter));
+        filter.search = "zeek".to_string();
+        assert!(!matches_filter(&with("high", "open"), &filter));
+    }
+
+    #[test]
+    fn test_bulk_targets_only_checked_and_visible() {
+        let mut open = with("high", "open");
+        open.id = "a1".to_string();
+        let mut resolved = with("high", "resolved");
+        resolved.id = "a2".to_string();
+        let mut unchecked = with("high", "open");
+        unchecked.id = "a3".to_string();
+        let alerts = vec![open, resolved, unchecked];
+        let checked: BTreeSet<String> = ["a2", "a1"].iter().map(|s| s.to_string()).collect();
+
+        let mut filter = FilterSet::default();
+        assert_eq!(bulk_targets(&alerts, &filter, &checked), vec!["a1", "a2"]);
+        filter.toggle_status("open");
+        assert_eq!(bulk_targets(&alerts, &filter, &checked), vec!["a1"]);
+        assert!(bulk_targets(&alerts, &filter, &BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_toggle_deselects() {
+        let mut filter = FilterSet::default();
+        filter.toggle_status("open");
+        filter.toggle_status("resolved");
+        filter.toggle_status("open");
+        assert_eq!(filter.statuses.iter().collect::<Vec<_>>(), vec!["resolved"]);
+        assert!(!matches_filter(&with("high", "open"), &filter));
+    }
+}
@@ -4,7 +4,7 @@
 //! displaying the React NetworkCanvas component will be overlaid.
 //! The actual canvas rendering is done by React in the webview.
 
-use iced::widget::{column, container, text, Space};
+use iced::widget::{button, column, container, row, text, Space};
 use iced::{Element, Length, Color, Background, Border};
 
 use crate::message::Message;
@@ -18,9 +18,17 @@ use crate::theme::colors;
 /// exact region, creating a seamless integration.
 pub fn view<'a>(network: &'a NetworkState) -> Element<'a, Message> {
     let title_row = container(
-        text("Network Canvas")
-            .size(12)
-            .color(colors::TEXT_MUTED)
+        row![
+            text("Network Canvas")
+                .size(12)
+                .color(colors::text_muted()),
+            Space::with_width(Length::Fill),
+            button(text("Export SVG").size(10))
+                .on_press(Message::ExportCanvasSvg)
+                .padding([2, 8])
+                .style(crate::theme::secondary_button_style),
+        ]
+        .align_y(iced::Alignment::Center)
     )
     .padding([8, 12]);
 
@@ -34,7 +42,7 @@ pub fn view<'a>(network: &'a NetworkState) -> Element<'a, Message> {
     let status_row = container(
         text(status_text)
             .size(10)
-            .color(colors::TEXT_MUTED)
+            .color(colors::text_muted())
     )
     .padding([4, 12]);
 
@@ -47,11 +55,11 @@ pub fn view<'a>(network: &'a NetworkState) -> Element<'a, Message> {
                 column![
                     text("React NetworkCanvas")
                         .size(18)
-                        .color(colors::CYAN),
+                        .color(colors::cyan()),
                     Space::with_height(8),
                     text("Loading embedded webview...")
                         .size(12)
-                        .color(colors::TEXT_MUTED),
+                        .color(colors::text_muted()),
                 ]
                 .align_x(iced::Alignment::Center)
             )
@@ -82,7 +90,7 @@ fn canvas_placeholder_style(theme: &iced::Theme) -> container::Style {
     container::Style {
         background: Some(Background::Color(Color::from_rgba(0.02, 0.02, 0.03, 1.0))),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 4.0.into(),
         },
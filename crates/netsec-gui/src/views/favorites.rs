@@ -0,0 +1,149 @@
+//! Pinned devices sidebar.
+//!
+//! Lists favorite devices with their live status and vulnerability count,
+//! resolved from the canvas nodes on every render, with one-click select and
+//! scan actions.
+
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Alignment, Background, Border, Color, Element, Length};
+
+use crate::message::{Message, NodeStatus};
+use crate::state::favorites::{Favorites, MAX_FAVORITES};
+use crate::state::network::{NetworkState, Node};
+use crate::theme::{self, colors};
+
+/// Width of the favorites sidebar.
+pub const FAVORITES_WIDTH: f32 = 200.0;
+
+fn status_color(status: NodeStatus) -> Color {
+    match status {
+        NodeStatus::Online => colors::status_online(),
+        NodeStatus::Offline => colors::status_offline(),
+        NodeStatus::Warning => colors::status_warning(),
+        NodeStatus::Compromised => colors::status_critical(),
+    }
+}
+
+/// Small icon-only action button.
+fn icon_button<'a>(icon: &'a str, msg: Option<Message>) -> Element<'a, Message> {
+    button(text(icon).size(10))
+        .on_press_maybe(msg)
+        .padding([2, 6])
+        .style(theme::secondary_button_style)
+        .into()
+}
+
+/// One pinned device row. `node` is `None` when the device is not currently
+/// in the inventory.
+fn favorite_row<'a>(
+    ip: &'a str,
+    node: Option<&'a Node>,
+    is_selected: bool,
+) -> Element<'a, Message> {
+    let (dot, label, vulns) = match node {
+        Some(node) => (status_color(node.status), node.label.as_str(), node.vulnerabilities.len()),
+        None => (colors::text_muted(), "Not in inventory", 0),
+    };
+
+    let info = column![
+        text(label).size(11).color(if node.is_some() {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        }),
+        row![
+            text(ip).size(9).color(colors::text_muted()),
+            Space::with_width(Length::Fill),
+            if vulns > 0 {
+                text(format!("{} vulns", vulns)).size(9).color(colors::orange())
+            } else {
+                text("")
+            },
+        ],
+    ]
+    .width(Length::Fill);
+
+    let select_msg = node.map(|n| Message::NodeSelected(n.id));
+    let scan_msg = node.map(|n| Message::ScanDevice(n.id));
+
+    let body = button(
+        row![
+            text("\u{25CF}").size(10).color(dot), // ●
+            Space::with_width(6),
+            info,
+        ]
+        .align_y(Alignment::Center),
+    )
+    .on_press_maybe(select_msg)
+    .padding([6, 8])
+    .width(Length::Fill)
+    .style(move |_, status| {
+        let bg = if is_selected {
+            Color { a: 0.15, ..colors::cyan() }
+        } else if matches!(status, button::Status::Hovered) {
+            Color::from_rgba(1.0, 1.0, 1.0, 0.05)
+        } else {
+            Color::TRANSPARENT
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: colors::text_primary(),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    });
+
+    let actions = row![
+        icon_button("\u{25B2}", Some(Message::FavoriteMove(ip.to_string(), -1))), // ▲
+        Space::with_width(2),
+        icon_button("\u{25BC}", Some(Message::FavoriteMove(ip.to_string(), 1))), // ▼
+        Space::with_width(Length::Fill),
+        icon_button("Scan", scan_msg),
+        Space::with_width(2),
+        icon_button("\u{2715}", Some(Message::TogglePinDevice(ip.to_string()))), // ✕
+    ]
+    .align_y(Alignment::Center)
+    .padding([0, 8]);
+
+    column![body, actions].spacing(2).into()
+}
+
+/// Render the favorites sidebar.
+pub fn view<'a>(favorites: &'a Favorites, network: &'a NetworkState) -> Element<'a, Message> {
+    let selected_ip = network.selected_node().map(|n| n.ip.as_str());
+
+    let header = row![
+        text("Pinned").size(12).color(colors::text_muted()),
+        Space::with_width(Length::Fill),
+        text(format!("{}/{}", favorites.len(), MAX_FAVORITES))
+            .size(9)
+            .color(colors::text_muted()),
+    ]
+    .align_y(Alignment::Center)
+    .padding([12, 16]);
+
+    let body: Element<'a, Message> = if favorites.is_empty() {
+        container(
+            text("Pin a device from the inspector to keep it here")
+                .size(10)
+                .color(colors::text_muted()),
+        )
+        .padding([8, 16])
+        .into()
+    } else {
+        let list = favorites.ips().iter().fold(column![].spacing(8), |col, ip| {
+            let node = network.nodes.iter().find(|n| &n.ip == ip);
+            col.push(favorite_row(ip, node, selected_ip == Some(ip.as_str())))
+        });
+        scrollable(container(list).padding([0, 8])).height(Length::Fill).into()
+    };
+
+    container(column![header, body])
+        .width(Length::Fixed(FAVORITES_WIDTH))
+        .height(Length::Fill)
+        .style(theme::panel_style)
+        .into()
+}
@@ -3,16 +3,17 @@
 use iced::widget::{button, column, container, row, text, Space};
 use iced::{Alignment, Background, Border, Color, Element, Length};
 
+use crate::api::WsState;
 use crate::message::Message;
 use crate::theme::{self, colors};
 
 /// Render a danger/warning style button (for vuln report).
 fn danger_button_style(_theme: &iced::Theme, status: iced::widget::button::Status) -> iced::widget::button::Style {
     let base = iced::widget::button::Style {
-        background: Some(Background::Color(Color::from_rgba(0.94, 0.27, 0.27, 0.1))),
-        text_color: colors::RED,
+        background: Some(Background::Color(colors::tint(colors::red(), 0.1))),
+        text_color: colors::red(),
         border: Border {
-            color: Color::from_rgba(0.94, 0.27, 0.27, 0.3),
+            color: colors::tint(colors::red(), 0.3),
             width: 1.0,
             radius: 4.0.into(),
         },
@@ -21,7 +22,7 @@ fn danger_button_style(_theme: &iced::Theme, status: iced::widget::button::Statu
 
     match status {
         iced::widget::button::Status::Hovered => iced::widget::button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.94, 0.27, 0.27, 0.2))),
+            background: Some(Background::Color(colors::tint(colors::red(), 0.2))),
             ..base
         },
         _ => base,
@@ -32,20 +33,21 @@ fn danger_button_style(_theme: &iced::Theme, status: iced::widget::button::Statu
 pub fn view<'a>(
     is_scanning: bool,
     vuln_count: usize,
-    ws_connected: bool,
+    ws_state: Option<&WsState>,
     device_count: usize,
     alert_count: usize,
     scan_count: usize,
+    unread_notifications: usize,
 ) -> Element<'a, Message> {
     // Logo and title
     let logo = container(
         text("\u{25CE}")  // ◎
             .size(20)
-            .color(colors::CYAN)
+            .color(colors::cyan())
     )
     .padding([4, 8])
     .style(|_| container::Style {
-        background: Some(Background::Color(Color::from_rgba(0.13, 0.83, 0.93, 0.1))),
+        background: Some(Background::Color(colors::tint(colors::cyan(), 0.1))),
         border: Border {
             radius: 4.0.into(),
             ..Default::default()
@@ -55,20 +57,27 @@ pub fn view<'a>(
 
     // Status message based on state
     let status_msg = if is_scanning {
-        "Scanning..."
-    } else if !ws_connected {
-        "Connecting to backend..."
+        "Scanning...".to_string()
     } else {
-        "System Ready"
+        match ws_state {
+            Some(WsState::Connected) => "System Ready".to_string(),
+            Some(WsState::Reconnecting { attempt }) => {
+                format!("Reconnecting to backend (attempt {attempt})...")
+            }
+            Some(WsState::Failed) => "Backend unreachable".to_string(),
+            _ => "Connecting to backend...".to_string(),
+        }
     };
 
     // Status color
     let status_color = if is_scanning {
-        colors::YELLOW
-    } else if !ws_connected {
-        colors::RED
+        colors::yellow()
     } else {
-        colors::GREEN
+        match ws_state {
+            Some(WsState::Connected) => colors::green(),
+            Some(WsState::Reconnecting { .. }) => colors::orange(),
+            _ => colors::red(),
+        }
     };
 
     let title_section = row![
@@ -77,7 +86,7 @@ pub fn view<'a>(
         column![
             text("NETWATCH")
                 .size(14)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             row![
                 // Connection status indicator
                 container(Space::with_width(6).height(6))
@@ -92,22 +101,22 @@ pub fn view<'a>(
                 Space::with_width(6),
                 text(status_msg)
                     .size(9)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_width(12),
                 // Device count
                 text(format!("{} devices", device_count))
                     .size(9)
-                    .color(colors::CYAN),
+                    .color(colors::cyan()),
                 Space::with_width(8),
                 // Alert count
                 if alert_count > 0 {
                     text(format!("{} alerts", alert_count))
                         .size(9)
-                        .color(colors::ORANGE)
+                        .color(colors::orange())
                 } else {
                     text("0 alerts")
                         .size(9)
-                        .color(colors::TEXT_MUTED)
+                        .color(colors::text_muted())
                 },
             ]
             .align_y(Alignment::Center)
@@ -129,7 +138,7 @@ pub fn view<'a>(
             )
             .padding([2, 6])
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::RED)),
+                background: Some(Background::Color(colors::red())),
                 border: Border {
                     radius: 8.0.into(),
                     ..Default::default()
@@ -168,7 +177,7 @@ pub fn view<'a>(
             )
             .padding([2, 6])
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::ORANGE)),
+                background: Some(Background::Color(colors::orange())),
                 border: Border {
                     radius: 8.0.into(),
                     ..Default::default()
@@ -193,10 +202,10 @@ pub fn view<'a>(
         .padding([6, 12])
         .style(|_theme, status| {
             let base = iced::widget::button::Style {
-                background: Some(Background::Color(Color::from_rgba(1.0, 0.6, 0.0, 0.1))),
-                text_color: colors::ORANGE,
+                background: Some(Background::Color(colors::tint(colors::orange(), 0.1))),
+                text_color: colors::orange(),
                 border: Border {
-                    color: Color::from_rgba(1.0, 0.6, 0.0, 0.3),
+                    color: colors::tint(colors::orange(), 0.3),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -205,7 +214,7 @@ pub fn view<'a>(
 
             match status {
                 iced::widget::button::Status::Hovered => iced::widget::button::Style {
-                    background: Some(Background::Color(Color::from_rgba(1.0, 0.6, 0.0, 0.2))),
+                    background: Some(Background::Color(colors::tint(colors::orange(), 0.2))),
                     ..base
                 },
                 _ => base,
@@ -226,7 +235,7 @@ pub fn view<'a>(
             )
             .padding([2, 6])
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::CYAN)),
+                background: Some(Background::Color(colors::cyan())),
                 border: Border {
                     radius: 8.0.into(),
                     ..Default::default()
@@ -251,10 +260,10 @@ pub fn view<'a>(
         .padding([6, 12])
         .style(|_theme, status| {
             let base = iced::widget::button::Style {
-                background: Some(Background::Color(Color::from_rgba(0.13, 0.83, 0.93, 0.1))),
-                text_color: colors::CYAN,
+                background: Some(Background::Color(colors::tint(colors::cyan(), 0.1))),
+                text_color: colors::cyan(),
                 border: Border {
-                    color: Color::from_rgba(0.13, 0.83, 0.93, 0.3),
+                    color: colors::tint(colors::cyan(), 0.3),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -263,7 +272,7 @@ pub fn view<'a>(
 
             match status {
                 iced::widget::button::Status::Hovered => iced::widget::button::Style {
-                    background: Some(Background::Color(Color::from_rgba(0.13, 0.83, 0.93, 0.2))),
+                    background: Some(Background::Color(colors::tint(colors::cyan(), 0.2))),
                     ..base
                 },
                 _ => base,
@@ -384,6 +393,42 @@ pub fn view<'a>(
         .padding([6, 12])
         .style(theme::secondary_button_style);
 
+    // Notification center, with an unread badge
+    let inbox_content: Element<'a, Message> = if unread_notifications > 0 {
+        row![
+            text("\u{2709}").size(12), // ✉
+            Space::with_width(6),
+            container(
+                text(unread_notifications.to_string())
+                    .size(9)
+                    .color(Color::WHITE)
+            )
+            .padding([2, 6])
+            .style(|_| container::Style {
+                background: Some(Background::Color(colors::cyan())),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        ]
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        text("\u{2709}").size(12).into() // ✉
+    };
+    let inbox_btn = button(inbox_content)
+        .on_press(Message::ShowNotificationCenter)
+        .padding([6, 12])
+        .style(theme::secondary_button_style);
+
+    // Pinned devices toggle
+    let pinned_btn = button(text("Pinned").size(10))
+        .on_press(Message::ToggleFavoritesPanel)
+        .padding([6, 12])
+        .style(theme::secondary_button_style);
+
     // Inspector toggle
     let inspector_btn = button(text("Inspector").size(10))
         .on_press(Message::ToggleInspectorPanel)
@@ -414,7 +459,11 @@ pub fn view<'a>(
         save_btn,
         Space::with_width(4),
         settings_btn,
+        Space::with_width(4),
+        inbox_btn,
         Space::with_width(12),
+        pinned_btn,
+        Space::with_width(4),
         terminal_btn,
         Space::with_width(4),
         inspector_btn,
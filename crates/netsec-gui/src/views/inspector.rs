@@ -4,8 +4,11 @@ use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Alignment, Background, Border, Color, Element, Length};
 
 use crate::message::{InspectorTab, Message, NodeId, Severity};
-use crate::state::network::{Connection, NetworkState, Node};
+use crate::state::favorites::Favorites;
+use crate::state::network::{Connection, Group, NetworkState, Node};
+use crate::state::traffic::{AnomalyLevel, PeerTraffic, TrafficHistory};
 use crate::theme::{self, colors};
+use crate::views::traffic::format_bytes;
 
 /// Fixed width for the inspector panel.
 pub const INSPECTOR_WIDTH: f32 = 280.0;
@@ -32,18 +35,18 @@ fn tab_button<'a>(
     .width(Length::FillPortion(1))
     .style(move |_, status| {
         let bg = if is_active {
-            Color::from_rgba(0.13, 0.83, 0.93, 0.05)
+            colors::tint(colors::cyan(), 0.05)
         } else if matches!(status, iced::widget::button::Status::Hovered) {
-            Color::from_rgba(1.0, 1.0, 1.0, 0.05)
+            colors::overlay(0.05)
         } else {
             Color::TRANSPARENT
         };
 
         iced::widget::button::Style {
             background: Some(Background::Color(bg)),
-            text_color: if is_active { colors::CYAN } else { colors::TEXT_MUTED },
+            text_color: if is_active { colors::cyan() } else { colors::text_muted() },
             border: Border {
-                color: if is_active { colors::CYAN } else { Color::TRANSPARENT },
+                color: if is_active { colors::cyan() } else { Color::TRANSPARENT },
                 width: if is_active { 2.0 } else { 0.0 },
                 radius: 0.0.into(),
             },
@@ -58,7 +61,7 @@ fn info_row<'a>(key: &'a str, value: &'a str, value_color: Color) -> Element<'a,
     row![
         text(key)
             .size(9)
-            .color(colors::TEXT_MUTED),
+            .color(colors::text_muted()),
         Space::with_width(Length::Fill),
         text(value)
             .size(10)
@@ -67,16 +70,72 @@ fn info_row<'a>(key: &'a str, value: &'a str, value_color: Color) -> Element<'a,
     .into()
 }
 
+/// Render a selected group: its members and collapse/delete actions.
+fn view_group<'a>(group: &'a Group, network: &'a NetworkState) -> Element<'a, Message> {
+    let mut members = column![].spacing(6);
+    for node in group.members.iter().filter_map(|&id| network.get_node(id)) {
+        members = members.push(
+            button(
+                row![
+                    text(&node.label).size(10).color(colors::text_primary()),
+                    Space::with_width(Length::Fill),
+                    text(&node.ip).size(9).color(colors::text_muted()),
+                ]
+                .align_y(Alignment::Center),
+            )
+            .on_press(Message::NodeSelected(node.id))
+            .padding([4, 8])
+            .width(Length::Fill)
+            .style(theme::secondary_button_style),
+        );
+    }
+
+    let collapse_label = if group.collapsed { "Expand" } else { "Collapse" };
+    let actions = row![
+        button(text(collapse_label).size(10))
+            .on_press(Message::SetGroupCollapsed(group.id, !group.collapsed))
+            .padding([4, 8])
+            .style(theme::primary_button_style),
+        Space::with_width(6),
+        button(text("Ungroup").size(10))
+            .on_press(Message::DeleteGroup(group.id, true))
+            .padding([4, 8])
+            .style(theme::secondary_button_style),
+        Space::with_width(6),
+        button(text("Delete All").size(10))
+            .on_press(Message::ShowConfirmDialog(
+                format!("Delete group \"{}\" and its {} nodes?", group.label, group.members.len()),
+                Box::new(Message::DeleteGroup(group.id, false)),
+            ))
+            .padding([4, 8])
+            .style(theme::secondary_button_style),
+    ];
+
+    column![
+        text(&group.label).size(16).color(colors::text_primary()),
+        text(format!("Group \u{2022} {} nodes", group.members.len()))
+            .size(9)
+            .color(colors::cyan()),
+        Space::with_height(8),
+        actions,
+        Space::with_height(8),
+        text("MEMBERS").size(9).color(colors::text_muted()),
+        scrollable(members),
+    ]
+    .spacing(6)
+    .into()
+}
+
 /// Render the details tab content.
 fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Message> {
     let mut content = column![].spacing(12);
 
     // Status indicator
     let status_color = match node.status {
-        crate::message::NodeStatus::Online => colors::GREEN,
-        crate::message::NodeStatus::Warning => colors::YELLOW,
-        crate::message::NodeStatus::Compromised => colors::RED,
-        crate::message::NodeStatus::Offline => colors::TEXT_MUTED,
+        crate::message::NodeStatus::Online => colors::green(),
+        crate::message::NodeStatus::Warning => colors::yellow(),
+        crate::message::NodeStatus::Compromised => colors::red(),
+        crate::message::NodeStatus::Offline => colors::text_muted(),
     };
 
     let status_text = match node.status {
@@ -101,19 +160,27 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         text(&node.label)
             .size(16)
             .color(if matches!(node.status, crate::message::NodeStatus::Compromised) {
-                colors::RED
+                colors::red()
             } else {
-                colors::TEXT_PRIMARY
+                colors::text_primary()
             }),
     ]
     .align_y(Alignment::Center);
 
     let node_type = text(node.node_type.label())
         .size(9)
-        .color(colors::CYAN);
+        .color(colors::cyan());
 
     content = content.push(header);
     content = content.push(node_type);
+    if let Some(group) = network.group_of(node.id) {
+        content = content.push(
+            button(text(format!("\u{2B1A} {}", group.label)).size(9))
+                .on_press(Message::NodeSelected(group.id))
+                .padding([2, 6])
+                .style(theme::secondary_button_style),
+        );
+    }
     content = content.push(Space::with_height(8));
 
     // Security breach alert
@@ -121,25 +188,25 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         let alert = container(
             column![
                 row![
-                    text("\u{26A0}").size(12).color(colors::RED),
+                    text("\u{26A0}").size(12).color(colors::red()),
                     Space::with_width(8),
                     text("SECURITY BREACH")
                         .size(9)
-                        .color(colors::RED),
+                        .color(colors::red()),
                 ]
                 .align_y(Alignment::Center),
                 Space::with_height(4),
                 text("Target has been successfully exploited.")
                     .size(9)
-                    .color(Color::from_rgba(1.0, 0.5, 0.5, 0.8)),
+                    .color(colors::tint(colors::red(), 0.8)),
             ]
         )
         .padding(12)
         .width(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(Color::from_rgba(0.94, 0.27, 0.27, 0.1))),
+            background: Some(Background::Color(colors::tint(colors::red(), 0.1))),
             border: Border {
-                color: Color::from_rgba(0.94, 0.27, 0.27, 0.3),
+                color: colors::tint(colors::red(), 0.3),
                 width: 1.0,
                 radius: 6.0.into(),
             },
@@ -153,14 +220,14 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         content = content.push(
             text("DETECTED VULNERABILITIES")
                 .size(9)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
         );
 
         for vuln in &node.vulnerabilities {
             let severity_color = match vuln.severity {
-                Severity::Critical => colors::RED,
-                Severity::High => colors::ORANGE,
-                Severity::Medium => colors::YELLOW,
+                Severity::Critical => colors::red(),
+                Severity::High => colors::orange(),
+                Severity::Medium => colors::yellow(),
                 Severity::Low => Color::from_rgb(0.23, 0.51, 0.95),
             };
 
@@ -169,7 +236,7 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     row![
                         text(&vuln.cve)
                             .size(10)
-                            .color(colors::TEXT_PRIMARY),
+                            .color(colors::text_primary()),
                         Space::with_width(8),
                         container(
                             text(vuln.severity.label())
@@ -194,15 +261,15 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     Space::with_height(4),
                     text(&vuln.description)
                         .size(8)
-                        .color(colors::TEXT_MUTED),
+                        .color(colors::text_muted()),
                 ]
             )
             .padding(8)
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.03))),
+                background: Some(Background::Color(colors::overlay(0.03))),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -214,11 +281,11 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         // No vulnerabilities
         let empty = container(
             column![
-                text("\u{2714}").size(24).color(colors::GREEN), // ✔
+                text("\u{2714}").size(24).color(colors::green()), // ✔
                 Space::with_height(8),
                 text("No Vulnerabilities Detected")
                     .size(9)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -226,7 +293,7 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         .width(Length::Fill)
         .style(|_| container::Style {
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 4.0.into(),
             },
@@ -240,30 +307,30 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
         content = content.push(Space::with_height(8));
         content = content.push(
             row![
-                text("\u{2261}").size(12).color(colors::GREEN), // ≡
+                text("\u{2261}").size(12).color(colors::green()), // ≡
                 Space::with_width(8),
                 text("OPEN PORTS")
                     .size(9)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_y(Alignment::Center)
         );
 
         for port in &node.ports {
-            let port_row = container(
+            let mut port_content = column![
                 row![
                     text(format!("{}/{}", port.number, port.protocol))
                         .size(10)
-                        .color(colors::GREEN),
+                        .color(colors::green()),
                     Space::with_width(8),
                     container(
                         text(&port.state)
                             .size(7)
-                            .color(colors::GREEN)
+                            .color(colors::green())
                     )
                     .padding([2, 4])
                     .style(|_| container::Style {
-                        background: Some(Background::Color(Color::from_rgba(0.13, 0.77, 0.37, 0.2))),
+                        background: Some(Background::Color(colors::tint(colors::green(), 0.2))),
                         border: Border {
                             radius: 2.0.into(),
                             ..Default::default()
@@ -273,16 +340,34 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     Space::with_width(Length::Fill),
                     text(port.service_name.as_deref().unwrap_or("-"))
                         .size(9)
-                        .color(colors::TEXT_MUTED),
+                        .color(colors::text_muted()),
                 ]
                 .align_y(Alignment::Center)
-            )
+            ];
+
+            // Web-service findings (NSE http-title / http-server-header)
+            if let Some(ref title) = port.http_title {
+                port_content = port_content.push(
+                    text(format!("\u{201C}{}\u{201D}", title)) // “title”
+                        .size(8)
+                        .color(colors::text_secondary()),
+                );
+            }
+            if let Some(ref server) = port.server_header {
+                port_content = port_content.push(
+                    text(format!("Server: {}", server))
+                        .size(8)
+                        .color(colors::text_muted()),
+                );
+            }
+
+            let port_row = container(port_content.spacing(2))
             .padding([4, 8])
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(Color::from_rgba(0.13, 0.77, 0.37, 0.05))),
+                background: Some(Background::Color(colors::tint(colors::green(), 0.05))),
                 border: Border {
-                    color: Color::from_rgba(0.13, 0.77, 0.37, 0.2),
+                    color: colors::tint(colors::green(), 0.2),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -296,16 +381,16 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
     content = content.push(Space::with_height(8));
     let info_section = container(
         column![
-            info_row("Address", &node.ip, colors::TEXT_PRIMARY),
+            info_row("Address", &node.ip, colors::text_primary()),
             if let Some(ref vendor) = node.vendor {
-                info_row("Vendor", vendor, colors::CYAN)
+                info_row("Vendor", vendor, colors::cyan())
             } else {
-                info_row("Vendor", "Unknown", colors::TEXT_MUTED)
+                info_row("Vendor", "Unknown", colors::text_muted())
             },
             if let Some(ref os) = node.os_family {
-                info_row("OS", os, colors::TEXT_SECONDARY)
+                info_row("OS", os, colors::text_secondary())
             } else {
-                info_row("OS", "Unknown", colors::TEXT_MUTED)
+                info_row("OS", "Unknown", colors::text_muted())
             },
         ]
         .spacing(6)
@@ -313,9 +398,9 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
     .padding(12)
     .width(Length::Fill)
     .style(|_| container::Style {
-        background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.03))),
+        background: Some(Background::Color(colors::overlay(0.03))),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 4.0.into(),
         },
@@ -337,15 +422,15 @@ fn view_details<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
     .width(Length::Fill)
     .style(|_, status| {
         let bg = if matches!(status, iced::widget::button::Status::Hovered) {
-            Color::from_rgba(0.94, 0.27, 0.27, 0.2)
+            colors::tint(colors::red(), 0.2)
         } else {
-            Color::from_rgba(0.94, 0.27, 0.27, 0.1)
+            colors::tint(colors::red(), 0.1)
         };
         iced::widget::button::Style {
             background: Some(Background::Color(bg)),
-            text_color: colors::RED,
+            text_color: colors::red(),
             border: Border {
-                color: Color::from_rgba(0.94, 0.27, 0.27, 0.3),
+                color: colors::tint(colors::red(), 0.3),
                 width: 1.0,
                 radius: 4.0.into(),
             },
@@ -368,7 +453,7 @@ fn view_connections<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a
             column![
                 text("No Active Connections")
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -387,8 +472,8 @@ fn view_connections<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a
 
         if let Some(other) = other_node {
             let status_color = match other.status {
-                crate::message::NodeStatus::Online => colors::GREEN,
-                _ => colors::RED,
+                crate::message::NodeStatus::Online => colors::green(),
+                _ => colors::red(),
             };
 
             let conn_type_label = match conn.connection_type {
@@ -412,20 +497,20 @@ fn view_connections<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a
                         column![
                             text(&other.label)
                                 .size(11)
-                                .color(colors::TEXT_PRIMARY),
+                                .color(colors::text_primary()),
                             text(&other.ip)
                                 .size(8)
-                                .color(colors::TEXT_MUTED),
+                                .color(colors::text_muted()),
                         ],
                         Space::with_width(Length::Fill),
                         container(
                             text(conn_type_label)
                                 .size(7)
-                                .color(colors::CYAN)
+                                .color(colors::cyan())
                         )
                         .padding([2, 6])
                         .style(|_| container::Style {
-                            background: Some(Background::Color(Color::from_rgba(0.13, 0.83, 0.93, 0.1))),
+                            background: Some(Background::Color(colors::tint(colors::cyan(), 0.1))),
                             border: Border {
                                 radius: 4.0.into(),
                                 ..Default::default()
@@ -439,9 +524,9 @@ fn view_connections<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a
             .padding(12)
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.03))),
+                background: Some(Background::Color(colors::overlay(0.03))),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -457,15 +542,153 @@ fn view_connections<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a
         .into()
 }
 
-/// Render the traffic tab content (placeholder).
-fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Message> {
+/// Accent color for a peer's anomaly level (red/amber for deviations).
+fn anomaly_color(level: AnomalyLevel) -> Color {
+    match level {
+        AnomalyLevel::Normal => colors::cyan(),
+        AnomalyLevel::Elevated => colors::yellow(),
+        AnomalyLevel::Anomalous => colors::red(),
+    }
+}
+
+/// Render one observed peer with its throughput, baseline, and anomaly badge.
+fn traffic_peer_row<'a>(
+    peer: &PeerTraffic,
+    label: String,
+    max_bytes: f64,
+    threshold: f32,
+) -> Element<'a, Message> {
+    let level = peer.anomaly_level(threshold);
+    let accent = anomaly_color(level);
+    let badge = match level {
+        AnomalyLevel::Normal => "",
+        AnomalyLevel::Elevated => "ELEVATED",
+        AnomalyLevel::Anomalous => "ANOMALY",
+    };
+    let portion = if max_bytes > 0.0 {
+        ((peer.current.bytes / max_bytes) * 100.0).clamp(1.0, 100.0) as u16
+    } else {
+        1
+    };
+    let baseline_text = match peer.baseline {
+        Some(b) => format!(
+            "Baseline {} \u{00B7} {:.0} conns",
+            format_bytes(b.bytes as i64),
+            b.connections
+        ),
+        None => "Building baseline...".to_string(),
+    };
+    let is_flagged = level != AnomalyLevel::Normal;
+
+    container(
+        column![
+            row![
+                text(label).size(9).color(colors::text_muted()),
+                Space::with_width(Length::Fill),
+                text(badge).size(8).color(accent),
+            ],
+            Space::with_height(8),
+            // Throughput bar relative to the busiest peer
+            container(row![
+                container(Space::with_width(Length::FillPortion(portion)))
+                    .height(Length::Fixed(4.0))
+                    .style(move |_| container::Style {
+                        background: Some(Background::Color(accent)),
+                        border: Border {
+                            radius: 2.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+                Space::with_width(Length::FillPortion(100 - portion)),
+            ])
+            .width(Length::Fill)
+            .height(Length::Fixed(4.0))
+            .style(|_| container::Style {
+                background: Some(Background::Color(colors::overlay(0.1))),
+                border: Border {
+                    radius: 2.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            Space::with_height(4),
+            row![
+                text(format_bytes(peer.current.bytes as i64)).size(9).color(accent),
+                Space::with_width(Length::Fill),
+                text(format!("{:.0} conns", peer.current.connections))
+                    .size(9)
+                    .color(accent),
+            ],
+            text(baseline_text).size(8).color(colors::text_muted()),
+        ]
+    )
+    .padding(12)
+    .width(Length::Fill)
+    .style(move |_| container::Style {
+        background: Some(Background::Color(if is_flagged {
+            Color { a: 0.08, ..accent }
+        } else {
+            colors::overlay(0.03)
+        })),
+        border: Border {
+            color: if is_flagged { accent } else { colors::border() },
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Render the traffic tab content.
+///
+/// Shows observed peers from real traffic flows, highlighting those above
+/// their baseline. Falls back to topology links when no flows were recorded
+/// for the device.
+fn view_traffic<'a>(
+    node: &'a Node,
+    network: &'a NetworkState,
+    traffic: &TrafficHistory,
+    threshold: f32,
+) -> Element<'a, Message> {
+    let peers = traffic.peers(&node.ip);
+    if !peers.is_empty() {
+        let max_bytes = peers.iter().map(|p| p.current.bytes).fold(0.0, f64::max);
+        let flagged = peers
+            .iter()
+            .filter(|p| p.anomaly_level(threshold) != AnomalyLevel::Normal)
+            .count();
+
+        let summary = text(if flagged == 0 {
+            format!("{} peers \u{00B7} no anomalies", peers.len())
+        } else {
+            format!("{} peers \u{00B7} {} above baseline", peers.len(), flagged)
+        })
+        .size(9)
+        .color(if flagged == 0 { colors::text_muted() } else { colors::yellow() });
+
+        let mut content = column![summary].spacing(12);
+        for peer in &peers {
+            let label = network
+                .nodes
+                .iter()
+                .find(|n| n.ip == peer.peer_ip)
+                .map(|n| format!("PEER: {} ({})", n.label, peer.peer_ip))
+                .unwrap_or_else(|| format!("PEER: {}", peer.peer_ip));
+            content = content.push(traffic_peer_row(peer, label, max_bytes, threshold));
+        }
+
+        return scrollable(content).height(Length::Fill).into();
+    }
+
     let connections = network.connections_for_node(node.id);
 
     if connections.is_empty() {
         return container(
             text("No Active Connections")
                 .size(10)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -486,11 +709,11 @@ fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     row![
                         text(format!("LINK: {}", other.label))
                             .size(9)
-                            .color(colors::TEXT_MUTED),
+                            .color(colors::text_muted()),
                         Space::with_width(Length::Fill),
                         text("LIVE")
                             .size(8)
-                            .color(colors::CYAN),
+                            .color(colors::cyan()),
                     ],
                     Space::with_height(8),
                     // Traffic bar visualization
@@ -499,7 +722,7 @@ fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                             container(Space::with_width(Length::FillPortion((conn.traffic * 10.0) as u16)))
                                 .height(Length::Fixed(4.0))
                                 .style(|_| container::Style {
-                                    background: Some(Background::Color(colors::CYAN)),
+                                    background: Some(Background::Color(colors::cyan())),
                                     border: Border {
                                         radius: 2.0.into(),
                                         ..Default::default()
@@ -512,7 +735,7 @@ fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     .width(Length::Fill)
                     .height(Length::Fixed(4.0))
                     .style(|_| container::Style {
-                        background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.1))),
+                        background: Some(Background::Color(colors::overlay(0.1))),
                         border: Border {
                             radius: 2.0.into(),
                             ..Default::default()
@@ -522,15 +745,15 @@ fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
                     Space::with_height(4),
                     text(format!("{:.1} MB/s", conn.traffic))
                         .size(9)
-                        .color(colors::CYAN),
+                        .color(colors::cyan()),
                 ]
             )
             .padding(12)
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.03))),
+                background: Some(Background::Color(colors::overlay(0.03))),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -550,17 +773,48 @@ fn view_traffic<'a>(node: &'a Node, network: &'a NetworkState) -> Element<'a, Me
 pub fn view<'a>(
     network: &'a NetworkState,
     active_tab: InspectorTab,
+    traffic: &TrafficHistory,
+    anomaly_threshold: f32,
+    favorites: &Favorites,
 ) -> Element<'a, Message> {
     let selected = network.selected_node();
 
-    // Header
+    // Header, with a pin toggle for the selected device
+    let pin_button: Element<'a, Message> = match selected {
+        Some(node) => {
+            let pinned = favorites.contains(&node.ip);
+            button(text(if pinned { "\u{2605} Pinned" } else { "\u{2606} Pin" }).size(10)) // ★ / ☆
+                .on_press(Message::TogglePinDevice(node.ip.clone()))
+                .padding([4, 8])
+                .style(if pinned {
+                    theme::primary_button_style
+                } else {
+                    theme::secondary_button_style
+                })
+                .into()
+        }
+        None => Space::with_width(0).into(),
+    };
     let header = container(
-        text("Inspector")
-            .size(12)
-            .color(colors::TEXT_MUTED)
+        row![
+            text("Inspector")
+                .size(12)
+                .color(colors::text_muted()),
+            Space::with_width(Length::Fill),
+            pin_button,
+        ]
+        .align_y(Alignment::Center)
     )
     .padding([12, 16]);
 
+    if let Some(group) = network.selected_group() {
+        return container(column![header, container(view_group(group, network)).padding(16)])
+            .width(Length::Fixed(INSPECTOR_WIDTH))
+            .height(Length::Fill)
+            .style(theme::panel_style)
+            .into();
+    }
+
     if let Some(node) = selected {
         // Tab bar
         let tabs = row![
@@ -574,7 +828,9 @@ pub fn view<'a>(
             match active_tab {
                 InspectorTab::Details => view_details(node, network),
                 InspectorTab::Connections => view_connections(node, network),
-                InspectorTab::Traffic => view_traffic(node, network),
+                InspectorTab::Traffic => {
+                    view_traffic(node, network, traffic, anomaly_threshold)
+                }
             }
         )
         .padding(16);
@@ -586,7 +842,7 @@ pub fn view<'a>(
                     .width(Length::Fill)
                     .style(|_| container::Style {
                         border: Border {
-                            color: colors::BORDER,
+                            color: colors::border(),
                             width: 1.0,
                             ..Default::default()
                         },
@@ -606,11 +862,11 @@ pub fn view<'a>(
                 header,
                 container(
                     column![
-                        text("\u{1F50D}").size(32).color(Color::from_rgba(1.0, 1.0, 1.0, 0.1)),
+                        text("\u{1F50D}").size(32).color(colors::overlay(0.1)),
                         Space::with_height(16),
                         text("Select a device to inspect")
                             .size(11)
-                            .color(colors::TEXT_MUTED),
+                            .color(colors::text_muted()),
                     ]
                     .align_x(Alignment::Center)
                 )
@@ -2,12 +2,15 @@
 
 pub mod alerts;
 pub mod canvas;
+pub mod favorites;
 pub mod header;
 pub mod inspector;
+pub mod notification_center;
 pub mod scans;
 pub mod scheduler;
 pub mod settings;
 pub mod terminal;
+pub mod theme_editor;
 pub mod toolbar;
 pub mod tools;
 pub mod traffic;
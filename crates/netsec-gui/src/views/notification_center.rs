@@ -0,0 +1,214 @@
+//! Notification center panel view.
+//!
+//! Lists past alert, scan and device notifications with read/unread state.
+//! Clicking an entry marks it read and jumps to the related detail view.
+
+use iced::widget::{button, column, container, horizontal_rule, row, scrollable, text, Space};
+use iced::{Alignment, Background, Border, Color, Element, Length};
+
+use crate::message::Message;
+use crate::state::notifications::{
+    NotificationCenter, NotificationEntry, NotificationFilter, NotificationKind,
+};
+use crate::theme::{self, colors};
+
+/// Accent color for an entry: alert severity, or a fixed color per kind.
+fn entry_color(entry: &NotificationEntry) -> Color {
+    match entry.kind {
+        NotificationKind::Alert => match entry.severity.as_deref() {
+            Some("critical") => colors::red(),
+            Some("high") => colors::orange(),
+            Some("medium") => colors::yellow(),
+            _ => colors::text_secondary(),
+        },
+        NotificationKind::Scan => colors::cyan(),
+        NotificationKind::Device => colors::green(),
+    }
+}
+
+/// Filter chip button.
+fn chip<'a>(label: &'a str, active: bool, msg: Message) -> Element<'a, Message> {
+    button(text(label).size(9))
+        .on_press(msg)
+        .padding([4, 10])
+        .style(if active {
+            theme::primary_button_style
+        } else {
+            theme::secondary_button_style
+        })
+        .into()
+}
+
+/// One notification row.
+fn entry_row(entry: &NotificationEntry) -> Element<'_, Message> {
+    let accent = entry_color(entry);
+    let unread = !entry.read;
+
+    let content = row![
+        text(if unread { "\u{25CF}" } else { " " }) // ●
+            .size(10)
+            .color(colors::cyan())
+            .width(Length::Fixed(12.0)),
+        column![
+            row![
+                text(entry.kind.label()).size(8).color(accent),
+                Space::with_width(8),
+                text(entry.timestamp.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                    .size(8)
+                    .color(colors::text_muted()),
+            ]
+            .align_y(Alignment::Center),
+            text(&entry.title).size(11).color(if unread {
+                colors::text_primary()
+            } else {
+                colors::text_secondary()
+            }),
+            text(&entry.body).size(9).color(colors::text_muted()),
+        ]
+        .spacing(2)
+        .width(Length::Fill),
+    ]
+    .align_y(Alignment::Center);
+
+    button(content)
+        .on_press(Message::NotificationOpened(entry.id))
+        .padding([8, 12])
+        .width(Length::Fill)
+        .style(move |_, status| {
+            let bg = if matches!(status, button::Status::Hovered) {
+                Color::from_rgba(1.0, 1.0, 1.0, 0.06)
+            } else if unread {
+                Color::from_rgba(1.0, 1.0, 1.0, 0.03)
+            } else {
+                Color::TRANSPARENT
+            };
+            button::Style {
+                background: Some(Background::Color(bg)),
+                text_color: colors::text_primary(),
+                border: Border {
+                    color: Color { a: 0.4, ..accent },
+                    width: if unread { 1.0 } else { 0.0 },
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+/// Render the notification center.
+pub fn view(center: &NotificationCenter, filter: NotificationFilter) -> Element<'_, Message> {
+    let unread = center.unread_count();
+
+    let header = row![
+        text("Notifications").size(18).color(colors::text_primary()),
+        Space::with_width(8),
+        text(format!("{} unread", unread)).size(10).color(colors::text_muted()),
+        Space::with_width(Length::Fill),
+        button(text("\u{2715}").size(14)) // ✕
+            .on_press(Message::HideNotificationCenter)
+            .padding([4, 8])
+            .style(|_theme, status| {
+                let bg = match status {
+                    button::Status::Hovered => colors::red(),
+                    _ => Color::TRANSPARENT,
+                };
+                button::Style {
+                    background: Some(Background::Color(bg)),
+                    text_color: colors::text_muted(),
+                    border: Border::default(),
+                    ..Default::default()
+                }
+            }),
+    ]
+    .padding([12, 16])
+    .align_y(Alignment::Center);
+
+    let kind_chip = |label, kind: Option<NotificationKind>| {
+        chip(label, filter.kind == kind, Message::NotificationFilterKind(kind))
+    };
+    let filters = row![
+        kind_chip("ALL", None),
+        Space::with_width(4),
+        kind_chip("ALERTS", Some(NotificationKind::Alert)),
+        Space::with_width(4),
+        kind_chip("SCANS", Some(NotificationKind::Scan)),
+        Space::with_width(4),
+        kind_chip("DEVICES", Some(NotificationKind::Device)),
+        Space::with_width(12),
+        chip("UNREAD ONLY", filter.unread_only, Message::NotificationToggleUnreadOnly),
+    ]
+    .align_y(Alignment::Center)
+    .padding([8, 16]);
+
+    let entries: Vec<&NotificationEntry> = center.filtered(filter).collect();
+    let list: Element<'_, Message> = if entries.is_empty() {
+        container(
+            text(if center.is_empty() {
+                "No notifications yet"
+            } else {
+                "No notifications match the filter"
+            })
+            .size(11)
+            .color(colors::text_muted()),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+    } else {
+        let rows = entries
+            .into_iter()
+            .fold(column![].spacing(4), |col, entry| col.push(entry_row(entry)));
+        scrollable(container(rows).padding([8, 16])).height(Length::Fill).into()
+    };
+
+    let footer = row![
+        button(text("Clear").size(11))
+            .on_press_maybe((!center.is_empty()).then_some(Message::NotificationsClear))
+            .padding([8, 16])
+            .style(theme::secondary_button_style),
+        Space::with_width(Length::Fill),
+        button(text("Mark All Read").size(11))
+            .on_press_maybe((unread > 0).then_some(Message::NotificationsMarkAllRead))
+            .padding([8, 16])
+            .style(theme::primary_button_style),
+    ]
+    .align_y(Alignment::Center)
+    .padding(16);
+
+    let content = column![
+        header,
+        horizontal_rule(1),
+        filters,
+        list,
+        horizontal_rule(1),
+        footer,
+    ];
+
+    // Modal overlay
+    container(
+        container(content)
+            .width(Length::Fixed(520.0))
+            .height(Length::Fixed(620.0))
+            .style(|_| container::Style {
+                background: Some(Background::Color(colors::bg_primary())),
+                border: Border {
+                    color: colors::border(),
+                    width: 1.0,
+                    radius: 8.0.into(),
+                },
+                ..Default::default()
+            }),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(|_| container::Style {
+        background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.7))),
+        ..Default::default()
+    })
+    .into()
+}
@@ -41,11 +41,11 @@ impl ScanStatus {
 
     pub fn color(&self) -> Color {
         match self {
-            Self::Pending => colors::YELLOW,
-            Self::Running => colors::CYAN,
-            Self::Completed => colors::GREEN,
-            Self::Failed => colors::RED,
-            Self::Cancelled => colors::TEXT_MUTED,
+            Self::Pending => colors::yellow(),
+            Self::Running => colors::cyan(),
+            Self::Completed => colors::green(),
+            Self::Failed => colors::red(),
+            Self::Cancelled => colors::text_muted(),
         }
     }
 
@@ -96,12 +96,12 @@ fn status_badge(status: ScanStatus) -> Element<'static, Message> {
 /// Render a scan type badge.
 fn scan_type_badge(scan_type: &str) -> Element<'_, Message> {
     let color = match scan_type.to_lowercase().as_str() {
-        "network" | "discovery" => colors::CYAN,
-        "vulnerability" | "vuln" => colors::ORANGE,
-        "service" => colors::GREEN,
+        "network" | "discovery" => colors::cyan(),
+        "vulnerability" | "vuln" => colors::orange(),
+        "service" => colors::green(),
         "os" => Color::from_rgb(0.6, 0.4, 0.8),
-        "full" => colors::RED,
-        _ => colors::TEXT_SECONDARY,
+        "full" => colors::red(),
+        _ => colors::text_secondary(),
     };
 
     container(
@@ -122,6 +122,40 @@ fn scan_type_badge(scan_type: &str) -> Element<'_, Message> {
     .into()
 }
 
+/// Scans passing the status filter, as listed on the dashboard: running
+/// scans first, then newest first.
+pub fn filtered_scans<'a>(scans: &'a [Scan], filter_status: Option<&str>) -> Vec<&'a Scan> {
+    let mut filtered: Vec<&Scan> = scans
+        .iter()
+        .filter(|scan| {
+            if let Some(status) = filter_status {
+                let scan_status = ScanStatus::from_str(&scan.status);
+                match status {
+                    "running" => scan_status == ScanStatus::Running,
+                    "completed" => scan_status == ScanStatus::Completed,
+                    "failed" => scan_status == ScanStatus::Failed,
+                    "pending" => scan_status == ScanStatus::Pending,
+                    _ => true,
+                }
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    filtered.sort_by(|a, b| {
+        let a_running = ScanStatus::from_str(&a.status) == ScanStatus::Running;
+        let b_running = ScanStatus::from_str(&b.status) == ScanStatus::Running;
+
+        match (a_running, b_running) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => b.created_at.cmp(&a.created_at),
+        }
+    });
+    filtered
+}
+
 /// Render a scan card.
 fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
     let status = ScanStatus::from_str(&scan.status);
@@ -155,7 +189,7 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
     let header = row![
         text(scan.tool.to_uppercase())
             .size(12)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(8),
         scan_type_badge(&scan.scan_type),
         Space::with_width(Length::Fill),
@@ -165,11 +199,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
 
     // Target row
     let target_row = row![
-        text("\u{1F3AF}").size(10).color(colors::TEXT_MUTED), // 🎯
+        text("\u{1F3AF}").size(10).color(colors::text_muted()), // 🎯
         Space::with_width(6),
         text(scan.target.clone())
             .size(10)
-            .color(colors::CYAN),
+            .color(colors::cyan()),
     ]
     .align_y(Alignment::Center);
 
@@ -181,7 +215,7 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
                 .height(4)
                 .style(|_| progress_bar::Style {
                     background: Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.1)),
-                    bar: Background::Color(colors::CYAN),
+                    bar: Background::Color(colors::cyan()),
                     border: Border {
                         radius: 2.0.into(),
                         ..Default::default()
@@ -191,11 +225,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
             row![
                 text(format!("{}%", scan.progress))
                     .size(9)
-                    .color(colors::CYAN),
+                    .color(colors::cyan()),
                 Space::with_width(Length::Fill),
                 text(time_display)
                     .size(9)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
         ]
         .into()
@@ -204,11 +238,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
         row![
             if scan.devices_found > 0 {
                 row![
-                    text("\u{1F4BB}").size(9).color(colors::TEXT_MUTED),
+                    text("\u{1F4BB}").size(9).color(colors::text_muted()),
                     Space::with_width(4),
                     text(format!("{} devices", scan.devices_found))
                         .size(9)
-                        .color(colors::GREEN),
+                        .color(colors::green()),
                 ]
                 .align_y(Alignment::Center)
             } else {
@@ -217,11 +251,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
             Space::with_width(12),
             if scan.alerts_generated > 0 {
                 row![
-                    text("\u{26A0}").size(9).color(colors::TEXT_MUTED),
+                    text("\u{26A0}").size(9).color(colors::text_muted()),
                     Space::with_width(4),
                     text(format!("{} alerts", scan.alerts_generated))
                         .size(9)
-                        .color(colors::ORANGE),
+                        .color(colors::orange()),
                 ]
                 .align_y(Alignment::Center)
             } else {
@@ -230,7 +264,7 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
             Space::with_width(Length::Fill),
             text(time_display)
                 .size(9)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ]
         .align_y(Alignment::Center)
         .into()
@@ -240,11 +274,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
     let error_section: Element<'_, Message> = if let Some(ref error) = scan.error_message {
         container(
             row![
-                text("\u{26A0}").size(10).color(colors::RED),
+                text("\u{26A0}").size(10).color(colors::red()),
                 Space::with_width(6),
                 text(error.clone())
                     .size(9)
-                    .color(colors::RED),
+                    .color(colors::red()),
             ]
             .align_y(Alignment::Center)
         )
@@ -280,11 +314,11 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
     };
 
     let border_color = if is_selected {
-        colors::CYAN
+        colors::cyan()
     } else if is_running {
         Color::from_rgba(0.13, 0.83, 0.93, 0.3)
     } else {
-        colors::BORDER
+        colors::border()
     };
 
     button(
@@ -303,7 +337,7 @@ fn scan_card(scan: &Scan, is_selected: bool) -> Element<'_, Message> {
         };
         iced::widget::button::Style {
             background: Some(Background::Color(bg)),
-            text_color: colors::TEXT_PRIMARY,
+            text_color: colors::text_primary(),
             border: Border {
                 color: border_color,
                 width: if is_running { 2.0 } else { 1.0 },
@@ -330,11 +364,11 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
         Space::with_height(12),
         text(format!("{} Scan", scan.tool.to_uppercase()))
             .size(16)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_height(4),
         text(scan.target.clone())
             .size(12)
-            .color(colors::CYAN),
+            .color(colors::cyan()),
     ];
 
     // Progress section
@@ -343,13 +377,13 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             Space::with_height(16),
             text("Progress")
                 .size(9)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
             Space::with_height(8),
             progress_bar(0.0..=100.0, scan.progress as f32)
                 .height(8)
                 .style(|_| progress_bar::Style {
                     background: Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.1)),
-                    bar: Background::Color(colors::CYAN),
+                    bar: Background::Color(colors::cyan()),
                     border: Border {
                         radius: 4.0.into(),
                         ..Default::default()
@@ -358,7 +392,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             Space::with_height(8),
             text(format!("{}% complete", scan.progress))
                 .size(11)
-                .color(colors::CYAN),
+                .color(colors::cyan()),
         ]
         .into()
     } else {
@@ -370,7 +404,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
         row![
             text(label)
                 .size(9)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
                 .width(Length::Fixed(100.0)),
             text(value)
                 .size(10)
@@ -381,14 +415,14 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
 
     let mut info_section = column![].spacing(8);
 
-    info_section = info_section.push(info_row("Tool", scan.tool.clone(), colors::TEXT_PRIMARY));
-    info_section = info_section.push(info_row("Type", scan.scan_type.clone(), colors::TEXT_SECONDARY));
+    info_section = info_section.push(info_row("Tool", scan.tool.clone(), colors::text_primary()));
+    info_section = info_section.push(info_row("Type", scan.scan_type.clone(), colors::text_secondary()));
 
     if let Some(ref started) = scan.started_at {
         info_section = info_section.push(info_row(
             "Started",
             started.format("%Y-%m-%d %H:%M:%S").to_string(),
-            colors::TEXT_SECONDARY,
+            colors::text_secondary(),
         ));
     }
 
@@ -396,7 +430,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
         info_section = info_section.push(info_row(
             "Completed",
             completed.format("%Y-%m-%d %H:%M:%S").to_string(),
-            colors::TEXT_SECONDARY,
+            colors::text_secondary(),
         ));
 
         // Duration
@@ -407,20 +441,20 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             } else {
                 format!("{}s", duration.num_seconds())
             };
-            info_section = info_section.push(info_row("Duration", duration_str, colors::TEXT_SECONDARY));
+            info_section = info_section.push(info_row("Duration", duration_str, colors::text_secondary()));
         }
     }
 
     info_section = info_section.push(info_row(
         "Devices Found",
         scan.devices_found.to_string(),
-        if scan.devices_found > 0 { colors::GREEN } else { colors::TEXT_MUTED },
+        if scan.devices_found > 0 { colors::green() } else { colors::text_muted() },
     ));
 
     info_section = info_section.push(info_row(
         "Alerts",
         scan.alerts_generated.to_string(),
-        if scan.alerts_generated > 0 { colors::ORANGE } else { colors::TEXT_MUTED },
+        if scan.alerts_generated > 0 { colors::orange() } else { colors::text_muted() },
     ));
 
     // Result summary
@@ -429,19 +463,19 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             Space::with_height(16),
             text("Summary")
                 .size(9)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
             Space::with_height(8),
             container(
                 text(summary.clone())
                     .size(10)
-                    .color(colors::TEXT_SECONDARY)
+                    .color(colors::text_secondary())
             )
             .padding(12)
             .width(Length::Fill)
             .style(|_| container::Style {
                 background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.2))),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -459,12 +493,12 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             Space::with_height(16),
             text("Error")
                 .size(9)
-                .color(colors::RED),
+                .color(colors::red()),
             Space::with_height(8),
             container(
                 text(error.clone())
                     .size(10)
-                    .color(colors::RED)
+                    .color(colors::red())
             )
             .padding(12)
             .width(Length::Fill)
@@ -504,7 +538,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::RED,
+                text_color: colors::red(),
                 border: Border {
                     color: Color::from_rgba(0.94, 0.27, 0.27, 0.3),
                     width: 1.0,
@@ -535,7 +569,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::CYAN,
+                text_color: colors::cyan(),
                 border: Border {
                     color: Color::from_rgba(0.13, 0.83, 0.93, 0.3),
                     width: 1.0,
@@ -559,7 +593,7 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
                     .style(|_| container::Style {
                         background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.02))),
                         border: Border {
-                            color: colors::BORDER,
+                            color: colors::border(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -577,9 +611,9 @@ fn scan_detail(scan: &Scan) -> Element<'_, Message> {
     .width(Length::Fixed(350.0))
     .height(Length::Fill)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SECONDARY)),
+        background: Some(Background::Color(colors::bg_secondary())),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             ..Default::default()
         },
@@ -603,7 +637,7 @@ fn stats_bar(scans: &[Scan]) -> Element<'_, Message> {
                     .color(color),
                 text(label)
                     .size(8)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -620,17 +654,17 @@ fn stats_bar(scans: &[Scan]) -> Element<'_, Message> {
     };
 
     row![
-        stat_box("RUNNING", running, colors::CYAN),
+        stat_box("RUNNING", running, colors::cyan()),
         Space::with_width(8),
-        stat_box("PENDING", pending, colors::YELLOW),
+        stat_box("PENDING", pending, colors::yellow()),
         Space::with_width(8),
-        stat_box("COMPLETED", completed, colors::GREEN),
+        stat_box("COMPLETED", completed, colors::green()),
         Space::with_width(8),
-        stat_box("FAILED", failed, colors::RED),
+        stat_box("FAILED", failed, colors::red()),
         Space::with_width(Length::Fill),
         text(format!("{} total scans", scans.len()))
             .size(10)
-            .color(colors::TEXT_MUTED),
+            .color(colors::text_muted()),
     ]
     .align_y(Alignment::Center)
     .into()
@@ -647,7 +681,7 @@ pub fn view<'a>(
         container(
             text("\u{1F50D}")  // 🔍
                 .size(20)
-                .color(colors::CYAN)
+                .color(colors::cyan())
         )
         .padding([8, 12])
         .style(|_| container::Style {
@@ -662,14 +696,19 @@ pub fn view<'a>(
         column![
             text("SCAN HISTORY")
                 .size(18)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             text("View and manage network scans")
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ],
         Space::with_width(Length::Fill),
+        button(text("Export CSV").size(10))
+            .on_press(Message::ExportScansCsv)
+            .padding([4, 8])
+            .style(crate::theme::secondary_button_style),
+        Space::with_width(8),
         button(
-            text("\u{21BB}").size(14).color(colors::TEXT_MUTED) // ↻
+            text("\u{21BB}").size(14).color(colors::text_muted()) // ↻
         )
         .on_press(Message::FetchScans)
         .padding([8, 12])
@@ -681,14 +720,14 @@ pub fn view<'a>(
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 border: Border::default(),
                 ..Default::default()
             }
         }),
         Space::with_width(8),
         button(
-            text("\u{2716}").size(18).color(colors::TEXT_MUTED) // ✖
+            text("\u{2716}").size(18).color(colors::text_muted()) // ✖
         )
         .on_press(Message::HideScansDashboard)
         .padding([8, 12])
@@ -700,7 +739,7 @@ pub fn view<'a>(
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 border: Border::default(),
                 ..Default::default()
             }
@@ -739,52 +778,22 @@ pub fn view<'a>(
     };
 
     let filters = row![
-        text("Filter:").size(9).color(colors::TEXT_MUTED),
+        text("Filter:").size(9).color(colors::text_muted()),
         Space::with_width(8),
-        filter_btn("ALL", filter_status.is_none(), colors::CYAN, None),
+        filter_btn("ALL", filter_status.is_none(), colors::cyan(), None),
         Space::with_width(4),
-        filter_btn("RUNNING", filter_status == Some("running"), colors::CYAN, Some("running")),
+        filter_btn("RUNNING", filter_status == Some("running"), colors::cyan(), Some("running")),
         Space::with_width(4),
-        filter_btn("COMPLETED", filter_status == Some("completed"), colors::GREEN, Some("completed")),
+        filter_btn("COMPLETED", filter_status == Some("completed"), colors::green(), Some("completed")),
         Space::with_width(4),
-        filter_btn("FAILED", filter_status == Some("failed"), colors::RED, Some("failed")),
+        filter_btn("FAILED", filter_status == Some("failed"), colors::red(), Some("failed")),
         Space::with_width(4),
-        filter_btn("PENDING", filter_status == Some("pending"), colors::YELLOW, Some("pending")),
+        filter_btn("PENDING", filter_status == Some("pending"), colors::yellow(), Some("pending")),
     ]
     .align_y(Alignment::Center)
     .padding([12, 24]);
 
-    // Filter scans
-    let filtered_scans: Vec<&Scan> = scans
-        .iter()
-        .filter(|scan| {
-            if let Some(status) = filter_status {
-                let scan_status = ScanStatus::from_str(&scan.status);
-                match status {
-                    "running" => scan_status == ScanStatus::Running,
-                    "completed" => scan_status == ScanStatus::Completed,
-                    "failed" => scan_status == ScanStatus::Failed,
-                    "pending" => scan_status == ScanStatus::Pending,
-                    _ => true,
-                }
-            } else {
-                true
-            }
-        })
-        .collect();
-
-    // Sort scans: running first, then by created_at desc
-    let mut sorted_scans = filtered_scans;
-    sorted_scans.sort_by(|a, b| {
-        let a_running = ScanStatus::from_str(&a.status) == ScanStatus::Running;
-        let b_running = ScanStatus::from_str(&b.status) == ScanStatus::Running;
-
-        match (a_running, b_running) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => b.created_at.cmp(&a.created_at),
-        }
-    });
+    let sorted_scans = filtered_scans(scans, filter_status);
 
     // Scan list
     let scan_list: Element<'a, Message> = if sorted_scans.is_empty() {
@@ -794,11 +803,11 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No scans found")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_height(8),
                 text("Start a scan from the toolbar")
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -847,7 +856,7 @@ pub fn view<'a>(
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         container(stats_section)
@@ -860,14 +869,14 @@ pub fn view<'a>(
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         filters,
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         container(main_content)
@@ -882,9 +891,9 @@ pub fn view<'a>(
             .width(Length::Fixed(1000.0))
             .height(Length::Fixed(650.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
@@ -24,17 +24,17 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
     let bg_color = if is_selected {
         Color::from_rgba(0.13, 0.83, 0.93, 0.15)
     } else {
-        colors::BG_SECONDARY
+        colors::bg_secondary()
     };
 
     let border_color = if is_selected {
-        colors::CYAN
+        colors::cyan()
     } else {
         Color::TRANSPARENT
     };
 
     let enabled = job.enabled;
-    let status_color = if enabled { colors::GREEN } else { colors::TEXT_MUTED };
+    let status_color = if enabled { colors::green() } else { colors::text_muted() };
     let status_text = if enabled { "Active" } else { "Paused" };
 
     let next_run = job.next_run
@@ -47,9 +47,9 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
             container(Space::with_width(12).height(12))
                 .style(move |_| container::Style {
                     background: Some(Background::Color(if enabled {
-                        colors::GREEN
+                        colors::green()
                     } else {
-                        colors::TEXT_MUTED
+                        colors::text_muted()
                     })),
                     border: Border {
                         radius: 6.0.into(),
@@ -80,7 +80,7 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
             row![
                 text(job.name.clone())
                     .size(13)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
                 Space::with_width(Length::Fill),
                 text(status_text)
                     .size(10)
@@ -91,7 +91,7 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
                 container(
                     text(job.task_type.clone())
                         .size(9)
-                        .color(colors::CYAN)
+                        .color(colors::cyan())
                 )
                 .padding([2, 8])
                 .style(|_| container::Style {
@@ -105,12 +105,12 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
                 Space::with_width(8),
                 text(format_trigger(&job.trigger_type, &job.trigger_args))
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
             Space::with_height(4),
             text(format!("Next run: {}", next_run))
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ]
         .width(Length::Fill),
     ]
@@ -129,7 +129,7 @@ fn job_card<'a>(job: &ScheduledJob, is_selected: bool) -> Element<'a, Message> {
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(hover_bg)),
-                text_color: colors::TEXT_PRIMARY,
+                text_color: colors::text_primary(),
                 border: Border {
                     color: border_color,
                     width: if is_selected { 1.0 } else { 0.0 },
@@ -149,7 +149,7 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
     let job_id_delete = job.id.clone();
 
     let enabled = job.enabled;
-    let status_color = if enabled { colors::GREEN } else { colors::TEXT_MUTED };
+    let status_color = if enabled { colors::green() } else { colors::text_muted() };
     let status_text = if enabled { "Active" } else { "Paused" };
 
     let next_run = job.next_run
@@ -171,10 +171,10 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
             column![
                 text(job.name.clone())
                     .size(16)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
                 text(format!("ID: {}", &job.id[..8]))
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
             Space::with_width(Length::Fill),
             container(
@@ -201,30 +201,30 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Schedule info
-        text("Schedule").size(12).color(colors::CYAN),
+        text("Schedule").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Trigger Type").size(10).color(colors::TEXT_MUTED),
-                text(job.trigger_type.clone()).size(12).color(colors::TEXT_PRIMARY),
+                text("Trigger Type").size(10).color(colors::text_muted()),
+                text(job.trigger_type.clone()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Next Run").size(10).color(colors::TEXT_MUTED),
-                text(next_run).size(12).color(colors::TEXT_PRIMARY),
+                text("Next Run").size(10).color(colors::text_muted()),
+                text(next_run).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
         Space::with_height(8),
         row![
             column![
-                text("Last Run").size(10).color(colors::TEXT_MUTED),
-                text(last_run).size(12).color(colors::TEXT_PRIMARY),
+                text("Last Run").size(10).color(colors::text_muted()),
+                text(last_run).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Created").size(10).color(colors::TEXT_MUTED),
-                text(created).size(12).color(colors::TEXT_PRIMARY),
+                text("Created").size(10).color(colors::text_muted()),
+                text(created).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -232,17 +232,17 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Task info
-        text("Task Configuration").size(12).color(colors::CYAN),
+        text("Task Configuration").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Task Type").size(10).color(colors::TEXT_MUTED),
-                text(job.task_type.clone()).size(12).color(colors::TEXT_PRIMARY),
+                text("Task Type").size(10).color(colors::text_muted()),
+                text(job.task_type.clone()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Parameters").size(10).color(colors::TEXT_MUTED),
-                text(format!("{} configured", params_count)).size(12).color(colors::TEXT_PRIMARY),
+                text("Parameters").size(10).color(colors::text_muted()),
+                text(format!("{} configured", params_count)).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -262,7 +262,7 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                 .padding([8, 16])
                 .style(|_theme, status| {
                     let bg = match status {
-                        iced::widget::button::Status::Hovered => colors::YELLOW,
+                        iced::widget::button::Status::Hovered => colors::yellow(),
                         _ => Color::from_rgba(1.0, 0.8, 0.0, 0.2),
                     };
                     iced::widget::button::Style {
@@ -270,10 +270,10 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                         text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                             Color::WHITE
                         } else {
-                            colors::YELLOW
+                            colors::yellow()
                         },
                         border: Border {
-                            color: colors::YELLOW,
+                            color: colors::yellow(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -294,7 +294,7 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                 .padding([8, 16])
                 .style(|_theme, status| {
                     let bg = match status {
-                        iced::widget::button::Status::Hovered => colors::GREEN,
+                        iced::widget::button::Status::Hovered => colors::green(),
                         _ => Color::from_rgba(0.34, 0.84, 0.44, 0.2),
                     };
                     iced::widget::button::Style {
@@ -302,10 +302,10 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                         text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                             Color::WHITE
                         } else {
-                            colors::GREEN
+                            colors::green()
                         },
                         border: Border {
-                            color: colors::GREEN,
+                            color: colors::green(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -329,7 +329,7 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                 .padding([8, 16])
                 .style(|_theme, status| {
                     let bg = match status {
-                        iced::widget::button::Status::Hovered => colors::RED,
+                        iced::widget::button::Status::Hovered => colors::red(),
                         _ => Color::from_rgba(0.94, 0.27, 0.27, 0.2),
                     };
                     iced::widget::button::Style {
@@ -337,10 +337,10 @@ fn job_detail<'a>(job: &ScheduledJob) -> Element<'a, Message> {
                         text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                             Color::WHITE
                         } else {
-                            colors::RED
+                            colors::red()
                         },
                         border: Border {
-                            color: colors::RED,
+                            color: colors::red(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -366,13 +366,13 @@ fn stats_bar<'a>(jobs: &[ScheduledJob]) -> Element<'a, Message> {
             row![
                 text("\u{1F4C5}").size(12), // 📅
                 Space::with_width(6),
-                text(format!("{} jobs", total_jobs)).size(11).color(colors::TEXT_PRIMARY),
+                text(format!("{} jobs", total_jobs)).size(11).color(colors::text_primary()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -385,13 +385,13 @@ fn stats_bar<'a>(jobs: &[ScheduledJob]) -> Element<'a, Message> {
             row![
                 text("\u{25B6}").size(12), // ▶
                 Space::with_width(6),
-                text(format!("{} active", active_count)).size(11).color(colors::GREEN),
+                text(format!("{} active", active_count)).size(11).color(colors::green()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -404,13 +404,13 @@ fn stats_bar<'a>(jobs: &[ScheduledJob]) -> Element<'a, Message> {
             row![
                 text("\u{23F8}").size(12), // ⏸
                 Space::with_width(6),
-                text(format!("{} paused", paused_count)).size(11).color(colors::TEXT_MUTED),
+                text(format!("{} paused", paused_count)).size(11).color(colors::text_muted()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -431,15 +431,15 @@ fn stats_bar<'a>(jobs: &[ScheduledJob]) -> Element<'a, Message> {
         .padding([6, 12])
         .style(|_theme, status| {
             let bg = match status {
-                iced::widget::button::Status::Hovered => colors::CYAN,
-                _ => colors::BG_SECONDARY,
+                iced::widget::button::Status::Hovered => colors::cyan(),
+                _ => colors::bg_secondary(),
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
                 text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                     Color::WHITE
                 } else {
-                    colors::TEXT_PRIMARY
+                    colors::text_primary()
                 },
                 border: Border {
                     radius: 4.0.into(),
@@ -468,19 +468,19 @@ pub fn view<'a>(
     let header = row![
         text("Scheduled Jobs")
             .size(18)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(Length::Fill),
         button(text("\u{2715}").size(14)) // ✕
             .on_press(Message::HideSchedulerDashboard)
             .padding([4, 8])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::RED,
+                    iced::widget::button::Status::Hovered => colors::red(),
                     _ => Color::TRANSPARENT,
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::TEXT_MUTED,
+                    text_color: colors::text_muted(),
                     border: Border::default(),
                     ..Default::default()
                 }
@@ -500,11 +500,11 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No scheduled jobs")
                     .size(14)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_height(8),
                 text("Create a job to automate tasks")
                     .size(11)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -540,9 +540,9 @@ pub fn view<'a>(
         .width(Length::Fixed(350.0))
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -552,11 +552,11 @@ pub fn view<'a>(
     } else {
         container(
             column![
-                text("\u{1F4C5}").size(32).color(colors::TEXT_MUTED), // 📅
+                text("\u{1F4C5}").size(32).color(colors::text_muted()), // 📅
                 Space::with_height(8),
                 text("Select a job")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -565,9 +565,9 @@ pub fn view<'a>(
         .center_x(Length::Fixed(350.0))
         .center_y(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -598,9 +598,9 @@ pub fn view<'a>(
             .width(Length::Fixed(850.0))
             .height(Length::Fixed(600.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
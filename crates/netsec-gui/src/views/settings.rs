@@ -2,8 +2,10 @@
 
 use iced::widget::{button, column, container, horizontal_rule, row, scrollable, text, text_input, toggler, Space};
 use iced::{Alignment, Background, Border, Color, Element, Length};
+use std::path::PathBuf;
 
 use crate::message::Message;
+use crate::state::traffic::DEFAULT_ANOMALY_THRESHOLD;
 use crate::theme::colors;
 
 /// Application settings state.
@@ -11,10 +13,30 @@ use crate::theme::colors;
 pub struct Settings {
     pub api_url: String,
     pub ws_url: String,
+    /// Sent as a bearer token on API requests; empty for none.
+    pub api_key: String,
     pub dark_mode: bool,
     pub notifications_enabled: bool,
     pub auto_refresh: bool,
     pub refresh_interval_secs: u32,
+    /// Factor over a peer's baseline at which inspector traffic is flagged.
+    pub traffic_anomaly_threshold: f32,
+    /// Directory exports are written to; empty for `~/Documents/NetWatch`.
+    pub export_dir: String,
+}
+
+impl Settings {
+    /// The API key to send, if one is set.
+    pub fn api_key(&self) -> Option<String> {
+        let key = self.api_key.trim();
+        (!key.is_empty()).then(|| key.to_string())
+    }
+
+    /// The export directory, if one is set.
+    pub fn export_dir(&self) -> Option<PathBuf> {
+        let dir = self.export_dir.trim();
+        (!dir.is_empty()).then(|| PathBuf::from(dir))
+    }
 }
 
 impl Default for Settings {
@@ -22,10 +44,13 @@ impl Default for Settings {
         Self {
             api_url: "http://127.0.0.1:8420".to_string(),
             ws_url: "ws://127.0.0.1:8420/ws".to_string(),
+            api_key: String::new(),
             dark_mode: true,
             notifications_enabled: true,
             auto_refresh: true,
             refresh_interval_secs: 30,
+            traffic_anomaly_threshold: DEFAULT_ANOMALY_THRESHOLD,
+            export_dir: String::new(),
         }
     }
 }
@@ -37,9 +62,9 @@ fn settings_section<'a>(
     content: Element<'a, Message>,
 ) -> Element<'a, Message> {
     column![
-        text(title).size(14).color(colors::CYAN),
+        text(title).size(14).color(colors::cyan()),
         Space::with_height(4),
-        text(description).size(10).color(colors::TEXT_MUTED),
+        text(description).size(10).color(colors::text_muted()),
         Space::with_height(12),
         content,
     ]
@@ -56,8 +81,8 @@ fn toggle_row<'a>(
 ) -> Element<'a, Message> {
     row![
         column![
-            text(label).size(12).color(colors::TEXT_PRIMARY),
-            text(description).size(10).color(colors::TEXT_MUTED),
+            text(label).size(12).color(colors::text_primary()),
+            text(description).size(10).color(colors::text_muted()),
         ]
         .width(Length::Fill),
         toggler(value)
@@ -68,19 +93,19 @@ fn toggle_row<'a>(
                     iced::widget::toggler::Status::Active { is_toggled } |
                     iced::widget::toggler::Status::Hovered { is_toggled } => {
                         if is_toggled {
-                            (colors::CYAN, Color::WHITE)
+                            (colors::cyan(), Color::WHITE)
                         } else {
-                            (colors::BG_SECONDARY, colors::TEXT_MUTED)
+                            (colors::bg_secondary(), colors::text_muted())
                         }
                     }
                     iced::widget::toggler::Status::Disabled => {
-                        (colors::BG_SECONDARY, colors::TEXT_MUTED)
+                        (colors::bg_secondary(), colors::text_muted())
                     }
                 };
                 iced::widget::toggler::Style {
                     background: bg,
                     background_border_width: 1.0,
-                    background_border_color: colors::BORDER,
+                    background_border_color: colors::border(),
                     foreground: fg,
                     foreground_border_width: 0.0,
                     foreground_border_color: Color::TRANSPARENT,
@@ -98,31 +123,52 @@ fn input_field<'a>(
     value: &'a str,
     placeholder: &'a str,
     on_change: impl Fn(String) -> Message + 'a,
+) -> Element<'a, Message> {
+    styled_input_field(label, value, placeholder, false, on_change)
+}
+
+/// Text input row component with the value masked.
+fn secret_input_field<'a>(
+    label: &'a str,
+    value: &'a str,
+    placeholder: &'a str,
+    on_change: impl Fn(String) -> Message + 'a,
+) -> Element<'a, Message> {
+    styled_input_field(label, value, placeholder, true, on_change)
+}
+
+fn styled_input_field<'a>(
+    label: &'a str,
+    value: &'a str,
+    placeholder: &'a str,
+    secure: bool,
+    on_change: impl Fn(String) -> Message + 'a,
 ) -> Element<'a, Message> {
     column![
-        text(label).size(11).color(colors::TEXT_MUTED),
+        text(label).size(11).color(colors::text_muted()),
         Space::with_height(4),
         text_input(placeholder, value)
             .on_input(on_change)
+            .secure(secure)
             .padding([8, 12])
             .size(12)
             .style(|_theme, status| {
                 let border_color = match status {
-                    iced::widget::text_input::Status::Focused => colors::CYAN,
-                    iced::widget::text_input::Status::Hovered => colors::BORDER,
-                    _ => colors::BORDER,
+                    iced::widget::text_input::Status::Focused => colors::cyan(),
+                    iced::widget::text_input::Status::Hovered => colors::border(),
+                    _ => colors::border(),
                 };
                 iced::widget::text_input::Style {
-                    background: Background::Color(colors::BG_SECONDARY),
+                    background: Background::Color(colors::bg_secondary()),
                     border: Border {
                         color: border_color,
                         width: 1.0,
                         radius: 4.0.into(),
                     },
-                    icon: colors::TEXT_MUTED,
-                    placeholder: colors::TEXT_MUTED,
-                    value: colors::TEXT_PRIMARY,
-                    selection: colors::CYAN,
+                    icon: colors::text_muted(),
+                    placeholder: colors::text_muted(),
+                    value: colors::text_primary(),
+                    selection: colors::cyan(),
                 }
             }),
     ]
@@ -130,25 +176,49 @@ fn input_field<'a>(
     .into()
 }
 
+/// Small -/+ stepper button.
+fn step_button<'a>(label: &'a str, on_press: Message) -> Element<'a, Message> {
+    button(text(label).size(12))
+        .on_press(on_press)
+        .padding([4, 12])
+        .style(|_theme, status| {
+            let bg = match status {
+                iced::widget::button::Status::Hovered => colors::bg_secondary(),
+                _ => colors::bg_primary(),
+            };
+            iced::widget::button::Style {
+                background: Some(Background::Color(bg)),
+                text_color: colors::text_primary(),
+                border: Border {
+                    color: colors::border(),
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
 /// Main settings view.
 pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
     // Header
     let header = row![
         text("Settings")
             .size(18)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(Length::Fill),
         button(text("\u{2715}").size(14)) // ✕
             .on_press(Message::HideSettings)
             .padding([4, 8])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::RED,
+                    iced::widget::button::Status::Hovered => colors::red(),
                     _ => Color::TRANSPARENT,
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::TEXT_MUTED,
+                    text_color: colors::text_muted(),
                     border: Border::default(),
                     ..Default::default()
                 }
@@ -175,6 +245,13 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                 "ws://127.0.0.1:8420/ws",
                 Message::SettingsUpdateWsUrl,
             ),
+            Space::with_height(12),
+            secret_input_field(
+                "API Key",
+                &settings.api_key,
+                "Optional",
+                Message::SettingsUpdateApiKey,
+            ),
         ]
         .into(),
     );
@@ -190,6 +267,34 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                 settings.dark_mode,
                 |_| Message::SettingsToggleDarkMode,
             ),
+            row![
+                column![
+                    text("Theme Colors").size(12).color(colors::text_primary()),
+                    text("Customize the color palette").size(10).color(colors::text_muted()),
+                ]
+                .width(Length::Fill),
+                button(text("Edit").size(11))
+                    .on_press(Message::ShowThemeEditor)
+                    .padding([4, 12])
+                    .style(|_theme, status| {
+                        let bg = match status {
+                            iced::widget::button::Status::Hovered => colors::bg_secondary(),
+                            _ => colors::bg_primary(),
+                        };
+                        iced::widget::button::Style {
+                            background: Some(Background::Color(bg)),
+                            text_color: colors::text_primary(),
+                            border: Border {
+                                color: colors::border(),
+                                width: 1.0,
+                                radius: 4.0.into(),
+                            },
+                            ..Default::default()
+                        }
+                    }),
+            ]
+            .align_y(Alignment::Center)
+            .padding([8, 0]),
         ]
         .into(),
     );
@@ -222,7 +327,7 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
             ),
             Space::with_height(8),
             row![
-                text("Refresh Interval").size(11).color(colors::TEXT_MUTED),
+                text("Refresh Interval").size(11).color(colors::text_muted()),
                 Space::with_width(Length::Fill),
                 button(text("-").size(12))
                     .on_press(Message::SettingsUpdateRefreshInterval(
@@ -231,14 +336,14 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                     .padding([4, 12])
                     .style(|_theme, status| {
                         let bg = match status {
-                            iced::widget::button::Status::Hovered => colors::BG_SECONDARY,
-                            _ => colors::BG_PRIMARY,
+                            iced::widget::button::Status::Hovered => colors::bg_secondary(),
+                            _ => colors::bg_primary(),
                         };
                         iced::widget::button::Style {
                             background: Some(Background::Color(bg)),
-                            text_color: colors::TEXT_PRIMARY,
+                            text_color: colors::text_primary(),
                             border: Border {
-                                color: colors::BORDER,
+                                color: colors::border(),
                                 width: 1.0,
                                 radius: 4.0.into(),
                             },
@@ -249,11 +354,11 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                 container(
                     text(format!("{}s", settings.refresh_interval_secs))
                         .size(12)
-                        .color(colors::TEXT_PRIMARY)
+                        .color(colors::text_primary())
                 )
                 .padding([4, 12])
                 .style(|_| container::Style {
-                    background: Some(Background::Color(colors::BG_SECONDARY)),
+                    background: Some(Background::Color(colors::bg_secondary())),
                     border: Border {
                         radius: 4.0.into(),
                         ..Default::default()
@@ -268,14 +373,14 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                     .padding([4, 12])
                     .style(|_theme, status| {
                         let bg = match status {
-                            iced::widget::button::Status::Hovered => colors::BG_SECONDARY,
-                            _ => colors::BG_PRIMARY,
+                            iced::widget::button::Status::Hovered => colors::bg_secondary(),
+                            _ => colors::bg_primary(),
                         };
                         iced::widget::button::Style {
                             background: Some(Background::Color(bg)),
-                            text_color: colors::TEXT_PRIMARY,
+                            text_color: colors::text_primary(),
                             border: Border {
-                                color: colors::BORDER,
+                                color: colors::border(),
                                 width: 1.0,
                                 radius: 4.0.into(),
                             },
@@ -288,21 +393,78 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
         .into(),
     );
 
+    // Traffic analysis section
+    let threshold = settings.traffic_anomaly_threshold;
+    let traffic_section = settings_section(
+        "Traffic Analysis",
+        "Flag inspector peers whose traffic exceeds their baseline",
+        column![
+            row![
+                column![
+                    text("Anomaly Threshold").size(12).color(colors::text_primary()),
+                    text("Amber above this factor, red above twice it")
+                        .size(10)
+                        .color(colors::text_muted()),
+                ]
+                .width(Length::Fill),
+                step_button(
+                    "-",
+                    Message::SettingsUpdateAnomalyThreshold((threshold - 0.5).max(1.5)),
+                ),
+                Space::with_width(8),
+                container(
+                    text(format!("{:.1}x", threshold))
+                        .size(12)
+                        .color(colors::text_primary())
+                )
+                .padding([4, 12])
+                .style(|_| container::Style {
+                    background: Some(Background::Color(colors::bg_secondary())),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                Space::with_width(8),
+                step_button(
+                    "+",
+                    Message::SettingsUpdateAnomalyThreshold((threshold + 0.5).min(10.0)),
+                ),
+            ]
+            .align_y(Alignment::Center),
+        ]
+        .into(),
+    );
+
+    // Export section
+    let export_section = settings_section(
+        "Export",
+        "Where canvas and CSV exports are saved",
+        column![input_field(
+            "Export Directory",
+            &settings.export_dir,
+            "~/Documents/NetWatch",
+            Message::SettingsUpdateExportDir,
+        )]
+        .into(),
+    );
+
     // About section
     let about_section = settings_section(
         "About",
         "Application information",
         column![
             row![
-                text("Version").size(11).color(colors::TEXT_MUTED),
+                text("Version").size(11).color(colors::text_muted()),
                 Space::with_width(Length::Fill),
-                text("0.1.0").size(11).color(colors::TEXT_PRIMARY),
+                text("0.1.0").size(11).color(colors::text_primary()),
             ],
             Space::with_height(8),
             row![
-                text("Build").size(11).color(colors::TEXT_MUTED),
+                text("Build").size(11).color(colors::text_muted()),
                 Space::with_width(Length::Fill),
-                text("Development").size(11).color(colors::TEXT_PRIMARY),
+                text("Development").size(11).color(colors::text_primary()),
             ],
         ]
         .into(),
@@ -331,6 +493,14 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                 Space::with_height(24),
                 horizontal_rule(1),
                 Space::with_height(24),
+                traffic_section,
+                Space::with_height(24),
+                horizontal_rule(1),
+                Space::with_height(24),
+                export_section,
+                Space::with_height(24),
+                horizontal_rule(1),
+                Space::with_height(24),
                 about_section,
                 Space::with_height(24),
             ]
@@ -354,7 +524,7 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                 .padding([10, 24])
                 .style(|_theme, status| {
                     let bg = match status {
-                        iced::widget::button::Status::Hovered => colors::CYAN,
+                        iced::widget::button::Status::Hovered => colors::cyan(),
                         _ => Color::from_rgba(0.13, 0.83, 0.93, 0.2),
                     };
                     iced::widget::button::Style {
@@ -362,10 +532,10 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
                         text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                             Color::WHITE
                         } else {
-                            colors::CYAN
+                            colors::cyan()
                         },
                         border: Border {
-                            color: colors::CYAN,
+                            color: colors::cyan(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -383,9 +553,9 @@ pub fn view<'a>(settings: &'a Settings) -> Element<'a, Message> {
             .width(Length::Fixed(500.0))
             .height(Length::Fixed(650.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
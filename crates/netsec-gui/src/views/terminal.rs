@@ -21,7 +21,7 @@ pub fn view<'a>(state: &'a TerminalState) -> Element<'a, Message> {
                 text(screen_content)
                     .size(13)
                     .font(iced::Font::MONOSPACE)
-                    .color(colors::TEXT_PRIMARY)
+                    .color(colors::text_primary())
             )
             .padding(8)
             .width(Length::Fill)
@@ -32,7 +32,7 @@ pub fn view<'a>(state: &'a TerminalState) -> Element<'a, Message> {
         container(
             text("No terminal open. Click + to create one.")
                 .size(14)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -63,10 +63,10 @@ fn view_tab_bar<'a>(state: &'a TerminalState) -> Element<'a, Message> {
         let is_active = state.active_index == Some(idx);
 
         let status_indicator = match tab.status {
-            TerminalStatus::Connected => text("●").size(8).color(colors::GREEN),
-            TerminalStatus::Connecting => text("●").size(8).color(colors::YELLOW),
-            TerminalStatus::Disconnected => text("●").size(8).color(colors::TEXT_MUTED),
-            TerminalStatus::Error => text("●").size(8).color(colors::RED),
+            TerminalStatus::Connected => text("●").size(8).color(colors::green()),
+            TerminalStatus::Connecting => text("●").size(8).color(colors::yellow()),
+            TerminalStatus::Disconnected => text("●").size(8).color(colors::text_muted()),
+            TerminalStatus::Error => text("●").size(8).color(colors::red()),
         };
 
         let tab_title = text(&tab.title).size(12);
@@ -0,0 +1,286 @@
+//! Theme editor panel view.
+//!
+//! Edits the active color palette with live preview. Each key has a hex
+//! input; valid edits are applied immediately, and contrast problems are
+//! listed before the theme can be saved.
+
+use iced::widget::{button, column, container, horizontal_rule, row, scrollable, text, text_input, Space};
+use iced::{Alignment, Background, Border, Color, Element, Length};
+
+use crate::message::Message;
+use crate::theme::{self, color_to_hex, colors, parse_hex_color, Palette};
+
+/// Theme editor state: the raw hex input for each palette key.
+#[derive(Debug, Clone)]
+pub struct ThemeEditor {
+    inputs: Vec<(&'static str, String)>,
+}
+
+impl ThemeEditor {
+    /// Start editing from the given palette.
+    pub fn new(palette: &Palette) -> Self {
+        let inputs = Palette::KEYS
+            .iter()
+            .map(|&key| {
+                let value = palette.get(key).map(color_to_hex).unwrap_or_default();
+                (key, value)
+            })
+            .collect();
+        Self { inputs }
+    }
+
+    /// The raw input for a key.
+    pub fn input(&self, key: &str) -> &str {
+        self.inputs
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    }
+
+    /// Update the raw input for a key. Unknown keys are ignored.
+    pub fn set_input(&mut self, key: &str, value: String) {
+        if let Some((_, v)) = self.inputs.iter_mut().find(|(k, _)| *k == key) {
+            *v = value;
+        }
+    }
+
+    /// Keys whose input is not a valid hex color.
+    pub fn invalid_keys(&self) -> Vec<&'static str> {
+        self.inputs
+            .iter()
+            .filter(|(_, v)| parse_hex_color(v).is_none())
+            .map(|(k, _)| *k)
+            .collect()
+    }
+
+    /// Build a palette from the inputs, keeping `base` colors for invalid ones.
+    pub fn preview(&self, base: &Palette) -> Palette {
+        let mut palette = *base;
+        for (key, value) in &self.inputs {
+            if let Some(color) = parse_hex_color(value) {
+                palette.set(key, color);
+            }
+        }
+        palette
+    }
+}
+
+/// Human-readable label for a palette key (`bg_primary` -> `Bg Primary`).
+fn key_label(key: &str) -> String {
+    key.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One editable palette row: label, swatch, and hex input.
+fn color_row<'a>(key: &'static str, value: &'a str) -> Element<'a, Message> {
+    let parsed = parse_hex_color(value);
+    let swatch_color = parsed.unwrap_or(Color::TRANSPARENT);
+    let invalid = parsed.is_none();
+
+    row![
+        text(key_label(key))
+            .size(11)
+            .color(colors::text_primary())
+            .width(Length::Fill),
+        container(Space::new(20, 20)).style(move |_| container::Style {
+            background: Some(Background::Color(swatch_color)),
+            border: Border {
+                color: colors::border(),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        }),
+        Space::with_width(8),
+        text_input("#rrggbb", value)
+            .on_input(move |v| Message::ThemeEditorColorChanged(key.to_string(), v))
+            .padding([6, 10])
+            .size(12)
+            .width(Length::Fixed(120.0))
+            .style(move |_theme, status| {
+                let border_color = if invalid {
+                    colors::red()
+                } else {
+                    match status {
+                        iced::widget::text_input::Status::Focused => colors::cyan(),
+                        _ => colors::border(),
+                    }
+                };
+                iced::widget::text_input::Style {
+                    background: Background::Color(colors::bg_secondary()),
+                    border: Border {
+                        color: border_color,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    icon: colors::text_muted(),
+                    placeholder: colors::text_muted(),
+                    value: colors::text_primary(),
+                    selection: colors::cyan(),
+                }
+            }),
+    ]
+    .align_y(Alignment::Center)
+    .padding([4, 0])
+    .into()
+}
+
+/// Footer button style shared by the editor actions.
+fn footer_button_style(accent: Color) -> impl Fn(&iced::Theme, button::Status) -> button::Style {
+    move |_theme, status| {
+        let bg = match status {
+            button::Status::Hovered => Color { a: 0.3, ..accent },
+            button::Status::Disabled => Color::TRANSPARENT,
+            _ => Color { a: 0.15, ..accent },
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: if matches!(status, button::Status::Disabled) {
+                colors::text_muted()
+            } else {
+                accent
+            },
+            border: Border {
+                color: accent,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Main theme editor view.
+pub fn view(editor: &ThemeEditor) -> Element<'_, Message> {
+    let header = row![
+        text("Theme Editor").size(18).color(colors::text_primary()),
+        Space::with_width(Length::Fill),
+        button(text("\u{2715}").size(14)) // ✕
+            .on_press(Message::HideThemeEditor)
+            .padding([4, 8])
+            .style(|_theme, status| {
+                let bg = match status {
+                    button::Status::Hovered => colors::red(),
+                    _ => Color::TRANSPARENT,
+                };
+                button::Style {
+                    background: Some(Background::Color(bg)),
+                    text_color: colors::text_muted(),
+                    border: Border::default(),
+                    ..Default::default()
+                }
+            }),
+    ]
+    .padding([12, 16])
+    .align_y(Alignment::Center);
+
+    let rows = Palette::KEYS
+        .iter()
+        .fold(column![].spacing(2), |col, &key| col.push(color_row(key, editor.input(key))));
+
+    // Validation: unparseable inputs and low-contrast pairs in the live palette
+    let invalid = editor.invalid_keys();
+    let issues = theme::palette().contrast_issues();
+    let can_save = invalid.is_empty() && issues.is_empty();
+
+    let mut status = column![].spacing(4);
+    if can_save {
+        status = status.push(text("All text/background pairs are readable").size(10).color(colors::green()));
+    }
+    for key in &invalid {
+        status = status.push(
+            text(format!("{}: invalid color", key_label(key)))
+                .size(10)
+                .color(colors::red()),
+        );
+    }
+    for issue in &issues {
+        status = status.push(text(issue.to_string()).size(10).color(colors::yellow()));
+    }
+
+    let footer = row![
+        button(text("Reset to Defaults").size(11))
+            .on_press(Message::ThemeEditorReset)
+            .padding([8, 16])
+            .style(footer_button_style(colors::text_secondary())),
+        Space::with_width(Length::Fill),
+        button(text("Cancel").size(11))
+            .on_press(Message::HideThemeEditor)
+            .padding([8, 16])
+            .style(footer_button_style(colors::text_secondary())),
+        Space::with_width(8),
+        button(text("Save Theme").size(11))
+            .on_press_maybe(can_save.then_some(Message::ThemeEditorSave))
+            .padding([8, 16])
+            .style(footer_button_style(colors::cyan())),
+    ]
+    .align_y(Alignment::Center)
+    .padding(16);
+
+    let content = column![
+        header,
+        horizontal_rule(1),
+        scrollable(column![Space::with_height(12), rows, Space::with_height(12)].padding([0, 16]))
+            .height(Length::Fill),
+        horizontal_rule(1),
+        container(status).padding([8, 16]),
+        footer,
+    ];
+
+    // Modal overlay
+    container(
+        container(content)
+            .width(Length::Fixed(460.0))
+            .height(Length::Fixed(650.0))
+            .style(|_| container::Style {
+                background: Some(Background::Color(colors::bg_primary())),
+                border: Border {
+                    color: colors::border(),
+                    width: 1.0,
+                    radius: 8.0.into(),
+                },
+                ..Default::default()
+            }),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(|_| container::Style {
+        background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.7))),
+        ..Default::default()
+    })
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_preview_applies_valid_inputs() {
+        let mut editor = ThemeEditor::new(&Palette::BUILTIN);
+        editor.set_input("cyan", "#ff00ff".to_string());
+        editor.set_input("red", "not-a-color".to_string());
+
+        let preview = editor.preview(&Palette::BUILTIN);
+        assert_eq!(preview.cyan, Color::from_rgb8(0xff, 0x00, 0xff));
+        assert_eq!(preview.red, Palette::BUILTIN.red);
+        assert_eq!(editor.invalid_keys(), vec!["red"]);
+    }
+
+    #[test]
+    fn test_key_label() {
+        assert_eq!(key_label("bg_primary"), "Bg Primary");
+        assert_eq!(key_label("cyan"), "Cyan");
+    }
+}
@@ -3,7 +3,7 @@
 use iced::widget::{button, column, container, row, text, Space, vertical_rule};
 use iced::{Alignment, Element, Length};
 
-use crate::message::{AttackTool, Message, NmapScanType, NodeType, ToolMode};
+use crate::message::{AttackTool, LayoutKind, Message, NmapScanType, NodeType, ToolMode};
 use crate::theme::{self, colors};
 
 /// Render a tool button.
@@ -87,12 +87,12 @@ fn attack_button<'a>(
 fn section_label<'a>(label: &'a str) -> Element<'a, Message> {
     text(label)
         .size(8)
-        .color(colors::TEXT_MUTED)
+        .color(colors::text_muted())
         .into()
 }
 
 /// Render the toolbar.
-pub fn view<'a>(current_mode: ToolMode) -> Element<'a, Message> {
+pub fn view<'a>(current_mode: ToolMode, layout: LayoutKind) -> Element<'a, Message> {
     let mode_section = column![
         tool_button(
             "Select",
@@ -144,6 +144,25 @@ pub fn view<'a>(current_mode: ToolMode) -> Element<'a, Message> {
     .spacing(4)
     .align_x(Alignment::Center);
 
+    let layout_section = column![
+        section_label("LAYOUT"),
+        Space::with_height(4),
+        tool_button(
+            "Radial",
+            '\u{2609}', // ☉
+            layout == LayoutKind::Radial,
+            Message::SetLayout(LayoutKind::Radial),
+        ),
+        tool_button(
+            "Force",
+            '\u{2058}', // ⁘
+            layout == LayoutKind::Force,
+            Message::SetLayout(LayoutKind::Force),
+        ),
+    ]
+    .spacing(4)
+    .align_x(Alignment::Center);
+
     let actions_section = column![
         tool_button("Group", '\u{2B1A}', false, Message::GroupSelected),
         tool_button("Delete", '\u{2716}', false, Message::DeleteSelected),
@@ -155,7 +174,7 @@ pub fn view<'a>(current_mode: ToolMode) -> Element<'a, Message> {
         container(Space::with_height(1))
             .width(Length::Fixed(48.0))
             .style(|_| container::Style {
-                background: Some(iced::Background::Color(colors::BORDER)),
+                background: Some(iced::Background::Color(colors::border())),
                 ..Default::default()
             })
     };
@@ -168,6 +187,8 @@ pub fn view<'a>(current_mode: ToolMode) -> Element<'a, Message> {
         nmap_section,
         divider(),
         attack_section,
+        divider(),
+        layout_section,
         Space::with_height(Length::Fill),
         actions_section,
     ]
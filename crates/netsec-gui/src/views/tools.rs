@@ -10,12 +10,12 @@ use crate::theme::colors;
 /// Tool status color.
 fn status_color(status: &str) -> Color {
     match status.to_lowercase().as_str() {
-        "available" | "healthy" | "ok" => colors::GREEN,
-        "busy" | "running" => colors::CYAN,
-        "warning" | "degraded" => colors::YELLOW,
-        "error" | "unavailable" | "failed" => colors::RED,
-        "unknown" => colors::TEXT_MUTED,
-        _ => colors::TEXT_MUTED,
+        "available" | "healthy" | "ok" => colors::green(),
+        "busy" | "running" => colors::cyan(),
+        "warning" | "degraded" => colors::yellow(),
+        "error" | "unavailable" | "failed" => colors::red(),
+        "unknown" => colors::text_muted(),
+        _ => colors::text_muted(),
     }
 }
 
@@ -40,11 +40,11 @@ fn tool_card<'a>(tool: &Tool, health: Option<&ToolHealth>, is_selected: bool) ->
     let bg_color = if is_selected {
         Color::from_rgba(0.13, 0.83, 0.93, 0.15)
     } else {
-        colors::BG_SECONDARY
+        colors::bg_secondary()
     };
 
     let border_color = if is_selected {
-        colors::CYAN
+        colors::cyan()
     } else {
         Color::TRANSPARENT
     };
@@ -62,10 +62,10 @@ fn tool_card<'a>(tool: &Tool, health: Option<&ToolHealth>, is_selected: bool) ->
             column![
                 text(tool.display_name.clone())
                     .size(13)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
                 text(tool.name.clone())
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
             Space::with_width(Length::Fill),
             // Status indicator
@@ -93,14 +93,14 @@ fn tool_card<'a>(tool: &Tool, health: Option<&ToolHealth>, is_selected: bool) ->
         // Description
         text(tool.description.clone().unwrap_or_else(|| "No description available".to_string()))
             .size(11)
-            .color(colors::TEXT_MUTED),
+            .color(colors::text_muted()),
         Space::with_height(8),
         // Tags/tasks
         row![
             container(
                 text(tool.category.clone())
                     .size(9)
-                    .color(colors::CYAN)
+                    .color(colors::cyan())
             )
             .padding([2, 8])
             .style(|_| container::Style {
@@ -114,7 +114,7 @@ fn tool_card<'a>(tool: &Tool, health: Option<&ToolHealth>, is_selected: bool) ->
             Space::with_width(8),
             text(tool.version.clone().unwrap_or_else(|| "unknown".to_string()))
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ],
     ]
     .padding(12)
@@ -132,7 +132,7 @@ fn tool_card<'a>(tool: &Tool, health: Option<&ToolHealth>, is_selected: bool) ->
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(hover_bg)),
-                text_color: colors::TEXT_PRIMARY,
+                text_color: colors::text_primary(),
                 border: Border {
                     color: border_color,
                     width: if is_selected { 1.0 } else { 0.0 },
@@ -165,10 +165,10 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
             column![
                 text(tool.display_name.clone())
                     .size(16)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
                 text(tool.name.clone())
                     .size(11)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
         ]
         .align_y(Alignment::Center),
@@ -176,7 +176,7 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
         horizontal_rule(1),
         Space::with_height(16),
         // Status
-        text("Status").size(12).color(colors::CYAN),
+        text("Status").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             container(Space::with_width(12).height(12))
@@ -195,7 +195,7 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
                     .color(status_col),
                 text(health_message)
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
         ]
         .align_y(Alignment::Center),
@@ -203,18 +203,18 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
         horizontal_rule(1),
         Space::with_height(16),
         // Info
-        text("Information").size(12).color(colors::CYAN),
+        text("Information").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Category").size(10).color(colors::TEXT_MUTED),
-                text(tool.category.clone()).size(12).color(colors::TEXT_PRIMARY),
+                text("Category").size(10).color(colors::text_muted()),
+                text(tool.category.clone()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Version").size(10).color(colors::TEXT_MUTED),
+                text("Version").size(10).color(colors::text_muted()),
                 text(tool.version.clone().unwrap_or_else(|| "Unknown".to_string()))
-                    .size(12).color(colors::TEXT_PRIMARY),
+                    .size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -222,16 +222,16 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
         horizontal_rule(1),
         Space::with_height(16),
         // Description
-        text("Description").size(12).color(colors::CYAN),
+        text("Description").size(12).color(colors::cyan()),
         Space::with_height(8),
         text(tool.description.clone().unwrap_or_else(|| "No description available".to_string()))
             .size(11)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_height(16),
         horizontal_rule(1),
         Space::with_height(16),
         // Supported tasks
-        text("Supported Tasks").size(12).color(colors::CYAN),
+        text("Supported Tasks").size(12).color(colors::cyan()),
         Space::with_height(8),
         {
             let tasks: Vec<Element<'a, Message>> = tool.supported_tasks
@@ -240,13 +240,13 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
                     container(
                         text(task.clone())
                             .size(10)
-                            .color(colors::TEXT_PRIMARY)
+                            .color(colors::text_primary())
                     )
                     .padding([4, 8])
                     .style(|_| container::Style {
-                        background: Some(Background::Color(colors::BG_PRIMARY)),
+                        background: Some(Background::Color(colors::bg_primary())),
                         border: Border {
-                            color: colors::BORDER,
+                            color: colors::border(),
                             width: 1.0,
                             radius: 4.0.into(),
                         },
@@ -257,7 +257,7 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
                 .collect();
 
             if tasks.is_empty() {
-                let empty: Element<'a, Message> = container(text("No tasks defined").size(10).color(colors::TEXT_MUTED))
+                let empty: Element<'a, Message> = container(text("No tasks defined").size(10).color(colors::text_muted()))
                     .into();
                 empty
             } else {
@@ -283,7 +283,7 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
         .width(Length::Fill)
         .style(|_theme, status| {
             let bg = match status {
-                iced::widget::button::Status::Hovered => colors::CYAN,
+                iced::widget::button::Status::Hovered => colors::cyan(),
                 _ => Color::from_rgba(0.13, 0.83, 0.93, 0.2),
             };
             iced::widget::button::Style {
@@ -291,10 +291,10 @@ fn tool_detail<'a>(tool: &Tool, health: Option<&ToolHealth>) -> Element<'a, Mess
                 text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                     Color::WHITE
                 } else {
-                    colors::CYAN
+                    colors::cyan()
                 },
                 border: Border {
-                    color: colors::CYAN,
+                    color: colors::cyan(),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
@@ -327,13 +327,13 @@ fn stats_bar<'a>(tools: &[Tool], health: &[ToolHealth]) -> Element<'a, Message>
             row![
                 text("\u{1F527}").size(12), // 🔧
                 Space::with_width(6),
-                text(format!("{} tools", total_tools)).size(11).color(colors::TEXT_PRIMARY),
+                text(format!("{} tools", total_tools)).size(11).color(colors::text_primary()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -346,13 +346,13 @@ fn stats_bar<'a>(tools: &[Tool], health: &[ToolHealth]) -> Element<'a, Message>
             row![
                 text("\u{2705}").size(12), // ✅
                 Space::with_width(6),
-                text(format!("{} healthy", healthy_count)).size(11).color(colors::GREEN),
+                text(format!("{} healthy", healthy_count)).size(11).color(colors::green()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -365,13 +365,13 @@ fn stats_bar<'a>(tools: &[Tool], health: &[ToolHealth]) -> Element<'a, Message>
             row![
                 text("\u{26A0}").size(12), // ⚠
                 Space::with_width(6),
-                text(format!("{} warning", warning_count)).size(11).color(colors::YELLOW),
+                text(format!("{} warning", warning_count)).size(11).color(colors::yellow()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -384,13 +384,13 @@ fn stats_bar<'a>(tools: &[Tool], health: &[ToolHealth]) -> Element<'a, Message>
             row![
                 text("\u{274C}").size(12), // ❌
                 Space::with_width(6),
-                text(format!("{} error", error_count)).size(11).color(colors::RED),
+                text(format!("{} error", error_count)).size(11).color(colors::red()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -411,15 +411,15 @@ fn stats_bar<'a>(tools: &[Tool], health: &[ToolHealth]) -> Element<'a, Message>
         .padding([6, 12])
         .style(|_theme, status| {
             let bg = match status {
-                iced::widget::button::Status::Hovered => colors::CYAN,
-                _ => colors::BG_SECONDARY,
+                iced::widget::button::Status::Hovered => colors::cyan(),
+                _ => colors::bg_secondary(),
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(bg)),
                 text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                     Color::WHITE
                 } else {
-                    colors::TEXT_PRIMARY
+                    colors::text_primary()
                 },
                 border: Border {
                     radius: 4.0.into(),
@@ -449,19 +449,19 @@ pub fn view<'a>(
     let header = row![
         text("Security Tools")
             .size(18)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(Length::Fill),
         button(text("\u{2715}").size(14)) // ✕
             .on_press(Message::HideToolsDashboard)
             .padding([4, 8])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::RED,
+                    iced::widget::button::Status::Hovered => colors::red(),
                     _ => Color::TRANSPARENT,
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::TEXT_MUTED,
+                    text_color: colors::text_muted(),
                     border: Border::default(),
                     ..Default::default()
                 }
@@ -481,11 +481,11 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No tools available")
                     .size(14)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_height(8),
                 text("Configure tools in the backend")
                     .size(11)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -523,9 +523,9 @@ pub fn view<'a>(
         .width(Length::Fixed(350.0))
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -535,11 +535,11 @@ pub fn view<'a>(
     } else {
         container(
             column![
-                text("\u{1F527}").size(32).color(colors::TEXT_MUTED), // 🔧
+                text("\u{1F527}").size(32).color(colors::text_muted()), // 🔧
                 Space::with_height(8),
                 text("Select a tool")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -548,9 +548,9 @@ pub fn view<'a>(
         .center_x(Length::Fixed(350.0))
         .center_y(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -581,9 +581,9 @@ pub fn view<'a>(
             .width(Length::Fixed(900.0))
             .height(Length::Fixed(650.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
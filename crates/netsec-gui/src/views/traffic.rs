@@ -16,7 +16,7 @@ pub enum TrafficDirection {
 }
 
 /// Format bytes to human-readable string.
-fn format_bytes(bytes: i64) -> String {
+pub fn format_bytes(bytes: i64) -> String {
     const KB: i64 = 1024;
     const MB: i64 = KB * 1024;
     const GB: i64 = MB * 1024;
@@ -35,13 +35,13 @@ fn format_bytes(bytes: i64) -> String {
 /// Protocol color.
 fn protocol_color(protocol: &str) -> Color {
     match protocol.to_uppercase().as_str() {
-        "TCP" => colors::CYAN,
-        "UDP" => colors::PURPLE,
-        "ICMP" => colors::YELLOW,
-        "HTTP" | "HTTPS" => colors::GREEN,
-        "DNS" => colors::ORANGE,
-        "SSH" => colors::RED,
-        _ => colors::TEXT_MUTED,
+        "TCP" => colors::cyan(),
+        "UDP" => colors::purple(),
+        "ICMP" => colors::yellow(),
+        "HTTP" | "HTTPS" => colors::green(),
+        "DNS" => colors::orange(),
+        "SSH" => colors::red(),
+        _ => colors::text_muted(),
     }
 }
 
@@ -51,11 +51,11 @@ fn traffic_card<'a>(flow: &'a TrafficFlow, is_selected: bool) -> Element<'a, Mes
     let bg_color = if is_selected {
         Color::from_rgba(0.13, 0.83, 0.93, 0.15)
     } else {
-        colors::BG_SECONDARY
+        colors::bg_secondary()
     };
 
     let border_color = if is_selected {
-        colors::CYAN
+        colors::cyan()
     } else {
         Color::TRANSPARENT
     };
@@ -86,32 +86,32 @@ fn traffic_card<'a>(flow: &'a TrafficFlow, is_selected: bool) -> Element<'a, Mes
             Space::with_width(8),
             text(flow.application.clone().unwrap_or_else(|| "Unknown".to_string()))
                 .size(12)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             Space::with_width(Length::Fill),
             text(format_bytes(total_bytes))
                 .size(11)
-                .color(colors::CYAN),
+                .color(colors::cyan()),
         ]
         .align_y(Alignment::Center),
         Space::with_height(8),
         // Source and destination
         row![
             column![
-                text("Source").size(9).color(colors::TEXT_MUTED),
+                text("Source").size(9).color(colors::text_muted()),
                 text(format!("{}:{}", flow.src_ip, flow.src_port.unwrap_or(0)))
                     .size(11)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
             ],
             Space::with_width(16),
             text("\u{2192}") // →
                 .size(14)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
             Space::with_width(16),
             column![
-                text("Destination").size(9).color(colors::TEXT_MUTED),
+                text("Destination").size(9).color(colors::text_muted()),
                 text(format!("{}:{}", flow.dst_ip, flow.dst_port.unwrap_or(0)))
                     .size(11)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
             ],
         ]
         .align_y(Alignment::Center),
@@ -120,13 +120,13 @@ fn traffic_card<'a>(flow: &'a TrafficFlow, is_selected: bool) -> Element<'a, Mes
         row![
             text(format!("{} packets", total_packets))
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
             Space::with_width(Length::Fill),
             text(format!("\u{2191}{} \u{2193}{}",
                 format_bytes(flow.bytes_sent),
                 format_bytes(flow.bytes_received)))
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ],
     ]
     .padding(12)
@@ -144,7 +144,7 @@ fn traffic_card<'a>(flow: &'a TrafficFlow, is_selected: bool) -> Element<'a, Mes
             };
             iced::widget::button::Style {
                 background: Some(Background::Color(hover_bg)),
-                text_color: colors::TEXT_PRIMARY,
+                text_color: colors::text_primary(),
                 border: Border {
                     color: border_color,
                     width: if is_selected { 1.0 } else { 0.0 },
@@ -187,10 +187,10 @@ fn traffic_detail<'a>(flow: &TrafficFlow) -> Element<'a, Message> {
             column![
                 text(flow.application.clone().unwrap_or_else(|| "Unknown Application".to_string()))
                     .size(14)
-                    .color(colors::TEXT_PRIMARY),
+                    .color(colors::text_primary()),
                 text(format!("Flow ID: {}", &flow.id[..8]))
                     .size(10)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ],
         ]
         .align_y(Alignment::Center),
@@ -198,32 +198,32 @@ fn traffic_detail<'a>(flow: &TrafficFlow) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Connection info
-        text("Connection").size(12).color(colors::CYAN),
+        text("Connection").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Source IP").size(10).color(colors::TEXT_MUTED),
-                text(flow.src_ip.clone()).size(12).color(colors::TEXT_PRIMARY),
+                text("Source IP").size(10).color(colors::text_muted()),
+                text(flow.src_ip.clone()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Source Port").size(10).color(colors::TEXT_MUTED),
+                text("Source Port").size(10).color(colors::text_muted()),
                 text(flow.src_port.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()))
-                    .size(12).color(colors::TEXT_PRIMARY),
+                    .size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
         Space::with_height(8),
         row![
             column![
-                text("Destination IP").size(10).color(colors::TEXT_MUTED),
-                text(flow.dst_ip.clone()).size(12).color(colors::TEXT_PRIMARY),
+                text("Destination IP").size(10).color(colors::text_muted()),
+                text(flow.dst_ip.clone()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Destination Port").size(10).color(colors::TEXT_MUTED),
+                text("Destination Port").size(10).color(colors::text_muted()),
                 text(flow.dst_port.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()))
-                    .size(12).color(colors::TEXT_PRIMARY),
+                    .size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -231,30 +231,30 @@ fn traffic_detail<'a>(flow: &TrafficFlow) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Transfer stats
-        text("Transfer Statistics").size(12).color(colors::CYAN),
+        text("Transfer Statistics").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Bytes Sent").size(10).color(colors::TEXT_MUTED),
-                text(format_bytes(flow.bytes_sent)).size(14).color(colors::GREEN),
+                text("Bytes Sent").size(10).color(colors::text_muted()),
+                text(format_bytes(flow.bytes_sent)).size(14).color(colors::green()),
             ]
             .width(Length::Fill),
             column![
-                text("Bytes Received").size(10).color(colors::TEXT_MUTED),
-                text(format_bytes(flow.bytes_received)).size(14).color(colors::ORANGE),
+                text("Bytes Received").size(10).color(colors::text_muted()),
+                text(format_bytes(flow.bytes_received)).size(14).color(colors::orange()),
             ]
             .width(Length::Fill),
         ],
         Space::with_height(8),
         row![
             column![
-                text("Packets Sent").size(10).color(colors::TEXT_MUTED),
-                text(flow.packets_sent.to_string()).size(12).color(colors::TEXT_PRIMARY),
+                text("Packets Sent").size(10).color(colors::text_muted()),
+                text(flow.packets_sent.to_string()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Packets Received").size(10).color(colors::TEXT_MUTED),
-                text(flow.packets_received.to_string()).size(12).color(colors::TEXT_PRIMARY),
+                text("Packets Received").size(10).color(colors::text_muted()),
+                text(flow.packets_received.to_string()).size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -262,17 +262,17 @@ fn traffic_detail<'a>(flow: &TrafficFlow) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Timing
-        text("Timing").size(12).color(colors::CYAN),
+        text("Timing").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Started").size(10).color(colors::TEXT_MUTED),
-                text(started).size(11).color(colors::TEXT_PRIMARY),
+                text("Started").size(10).color(colors::text_muted()),
+                text(started).size(11).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Ended").size(10).color(colors::TEXT_MUTED),
-                text(ended).size(11).color(colors::TEXT_PRIMARY),
+                text("Ended").size(10).color(colors::text_muted()),
+                text(ended).size(11).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -280,19 +280,19 @@ fn traffic_detail<'a>(flow: &TrafficFlow) -> Element<'a, Message> {
         horizontal_rule(1),
         Space::with_height(16),
         // Geo info
-        text("Geolocation").size(12).color(colors::CYAN),
+        text("Geolocation").size(12).color(colors::cyan()),
         Space::with_height(8),
         row![
             column![
-                text("Source Country").size(10).color(colors::TEXT_MUTED),
+                text("Source Country").size(10).color(colors::text_muted()),
                 text(flow.country_src.clone().unwrap_or_else(|| "Unknown".to_string()))
-                    .size(12).color(colors::TEXT_PRIMARY),
+                    .size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
             column![
-                text("Destination Country").size(10).color(colors::TEXT_MUTED),
+                text("Destination Country").size(10).color(colors::text_muted()),
                 text(flow.country_dst.clone().unwrap_or_else(|| "Unknown".to_string()))
-                    .size(12).color(colors::TEXT_PRIMARY),
+                    .size(12).color(colors::text_primary()),
             ]
             .width(Length::Fill),
         ],
@@ -318,13 +318,13 @@ fn stats_bar<'a>(flows: &[TrafficFlow]) -> Element<'a, Message> {
             row![
                 text("\u{1F4CA}").size(12), // 📊
                 Space::with_width(6),
-                text(format!("{} flows", total_flows)).size(11).color(colors::TEXT_PRIMARY),
+                text(format!("{} flows", total_flows)).size(11).color(colors::text_primary()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -337,13 +337,13 @@ fn stats_bar<'a>(flows: &[TrafficFlow]) -> Element<'a, Message> {
             row![
                 text("\u{21C5}").size(12), // ⇅
                 Space::with_width(6),
-                text(format_bytes(total_bytes)).size(11).color(colors::CYAN),
+                text(format_bytes(total_bytes)).size(11).color(colors::cyan()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -356,13 +356,13 @@ fn stats_bar<'a>(flows: &[TrafficFlow]) -> Element<'a, Message> {
             row![
                 text("\u{1F4E6}").size(12), // 📦
                 Space::with_width(6),
-                text(format!("{} pkts", total_packets)).size(11).color(colors::TEXT_PRIMARY),
+                text(format!("{} pkts", total_packets)).size(11).color(colors::text_primary()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -373,17 +373,17 @@ fn stats_bar<'a>(flows: &[TrafficFlow]) -> Element<'a, Message> {
         // Protocol breakdown
         container(
             row![
-                text(format!("TCP: {}", tcp_count)).size(10).color(colors::CYAN),
+                text(format!("TCP: {}", tcp_count)).size(10).color(colors::cyan()),
                 Space::with_width(12),
-                text(format!("UDP: {}", udp_count)).size(10).color(colors::PURPLE),
+                text(format!("UDP: {}", udp_count)).size(10).color(colors::purple()),
                 Space::with_width(12),
-                text(format!("Other: {}", other_count)).size(10).color(colors::TEXT_MUTED),
+                text(format!("Other: {}", other_count)).size(10).color(colors::text_muted()),
             ]
             .align_y(Alignment::Center)
         )
         .padding([6, 12])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
@@ -421,7 +421,7 @@ pub fn view<'a>(
     let header = row![
         text("Network Traffic")
             .size(18)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(Length::Fill),
         // Protocol filter buttons
         button(text("All").size(10))
@@ -431,11 +431,11 @@ pub fn view<'a>(
                 let is_active = filter_protocol.is_none();
                 iced::widget::button::Style {
                     background: Some(Background::Color(if is_active {
-                        colors::CYAN
+                        colors::cyan()
                     } else {
-                        colors::BG_SECONDARY
+                        colors::bg_secondary()
                     })),
-                    text_color: if is_active { Color::WHITE } else { colors::TEXT_MUTED },
+                    text_color: if is_active { Color::WHITE } else { colors::text_muted() },
                     border: Border {
                         radius: 4.0.into(),
                         ..Default::default()
@@ -451,11 +451,11 @@ pub fn view<'a>(
                 let is_active = filter_protocol.as_ref().map_or(false, |p| p == "TCP");
                 iced::widget::button::Style {
                     background: Some(Background::Color(if is_active {
-                        colors::CYAN
+                        colors::cyan()
                     } else {
-                        colors::BG_SECONDARY
+                        colors::bg_secondary()
                     })),
-                    text_color: if is_active { Color::WHITE } else { colors::TEXT_MUTED },
+                    text_color: if is_active { Color::WHITE } else { colors::text_muted() },
                     border: Border {
                         radius: 4.0.into(),
                         ..Default::default()
@@ -471,11 +471,11 @@ pub fn view<'a>(
                 let is_active = filter_protocol.as_ref().map_or(false, |p| p == "UDP");
                 iced::widget::button::Style {
                     background: Some(Background::Color(if is_active {
-                        colors::PURPLE
+                        colors::purple()
                     } else {
-                        colors::BG_SECONDARY
+                        colors::bg_secondary()
                     })),
-                    text_color: if is_active { Color::WHITE } else { colors::TEXT_MUTED },
+                    text_color: if is_active { Color::WHITE } else { colors::text_muted() },
                     border: Border {
                         radius: 4.0.into(),
                         ..Default::default()
@@ -489,12 +489,12 @@ pub fn view<'a>(
             .padding([4, 8])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::RED,
+                    iced::widget::button::Status::Hovered => colors::red(),
                     _ => Color::TRANSPARENT,
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::TEXT_MUTED,
+                    text_color: colors::text_muted(),
                     border: Border::default(),
                     ..Default::default()
                 }
@@ -514,11 +514,11 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No traffic flows")
                     .size(14)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
                 Space::with_height(8),
                 text("Traffic will appear here when detected")
                     .size(11)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -554,9 +554,9 @@ pub fn view<'a>(
         .width(Length::Fixed(350.0))
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -566,11 +566,11 @@ pub fn view<'a>(
     } else {
         container(
             column![
-                text("\u{1F4CA}").size(32).color(colors::TEXT_MUTED), // 📊
+                text("\u{1F4CA}").size(32).color(colors::text_muted()), // 📊
                 Space::with_height(8),
                 text("Select a flow")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -579,9 +579,9 @@ pub fn view<'a>(
         .center_x(Length::Fixed(350.0))
         .center_y(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SECONDARY)),
+            background: Some(Background::Color(colors::bg_secondary())),
             border: Border {
-                color: colors::BORDER,
+                color: colors::border(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -614,9 +614,9 @@ pub fn view<'a>(
             .width(Length::Fixed(1000.0))
             .height(Length::Fixed(700.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
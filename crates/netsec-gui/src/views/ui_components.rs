@@ -34,10 +34,10 @@ impl Toast {
 /// Get color for toast level.
 fn toast_color(level: &ToastLevel) -> Color {
     match level {
-        ToastLevel::Info => colors::CYAN,
-        ToastLevel::Success => colors::GREEN,
-        ToastLevel::Warning => colors::YELLOW,
-        ToastLevel::Error => colors::RED,
+        ToastLevel::Info => colors::cyan(),
+        ToastLevel::Success => colors::green(),
+        ToastLevel::Warning => colors::yellow(),
+        ToastLevel::Error => colors::red(),
     }
 }
 
@@ -67,7 +67,7 @@ fn toast_view<'a>(toast: &Toast) -> Element<'a, Message> {
             // Message
             text(toast.message.clone())
                 .size(12)
-                .color(colors::TEXT_PRIMARY)
+                .color(colors::text_primary())
                 .width(Length::Fill),
             // Dismiss button
             button(text("\u{2715}").size(10)) // ✕
@@ -80,7 +80,7 @@ fn toast_view<'a>(toast: &Toast) -> Element<'a, Message> {
                     };
                     iced::widget::button::Style {
                         background: Some(Background::Color(bg)),
-                        text_color: colors::TEXT_MUTED,
+                        text_color: colors::text_muted(),
                         border: Border::default(),
                         ..Default::default()
                     }
@@ -90,7 +90,7 @@ fn toast_view<'a>(toast: &Toast) -> Element<'a, Message> {
         .padding(12)
     )
     .style(move |_| container::Style {
-        background: Some(Background::Color(colors::BG_SECONDARY)),
+        background: Some(Background::Color(colors::bg_secondary())),
         border: Border {
             color,
             width: 1.0,
@@ -148,12 +148,12 @@ impl ConfirmDialog {
 pub fn confirm_dialog_view<'a>(dialog: &ConfirmDialog) -> Element<'a, Message> {
     let content = column![
         // Icon
-        text("\u{2753}").size(36).color(colors::YELLOW), // ❓
+        text("\u{2753}").size(36).color(colors::yellow()), // ❓
         Space::with_height(16),
         // Message
         text(dialog.message.clone())
             .size(14)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_height(24),
         // Buttons
         row![
@@ -165,14 +165,14 @@ pub fn confirm_dialog_view<'a>(dialog: &ConfirmDialog) -> Element<'a, Message> {
             .padding([10, 24])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::BG_SECONDARY,
-                    _ => colors::BG_PRIMARY,
+                    iced::widget::button::Status::Hovered => colors::bg_secondary(),
+                    _ => colors::bg_primary(),
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::TEXT_MUTED,
+                    text_color: colors::text_muted(),
                     border: Border {
-                        color: colors::BORDER,
+                        color: colors::border(),
                         width: 1.0,
                         radius: 4.0.into(),
                     },
@@ -188,7 +188,7 @@ pub fn confirm_dialog_view<'a>(dialog: &ConfirmDialog) -> Element<'a, Message> {
             .padding([10, 24])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::CYAN,
+                    iced::widget::button::Status::Hovered => colors::cyan(),
                     _ => Color::from_rgba(0.13, 0.83, 0.93, 0.2),
                 };
                 iced::widget::button::Style {
@@ -196,10 +196,10 @@ pub fn confirm_dialog_view<'a>(dialog: &ConfirmDialog) -> Element<'a, Message> {
                     text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                         Color::WHITE
                     } else {
-                        colors::CYAN
+                        colors::cyan()
                     },
                     border: Border {
-                        color: colors::CYAN,
+                        color: colors::cyan(),
                         width: 1.0,
                         radius: 4.0.into(),
                     },
@@ -216,9 +216,9 @@ pub fn confirm_dialog_view<'a>(dialog: &ConfirmDialog) -> Element<'a, Message> {
     container(
         container(content)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
@@ -243,19 +243,19 @@ pub fn loading_spinner<'a>(message: &'a str) -> Element<'a, Message> {
             // Animated spinner (using text characters)
             text("\u{27F3}") // ⟳
                 .size(32)
-                .color(colors::CYAN),
+                .color(colors::cyan()),
             Space::with_height(12),
             text(message)
                 .size(12)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ]
         .align_x(Alignment::Center)
     )
     .padding(24)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SECONDARY)),
+        background: Some(Background::Color(colors::bg_secondary())),
         border: Border {
-            color: colors::BORDER,
+            color: colors::border(),
             width: 1.0,
             radius: 8.0.into(),
         },
@@ -288,11 +288,11 @@ pub fn empty_state<'a>(icon: &'a str, title: &'a str, description: &'a str) -> E
             Space::with_height(16),
             text(title)
                 .size(14)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             Space::with_height(8),
             text(description)
                 .size(11)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ]
         .align_x(Alignment::Center)
     )
@@ -311,11 +311,11 @@ pub fn error_state<'a>(error_message: &'a str) -> Element<'a, Message> {
             Space::with_height(16),
             text("An error occurred")
                 .size(14)
-                .color(colors::RED),
+                .color(colors::red()),
             Space::with_height(8),
             text(error_message)
                 .size(11)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
             Space::with_height(16),
             button(
                 row![
@@ -329,15 +329,15 @@ pub fn error_state<'a>(error_message: &'a str) -> Element<'a, Message> {
             .padding([8, 16])
             .style(|_theme, status| {
                 let bg = match status {
-                    iced::widget::button::Status::Hovered => colors::CYAN,
-                    _ => colors::BG_SECONDARY,
+                    iced::widget::button::Status::Hovered => colors::cyan(),
+                    _ => colors::bg_secondary(),
                 };
                 iced::widget::button::Style {
                     background: Some(Background::Color(bg)),
                     text_color: if matches!(status, iced::widget::button::Status::Hovered) {
                         Color::WHITE
                     } else {
-                        colors::TEXT_PRIMARY
+                        colors::text_primary()
                     },
                     border: Border {
                         radius: 4.0.into(),
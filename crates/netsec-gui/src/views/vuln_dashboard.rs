@@ -10,9 +10,9 @@ use crate::theme::colors;
 /// Get severity badge color.
 fn severity_color(severity: &Severity) -> Color {
     match severity {
-        Severity::Critical => colors::RED,
-        Severity::High => colors::ORANGE,
-        Severity::Medium => colors::YELLOW,
+        Severity::Critical => colors::red(),
+        Severity::High => colors::orange(),
+        Severity::Medium => colors::yellow(),
         Severity::Low => Color::from_rgb(0.23, 0.51, 0.95), // Blue
     }
 }
@@ -68,7 +68,7 @@ fn vuln_card<'a>(
     let header = row![
         text(&vuln.cve)
             .size(12)
-            .color(colors::TEXT_PRIMARY),
+            .color(colors::text_primary()),
         Space::with_width(8),
         severity_label,
     ]
@@ -76,19 +76,19 @@ fn vuln_card<'a>(
 
     let description = text(&vuln.description)
         .size(10)
-        .color(colors::TEXT_SECONDARY);
+        .color(colors::text_secondary());
 
     let device_info = row![
         text("\u{1F4BB}").size(10), // 💻
         Space::with_width(6),
         text(node_label)
             .size(10)
-            .color(colors::CYAN),
+            .color(colors::cyan()),
         Space::with_width(8),
         container(
             text(node_ip)
                 .size(9)
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
         )
         .padding([2, 6])
         .style(|_| container::Style {
@@ -148,7 +148,7 @@ pub fn view<'a>(
         container(
             text("\u{26A0}")  // ⚠
                 .size(20)
-                .color(colors::RED)
+                .color(colors::red())
         )
         .padding([8, 12])
         .style(|_| container::Style {
@@ -163,21 +163,21 @@ pub fn view<'a>(
         column![
             text("GLOBAL VULNERABILITY REPORT")
                 .size(18)
-                .color(colors::TEXT_PRIMARY),
+                .color(colors::text_primary()),
             text(format!("{} Issues Detected Across {} Assets", total, network.nodes.len()))
                 .size(10)
-                .color(colors::TEXT_MUTED),
+                .color(colors::text_muted()),
         ],
         Space::with_width(Length::Fill),
         button(
-            text("\u{2716}").size(18).color(colors::TEXT_MUTED) // ✖
+            text("\u{2716}").size(18).color(colors::text_muted()) // ✖
         )
         .on_press(Message::HideVulnDashboard)
         .padding([8, 12])
         .style(|theme, status| {
             let mut style = iced::widget::button::Style {
                 background: Some(Background::Color(Color::TRANSPARENT)),
-                text_color: colors::TEXT_MUTED,
+                text_color: colors::text_muted(),
                 ..Default::default()
             };
             if matches!(status, iced::widget::button::Status::Hovered) {
@@ -196,7 +196,7 @@ pub fn view<'a>(
             row![
                 text(label).size(9).color(if is_active { Color::WHITE } else { color }),
                 Space::with_width(4),
-                text(count.to_string()).size(9).color(if is_active { Color::WHITE } else { colors::TEXT_MUTED }),
+                text(count.to_string()).size(9).color(if is_active { Color::WHITE } else { colors::text_muted() }),
             ]
         )
         .on_press(Message::VulnFilterSeverity(sev))
@@ -223,15 +223,15 @@ pub fn view<'a>(
     };
 
     let filters = row![
-        text("Filter:").size(9).color(colors::TEXT_MUTED),
+        text("Filter:").size(9).color(colors::text_muted()),
         Space::with_width(8),
-        filter_btn(None, "ALL", total, colors::CYAN),
+        filter_btn(None, "ALL", total, colors::cyan()),
         Space::with_width(4),
-        filter_btn(Some(Severity::Critical), "CRITICAL", critical, colors::RED),
+        filter_btn(Some(Severity::Critical), "CRITICAL", critical, colors::red()),
         Space::with_width(4),
-        filter_btn(Some(Severity::High), "HIGH", high, colors::ORANGE),
+        filter_btn(Some(Severity::High), "HIGH", high, colors::orange()),
         Space::with_width(4),
-        filter_btn(Some(Severity::Medium), "MEDIUM", medium, colors::YELLOW),
+        filter_btn(Some(Severity::Medium), "MEDIUM", medium, colors::yellow()),
         Space::with_width(4),
         filter_btn(Some(Severity::Low), "LOW", low, Color::from_rgb(0.23, 0.51, 0.95)),
         Space::with_width(Length::Fill),
@@ -276,7 +276,7 @@ pub fn view<'a>(
                 Space::with_height(16),
                 text("No matching vulnerabilities found")
                     .size(12)
-                    .color(colors::TEXT_MUTED),
+                    .color(colors::text_muted()),
             ]
             .align_x(Alignment::Center)
         )
@@ -305,14 +305,14 @@ pub fn view<'a>(
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         filters,
         container(Space::with_height(1))
             .width(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BORDER)),
+                background: Some(Background::Color(colors::border())),
                 ..Default::default()
             }),
         vuln_list,
@@ -324,9 +324,9 @@ pub fn view<'a>(
             .width(Length::Fixed(900.0))
             .height(Length::Fixed(600.0))
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_PRIMARY)),
+                background: Some(Background::Color(colors::bg_primary())),
                 border: Border {
-                    color: colors::BORDER,
+                    color: colors::border(),
                     width: 1.0,
                     radius: 8.0.into(),
                 },
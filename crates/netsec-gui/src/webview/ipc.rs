@@ -2,9 +2,11 @@
 //!
 //! Defines the JSON structures used for bidirectional communication.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use crate::message::{NodeId, NodeStatus, NodeType, Severity};
-use crate::state::network::{NetworkState, Node, Connection, Port, Vulnerability};
+use crate::state::network::{Connection, Group, NetworkState, Node, Port, Vulnerability};
 
 /// Events sent from the React webview to Rust.
 #[derive(Debug, Clone, Deserialize)]
@@ -86,6 +88,9 @@ pub struct NodeJson {
     pub width: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<f32>,
+    /// Recently changed by a refresh.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub highlighted: bool,
 }
 
 impl From<&Node> for NodeJson {
@@ -110,10 +115,58 @@ impl From<&Node> for NodeJson {
             parent_id: node.parent_id.map(|id| id.0.to_string()),
             width: node.width,
             height: node.height,
+            highlighted: node.is_highlighted(),
         }
     }
 }
 
+impl NodeJson {
+    /// A group container, or a collapsed group's super-node, labelled with
+    /// its member count when collapsed and showing its worst member status.
+    fn group(group: &Group, state: &NetworkState) -> Option<Self> {
+        let (x, y, width, height) = state.group_bounds(group)?;
+        let status = group
+            .members
+            .iter()
+            .filter_map(|&id| state.get_node(id))
+            .map(|n| n.status)
+            .max_by_key(|status| match status {
+                NodeStatus::Online => 0,
+                NodeStatus::Offline => 1,
+                NodeStatus::Warning => 2,
+                NodeStatus::Compromised => 3,
+            })
+            .unwrap_or_default();
+        let label = if group.collapsed {
+            format!("{} ({})", group.label, group.members.len())
+        } else {
+            group.label.clone()
+        };
+        Some(Self {
+            id: group.id.0.to_string(),
+            node_type: node_type_to_string(NodeType::Group),
+            x,
+            y,
+            label,
+            status: node_status_to_string(status),
+            ip: String::new(),
+            mac: None,
+            vendor: None,
+            oui: None,
+            hostname: None,
+            os_family: None,
+            signal_strength: None,
+            ssids: Vec::new(),
+            ports: Vec::new(),
+            vulnerabilities: Vec::new(),
+            parent_id: None,
+            width: Some(width),
+            height: Some(height),
+            highlighted: false,
+        })
+    }
+}
+
 /// Port data serialized for React.
 #[derive(Debug, Clone, Serialize)]
 pub struct PortJson {
@@ -212,11 +265,47 @@ pub struct NetworkStateJson {
 }
 
 impl From<&NetworkState> for NetworkStateJson {
+    /// Members of collapsed groups are replaced by the group's super-node,
+    /// and their connections are redrawn to it; links inside a collapsed
+    /// group are hidden.
     fn from(state: &NetworkState) -> Self {
+        let collapsed_into: HashMap<NodeId, NodeId> = state
+            .groups
+            .iter()
+            .filter(|g| g.collapsed)
+            .flat_map(|g| g.members.iter().map(move |&m| (m, g.id)))
+            .collect();
+        let shown = |id: NodeId| collapsed_into.get(&id).copied().unwrap_or(id);
+
+        let mut nodes: Vec<NodeJson> = state
+            .nodes
+            .iter()
+            .filter(|n| !collapsed_into.contains_key(&n.id))
+            .map(NodeJson::from)
+            .collect();
+        nodes.extend(state.groups.iter().filter_map(|g| NodeJson::group(g, state)));
+
+        let mut connections: Vec<ConnectionJson> = Vec::new();
+        for conn in &state.connections {
+            let (from, to) = (shown(conn.from), shown(conn.to));
+            if from == to {
+                continue;
+            }
+            let mut json = ConnectionJson::from(conn);
+            json.from = from.0.to_string();
+            json.to = to.0.to_string();
+            let duplicate = connections.iter().any(|c| {
+                (c.from == json.from && c.to == json.to) || (c.from == json.to && c.to == json.from)
+            });
+            if !duplicate {
+                connections.push(json);
+            }
+        }
+
         Self {
             seq: 0, // Will be overwritten by sync_state_to_webview
-            nodes: state.nodes.iter().map(NodeJson::from).collect(),
-            connections: state.connections.iter().map(ConnectionJson::from).collect(),
+            nodes,
+            connections,
             selected_ids: state.selected_ids.iter().map(|id| id.0.to_string()).collect(),
             hovered_connection: state.hovered_connection.map(|id| id.0.to_string()),
             pan: state.pan,
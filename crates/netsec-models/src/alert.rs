@@ -4,6 +4,8 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// Alert severity levels.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
@@ -36,6 +38,44 @@ impl Severity {
             _ => Self::Info,
         }
     }
+
+    /// Raise by `levels`, clamped at [`Severity::Critical`].
+    pub fn escalate(self, levels: u8) -> Self {
+        const LEVELS: [Severity; 5] = [
+            Severity::Info,
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical,
+        ];
+        let index = (self as usize).saturating_add(levels as usize);
+        LEVELS[index.min(LEVELS.len() - 1)]
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error parsing a [`Severity`] name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown severity: {0}")]
+pub struct ParseSeverityError(pub String);
+
+impl std::str::FromStr for Severity {
+    type Err = ParseSeverityError;
+
+    /// Parse a severity name, ignoring case and surrounding whitespace.
+    /// Unlike [`Severity::from_str_lossy`], unknown names are an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        [Self::Info, Self::Low, Self::Medium, Self::High, Self::Critical]
+            .into_iter()
+            .find(|sev| sev.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseSeverityError(s.to_string()))
+    }
 }
 
 /// Alert status.
@@ -70,7 +110,7 @@ impl AlertStatus {
 }
 
 /// Alert category.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum AlertCategory {
     Intrusion,
@@ -131,11 +171,16 @@ pub struct Alert {
     pub raw_data: Option<String>,
     pub first_seen: String,
     pub last_seen: String,
+    /// Analyst note history, oldest first. Not an `alerts` column; filled in
+    /// by the repo functions that load notes.
+    #[serde(default)]
+    #[cfg_attr(feature = "sqlx", sqlx(skip))]
+    pub note_history: Vec<AlertNote>,
 }
 
 impl Alert {
     pub fn new(title: String, source_tool: String, fingerprint: String) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             severity: Severity::Info.as_str().to_string(),
@@ -156,6 +201,7 @@ impl Alert {
             raw_data: None,
             first_seen: now.clone(),
             last_seen: now,
+            note_history: Vec::new(),
         }
     }
 
@@ -172,6 +218,32 @@ impl Alert {
     }
 }
 
+/// An analyst note on an alert (database row). Notes are append-only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct AlertNote {
+    pub id: String,
+    pub alert_id: String,
+    /// Position in the alert's history, starting at 1.
+    pub seq: i64,
+    pub author: String,
+    pub text: String,
+    pub created_at: String,
+}
+
+impl AlertNote {
+    pub fn new(alert_id: String, seq: i64, author: String, text: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alert_id,
+            seq,
+            author,
+            text,
+            created_at: now_rfc3339(),
+        }
+    }
+}
+
 /// A normalized alert from the pipeline input stage (not stored directly).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NormalizedAlert {
@@ -264,6 +336,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_severity_from_str() {
+        assert_eq!("critical".parse::<Severity>(), Ok(Severity::Critical));
+        assert_eq!("HIGH".parse::<Severity>(), Ok(Severity::High));
+        assert_eq!(" Medium ".parse::<Severity>(), Ok(Severity::Medium));
+        let err = "urgent".parse::<Severity>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown severity: urgent");
+        assert!("".parse::<Severity>().is_err());
+    }
+
+    #[test]
+    fn test_severity_display_and_serde_match_as_str() {
+        for s in [Severity::Info, Severity::Low, Severity::Medium, Severity::High, Severity::Critical] {
+            assert_eq!(s.to_string(), s.as_str());
+            let json = serde_json::to_string(&s).unwrap();
+            assert_eq!(json, format!("\"{}\"", s.as_str()));
+            assert_eq!(serde_json::from_str::<Severity>(&json).unwrap(), s);
+            assert_eq!(s.to_string().parse::<Severity>(), Ok(s));
+        }
+    }
+
+    #[test]
+    fn test_severity_escalate_and_max() {
+        assert_eq!(Severity::Info.escalate(0), Severity::Info);
+        assert_eq!(Severity::Low.escalate(1), Severity::Medium);
+        assert_eq!(Severity::Medium.escalate(2), Severity::Critical);
+        assert_eq!(Severity::High.escalate(5), Severity::Critical);
+        assert_eq!(Severity::Critical.escalate(u8::MAX), Severity::Critical);
+        assert_eq!(Severity::Low.max(Severity::High), Severity::High);
+        let worst = [Severity::Medium, Severity::Critical, Severity::Info].into_iter().max();
+        assert_eq!(worst, Some(Severity::Critical));
+    }
+
     // A5: from_str_lossy fallback tests
     #[test]
     fn test_severity_from_str_lossy_fallback() {
@@ -1,9 +1,10 @@
 //! Device model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// Network device status.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -82,6 +83,25 @@ impl DeviceType {
     }
 }
 
+/// How a device was first found.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryMethod {
+    /// An nmap scan.
+    Active,
+    /// Observed traffic: ARP, DHCP, mDNS or SSDP.
+    Passive,
+}
+
+impl DiscoveryMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Passive => "passive",
+        }
+    }
+}
+
 /// A discovered network device.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -100,12 +120,14 @@ pub struct Device {
     // Added in migration 011
     pub os_version: Option<String>,
     pub notes: Option<String>,
+    // Added in migration 024
+    pub discovery_method: Option<String>,
 }
 
 impl Device {
     /// Create a new device with generated ID and timestamps.
     pub fn new(ip: String) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             ip,
@@ -120,6 +142,7 @@ impl Device {
             last_seen: now,
             os_version: None,
             notes: None,
+            discovery_method: None,
         }
     }
 
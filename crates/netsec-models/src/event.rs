@@ -1,9 +1,10 @@
 //! Event bus model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// Types of events emitted by the system.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -11,10 +12,17 @@ pub enum EventType {
     DeviceDiscovered,
     DeviceUpdated,
     DeviceLost,
+    /// A device not seen for longer than the offline threshold. Payload
+    /// `{"device_id", "ip", "last_seen"}`.
+    DeviceOffline,
     AlertCreated,
     AlertUpdated,
     AlertResolved,
+    /// A scheduled job was dispatched. Payload `{"job_id", "task_type",
+    /// "task_params"}`.
     ScanStarted,
+    /// Payload `{"scan_id", "progress"}`, progress an integer percentage.
+    ScanProgress,
     ScanCompleted,
     ScanFailed,
     ThreatDetected,
@@ -22,6 +30,82 @@ pub enum EventType {
     MetadataExtracted,
 }
 
+/// The JSON type a required payload field must have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    String,
+    Integer,
+    /// A severity name (`"info"` .. `"critical"`).
+    Severity,
+}
+
+impl FieldKind {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Integer => "an integer",
+            Self::Severity => "a severity name",
+        }
+    }
+
+    fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Severity => value
+                .as_str()
+                .is_some_and(|s| s.parse::<crate::alert::Severity>().is_ok()),
+        }
+    }
+}
+
+impl EventType {
+    /// Payload fields consumers rely on for this event type.
+    fn required_fields(&self) -> &'static [(&'static str, FieldKind)] {
+        match self {
+            Self::ScanStarted => {
+                &[("job_id", FieldKind::String), ("task_type", FieldKind::String)]
+            }
+            Self::ScanProgress => {
+                &[("scan_id", FieldKind::String), ("progress", FieldKind::Integer)]
+            }
+            Self::DeviceDiscovered
+            | Self::DeviceUpdated
+            | Self::DeviceLost
+            | Self::DeviceOffline => {
+                &[("device_id", FieldKind::String), ("ip", FieldKind::String)]
+            }
+            Self::AlertCreated => {
+                &[("title", FieldKind::String), ("severity", FieldKind::Severity)]
+            }
+            Self::SystemHealth => &[("subsystem", FieldKind::String)],
+            Self::MetadataExtracted => &[("file", FieldKind::String)],
+            // Completions carry a `job_id` for scheduled runs and a `scan_id`
+            // for direct scans; consumers read whichever is present
+            Self::ScanCompleted
+            | Self::ScanFailed
+            | Self::AlertUpdated
+            | Self::AlertResolved
+            | Self::ThreatDetected => &[],
+        }
+    }
+}
+
+/// Errors from [`NetsecEvent::new_validated`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EventError {
+    #[error("{event_type:?} payload must be a JSON object")]
+    NotAnObject { event_type: EventType },
+    #[error("{event_type:?} payload is missing `{field}`")]
+    MissingField { event_type: EventType, field: &'static str },
+    #[error("{event_type:?} payload field `{field}` must be {expected}")]
+    InvalidField {
+        event_type: EventType,
+        field: &'static str,
+        expected: &'static str,
+    },
+}
+
 /// An event emitted on the event bus.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetsecEvent {
@@ -29,16 +113,51 @@ pub struct NetsecEvent {
     pub event_type: EventType,
     pub payload: serde_json::Value,
     pub timestamp: String,
+    /// Position in the publishing bus's event order, starting at 1. Assigned
+    /// by `EventBus::publish`; 0 until then.
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 impl NetsecEvent {
+    /// Create an event without checking the payload.
     pub fn new(event_type: EventType, payload: serde_json::Value) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             event_type,
             payload,
-            timestamp: Utc::now().to_rfc3339(),
+            timestamp: now_rfc3339(),
+            sequence: 0,
+        }
+    }
+
+    /// Create an event, first checking that the payload is an object with
+    /// the fields consumers of `event_type` read (e.g. `job_id` and
+    /// `task_type` for `ScanStarted`, `progress` as an integer for
+    /// `ScanProgress`). Extra fields are allowed.
+    pub fn new_validated(
+        event_type: EventType,
+        payload: serde_json::Value,
+    ) -> Result<Self, EventError> {
+        let Some(fields) = payload.as_object() else {
+            return Err(EventError::NotAnObject { event_type });
+        };
+        for &(field, kind) in event_type.required_fields() {
+            match fields.get(field) {
+                None | Some(serde_json::Value::Null) => {
+                    return Err(EventError::MissingField { event_type, field });
+                }
+                Some(value) if !kind.matches(value) => {
+                    return Err(EventError::InvalidField {
+                        event_type,
+                        field,
+                        expected: kind.describe(),
+                    });
+                }
+                Some(_) => {}
+            }
         }
+        Ok(Self::new(event_type, payload))
     }
 }
 
@@ -91,7 +210,7 @@ impl DeviceEvent {
             device_id,
             event_type: event_type.as_str().to_string(),
             details: details.to_string(),
-            created_at: Utc::now().to_rfc3339(),
+            created_at: now_rfc3339(),
         }
     }
 }
@@ -114,7 +233,7 @@ impl Observation {
             device_id,
             protocol,
             source_data: source_data.to_string(),
-            created_at: Utc::now().to_rfc3339(),
+            created_at: now_rfc3339(),
         }
     }
 }
@@ -178,6 +297,87 @@ mod tests {
         assert_eq!(DeviceEventType::from_str_lossy(""), DeviceEventType::Updated);
     }
 
+    #[test]
+    fn test_new_validated_scan_events() {
+        let event = NetsecEvent::new_validated(
+            EventType::ScanProgress,
+            serde_json::json!({"scan_id": "scan-1", "progress": 40, "status": "running"}),
+        )
+        .unwrap();
+        assert_eq!(event.event_type, EventType::ScanProgress);
+        assert_eq!(event.payload["progress"], 40);
+
+        // Shaped like the scheduler's dispatch and completion events
+        assert!(NetsecEvent::new_validated(
+            EventType::ScanStarted,
+            serde_json::json!({
+                "job_id": "job-1",
+                "task_type": "discovery_scan",
+                "task_params": "{\"target\": \"10.0.0.0/24\"}",
+            }),
+        )
+        .is_ok());
+        assert!(NetsecEvent::new_validated(
+            EventType::ScanCompleted,
+            serde_json::json!({"job_id": "job-1"}),
+        )
+        .is_ok());
+
+        let err = NetsecEvent::new_validated(
+            EventType::ScanStarted,
+            serde_json::json!({"job_id": "job-1", "target": "10.0.0.0/24"}),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            EventError::MissingField { event_type: EventType::ScanStarted, field: "task_type" }
+        );
+
+        let err = NetsecEvent::new_validated(
+            EventType::ScanProgress,
+            serde_json::json!({"scan_id": "scan-1", "progress": "40%"}),
+        )
+        .unwrap_err();
+        assert!(matches!(err, EventError::InvalidField { field: "progress", .. }));
+    }
+
+    #[test]
+    fn test_new_validated_device_and_alert_events() {
+        assert!(NetsecEvent::new_validated(
+            EventType::DeviceDiscovered,
+            serde_json::json!({"device_id": "dev-1", "ip": "10.0.0.5", "device_type": "unknown"}),
+        )
+        .is_ok());
+        let err = NetsecEvent::new_validated(
+            EventType::DeviceDiscovered,
+            serde_json::json!({"device_id": "dev-1", "ip": null}),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "DeviceDiscovered payload is missing `ip`");
+
+        assert!(NetsecEvent::new_validated(
+            EventType::AlertCreated,
+            serde_json::json!({"title": "Port scan", "severity": "high"}),
+        )
+        .is_ok());
+        let err = NetsecEvent::new_validated(
+            EventType::AlertCreated,
+            serde_json::json!({"title": "Port scan", "severity": "urgent"}),
+        )
+        .unwrap_err();
+        assert!(matches!(err, EventError::InvalidField { field: "severity", .. }));
+    }
+
+    #[test]
+    fn test_new_validated_requires_object_payload() {
+        let err = NetsecEvent::new_validated(EventType::ThreatDetected, serde_json::json!([1, 2]))
+            .unwrap_err();
+        assert_eq!(err, EventError::NotAnObject { event_type: EventType::ThreatDetected });
+        // No required fields, but still an object
+        let empty = NetsecEvent::new_validated(EventType::ThreatDetected, serde_json::json!({}));
+        assert!(empty.is_ok());
+    }
+
     // A7: Constructor defaults
     #[test]
     fn test_netsec_event_constructor_defaults() {
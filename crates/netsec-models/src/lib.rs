@@ -11,5 +11,6 @@ pub mod event;
 pub mod plugin;
 pub mod port;
 pub mod scan;
+pub mod time;
 pub mod traffic;
 pub mod vulnerability;
@@ -1,9 +1,10 @@
 //! Plugin system model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// Plugin categories.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -40,6 +41,8 @@ pub enum PluginStatus {
 pub enum TriggerType {
     Cron,
     Interval,
+    /// Fires a single time at a given instant.
+    Once,
 }
 
 impl TriggerType {
@@ -47,12 +50,14 @@ impl TriggerType {
         match self {
             Self::Cron => "cron",
             Self::Interval => "interval",
+            Self::Once => "once",
         }
     }
 
     pub fn from_str_lossy(s: &str) -> Self {
         match s {
             "cron" => Self::Cron,
+            "once" => Self::Once,
             _ => Self::Interval,
         }
     }
@@ -60,7 +65,6 @@ impl TriggerType {
 
 /// A scheduled job record (database row).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
 pub struct ScheduledJob {
     pub id: String,
     pub trigger_type: String,
@@ -70,11 +74,47 @@ pub struct ScheduledJob {
     pub enabled: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// RFC3339 time of the last dispatch, `None` if never run.
+    #[serde(default)]
+    pub last_run: Option<String>,
+    /// IANA timezone cron triggers are evaluated in, e.g. `America/New_York`.
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+// Not derived: SQLite stores `enabled` as an INTEGER, which the `Any` driver
+// won't decode as `bool`, while Postgres stores a real BOOLEAN.
+#[cfg(feature = "sqlx")]
+impl<'r> sqlx::FromRow<'r, sqlx::any::AnyRow> for ScheduledJob {
+    fn from_row(row: &'r sqlx::any::AnyRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let enabled = match row.try_get::<bool, _>("enabled") {
+            Ok(enabled) => enabled,
+            Err(_) => row.try_get::<i64, _>("enabled")? != 0,
+        };
+        Ok(Self {
+            id: row.try_get("id")?,
+            trigger_type: row.try_get("trigger_type")?,
+            trigger_args: row.try_get("trigger_args")?,
+            task_type: row.try_get("task_type")?,
+            task_params: row.try_get("task_params")?,
+            enabled,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            last_run: row.try_get("last_run")?,
+            timezone: row.try_get("timezone")?,
+        })
+    }
 }
 
 impl ScheduledJob {
     pub fn new(trigger_type: TriggerType, task_type: String) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             trigger_type: trigger_type.as_str().to_string(),
@@ -84,6 +124,8 @@ impl ScheduledJob {
             enabled: true,
             created_at: now.clone(),
             updated_at: now,
+            last_run: None,
+            timezone: default_timezone(),
         }
     }
 }
@@ -105,7 +147,7 @@ mod tests {
     // A4: TriggerType enum roundtrip
     #[test]
     fn test_trigger_type_roundtrip() {
-        for t in [TriggerType::Cron, TriggerType::Interval] {
+        for t in [TriggerType::Cron, TriggerType::Interval, TriggerType::Once] {
             assert_eq!(TriggerType::from_str_lossy(t.as_str()), t);
         }
     }
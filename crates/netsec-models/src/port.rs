@@ -1,9 +1,10 @@
 //! Port model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// A port discovered on a device.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -18,11 +19,14 @@ pub struct Port {
     pub banner: Option<String>,
     pub first_seen: String,
     pub last_seen: String,
+    // Added in migration 020
+    pub http_title: Option<String>,
+    pub server_header: Option<String>,
 }
 
 impl Port {
     pub fn new(device_id: String, port_number: u16, protocol: String) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             device_id,
@@ -34,6 +38,8 @@ impl Port {
             banner: None,
             first_seen: now.clone(),
             last_seen: now,
+            http_title: None,
+            server_header: None,
         }
     }
 }
@@ -63,6 +69,8 @@ mod tests {
         assert!(port.service_name.is_none());
         assert!(port.service_version.is_none());
         assert!(port.banner.is_none());
+        assert!(port.http_title.is_none());
+        assert!(port.server_header.is_none());
         uuid::Uuid::parse_str(&port.id).expect("id should be valid UUID");
         assert!(!port.first_seen.is_empty());
         assert!(!port.last_seen.is_empty());
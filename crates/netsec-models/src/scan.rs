@@ -1,9 +1,10 @@
 //! Scan model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// Scan status.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -47,9 +48,18 @@ pub enum ScanType {
     Vulnerability,
     Full,
     Custom,
+    /// UDP scan (`-sU`).
+    Udp,
+    /// Combined TCP SYN and UDP scan (`-sS -sU`).
+    TcpUdp,
 }
 
 impl ScanType {
+    /// Whether the scan probes UDP ports.
+    pub fn includes_udp(&self) -> bool {
+        matches!(self, Self::Udp | Self::TcpUdp)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Discovery => "discovery",
@@ -57,6 +67,8 @@ impl ScanType {
             Self::Vulnerability => "vulnerability",
             Self::Full => "full",
             Self::Custom => "custom",
+            Self::Udp => "udp",
+            Self::TcpUdp => "tcp_udp",
         }
     }
 
@@ -66,6 +78,8 @@ impl ScanType {
             "port" => Self::Port,
             "vulnerability" => Self::Vulnerability,
             "full" => Self::Full,
+            "udp" => Self::Udp,
+            "tcp_udp" => Self::TcpUdp,
             _ => Self::Custom,
         }
     }
@@ -102,7 +116,7 @@ impl Scan {
             results: "{}".to_string(),
             started_at: None,
             completed_at: None,
-            created_at: Utc::now().to_rfc3339(),
+            created_at: now_rfc3339(),
         }
     }
 }
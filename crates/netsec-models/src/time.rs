@@ -0,0 +1,71 @@
+//! RFC3339 timestamp helpers.
+//!
+//! All timestamps are stored as RFC3339 strings. Use these helpers instead of
+//! calling `to_rfc3339()` / `DateTime::parse_from_rfc3339` directly so the
+//! format stays consistent and parse failures surface as a typed error.
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+/// Error produced when a stored timestamp cannot be parsed.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("invalid RFC3339 timestamp {value:?}: {reason}")]
+pub struct TimeError {
+    pub value: String,
+    pub reason: String,
+}
+
+/// The current UTC time formatted as RFC3339.
+pub fn now_rfc3339() -> String {
+    to_rfc3339(&Utc::now())
+}
+
+/// Format a UTC timestamp as RFC3339 (the storage format).
+pub fn to_rfc3339(dt: &DateTime<Utc>) -> String {
+    dt.to_rfc3339()
+}
+
+/// Parse an RFC3339 timestamp (any offset) into UTC.
+pub fn parse_rfc3339(s: &str) -> Result<DateTime<Utc>, TimeError> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| TimeError {
+            value: s.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_roundtrip() {
+        let dt = Utc.with_ymd_and_hms(2024, 6, 15, 14, 30, 0).unwrap();
+        let s = to_rfc3339(&dt);
+        assert_eq!(s, "2024-06-15T14:30:00+00:00");
+        assert_eq!(parse_rfc3339(&s).unwrap(), dt);
+    }
+
+    #[test]
+    fn test_now_rfc3339_parses() {
+        let s = now_rfc3339();
+        assert!(parse_rfc3339(&s).is_ok());
+    }
+
+    #[test]
+    fn test_parse_converts_offset_to_utc() {
+        let dt = parse_rfc3339("2024-06-15T16:30:00+02:00").unwrap();
+        assert_eq!(dt, Utc.with_ymd_and_hms(2024, 6, 15, 14, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for bad in ["", "not a date", "2024-06-15", "2024-06-15 14:30:00"] {
+            let err = parse_rfc3339(bad).unwrap_err();
+            assert_eq!(err.value, bad);
+            assert!(err.to_string().contains("invalid RFC3339 timestamp"));
+        }
+    }
+}
@@ -1,9 +1,10 @@
 //! Traffic flow model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::time::now_rfc3339;
+
 /// A network traffic flow record (database row).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -20,11 +21,14 @@ pub struct TrafficFlow {
     pub packets_received: i64,
     pub first_seen: String,
     pub last_seen: String,
+    // Added in migration 023
+    pub country_src: Option<String>,
+    pub country_dst: Option<String>,
 }
 
 impl TrafficFlow {
     pub fn new(src_ip: String, src_port: u16, dst_ip: String, dst_port: u16, protocol: String) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             src_ip,
@@ -38,6 +42,8 @@ impl TrafficFlow {
             packets_received: 0,
             first_seen: now.clone(),
             last_seen: now,
+            country_src: None,
+            country_dst: None,
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Vulnerability model types.
 
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::alert::Severity;
 
+use crate::time::now_rfc3339;
+
 /// A discovered vulnerability (database row).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -31,7 +32,7 @@ pub struct Vulnerability {
 
 impl Vulnerability {
     pub fn new(title: String, source_tool: String, severity: Severity) -> Self {
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
         Self {
             id: Uuid::new_v4().to_string(),
             cve_id: None,
@@ -51,6 +52,24 @@ impl Vulnerability {
             references_json: None,
         }
     }
+
+    /// Severity for a CVSS base score, using the CVSS v3 qualitative bands:
+    /// 0.0 none ([`Severity::Info`]), 0.1-3.9 low, 4.0-6.9 medium, 7.0-8.9
+    /// high and 9.0-10.0 critical. Scores that aren't positive (or NaN) are
+    /// `Info`.
+    pub fn severity_from_cvss(score: f64) -> Severity {
+        if score >= 9.0 {
+            Severity::Critical
+        } else if score >= 7.0 {
+            Severity::High
+        } else if score >= 4.0 {
+            Severity::Medium
+        } else if score > 0.0 {
+            Severity::Low
+        } else {
+            Severity::Info
+        }
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +106,24 @@ mod tests {
         assert!(!v.created_at.is_empty());
         assert!(!v.updated_at.is_empty());
     }
+
+    #[test]
+    fn test_severity_from_cvss_bands() {
+        let cases = [
+            (0.0, Severity::Info),
+            (0.1, Severity::Low),
+            (3.9, Severity::Low),
+            (4.0, Severity::Medium),
+            (6.9, Severity::Medium),
+            (7.0, Severity::High),
+            (8.9, Severity::High),
+            (9.0, Severity::Critical),
+            (10.0, Severity::Critical),
+            (-1.0, Severity::Info),
+            (f64::NAN, Severity::Info),
+        ];
+        for (score, expected) in cases {
+            assert_eq!(Vulnerability::severity_from_cvss(score), expected, "{score}");
+        }
+    }
 }
@@ -1,9 +1,12 @@
 //! High-performance parsers for security tool output.
 //!
 //! Consolidates nmap XML, Suricata EVE JSON, Zeek log, and PCAP flow parsers
-//! that were previously in separate crates (netsec-nmap, netsec-stream).
+//! that were previously in separate crates (netsec-nmap, netsec-stream), plus
+//! masscan JSON.
 
 pub mod nmap;
+pub mod masscan;
+pub mod nmap_vuln;
 pub mod suricata;
 pub mod pcap;
 pub mod zeek;
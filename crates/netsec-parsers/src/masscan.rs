@@ -0,0 +1,227 @@
+//! Masscan JSON output (`-oJ`) parser.
+//!
+//! Masscan writes one record per finding: an open port, a banner grabbed from
+//! a port, or a ping reply, each naming the host IP. The array isn't always
+//! valid JSON (older versions leave a trailing comma and append a
+//! `{"finished": 1}` marker), so records are read one value at a time.
+//!
+//! [`to_nmap_result`] groups the records per host into the nmap result shape
+//! the scanner already consumes.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::nmap::{NmapHost, NmapPort, NmapScanResult};
+
+/// Error parsing masscan JSON output.
+#[derive(Debug, Error)]
+pub enum MasscanParseError {
+    /// A record that isn't a JSON object of the expected shape.
+    #[error("masscan record {record}: {source}")]
+    Json {
+        /// 1-based index of the record in the output.
+        record: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// One masscan finding.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MasscanRecord {
+    pub ip: String,
+    #[serde(default)]
+    pub ports: Vec<MasscanPort>,
+}
+
+/// A port entry of a [`MasscanRecord`].
+///
+/// Port-status records carry `status`; banner records carry `service`
+/// instead. Ping replies are reported as port 0 with protocol `icmp`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MasscanPort {
+    pub port: u16,
+    pub proto: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub ttl: Option<u8>,
+    #[serde(default)]
+    pub service: Option<MasscanService>,
+}
+
+/// A banner masscan grabbed from a port (`--banners`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MasscanService {
+    pub name: String,
+    #[serde(default)]
+    pub banner: String,
+}
+
+/// Parse masscan `-oJ` output into its records.
+///
+/// Accepts the bracketed array with or without a trailing comma, as well as
+/// bare newline- or comma-separated records. Entries without an `ip` (the
+/// `finished` marker) are skipped.
+pub fn parse_masscan_json(data: &str) -> Result<Vec<MasscanRecord>, MasscanParseError> {
+    let mut records = Vec::new();
+    let mut rest = data.trim();
+    rest = rest.strip_prefix('[').unwrap_or(rest);
+    rest = rest.strip_suffix(']').unwrap_or(rest);
+
+    let mut index = 0;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        index += 1;
+        let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+        let value = match stream.next() {
+            Some(Ok(value)) => value,
+            Some(Err(source)) => return Err(MasscanParseError::Json { record: index, source }),
+            None => break,
+        };
+        rest = &rest[stream.byte_offset()..];
+
+        if value.get("ip").is_none() {
+            continue;
+        }
+        let record = serde_json::from_value(value)
+            .map_err(|source| MasscanParseError::Json { record: index, source })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Group masscan records per host, in order of first appearance.
+///
+/// Every host is `up`, its address stored under `ipv4` or `ipv6`. Open
+/// ports become ports in state `open`; a banner's service name fills in the
+/// port's `service["name"]`, except the `title` and `http.server` banners,
+/// which are stored like nmap's `http-title` and `http-server-header` script
+/// output. Ping replies only mark the host as up.
+pub fn to_nmap_result(records: &[MasscanRecord]) -> NmapScanResult {
+    let mut result = NmapScanResult::default();
+    result.scan_info.insert("scanner".to_string(), "masscan".to_string());
+    let mut by_ip: HashMap<&str, usize> = HashMap::new();
+
+    for record in records {
+        let idx = *by_ip.entry(record.ip.as_str()).or_insert_with(|| {
+            let family = if record.ip.contains(':') { "ipv6" } else { "ipv4" };
+            result.hosts.push(NmapHost {
+                status: "up".to_string(),
+                addresses: HashMap::from([(family.to_string(), record.ip.clone())]),
+                ..Default::default()
+            });
+            result.hosts.len() - 1
+        });
+        let host = &mut result.hosts[idx];
+
+        for entry in &record.ports {
+            if !matches!(entry.proto.as_str(), "tcp" | "udp" | "sctp") {
+                continue;
+            }
+            let pos = host
+                .ports
+                .iter()
+                .position(|p| p.port == entry.port && p.protocol == entry.proto);
+            let port = match pos {
+                Some(pos) => &mut host.ports[pos],
+                None => {
+                    host.ports.push(NmapPort {
+                        port: entry.port,
+                        protocol: entry.proto.clone(),
+                        state: "open".to_string(),
+                        ..Default::default()
+                    });
+                    host.ports.last_mut().expect("just pushed")
+                }
+            };
+            if let Some(status) = &entry.status {
+                port.state = status.clone();
+            }
+            if let Some(service) = &entry.service {
+                let banner = service.banner.trim().to_string();
+                match service.name.as_str() {
+                    "title" => {
+                        port.scripts.insert("http-title".to_string(), banner);
+                    }
+                    "http.server" => {
+                        port.scripts.insert("http-server-header".to_string(), banner);
+                    }
+                    name => {
+                        port.service.entry("name".to_string()).or_insert(name.to_string());
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../../tests/fixtures/masscan_sample.json");
+
+    #[test]
+    fn test_parse_masscan_json() {
+        let records = parse_masscan_json(SAMPLE).unwrap();
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[0].ip, "192.168.1.10");
+        assert_eq!(records[0].ports[0].port, 80);
+        assert_eq!(records[0].ports[0].status.as_deref(), Some("open"));
+        assert_eq!(records[0].ports[0].ttl, Some(64));
+        let service = records[3].ports[0].service.as_ref().unwrap();
+        assert_eq!(service.name, "title");
+        assert_eq!(service.banner, "Router Login");
+    }
+
+    #[test]
+    fn test_parse_masscan_json_variants() {
+        // Trailing comma before the closing bracket (masscan < 1.3)
+        let records =
+            parse_masscan_json("[\n{\"ip\": \"10.0.0.1\", \"ports\": []},\n]\n").unwrap();
+        assert_eq!(records.len(), 1);
+        // Bare records, no brackets
+        let records = parse_masscan_json(
+            "{\"ip\": \"10.0.0.1\", \"ports\": []}\n{\"ip\": \"10.0.0.2\", \"ports\": []}\n",
+        )
+        .unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(parse_masscan_json("").unwrap().is_empty());
+        assert!(parse_masscan_json("[\n]").unwrap().is_empty());
+
+        let err = parse_masscan_json("[{\"ip\": \"10.0.0.1\"}, {\"ip\": 7}]").unwrap_err();
+        assert!(matches!(err, MasscanParseError::Json { record: 2, .. }));
+        let err = parse_masscan_json("[{\"ip\": \"10.0.0.1\"}, {oops}]").unwrap_err();
+        assert!(matches!(err, MasscanParseError::Json { record: 2, .. }));
+    }
+
+    #[test]
+    fn test_to_nmap_result_groups_hosts() {
+        let result = to_nmap_result(&parse_masscan_json(SAMPLE).unwrap());
+        assert_eq!(result.hosts.len(), 3);
+
+        let host = &result.hosts[0];
+        assert_eq!(host.status, "up");
+        assert_eq!(host.addresses["ipv4"], "192.168.1.10");
+        let ports: Vec<u16> = host.ports.iter().map(|p| p.port).collect();
+        assert_eq!(ports, vec![80, 22]);
+        let http = &host.ports[0];
+        assert_eq!(http.state, "open");
+        assert_eq!(http.service["name"], "http");
+        assert_eq!(http.scripts["http-title"], "Router Login");
+
+        assert_eq!(result.hosts[1].ports[0].protocol, "udp");
+        // A ping reply marks the host up without ports
+        assert_eq!(result.hosts[2].addresses["ipv4"], "192.168.1.30");
+        assert!(result.hosts[2].ports.is_empty());
+    }
+}
@@ -1,13 +1,17 @@
-//! Nmap XML output parser.
+//! Nmap output parsers.
 //!
 //! Ported from the original netsec-nmap crate. Parses Nmap's -oX XML output
-//! into structured Rust types using quick-xml.
+//! into structured Rust types using quick-xml, and its -oG grepable output
+//! into the same types.
 
+use netsec_models::vulnerability::Vulnerability;
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::nmap_vuln::host_vulnerabilities;
+
 /// Result of parsing an Nmap XML scan.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NmapScanResult {
@@ -32,6 +36,9 @@ pub struct NmapPort {
     pub protocol: String,
     pub state: String,
     pub service: HashMap<String, String>,
+    /// NSE script output keyed by script id (e.g. `http-title`).
+    #[serde(default)]
+    pub scripts: HashMap<String, String>,
 }
 
 /// Parse Nmap XML output into structured data.
@@ -42,6 +49,7 @@ pub fn parse_nmap_xml(xml_data: &str) -> Result<NmapScanResult, String> {
 
     let mut current_host: Option<NmapHost> = None;
     let mut in_host = false;
+    let mut in_port = false;
     let mut buf = Vec::new();
 
     loop {
@@ -119,6 +127,7 @@ pub fn parse_nmap_xml(xml_data: &str) -> Result<NmapScanResult, String> {
                         }
                     }
                     "port" if in_host => {
+                        in_port = true;
                         if let Some(ref mut host) = current_host {
                             let mut port = NmapPort::default();
                             for attr in e.attributes().flatten() {
@@ -167,6 +176,35 @@ pub fn parse_nmap_xml(xml_data: &str) -> Result<NmapScanResult, String> {
                             }
                         }
                     }
+                    "script" if in_port => {
+                        if let Some(ref mut host) = current_host {
+                            if let Some(last) = host.ports.last_mut() {
+                                let mut id = String::new();
+                                let mut output = String::new();
+                                for attr in e.attributes().flatten() {
+                                    match attr.key.as_ref() {
+                                        b"id" => {
+                                            id = String::from_utf8_lossy(&attr.value)
+                                                .to_string()
+                                        }
+                                        b"output" => {
+                                            output = attr
+                                                .unescape_value()
+                                                .map(|v| v.to_string())
+                                                .unwrap_or_else(|_| {
+                                                    String::from_utf8_lossy(&attr.value)
+                                                        .to_string()
+                                                })
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                                if !id.is_empty() {
+                                    last.scripts.insert(id, output);
+                                }
+                            }
+                        }
+                    }
                     "osmatch" if in_host => {
                         if let Some(ref mut host) = current_host {
                             for attr in e.attributes().flatten() {
@@ -183,11 +221,16 @@ pub fn parse_nmap_xml(xml_data: &str) -> Result<NmapScanResult, String> {
             }
             Ok(Event::End(ref e)) => {
                 let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                if name == "host" {
-                    if let Some(host) = current_host.take() {
-                        result.hosts.push(host);
+                match name.as_str() {
+                    "host" => {
+                        if let Some(host) = current_host.take() {
+                            result.hosts.push(host);
+                        }
+                        in_host = false;
+                        in_port = false;
                     }
-                    in_host = false;
+                    "port" | "ports" => in_port = false,
+                    _ => {}
                 }
             }
             Ok(Event::Eof) => break,
@@ -200,6 +243,149 @@ pub fn parse_nmap_xml(xml_data: &str) -> Result<NmapScanResult, String> {
     Ok(result)
 }
 
+/// Parse the XML of an `nmap --script vuln` scan into vulnerability rows.
+///
+/// Port scripts are read with [`crate::nmap_vuln::parse_vuln_script`]
+/// (`vulners`, `vulscan` and the standard NSE vuln report), giving one row per
+/// CVE per port with severity mapped from the CVSS score. Malformed XML is
+/// logged and yields no rows.
+pub fn parse_nse_vuln_output(xml: &str) -> Vec<Vulnerability> {
+    match parse_nmap_xml(xml) {
+        Ok(result) => result.hosts.iter().flat_map(host_vulnerabilities).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to parse nmap vuln output: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Parse Nmap grepable (`-oG`) output into the same structures as
+/// [`parse_nmap_xml`].
+///
+/// `Status:` and `Ports:` lines for the same host are merged; a host with
+/// only a `Ports:` line (e.g. with `-Pn`) counts as up. Each port entry's
+/// state (`open`, `filtered`, `closed`, `open|filtered`, ...) is kept as is,
+/// and the service name and version become the `name` and `version` service
+/// entries. The `# Nmap ... as: <args>` header fills `scan_info` with
+/// `scanner`, `version` and `args`. Unknown fields and comments are ignored.
+pub fn parse_nmap_grepable(input: &str) -> Result<NmapScanResult, String> {
+    let mut result = NmapScanResult::default();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for (number, line) in input.lines().enumerate() {
+        let number = number + 1;
+        if let Some(header) = line.strip_prefix("# Nmap ") {
+            if let Some((version, _)) = header.split_once(" scan initiated ") {
+                result.scan_info.insert("scanner".to_string(), "nmap".to_string());
+                result.scan_info.insert("version".to_string(), version.to_string());
+            }
+            if let Some((_, args)) = header.split_once(" as: ") {
+                result.scan_info.insert("args".to_string(), args.to_string());
+            }
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        let Some(host_field) = fields.next().and_then(|f| f.strip_prefix("Host: ")) else {
+            continue;
+        };
+        let (ip, hostname) = match host_field.split_once(' ') {
+            Some((ip, name)) => (ip, name.trim().trim_start_matches('(').trim_end_matches(')')),
+            None => (host_field.trim(), ""),
+        };
+        if ip.is_empty() {
+            return Err(format!("line {number}: host line without an address"));
+        }
+
+        let host = match index.get(ip) {
+            Some(&i) => &mut result.hosts[i],
+            None => {
+                index.insert(ip.to_string(), result.hosts.len());
+                let addr_type = if ip.contains(':') { "ipv6" } else { "ipv4" };
+                let mut host = NmapHost {
+                    status: "unknown".to_string(),
+                    ..Default::default()
+                };
+                host.addresses.insert(addr_type.to_string(), ip.to_string());
+                if !hostname.is_empty() {
+                    host.hostnames
+                        .push(HashMap::from([("name".to_string(), hostname.to_string())]));
+                }
+                result.hosts.push(host);
+                result.hosts.last_mut().expect("just pushed")
+            }
+        };
+
+        for field in fields {
+            let Some((key, value)) = field.split_once(": ") else {
+                continue;
+            };
+            match key {
+                "Status" => host.status = value.trim().to_lowercase(),
+                "Ports" => {
+                    if host.status == "unknown" {
+                        host.status = "up".to_string();
+                    }
+                    for entry in split_port_entries(value) {
+                        let port = parse_grepable_port(entry)
+                            .ok_or_else(|| format!("line {number}: invalid port entry '{entry}'"))?;
+                        host.ports.push(port);
+                    }
+                }
+                "OS" => {
+                    host.os.insert("name".to_string(), value.trim().to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Split a `Ports:` field into entries. Entries are separated by `", "`, but
+/// version strings may contain that too, so a new entry only starts at
+/// `<digits>/`.
+fn split_port_entries(field: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    for (i, _) in field.match_indices(", ") {
+        let next = &field[i + 2..];
+        let digits = next.chars().take_while(char::is_ascii_digit).count();
+        if digits > 0 && next[digits..].starts_with('/') {
+            entries.push(field[start..i].trim());
+            start = i + 2;
+        }
+    }
+    entries.push(field[start..].trim());
+    entries.retain(|e| !e.is_empty());
+    entries
+}
+
+/// Parse `port/state/protocol/owner/service/rpc_info/version/`. nmap writes
+/// `/` inside fields as `|`, so an entry always has exactly seven fields, each
+/// followed by `/`.
+fn parse_grepable_port(entry: &str) -> Option<NmapPort> {
+    let parts: Vec<&str> = entry.strip_suffix('/')?.split('/').collect();
+    if parts.len() != 7 || parts[1].is_empty() || parts[2].is_empty() {
+        return None;
+    }
+    let mut port = NmapPort {
+        port: parts[0].parse().ok()?,
+        state: parts[1].to_string(),
+        protocol: parts[2].to_string(),
+        ..Default::default()
+    };
+    let mut field = |i: usize, key: &str| {
+        if let Some(value) = parts.get(i).filter(|v| !v.is_empty()) {
+            port.service.insert(key.to_string(), value.to_string());
+        }
+    };
+    field(4, "name");
+    field(6, "version");
+    Some(port)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -313,6 +499,77 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_nse_vuln_output_fixture() {
+        let xml = include_str!("../../../tests/fixtures/nmap_vuln.xml");
+        let vulns = parse_nse_vuln_output(xml);
+        let found: Vec<(Option<i64>, &str)> = vulns
+            .iter()
+            .map(|v| (v.port, v.cve_id.as_deref().unwrap()))
+            .collect();
+        // vulners and vulscan both report CVE-2016-10012: one row. The
+        // not-vulnerable Slowloris report and http-title yield nothing.
+        assert_eq!(
+            found,
+            vec![
+                (Some(22), "CVE-2016-10010"),
+                (Some(22), "CVE-2016-10012"),
+                (Some(22), "CVE-2023-38408"),
+                (Some(80), "CVE-2011-3192"),
+            ]
+        );
+        assert!(vulns.iter().all(|v| v.device_ip.as_deref() == Some("192.168.1.20")));
+
+        let by_cve = |id: &str| vulns.iter().find(|v| v.cve_id.as_deref() == Some(id)).unwrap();
+        let critical = by_cve("CVE-2023-38408");
+        assert_eq!(critical.cvss_score, Some(9.8));
+        assert_eq!(critical.severity, "critical");
+        assert_eq!(critical.service.as_deref(), Some("ssh"));
+        let merged = by_cve("CVE-2016-10012");
+        assert_eq!(merged.cvss_score, Some(7.8));
+        assert_eq!(merged.severity, "high");
+        let apache = by_cve("CVE-2011-3192");
+        assert_eq!(apache.title, "Apache byterange filter DoS");
+        assert_eq!(apache.severity, "high");
+        // No score from vulscan alone
+        assert_eq!(by_cve("CVE-2016-10010").cvss_score, None);
+
+        let malformed = include_str!("../../../tests/fixtures/nmap_malformed.xml");
+        assert!(parse_nse_vuln_output(malformed).is_empty());
+    }
+
+    #[test]
+    fn test_nmap_port_script_output() {
+        let xml = r#"<?xml version="1.0"?>
+<nmaprun scanner="nmap">
+  <host>
+    <status state="up"/>
+    <address addr="10.0.0.8" addrtype="ipv4"/>
+    <ports>
+      <port protocol="tcp" portid="80">
+        <state state="open"/>
+        <service name="http"/>
+        <script id="http-title" output="Welcome to nginx!"><elem key="title">Welcome to nginx!</elem></script>
+        <script id="http-server-header" output="nginx/1.18.0 (Ubuntu)"/>
+      </port>
+      <port protocol="tcp" portid="22"><state state="open"/></port>
+    </ports>
+    <hostscript>
+      <script id="smb-os-discovery" output="OS: Windows"/>
+    </hostscript>
+  </host>
+</nmaprun>"#;
+        let result = parse_nmap_xml(xml).unwrap();
+        let ports = &result.hosts[0].ports;
+        assert_eq!(ports[0].scripts.get("http-title").unwrap(), "Welcome to nginx!");
+        assert_eq!(
+            ports[0].scripts.get("http-server-header").unwrap(),
+            "nginx/1.18.0 (Ubuntu)"
+        );
+        // Host-level scripts are not attributed to the last port.
+        assert!(ports[1].scripts.is_empty());
+    }
+
     // C2: Multiple hosts
     #[test]
     fn test_nmap_multiple_hosts() {
@@ -346,4 +603,72 @@ mod tests {
         assert_eq!(result.hosts[2].status, "down");
         assert!(result.hosts[2].ports.is_empty());
     }
+
+    #[test]
+    fn test_parse_grepable_fixture() {
+        let input = include_str!("../../../tests/fixtures/nmap_grepable.gnmap");
+        let result = parse_nmap_grepable(input).unwrap();
+        assert_eq!(result.scan_info.get("version").unwrap(), "7.94");
+        assert_eq!(
+            result.scan_info.get("args").unwrap(),
+            "nmap -sV -oG - 192.168.1.0/24"
+        );
+        assert_eq!(result.hosts.len(), 3);
+
+        let router = &result.hosts[0];
+        assert_eq!(router.status, "up");
+        assert_eq!(router.addresses.get("ipv4").unwrap(), "192.168.1.1");
+        assert_eq!(router.hostnames[0].get("name").unwrap(), "router.lan");
+        let ports: Vec<(u16, &str, &str)> = router
+            .ports
+            .iter()
+            .map(|p| (p.port, p.protocol.as_str(), p.state.as_str()))
+            .collect();
+        assert_eq!(
+            ports,
+            vec![(22, "tcp", "open"), (53, "udp", "open"), (80, "tcp", "filtered")]
+        );
+        assert_eq!(router.ports[0].service.get("name").unwrap(), "ssh");
+        // The comma inside the version string doesn't split the entry
+        assert_eq!(
+            router.ports[0].service.get("version").unwrap(),
+            "OpenSSH 8.9p1 Ubuntu 3ubuntu0.4 (Ubuntu Linux; protocol 2.0)"
+        );
+        assert_eq!(router.ports[2].service.get("name").unwrap(), "http");
+        assert!(!router.ports[2].service.contains_key("version"));
+
+        let web = &result.hosts[1];
+        assert!(web.hostnames.is_empty());
+        assert_eq!(web.os.get("name").unwrap(), "Linux 5.0 - 5.4");
+        let states: Vec<&str> = web.ports.iter().map(|p| p.state.as_str()).collect();
+        assert_eq!(states, vec!["open", "closed", "open|filtered"]);
+        assert_eq!(web.ports[0].service.get("name").unwrap(), "ssl|https");
+
+        assert_eq!(result.hosts[2].status, "down");
+        assert!(result.hosts[2].ports.is_empty());
+    }
+
+    #[test]
+    fn test_parse_grepable_ports_only_host_is_up() {
+        let input = "Host: fe80::1 ()\tPorts: 22/open/tcp//ssh///\n";
+        let result = parse_nmap_grepable(input).unwrap();
+        assert_eq!(result.hosts[0].status, "up");
+        assert_eq!(result.hosts[0].addresses.get("ipv6").unwrap(), "fe80::1");
+        assert_eq!(result.hosts[0].ports[0].port, 22);
+    }
+
+    #[test]
+    fn test_parse_grepable_invalid_port_entry() {
+        let input = "Host: 10.0.0.1 ()\tPorts: 22/open/tcp//ssh///, http/open/tcp////\n";
+        let err = parse_nmap_grepable(input).unwrap_err();
+        assert!(err.contains("line 1"), "{err}");
+        // Entries need seven `/`-terminated fields
+        for entry in ["22/open/tcp//ssh//", "22/open/tcp//ssh//x", "22/open/tcp//ssh////"] {
+            let input = format!("Host: 10.0.0.1 ()\tPorts: {entry}\n");
+            assert!(parse_nmap_grepable(&input).is_err(), "{entry}");
+        }
+
+        let empty = parse_nmap_grepable("not grepable output").unwrap();
+        assert!(empty.hosts.is_empty());
+    }
 }
@@ -0,0 +1,474 @@
+//! Nmap vulnerability script output parser.
+//!
+//! Extracts CVE ids, CVSS scores, and references from the `vulners` and
+//! `vulscan` scripts and from the standard NSE `vuln` category report format,
+//! and maps them onto `Vulnerability` models linked to the scanned host and
+//! port.
+
+use std::collections::BTreeMap;
+
+use netsec_models::alert::Severity;
+use netsec_models::vulnerability::Vulnerability;
+
+use crate::nmap::{NmapHost, NmapPort};
+
+/// A single CVE reported by a vulnerability script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VulnFinding {
+    /// CVE identifier, e.g. `CVE-2017-0143`.
+    pub cve_id: String,
+    /// CVSS base score, if the script reported one.
+    pub cvss_score: Option<f64>,
+    /// Human-readable title (the vuln block title, or the CVE id).
+    pub title: String,
+    /// Severity label from the script (`Risk factor:`), if any.
+    pub risk_factor: Option<String>,
+    /// Reference URLs.
+    pub references: Vec<String>,
+    /// Whether the script flagged a known public exploit.
+    pub exploit: bool,
+}
+
+/// Whether `id` looks like a CVE identifier (`CVE-YYYY-NNNN...`).
+fn is_cve_id(id: &str) -> bool {
+    let Some(rest) = id.strip_prefix("CVE-") else {
+        return false;
+    };
+    let mut parts = rest.splitn(2, '-');
+    let year = parts.next().unwrap_or("");
+    let seq = parts.next().unwrap_or("");
+    year.len() == 4
+        && year.chars().all(|c| c.is_ascii_digit())
+        && seq.len() >= 4
+        && seq.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parse `vulners` script output.
+///
+/// Each result line is `<id> <score> <url> [*EXPLOIT*]`, grouped under a CPE
+/// header. Only CVE entries are returned; exploit-db, Packet Storm and other
+/// non-CVE entries are not linked to a CVE in the output and are skipped.
+pub fn parse_vulners(output: &str) -> Vec<VulnFinding> {
+    let mut findings = Vec::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [id, score, rest @ ..] = fields.as_slice() else {
+            continue;
+        };
+        if !is_cve_id(id) {
+            continue;
+        }
+        let Ok(score) = score.parse::<f64>() else {
+            continue;
+        };
+        let references = rest
+            .iter()
+            .filter(|f| f.starts_with("http://") || f.starts_with("https://"))
+            .map(|f| f.to_string())
+            .collect();
+        findings.push(VulnFinding {
+            cve_id: id.to_string(),
+            cvss_score: Some(score),
+            title: id.to_string(),
+            risk_factor: None,
+            references,
+            exploit: rest.contains(&"*EXPLOIT*"),
+        });
+    }
+    findings
+}
+
+/// Parse `vulscan` script output.
+///
+/// Results are listed per database as `[<id>] <title>` lines; entries whose id
+/// is a CVE (the MITRE CVE database) are returned. vulscan's default report
+/// carries no scores, so `cvss_score` is `None`.
+pub fn parse_vulscan(output: &str) -> Vec<VulnFinding> {
+    let mut findings = Vec::new();
+    for line in output.lines() {
+        let entry = line.trim().strip_prefix('[').and_then(|l| l.split_once(']'));
+        let Some((id, title)) = entry else {
+            continue;
+        };
+        if !is_cve_id(id) {
+            continue;
+        }
+        let title = title.trim();
+        findings.push(VulnFinding {
+            cve_id: id.to_string(),
+            cvss_score: None,
+            title: if title.is_empty() { id.to_string() } else { title.to_string() },
+            risk_factor: None,
+            references: Vec::new(),
+            exploit: false,
+        });
+    }
+    findings
+}
+
+/// One `VULNERABLE:` block being accumulated by [`parse_vuln_report`].
+#[derive(Default)]
+struct VulnBlock {
+    title: Option<String>,
+    state: String,
+    cve_ids: Vec<String>,
+    cvss_score: Option<f64>,
+    risk_factor: Option<String>,
+    references: Vec<String>,
+}
+
+impl VulnBlock {
+    fn finish(self, findings: &mut Vec<VulnFinding>) {
+        let state = self.state.to_ascii_uppercase();
+        if !state.contains("VULNERABLE") || state.starts_with("NOT VULNERABLE") {
+            return;
+        }
+        let exploit = state.contains("EXPLOITABLE");
+        for cve_id in self.cve_ids {
+            findings.push(VulnFinding {
+                title: self.title.clone().unwrap_or_else(|| cve_id.clone()),
+                cve_id,
+                cvss_score: self.cvss_score,
+                risk_factor: self.risk_factor.clone(),
+                references: self.references.clone(),
+                exploit,
+            });
+        }
+    }
+}
+
+/// Parse the first number following `label` in `line`, e.g. `CVSSv2: 7.5`.
+fn score_after(line: &str, label: &str) -> Option<f64> {
+    let idx = line.find(label)?;
+    line[idx + label.len()..]
+        .split_whitespace()
+        .next()
+        .and_then(|s| s.parse().ok())
+}
+
+/// Parse the standard NSE `vuln` report format used by `smb-vuln-*`,
+/// `http-vuln-*`, `ssl-heartbleed` and friends.
+///
+/// Blocks start with `VULNERABLE:` followed by a title line, then `State:`,
+/// `IDs:` (`CVE:CVE-...` entries), an optional `Risk factor:` with CVSS, and
+/// a `References:` list. Blocks whose state is not vulnerable are dropped;
+/// a block with several CVEs yields one finding per CVE.
+pub fn parse_vuln_report(output: &str) -> Vec<VulnFinding> {
+    let mut findings = Vec::new();
+    let mut block: Option<VulnBlock> = None;
+    let mut in_references = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed == "VULNERABLE:" {
+            if let Some(done) = block.take() {
+                done.finish(&mut findings);
+            }
+            block = Some(VulnBlock::default());
+            in_references = false;
+            continue;
+        }
+        let Some(current) = block.as_mut() else {
+            continue;
+        };
+
+        if let Some(state) = trimmed.strip_prefix("State:") {
+            current.state = state.trim().to_string();
+            in_references = false;
+        } else if let Some(ids) = trimmed.strip_prefix("IDs:") {
+            current.cve_ids.extend(
+                ids.split_whitespace()
+                    .filter_map(|id| id.strip_prefix("CVE:"))
+                    .filter(|id| is_cve_id(id))
+                    .map(String::from),
+            );
+            in_references = false;
+        } else if let Some(risk) = trimmed.strip_prefix("Risk factor:") {
+            current.risk_factor = risk.split_whitespace().next().map(|r| r.to_ascii_lowercase());
+            current.cvss_score = score_after(risk, "CVSSv3:")
+                .or_else(|| score_after(risk, "CVSSv2:"))
+                .or_else(|| score_after(risk, "CVSS:"));
+            in_references = false;
+        } else if trimmed.starts_with("References:") {
+            in_references = true;
+        } else if in_references && (trimmed.starts_with("http://") || trimmed.starts_with("https://")) {
+            current.references.push(trimmed.to_string());
+        } else if current.title.is_none() && current.state.is_empty() {
+            current.title = Some(trimmed.to_string());
+        } else {
+            in_references = false;
+        }
+    }
+    if let Some(done) = block {
+        done.finish(&mut findings);
+    }
+    findings
+}
+
+/// Parse a script's output according to its id.
+pub fn parse_vuln_script(script_id: &str, output: &str) -> Vec<VulnFinding> {
+    match script_id {
+        "vulners" => parse_vulners(output),
+        "vulscan" => parse_vulscan(output),
+        _ => parse_vuln_report(output),
+    }
+}
+
+/// Merge findings for the same CVE, keeping the highest score and the union
+/// of references. Output is sorted by CVE id.
+fn merge_findings(findings: Vec<VulnFinding>) -> Vec<VulnFinding> {
+    let mut by_cve: BTreeMap<String, VulnFinding> = BTreeMap::new();
+    for finding in findings {
+        match by_cve.get_mut(&finding.cve_id) {
+            Some(existing) => {
+                existing.cvss_score = match (existing.cvss_score, finding.cvss_score) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+                existing.exploit |= finding.exploit;
+                if existing.title == existing.cve_id {
+                    existing.title = finding.title;
+                }
+                if existing.risk_factor.is_none() {
+                    existing.risk_factor = finding.risk_factor;
+                }
+                for reference in finding.references {
+                    if !existing.references.contains(&reference) {
+                        existing.references.push(reference);
+                    }
+                }
+            }
+            None => {
+                by_cve.insert(finding.cve_id.clone(), finding);
+            }
+        }
+    }
+    by_cve.into_values().collect()
+}
+
+/// Build `Vulnerability` rows for every CVE reported on a port.
+///
+/// All scripts on the port are considered; the same CVE reported by several
+/// scripts produces a single row.
+pub fn port_vulnerabilities(ip: &str, port: &NmapPort) -> Vec<Vulnerability> {
+    let findings = port
+        .scripts
+        .iter()
+        .flat_map(|(id, output)| parse_vuln_script(id, output))
+        .collect();
+
+    merge_findings(findings)
+        .into_iter()
+        .map(|finding| {
+            let severity = match (finding.cvss_score, finding.risk_factor.as_deref()) {
+                (Some(score), _) => Vulnerability::severity_from_cvss(score),
+                (None, Some(risk)) => Severity::from_str_lossy(risk),
+                (None, None) => Severity::Info,
+            };
+            let mut vuln = Vulnerability::new(finding.title, "nmap".to_string(), severity);
+            vuln.description = if finding.exploit {
+                format!("{} on {}/{} (public exploit available)", finding.cve_id, port.port, port.protocol)
+            } else {
+                format!("{} on {}/{}", finding.cve_id, port.port, port.protocol)
+            };
+            vuln.cve_id = Some(finding.cve_id);
+            vuln.cvss_score = finding.cvss_score;
+            vuln.device_ip = Some(ip.to_string());
+            vuln.port = Some(i64::from(port.port));
+            vuln.service = port.service.get("name").cloned();
+            if !finding.references.is_empty() {
+                vuln.references_json = serde_json::to_string(&finding.references).ok();
+            }
+            vuln
+        })
+        .collect()
+}
+
+/// Build `Vulnerability` rows for every port on a host.
+///
+/// Returns nothing for hosts without an IPv4/IPv6 address.
+pub fn host_vulnerabilities(host: &NmapHost) -> Vec<Vulnerability> {
+    let Some(ip) = host
+        .addresses
+        .get("ipv4")
+        .or_else(|| host.addresses.get("ipv6"))
+    else {
+        return Vec::new();
+    };
+    host.ports
+        .iter()
+        .flat_map(|port| port_vulnerabilities(ip, port))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const VULNERS_OUTPUT: &str = "
+  cpe:/a:openbsd:openssh:7.4:
+    \tCVE-2023-38408\t9.8\thttps://vulners.com/cve/CVE-2023-38408
+    \tEDB-ID:40136\t4.3\thttps://vulners.com/exploitdb/EDB-ID:40136\t*EXPLOIT*
+    \tCVE-2016-10012\t7.8\thttps://vulners.com/cve/CVE-2016-10012\t*EXPLOIT*
+    \tCVE-2020-15778\t6.8\thttps://vulners.com/cve/CVE-2020-15778";
+
+    const MS17_010_OUTPUT: &str = "
+  VULNERABLE:
+  Remote Code Execution vulnerability in Microsoft SMBv1 servers (ms17-010)
+    State: VULNERABLE
+    IDs:  CVE:CVE-2017-0143
+    Risk factor: HIGH
+      A critical remote code execution vulnerability exists in Microsoft SMBv1
+       servers (ms17-010).
+    Disclosure date: 2017-03-14
+    References:
+      https://technet.microsoft.com/en-us/library/security/ms17-010.aspx
+      https://cve.mitre.org/cgi-bin/cvename.cgi?name=CVE-2017-0143";
+
+    const MULTI_BLOCK_OUTPUT: &str = "
+  VULNERABLE:
+  Apache byterange filter DoS
+    State: VULNERABLE
+    IDs:  BID:49303  CVE:CVE-2011-3192
+    Risk factor: High  CVSSv2: 7.8 (HIGH) (AV:N/AC:L/Au:N/C:N/I:N/A:C)
+    References:
+      https://www.tenable.com/plugins/nessus/55976
+  VULNERABLE:
+  Slowloris DOS attack
+    State: NOT VULNERABLE
+    IDs:  CVE:CVE-2007-6750
+  VULNERABLE:
+  OpenSSL Heartbleed
+    State: VULNERABLE (Exploitable)
+    IDs:  CVE:CVE-2014-0160  CVE:CVE-2014-0346
+    Risk factor: High";
+
+    const VULSCAN_OUTPUT: &str = "VulDB - https://vuldb.com:
+[99118] OpenSSH up to 7.4 sshd privilege escalation
+
+MITRE CVE - https://cve.mitre.org:
+[CVE-2016-10012] The shared memory manager in sshd in OpenSSH before 7.4 lacks a bounds check
+[CVE-2016-10010]
+";
+
+    fn port_with(scripts: &[(&str, &str)]) -> NmapPort {
+        let mut service = HashMap::new();
+        service.insert("name".to_string(), "ssh".to_string());
+        NmapPort {
+            port: 22,
+            protocol: "tcp".to_string(),
+            state: "open".to_string(),
+            service,
+            scripts: scripts
+                .iter()
+                .map(|(id, out)| (id.to_string(), out.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_vulners_extracts_cves_and_scores() {
+        let findings = parse_vulners(VULNERS_OUTPUT);
+        let ids: Vec<&str> = findings.iter().map(|f| f.cve_id.as_str()).collect();
+        assert_eq!(ids, vec!["CVE-2023-38408", "CVE-2016-10012", "CVE-2020-15778"]);
+        assert_eq!(findings[0].cvss_score, Some(9.8));
+        assert_eq!(
+            findings[0].references,
+            vec!["https://vulners.com/cve/CVE-2023-38408".to_string()]
+        );
+        assert!(!findings[0].exploit);
+        assert!(findings[1].exploit);
+    }
+
+    #[test]
+    fn test_parse_vulscan_keeps_cve_entries() {
+        let findings = parse_vulscan(VULSCAN_OUTPUT);
+        let ids: Vec<&str> = findings.iter().map(|f| f.cve_id.as_str()).collect();
+        assert_eq!(ids, vec!["CVE-2016-10012", "CVE-2016-10010"]);
+        assert!(findings[0].title.starts_with("The shared memory manager"));
+        assert_eq!(findings[0].cvss_score, None);
+        // No title: fall back to the id
+        assert_eq!(findings[1].title, "CVE-2016-10010");
+    }
+
+    #[test]
+    fn test_parse_vuln_report_single_block() {
+        let findings = parse_vuln_report(MS17_010_OUTPUT);
+        assert_eq!(findings.len(), 1);
+        let f = &findings[0];
+        assert_eq!(f.cve_id, "CVE-2017-0143");
+        assert_eq!(
+            f.title,
+            "Remote Code Execution vulnerability in Microsoft SMBv1 servers (ms17-010)"
+        );
+        assert_eq!(f.risk_factor.as_deref(), Some("high"));
+        assert_eq!(f.cvss_score, None);
+        assert_eq!(f.references.len(), 2);
+        assert!(f.references[1].ends_with("CVE-2017-0143"));
+    }
+
+    #[test]
+    fn test_parse_vuln_report_multiple_blocks() {
+        let findings = parse_vuln_report(MULTI_BLOCK_OUTPUT);
+        let ids: Vec<&str> = findings.iter().map(|f| f.cve_id.as_str()).collect();
+        // NOT VULNERABLE block is dropped; Heartbleed yields one row per CVE
+        assert_eq!(ids, vec!["CVE-2011-3192", "CVE-2014-0160", "CVE-2014-0346"]);
+        assert_eq!(findings[0].cvss_score, Some(7.8));
+        assert!(!findings[0].exploit);
+        assert!(findings[1].exploit);
+    }
+
+    #[test]
+    fn test_port_vulnerabilities_models() {
+        let port = port_with(&[("vulners", VULNERS_OUTPUT)]);
+        let vulns = port_vulnerabilities("10.0.0.5", &port);
+        assert_eq!(vulns.len(), 3);
+
+        let critical = vulns
+            .iter()
+            .find(|v| v.cve_id.as_deref() == Some("CVE-2023-38408"))
+            .unwrap();
+        assert_eq!(critical.severity, "critical");
+        assert_eq!(critical.cvss_score, Some(9.8));
+        assert_eq!(critical.device_ip.as_deref(), Some("10.0.0.5"));
+        assert_eq!(critical.port, Some(22));
+        assert_eq!(critical.service.as_deref(), Some("ssh"));
+        assert_eq!(critical.source_tool, "nmap");
+        let refs: Vec<String> =
+            serde_json::from_str(critical.references_json.as_deref().unwrap()).unwrap();
+        assert_eq!(refs, vec!["https://vulners.com/cve/CVE-2023-38408".to_string()]);
+
+        let exploitable = vulns
+            .iter()
+            .find(|v| v.cve_id.as_deref() == Some("CVE-2016-10012"))
+            .unwrap();
+        assert_eq!(exploitable.severity, "high");
+        assert!(exploitable.description.contains("public exploit"));
+    }
+
+    #[test]
+    fn test_same_cve_from_two_scripts_merged() {
+        let port = port_with(&[
+            ("vulners", "    \tCVE-2017-0143\t8.1\thttps://vulners.com/cve/CVE-2017-0143"),
+            ("smb-vuln-ms17-010", MS17_010_OUTPUT),
+        ]);
+        let vulns = port_vulnerabilities("10.0.0.5", &port);
+        assert_eq!(vulns.len(), 1);
+        let v = &vulns[0];
+        assert_eq!(v.cvss_score, Some(8.1));
+        assert!(v.title.contains("ms17-010"));
+        let refs: Vec<String> = serde_json::from_str(v.references_json.as_deref().unwrap()).unwrap();
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_non_vuln_scripts_ignored() {
+        let port = port_with(&[("http-title", "Welcome to nginx!"), ("ssh-hostkey", "2048 aa:bb")]);
+        assert!(port_vulnerabilities("10.0.0.5", &port).is_empty());
+    }
+}
@@ -1,10 +1,18 @@
 //! Lightweight flow extraction from packet data.
 //!
 //! Ported from the original netsec-stream crate's pcap_parser module.
-//! Aggregates pre-parsed packet records into network flows.
+//! Aggregates pre-parsed packet records, or raw Ethernet/IP packets, into
+//! bidirectional network flows.
+//!
+//! A flow is keyed by its 5-tuple in either direction: the first packet seen
+//! sets `src`/`dst`, later packets from `src` count as sent and packets from
+//! `dst` as received. ICMP and ICMPv6 have no ports, so the message type and
+//! code take their place (`src_port` = type, `dst_port` = code), with echo
+//! replies keyed under the echo request type so a ping exchange is one flow.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 /// A network flow aggregated from packet records.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,23 +39,42 @@ struct FlowKey {
     protocol: String,
 }
 
-struct FlowStats {
-    bytes_sent: u64,
-    packets_sent: u64,
-    first_seen: String,
-    last_seen: String,
+impl FlowKey {
+    /// The same flow seen from the other end. ICMP type/code don't swap.
+    fn reversed(&self) -> Self {
+        let icmp = is_icmp(&self.protocol);
+        Self {
+            src_ip: self.dst_ip.clone(),
+            dst_ip: self.src_ip.clone(),
+            src_port: if icmp { self.src_port } else { self.dst_port },
+            dst_port: if icmp { self.dst_port } else { self.src_port },
+            protocol: self.protocol.clone(),
+        }
+    }
+}
+
+/// One packet, reduced to what flow aggregation needs.
+struct PacketRecord {
+    key: FlowKey,
+    bytes: u64,
+    timestamp: String,
+}
+
+fn is_icmp(protocol: &str) -> bool {
+    protocol == "icmp" || protocol == "icmpv6"
 }
 
 /// Aggregate packet records (as a JSON array string) into network flows.
+///
+/// For `icmp`/`icmpv6` records, `src_port` and `dst_port` carry the message
+/// type and code.
 pub fn extract_flows(packets_json: &str) -> Vec<Flow> {
     let packets: Vec<serde_json::Value> = match serde_json::from_str(packets_json) {
         Ok(p) => p,
         Err(_) => return Vec::new(),
     };
 
-    let mut flows: HashMap<FlowKey, FlowStats> = HashMap::new();
-
-    for pkt in &packets {
+    let records = packets.iter().map(|pkt| {
         let src_ip = pkt
             .get("src_ip")
             .and_then(|v| v.as_str())
@@ -78,42 +105,199 @@ pub fn extract_flows(packets_json: &str) -> Vec<Flow> {
             .unwrap_or("")
             .to_string();
 
-        let key = FlowKey {
-            src_ip: src_ip.clone(),
-            dst_ip: dst_ip.clone(),
-            src_port,
-            dst_port,
-            protocol: protocol.clone(),
+        let (src_port, dst_port) = if is_icmp(&protocol) {
+            (icmp_flow_type(&protocol, src_port as u8), dst_port)
+        } else {
+            (src_port, dst_port)
         };
+        PacketRecord {
+            key: FlowKey {
+                src_ip,
+                dst_ip,
+                src_port,
+                dst_port,
+                protocol,
+            },
+            bytes,
+            timestamp,
+        }
+    });
 
-        let entry = flows.entry(key).or_insert(FlowStats {
-            bytes_sent: 0,
-            packets_sent: 0,
-            first_seen: timestamp.clone(),
-            last_seen: timestamp.clone(),
-        });
+    aggregate(records)
+}
+
+/// Aggregate raw Ethernet frames, given with their capture timestamps, into
+/// network flows.
+///
+/// IPv4 and IPv6 (including 802.1Q-tagged frames and IPv6 extension
+/// headers) are decoded; other frames are skipped. A packet's byte count is
+/// its IP length.
+pub fn extract_flows_from_frames<'a, I>(frames: I) -> Vec<Flow>
+where
+    I: IntoIterator<Item = (&'a str, &'a [u8])>,
+{
+    aggregate(
+        frames
+            .into_iter()
+            .filter_map(|(timestamp, frame)| decode_ethernet(frame, timestamp)),
+    )
+}
+
+/// Like [`extract_flows_from_frames`], for raw IP packets without a link
+/// layer header (e.g. `LINKTYPE_RAW` captures).
+pub fn extract_flows_from_ip_packets<'a, I>(packets: I) -> Vec<Flow>
+where
+    I: IntoIterator<Item = (&'a str, &'a [u8])>,
+{
+    aggregate(
+        packets
+            .into_iter()
+            .filter_map(|(timestamp, packet)| decode_ip(packet, timestamp)),
+    )
+}
 
-        entry.bytes_sent += bytes;
-        entry.packets_sent += 1;
-        entry.last_seen = timestamp;
+/// Fold packet records into bidirectional flows, in order of first packet.
+fn aggregate(records: impl Iterator<Item = PacketRecord>) -> Vec<Flow> {
+    let mut flows: Vec<Flow> = Vec::new();
+    let mut index: HashMap<FlowKey, usize> = HashMap::new();
+
+    for record in records {
+        let reversed = record.key.reversed();
+        let (i, outbound) = match index.get(&record.key) {
+            Some(&i) => (i, true),
+            None => match index.get(&reversed) {
+                Some(&i) => (i, false),
+                None => {
+                    let key = record.key;
+                    flows.push(Flow {
+                        src_ip: key.src_ip.clone(),
+                        dst_ip: key.dst_ip.clone(),
+                        src_port: key.src_port,
+                        dst_port: key.dst_port,
+                        protocol: key.protocol.clone(),
+                        bytes_sent: 0,
+                        bytes_received: 0,
+                        packets_sent: 0,
+                        packets_received: 0,
+                        first_seen: record.timestamp.clone(),
+                        last_seen: record.timestamp.clone(),
+                    });
+                    index.insert(key, flows.len() - 1);
+                    (flows.len() - 1, true)
+                }
+            },
+        };
+
+        let flow = &mut flows[i];
+        if outbound {
+            flow.bytes_sent += record.bytes;
+            flow.packets_sent += 1;
+        } else {
+            flow.bytes_received += record.bytes;
+            flow.packets_received += 1;
+        }
+        flow.last_seen = record.timestamp;
     }
 
     flows
-        .into_iter()
-        .map(|(key, stats)| Flow {
-            src_ip: key.src_ip,
-            dst_ip: key.dst_ip,
-            src_port: key.src_port,
-            dst_port: key.dst_port,
-            protocol: key.protocol,
-            bytes_sent: stats.bytes_sent,
-            bytes_received: 0,
-            packets_sent: stats.packets_sent,
-            packets_received: 0,
-            first_seen: stats.first_seen,
-            last_seen: stats.last_seen,
-        })
-        .collect()
+}
+
+/// The type an ICMP message is keyed under: echo replies map to the echo
+/// request so both directions of a ping share one flow.
+fn icmp_flow_type(protocol: &str, icmp_type: u8) -> u16 {
+    let icmp_type = match (protocol, icmp_type) {
+        ("icmp", 0) => 8,
+        ("icmpv6", 129) => 128,
+        (_, t) => t,
+    };
+    icmp_type as u16
+}
+
+fn decode_ethernet(frame: &[u8], timestamp: &str) -> Option<PacketRecord> {
+    let mut ethertype = u16::from_be_bytes([*frame.get(12)?, *frame.get(13)?]);
+    let mut offset = 14;
+    // 802.1Q / 802.1ad VLAN tags
+    while ethertype == 0x8100 || ethertype == 0x88a8 {
+        ethertype = u16::from_be_bytes([*frame.get(offset + 2)?, *frame.get(offset + 3)?]);
+        offset += 4;
+    }
+    match ethertype {
+        0x0800 | 0x86dd => decode_ip(frame.get(offset..)?, timestamp),
+        _ => None,
+    }
+}
+
+fn decode_ip(packet: &[u8], timestamp: &str) -> Option<PacketRecord> {
+    let (src_ip, dst_ip, protocol, payload, bytes) = match packet.first()? >> 4 {
+        4 => {
+            let header_len = ((packet[0] & 0x0f) as usize) * 4;
+            let total_len = u16::from_be_bytes([*packet.get(2)?, *packet.get(3)?]) as usize;
+            if header_len < 20 || total_len < header_len {
+                return None;
+            }
+            let src: [u8; 4] = packet.get(12..16)?.try_into().ok()?;
+            let dst: [u8; 4] = packet.get(16..20)?.try_into().ok()?;
+            let end = total_len.min(packet.len());
+            (
+                Ipv4Addr::from(src).to_string(),
+                Ipv4Addr::from(dst).to_string(),
+                packet[9],
+                packet.get(header_len..end)?,
+                total_len as u64,
+            )
+        }
+        6 => {
+            let payload_len = u16::from_be_bytes([*packet.get(4)?, *packet.get(5)?]) as usize;
+            let src: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+            let dst: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+            let mut next_header = packet[6];
+            let mut offset = 40;
+            // Skip hop-by-hop, routing, fragment and destination options headers
+            while matches!(next_header, 0 | 43 | 44 | 60) {
+                let len = if next_header == 44 {
+                    8
+                } else {
+                    (*packet.get(offset + 1)? as usize + 1) * 8
+                };
+                next_header = *packet.get(offset)?;
+                offset += len;
+            }
+            let end = (40 + payload_len).min(packet.len());
+            (
+                Ipv6Addr::from(src).to_string(),
+                Ipv6Addr::from(dst).to_string(),
+                next_header,
+                packet.get(offset..end)?,
+                (40 + payload_len) as u64,
+            )
+        }
+        _ => return None,
+    };
+
+    let port = |at: usize| Some(u16::from_be_bytes([*payload.get(at)?, *payload.get(at + 1)?]));
+    let (protocol, src_port, dst_port) = match protocol {
+        6 => ("tcp".to_string(), port(0)?, port(2)?),
+        17 => ("udp".to_string(), port(0)?, port(2)?),
+        1 | 58 => {
+            let name = if protocol == 1 { "icmp" } else { "icmpv6" };
+            let icmp_type = *payload.first()?;
+            let code = *payload.get(1)?;
+            (name.to_string(), icmp_flow_type(name, icmp_type), code as u16)
+        }
+        other => (other.to_string(), 0, 0),
+    };
+
+    Some(PacketRecord {
+        key: FlowKey {
+            src_ip,
+            dst_ip,
+            src_port,
+            dst_port,
+            protocol,
+        },
+        bytes,
+        timestamp: timestamp.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -160,4 +344,153 @@ mod tests {
         assert_eq!(flows[0].src_port, 54321);
         assert_eq!(flows[0].dst_port, 443);
     }
+
+    #[test]
+    fn test_extract_flows_json_bidirectional() {
+        let json = r#"[
+            {"src_ip":"10.0.0.1","dst_ip":"10.0.0.2","src_port":12345,"dst_port":80,"protocol":"tcp","bytes":100,"timestamp":"t1"},
+            {"src_ip":"10.0.0.2","dst_ip":"10.0.0.1","src_port":80,"dst_port":12345,"protocol":"tcp","bytes":900,"timestamp":"t2"},
+            {"src_ip":"10.0.0.2","dst_ip":"10.0.0.1","src_port":80,"dst_port":12346,"protocol":"tcp","bytes":5,"timestamp":"t3"}
+        ]"#;
+        let flows = extract_flows(json);
+        assert_eq!(flows.len(), 2);
+        assert_eq!((flows[0].bytes_sent, flows[0].bytes_received), (100, 900));
+        assert_eq!((flows[0].packets_sent, flows[0].packets_received), (1, 1));
+        assert_eq!(flows[0].last_seen, "t2");
+        // A different client port is a different flow, initiated by 10.0.0.2
+        assert_eq!(flows[1].src_ip, "10.0.0.2");
+    }
+
+    fn ethernet(ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0u8; 12];
+        frame.extend(ethertype.to_be_bytes());
+        frame.extend(payload);
+        frame
+    }
+
+    fn ipv4(src: [u8; 4], dst: [u8; 4], protocol: u8, payload: &[u8]) -> Vec<u8> {
+        let total = (20 + payload.len()) as u16;
+        let mut packet = vec![0x45, 0];
+        packet.extend(total.to_be_bytes());
+        packet.extend([0, 0, 0, 0, 64, protocol, 0, 0]);
+        packet.extend(src);
+        packet.extend(dst);
+        packet.extend(payload);
+        packet
+    }
+
+    fn ipv6(src: Ipv6Addr, dst: Ipv6Addr, next_header: u8, payload: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0x60, 0, 0, 0];
+        packet.extend((payload.len() as u16).to_be_bytes());
+        packet.extend([next_header, 64]);
+        packet.extend(src.octets());
+        packet.extend(dst.octets());
+        packet.extend(payload);
+        packet
+    }
+
+    /// A minimal TCP header followed by `data` bytes of payload.
+    fn tcp(src_port: u16, dst_port: u16, data: usize) -> Vec<u8> {
+        let mut segment = Vec::new();
+        segment.extend(src_port.to_be_bytes());
+        segment.extend(dst_port.to_be_bytes());
+        segment.extend([0u8; 8]);
+        segment.extend([0x50, 0x18, 0, 0, 0, 0, 0, 0]);
+        segment.resize(20 + data, 0xab);
+        segment
+    }
+
+    fn icmp(icmp_type: u8, code: u8) -> Vec<u8> {
+        vec![icmp_type, code, 0, 0, 0, 1, 0, 1]
+    }
+
+    #[test]
+    fn test_frames_tcp_conversation() {
+        let (client, server) = ([192, 168, 1, 10], [192, 168, 1, 1]);
+        let packets = [
+            ethernet(0x0800, &ipv4(client, server, 6, &tcp(50000, 80, 0))),
+            ethernet(0x0800, &ipv4(server, client, 6, &tcp(80, 50000, 0))),
+            ethernet(0x0800, &ipv4(client, server, 6, &tcp(50000, 80, 0))),
+            ethernet(0x0800, &ipv4(client, server, 6, &tcp(50000, 80, 100))),
+            ethernet(0x0800, &ipv4(server, client, 6, &tcp(80, 50000, 500))),
+            // Not IP: ignored
+            ethernet(0x0806, &[0u8; 28]),
+        ];
+        let timestamps = ["t1", "t2", "t3", "t4", "t5", "t6"];
+        let flows = extract_flows_from_frames(
+            timestamps.iter().copied().zip(packets.iter().map(Vec::as_slice)),
+        );
+
+        assert_eq!(flows.len(), 1);
+        let flow = &flows[0];
+        assert_eq!((flow.src_ip.as_str(), flow.src_port), ("192.168.1.10", 50000));
+        assert_eq!((flow.dst_ip.as_str(), flow.dst_port), ("192.168.1.1", 80));
+        assert_eq!(flow.protocol, "tcp");
+        assert_eq!((flow.packets_sent, flow.packets_received), (3, 2));
+        assert_eq!(flow.bytes_sent, 40 + 40 + 140);
+        assert_eq!(flow.bytes_received, 40 + 540);
+        assert_eq!((flow.first_seen.as_str(), flow.last_seen.as_str()), ("t1", "t5"));
+    }
+
+    #[test]
+    fn test_frames_icmp_echo_exchange() {
+        let (a, b) = ([10, 0, 0, 1], [10, 0, 0, 2]);
+        let packets = [
+            ethernet(0x0800, &ipv4(a, b, 1, &icmp(8, 0))),
+            ethernet(0x0800, &ipv4(b, a, 1, &icmp(0, 0))),
+            ethernet(0x0800, &ipv4(a, b, 1, &icmp(8, 0))),
+            ethernet(0x0800, &ipv4(b, a, 1, &icmp(0, 0))),
+            // Unreachable is a different ICMP "flow"
+            ethernet(0x0800, &ipv4(b, a, 1, &icmp(3, 3))),
+        ];
+        let flows = extract_flows_from_frames(packets.iter().map(|p| ("t", p.as_slice())));
+
+        assert_eq!(flows.len(), 2);
+        let echo = &flows[0];
+        assert_eq!(echo.protocol, "icmp");
+        assert_eq!((echo.src_port, echo.dst_port), (8, 0));
+        assert_eq!((echo.packets_sent, echo.packets_received), (2, 2));
+        assert_eq!((echo.bytes_sent, echo.bytes_received), (56, 56));
+        assert_eq!((flows[1].src_port, flows[1].dst_port), (3, 3));
+        assert_eq!(flows[1].src_ip, "10.0.0.2");
+    }
+
+    #[test]
+    fn test_ipv6_packets() {
+        let a: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let b: Ipv6Addr = "2001:db8::2".parse().unwrap();
+
+        // UDP behind a hop-by-hop options header, in a VLAN-tagged frame
+        let mut hop_by_hop = vec![17, 0, 0, 0, 0, 0, 0, 0];
+        hop_by_hop.extend([0xaa, 0xbb, 0x00, 0x35, 0, 12, 0, 0, 1, 2, 3, 4]);
+        let mut tagged = vec![0u8; 12];
+        tagged.extend([0x81, 0x00, 0x00, 0x0a, 0x86, 0xdd]);
+        tagged.extend(ipv6(a, b, 0, &hop_by_hop));
+        let flows = extract_flows_from_frames([("t1", tagged.as_slice())]);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].protocol, "udp");
+        assert_eq!((flows[0].src_port, flows[0].dst_port), (0xaabb, 53));
+        assert_eq!(flows[0].bytes_sent, 40 + 20);
+
+        // ICMPv6 echo request and reply as raw IP packets
+        let request = ipv6(a, b, 58, &icmp(128, 0));
+        let reply = ipv6(b, a, 58, &icmp(129, 0));
+        let flows = extract_flows_from_ip_packets([
+            ("t1", request.as_slice()),
+            ("t2", reply.as_slice()),
+        ]);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].protocol, "icmpv6");
+        assert_eq!(flows[0].src_ip, "2001:db8::1");
+        assert_eq!((flows[0].src_port, flows[0].dst_port), (128, 0));
+        assert_eq!((flows[0].packets_sent, flows[0].packets_received), (1, 1));
+    }
+
+    #[test]
+    fn test_truncated_packets_are_skipped() {
+        let packet = ipv4([10, 0, 0, 1], [10, 0, 0, 2], 6, &tcp(1, 2, 0));
+        let truncated = [&packet[..10], &packet[..21], &[][..]];
+        let flows = extract_flows_from_ip_packets(truncated.iter().map(|p| ("t", *p)));
+        assert!(flows.is_empty());
+    }
 }
@@ -1,57 +1,214 @@
 //! Zeek tab-separated log file parser.
 //!
 //! Ported from the original netsec-stream crate's zeek_parser module.
+//!
+//! Column layout comes from the log's own header directives (`#separator`,
+//! `#set_separator`, `#unset_field`, `#empty_field`, `#fields`, `#types`),
+//! so reordered or added columns in other Zeek versions parse correctly.
 
 use std::collections::HashMap;
 
 /// A single record from a Zeek log file.
 pub type ZeekRecord = HashMap<String, String>;
 
-/// Parse Zeek tab-separated log data into a list of records.
+/// A single record from a Zeek log file with values typed per `#types`.
+pub type ZeekTypedRecord = HashMap<String, ZeekValue>;
+
+/// A field value from a Zeek log, converted according to its `#types` entry.
 ///
-/// Expects the `#fields` header line to define column names.
-/// Lines starting with `#` (other than `#fields`) are skipped.
-/// The `-` and `(empty)` values are omitted from the output.
-pub fn parse_zeek_log(data: &str) -> Vec<ZeekRecord> {
-    let mut records = Vec::new();
-    let mut headers: Vec<String> = Vec::new();
+/// Values that don't parse as their declared type are kept as `String`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZeekValue {
+    /// The `#unset_field` token (`-` by default).
+    Unset,
+    /// The `#empty_field` token (`(empty)` by default).
+    Empty,
+    /// `string`, `addr`, `subnet`, `enum` and any type without a dedicated variant.
+    String(String),
+    /// `count` and `port`.
+    Count(u64),
+    /// `int`.
+    Int(i64),
+    /// `double`, `time` and `interval`.
+    Double(f64),
+    /// `bool` (`T` / `F`).
+    Bool(bool),
+    /// `set[..]` and `vector[..]`, split on `#set_separator`.
+    Set(Vec<String>),
+}
 
-    for line in data.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
+impl ZeekValue {
+    fn parse(raw: &str, ty: &str, header: &ZeekHeader) -> Self {
+        if raw == header.unset_field {
+            return ZeekValue::Unset;
+        }
+        if raw == header.empty_field {
+            return ZeekValue::Empty;
+        }
+        let fallback = || ZeekValue::String(raw.to_string());
+        match ty {
+            "count" | "port" => raw.parse().map(ZeekValue::Count).unwrap_or_else(|_| fallback()),
+            "int" => raw.parse().map(ZeekValue::Int).unwrap_or_else(|_| fallback()),
+            "double" | "time" | "interval" => {
+                raw.parse().map(ZeekValue::Double).unwrap_or_else(|_| fallback())
+            }
+            "bool" => match raw {
+                "T" => ZeekValue::Bool(true),
+                "F" => ZeekValue::Bool(false),
+                _ => fallback(),
+            },
+            t if t.starts_with("set[") || t.starts_with("vector[") => ZeekValue::Set(
+                raw.split(header.set_separator.as_str())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            _ => fallback(),
+        }
+    }
+}
+
+/// Header directives in effect for the data lines that follow them.
+#[derive(Debug, Clone)]
+struct ZeekHeader {
+    separator: String,
+    set_separator: String,
+    unset_field: String,
+    empty_field: String,
+    fields: Vec<String>,
+    types: Vec<String>,
+}
+
+impl Default for ZeekHeader {
+    fn default() -> Self {
+        Self {
+            separator: "\t".to_string(),
+            set_separator: ",".to_string(),
+            unset_field: "-".to_string(),
+            empty_field: "(empty)".to_string(),
+            fields: Vec::new(),
+            types: Vec::new(),
+        }
+    }
+}
+
+impl ZeekHeader {
+    /// Apply a `#` directive line. Unknown directives (`#path`, `#open`, ...)
+    /// are ignored.
+    fn apply(&mut self, line: &str) {
+        // `#separator` is always space-delimited since it defines the separator.
+        if let Some(value) = line.strip_prefix("#separator ") {
+            self.separator = unescape(value.trim());
+            return;
         }
+        let sep = self.separator.clone();
+        let Some((directive, rest)) = line.split_once(sep.as_str()) else {
+            return;
+        };
+        let split = |s: &str| s.split(sep.as_str()).map(str::to_string).collect();
+        match directive {
+            "#set_separator" => self.set_separator = unescape(rest),
+            "#unset_field" => self.unset_field = rest.to_string(),
+            "#empty_field" => self.empty_field = rest.to_string(),
+            "#fields" => self.fields = split(rest),
+            "#types" => self.types = split(rest),
+            _ => {}
+        }
+    }
+}
 
-        if line.starts_with("#fields") {
-            headers = line
-                .split('\t')
-                .skip(1)
-                .map(|s| s.to_string())
-                .collect();
+/// Decode `\xNN` escapes as used in `#separator \x09`.
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(pos) = rest.find("\\x") {
+        out.push_str(&rest[..pos]);
+        let hex = rest.get(pos + 2..pos + 4);
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(b) => {
+                out.push(char::from(b));
+                rest = &rest[pos + 4..];
+            }
+            None => {
+                out.push_str("\\x");
+                rest = &rest[pos + 2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Walk the log, calling `f` with the active header and the split values of
+/// every data line that follows a `#fields` directive.
+fn for_each_row(data: &str, mut f: impl FnMut(&ZeekHeader, &[&str])) {
+    let mut header = ZeekHeader::default();
+
+    for line in data.lines() {
+        // Only strip line endings: a trailing separator is a real (empty) column.
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
             continue;
         }
 
         if line.starts_with('#') {
+            header.apply(line);
             continue;
         }
 
-        if headers.is_empty() {
+        if header.fields.is_empty() {
             continue;
         }
 
-        let values: Vec<&str> = line.split('\t').collect();
-        let mut record = HashMap::new();
+        let values: Vec<&str> = line.split(header.separator.as_str()).collect();
+        f(&header, &values);
+    }
+}
 
-        for (i, header) in headers.iter().enumerate() {
-            let value = values.get(i).unwrap_or(&"-");
-            if *value != "-" && *value != "(empty)" {
-                record.insert(header.clone(), value.to_string());
+/// Parse Zeek log data into records with values typed per the `#types` line.
+///
+/// Columns are mapped by the `#fields` header and split on the declared
+/// `#separator`. Unset and empty fields are kept as [`ZeekValue::Unset`] and
+/// [`ZeekValue::Empty`]; columns missing from a short line are `Unset`.
+pub fn parse_zeek_typed(data: &str) -> Vec<ZeekTypedRecord> {
+    let mut records = Vec::new();
+    for_each_row(data, |header, values| {
+        let record = header
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let value = match values.get(i) {
+                    Some(raw) => {
+                        let ty = header.types.get(i).map(String::as_str).unwrap_or("string");
+                        ZeekValue::parse(raw, ty, header)
+                    }
+                    None => ZeekValue::Unset,
+                };
+                (name.clone(), value)
+            })
+            .collect();
+        records.push(record);
+    });
+    records
+}
+
+/// Parse Zeek log data into a list of records.
+///
+/// Expects the `#fields` header line to define column names; values are split
+/// on the declared `#separator` (tab by default). Unset and empty values
+/// (`-` and `(empty)` unless the header overrides them) are omitted.
+pub fn parse_zeek_log(data: &str) -> Vec<ZeekRecord> {
+    let mut records = Vec::new();
+    for_each_row(data, |header, values| {
+        let mut record = HashMap::new();
+        for (i, name) in header.fields.iter().enumerate() {
+            let Some(value) = values.get(i) else { continue };
+            if *value != header.unset_field && *value != header.empty_field {
+                record.insert(name.clone(), value.to_string());
             }
         }
-
         records.push(record);
-    }
-
+    });
     records
 }
 
@@ -100,4 +257,78 @@ mod tests {
         // All values are "-", so all should be omitted
         assert!(records[0].is_empty());
     }
+
+    const CONN_LOG_COMMA: &str = "\
+#separator \\x2c
+#set_separator,|
+#empty_field,(empty)
+#unset_field,-
+#path,conn
+#fields,ts,uid,id.orig_h,id.orig_p,id.resp_h,id.resp_p,proto,service,duration,local_orig,conn_state,tunnel_parents
+#types,time,string,addr,port,addr,port,enum,string,interval,bool,string,set[string]
+1705312800.123456,CAbc12,10.0.0.5,52344,93.184.216.34,443,tcp,ssl,1.5,T,SF,(empty)
+1705312801.000000,CDef34,10.0.0.5,52345,10.0.0.9,22,tcp,-,-,F,REJ,tun1|tun2
+";
+
+    #[test]
+    fn test_typed_non_default_separator() {
+        let records = parse_zeek_typed(CONN_LOG_COMMA);
+        assert_eq!(records.len(), 2);
+
+        let r = &records[0];
+        assert_eq!(r["ts"], ZeekValue::Double(1705312800.123456));
+        assert_eq!(r["uid"], ZeekValue::String("CAbc12".into()));
+        assert_eq!(r["id.orig_h"], ZeekValue::String("10.0.0.5".into()));
+        assert_eq!(r["id.resp_p"], ZeekValue::Count(443));
+        assert_eq!(r["proto"], ZeekValue::String("tcp".into()));
+        assert_eq!(r["duration"], ZeekValue::Double(1.5));
+        assert_eq!(r["local_orig"], ZeekValue::Bool(true));
+        assert_eq!(r["tunnel_parents"], ZeekValue::Empty);
+
+        let r = &records[1];
+        assert_eq!(r["conn_state"], ZeekValue::String("REJ".into()));
+        assert_eq!(r["local_orig"], ZeekValue::Bool(false));
+        assert_eq!(
+            r["tunnel_parents"],
+            ZeekValue::Set(vec!["tun1".into(), "tun2".into()])
+        );
+    }
+
+    #[test]
+    fn test_typed_unset_field() {
+        let records = parse_zeek_typed(CONN_LOG_COMMA);
+        assert_eq!(records[1]["service"], ZeekValue::Unset);
+        assert_eq!(records[1]["duration"], ZeekValue::Unset);
+
+        // The string-valued parser omits unset and empty fields
+        let records = parse_zeek_log(CONN_LOG_COMMA);
+        assert!(!records[1].contains_key("service"));
+        assert!(!records[0].contains_key("tunnel_parents"));
+        assert_eq!(records[1].get("id.resp_h").unwrap(), "10.0.0.9");
+    }
+
+    #[test]
+    fn test_column_order_from_header() {
+        let data = "#fields\tid.resp_h\tts\tuid\n10.0.0.2\t1705312800.0\tCk1\n";
+        let records = parse_zeek_log(data);
+        assert_eq!(records[0].get("id.resp_h").unwrap(), "10.0.0.2");
+        assert_eq!(records[0].get("uid").unwrap(), "Ck1");
+    }
+
+    #[test]
+    fn test_custom_unset_token() {
+        let data = "#unset_field\tNONE\n#fields\tts\thost\n#types\ttime\tstring\n1.0\tNONE\n";
+        let typed = parse_zeek_typed(data);
+        assert_eq!(typed[0]["host"], ZeekValue::Unset);
+        assert!(!parse_zeek_log(data)[0].contains_key("host"));
+    }
+
+    #[test]
+    fn test_short_line_and_bad_type() {
+        let data = "#fields\tts\tport\thost\n#types\ttime\tport\tstring\nnot-a-time\t80\n";
+        let records = parse_zeek_typed(data);
+        assert_eq!(records[0]["ts"], ZeekValue::String("not-a-time".into()));
+        assert_eq!(records[0]["port"], ZeekValue::Count(80));
+        assert_eq!(records[0]["host"], ZeekValue::Unset);
+    }
 }
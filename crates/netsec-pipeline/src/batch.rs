@@ -0,0 +1,253 @@
+//! Batch ingestion.
+//!
+//! [`Pipeline::process_batch`] runs many alerts through the same stages as
+//! [`Pipeline::process`], but reads the existing fingerprints and the
+//! correlation window from the database once up front, deduplicates and
+//! correlates against that in-memory view (which also sees earlier alerts of
+//! the same batch), and writes the results back in bulk.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use netsec_db::repo::alerts;
+use netsec_models::alert::{Alert, NormalizedAlert};
+use netsec_models::time::now_rfc3339;
+
+use crate::correlation::{self, Correlation, CorrelationRule};
+use crate::metrics::Stage;
+use crate::{deduplication, dispatch, scoring, Pipeline, PipelineError, PipelineResult};
+
+/// Where a fingerprint was last seen.
+enum Known {
+    /// An alert already in the database.
+    Stored(Box<Alert>),
+    /// A new alert of this batch, by index into the pending inserts.
+    Pending(usize),
+}
+
+/// Per-input result before the batch is written.
+enum Outcome {
+    /// Duplicate of an alert already in the database.
+    Stored(Alert),
+    /// A new alert, or a duplicate of one; only valid once the batch's new
+    /// alerts are persisted.
+    Pending(Alert),
+}
+
+impl Pipeline {
+    /// Process many alerts at once.
+    ///
+    /// Equivalent to calling [`process`](Self::process) on each alert in
+    /// order, including deduplication and correlation against earlier alerts
+    /// of the same batch, but with a fixed number of database round trips:
+    /// one read of existing fingerprints, one read of the correlation window,
+    /// one transaction for count and correlation updates, and one
+    /// [`send_batch`](dispatch::DispatchTarget::send_batch) per dispatch
+    /// target (a single transaction for the database).
+    ///
+    /// Results are in input order. If the authoritative target fails, every
+    /// alert that depends on the batch's inserts gets an error.
+    pub async fn process_batch(&self, alerts: Vec<NormalizedAlert>) -> Vec<PipelineResult<Alert>> {
+        if alerts.is_empty() {
+            return Vec::new();
+        }
+        let total = alerts.len();
+
+        let mut alerts = alerts;
+        for alert in &mut alerts {
+            let strategy = self.config.dedup_strategy(&alert.category);
+            alert.fingerprint = deduplication::fingerprint(alert, strategy);
+        }
+
+        let mut rules: Vec<CorrelationRule> = self.config.correlation_rules.clone();
+        rules.push(CorrelationRule::device_window(self.config.correlation_window_secs));
+        let windows: Vec<String> = rules.iter().map(correlation::window_start).collect();
+
+        let (existing, mut recent) = match self.load_view(&alerts, &windows).await {
+            Ok(view) => view,
+            Err(e) => return batch_failure(total, &e),
+        };
+
+        let mut known: HashMap<String, Known> = HashMap::new();
+        // Newest first, so the first hit per fingerprint is the one
+        // `get_by_fingerprint` would return
+        for alert in existing {
+            known
+                .entry(alert.fingerprint.clone())
+                .or_insert(Known::Stored(Box::new(alert)));
+        }
+
+        let now = now_rfc3339();
+        let mut pending: Vec<Alert> = Vec::new();
+        let mut pending_index: HashMap<String, usize> = HashMap::new();
+        let mut count_deltas: HashMap<String, i64> = HashMap::new();
+        let mut backfills: Vec<(String, String)> = Vec::new();
+        let mut outcomes = Vec::with_capacity(total);
+
+        for normalized in &alerts {
+            // Stage 2: Deduplicate
+            let started = Instant::now();
+            let duplicate = match known.get_mut(&normalized.fingerprint) {
+                Some(Known::Stored(found)) => {
+                    found.count += 1;
+                    found.updated_at = now.clone();
+                    *count_deltas.entry(found.id.clone()).or_insert(0) += 1;
+                    Some(Outcome::Stored(found.as_ref().clone()))
+                }
+                Some(Known::Pending(index)) => {
+                    let found = &mut pending[*index];
+                    found.count += 1;
+                    found.updated_at = now.clone();
+                    Some(Outcome::Pending(found.clone()))
+                }
+                None => None,
+            };
+            self.record(Stage::Dedup, started);
+            if let Some(outcome) = duplicate {
+                self.metrics.lock().unwrap_or_else(|e| e.into_inner()).duplicates_dropped += 1;
+                outcomes.push(outcome);
+                continue;
+            }
+
+            // Stage 3: Correlate
+            let started = Instant::now();
+            let mut correlation_id = None;
+            for (rule, since) in rules.iter().zip(&windows) {
+                match correlation::evaluate(&recent, normalized, rule, since) {
+                    Correlation::None => continue,
+                    Correlation::Existing(cid) => correlation_id = Some(cid),
+                    Correlation::New { cid, backfill } => {
+                        for id in backfill {
+                            if let Some(r) = recent.iter_mut().find(|r| r.id == id) {
+                                r.correlation_id = Some(cid.clone());
+                            }
+                            match pending_index.get(&id) {
+                                Some(&index) => pending[index].correlation_id = Some(cid.clone()),
+                                None => backfills.push((id, cid.clone())),
+                            }
+                        }
+                        correlation_id = Some(cid);
+                    }
+                }
+                break;
+            }
+            self.record(Stage::Correlate, started);
+
+            // Stage 4: Score
+            let started = Instant::now();
+            let base_severity = scoring::score(normalized, &self.config).await;
+            let final_severity =
+                scoring::apply_rules(&self.scoring_rules, normalized, base_severity);
+            self.record(Stage::Score, started);
+
+            let alert = dispatch::build_alert(normalized, final_severity, correlation_id);
+            known.insert(alert.fingerprint.clone(), Known::Pending(pending.len()));
+            pending_index.insert(alert.id.clone(), pending.len());
+            recent.insert(0, alert.clone());
+            outcomes.push(Outcome::Pending(alert.clone()));
+            pending.push(alert);
+        }
+
+        if let Err(e) = self.apply_updates(&count_deltas, &backfills, &now).await {
+            return batch_failure(total, &e);
+        }
+
+        // Stage 5: Dispatch
+        let started = Instant::now();
+        let dispatch_error = self.dispatch_batch(&pending).await;
+        self.record(Stage::Dispatch, started);
+
+        outcomes
+            .into_iter()
+            .map(|outcome| match outcome {
+                Outcome::Stored(alert) => Ok(alert),
+                Outcome::Pending(alert) => match &dispatch_error {
+                    None => Ok(alert),
+                    Some(e) => Err(PipelineError::Batch(e.clone())),
+                },
+            })
+            .collect()
+    }
+
+    /// Read existing alerts for the batch's fingerprints and every alert
+    /// inside the widest correlation window.
+    async fn load_view(
+        &self,
+        alerts: &[NormalizedAlert],
+        windows: &[String],
+    ) -> PipelineResult<(Vec<Alert>, Vec<Alert>)> {
+        let mut fingerprints: Vec<String> = alerts.iter().map(|a| a.fingerprint.clone()).collect();
+        fingerprints.sort();
+        fingerprints.dedup();
+        let existing = alerts::list_by_fingerprints(&self.pool, &fingerprints).await?;
+
+        let oldest = windows.iter().min().cloned().unwrap_or_else(now_rfc3339);
+        let recent = alerts::list_since(&self.pool, &oldest).await?;
+        Ok((existing, recent))
+    }
+
+    /// Write duplicate counts and correlation backfills in one transaction.
+    async fn apply_updates(
+        &self,
+        count_deltas: &HashMap<String, i64>,
+        backfills: &[(String, String)],
+        now: &str,
+    ) -> PipelineResult<()> {
+        if count_deltas.is_empty() && backfills.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        for (id, delta) in count_deltas {
+            sqlx::query("UPDATE alerts SET count = count + $1, updated_at = $2 WHERE id = $3")
+                .bind(delta)
+                .bind(now)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for (id, cid) in backfills {
+            sqlx::query("UPDATE alerts SET correlation_id = $1, updated_at = $2 WHERE id = $3")
+                .bind(cid)
+                .bind(now)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Send the new alerts to every target, authoritative targets first.
+    /// Returns the first authoritative target's error, if any, without
+    /// sending to the others; other failures are only logged.
+    async fn dispatch_batch(&self, alerts: &[Alert]) -> Option<String> {
+        if alerts.is_empty() {
+            return None;
+        }
+        let (authoritative, others): (Vec<_>, Vec<_>) =
+            self.dispatch_targets.iter().partition(|target| target.is_authoritative());
+        for target in authoritative {
+            if let Err(e) = target.send_batch(alerts).await {
+                return Some(format!("{}: {}", target.name(), e));
+            }
+        }
+        for target in others {
+            if let Err(e) = target.send_batch(alerts).await {
+                tracing::warn!(
+                    target_name = target.name(),
+                    alerts = alerts.len(),
+                    error = %e,
+                    "Alert batch dispatch target failed"
+                );
+            }
+        }
+        None
+    }
+}
+
+/// The same error for every alert of a failed batch.
+fn batch_failure(total: usize, error: &PipelineError) -> Vec<PipelineResult<Alert>> {
+    (0..total)
+        .map(|_| Err(PipelineError::Batch(error.to_string())))
+        .collect()
+}
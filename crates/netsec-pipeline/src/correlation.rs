@@ -1,69 +1,209 @@
 //! Alert correlation stage.
 //!
-//! Groups related alerts from the same device within a time window
-//! by assigning them a shared `correlation_id`.
+//! Groups related alerts by assigning them a shared `correlation_id`.
+//! Grouping is driven by declarative [`CorrelationRule`]s; the default rule
+//! groups alerts from the same device within a time window.
 
 use chrono::{Duration, Utc};
 use netsec_db::repo::alerts;
-use netsec_models::alert::NormalizedAlert;
-use sqlx::SqlitePool;
+use netsec_db::DbPool;
+use netsec_models::alert::{Alert, NormalizedAlert};
+use netsec_models::time::{now_rfc3339, to_rfc3339};
 use uuid::Uuid;
 
 use crate::PipelineResult;
 
-/// Determine a correlation_id for the given alert.
+/// An alert attribute that correlation rules can group by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Field {
+    DeviceIp,
+    SourceTool,
+    Category,
+    Title,
+    /// A top-level key of the alert's raw data, e.g. `src_ip` or `sig_id`.
+    RawData(String),
+}
+
+impl Field {
+    fn of_normalized(&self, alert: &NormalizedAlert) -> Option<String> {
+        match self {
+            Self::DeviceIp => alert.device_ip.clone(),
+            Self::SourceTool => Some(alert.source_tool.clone()),
+            Self::Category => Some(alert.category.as_str().to_string()),
+            Self::Title => Some(alert.title.clone()),
+            Self::RawData(key) => alert.raw_data.get(key).and_then(json_key),
+        }
+    }
+
+    fn of_stored(&self, alert: &Alert) -> Option<String> {
+        match self {
+            Self::DeviceIp => alert.device_ip.clone(),
+            Self::SourceTool => Some(alert.source_tool.clone()),
+            Self::Category => Some(alert.category.clone()),
+            Self::Title => Some(alert.title.clone()),
+            Self::RawData(key) => alert
+                .raw_data
+                .as_deref()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+                .and_then(|raw| raw.get(key).and_then(json_key)),
+        }
+    }
+}
+
+/// Group key form of a raw-data value; strings are compared without quotes.
+fn json_key(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Correlate alerts that agree on every `group_by` field once at least
+/// `min_count` of them (including the new one) occur within `window_secs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationRule {
+    pub window_secs: i64,
+    pub group_by: Vec<Field>,
+    pub min_count: usize,
+}
+
+impl CorrelationRule {
+    /// The default rule: every alert from the same device within the window
+    /// shares a correlation id.
+    pub fn device_window(window_secs: i64) -> Self {
+        Self {
+            window_secs,
+            group_by: vec![Field::DeviceIp],
+            min_count: 1,
+        }
+    }
+}
+
+/// Determine a correlation_id for the given alert using the default
+/// device-window rule.
 ///
 /// - If the alert has no `device_ip`, returns `None`.
 /// - Queries recent alerts for the same device within `window_secs`.
 /// - If a recent alert already has a `correlation_id`, reuses it.
 /// - Otherwise, generates a new UUID and backfills existing correlated alerts.
 pub async fn correlate(
-    pool: &SqlitePool,
+    pool: &DbPool,
     alert: &NormalizedAlert,
     window_secs: i64,
 ) -> PipelineResult<Option<String>> {
-    let device_ip = match &alert.device_ip {
-        Some(ip) => ip,
-        None => return Ok(None),
-    };
+    correlate_rule(pool, alert, &CorrelationRule::device_window(window_secs)).await
+}
 
-    let since = (Utc::now() - Duration::seconds(window_secs)).to_rfc3339();
-    let recent = alerts::list_by_device_ip_since(pool, device_ip, &since).await?;
+/// Apply one rule to the given alert.
+///
+/// Returns `None` if the alert lacks a `group_by` field or fewer than
+/// `min_count` alerts (counting this one) share its group in the window.
+/// Otherwise reuses the group's existing `correlation_id`, or generates one
+/// and backfills it onto the group's stored alerts.
+pub async fn correlate_rule(
+    pool: &DbPool,
+    alert: &NormalizedAlert,
+    rule: &CorrelationRule,
+) -> PipelineResult<Option<String>> {
+    let since = window_start(rule);
+    let candidates = match &alert.device_ip {
+        // Narrow the query through the device index when possible
+        Some(ip) if rule.group_by.contains(&Field::DeviceIp) => {
+            alerts::list_by_device_ip_since(pool, ip, &since).await?
+        }
+        _ => alerts::list_since(pool, &since).await?,
+    };
 
-    if recent.is_empty() {
-        // First alert for this device in the window — new correlation group
-        let cid = Uuid::new_v4().to_string();
-        return Ok(Some(cid));
+    match evaluate(&candidates, alert, rule, &since) {
+        Correlation::None => Ok(None),
+        Correlation::Existing(cid) => Ok(Some(cid)),
+        Correlation::New { cid, backfill } => {
+            // Backfill the group's stored alerts
+            let now = now_rfc3339();
+            for id in &backfill {
+                sqlx::query("UPDATE alerts SET correlation_id = $1, updated_at = $2 WHERE id = $3")
+                    .bind(&cid)
+                    .bind(&now)
+                    .bind(id)
+                    .execute(pool)
+                    .await
+                    .map_err(crate::PipelineError::Database)?;
+            }
+            Ok(Some(cid))
+        }
     }
+}
 
-    // Check if any recent alert already has a correlation_id
-    for r in &recent {
-        if let Some(ref cid) = r.correlation_id {
-            return Ok(Some(cid.clone()));
-        }
+/// Earliest `created_at` inside the rule's window, as of now.
+pub(crate) fn window_start(rule: &CorrelationRule) -> String {
+    to_rfc3339(&(Utc::now() - Duration::seconds(rule.window_secs)))
+}
+
+/// What a rule decides for one alert.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Correlation {
+    /// The rule does not apply.
+    None,
+    /// Join the group's existing correlation id.
+    Existing(String),
+    /// Start a correlation group; `backfill` lists stored alert ids that
+    /// must be given `cid`.
+    New { cid: String, backfill: Vec<String> },
+}
+
+/// Decide the correlation of `alert` against already stored alerts.
+///
+/// `stored` may contain alerts outside the rule's group or window; only those
+/// created at or after `since` and agreeing on every `group_by` field count.
+pub(crate) fn evaluate(
+    stored: &[Alert],
+    alert: &NormalizedAlert,
+    rule: &CorrelationRule,
+    since: &str,
+) -> Correlation {
+    let Some(key) = rule
+        .group_by
+        .iter()
+        .map(|field| field.of_normalized(alert))
+        .collect::<Option<Vec<String>>>()
+    else {
+        return Correlation::None;
+    };
+
+    let recent: Vec<&Alert> = stored
+        .iter()
+        .filter(|r| r.created_at.as_str() >= since)
+        .filter(|r| {
+            rule.group_by
+                .iter()
+                .zip(&key)
+                .all(|(field, value)| field.of_stored(r).as_ref() == Some(value))
+        })
+        .collect();
+
+    if recent.len() + 1 < rule.min_count {
+        return Correlation::None;
     }
 
-    // No existing correlation_id — generate one and backfill
-    let cid = Uuid::new_v4().to_string();
-    let now = Utc::now().to_rfc3339();
-    for r in &recent {
-        sqlx::query("UPDATE alerts SET correlation_id = ?, updated_at = ? WHERE id = ?")
-            .bind(&cid)
-            .bind(&now)
-            .bind(&r.id)
-            .execute(pool)
-            .await
-            .map_err(crate::PipelineError::Database)?;
+    // Reuse the group's correlation_id if any alert already has one
+    if let Some(cid) = recent.iter().find_map(|r| r.correlation_id.clone()) {
+        return Correlation::Existing(cid);
     }
 
-    Ok(Some(cid))
+    // First alert for this group in the window, or a group not yet
+    // correlated — new correlation id
+    Correlation::New {
+        cid: Uuid::new_v4().to_string(),
+        backfill: recent.iter().map(|r| r.id.clone()).collect(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use netsec_db::{pool::create_test_pool, run_migrations};
     use netsec_db::repo::alerts as alert_repo;
+    use netsec_db::{pool::create_test_pool, run_migrations};
     use netsec_models::alert::{Alert, AlertCategory, Severity};
 
     fn make_normalized(device_ip: Option<&str>, fingerprint: &str) -> NormalizedAlert {
@@ -150,7 +290,7 @@ mod tests {
         a1.device_ip = Some("10.0.0.20".to_string());
         a1.correlation_id = Some("old-cid".to_string());
         // Set created_at to 10 minutes ago (outside 5-min window)
-        let old_time = (Utc::now() - Duration::seconds(600)).to_rfc3339();
+        let old_time = to_rfc3339(&(Utc::now() - Duration::seconds(600)));
         a1.created_at = old_time.clone();
         a1.updated_at = old_time;
         alert_repo::insert(&pool, &a1).await.unwrap();
@@ -161,4 +301,76 @@ mod tests {
         assert!(cid.is_some());
         assert_ne!(cid, Some("old-cid".to_string()));
     }
+
+    /// "3+ failed logins from the same source IP within 60s".
+    fn failed_login_rule() -> CorrelationRule {
+        CorrelationRule {
+            window_secs: 60,
+            group_by: vec![Field::Title, Field::RawData("src_ip".to_string())],
+            min_count: 3,
+        }
+    }
+
+    async fn insert_failed_login(pool: &DbPool, src_ip: &str, fingerprint: &str) -> Alert {
+        let mut alert = Alert::new("Failed login".into(), "test".into(), fingerprint.into());
+        alert.device_ip = Some("10.0.0.100".to_string());
+        alert.correlation_id = None;
+        alert.raw_data = Some(serde_json::json!({"src_ip": src_ip}).to_string());
+        alert_repo::insert(pool, &alert).await.unwrap();
+        alert
+    }
+
+    fn failed_login(src_ip: &str) -> NormalizedAlert {
+        NormalizedAlert {
+            title: "Failed login".to_string(),
+            raw_data: serde_json::json!({"src_ip": src_ip}),
+            ..make_normalized(Some("10.0.0.100"), "fp-login-new")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rule_threshold_correlates_group() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let rule = failed_login_rule();
+
+        let first = insert_failed_login(&pool, "203.0.113.7", "fp-login-1").await;
+        // Different source: not part of the group
+        let other = insert_failed_login(&pool, "198.51.100.1", "fp-login-x").await;
+
+        // Two attempts so far: below the threshold
+        let cid = correlate_rule(&pool, &failed_login("203.0.113.7"), &rule).await.unwrap();
+        assert!(cid.is_none());
+
+        let second = insert_failed_login(&pool, "203.0.113.7", "fp-login-2").await;
+
+        // The third attempt meets the threshold and backfills the group
+        let cid = correlate_rule(&pool, &failed_login("203.0.113.7"), &rule)
+            .await
+            .unwrap()
+            .expect("threshold met");
+        for id in [&first.id, &second.id] {
+            let stored = alert_repo::get_by_id(&pool, id).await.unwrap().unwrap();
+            assert_eq!(stored.correlation_id.as_deref(), Some(cid.as_str()));
+        }
+        let stored = alert_repo::get_by_id(&pool, &other.id).await.unwrap().unwrap();
+        assert!(stored.correlation_id.is_none());
+
+        // Further attempts join the same incident
+        let again = correlate_rule(&pool, &failed_login("203.0.113.7"), &rule).await.unwrap();
+        assert_eq!(again, Some(cid));
+    }
+
+    #[tokio::test]
+    async fn test_rule_missing_group_field_does_not_apply() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let rule = CorrelationRule {
+            min_count: 1,
+            ..failed_login_rule()
+        };
+        let na = make_normalized(Some("10.0.0.100"), "fp-no-src");
+        assert!(correlate_rule(&pool, &na, &rule).await.unwrap().is_none());
+    }
 }
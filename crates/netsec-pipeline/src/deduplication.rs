@@ -2,11 +2,14 @@
 //!
 //! Checks whether a normalized alert already exists in the database by fingerprint.
 //! If it does, increments the existing alert's count and returns it as a duplicate.
+//!
+//! The fingerprint is chosen by a per-category [`DedupStrategy`]: port-scan
+//! style alerts can collapse per device, IDS alerts per signature.
 
-use chrono::Utc;
 use netsec_db::repo::alerts;
+use netsec_db::DbPool;
 use netsec_models::alert::{Alert, NormalizedAlert};
-use sqlx::SqlitePool;
+use netsec_models::time::now_rfc3339;
 
 use crate::PipelineResult;
 
@@ -18,18 +21,52 @@ pub enum DeduplicationResult {
     New,
 }
 
-/// Check the database for an existing alert with the same fingerprint.
+/// How the deduplication fingerprint of an alert is built.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DedupStrategy {
+    /// The fingerprint computed by the normalizer.
+    #[default]
+    Normalized,
+    /// One alert per `(device_ip, category)`.
+    DeviceCategory,
+    /// One alert per IDS signature (`sig_id` in the raw data). Falls back to
+    /// the normalizer's fingerprint when the alert carries no signature.
+    Signature,
+}
+
+/// Compute the deduplication fingerprint of `normalized` under `strategy`.
+pub fn fingerprint(normalized: &NormalizedAlert, strategy: &DedupStrategy) -> String {
+    match strategy {
+        DedupStrategy::Normalized => normalized.fingerprint.clone(),
+        DedupStrategy::DeviceCategory => format!(
+            "device_category:{}:{}",
+            normalized.category.as_str(),
+            normalized.device_ip.as_deref().unwrap_or_default()
+        ),
+        DedupStrategy::Signature => {
+            match normalized.raw_data.get("sig_id").and_then(|v| v.as_u64()) {
+                Some(sig_id) => format!("signature:{}:{}", normalized.source_tool, sig_id),
+                None => normalized.fingerprint.clone(),
+            }
+        }
+    }
+}
+
+/// Check the database for an existing alert with the same fingerprint under
+/// `strategy`.
 ///
 /// If found, increment its count and return `Duplicate`. Otherwise return `New`.
 pub async fn deduplicate(
-    pool: &SqlitePool,
+    pool: &DbPool,
     alert: &NormalizedAlert,
+    strategy: &DedupStrategy,
 ) -> PipelineResult<DeduplicationResult> {
-    let existing = alerts::get_by_fingerprint(pool, &alert.fingerprint).await?;
+    let fingerprint = fingerprint(alert, strategy);
+    let existing = alerts::get_by_fingerprint(pool, &fingerprint).await?;
 
     match existing {
         Some(mut found) => {
-            let now = Utc::now().to_rfc3339();
+            let now = now_rfc3339();
             alerts::increment_count(pool, &found.id, &now).await?;
             found.count += 1;
             found.updated_at = now;
@@ -42,6 +79,7 @@ pub async fn deduplicate(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
     use netsec_db::{pool::create_test_pool, run_migrations};
     use netsec_models::alert::{AlertCategory, Severity};
 
@@ -65,7 +103,7 @@ mod tests {
         run_migrations(&pool).await.unwrap();
 
         let normalized = make_normalized("fp-unique-1");
-        let result = deduplicate(&pool, &normalized).await.unwrap();
+        let result = deduplicate(&pool, &normalized, &DedupStrategy::Normalized).await.unwrap();
         assert!(matches!(result, DeduplicationResult::New));
     }
 
@@ -81,7 +119,7 @@ mod tests {
 
         // Deduplicate should find it
         let normalized = make_normalized("fp-dup-1");
-        let result = deduplicate(&pool, &normalized).await.unwrap();
+        let result = deduplicate(&pool, &normalized, &DedupStrategy::Normalized).await.unwrap();
         match result {
             DeduplicationResult::Duplicate(a) => assert_eq!(a.count, 2),
             _ => panic!("Expected duplicate"),
@@ -95,8 +133,8 @@ mod tests {
 
         let n1 = make_normalized("fp-a");
         let n2 = make_normalized("fp-b");
-        let r1 = deduplicate(&pool, &n1).await.unwrap();
-        let r2 = deduplicate(&pool, &n2).await.unwrap();
+        let r1 = deduplicate(&pool, &n1, &DedupStrategy::Normalized).await.unwrap();
+        let r2 = deduplicate(&pool, &n2, &DedupStrategy::Normalized).await.unwrap();
         assert!(matches!(r1, DeduplicationResult::New));
         assert!(matches!(r2, DeduplicationResult::New));
     }
@@ -113,24 +151,103 @@ mod tests {
         let normalized = make_normalized("fp-triple");
 
         // First dedup -> count 2
-        let r1 = deduplicate(&pool, &normalized).await.unwrap();
+        let r1 = deduplicate(&pool, &normalized, &DedupStrategy::Normalized).await.unwrap();
         match &r1 {
             DeduplicationResult::Duplicate(a) => assert_eq!(a.count, 2),
             _ => panic!("Expected duplicate"),
         }
 
         // Second dedup -> count 3
-        let r2 = deduplicate(&pool, &normalized).await.unwrap();
+        let r2 = deduplicate(&pool, &normalized, &DedupStrategy::Normalized).await.unwrap();
         match &r2 {
             DeduplicationResult::Duplicate(a) => assert_eq!(a.count, 3),
             _ => panic!("Expected duplicate"),
         }
 
         // Third dedup -> count 4
-        let r3 = deduplicate(&pool, &normalized).await.unwrap();
+        let r3 = deduplicate(&pool, &normalized, &DedupStrategy::Normalized).await.unwrap();
         match r3 {
             DeduplicationResult::Duplicate(a) => assert_eq!(a.count, 4),
             _ => panic!("Expected duplicate"),
         }
     }
+
+    fn ids_alert(sig_id: u64, src_ip: &str, dest_ip: &str) -> NormalizedAlert {
+        NormalizedAlert {
+            source_tool: "suricata".to_string(),
+            category: AlertCategory::Intrusion,
+            device_ip: Some(src_ip.to_string()),
+            raw_data: serde_json::json!({"sig_id": sig_id, "src_ip": src_ip, "dest_ip": dest_ip}),
+            ..make_normalized(&format!("suricata:{sig_id}:{src_ip}:{dest_ip}"))
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_strategies() {
+        let a = ids_alert(2001, "10.0.0.1", "10.0.0.9");
+        let b = ids_alert(2001, "10.0.0.2", "10.0.0.9");
+        let c = ids_alert(2002, "10.0.0.1", "10.0.0.9");
+
+        // Normalized keeps the per-flow fingerprint
+        assert_ne!(
+            fingerprint(&a, &DedupStrategy::Normalized),
+            fingerprint(&b, &DedupStrategy::Normalized)
+        );
+
+        // Signature collapses across hosts, not across signatures
+        assert_eq!(
+            fingerprint(&a, &DedupStrategy::Signature),
+            fingerprint(&b, &DedupStrategy::Signature)
+        );
+        assert_ne!(
+            fingerprint(&a, &DedupStrategy::Signature),
+            fingerprint(&c, &DedupStrategy::Signature)
+        );
+
+        // DeviceCategory collapses per device, whatever the signature
+        assert_eq!(
+            fingerprint(&a, &DedupStrategy::DeviceCategory),
+            fingerprint(&c, &DedupStrategy::DeviceCategory)
+        );
+        assert_ne!(
+            fingerprint(&a, &DedupStrategy::DeviceCategory),
+            fingerprint(&b, &DedupStrategy::DeviceCategory)
+        );
+    }
+
+    #[test]
+    fn test_signature_strategy_falls_back_without_sig_id() {
+        let alert = make_normalized("fp-no-sig");
+        assert_eq!(fingerprint(&alert, &DedupStrategy::Signature), "fp-no-sig");
+    }
+
+    #[tokio::test]
+    async fn test_strategy_collapses_matching_fingerprints() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let first = ids_alert(2001, "10.0.0.1", "10.0.0.9");
+        let strategy = DedupStrategy::Signature;
+        let mut stored =
+            Alert::new("ET TEST".into(), "suricata".into(), fingerprint(&first, &strategy));
+        stored.category = "intrusion".to_string();
+        alerts::insert(&pool, &stored).await.unwrap();
+
+        // Same signature from another host collapses
+        let same_sig = ids_alert(2001, "10.0.0.2", "10.0.0.9");
+        let result = deduplicate(&pool, &same_sig, &strategy).await.unwrap();
+        match result {
+            DeduplicationResult::Duplicate(a) => assert_eq!(a.id, stored.id),
+            _ => panic!("Expected duplicate"),
+        }
+
+        // A different signature does not
+        let other_sig = ids_alert(2002, "10.0.0.1", "10.0.0.9");
+        let result = deduplicate(&pool, &other_sig, &strategy).await.unwrap();
+        assert!(matches!(result, DeduplicationResult::New));
+
+        // Nor does the same alert under the per-flow strategy
+        let result = deduplicate(&pool, &same_sig, &DedupStrategy::Normalized).await.unwrap();
+        assert!(matches!(result, DeduplicationResult::New));
+    }
 }
@@ -1,17 +1,19 @@
 //! Alert dispatch stage.
 //!
 //! Builds the final [`Alert`] from a [`NormalizedAlert`] and sends it to
-//! one or more dispatch targets (database, event bus, log).
+//! one or more dispatch targets (database, event bus, log, webhook).
 
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 
-use chrono::Utc;
 use netsec_db::repo::alerts as alert_repo;
+use netsec_db::DbPool;
 use netsec_events::EventBus;
 use netsec_models::alert::{Alert, AlertStatus, NormalizedAlert, Severity};
 use netsec_models::event::{EventType, NetsecEvent};
-use sqlx::SqlitePool;
+use netsec_models::time::now_rfc3339;
 use uuid::Uuid;
 
 use crate::{PipelineError, PipelineResult};
@@ -24,15 +26,58 @@ pub trait DispatchTarget: Send + Sync {
     ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>>;
 
     fn name(&self) -> &str;
+
+    /// Send several alerts. The default sends them one by one, stopping at
+    /// the first failure; targets with a cheaper bulk path override it.
+    fn send_batch<'a>(
+        &'a self,
+        alerts: &'a [Alert],
+    ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+        Box::pin(async move {
+            for alert in alerts {
+                self.send(alert).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Whether a failure of this target fails the whole dispatch. Only the
+    /// store of record should be authoritative; failures of other targets
+    /// are reported in the [`DispatchReport`] instead.
+    fn is_authoritative(&self) -> bool {
+        false
+    }
+}
+
+/// Outcome of dispatching one alert to every target.
+#[derive(Debug)]
+pub struct DispatchReport {
+    pub alert: Alert,
+    /// Target name and result, in target order.
+    pub results: Vec<(String, Result<(), PipelineError>)>,
+}
+
+impl DispatchReport {
+    /// Targets that failed, with their errors.
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &PipelineError)> {
+        self.results
+            .iter()
+            .filter_map(|(name, result)| result.as_ref().err().map(|e| (name.as_str(), e)))
+    }
+
+    /// Whether every target succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
 }
 
 /// Inserts the alert into the SQLite database.
 pub struct DatabaseTarget {
-    pool: SqlitePool,
+    pool: DbPool,
 }
 
 impl DatabaseTarget {
-    pub fn new(pool: SqlitePool) -> Self {
+    pub fn new(pool: DbPool) -> Self {
         Self { pool }
     }
 }
@@ -52,6 +97,22 @@ impl DispatchTarget for DatabaseTarget {
     fn name(&self) -> &str {
         "database"
     }
+
+    /// Inserts the whole batch in a single transaction.
+    fn send_batch<'a>(
+        &'a self,
+        alerts: &'a [Alert],
+    ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+        Box::pin(async move {
+            alert_repo::insert_many(&self.pool, alerts)
+                .await
+                .map_err(PipelineError::Database)
+        })
+    }
+
+    fn is_authoritative(&self) -> bool {
+        true
+    }
 }
 
 /// Publishes an [`NetsecEvent`] with `AlertCreated` type onto the event bus.
@@ -112,17 +173,124 @@ impl DispatchTarget for LogTarget {
     }
 }
 
+/// Default per-request timeout for [`WebhookTarget`].
+pub const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// POSTs the alert as JSON to an external webhook (Slack, generic HTTP
+/// receiver, ...).
+///
+/// A 5xx response is retried once; any other non-success status or a
+/// transport error fails immediately.
+pub struct WebhookTarget {
+    client: reqwest::Client,
+    url: String,
+    bearer_token: Option<String>,
+    timeout: Duration,
+}
+
+impl WebhookTarget {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            bearer_token: None,
+            timeout: DEFAULT_WEBHOOK_TIMEOUT,
+        }
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Set the per-request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn post(&self, alert: &Alert) -> Result<reqwest::StatusCode, PipelineError> {
+        let mut request = self.client.post(&self.url).timeout(self.timeout).json(alert);
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| PipelineError::Dispatch(format!("webhook request failed: {e}")))?;
+        Ok(response.status())
+    }
+}
+
+impl DispatchTarget for WebhookTarget {
+    fn send<'a>(
+        &'a self,
+        alert: &'a Alert,
+    ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut status = self.post(alert).await?;
+            if status.is_server_error() {
+                tracing::debug!(status = status.as_u16(), "Webhook returned 5xx, retrying once");
+                status = self.post(alert).await?;
+            }
+
+            if status.is_success() {
+                Ok(())
+            } else {
+                Err(PipelineError::Dispatch(format!("webhook returned {status}")))
+            }
+        })
+    }
+
+    fn name(&self) -> &str {
+        "webhook"
+    }
+}
+
 /// Build an [`Alert`] from a normalized alert + scoring/correlation results,
 /// then send it to all dispatch targets.
+///
+/// [Authoritative](DispatchTarget::is_authoritative) targets go first. If one
+/// fails this returns `Err` and the other targets are skipped, so nothing is
+/// announced for an alert that wasn't stored. After that every target is
+/// tried even if an earlier one fails, so e.g. a webhook outage cannot block
+/// the rest; those failures are listed in the returned [`DispatchReport`].
 pub async fn dispatch(
     normalized: &NormalizedAlert,
     final_severity: Severity,
     correlation_id: Option<String>,
-    targets: &[Box<dyn DispatchTarget>],
-) -> PipelineResult<Alert> {
-    let now = Utc::now().to_rfc3339();
+    targets: &[Arc<dyn DispatchTarget>],
+) -> PipelineResult<DispatchReport> {
+    let alert = build_alert(normalized, final_severity, correlation_id);
+
+    let (authoritative, others): (Vec<_>, Vec<_>) =
+        targets.iter().enumerate().partition(|(_, target)| target.is_authoritative());
+    let mut results = Vec::with_capacity(targets.len());
+    for (i, target) in authoritative {
+        if let Err(e) = target.send(&alert).await {
+            return Err(PipelineError::Dispatch(format!("{}: {}", target.name(), e)));
+        }
+        results.push((i, target.name().to_string(), Ok(())));
+    }
+    for (i, target) in others {
+        results.push((i, target.name().to_string(), target.send(&alert).await));
+    }
+    results.sort_by_key(|(i, _, _)| *i);
+
+    let results = results.into_iter().map(|(_, name, result)| (name, result)).collect();
+    Ok(DispatchReport { alert, results })
+}
+
+/// Build the [`Alert`] that will be dispatched for a normalized alert.
+pub fn build_alert(
+    normalized: &NormalizedAlert,
+    final_severity: Severity,
+    correlation_id: Option<String>,
+) -> Alert {
+    let now = now_rfc3339();
 
-    let alert = Alert {
+    Alert {
         id: Uuid::new_v4().to_string(),
         severity: final_severity.as_str().to_string(),
         status: AlertStatus::New.as_str().to_string(),
@@ -142,21 +310,14 @@ pub async fn dispatch(
         raw_data: Some(normalized.raw_data.to_string()),
         first_seen: now.clone(),
         last_seen: now,
-    };
-
-    for target in targets {
-        target
-            .send(&alert)
-            .await
-            .map_err(|e| PipelineError::Dispatch(format!("{}: {}", target.name(), e)))?;
+        note_history: Vec::new(),
     }
-
-    Ok(alert)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
     use netsec_db::{pool::create_test_pool, run_migrations};
     use netsec_models::alert::{AlertCategory, Severity};
 
@@ -177,11 +338,12 @@ mod tests {
     #[tokio::test]
     async fn test_correct_field_mapping() {
         let normalized = make_normalized();
-        let targets: Vec<Box<dyn DispatchTarget>> = vec![];
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![];
 
         let alert = dispatch(&normalized, Severity::Critical, Some("cid-1".into()), &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
 
         assert_eq!(alert.severity, "critical");
         assert_eq!(alert.status, "new");
@@ -202,11 +364,12 @@ mod tests {
 
         let normalized = make_normalized();
         let db_target = DatabaseTarget::new(pool.clone());
-        let targets: Vec<Box<dyn DispatchTarget>> = vec![Box::new(db_target)];
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![Arc::new(db_target)];
 
         let alert = dispatch(&normalized, Severity::High, None, &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
 
         // Verify the alert was inserted
         let from_db = alert_repo::get_by_id(&pool, &alert.id).await.unwrap();
@@ -223,11 +386,12 @@ mod tests {
 
         let normalized = make_normalized();
         let bus_target = EventBusTarget::new(bus.clone());
-        let targets: Vec<Box<dyn DispatchTarget>> = vec![Box::new(bus_target)];
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![Arc::new(bus_target)];
 
         let alert = dispatch(&normalized, Severity::High, None, &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
 
         let event = rx.recv().await.unwrap();
         assert_eq!(event.event_type, EventType::AlertCreated);
@@ -244,15 +408,16 @@ mod tests {
         let mut rx = bus.subscribe();
 
         let normalized = make_normalized();
-        let targets: Vec<Box<dyn DispatchTarget>> = vec![
-            Box::new(DatabaseTarget::new(pool.clone())),
-            Box::new(EventBusTarget::new(bus.clone())),
-            Box::new(LogTarget),
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![
+            Arc::new(DatabaseTarget::new(pool.clone())),
+            Arc::new(EventBusTarget::new(bus.clone())),
+            Arc::new(LogTarget),
         ];
 
         let alert = dispatch(&normalized, Severity::High, None, &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
 
         // DB target worked
         let from_db = alert_repo::get_by_id(&pool, &alert.id).await.unwrap();
@@ -263,22 +428,217 @@ mod tests {
         assert_eq!(event.event_type, EventType::AlertCreated);
     }
 
+    /// Dispatch through a lone webhook target, returning its result.
+    async fn dispatch_to_webhook(target: WebhookTarget) -> PipelineResult<Alert> {
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![Arc::new(target)];
+        let report = dispatch(&make_normalized(), Severity::High, None, &targets)
+            .await
+            .unwrap();
+        let (name, result) = report.results.into_iter().next().unwrap();
+        assert_eq!(name, "webhook");
+        result.map(|()| report.alert)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_alert_json_with_bearer_token() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/alerts"))
+            .and(header("authorization", "Bearer s3cret"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let target = WebhookTarget::new(format!("{}/hooks/alerts", server.uri()))
+            .with_bearer_token("s3cret");
+        let alert = dispatch_to_webhook(target).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: Alert = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body.id, alert.id);
+        assert_eq!(body.title, "ET SCAN Test");
+        assert_eq!(body.severity, "high");
+        assert_eq!(body.fingerprint, "dispatch-fp-1");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_once_on_server_error() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(dispatch_to_webhook(WebhookTarget::new(server.uri())).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_failures_are_dispatch_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // Persistent 5xx: one retry, then give up
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let err = dispatch_to_webhook(WebhookTarget::new(server.uri())).await.unwrap_err();
+        assert!(matches!(err, PipelineError::Dispatch(_)));
+        assert!(err.to_string().contains("webhook"));
+
+        // 4xx is not retried
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let err = dispatch_to_webhook(WebhookTarget::new(server.uri())).await.unwrap_err();
+        assert!(matches!(err, PipelineError::Dispatch(_)));
+
+        // Timeout
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+        let target = WebhookTarget::new(server.uri()).with_timeout(Duration::from_millis(100));
+        let err = dispatch_to_webhook(target).await.unwrap_err();
+        assert!(matches!(err, PipelineError::Dispatch(_)));
+    }
+
+    /// A target that always fails.
+    struct FailingTarget {
+        authoritative: bool,
+    }
+
+    impl DispatchTarget for FailingTarget {
+        fn send<'a>(
+            &'a self,
+            _alert: &'a Alert,
+        ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+            Box::pin(async { Err(PipelineError::Dispatch("unreachable".to_string())) })
+        }
+
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn is_authoritative(&self) -> bool {
+            self.authoritative
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_target_does_not_block_persistence() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let normalized = make_normalized();
+        // The failing target runs first; later targets must still fire
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![
+            Arc::new(FailingTarget { authoritative: false }),
+            Arc::new(DatabaseTarget::new(pool.clone())),
+        ];
+
+        let report = dispatch(&normalized, Severity::High, None, &targets)
+            .await
+            .unwrap();
+
+        assert!(!report.is_complete());
+        let failures: Vec<&str> = report.failures().map(|(name, _)| name).collect();
+        assert_eq!(failures, vec!["failing"]);
+        assert_eq!(report.results[1].0, "database");
+        assert!(report.results[1].1.is_ok());
+
+        let from_db = alert_repo::get_by_id(&pool, &report.alert.id).await.unwrap();
+        assert!(from_db.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_authoritative_failure_fails_dispatch() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        let normalized = make_normalized();
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![
+            Arc::new(FailingTarget { authoritative: true }),
+            Arc::new(EventBusTarget::new(bus.clone())),
+        ];
+
+        let err = dispatch(&normalized, Severity::High, None, &targets)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("failing"));
+
+        // Nothing is announced for an alert that wasn't stored
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_authoritative_targets_run_first() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        // Listed before the database, but only sent once the insert succeeded
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![
+            Arc::new(EventBusTarget::new(bus.clone())),
+            Arc::new(DatabaseTarget::new(pool.clone())),
+        ];
+        let report = dispatch(&make_normalized(), Severity::High, None, &targets)
+            .await
+            .unwrap();
+        let names: Vec<&str> = report.results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["event_bus", "database"]);
+        assert!(report.is_complete());
+        assert_eq!(rx.recv().await.unwrap().event_type, EventType::AlertCreated);
+
+        // A failed store skips the bus even when it is listed first
+        let failing: Vec<Arc<dyn DispatchTarget>> = vec![
+            Arc::new(EventBusTarget::new(bus.clone())),
+            Arc::new(FailingTarget { authoritative: true }),
+        ];
+        assert!(dispatch(&make_normalized(), Severity::High, None, &failing).await.is_err());
+        assert!(rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn test_log_target_for_high_severity() {
         // LogTarget should not error for any severity
         let normalized = make_normalized();
-        let targets: Vec<Box<dyn DispatchTarget>> = vec![Box::new(LogTarget)];
+        let targets: Vec<Arc<dyn DispatchTarget>> = vec![Arc::new(LogTarget)];
 
         // High severity — should log (we just verify no error)
         let alert = dispatch(&normalized, Severity::High, None, &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
         assert_eq!(alert.severity, "high");
 
         // Critical severity — should also log
         let alert2 = dispatch(&normalized, Severity::Critical, None, &targets)
             .await
-            .unwrap();
+            .unwrap()
+            .alert;
         assert_eq!(alert2.severity, "critical");
     }
 }
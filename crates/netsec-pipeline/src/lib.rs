@@ -5,12 +5,24 @@ pub mod deduplication;
 pub mod correlation;
 pub mod scoring;
 pub mod dispatch;
+pub mod metrics;
+pub mod suppression;
+mod batch;
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use netsec_db::repo::alerts;
+use netsec_db::DbPool;
 use netsec_events::EventBus;
-use netsec_models::alert::{Alert, NormalizedAlert};
-use sqlx::SqlitePool;
+use netsec_models::alert::{Alert, AlertCategory, NormalizedAlert};
+use netsec_models::time::now_rfc3339;
 use thiserror::Error;
 
+use metrics::{PipelineMetrics, Stage};
+use normalization::ParserOutput;
+
 #[derive(Debug, Error)]
 pub enum PipelineError {
     #[error("normalization error: {0}")]
@@ -25,11 +37,14 @@ pub enum PipelineError {
     EventBus(String),
     #[error("validation error: {0}")]
     Validation(String),
+    #[error("batch error: {0}")]
+    Batch(String),
 }
 
 pub type PipelineResult<T> = Result<T, PipelineError>;
 
 /// Configuration for the alert processing pipeline.
+#[derive(Clone)]
 pub struct PipelineConfig {
     /// Window in seconds for correlating alerts from the same device.
     pub correlation_window_secs: i64,
@@ -37,6 +52,17 @@ pub struct PipelineConfig {
     pub critical_ports: Vec<u16>,
     /// Threshold for high-count deduplication (reserved for future use).
     pub high_count_threshold: i64,
+    /// Deduplication strategy per alert category. Categories not listed use
+    /// [`DedupStrategy::Normalized`](deduplication::DedupStrategy::Normalized).
+    pub dedup_strategies: HashMap<AlertCategory, deduplication::DedupStrategy>,
+    /// Correlation rules tried in order; the first that yields a correlation
+    /// id wins. Alerts no rule correlates fall back to the device-window rule
+    /// built from `correlation_window_secs`.
+    pub correlation_rules: Vec<correlation::CorrelationRule>,
+    /// Rate limit on alerts sharing a dedup fingerprint. Alerts over the
+    /// limit only bump the count of the burst's first alert. `None` disables
+    /// suppression.
+    pub suppression: Option<suppression::SuppressionWindow>,
 }
 
 impl PipelineConfig {
@@ -45,6 +71,10 @@ impl PipelineConfig {
     /// - `correlation_window_secs` must be 0..=86400.
     /// - `critical_ports` must have at most 100 entries.
     /// - `high_count_threshold` must be >= 1.
+    /// - Each correlation rule needs a window of 0..=86400, at least one
+    ///   `group_by` field and `min_count >= 1`.
+    /// - A suppression window needs `max_alerts >= 1` and `window_secs` of
+    ///   1..=86400.
     pub fn validate(&self) -> PipelineResult<()> {
         if self.correlation_window_secs < 0 || self.correlation_window_secs > 86400 {
             return Err(PipelineError::Validation(format!(
@@ -67,8 +97,47 @@ impl PipelineConfig {
             )));
         }
 
+        for (i, rule) in self.correlation_rules.iter().enumerate() {
+            if rule.window_secs < 0 || rule.window_secs > 86400 {
+                return Err(PipelineError::Validation(format!(
+                    "correlation rule {i}: window_secs must be 0-86400, got {}",
+                    rule.window_secs
+                )));
+            }
+            if rule.group_by.is_empty() {
+                return Err(PipelineError::Validation(format!(
+                    "correlation rule {i}: group_by must not be empty"
+                )));
+            }
+            if rule.min_count < 1 {
+                return Err(PipelineError::Validation(format!(
+                    "correlation rule {i}: min_count must be >= 1"
+                )));
+            }
+        }
+
+        if let Some(window) = &self.suppression {
+            if window.max_alerts < 1 {
+                return Err(PipelineError::Validation(
+                    "suppression max_alerts must be >= 1".to_string(),
+                ));
+            }
+            if window.window_secs < 1 || window.window_secs > 86400 {
+                return Err(PipelineError::Validation(format!(
+                    "suppression window_secs must be 1-86400, got {}",
+                    window.window_secs
+                )));
+            }
+        }
+
         Ok(())
     }
+
+    /// The deduplication strategy for alerts of `category`.
+    pub fn dedup_strategy(&self, category: &AlertCategory) -> &deduplication::DedupStrategy {
+        const DEFAULT: deduplication::DedupStrategy = deduplication::DedupStrategy::Normalized;
+        self.dedup_strategies.get(category).unwrap_or(&DEFAULT)
+    }
 }
 
 impl Default for PipelineConfig {
@@ -77,36 +146,49 @@ impl Default for PipelineConfig {
             correlation_window_secs: 300,
             critical_ports: vec![22, 23, 3389, 445, 1433, 3306, 5432, 6379, 27017],
             high_count_threshold: 5,
+            dedup_strategies: HashMap::new(),
+            correlation_rules: Vec::new(),
+            suppression: None,
         }
     }
 }
 
 /// The 5-stage alert processing pipeline.
+///
+/// Clones share dispatch targets, scoring rules, metrics and suppression
+/// state.
+#[derive(Clone)]
 pub struct Pipeline {
-    pool: SqlitePool,
+    pool: DbPool,
     #[allow(dead_code)]
     event_bus: EventBus,
     config: PipelineConfig,
-    dispatch_targets: Vec<Box<dyn dispatch::DispatchTarget>>,
+    dispatch_targets: Vec<Arc<dyn dispatch::DispatchTarget>>,
+    scoring_rules: Vec<Arc<dyn scoring::ScoringRule>>,
+    metrics: Arc<Mutex<PipelineMetrics>>,
+    suppressor: Arc<Mutex<suppression::Suppressor>>,
 }
 
 impl Pipeline {
     /// Create a pipeline with default config and DB + EventBus dispatch targets.
-    pub fn new(pool: SqlitePool, event_bus: EventBus) -> Self {
+    pub fn new(pool: DbPool, event_bus: EventBus) -> Self {
         let db_target = dispatch::DatabaseTarget::new(pool.clone());
         let bus_target = dispatch::EventBusTarget::new(event_bus.clone());
         Self {
             pool,
             event_bus,
             config: PipelineConfig::default(),
-            dispatch_targets: vec![Box::new(db_target), Box::new(bus_target)],
+            dispatch_targets: vec![Arc::new(db_target), Arc::new(bus_target)],
+            scoring_rules: Vec::new(),
+            metrics: Arc::new(Mutex::new(PipelineMetrics::default())),
+            suppressor: Arc::default(),
         }
     }
 
     /// Create a pipeline with a custom config and DB + EventBus dispatch targets.
     ///
     /// Returns an error if the config fails validation.
-    pub fn with_config(pool: SqlitePool, event_bus: EventBus, config: PipelineConfig) -> PipelineResult<Self> {
+    pub fn with_config(pool: DbPool, event_bus: EventBus, config: PipelineConfig) -> PipelineResult<Self> {
         config.validate()?;
         let db_target = dispatch::DatabaseTarget::new(pool.clone());
         let bus_target = dispatch::EventBusTarget::new(event_bus.clone());
@@ -114,45 +196,154 @@ impl Pipeline {
             pool,
             event_bus,
             config,
-            dispatch_targets: vec![Box::new(db_target), Box::new(bus_target)],
+            dispatch_targets: vec![Arc::new(db_target), Arc::new(bus_target)],
+            scoring_rules: Vec::new(),
+            metrics: Arc::new(Mutex::new(PipelineMetrics::default())),
+            suppressor: Arc::default(),
         })
     }
 
     /// Add an additional dispatch target.
     pub fn add_dispatch_target(&mut self, target: Box<dyn dispatch::DispatchTarget>) {
-        self.dispatch_targets.push(target);
+        self.dispatch_targets.push(Arc::from(target));
+    }
+
+    /// Add a scoring rule. Rules run in the order added, after the built-in
+    /// critical-port scoring.
+    pub fn add_scoring_rule(&mut self, rule: Box<dyn scoring::ScoringRule>) {
+        self.scoring_rules.push(Arc::from(rule));
+    }
+
+    /// A snapshot of the per-stage counters and timings.
+    pub fn metrics(&self) -> PipelineMetrics {
+        self.metrics.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Record a stage run that began at `started`.
+    fn record(&self, stage: Stage, started: Instant) {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(stage, started.elapsed());
+    }
+
+    /// Stage 1: normalize parser output into alerts ready for [`process`](Self::process).
+    pub fn normalize(&self, output: ParserOutput) -> PipelineResult<Vec<NormalizedAlert>> {
+        let started = Instant::now();
+        let result = normalization::normalize(output);
+        self.record(Stage::Normalize, started);
+        result
     }
 
     /// Process a normalized alert through the pipeline stages:
     /// deduplicate -> correlate -> score -> dispatch.
+    ///
+    /// With a [`suppression`](PipelineConfig::suppression) window configured,
+    /// alerts over its rate limit skip the stages and return the burst's
+    /// first alert with its count bumped.
     pub async fn process(&self, normalized: NormalizedAlert) -> PipelineResult<Alert> {
-        // Stage 2: Deduplicate
-        let dedup_result = deduplication::deduplicate(&self.pool, &normalized).await?;
-        if let deduplication::DeduplicationResult::Duplicate(existing) = dedup_result {
+        let Some(window) = &self.config.suppression else {
+            return self.run_stages(normalized).await;
+        };
+        let strategy = self.config.dedup_strategy(&normalized.category);
+        let fingerprint = deduplication::fingerprint(&normalized, strategy);
+
+        let admission = self
+            .suppressor
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .admit(&fingerprint, window, Instant::now());
+        match admission {
+            suppression::Admission::Allow => self.run_stages(normalized).await,
+            suppression::Admission::Lead(generation) => {
+                // Abandons the burst on failure, or if this future is dropped
+                let guard =
+                    suppression::LeadGuard::new(self.suppressor.clone(), fingerprint, generation);
+                let result = self.run_stages(normalized).await;
+                if let Ok(alert) = &result {
+                    guard.resolve(&alert.id);
+                }
+                result
+            }
+            suppression::Admission::Suppress(mut lead) => {
+                // A failed lead closes the channel; fall back to the stages
+                let lead_id = match lead.wait_for(Option::is_some).await {
+                    Ok(id) => id.clone(),
+                    Err(_) => None,
+                };
+                let Some(lead_id) = lead_id else {
+                    return self.run_stages(normalized).await;
+                };
+                let now = now_rfc3339();
+                alerts::increment_count(&self.pool, &lead_id, &now).await?;
+                let alert = alerts::get_by_id(&self.pool, &lead_id)
+                    .await?
+                    .ok_or(sqlx::Error::RowNotFound)?;
+                self.metrics.lock().unwrap_or_else(|e| e.into_inner()).alerts_suppressed += 1;
+                Ok(alert)
+            }
+        }
+    }
+
+    async fn run_stages(&self, mut normalized: NormalizedAlert) -> PipelineResult<Alert> {
+        // Stage 2: Deduplicate. The stored alert carries the strategy's
+        // fingerprint so later alerts can match it.
+        let started = Instant::now();
+        let strategy = self.config.dedup_strategy(&normalized.category);
+        let dedup_result = deduplication::deduplicate(&self.pool, &normalized, strategy).await;
+        self.record(Stage::Dedup, started);
+        normalized.fingerprint = deduplication::fingerprint(&normalized, strategy);
+        if let deduplication::DeduplicationResult::Duplicate(existing) = dedup_result? {
+            self.metrics.lock().unwrap_or_else(|e| e.into_inner()).duplicates_dropped += 1;
             return Ok(*existing);
         }
 
         // Stage 3: Correlate
-        let correlation_id = correlation::correlate(
-            &self.pool,
-            &normalized,
-            self.config.correlation_window_secs,
-        )
-        .await?;
+        let started = Instant::now();
+        let correlation_id = self.correlate(&normalized).await;
+        self.record(Stage::Correlate, started);
+        let correlation_id = correlation_id?;
 
         // Stage 4: Score
-        let final_severity = scoring::score(&normalized, &self.config).await;
+        let started = Instant::now();
+        let base_severity = scoring::score(&normalized, &self.config).await;
+        let final_severity = scoring::apply_rules(&self.scoring_rules, &normalized, base_severity);
+        self.record(Stage::Score, started);
 
         // Stage 5: Dispatch
-        let alert = dispatch::dispatch(
+        let started = Instant::now();
+        let report = dispatch::dispatch(
             &normalized,
             final_severity,
             correlation_id,
             &self.dispatch_targets,
         )
-        .await?;
+        .await;
+        self.record(Stage::Dispatch, started);
+        let report = report?;
 
-        Ok(alert)
+        for (target, error) in report.failures() {
+            tracing::warn!(
+                target_name = target,
+                alert_id = report.alert.id.as_str(),
+                error = %error,
+                "Alert dispatch target failed"
+            );
+        }
+
+        Ok(report.alert)
+    }
+
+    /// Try the configured correlation rules in order, then the default
+    /// device-window rule.
+    async fn correlate(&self, normalized: &NormalizedAlert) -> PipelineResult<Option<String>> {
+        for rule in &self.config.correlation_rules {
+            let correlation_id = correlation::correlate_rule(&self.pool, normalized, rule).await?;
+            if correlation_id.is_some() {
+                return Ok(correlation_id);
+            }
+        }
+        correlation::correlate(&self.pool, normalized, self.config.correlation_window_secs).await
     }
 }
 
@@ -196,6 +387,32 @@ mod tests {
         assert!(err.to_string().contains("high_count_threshold must be >= 1"));
     }
 
+    #[test]
+    fn test_pipeline_config_invalid_correlation_rule() {
+        let rule = correlation::CorrelationRule {
+            window_secs: 60,
+            group_by: vec![],
+            min_count: 3,
+        };
+        let config = PipelineConfig {
+            correlation_rules: vec![rule.clone()],
+            ..PipelineConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("group_by must not be empty"));
+
+        let config = PipelineConfig {
+            correlation_rules: vec![correlation::CorrelationRule {
+                group_by: vec![correlation::Field::DeviceIp],
+                min_count: 0,
+                ..rule
+            }],
+            ..PipelineConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("min_count must be >= 1"));
+    }
+
     #[test]
     fn test_pipeline_config_negative_window() {
         let config = PipelineConfig {
@@ -205,4 +422,28 @@ mod tests {
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("correlation_window_secs must be 0-86400"));
     }
+
+    #[test]
+    fn test_pipeline_config_invalid_suppression() {
+        let window = suppression::SuppressionWindow {
+            max_alerts: 0,
+            window_secs: 60,
+        };
+        let config = PipelineConfig {
+            suppression: Some(window.clone()),
+            ..PipelineConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("max_alerts must be >= 1"));
+
+        let config = PipelineConfig {
+            suppression: Some(suppression::SuppressionWindow {
+                max_alerts: 5,
+                window_secs: 0,
+            }),
+            ..PipelineConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("window_secs must be 1-86400"));
+    }
 }
@@ -0,0 +1,92 @@
+//! Pipeline throughput metrics.
+//!
+//! Counts and cumulative wall-clock time per stage, recorded by
+//! [`Pipeline`](crate::Pipeline) as alerts flow through it.
+
+use std::time::Duration;
+
+/// A pipeline stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Normalize,
+    Dedup,
+    Correlate,
+    Score,
+    Dispatch,
+}
+
+/// How often a stage ran and how long it took in total.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageMetrics {
+    pub count: u64,
+    pub total: Duration,
+}
+
+impl StageMetrics {
+    /// Mean duration per run, or `None` if the stage never ran.
+    pub fn average(&self) -> Option<Duration> {
+        (self.count > 0).then(|| self.total / self.count as u32)
+    }
+}
+
+/// A snapshot of pipeline metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineMetrics {
+    pub normalize: StageMetrics,
+    pub dedup: StageMetrics,
+    pub correlate: StageMetrics,
+    pub score: StageMetrics,
+    pub dispatch: StageMetrics,
+    /// Alerts that stopped at the dedup stage because they matched an
+    /// existing alert.
+    pub duplicates_dropped: u64,
+    /// Alerts over the suppression window's rate limit, folded into the
+    /// count of their burst's first alert without running the stages.
+    pub alerts_suppressed: u64,
+}
+
+impl PipelineMetrics {
+    pub fn stage(&self, stage: Stage) -> &StageMetrics {
+        match stage {
+            Stage::Normalize => &self.normalize,
+            Stage::Dedup => &self.dedup,
+            Stage::Correlate => &self.correlate,
+            Stage::Score => &self.score,
+            Stage::Dispatch => &self.dispatch,
+        }
+    }
+
+    /// Record one run of `stage` that took `elapsed`.
+    pub fn record(&mut self, stage: Stage, elapsed: Duration) {
+        let metrics = match stage {
+            Stage::Normalize => &mut self.normalize,
+            Stage::Dedup => &mut self.dedup,
+            Stage::Correlate => &mut self.correlate,
+            Stage::Score => &mut self.score,
+            Stage::Dispatch => &mut self.dispatch,
+        };
+        metrics.count += 1;
+        metrics.total += elapsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_average() {
+        let mut metrics = PipelineMetrics::default();
+        assert_eq!(metrics.stage(Stage::Score).average(), None);
+
+        metrics.record(Stage::Score, Duration::from_millis(10));
+        metrics.record(Stage::Score, Duration::from_millis(30));
+        metrics.record(Stage::Dispatch, Duration::from_millis(5));
+
+        assert_eq!(metrics.score.count, 2);
+        assert_eq!(metrics.score.total, Duration::from_millis(40));
+        assert_eq!(metrics.score.average(), Some(Duration::from_millis(20)));
+        assert_eq!(metrics.stage(Stage::Dispatch).count, 1);
+        assert_eq!(metrics.dedup, StageMetrics::default());
+    }
+}
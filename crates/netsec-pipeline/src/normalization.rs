@@ -301,6 +301,7 @@ mod tests {
                     m.insert("name".to_string(), "ssh".to_string());
                     m
                 },
+                ..Default::default()
             },
             NmapPort {
                 port: 80,
@@ -311,6 +312,7 @@ mod tests {
                     m.insert("name".to_string(), "http".to_string());
                     m
                 },
+                ..Default::default()
             },
         ];
         let host = make_nmap_host(ports, HashMap::new());
@@ -338,6 +340,7 @@ mod tests {
             protocol: "tcp".to_string(),
             state: "filtered".to_string(),
             service: HashMap::new(),
+            ..Default::default()
         }];
         let host = make_nmap_host(ports, HashMap::new());
         let alerts = normalize(ParserOutput::Nmap(host)).unwrap();
@@ -351,6 +354,7 @@ mod tests {
             protocol: "tcp".to_string(),
             state: "open".to_string(),
             service: HashMap::new(),
+            ..Default::default()
         }];
         let host1 = make_nmap_host(ports.clone(), HashMap::new());
         let host2 = make_nmap_host(ports, HashMap::new());
@@ -2,25 +2,63 @@
 //!
 //! Adjusts the base severity of a normalized alert based on contextual signals
 //! such as whether the alert targets a critical port.
+//!
+//! The built-in scorer applies [`CriticalPortRule`]; site-specific
+//! [`ScoringRule`]s registered on the pipeline then run in order, each seeing
+//! the severity produced by the previous one.
+
+use std::sync::Arc;
 
 use netsec_models::alert::{NormalizedAlert, Severity};
 
 use crate::PipelineConfig;
 
+/// A severity adjustment applied during scoring.
+pub trait ScoringRule: Send + Sync {
+    /// Return the new severity given the severity so far.
+    fn apply(&self, alert: &NormalizedAlert, current: Severity) -> Severity;
+}
+
+/// Boosts severity by one level when the alert targets a critical port.
+pub struct CriticalPortRule {
+    ports: Vec<u16>,
+}
+
+impl CriticalPortRule {
+    pub fn new(ports: Vec<u16>) -> Self {
+        Self { ports }
+    }
+}
+
+impl ScoringRule for CriticalPortRule {
+    fn apply(&self, alert: &NormalizedAlert, current: Severity) -> Severity {
+        if is_critical_port_alert(alert, &self.ports) {
+            escalate(current, 1)
+        } else {
+            current
+        }
+    }
+}
+
 /// Score the alert and return the final severity.
 ///
 /// Currently checks if the alert targets a critical port and boosts severity by 1 level.
 pub async fn score(alert: &NormalizedAlert, config: &PipelineConfig) -> Severity {
-    let mut numeric = severity_to_score(alert.severity);
-
-    if is_critical_port_alert(alert, &config.critical_ports) {
-        numeric += 1;
-    }
+    CriticalPortRule::new(config.critical_ports.clone()).apply(alert, alert.severity)
+}
 
-    // Clamp to valid range
-    numeric = numeric.min(4);
+/// Apply `rules` in order, starting from `severity`.
+pub fn apply_rules(
+    rules: &[Arc<dyn ScoringRule>],
+    alert: &NormalizedAlert,
+    severity: Severity,
+) -> Severity {
+    rules.iter().fold(severity, |current, rule| rule.apply(alert, current))
+}
 
-    score_to_severity(numeric)
+/// Raise `severity` by `levels`, clamped at [`Severity::Critical`].
+pub fn escalate(severity: Severity, levels: u8) -> Severity {
+    severity.escalate(levels)
 }
 
 /// Convert a [`Severity`] to a numeric score (0-4).
@@ -116,6 +154,71 @@ mod tests {
         assert_eq!(result, Severity::Critical); // Critical + 1 clamped to Critical
     }
 
+    /// Escalates cameras with RTSP exposed to at least High.
+    struct CameraRtspRule;
+
+    impl ScoringRule for CameraRtspRule {
+        fn apply(&self, alert: &NormalizedAlert, current: Severity) -> Severity {
+            let raw = &alert.raw_data;
+            let is_camera = raw.get("device_type").and_then(|v| v.as_str()) == Some("camera");
+            let rtsp = raw.get("port").and_then(|v| v.as_u64()) == Some(554);
+            if is_camera && rtsp {
+                current.max(Severity::High)
+            } else {
+                current
+            }
+        }
+    }
+
+    /// Forces a fixed severity.
+    struct SetRule(Severity);
+
+    impl ScoringRule for SetRule {
+        fn apply(&self, _alert: &NormalizedAlert, _current: Severity) -> Severity {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_escalates() {
+        let rules: Vec<Arc<dyn ScoringRule>> = vec![Arc::new(CameraRtspRule)];
+
+        let camera = make_alert(
+            Severity::Low,
+            serde_json::json!({"device_type": "camera", "port": 554}),
+        );
+        assert_eq!(apply_rules(&rules, &camera, camera.severity), Severity::High);
+
+        let printer = make_alert(
+            Severity::Low,
+            serde_json::json!({"device_type": "printer", "port": 554}),
+        );
+        assert_eq!(apply_rules(&rules, &printer, printer.severity), Severity::Low);
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let alert = make_alert(Severity::Info, serde_json::json!({"port": 22}));
+        let boost = || Arc::new(CriticalPortRule::new(vec![22])) as Arc<dyn ScoringRule>;
+
+        // Set to Medium, then boost: High
+        let rules = vec![Arc::new(SetRule(Severity::Medium)) as Arc<dyn ScoringRule>, boost()];
+        assert_eq!(apply_rules(&rules, &alert, alert.severity), Severity::High);
+
+        // Boost, then set: the later rule wins
+        let rules = vec![boost(), Arc::new(SetRule(Severity::Medium)) as Arc<dyn ScoringRule>];
+        assert_eq!(apply_rules(&rules, &alert, alert.severity), Severity::Medium);
+
+        assert_eq!(apply_rules(&[], &alert, Severity::Low), Severity::Low);
+    }
+
+    #[test]
+    fn test_escalate_clamps() {
+        assert_eq!(escalate(Severity::Info, 2), Severity::Medium);
+        assert_eq!(escalate(Severity::High, 3), Severity::Critical);
+        assert_eq!(escalate(Severity::Critical, u8::MAX), Severity::Critical);
+    }
+
     #[test]
     fn test_severity_roundtrip() {
         for sev in [
@@ -0,0 +1,319 @@
+//! Alert storm suppression.
+//!
+//! Deduplication collapses an alert onto a stored alert with the same
+//! fingerprint, which needs a database round trip per alert and lets a burst
+//! of concurrent arrivals race past it before the first one is stored. The
+//! suppression window is rate-based instead: it counts arrivals per
+//! fingerprint in memory, and once more than
+//! [`max_alerts`](SuppressionWindow::max_alerts) arrive within
+//! [`window_secs`](SuppressionWindow::window_secs), the rest skip the
+//! pipeline and only bump the count of the alert that opened the burst.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+/// Rate limit on alerts sharing a fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressionWindow {
+    /// Alerts per fingerprint let through per window.
+    pub max_alerts: u32,
+    /// Length of the sliding window in seconds.
+    pub window_secs: u64,
+}
+
+impl SuppressionWindow {
+    fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs)
+    }
+}
+
+/// What to do with an arriving alert.
+pub(crate) enum Admission {
+    /// The first alert of a new burst, with the burst's generation. Hold a
+    /// [`LeadGuard`] while it is processed and resolve it with the stored id.
+    Lead(u64),
+    /// Within the limit; process normally.
+    Allow,
+    /// Over the limit. The receiver yields the id of the burst's first alert
+    /// once it is stored, and closes if that alert failed.
+    Suppress(watch::Receiver<Option<String>>),
+}
+
+/// Arrivals of one fingerprint within the current window.
+struct Burst {
+    /// Tells this burst apart from earlier ones with the same fingerprint.
+    generation: u64,
+    arrivals: VecDeque<Instant>,
+    /// Id of the alert that opened the burst, once stored.
+    lead: watch::Sender<Option<String>>,
+}
+
+/// Per-fingerprint arrival tracking, shared by clones of a pipeline.
+#[derive(Default)]
+pub(crate) struct Suppressor {
+    bursts: HashMap<String, Burst>,
+    /// Generation of the most recently opened burst.
+    generation: u64,
+}
+
+impl Suppressor {
+    /// Record an alert with `fingerprint` arriving at `now`.
+    pub(crate) fn admit(
+        &mut self,
+        fingerprint: &str,
+        config: &SuppressionWindow,
+        now: Instant,
+    ) -> Admission {
+        let window = config.window();
+        let expired = |at: &Instant| now.saturating_duration_since(*at) >= window;
+
+        if let Some(burst) = self.bursts.get_mut(fingerprint) {
+            while burst.arrivals.front().is_some_and(expired) {
+                burst.arrivals.pop_front();
+            }
+            if !burst.arrivals.is_empty() {
+                burst.arrivals.push_back(now);
+                return if burst.arrivals.len() > config.max_alerts as usize {
+                    Admission::Suppress(burst.lead.subscribe())
+                } else {
+                    Admission::Allow
+                };
+            }
+        }
+
+        // A new burst; forget bursts whose window has passed
+        self.bursts
+            .retain(|_, burst| burst.arrivals.back().is_some_and(|at| !expired(at)));
+        self.generation += 1;
+        self.bursts.insert(
+            fingerprint.to_string(),
+            Burst {
+                generation: self.generation,
+                arrivals: VecDeque::from([now]),
+                lead: watch::Sender::new(None),
+            },
+        );
+        Admission::Lead(self.generation)
+    }
+
+    /// The burst for `fingerprint`, if it is still the one of `generation`.
+    /// A slow lead of an expired burst must not touch the burst that
+    /// replaced it.
+    fn current(&self, fingerprint: &str, generation: u64) -> Option<&Burst> {
+        self.bursts
+            .get(fingerprint)
+            .filter(|burst| burst.generation == generation)
+    }
+
+    /// The first alert of burst `generation` for `fingerprint` was stored as
+    /// `alert_id`. Only the first resolve counts, and none once the burst has
+    /// been replaced.
+    pub(crate) fn resolve(&mut self, fingerprint: &str, generation: u64, alert_id: &str) {
+        if let Some(burst) = self.current(fingerprint, generation) {
+            burst.lead.send_if_modified(|lead| {
+                let unset = lead.is_none();
+                if unset {
+                    *lead = Some(alert_id.to_string());
+                }
+                unset
+            });
+        }
+    }
+
+    /// The first alert of burst `generation` for `fingerprint` failed; alerts
+    /// waiting on it fall back to normal processing and the next arrival
+    /// opens a new burst. Does nothing once the burst has been replaced.
+    pub(crate) fn abandon(&mut self, fingerprint: &str, generation: u64) {
+        if self.current(fingerprint, generation).is_some() {
+            self.bursts.remove(fingerprint);
+        }
+    }
+}
+
+/// Held by the first alert of a burst while it is processed.
+///
+/// Dropping the guard without [`resolve`](Self::resolve), because the alert
+/// failed or its future was dropped, abandons the burst so the alerts waiting
+/// on it aren't left hanging.
+pub(crate) struct LeadGuard {
+    suppressor: Arc<Mutex<Suppressor>>,
+    fingerprint: String,
+    generation: u64,
+    resolved: bool,
+}
+
+impl LeadGuard {
+    pub(crate) fn new(
+        suppressor: Arc<Mutex<Suppressor>>,
+        fingerprint: String,
+        generation: u64,
+    ) -> Self {
+        Self {
+            suppressor,
+            fingerprint,
+            generation,
+            resolved: false,
+        }
+    }
+
+    /// The first alert was stored as `alert_id`.
+    pub(crate) fn resolve(mut self, alert_id: &str) {
+        self.suppressor
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .resolve(&self.fingerprint, self.generation, alert_id);
+        self.resolved = true;
+    }
+}
+
+impl Drop for LeadGuard {
+    fn drop(&mut self) {
+        if !self.resolved {
+            self.suppressor
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .abandon(&self.fingerprint, self.generation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_alerts: u32) -> SuppressionWindow {
+        SuppressionWindow {
+            max_alerts,
+            window_secs: 10,
+        }
+    }
+
+    #[test]
+    fn test_admit_limits_per_fingerprint() {
+        let mut suppressor = Suppressor::default();
+        let config = config(2);
+        let start = Instant::now();
+
+        assert!(matches!(suppressor.admit("a", &config, start), Admission::Lead(_)));
+        assert!(matches!(suppressor.admit("a", &config, start), Admission::Allow));
+        assert!(matches!(suppressor.admit("a", &config, start), Admission::Suppress(_)));
+        // Other fingerprints are counted separately
+        assert!(matches!(suppressor.admit("b", &config, start), Admission::Lead(_)));
+    }
+
+    #[test]
+    fn test_window_slides() {
+        let mut suppressor = Suppressor::default();
+        let config = config(1);
+        let start = Instant::now();
+
+        assert!(matches!(suppressor.admit("a", &config, start), Admission::Lead(_)));
+        let later = start + Duration::from_secs(5);
+        assert!(matches!(suppressor.admit("a", &config, later), Admission::Suppress(_)));
+        // The first arrival has left the window, the second hasn't
+        let later = start + Duration::from_secs(12);
+        assert!(matches!(suppressor.admit("a", &config, later), Admission::Suppress(_)));
+        // Quiet for a full window: a new burst
+        let later = start + Duration::from_secs(30);
+        assert!(matches!(suppressor.admit("a", &config, later), Admission::Lead(_)));
+    }
+
+    #[tokio::test]
+    async fn test_suppressed_wait_for_lead() {
+        let mut suppressor = Suppressor::default();
+        let config = config(1);
+        let now = Instant::now();
+
+        let Admission::Lead(a) = suppressor.admit("a", &config, now) else {
+            panic!("expected a lead");
+        };
+        let Admission::Suppress(mut rx) = suppressor.admit("a", &config, now) else {
+            panic!("expected suppression");
+        };
+        suppressor.resolve("a", a, "alert-1");
+        let id = rx.wait_for(Option::is_some).await.unwrap().clone();
+        assert_eq!(id.as_deref(), Some("alert-1"));
+
+        let Admission::Suppress(mut rx) = suppressor.admit("a", &config, now) else {
+            panic!("expected suppression");
+        };
+        // Already resolved
+        assert!(rx.wait_for(Option::is_some).await.is_ok());
+
+        let Admission::Lead(b) = suppressor.admit("b", &config, now) else {
+            panic!("expected a lead");
+        };
+        let Admission::Suppress(mut rx) = suppressor.admit("b", &config, now) else {
+            panic!("expected suppression");
+        };
+        suppressor.abandon("b", b);
+        assert!(rx.wait_for(Option::is_some).await.is_err());
+        assert!(matches!(suppressor.admit("b", &config, now), Admission::Lead(_)));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_lead_guard_abandons_burst() {
+        let suppressor = Arc::new(Mutex::new(Suppressor::default()));
+        let config = config(1);
+        let now = Instant::now();
+
+        let admit = |fingerprint: &str| suppressor.lock().unwrap().admit(fingerprint, &config, now);
+        let Admission::Lead(generation) = admit("a") else {
+            panic!("expected a lead");
+        };
+        let guard = LeadGuard::new(suppressor.clone(), "a".to_string(), generation);
+        let Admission::Suppress(mut rx) = admit("a") else {
+            panic!("expected suppression");
+        };
+        drop(guard);
+        assert!(rx.wait_for(Option::is_some).await.is_err());
+        let Admission::Lead(generation) = admit("a") else {
+            panic!("expected a lead");
+        };
+
+        let guard = LeadGuard::new(suppressor.clone(), "a".to_string(), generation);
+        let Admission::Suppress(mut rx) = admit("a") else {
+            panic!("expected suppression");
+        };
+        guard.resolve("alert-2");
+        let id = rx.wait_for(Option::is_some).await.unwrap().clone();
+        assert_eq!(id.as_deref(), Some("alert-2"));
+    }
+
+    #[tokio::test]
+    async fn test_stale_lead_leaves_new_burst_alone() {
+        let suppressor = Arc::new(Mutex::new(Suppressor::default()));
+        let config = config(1);
+        let start = Instant::now();
+        let admit = |at: Instant| suppressor.lock().unwrap().admit("a", &config, at);
+
+        let Admission::Lead(old) = admit(start) else {
+            panic!("expected a lead");
+        };
+        let old_lead = LeadGuard::new(suppressor.clone(), "a".to_string(), old);
+
+        // The old burst expires while its lead is still running
+        let later = start + Duration::from_secs(30);
+        let Admission::Lead(new) = admit(later) else {
+            panic!("expected a new burst");
+        };
+        assert_ne!(old, new);
+        let new_lead = LeadGuard::new(suppressor.clone(), "a".to_string(), new);
+        let Admission::Suppress(mut rx) = admit(later) else {
+            panic!("expected suppression");
+        };
+
+        // Neither resolving nor abandoning the old burst touches the new one
+        old_lead.resolve("old-alert");
+        drop(LeadGuard::new(suppressor.clone(), "a".to_string(), old));
+        assert!(rx.borrow().is_none());
+        assert!(matches!(admit(later), Admission::Suppress(_)));
+
+        new_lead.resolve("new-alert");
+        let id = rx.wait_for(Option::is_some).await.unwrap().clone();
+        assert_eq!(id.as_deref(), Some("new-alert"));
+    }
+}
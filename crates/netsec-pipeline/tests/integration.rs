@@ -1,15 +1,23 @@
 //! End-to-end integration tests for the alert processing pipeline.
 
 use chrono::Utc;
-use netsec_db::{pool::create_test_pool, run_migrations};
 use netsec_db::repo::alerts as alert_repo;
+use netsec_db::{pool::create_test_pool, run_migrations, DbPool};
 use netsec_events::EventBus;
-use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
+use netsec_models::alert::{Alert, AlertCategory, NormalizedAlert, Severity};
 use netsec_parsers::nmap::{NmapHost, NmapPort};
 use netsec_parsers::suricata::{EveAlert, EveEvent};
+use netsec_pipeline::deduplication::DedupStrategy;
+use netsec_pipeline::dispatch::DispatchTarget;
 use netsec_pipeline::normalization::{normalize, ParserOutput};
-use netsec_pipeline::Pipeline;
+use netsec_pipeline::scoring::ScoringRule;
+use netsec_pipeline::suppression::SuppressionWindow;
+use netsec_pipeline::{Pipeline, PipelineConfig, PipelineError};
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_pipeline_nmap_end_to_end() {
@@ -33,6 +41,7 @@ async fn test_pipeline_nmap_end_to_end() {
                 m.insert("name".to_string(), "http".to_string());
                 m
             },
+            ..Default::default()
         }],
         os: HashMap::new(),
     };
@@ -117,6 +126,53 @@ async fn test_pipeline_dedup_integration() {
     assert_eq!(second.fingerprint, first.fingerprint);
 }
 
+#[tokio::test]
+async fn test_pipeline_dedup_strategy_per_category() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let bus = EventBus::new();
+    let mut config = PipelineConfig::default();
+    config
+        .dedup_strategies
+        .insert(AlertCategory::Intrusion, DedupStrategy::Signature);
+    let pipeline = Pipeline::with_config(pool.clone(), bus, config).unwrap();
+
+    let ids_alert = |src_ip: &str, category: AlertCategory| NormalizedAlert {
+        source_tool: "suricata".to_string(),
+        severity: Severity::Medium,
+        category,
+        title: "ET SCAN Strategy".to_string(),
+        description: "Test".to_string(),
+        device_ip: Some(src_ip.to_string()),
+        fingerprint: format!("suricata:4001:{src_ip}:10.0.0.9"),
+        raw_data: serde_json::json!({"sig_id": 4001}),
+        timestamp: Utc::now(),
+    };
+
+    // Intrusion alerts dedup on the signature, across source hosts
+    let first = pipeline
+        .process(ids_alert("10.0.0.1", AlertCategory::Intrusion))
+        .await
+        .unwrap();
+    let second = pipeline
+        .process(ids_alert("10.0.0.2", AlertCategory::Intrusion))
+        .await
+        .unwrap();
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.count, 2);
+
+    // Other categories keep the per-flow fingerprint
+    let a = pipeline
+        .process(ids_alert("10.0.0.1", AlertCategory::Anomaly))
+        .await
+        .unwrap();
+    let b = pipeline
+        .process(ids_alert("10.0.0.2", AlertCategory::Anomaly))
+        .await
+        .unwrap();
+    assert_ne!(a.id, b.id);
+}
+
 #[tokio::test]
 async fn test_pipeline_correlation_groups_same_device() {
     let pool = create_test_pool().await.unwrap();
@@ -184,6 +240,42 @@ async fn test_pipeline_scoring_critical_port() {
     assert_eq!(result.severity, "low");
 }
 
+/// Escalates every alert to Critical.
+struct AlwaysCritical;
+
+impl ScoringRule for AlwaysCritical {
+    fn apply(&self, _alert: &NormalizedAlert, _current: Severity) -> Severity {
+        Severity::Critical
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_custom_scoring_rule() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let bus = EventBus::new();
+    let mut pipeline = Pipeline::new(pool.clone(), bus);
+    pipeline.add_scoring_rule(Box::new(AlwaysCritical));
+
+    let alert = NormalizedAlert {
+        source_tool: "nmap".to_string(),
+        severity: Severity::Info,
+        category: AlertCategory::Vulnerability,
+        title: "Open port 8080".to_string(),
+        description: "HTTP proxy on port 8080".to_string(),
+        device_ip: Some("10.0.0.1".to_string()),
+        fingerprint: "score-rule-fp-8080".to_string(),
+        raw_data: serde_json::json!({"port": 8080}),
+        timestamp: Utc::now(),
+    };
+
+    let result = pipeline.process(alert).await.unwrap();
+    assert_eq!(result.severity, "critical");
+
+    let from_db = alert_repo::get_by_id(&pool, &result.id).await.unwrap().unwrap();
+    assert_eq!(from_db.severity, "critical");
+}
+
 /// Verify all Suricata severity values (1-4) map to the correct output
 /// through the full normalization -> pipeline path.
 #[tokio::test]
@@ -230,3 +322,276 @@ async fn test_pipeline_suricata_full_severity_range() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_pipeline_metrics_counts_stages_and_duplicates() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let bus = EventBus::new();
+    let pipeline = Pipeline::new(pool.clone(), bus);
+    // Clones share the same counters
+    let clone = pipeline.clone();
+
+    let event = |sig_id: u64| EveEvent {
+        timestamp: Some("2024-01-15T10:00:00".to_string()),
+        event_type: Some("alert".to_string()),
+        src_ip: Some("10.0.0.1".to_string()),
+        src_port: Some(54321),
+        dest_ip: Some("10.0.0.2".to_string()),
+        dest_port: Some(80),
+        proto: Some("TCP".to_string()),
+        alert: Some(EveAlert {
+            action: Some("allowed".to_string()),
+            signature: Some(format!("ET TEST metrics {sig_id}")),
+            signature_id: Some(sig_id),
+            severity: Some(2),
+            category: Some("Test".to_string()),
+        }),
+    };
+
+    // Three distinct alerts plus one duplicate of the first
+    for sig_id in [5001, 5002, 5003, 5001] {
+        let alerts = pipeline.normalize(ParserOutput::Suricata(event(sig_id))).unwrap();
+        for alert in alerts {
+            clone.process(alert).await.unwrap();
+        }
+    }
+
+    let metrics = pipeline.metrics();
+    assert_eq!(metrics.normalize.count, 4);
+    assert_eq!(metrics.dedup.count, 4);
+    assert_eq!(metrics.duplicates_dropped, 1);
+    // The duplicate short-circuits before the later stages
+    assert_eq!(metrics.correlate.count, 3);
+    assert_eq!(metrics.score.count, 3);
+    assert_eq!(metrics.dispatch.count, 3);
+    assert!(metrics.dispatch.total > std::time::Duration::ZERO);
+    assert_eq!(clone.metrics(), metrics);
+}
+
+#[tokio::test]
+async fn test_pipeline_process_batch_dedups_within_batch() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let bus = EventBus::new();
+    let pipeline = Pipeline::new(pool.clone(), bus);
+
+    let alert = |fingerprint: &str, device_ip: &str| NormalizedAlert {
+        source_tool: "suricata".to_string(),
+        severity: Severity::Medium,
+        category: AlertCategory::Intrusion,
+        title: format!("Batch {fingerprint}"),
+        description: "Test".to_string(),
+        device_ip: Some(device_ip.to_string()),
+        fingerprint: fingerprint.to_string(),
+        raw_data: serde_json::json!({}),
+        timestamp: Utc::now(),
+    };
+
+    // Already stored before the batch
+    let stored = pipeline.process(alert("fp-b-stored", "10.0.1.9")).await.unwrap();
+
+    let batch = vec![
+        alert("fp-b-1", "10.0.1.1"),
+        alert("fp-b-2", "10.0.1.2"),
+        alert("fp-b-1", "10.0.1.1"),
+        alert("fp-b-stored", "10.0.1.9"),
+        alert("fp-b-3", "10.0.1.1"),
+        alert("fp-b-1", "10.0.1.1"),
+    ];
+    let results: Vec<_> = pipeline
+        .process_batch(batch)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    // Per-input ordering is preserved
+    let fingerprints: Vec<&str> = results.iter().map(|a| a.fingerprint.as_str()).collect();
+    assert_eq!(
+        fingerprints,
+        vec!["fp-b-1", "fp-b-2", "fp-b-1", "fp-b-stored", "fp-b-3", "fp-b-1"]
+    );
+
+    // Duplicates inside the batch collapse onto the first occurrence
+    assert_eq!(results[2].id, results[0].id);
+    assert_eq!(results[5].id, results[0].id);
+    assert_eq!(results[2].count, 2);
+    assert_eq!(results[5].count, 3);
+    assert_ne!(results[1].id, results[0].id);
+
+    // ...and onto alerts stored before the batch
+    assert_eq!(results[3].id, stored.id);
+    assert_eq!(results[3].count, 2);
+
+    // Same device within the window shares a correlation id
+    assert!(results[0].correlation_id.is_some());
+    assert_eq!(results[4].correlation_id, results[0].correlation_id);
+    assert_ne!(results[1].correlation_id, results[0].correlation_id);
+
+    // 1 stored + 3 new rows, with counts persisted
+    assert_eq!(alert_repo::count(&pool).await.unwrap(), 4);
+    let first = alert_repo::get_by_id(&pool, &results[0].id).await.unwrap().unwrap();
+    assert_eq!(first.count, 3);
+    let stored = alert_repo::get_by_id(&pool, &stored.id).await.unwrap().unwrap();
+    assert_eq!(stored.count, 2);
+
+    assert_eq!(pipeline.metrics().duplicates_dropped, 3);
+    assert!(pipeline.process_batch(Vec::new()).await.is_empty());
+}
+
+/// Counts the alerts it receives.
+struct CountingTarget(Arc<AtomicUsize>);
+
+impl DispatchTarget for CountingTarget {
+    fn send<'a>(
+        &'a self,
+        _alert: &'a Alert,
+    ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn name(&self) -> &str {
+        "counting"
+    }
+}
+
+fn suppressed_pipeline(pool: DbPool, max_alerts: u32) -> (Pipeline, Arc<AtomicUsize>) {
+    let config = PipelineConfig {
+        suppression: Some(SuppressionWindow {
+            max_alerts,
+            window_secs: 60,
+        }),
+        ..PipelineConfig::default()
+    };
+    let mut pipeline = Pipeline::with_config(pool, EventBus::new(), config).unwrap();
+    let sent = Arc::new(AtomicUsize::new(0));
+    pipeline.add_dispatch_target(Box::new(CountingTarget(sent.clone())));
+    (pipeline, sent)
+}
+
+fn burst_alert(fingerprint: &str) -> NormalizedAlert {
+    NormalizedAlert {
+        source_tool: "suricata".to_string(),
+        severity: Severity::Medium,
+        category: AlertCategory::Intrusion,
+        title: "Burst".to_string(),
+        description: "Test".to_string(),
+        device_ip: Some("10.0.2.1".to_string()),
+        fingerprint: fingerprint.to_string(),
+        raw_data: serde_json::json!({}),
+        timestamp: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_suppression_collapses_burst() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let (pipeline, sent) = suppressed_pipeline(pool.clone(), 1);
+
+    let first = pipeline.process(burst_alert("fp-burst")).await.unwrap();
+    for expected in 2..=10 {
+        let alert = pipeline.process(burst_alert("fp-burst")).await.unwrap();
+        assert_eq!(alert.id, first.id);
+        assert_eq!(alert.count, expected);
+    }
+
+    // Only the first alert was dispatched; the rest skipped every stage
+    assert_eq!(sent.load(Ordering::SeqCst), 1);
+    let metrics = pipeline.metrics();
+    assert_eq!(metrics.alerts_suppressed, 9);
+    assert_eq!(metrics.dedup.count, 1);
+    assert_eq!(metrics.duplicates_dropped, 0);
+    let stored = alert_repo::get_by_id(&pool, &first.id).await.unwrap().unwrap();
+    assert_eq!(stored.count, 10);
+
+    // Other fingerprints aren't affected by the burst
+    pipeline.process(burst_alert("fp-other")).await.unwrap();
+    assert_eq!(sent.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_pipeline_suppression_concurrent_burst() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let (pipeline, sent) = suppressed_pipeline(pool.clone(), 1);
+
+    // Arrivals racing the first alert's insert wait for it instead of
+    // slipping past deduplication
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..8 {
+        let pipeline = pipeline.clone();
+        tasks.spawn(async move { pipeline.process(burst_alert("fp-race")).await });
+    }
+    let mut ids = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        ids.push(result.unwrap().unwrap().id);
+    }
+    ids.dedup();
+    assert_eq!(ids.len(), 1);
+
+    assert_eq!(sent.load(Ordering::SeqCst), 1);
+    assert_eq!(pipeline.metrics().alerts_suppressed, 7);
+    let stored = alert_repo::get_by_id(&pool, &ids[0]).await.unwrap().unwrap();
+    assert_eq!(stored.count, 8);
+}
+
+/// Hangs on the first alert it receives, then lets the rest through.
+struct HangOnceTarget {
+    entered: Arc<tokio::sync::Notify>,
+    calls: AtomicUsize,
+}
+
+impl DispatchTarget for HangOnceTarget {
+    fn send<'a>(
+        &'a self,
+        _alert: &'a Alert,
+    ) -> Pin<Box<dyn Future<Output = Result<(), PipelineError>> + Send + 'a>> {
+        let first = self.calls.fetch_add(1, Ordering::SeqCst) == 0;
+        Box::pin(async move {
+            if first {
+                self.entered.notify_one();
+                std::future::pending::<()>().await;
+            }
+            Ok(())
+        })
+    }
+
+    fn name(&self) -> &str {
+        "hang-once"
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_suppression_cancelled_lead_releases_burst() {
+    let pool = create_test_pool().await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let (mut pipeline, _) = suppressed_pipeline(pool.clone(), 1);
+    let entered = Arc::new(tokio::sync::Notify::new());
+    pipeline.add_dispatch_target(Box::new(HangOnceTarget {
+        entered: entered.clone(),
+        calls: AtomicUsize::new(0),
+    }));
+
+    let lead = {
+        let pipeline = pipeline.clone();
+        tokio::spawn(async move { pipeline.process(burst_alert("fp-cancel")).await })
+    };
+    entered.notified().await;
+    let waiter = {
+        let pipeline = pipeline.clone();
+        tokio::spawn(async move { pipeline.process(burst_alert("fp-cancel")).await })
+    };
+    tokio::task::yield_now().await;
+
+    // Dropping the lead mid-dispatch must not strand the alert waiting on it
+    lead.abort();
+    assert!(lead.await.unwrap_err().is_cancelled());
+    let result = tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+        .await
+        .expect("suppressed alert hung on a cancelled lead");
+    assert!(result.unwrap().is_ok());
+    assert_eq!(pipeline.metrics().alerts_suppressed, 0);
+}
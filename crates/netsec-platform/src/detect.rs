@@ -1,4 +1,8 @@
-//! Operating system detection.
+//! Operating system detection and tool binary resolution.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
 
 use serde::{Deserialize, Serialize};
 
@@ -24,3 +28,258 @@ pub fn detect_platform() -> OsType {
         OsType::Unknown
     }
 }
+
+/// Install directories checked after `PATH`.
+const COMMON_BIN_DIRS: &[&str] = &[
+    "/usr/bin",
+    "/usr/local/bin",
+    "/usr/sbin",
+    "/opt/homebrew/bin",
+    r"C:\Program Files (x86)\Nmap",
+    r"C:\Program Files\Nmap",
+];
+
+/// Filesystem check used by [`BinaryResolver`]: is `path` a usable binary?
+pub type FileLookup = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// Whether `path` is a file the current user could execute: on Unix, a
+/// regular file with an execute bit set; elsewhere, any existing file.
+pub fn is_executable(path: &Path) -> bool {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        meta.is_file()
+    }
+}
+
+static SHARED_RESOLVER: LazyLock<BinaryResolver> = LazyLock::new(BinaryResolver::new);
+
+/// Finds tool binaries (`nmap`, `masscan`, ...) and remembers where they are.
+///
+/// Lookups check user-pinned overrides first, then each `PATH` entry, then
+/// common install locations. Hits are cached until [`invalidate`]d; misses
+/// are not, so a tool installed later is found on the next call. Clones share
+/// the cache and overrides.
+///
+/// [`invalidate`]: BinaryResolver::invalidate
+#[derive(Clone)]
+pub struct BinaryResolver {
+    cache: Arc<Mutex<HashMap<String, PathBuf>>>,
+    overrides: Arc<Mutex<HashMap<String, PathBuf>>>,
+    search_dirs: Vec<PathBuf>,
+    exists: FileLookup,
+}
+
+impl std::fmt::Debug for BinaryResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinaryResolver")
+            .field("search_dirs", &self.search_dirs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for BinaryResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinaryResolver {
+    /// A resolver over the current `PATH` and the common install locations.
+    /// Files that aren't executable are skipped, as a shell would.
+    pub fn new() -> Self {
+        let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        dirs.extend(COMMON_BIN_DIRS.iter().map(PathBuf::from));
+        Self::with_lookup(dirs, Arc::new(is_executable))
+    }
+
+    /// A resolver searching `search_dirs` in order, using `exists` to test
+    /// candidate paths.
+    pub fn with_lookup(search_dirs: Vec<PathBuf>, exists: FileLookup) -> Self {
+        Self {
+            cache: Arc::default(),
+            overrides: Arc::default(),
+            search_dirs,
+            exists,
+        }
+    }
+
+    /// The process-wide resolver the scanners share.
+    pub fn shared() -> &'static BinaryResolver {
+        &SHARED_RESOLVER
+    }
+
+    /// Pin `binary` to `path`. A pinned binary resolves only to that path,
+    /// never to a search hit.
+    pub fn set_override(&self, binary: &str, path: impl Into<PathBuf>) {
+        lock(&self.overrides).insert(binary.to_string(), path.into());
+        self.invalidate(binary);
+    }
+
+    /// Remove a pin set with [`BinaryResolver::set_override`].
+    pub fn clear_override(&self, binary: &str) {
+        lock(&self.overrides).remove(binary);
+        self.invalidate(binary);
+    }
+
+    /// Path to `binary`, or `None` if it can't be found (or its pinned path
+    /// doesn't pass the lookup, e.g. isn't executable).
+    pub fn resolve(&self, binary: &str) -> Option<PathBuf> {
+        if let Some(path) = lock(&self.cache).get(binary) {
+            return Some(path.clone());
+        }
+        let pinned = lock(&self.overrides).get(binary).cloned();
+        let found = match pinned {
+            Some(path) => {
+                let ok = (self.exists)(&path);
+                if !ok {
+                    tracing::warn!(
+                        "Pinned {binary} path {} does not exist or is not executable",
+                        path.display()
+                    );
+                }
+                ok.then_some(path)
+            }
+            None => self.search(binary),
+        }?;
+        lock(&self.cache).insert(binary.to_string(), found.clone());
+        Some(found)
+    }
+
+    fn search(&self, binary: &str) -> Option<PathBuf> {
+        let names: Vec<String> = if cfg!(windows) && Path::new(binary).extension().is_none() {
+            vec![format!("{binary}.exe"), binary.to_string()]
+        } else {
+            vec![binary.to_string()]
+        };
+        self.search_dirs
+            .iter()
+            .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .find(|candidate| (self.exists)(candidate))
+    }
+
+    /// Forget the cached path for `binary`, e.g. after it failed to launch.
+    pub fn invalidate(&self, binary: &str) {
+        lock(&self.cache).remove(binary);
+    }
+
+    /// Forget every cached path.
+    pub fn invalidate_all(&self) {
+        lock(&self.cache).clear();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A resolver over a fake filesystem, counting lookups.
+    fn fake(files: &[&str]) -> (BinaryResolver, Arc<Mutex<HashSet<PathBuf>>>, Arc<AtomicUsize>) {
+        let fs: Arc<Mutex<HashSet<PathBuf>>> =
+            Arc::new(Mutex::new(files.iter().map(PathBuf::from).collect()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (fs2, calls2) = (fs.clone(), calls.clone());
+        let resolver = BinaryResolver::with_lookup(
+            vec![PathBuf::from("/usr/bin"), PathBuf::from("/opt/tools")],
+            Arc::new(move |p: &Path| {
+                calls2.fetch_add(1, Ordering::SeqCst);
+                fs2.lock().unwrap().contains(p)
+            }),
+        );
+        (resolver, fs, calls)
+    }
+
+    #[test]
+    fn test_resolve_caches_hits() {
+        let (resolver, fs, calls) = fake(&["/opt/tools/nmap"]);
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/opt/tools/nmap")));
+        let after_first = calls.load(Ordering::SeqCst);
+        assert!(after_first >= 2);
+
+        // Cached: no filesystem access, even if the file has since moved
+        fs.lock().unwrap().clear();
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/opt/tools/nmap")));
+        assert_eq!(calls.load(Ordering::SeqCst), after_first);
+
+        resolver.invalidate("nmap");
+        assert_eq!(resolver.resolve("nmap"), None);
+    }
+
+    #[test]
+    fn test_misses_are_not_cached() {
+        let (resolver, fs, _) = fake(&[]);
+        assert_eq!(resolver.resolve("masscan"), None);
+        fs.lock().unwrap().insert(PathBuf::from("/usr/bin/masscan"));
+        assert_eq!(resolver.resolve("masscan"), Some(PathBuf::from("/usr/bin/masscan")));
+    }
+
+    #[test]
+    fn test_search_order() {
+        let (resolver, _, _) = fake(&["/usr/bin/nmap", "/opt/tools/nmap"]);
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/usr/bin/nmap")));
+    }
+
+    #[test]
+    fn test_override_pins_path() {
+        let (resolver, _, _) = fake(&["/usr/bin/nmap", "/home/me/nmap-dev/nmap"]);
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/usr/bin/nmap")));
+
+        // Pinning replaces the cached search hit
+        resolver.set_override("nmap", "/home/me/nmap-dev/nmap");
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/home/me/nmap-dev/nmap")));
+
+        // A missing pinned path doesn't fall back to the search
+        resolver.set_override("nmap", "/does/not/exist");
+        assert_eq!(resolver.resolve("nmap"), None);
+
+        resolver.clear_override("nmap");
+        assert_eq!(resolver.resolve("nmap"), Some(PathBuf::from("/usr/bin/nmap")));
+    }
+
+    #[test]
+    fn test_clones_share_cache() {
+        let (resolver, fs, _) = fake(&["/usr/bin/nmap"]);
+        let clone = resolver.clone();
+        assert!(clone.resolve("nmap").is_some());
+        fs.lock().unwrap().clear();
+        assert!(resolver.resolve("nmap").is_some());
+        clone.invalidate_all();
+        assert!(resolver.resolve("nmap").is_none());
+    }
+
+    #[test]
+    fn test_is_executable() {
+        let dir = std::env::temp_dir().join(format!("netsec-exec-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("tool");
+        std::fs::write(&file, "#!/bin/sh\n").unwrap();
+
+        assert!(!is_executable(&dir.join("missing")));
+        // Directories never qualify, executable bit or not
+        assert!(!is_executable(&dir));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert!(!is_executable(&file));
+            std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert!(is_executable(&file));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,120 @@
-//! Privilege and elevation checking.
+//! Privilege and elevation checking, and dropping root after privileged work.
 //!
-//! Cross-platform implementation using `std::process::Command`.
+//! Cross-platform implementation using `std::process::Command`; dropping
+//! privileges uses `setgid`/`setuid` on Unix and is a no-op on Windows.
+
+/// Errors dropping privileges.
+#[derive(Debug, thiserror::Error)]
+pub enum PrivError {
+    #[error("unknown user: {0}")]
+    UnknownUser(String),
+    #[error("reading user database failed: {0}")]
+    UserDatabase(std::io::Error),
+    #[error("{call} failed: {source}")]
+    Syscall {
+        call: &'static str,
+        source: std::io::Error,
+    },
+    #[error("root privileges could be regained after dropping to {0}")]
+    NotDropped(String),
+}
+
+/// A user's numeric ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserIds {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+/// Look up `user` in `/etc/passwd`-format content.
+///
+/// Comment and malformed lines are skipped.
+pub fn parse_passwd_entry(passwd: &str, user: &str) -> Option<UserIds> {
+    passwd
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let mut fields = line.split(':');
+            if fields.next()? != user {
+                return None;
+            }
+            let _password = fields.next()?;
+            let uid = fields.next()?.trim().parse().ok()?;
+            let gid = fields.next()?.trim().parse().ok()?;
+            Some(UserIds { uid, gid })
+        })
+}
+
+/// Resolve `user` to its uid/gid from `/etc/passwd`.
+pub fn resolve_user(user: &str) -> Result<UserIds, PrivError> {
+    let passwd = std::fs::read_to_string("/etc/passwd").map_err(PrivError::UserDatabase)?;
+    parse_passwd_entry(&passwd, user).ok_or_else(|| PrivError::UnknownUser(user.to_string()))
+}
+
+/// Permanently switch the process to `to_user`.
+///
+/// - **Unix**: resets supplementary groups, then `setgid` and `setuid` to the
+///   user's ids, and checks root can't be regained. This is one-way: nothing
+///   the process runs afterwards (nmap included) gets root back. Already
+///   running as `to_user` is not an error.
+/// - **Windows/other**: a no-op; the process keeps its token.
+pub fn drop_privileges(to_user: &str) -> Result<(), PrivError> {
+    #[cfg(unix)]
+    {
+        let ids = resolve_user(to_user)?;
+        // SAFETY: plain libc calls with no pointers except the one-element
+        // group list, which outlives the call.
+        unsafe {
+            if libc::geteuid() == ids.uid && libc::getegid() == ids.gid {
+                return Ok(());
+            }
+            let groups = [ids.gid as libc::gid_t];
+            if libc::setgroups(1, groups.as_ptr()) != 0 {
+                return Err(syscall_err("setgroups"));
+            }
+            if libc::setgid(ids.gid) != 0 {
+                return Err(syscall_err("setgid"));
+            }
+            if libc::setuid(ids.uid) != 0 {
+                return Err(syscall_err("setuid"));
+            }
+            if ids.uid != 0 && libc::setuid(0) == 0 {
+                return Err(PrivError::NotDropped(to_user.to_string()));
+            }
+        }
+        tracing::info!("Dropped privileges to {to_user} (uid {}, gid {})", ids.uid, ids.gid);
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        tracing::debug!("drop_privileges({to_user}) is a no-op on this platform");
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn syscall_err(call: &'static str) -> PrivError {
+    PrivError::Syscall {
+        call,
+        source: std::io::Error::last_os_error(),
+    }
+}
+
+/// Run `f` with the current (elevated) privileges, then drop to `to_user`.
+///
+/// The drop is permanent for the whole process, so do every operation that
+/// needs root (opening raw sockets, binding low ports) inside `f`; handles
+/// opened there stay usable afterwards. On failure to drop, `f`'s result is
+/// discarded and the process should not continue as a long-lived service.
+pub fn with_privileges<F, T>(to_user: &str, f: F) -> Result<T, PrivError>
+where
+    F: FnOnce() -> T,
+{
+    let result = f();
+    drop_privileges(to_user)?;
+    Ok(result)
+}
 
 /// Check if the current process has elevated/admin privileges.
 ///
@@ -55,6 +169,59 @@ mod tests {
         assert_eq!(first, second);
     }
 
+    #[test]
+    fn test_parse_passwd_entry() {
+        let passwd = "# local users\n\
+                      root:x:0:0:root:/root:/bin/bash\n\
+                      broken line\n\
+                      netsec:x:998:997:NetSec service:/var/lib/netsec:/usr/sbin/nologin\n\
+                      nobody:x:65534:65534:nobody:/nonexistent:/usr/sbin/nologin\n";
+        assert_eq!(parse_passwd_entry(passwd, "root"), Some(UserIds { uid: 0, gid: 0 }));
+        assert_eq!(
+            parse_passwd_entry(passwd, "netsec"),
+            Some(UserIds { uid: 998, gid: 997 })
+        );
+        assert_eq!(parse_passwd_entry(passwd, "net"), None);
+        assert_eq!(parse_passwd_entry(passwd, "broken line"), None);
+        assert_eq!(parse_passwd_entry("bad:x:abc:1:::\n", "bad"), None);
+    }
+
+    #[test]
+    fn test_resolve_unknown_user() {
+        assert!(matches!(
+            resolve_user("no-such-user-netsec-test"),
+            Err(PrivError::UnknownUser(_)) | Err(PrivError::UserDatabase(_))
+        ));
+    }
+
+    /// Dropping privileges changes the whole process, so the drop itself runs
+    /// in a child copy of the test binary.
+    #[cfg(unix)]
+    #[test]
+    fn test_drop_privileges_as_root() {
+        const CHILD_ENV: &str = "NETSEC_TEST_DROP_PRIVILEGES";
+        if std::env::var_os(CHILD_ENV).is_some() {
+            let ran = with_privileges("nobody", || "privileged work").unwrap();
+            assert_eq!(ran, "privileged work");
+            // SAFETY: plain libc getters
+            let (euid, egid) = unsafe { (libc::geteuid(), libc::getegid()) };
+            assert_eq!((euid, egid), (65534, 65534));
+            assert_eq!(unsafe { libc::setuid(0) }, -1);
+            return;
+        }
+        if !is_elevated() || resolve_user("nobody").ok() != Some(UserIds { uid: 65534, gid: 65534 })
+        {
+            eprintln!("skipping: needs root and a nobody user with uid/gid 65534");
+            return;
+        }
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "privileges::tests::test_drop_privileges_as_root"])
+            .env(CHILD_ENV, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
     #[test]
     fn test_platform_detection_consistent() {
         let platform = detect_platform();
@@ -2,6 +2,10 @@
 //!
 //! Provides pure parsing functions for systemctl and sc query output,
 //! plus a real `get_service_status` function that invokes the appropriate command.
+//! [`install_service`] and [`uninstall_service`] register the backend to start
+//! at boot: a systemd unit on Linux, an SCM service on Windows.
+
+use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
@@ -161,6 +165,287 @@ pub async fn get_service_status(name: &str) -> ServiceStatus {
     }
 }
 
+/// Errors installing or removing a service.
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceError {
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("invalid service spec: {0}")]
+    InvalidSpec(String),
+    #[error("{command} failed: {message}")]
+    CommandFailed { command: String, message: String },
+    #[error("service io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("service installation is not supported on this platform")]
+    Unsupported,
+}
+
+/// When the service manager restarts the service after it exits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    Never,
+    #[default]
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    /// The systemd `Restart=` value.
+    pub fn systemd_value(&self) -> &'static str {
+        match self {
+            Self::Never => "no",
+            Self::OnFailure => "on-failure",
+            Self::Always => "always",
+        }
+    }
+}
+
+/// What to register as a boot-time service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSpec {
+    /// Service name, without a `.service` suffix.
+    pub name: String,
+    /// Absolute path of the executable.
+    pub exec_path: PathBuf,
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+}
+
+impl ServiceSpec {
+    /// Check the name is safe to use as a unit/service name and the
+    /// executable path is absolute.
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        validate_name(&self.name)?;
+        if !self.exec_path.is_absolute() {
+            return Err(ServiceError::InvalidSpec(format!(
+                "exec path must be absolute: {}",
+                self.exec_path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), ServiceError> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(ServiceError::InvalidSpec(format!("invalid service name: {name:?}")))
+    }
+}
+
+/// Quote one `ExecStart=` word: double-quoted when it contains whitespace,
+/// quotes or backslashes, with `%` and `$` doubled so systemd doesn't expand
+/// specifiers or environment variables in it.
+fn systemd_quote(word: &str) -> String {
+    let word = word.replace('%', "%%").replace('$', "$$");
+    if !word.is_empty() && !word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        return word;
+    }
+    format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Render the systemd unit file for `spec`.
+pub fn render_systemd_unit(spec: &ServiceSpec) -> String {
+    let exec: Vec<String> = std::iter::once(spec.exec_path.to_string_lossy().into_owned())
+        .chain(spec.args.iter().cloned())
+        .map(|w| systemd_quote(&w))
+        .collect();
+    let mut unit = format!(
+        "[Unit]\n\
+         Description={name}\n\
+         Wants=network-online.target\n\
+         After=network-online.target\n\
+         \n\
+         [Service]\n\
+         Type=simple\n\
+         ExecStart={exec}\n\
+         Restart={restart}\n",
+        name = spec.name,
+        exec = exec.join(" "),
+        restart = spec.restart.systemd_value(),
+    );
+    if spec.restart != RestartPolicy::Never {
+        unit.push_str("RestartSec=5\n");
+    }
+    unit.push_str("\n[Install]\nWantedBy=multi-user.target\n");
+    unit
+}
+
+/// Render the `sc create` `binPath=` value for `spec`: the executable and
+/// every argument containing whitespace are double-quoted.
+pub fn render_windows_bin_path(spec: &ServiceSpec) -> String {
+    let quote = |w: &str| {
+        if w.is_empty() || w.contains(char::is_whitespace) {
+            format!("\"{}\"", w.replace('"', "\\\""))
+        } else {
+            w.to_string()
+        }
+    };
+    let exe = format!("\"{}\"", spec.exec_path.to_string_lossy());
+    std::iter::once(exe)
+        .chain(spec.args.iter().map(|a| quote(a)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Path of the systemd unit file for service `name`.
+#[cfg(target_os = "linux")]
+pub fn systemd_unit_path(name: &str) -> PathBuf {
+    PathBuf::from("/etc/systemd/system").join(format!("{name}.service"))
+}
+
+/// Run a service-manager command, mapping "access denied" failures to
+/// [`ServiceError::PermissionDenied`].
+#[cfg(any(target_os = "linux", windows))]
+async fn run_command(program: &str, args: &[&str]) -> Result<(), ServiceError> {
+    let command = format!("{program} {}", args.join(" "));
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| ServiceError::CommandFailed {
+            command: command.clone(),
+            message: e.to_string(),
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let message = [&output.stdout, &output.stderr]
+        .iter()
+        .map(|out| String::from_utf8_lossy(out).trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let lower = message.to_lowercase();
+    // sc reports ERROR_ACCESS_DENIED as exit code 5
+    if lower.contains("access denied")
+        || lower.contains("access is denied")
+        || lower.contains("interactive authentication required")
+        || (cfg!(windows) && output.status.code() == Some(5))
+    {
+        return Err(ServiceError::PermissionDenied(format!("{command}: {message}")));
+    }
+    Err(ServiceError::CommandFailed { command, message })
+}
+
+#[cfg(any(target_os = "linux", windows))]
+fn require_elevated(action: &str) -> Result<(), ServiceError> {
+    if crate::privileges::is_elevated() {
+        Ok(())
+    } else {
+        Err(ServiceError::PermissionDenied(format!(
+            "{action} requires root/administrator privileges"
+        )))
+    }
+}
+
+/// Register `spec` to start at boot.
+///
+/// - **Linux**: writes `/etc/systemd/system/{name}.service`, reloads systemd
+///   and enables the unit.
+/// - **Windows**: creates an auto-start service with `sc create` and, unless
+///   the policy is [`RestartPolicy::Never`], restart-on-failure actions.
+/// - **Other**: [`ServiceError::Unsupported`].
+///
+/// Fails with [`ServiceError::PermissionDenied`] when not elevated.
+pub async fn install_service(spec: ServiceSpec) -> Result<(), ServiceError> {
+    spec.validate()?;
+
+    #[cfg(target_os = "linux")]
+    {
+        require_elevated("installing a service")?;
+        let path = systemd_unit_path(&spec.name);
+        tokio::fs::write(&path, render_systemd_unit(&spec))
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => {
+                    ServiceError::PermissionDenied(format!("writing {}: {e}", path.display()))
+                }
+                _ => ServiceError::Io(e),
+            })?;
+        run_command("systemctl", &["daemon-reload"]).await?;
+        run_command("systemctl", &["enable", &format!("{}.service", spec.name)]).await?;
+        tracing::info!("Installed systemd unit {}", path.display());
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        require_elevated("installing a service")?;
+        let bin_path = render_windows_bin_path(&spec);
+        run_command(
+            "sc",
+            &["create", &spec.name, "binPath=", &bin_path, "start=", "auto"],
+        )
+        .await?;
+        let actions = match spec.restart {
+            RestartPolicy::Never => None,
+            // The SCM only acts on abnormal exits, so both map to restart
+            RestartPolicy::OnFailure | RestartPolicy::Always => Some("restart/5000"),
+        };
+        if let Some(actions) = actions {
+            run_command(
+                "sc",
+                &["failure", &spec.name, "reset=", "86400", "actions=", actions],
+            )
+            .await?;
+        }
+        tracing::info!("Installed Windows service {}", spec.name);
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    {
+        Err(ServiceError::Unsupported)
+    }
+}
+
+/// Stop and remove a service registered by [`install_service`].
+///
+/// Fails with [`ServiceError::PermissionDenied`] when not elevated.
+pub async fn uninstall_service(name: &str) -> Result<(), ServiceError> {
+    validate_name(name)?;
+
+    #[cfg(target_os = "linux")]
+    {
+        require_elevated("removing a service")?;
+        let unit = format!("{name}.service");
+        run_command("systemctl", &["disable", "--now", &unit]).await?;
+        let path = systemd_unit_path(name);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(ServiceError::Io(e)),
+        }
+        run_command("systemctl", &["daemon-reload"]).await?;
+        tracing::info!("Removed systemd unit {}", path.display());
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        require_elevated("removing a service")?;
+        // A service that isn't running fails to stop; deleting is what matters
+        let _ = run_command("sc", &["stop", name]).await;
+        run_command("sc", &["delete", name]).await?;
+        tracing::info!("Removed Windows service {name}");
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    {
+        Err(ServiceError::Unsupported)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +520,79 @@ mod tests {
         assert_eq!(status.pid, None);
         assert_eq!(status.name, "unknown");
     }
+
+    fn spec(args: &[&str], restart: RestartPolicy) -> ServiceSpec {
+        ServiceSpec {
+            name: "netsec".to_string(),
+            exec_path: PathBuf::from("/opt/netsec/bin/netsec-core"),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            restart,
+        }
+    }
+
+    #[test]
+    fn test_render_systemd_unit() {
+        let unit = render_systemd_unit(&spec(&["serve", "--port", "8420"], RestartPolicy::Always));
+        assert_eq!(
+            unit,
+            "[Unit]\n\
+             Description=netsec\n\
+             Wants=network-online.target\n\
+             After=network-online.target\n\
+             \n\
+             [Service]\n\
+             Type=simple\n\
+             ExecStart=/opt/netsec/bin/netsec-core serve --port 8420\n\
+             Restart=always\n\
+             RestartSec=5\n\
+             \n\
+             [Install]\n\
+             WantedBy=multi-user.target\n"
+        );
+    }
+
+    #[test]
+    fn test_render_systemd_unit_quoting_and_restart() {
+        let unit = render_systemd_unit(&spec(
+            &["--db", "/var/lib/net sec/db.sqlite", "--fmt=%H", "say \"hi\"", "$HOME/${USER}"],
+            RestartPolicy::Never,
+        ));
+        assert!(unit.contains(
+            "ExecStart=/opt/netsec/bin/netsec-core --db \"/var/lib/net sec/db.sqlite\" \
+             --fmt=%%H \"say \\\"hi\\\"\" $$HOME/$${USER}\n"
+        ));
+        assert!(unit.contains("Restart=no\n"));
+        assert!(!unit.contains("RestartSec"));
+
+        let unit = render_systemd_unit(&spec(&[], RestartPolicy::OnFailure));
+        assert!(unit.contains("ExecStart=/opt/netsec/bin/netsec-core\n"));
+        assert!(unit.contains("Restart=on-failure\n"));
+    }
+
+    #[test]
+    fn test_render_windows_bin_path() {
+        let mut s = spec(
+            &["serve", "--config", "C:\\Program Files\\netsec\\cfg.toml"],
+            RestartPolicy::Always,
+        );
+        s.exec_path = PathBuf::from("C:\\Program Files\\netsec\\netsec-core.exe");
+        assert_eq!(
+            render_windows_bin_path(&s),
+            "\"C:\\Program Files\\netsec\\netsec-core.exe\" serve --config \
+             \"C:\\Program Files\\netsec\\cfg.toml\""
+        );
+    }
+
+    #[test]
+    fn test_spec_validation() {
+        assert!(spec(&[], RestartPolicy::Always).validate().is_ok());
+        for name in ["", "net sec", "../etc/passwd", "a;b"] {
+            let mut s = spec(&[], RestartPolicy::Always);
+            s.name = name.to_string();
+            assert!(matches!(s.validate(), Err(ServiceError::InvalidSpec(_))), "{name:?}");
+        }
+        let mut s = spec(&[], RestartPolicy::Always);
+        s.exec_path = PathBuf::from("bin/netsec-core");
+        assert!(matches!(s.validate(), Err(ServiceError::InvalidSpec(_))));
+    }
 }
@@ -1,10 +1,13 @@
 //! Cross-platform PTY abstraction for terminal emulation.
 //!
 //! This crate provides a unified interface for creating and managing
-//! pseudo-terminal sessions across Windows and Unix platforms.
+//! pseudo-terminal sessions across Windows and Unix platforms, with
+//! optional asciinema-format session recording.
 
+mod recording;
 mod session;
 mod shell;
 
-pub use session::{PtySession, PtyError};
+pub use recording::replay;
+pub use session::{PtyError, PtyOptions, PtySession};
 pub use shell::{ShellInfo, detect_available_shells};
@@ -0,0 +1,216 @@
+//! Session recording and replay in asciinema v2 format.
+//!
+//! A recording is a header line (JSON object with `version`, `width`,
+//! `height` and `timestamp`) followed by one `[seconds, "o", data]` event per
+//! output chunk, so files play back with `asciinema play` as well as [`replay`].
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::session::PtyError;
+
+/// Feeds output chunks to a background writer thread.
+///
+/// Recording only costs the PTY read loop a channel send; the file is written
+/// on the recorder's own thread, which exits once the recorder is dropped and
+/// every queued chunk is written.
+pub(crate) struct Recorder {
+    tx: mpsc::Sender<(Duration, Vec<u8>)>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Create `path` (truncating it), write the header and start the writer.
+    pub(crate) fn start(
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        shell: &str,
+    ) -> Result<Self, PtyError> {
+        let mut out = BufWriter::new(File::create(path)?);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": timestamp,
+            "env": { "TERM": "xterm-256color", "SHELL": shell },
+        });
+        writeln!(out, "{header}")?;
+        out.flush()?;
+
+        let (tx, rx) = mpsc::channel::<(Duration, Vec<u8>)>();
+        std::thread::spawn(move || {
+            // Bytes of a UTF-8 sequence split across chunks
+            let mut pending = Vec::new();
+            for (elapsed, chunk) in rx {
+                pending.extend_from_slice(&chunk);
+                let text = take_utf8(&mut pending);
+                if text.is_empty() {
+                    continue;
+                }
+                let event = serde_json::json!([elapsed.as_secs_f64(), "o", text]);
+                if let Err(e) = writeln!(out, "{event}").and_then(|_| out.flush()) {
+                    tracing::warn!("Stopping PTY recording: {}", e);
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            tx,
+            started: Instant::now(),
+        })
+    }
+
+    /// Queue an output chunk, stamped with the time since recording started.
+    pub(crate) fn record(&self, data: &[u8]) {
+        let _ = self.tx.send((self.started.elapsed(), data.to_vec()));
+    }
+}
+
+/// Drain the decodable prefix of `buf` as a string, leaving an incomplete
+/// trailing UTF-8 sequence behind. Invalid bytes become U+FFFD.
+fn take_utf8(buf: &mut Vec<u8>) -> String {
+    let mut text = String::new();
+    let mut rest: &[u8] = buf;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(s) => {
+                text.push_str(s);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(n) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[n..];
+                    }
+                    // Incomplete sequence at the end: wait for more bytes
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    *buf = rest.to_vec();
+    text
+}
+
+/// Read back a recording as `(delay, data)` frames, where `delay` is the time
+/// since the previous frame divided by `speed` (2.0 plays twice as fast; zero
+/// or a negative speed yields no delays).
+///
+/// Input (`"i"`) events and malformed lines are skipped.
+pub fn replay(
+    path: impl AsRef<Path>,
+    speed: f64,
+) -> Result<impl Iterator<Item = (Duration, Vec<u8>)>, PtyError> {
+    let mut lines = BufReader::new(File::open(path)?).lines();
+    // The header carries nothing replay needs
+    lines.next().transpose()?;
+
+    let mut previous = 0.0_f64;
+    Ok(lines.map_while(Result::ok).filter_map(move |line| {
+        let event: serde_json::Value = serde_json::from_str(&line).ok()?;
+        let time = event.get(0)?.as_f64()?;
+        if event.get(1)?.as_str()? != "o" {
+            return None;
+        }
+        let data = event.get(2)?.as_str()?.as_bytes().to_vec();
+
+        let gap = (time - previous).max(0.0);
+        previous = time;
+        let delay = if speed > 0.0 && speed.is_finite() {
+            Duration::from_secs_f64(gap / speed)
+        } else {
+            Duration::ZERO
+        };
+        Some((delay, data))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_utf8_keeps_split_sequence() {
+        // "é" is 0xC3 0xA9
+        let mut buf = vec![b'a', 0xC3];
+        assert_eq!(take_utf8(&mut buf), "a");
+        assert_eq!(buf, vec![0xC3]);
+        buf.push(0xA9);
+        assert_eq!(take_utf8(&mut buf), "é");
+        assert!(buf.is_empty());
+
+        let mut buf = vec![b'x', 0xFF, b'y'];
+        assert_eq!(take_utf8(&mut buf), "x\u{FFFD}y");
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let dir = std::env::temp_dir().join(format!("netsec-pty-rec-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.cast");
+
+        let recorder = Recorder::start(&path, 100, 30, "/bin/sh").unwrap();
+        recorder.record(b"$ ls\r\n");
+        std::thread::sleep(Duration::from_millis(200));
+        recorder.record(b"file.txt\r\n");
+        std::thread::sleep(Duration::from_millis(100));
+        recorder.record(&[0xE2, 0x9C]);
+        recorder.record(&[0x93, b'\n']); // "✓\n" split mid-character
+        drop(recorder);
+
+        // The writer thread finishes asynchronously once the channel closes
+        let mut frames = Vec::new();
+        for _ in 0..50 {
+            frames = replay(&path, 1.0).unwrap().collect();
+            if frames.len() == 3 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let header: serde_json::Value = serde_json::from_str(
+            std::fs::read_to_string(&path).unwrap().lines().next().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 100);
+        assert_eq!(header["height"], 30);
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].1, b"$ ls\r\n");
+        assert_eq!(frames[1].1, b"file.txt\r\n");
+        assert_eq!(frames[2].1, "✓\n".as_bytes());
+        assert!(frames[0].0 < Duration::from_millis(100));
+        assert!(frames[1].0 >= Duration::from_millis(200));
+        assert!(frames[1].0 < Duration::from_millis(600));
+        assert!(frames[2].0 >= Duration::from_millis(100));
+
+        let fast: Vec<_> = replay(&path, 2.0).unwrap().collect();
+        assert!(fast[1].0 >= Duration::from_millis(100));
+        assert!(fast[1].0 < frames[1].0);
+        assert!(replay(&path, 0.0).unwrap().all(|(d, _)| d.is_zero()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_replay_missing_file() {
+        assert!(replay("/nonexistent/netsec.cast", 1.0).is_err());
+    }
+}
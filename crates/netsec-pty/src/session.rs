@@ -1,10 +1,12 @@
 //! PTY session management.
 
-use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
+use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::recording::Recorder;
 use crate::shell::ShellInfo;
 
 /// Errors that can occur during PTY operations.
@@ -16,6 +18,9 @@ pub enum PtyError {
     #[error("Failed to spawn shell: {0}")]
     Spawn(String),
 
+    #[error("Failed to resize PTY: {0}")]
+    Resize(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -23,18 +28,83 @@ pub enum PtyError {
     NotInitialized,
 }
 
+/// How to spawn the shell for a [`PtySession`].
+#[derive(Debug, Clone)]
+pub struct PtyOptions {
+    /// Initial working directory; the current process's if `None`.
+    pub cwd: Option<PathBuf>,
+    /// Extra environment variables, applied on top of the inherited
+    /// environment and the `TERM` defaults.
+    pub env: Vec<(String, String)>,
+    pub shell: ShellInfo,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+impl PtyOptions {
+    /// Options for `shell` at 80x24 with the inherited cwd and environment.
+    pub fn new(shell: ShellInfo) -> Self {
+        Self {
+            cwd: None,
+            env: Vec::new(),
+            shell,
+            cols: 80,
+            rows: 24,
+        }
+    }
+}
+
 /// A PTY session wrapping a shell process.
+///
+/// The master side and current size sit behind a lock so the session can be
+/// resized through a shared reference, e.g. from the GUI thread while a
+/// reader thread drains output.
 pub struct PtySession {
-    pair: PtyPair,
+    master: std::sync::Mutex<Box<dyn MasterPty + Send>>,
     reader: Arc<Mutex<Box<dyn Read + Send>>>,
     writer: Arc<Mutex<Box<dyn Write + Send>>>,
     shell: ShellInfo,
-    size: PtySize,
+    size: std::sync::Mutex<PtySize>,
+    recorder: Arc<std::sync::Mutex<Option<Recorder>>>,
+}
+
+/// Passes output through while a recording is active.
+struct TeeReader {
+    inner: Box<dyn Read + Send>,
+    recorder: Arc<std::sync::Mutex<Option<Recorder>>>,
+}
+
+impl Read for TeeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            if let Some(recorder) = &*self.recorder.lock().unwrap_or_else(|e| e.into_inner()) {
+                recorder.record(&buf[..n]);
+            }
+        }
+        Ok(n)
+    }
 }
 
 impl PtySession {
     /// Create a new PTY session with the given shell.
     pub fn new(shell: &ShellInfo, cols: u16, rows: u16) -> Result<Self, PtyError> {
+        Self::spawn_with(PtyOptions {
+            cols,
+            rows,
+            ..PtyOptions::new(shell.clone())
+        })
+    }
+
+    /// Create a new PTY session, spawning the shell as described by `options`.
+    pub fn spawn_with(options: PtyOptions) -> Result<Self, PtyError> {
+        let PtyOptions {
+            cwd,
+            env,
+            shell,
+            cols,
+            rows,
+        } = options;
         let pty_system = native_pty_system();
 
         let size = PtySize {
@@ -64,11 +134,20 @@ impl PtySession {
             cmd.env("COLORTERM", "truecolor");
         }
 
-        // Spawn the shell
+        for (key, value) in &env {
+            cmd.env(key, value);
+        }
+        if let Some(cwd) = &cwd {
+            cmd.cwd(cwd);
+        }
+
+        // Spawn the shell. The slave end is dropped afterwards so reads see
+        // EOF once the shell exits.
         let _child = pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| PtyError::Spawn(e.to_string()))?;
+        drop(pair.slave);
 
         // Get reader and writer
         let reader = pair
@@ -81,12 +160,19 @@ impl PtySession {
             .take_writer()
             .map_err(|e| PtyError::Creation(e.to_string()))?;
 
+        let recorder = Arc::new(std::sync::Mutex::new(None));
+        let reader: Box<dyn Read + Send> = Box::new(TeeReader {
+            inner: reader,
+            recorder: Arc::clone(&recorder),
+        });
+
         Ok(Self {
-            pair,
+            master: std::sync::Mutex::new(pair.master),
             reader: Arc::new(Mutex::new(reader)),
             writer: Arc::new(Mutex::new(writer)),
-            shell: shell.clone(),
-            size,
+            shell,
+            size: std::sync::Mutex::new(size),
+            recorder,
         })
     }
 
@@ -97,26 +183,63 @@ impl PtySession {
 
     /// Get the current terminal size.
     pub fn size(&self) -> (u16, u16) {
-        (self.size.cols, self.size.rows)
+        let size = self.size.lock().unwrap_or_else(|e| e.into_inner());
+        (size.cols, size.rows)
     }
 
-    /// Resize the terminal.
-    pub fn resize(&mut self, cols: u16, rows: u16) -> Result<(), PtyError> {
-        self.size = PtySize {
+    /// Resize the terminal (`TIOCSWINSZ` on Unix, `ResizePseudoConsole` on
+    /// Windows), which signals the child with `SIGWINCH` on Unix.
+    ///
+    /// Resizing to the current size is a no-op, as is a zero dimension (sent
+    /// while a window is minimized).
+    pub fn resize(&self, cols: u16, rows: u16) -> Result<(), PtyError> {
+        if cols == 0 || rows == 0 {
+            return Ok(());
+        }
+        let mut size = self.size.lock().unwrap_or_else(|e| e.into_inner());
+        if size.cols == cols && size.rows == rows {
+            return Ok(());
+        }
+
+        let new_size = PtySize {
             rows,
             cols,
             pixel_width: 0,
             pixel_height: 0,
         };
+        self.master
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .resize(new_size)
+            .map_err(|e| PtyError::Resize(e.to_string()))?;
+        *size = new_size;
 
-        self.pair
-            .master
-            .resize(self.size)
-            .map_err(|e| PtyError::Creation(e.to_string()))?;
+        Ok(())
+    }
 
+    /// Record all further output to `path` in asciinema v2 format, replacing
+    /// any recording already in progress. The file is created or truncated.
+    ///
+    /// Chunks are written on a background thread, so reading the PTY never
+    /// waits on disk I/O.
+    pub fn start_recording(&self, path: impl AsRef<Path>) -> Result<(), PtyError> {
+        let (cols, rows) = self.size();
+        let recorder =
+            Recorder::start(path.as_ref(), cols, rows, &self.shell.path.to_string_lossy())?;
+        *self.recorder.lock().unwrap_or_else(|e| e.into_inner()) = Some(recorder);
         Ok(())
     }
 
+    /// Stop recording. Output already captured is still written out.
+    pub fn stop_recording(&self) {
+        self.recorder.lock().unwrap_or_else(|e| e.into_inner()).take();
+    }
+
+    /// Whether output is currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
     /// Write data to the PTY (send input to the shell).
     pub async fn write(&self, data: &[u8]) -> Result<(), PtyError> {
         let mut writer = self.writer.lock().await;
@@ -149,7 +272,104 @@ impl std::fmt::Debug for PtySession {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PtySession")
             .field("shell", &self.shell)
-            .field("size", &format!("{}x{}", self.size.cols, self.size.rows))
+            .field("size", &format!("{}x{}", self.size().0, self.size().1))
             .finish()
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    fn sh() -> ShellInfo {
+        ShellInfo::new("sh", "Shell", "/bin/sh")
+    }
+
+    /// Run `command` in the session and wait until its output contains `needle`.
+    fn run_until(session: &PtySession, command: &str, needle: &str) -> String {
+        let reader = session.reader();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 1024];
+            loop {
+                let n = match reader.blocking_lock().read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                if tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        rt.block_on(session.write(command.as_bytes())).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut output = String::new();
+        while !output.contains(needle) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match rx.recv_timeout(left) {
+                Ok(chunk) => output.push_str(&String::from_utf8_lossy(&chunk)),
+                Err(_) => panic!("timed out waiting for {needle:?}; got {output:?}"),
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_resize_visible_to_child() {
+        let session = PtySession::new(&sh(), 80, 24).unwrap();
+        session.resize(100, 40).unwrap();
+        // Repeated and zero-sized resizes are harmless
+        session.resize(100, 40).unwrap();
+        session.resize(0, 0).unwrap();
+        assert_eq!(session.size(), (100, 40));
+
+        // `stty size` prints "rows cols"; the marker keeps the echoed command
+        // line from matching
+        run_until(&session, "echo SIZE=$(stty size)\n", "SIZE=40 100");
+    }
+
+    #[test]
+    fn test_recording_captures_output() {
+        let path = std::env::temp_dir().join(format!("netsec-pty-{}.cast", std::process::id()));
+        let session = PtySession::new(&sh(), 80, 24).unwrap();
+        session.start_recording(&path).unwrap();
+        assert!(session.is_recording());
+        run_until(&session, "echo REC=$((40 + 2))\n", "REC=42");
+        session.stop_recording();
+        assert!(!session.is_recording());
+
+        let mut played = String::new();
+        for _ in 0..50 {
+            played = crate::replay(&path, 1.0)
+                .unwrap()
+                .map(|(_, data)| String::from_utf8(data).unwrap())
+                .collect();
+            if played.contains("REC=42") {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(played.contains("REC=42"), "{played:?}");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_spawn_with_cwd_and_env() {
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let session = PtySession::spawn_with(PtyOptions {
+            cwd: Some(dir.clone()),
+            env: vec![("NETSEC_PTY_TEST".into(), "hello-pty".into())],
+            ..PtyOptions::new(sh())
+        })
+        .unwrap();
+        assert_eq!(session.size(), (80, 24));
+
+        let expected = format!("VAR=hello-pty PWD={}", dir.display());
+        run_until(&session, "echo VAR=$NETSEC_PTY_TEST PWD=$(pwd)\n", &expected);
+    }
+}
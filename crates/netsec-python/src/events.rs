@@ -0,0 +1,145 @@
+//! PyO3 event bus bindings — wraps netsec-events for Python consumption.
+//!
+//! Event types cross the boundary as their snake_case names
+//! (`"scan_completed"`), and payloads as plain Python objects converted
+//! through the `json` module.
+
+// pyo3 0.22's `#[pyfunction]` expansion converts `PyErr` into itself
+#![allow(clippy::useless_conversion)]
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use netsec_events::EventBus;
+use netsec_models::event::{EventType, NetsecEvent};
+
+/// Parse a snake_case event type name.
+fn event_type_from_str(name: &str) -> PyResult<EventType> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("unknown event type: {name}")))
+}
+
+/// Convert a Python object to JSON. `json.dumps` raises `TypeError` for
+/// objects it can't serialize; that is reported as `ValueError`, like any
+/// other bad payload.
+fn to_value(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    let text: String = py
+        .import_bound("json")?
+        .call_method1("dumps", (obj,))
+        .map_err(|e| PyValueError::new_err(format!("payload is not JSON-serializable: {e}")))?
+        .extract()?;
+    serde_json::from_str(&text).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn to_py(py: Python<'_>, value: &impl serde::Serialize) -> PyResult<PyObject> {
+    let text = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py.import_bound("json")?.call_method1("loads", (text,))?.unbind())
+}
+
+/// The engine's event bus.
+#[pyclass(name = "EventBus")]
+pub struct PyEventBus {
+    bus: EventBus,
+}
+
+#[pymethods]
+impl PyEventBus {
+    #[new]
+    fn new() -> Self {
+        Self {
+            bus: EventBus::new(),
+        }
+    }
+
+    /// Publish an event; returns how many receivers it reached.
+    /// Raises `ValueError` for an unknown event type or a payload that
+    /// isn't JSON-serializable.
+    #[pyo3(signature = (event_type, payload = None))]
+    fn publish(
+        &self,
+        py: Python<'_>,
+        event_type: &str,
+        payload: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<usize> {
+        let event_type = event_type_from_str(event_type)?;
+        let payload = match payload {
+            Some(obj) => to_value(py, obj)?,
+            None => serde_json::json!({}),
+        };
+        // Nobody listening isn't an error for Python callers
+        Ok(self.bus.publish(NetsecEvent::new(event_type, payload)).unwrap_or(0))
+    }
+
+    /// A receiver for events published from now on.
+    fn subscribe(&self) -> PyResult<PyEventReceiver> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(PyEventReceiver {
+            rx: Mutex::new(self.bus.subscribe()),
+            runtime,
+        })
+    }
+
+    fn subscriber_count(&self) -> usize {
+        self.bus.subscriber_count()
+    }
+}
+
+/// Receives events from an `EventBus`.
+#[pyclass(name = "EventReceiver")]
+pub struct PyEventReceiver {
+    rx: Mutex<broadcast::Receiver<NetsecEvent>>,
+    runtime: tokio::runtime::Runtime,
+}
+
+#[pymethods]
+impl PyEventReceiver {
+    /// Wait for the next event and return it as a dict with `id`,
+    /// `event_type`, `payload` and `timestamp`, or `None` on timeout.
+    ///
+    /// The GIL is released while waiting, so asyncio code can use
+    /// `await asyncio.to_thread(receiver.recv, timeout)`. Events missed by a
+    /// lagging receiver are skipped.
+    #[pyo3(signature = (timeout = None))]
+    fn recv(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<Option<PyObject>> {
+        let event = py.allow_threads(|| {
+            let mut rx = self.rx.lock().unwrap_or_else(|e| e.into_inner());
+            self.runtime.block_on(async {
+                let next = async {
+                    loop {
+                        match rx.recv().await {
+                            Ok(event) => return Ok(event),
+                            Err(RecvError::Lagged(_)) => continue,
+                            Err(RecvError::Closed) => return Err(()),
+                        }
+                    }
+                };
+                match timeout {
+                    Some(secs) => {
+                        let limit =
+                            Duration::try_from_secs_f64(secs.max(0.0)).unwrap_or(Duration::MAX);
+                        tokio::time::timeout(limit, next).await.ok()
+                    }
+                    None => Some(next.await),
+                }
+            })
+        });
+        match event {
+            Some(Ok(event)) => to_py(py, &event).map(Some),
+            Some(Err(())) => Err(PyRuntimeError::new_err("event bus closed")),
+            None => Ok(None),
+        }
+    }
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyEventBus>()?;
+    m.add_class::<PyEventReceiver>()?;
+    Ok(())
+}
@@ -4,12 +4,17 @@
 //! call into for all core engine operations.
 //!
 //! Phase 0: Re-exports existing parser functions for backward compatibility.
-//! Phase 5: Full engine, services, event bus, scheduler bindings.
+//! Phase 5: Full engine, services, event bus, scheduler bindings. The event
+//! bus is exposed as `netsec_core.EventBus`, active scans as
+//! `netsec_core.Scanner` and trigger due checks under `netsec_core.scheduler`.
 
 use pyo3::prelude::*;
 
+mod events;
 // Re-export existing parser functions for backward compatibility
 mod parsers;
+mod scanner;
+mod scheduler;
 
 /// The netsec_core Python module.
 #[pymodule]
@@ -19,5 +24,13 @@ fn netsec_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     parsers::register(&parsers_mod)?;
     m.add_submodule(&parsers_mod)?;
 
+    // Scheduler sub-module (trigger parsing and due checks)
+    let scheduler_mod = PyModule::new_bound(m.py(), "scheduler")?;
+    scheduler::register(&scheduler_mod)?;
+    m.add_submodule(&scheduler_mod)?;
+
+    events::register(m)?;
+    scanner::register(m)?;
+
     Ok(())
 }
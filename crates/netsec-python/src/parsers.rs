@@ -3,11 +3,39 @@
 //! Provides backward-compatible functions matching the old netsec_nmap and
 //! netsec_stream Python modules.
 //!
-//! Stub — parser functions will be wired in Task #3 (move existing parsers).
+//! The Suricata EVE functions (from netsec_stream) are wired; the remaining
+//! parser functions will be wired in Task #3 (move existing parsers).
 
+// pyo3 0.22's `#[pyfunction]` expansion converts `PyErr` into itself
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-pub fn register(_m: &Bound<'_, PyModule>) -> PyResult<()> {
-    // Parser functions will be registered here after migration
+use netsec_parsers::suricata::{self, EveEvent};
+
+fn to_json(events: &[EveEvent]) -> PyResult<String> {
+    serde_json::to_string(events).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Parse newline-delimited EVE JSON into a JSON array of events.
+#[pyfunction]
+#[pyo3(signature = (data, alerts_only = false))]
+fn parse_eve_batch(data: &str, alerts_only: bool) -> PyResult<String> {
+    to_json(&suricata::parse_eve_batch(data, alerts_only))
+}
+
+/// Like `parse_eve_batch`, but takes bytes that may be gzip-compressed.
+/// Raises `ValueError` on a truncated or corrupt gzip stream.
+#[pyfunction]
+#[pyo3(signature = (data, alerts_only = false))]
+fn parse_eve_batch_gz(data: &[u8], alerts_only: bool) -> PyResult<String> {
+    let events = suricata::parse_eve_batch_gz(data, alerts_only).map_err(PyValueError::new_err)?;
+    to_json(&events)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_eve_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_eve_batch_gz, m)?)?;
     Ok(())
 }
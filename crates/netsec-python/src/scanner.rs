@@ -0,0 +1,135 @@
+//! PyO3 active scan bindings — wraps netsec-scanner's `ActiveScanner`.
+//!
+//! Scans run on a Tokio runtime owned by the `Scanner` object with the GIL
+//! released, so FastAPI routers can
+//! `await asyncio.to_thread(scanner.run_scan, ...)`.
+
+// pyo3 0.22's `#[pyfunction]` expansion converts `PyErr` into itself
+#![allow(clippy::useless_conversion)]
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use netsec_events::EventBus;
+use netsec_models::scan::ScanType;
+use netsec_scanner::active::{self, ActiveScanner, ScanConfig};
+
+create_exception!(netsec_core, ScannerError, PyException, "An active scan failed.");
+
+fn scanner_err(e: impl std::fmt::Display) -> PyErr {
+    ScannerError::new_err(e.to_string())
+}
+
+/// Turn a database path into a URL; URLs (`sqlite:...`, `postgres://...`)
+/// pass through unchanged.
+fn database_url(path: &str) -> String {
+    if path.starts_with("sqlite:") || path.contains("://") {
+        path.to_string()
+    } else {
+        format!("sqlite:{path}")
+    }
+}
+
+fn scan_config(
+    target: &str,
+    scan_type: &str,
+    timing: u8,
+    ports: Option<String>,
+    dry_run: bool,
+) -> PyResult<ScanConfig> {
+    let parsed = ScanType::from_str_lossy(scan_type);
+    if parsed.as_str() != scan_type {
+        return Err(scanner_err(format!("unknown scan type: {scan_type}")));
+    }
+    let config = ScanConfig {
+        target: target.to_string(),
+        scan_type: parsed,
+        timing,
+        ports,
+        dry_run,
+        ..Default::default()
+    };
+    config.validate().map_err(scanner_err)?;
+    Ok(config)
+}
+
+/// The nmap arguments a scan would run with. Raises `ScannerError` for an
+/// invalid configuration.
+#[pyfunction]
+#[pyo3(signature = (target, scan_type = "discovery", timing = 3, ports = None))]
+fn build_nmap_args(
+    target: &str,
+    scan_type: &str,
+    timing: u8,
+    ports: Option<String>,
+) -> PyResult<Vec<String>> {
+    let config = scan_config(target, scan_type, timing, ports, false)?;
+    Ok(active::build_nmap_args(&config))
+}
+
+/// Runs active scans against a database, publishing to an internal event bus.
+#[pyclass(name = "Scanner")]
+pub struct PyScanner {
+    runtime: tokio::runtime::Runtime,
+    scanner: ActiveScanner,
+}
+
+#[pymethods]
+impl PyScanner {
+    /// Open (and migrate) the database at `db_path`, a SQLite file path or a
+    /// database URL.
+    #[new]
+    fn new(py: Python<'_>, db_path: &str) -> PyResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(scanner_err)?;
+        let url = database_url(db_path);
+        let pool = py
+            .allow_threads(|| {
+                runtime.block_on(async {
+                    let pool = netsec_db::create_pool(&url).await.map_err(|e| e.to_string())?;
+                    netsec_db::run_migrations(&pool).await.map_err(|e| e.to_string())?;
+                    Ok::<_, String>(pool)
+                })
+            })
+            .map_err(|e| scanner_err(format!("database {url}: {e}")))?;
+        Ok(Self {
+            scanner: ActiveScanner::new(pool, EventBus::new()),
+            runtime,
+        })
+    }
+
+    /// Run a scan and return the discovered devices as dicts.
+    ///
+    /// Blocks with the GIL released until the scan finishes; with
+    /// `dry_run=True` nmap isn't run and no devices are returned. Raises
+    /// `ScannerError` if the configuration is invalid or the scan fails.
+    #[pyo3(signature = (
+        target, scan_type = "discovery", timing = 3, ports = None, dry_run = false
+    ))]
+    fn run_scan(
+        &self,
+        py: Python<'_>,
+        target: &str,
+        scan_type: &str,
+        timing: u8,
+        ports: Option<String>,
+        dry_run: bool,
+    ) -> PyResult<PyObject> {
+        let config = scan_config(target, scan_type, timing, ports, dry_run)?;
+        let devices = py
+            .allow_threads(|| self.runtime.block_on(self.scanner.run_scan(&config)))
+            .map_err(scanner_err)?;
+        let text = serde_json::to_string(&devices).map_err(scanner_err)?;
+        Ok(py.import_bound("json")?.call_method1("loads", (text,))?.unbind())
+    }
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ScannerError", m.py().get_type_bound::<ScannerError>())?;
+    m.add_class::<PyScanner>()?;
+    m.add_function(wrap_pyfunction!(build_nmap_args, m)?)?;
+    Ok(())
+}
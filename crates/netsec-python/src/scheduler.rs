@@ -0,0 +1,84 @@
+//! PyO3 scheduler bindings — the trigger parsing and due checks from
+//! netsec-scheduler, so the Python scheduler layer doesn't reimplement them.
+//!
+//! Times cross the boundary as ISO8601/RFC3339 strings; naive times are
+//! rejected rather than guessed at.
+
+// pyo3 0.22's `#[pyfunction]` expansion converts `PyErr` into itself
+#![allow(clippy::useless_conversion)]
+
+use std::time::Duration;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use netsec_models::time::{parse_rfc3339, to_rfc3339};
+
+fn value_err(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Seconds between runs from interval trigger args (`{"interval_secs": N}`).
+#[pyfunction]
+fn parse_interval_args(args: &str) -> PyResult<u64> {
+    netsec_scheduler::parse_interval_args(args)
+        .map(|d| d.as_secs())
+        .map_err(value_err)
+}
+
+/// Whether an interval job last run at `last_run` (or never) is due at `now_iso`.
+/// An unparseable `last_run` is never due.
+#[pyfunction]
+#[pyo3(signature = (last_run, interval_secs, now_iso))]
+fn interval_is_due(last_run: Option<&str>, interval_secs: u64, now_iso: &str) -> PyResult<bool> {
+    let now = parse_rfc3339(now_iso).map_err(value_err)?;
+    Ok(netsec_scheduler::is_interval_due_at(
+        last_run,
+        Duration::from_secs(interval_secs),
+        &now,
+    ))
+}
+
+/// The cron expression from cron trigger args (`{"cron": "..."}`).
+#[pyfunction]
+fn parse_cron_args(args: &str) -> PyResult<String> {
+    netsec_scheduler::parse_cron_args(args).map_err(value_err)
+}
+
+/// Whether `expr` matches the minute of `now_iso`, evaluated in the IANA
+/// timezone `tz` (UTC by default). Invalid expressions never fire.
+#[pyfunction]
+#[pyo3(signature = (expr, now_iso, tz = None))]
+fn cron_is_due(expr: &str, now_iso: &str, tz: Option<&str>) -> PyResult<bool> {
+    let now = parse_rfc3339(now_iso).map_err(value_err)?;
+    let tz = match tz {
+        Some(name) => name
+            .parse()
+            .map_err(|_| value_err(format!("unknown timezone: {name}")))?,
+        None => chrono_tz::Tz::UTC,
+    };
+    Ok(netsec_scheduler::is_cron_due_tz(expr, &now, tz))
+}
+
+/// When a trigger next fires strictly after `after_iso`, as RFC3339 UTC, or
+/// `None` if it never will.
+#[pyfunction]
+fn next_run_after(
+    trigger_type: &str,
+    trigger_args: &str,
+    after_iso: &str,
+) -> PyResult<Option<String>> {
+    let after = parse_rfc3339(after_iso).map_err(value_err)?;
+    let next = netsec_scheduler::next_run_after(trigger_type, trigger_args, after)
+        .map_err(value_err)?;
+    Ok(next.as_ref().map(to_rfc3339))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_interval_args, m)?)?;
+    m.add_function(wrap_pyfunction!(interval_is_due, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_cron_args, m)?)?;
+    m.add_function(wrap_pyfunction!(cron_is_due, m)?)?;
+    m.add_function(wrap_pyfunction!(next_run_after, m)?)?;
+    Ok(())
+}
@@ -1,17 +1,58 @@
-//! Active scanning: nmap argument building, result processing, and device persistence.
+//! Active scanning: nmap and masscan argument building, result processing, and
+//! device persistence.
 
-use chrono::Utc;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+use netsec_db::DbPool;
 use netsec_events::EventBus;
-use netsec_models::device::{Device, DeviceStatus};
+use netsec_models::device::{Device, DeviceStatus, DiscoveryMethod};
 use netsec_models::event::{EventType, NetsecEvent};
 use netsec_models::port::Port;
 use netsec_models::scan::{Scan, ScanStatus, ScanType};
+use netsec_models::time::now_rfc3339;
+use netsec_parsers::masscan::MasscanRecord;
 use netsec_parsers::nmap::NmapScanResult;
-use sqlx::SqlitePool;
+use tokio::sync::{mpsc, watch, OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::CancellationToken;
 
+use crate::executor::{ScanExecutor, SystemExecutor};
 use crate::fingerprint;
+use crate::mac_conflict::{self, DuplicateMac};
+use crate::oui;
 use crate::{ScannerError, ScannerResult};
 
+/// Most ports a UDP scan may probe. UDP scanning waits out timeouts on every
+/// closed port, so a full 65k sweep would take hours.
+pub const MAX_UDP_PORTS: usize = 1024;
+
+/// Ports masscan probes when a scan gives no port list. Masscan has no
+/// "top ports" default like nmap, so this stands in for it.
+pub const MASSCAN_DEFAULT_PORTS: &str = "1-1024";
+
+/// Masscan `--rate` (packets per second) for each nmap timing template.
+const MASSCAN_RATES: [u32; 6] = [100, 1_000, 5_000, 10_000, 50_000, 100_000];
+
+/// The program that runs an active scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScannerEngine {
+    /// nmap: slower, with service, OS and script detection.
+    #[default]
+    Nmap,
+    /// masscan: fast port discovery over large ranges, banners only.
+    Masscan,
+}
+
+impl ScannerEngine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nmap => "nmap",
+            Self::Masscan => "masscan",
+        }
+    }
+}
+
 /// Configuration for an active scan.
 #[derive(Debug, Clone)]
 pub struct ScanConfig {
@@ -23,6 +64,28 @@ pub struct ScanConfig {
     pub timing: u8,
     /// Optional port specification (e.g. "22,80,443" or "1-1024").
     pub ports: Option<String>,
+    /// Record the nmap command that would run instead of executing it.
+    pub dry_run: bool,
+    /// IPs or CIDRs to leave out of the target range (nmap `--exclude`).
+    pub exclude: Vec<String>,
+    /// Which program runs the scan.
+    pub scanner: ScannerEngine,
+}
+
+impl Default for ScanConfig {
+    /// A discovery scan at nmap's default timing (`-T3`). The target is empty
+    /// and must be set before the config validates.
+    fn default() -> Self {
+        Self {
+            target: String::new(),
+            scan_type: ScanType::Discovery,
+            timing: 3,
+            ports: None,
+            dry_run: false,
+            exclude: Vec::new(),
+            scanner: ScannerEngine::default(),
+        }
+    }
 }
 
 impl ScanConfig {
@@ -31,6 +94,9 @@ impl ScanConfig {
     /// - Target must be non-empty and contain only valid IP/CIDR characters.
     /// - Ports (if specified) must contain only digits, commas, and hyphens.
     /// - Timing must be 0-5 (nmap -T flag range).
+    /// - UDP scans need a port list of at most [`MAX_UDP_PORTS`] ports.
+    /// - Every `exclude` entry must be an IP address or CIDR.
+    /// - Masscan targets can't use nmap's short ranges (`10.0.0.1-50`).
     pub fn validate(&self) -> ScannerResult<()> {
         if self.target.is_empty() {
             return Err(ScannerError::Validation(
@@ -63,10 +129,86 @@ impl ScanConfig {
             )));
         }
 
+        for entry in &self.exclude {
+            if !is_ip_or_cidr(entry) {
+                return Err(ScannerError::Validation(format!(
+                    "exclude entry is not an IP or CIDR: '{entry}'"
+                )));
+            }
+        }
+
+        if self.scanner == ScannerEngine::Masscan {
+            check_masscan_target(&self.target)?;
+        }
+
+        if self.scan_type.includes_udp() {
+            let Some(ref ports) = self.ports else {
+                return Err(ScannerError::Validation(
+                    "UDP scans require a port list".to_string(),
+                ));
+            };
+            let count = count_ports(ports)?;
+            if count > MAX_UDP_PORTS {
+                return Err(ScannerError::Validation(format!(
+                    "UDP scans are limited to {MAX_UDP_PORTS} ports, got {count}"
+                )));
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Whether `entry` is an IPv4/IPv6 address, optionally with a prefix length
+/// valid for its family.
+fn is_ip_or_cidr(entry: &str) -> bool {
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (entry, None),
+    };
+    let Ok(addr) = addr.parse::<IpAddr>() else {
+        return false;
+    };
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    prefix.is_none_or(|p| p.parse::<u8>().is_ok_and(|p| p <= max_prefix))
+}
+
+/// Masscan takes an address, a CIDR or a range with both ends written out
+/// (`10.0.0.1-10.0.0.50`); nmap's short form `10.0.0.1-50` is an error there.
+fn check_masscan_target(target: &str) -> ScannerResult<()> {
+    match target.split_once('-') {
+        Some((_, end)) if end.parse::<IpAddr>().is_err() => Err(ScannerError::Validation(
+            format!("masscan ranges need both ends as full addresses, got '{target}'"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Number of ports in a `22,80,1000-2000` style specification. Overlapping
+/// entries are counted twice.
+fn count_ports(spec: &str) -> ScannerResult<usize> {
+    let invalid = || ScannerError::Validation(format!("invalid ports specification: '{spec}'"));
+    let parse = |s: &str| s.parse::<u16>().map_err(|_| invalid());
+
+    let mut count = 0;
+    for item in spec.split(',') {
+        count += match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(invalid());
+                }
+                usize::from(end - start) + 1
+            }
+            None => {
+                parse(item)?;
+                1
+            }
+        };
+    }
+    Ok(count)
+}
+
 /// A host discovered during an active scan (intermediate representation).
 #[derive(Debug, Clone)]
 pub struct DiscoveredHost {
@@ -86,6 +228,10 @@ pub struct DiscoveredPort {
     pub state: String,
     pub service_name: Option<String>,
     pub service_version: Option<String>,
+    /// Page title from the `http-title` NSE script.
+    pub http_title: Option<String>,
+    /// `Server` header from the `http-server-header` NSE script.
+    pub server_header: Option<String>,
 }
 
 /// Build nmap command-line arguments from a scan configuration.
@@ -93,10 +239,13 @@ pub struct DiscoveredPort {
 /// Scan types:
 /// - Discovery: `-sn -T{timing} {target}`
 /// - Port: `-sS -T{timing} [-p ports] -oX - {target}`
-/// - Full: `-sS -sV -O -T{timing} -oX - {target}`
+/// - Full: `-sS -sV -O --script http-title,http-server-header -T{timing} -oX - {target}`
+/// - Udp: `-sU -T{timing} [-p ports] -oX - {target}`
+/// - TcpUdp: `-sS -sU -T{timing} [-p ports] -oX - {target}`
 /// - Others: treated like Port scan
 ///
 /// Always includes `-oX -` for XML output to stdout (except Discovery).
+/// A non-empty `exclude` list adds `--exclude a,b` just before the target.
 pub fn build_nmap_args(config: &ScanConfig) -> Vec<String> {
     let mut args: Vec<String> = Vec::new();
 
@@ -104,20 +253,27 @@ pub fn build_nmap_args(config: &ScanConfig) -> Vec<String> {
         ScanType::Discovery => {
             args.push("-sn".to_string());
             args.push(format!("-T{}", config.timing.min(5)));
-            args.push(config.target.clone());
         }
         ScanType::Full => {
             args.push("-sS".to_string());
             args.push("-sV".to_string());
             args.push("-O".to_string());
+            args.push("--script".to_string());
+            args.push(crate::web::WEB_SCRIPTS.join(","));
             args.push(format!("-T{}", config.timing.min(5)));
             args.push("-oX".to_string());
             args.push("-".to_string());
-            args.push(config.target.clone());
         }
         _ => {
             // Port, Vulnerability, Custom — all use port-scan style
-            args.push("-sS".to_string());
+            match config.scan_type {
+                ScanType::Udp => args.push("-sU".to_string()),
+                ScanType::TcpUdp => {
+                    args.push("-sS".to_string());
+                    args.push("-sU".to_string());
+                }
+                _ => args.push("-sS".to_string()),
+            }
             args.push(format!("-T{}", config.timing.min(5)));
             if let Some(ref ports) = config.ports {
                 args.push("-p".to_string());
@@ -125,17 +281,107 @@ pub fn build_nmap_args(config: &ScanConfig) -> Vec<String> {
             }
             args.push("-oX".to_string());
             args.push("-".to_string());
-            args.push(config.target.clone());
         }
     }
 
+    if !config.exclude.is_empty() {
+        args.push("--exclude".to_string());
+        args.push(config.exclude.join(","));
+    }
+    args.push(config.target.clone());
+
     args
 }
 
+/// Build masscan command-line arguments from a scan configuration.
+///
+/// Scan types:
+/// - Discovery: `--ping --rate {rate} -oJ - {target}`
+/// - Full: `-p {ports} --banners --rate {rate} -oJ - {target}`
+/// - Udp: `-p U:{ports} --rate {rate} -oJ - {target}`
+/// - TcpUdp: `-p {ports},U:{ports} --rate {rate} -oJ - {target}`
+/// - Others: `-p {ports} --rate {rate} -oJ - {target}`
+///
+/// Ports default to [`MASSCAN_DEFAULT_PORTS`]. The rate follows the timing
+/// template, from 100 packets/s at `0` to 100,000 at `5`. A non-empty
+/// `exclude` list adds `--exclude a,b` just before the target.
+///
+/// Returns an error for nmap-style short ranges such as `10.0.0.1-50`, which
+/// masscan rejects.
+pub fn build_masscan_args(config: &ScanConfig) -> ScannerResult<Vec<String>> {
+    check_masscan_target(&config.target)?;
+    let mut args: Vec<String> = Vec::new();
+
+    if config.scan_type == ScanType::Discovery {
+        args.push("--ping".to_string());
+    } else {
+        let ports = config.ports.as_deref().unwrap_or(MASSCAN_DEFAULT_PORTS);
+        let udp = || {
+            ports
+                .split(',')
+                .map(|item| format!("U:{item}"))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        args.push("-p".to_string());
+        args.push(match config.scan_type {
+            ScanType::Udp => udp(),
+            ScanType::TcpUdp => format!("{ports},{}", udp()),
+            _ => ports.to_string(),
+        });
+        if config.scan_type == ScanType::Full {
+            args.push("--banners".to_string());
+        }
+    }
+
+    args.push("--rate".to_string());
+    args.push(MASSCAN_RATES[usize::from(config.timing.min(5))].to_string());
+    args.push("-oJ".to_string());
+    args.push("-".to_string());
+
+    if !config.exclude.is_empty() {
+        args.push("--exclude".to_string());
+        args.push(config.exclude.join(","));
+    }
+    args.push(config.target.clone());
+
+    Ok(args)
+}
+
+/// Render the nmap command line for a scan configuration, as recorded by
+/// dry runs. The program is always shown as `nmap`.
+///
+/// Arguments are space-joined without quoting; `ScanConfig::validate` already
+/// rejects targets and port specs containing whitespace or shell syntax.
+pub fn format_nmap_command(config: &ScanConfig) -> String {
+    join_command("nmap", build_nmap_args(config))
+}
+
+/// Render the command line of the engine `config` selects, as recorded by
+/// dry runs. Quoting is as for [`format_nmap_command`]; errors are as for
+/// [`build_masscan_args`].
+pub fn format_scan_command(config: &ScanConfig) -> ScannerResult<String> {
+    match config.scanner {
+        ScannerEngine::Nmap => Ok(format_nmap_command(config)),
+        ScannerEngine::Masscan => Ok(join_command("masscan", build_masscan_args(config)?)),
+    }
+}
+
+fn join_command(program: &str, args: Vec<String>) -> String {
+    let mut command = String::from(program);
+    for arg in args {
+        command.push(' ');
+        command.push_str(&arg);
+    }
+    command
+}
+
 /// Convert a parsed `NmapScanResult` into `DiscoveredHost` structs.
 ///
 /// Only includes hosts with status "up". Extracts IP from `addresses["ipv4"]`,
-/// MAC from `addresses["mac"]`, vendor from `addresses["vendor"]`,
+/// falling back to `addresses["ipv6"]` for IPv6-only hosts, in canonical form
+/// so the same host always maps to the same device; MAC from
+/// `addresses["mac"]`, vendor from `addresses["vendor"]`,
 /// hostname from the first `hostnames` entry, and OS from `os["name"]`.
 pub fn process_nmap_results(scan_result: &NmapScanResult) -> Vec<DiscoveredHost> {
     scan_result
@@ -144,6 +390,7 @@ pub fn process_nmap_results(scan_result: &NmapScanResult) -> Vec<DiscoveredHost>
         .filter(|h| h.status == "up")
         .filter_map(|host| {
             let ip = host.addresses.get("ipv4").or(host.addresses.get("ipv6"))?;
+            let ip = canonical_ip(ip);
 
             let mac = host.addresses.get("mac").cloned();
             let vendor = host.addresses.get("vendor").cloned();
@@ -156,17 +403,22 @@ pub fn process_nmap_results(scan_result: &NmapScanResult) -> Vec<DiscoveredHost>
             let ports = host
                 .ports
                 .iter()
-                .map(|p| DiscoveredPort {
-                    port: p.port,
-                    protocol: p.protocol.clone(),
-                    state: p.state.clone(),
-                    service_name: p.service.get("name").cloned(),
-                    service_version: p.service.get("version").cloned(),
+                .map(|p| {
+                    let web = crate::web::extract_web_info(&p.scripts);
+                    DiscoveredPort {
+                        port: p.port,
+                        protocol: p.protocol.clone(),
+                        state: p.state.clone(),
+                        service_name: p.service.get("name").cloned(),
+                        service_version: p.service.get("version").cloned(),
+                        http_title: web.http_title,
+                        server_header: web.server_header,
+                    }
                 })
                 .collect();
 
             Some(DiscoveredHost {
-                ip: ip.clone(),
+                ip,
                 mac,
                 hostname,
                 vendor,
@@ -177,97 +429,261 @@ pub fn process_nmap_results(scan_result: &NmapScanResult) -> Vec<DiscoveredHost>
         .collect()
 }
 
+/// Convert parsed masscan records into `DiscoveredHost` structs, one per IP.
+///
+/// Records are grouped by [`netsec_parsers::masscan::to_nmap_result`] and then
+/// read like nmap results, so banners fill in service names and web titles.
+pub fn process_masscan_results(records: &[MasscanRecord]) -> Vec<DiscoveredHost> {
+    process_nmap_results(&netsec_parsers::masscan::to_nmap_result(records))
+}
+
+/// Fold a discovered host's details into its device record. Fresh nmap data
+/// overwrites MAC, vendor and OS; a known hostname is kept.
+fn merge_host(device: &mut Device, host: &DiscoveredHost, now: &str) {
+    device.last_seen = now.to_string();
+    device.status = DeviceStatus::Online.as_str().to_string();
+    if let Some(ref mac) = host.mac {
+        device.mac = Some(mac.clone());
+    }
+    if device.hostname.is_none() {
+        device.hostname = host.hostname.clone();
+    }
+    if let Some(ref vendor) = host.vendor {
+        device.vendor = Some(vendor.clone());
+    } else if device.vendor.is_none() {
+        device.vendor = oui_vendor(host);
+    }
+    if let Some(ref os) = host.os_info {
+        device.os_family = Some(os.clone());
+    }
+}
+
+/// Vendor of a host's MAC from the OUI table.
+fn oui_vendor(host: &DiscoveredHost) -> Option<String> {
+    host.mac.as_deref().and_then(oui::lookup_vendor)
+}
+
+/// Canonical text of an address (`FE80:0:0::0001` becomes `fe80::1`), or the
+/// input unchanged if it doesn't parse.
+fn canonical_ip(ip: &str) -> String {
+    ip.parse::<IpAddr>().map_or_else(|_| ip.to_string(), |addr| addr.to_string())
+}
+
+/// How many scans [`ActiveScanner::new`] lets execute at once.
+pub const DEFAULT_MAX_CONCURRENT_SCANS: usize = 3;
+
+/// Caps how many scans execute at once, so a burst of requests can't spawn
+/// dozens of nmap processes. Clones share the same permits.
+#[derive(Debug, Clone)]
+pub struct ScanLimiter {
+    permits: Arc<Semaphore>,
+    max: usize,
+}
+
+impl ScanLimiter {
+    /// Allow `max` concurrent scans; zero is raised to one so scans can
+    /// always make progress.
+    pub fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max)),
+            max,
+        }
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Wait for a free slot. The slot is released when the permit drops.
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("scan limiter semaphore is never closed")
+    }
+}
+
 /// Active scanner with database persistence and event publishing.
 pub struct ActiveScanner {
-    pool: SqlitePool,
+    pool: DbPool,
     event_bus: EventBus,
+    /// MACs legitimately bound to several IPs (excluded from duplicate checks).
+    mac_allowlist: Vec<String>,
+    executor: Arc<dyn ScanExecutor>,
+    /// Cancellation tokens of the scans currently running, by scan ID.
+    running: Mutex<HashMap<String, CancellationToken>>,
+    limiter: ScanLimiter,
+    /// Number of [`run_scan`](Self::run_scan) calls not yet finished,
+    /// including their final status update.
+    in_progress: watch::Sender<usize>,
+}
+
+/// Counts a scan as in progress until dropped, including when the scan's
+/// future is dropped.
+struct InProgress<'a>(&'a watch::Sender<usize>);
+
+impl<'a> InProgress<'a> {
+    fn enter(counter: &'a watch::Sender<usize>) -> Self {
+        counter.send_modify(|n| *n += 1);
+        Self(counter)
+    }
+}
+
+impl Drop for InProgress<'_> {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n -= 1);
+    }
 }
 
 impl ActiveScanner {
-    pub fn new(pool: SqlitePool, event_bus: EventBus) -> Self {
-        Self { pool, event_bus }
+    pub fn new(pool: DbPool, event_bus: EventBus) -> Self {
+        Self::with_max_concurrency(pool, event_bus, DEFAULT_MAX_CONCURRENT_SCANS)
+    }
+
+    /// Create a scanner that executes at most `max_concurrent` scans at once;
+    /// further scans wait for a slot (see [`ScanLimiter`]).
+    pub fn with_max_concurrency(
+        pool: DbPool,
+        event_bus: EventBus,
+        max_concurrent: usize,
+    ) -> Self {
+        Self {
+            pool,
+            event_bus,
+            mac_allowlist: Vec::new(),
+            executor: Arc::new(SystemExecutor),
+            running: Mutex::new(HashMap::new()),
+            limiter: ScanLimiter::new(max_concurrent),
+            in_progress: watch::Sender::new(0),
+        }
+    }
+
+    /// Run scans with `executor` instead of the system nmap or masscan.
+    pub fn with_executor(mut self, executor: Box<dyn ScanExecutor>) -> Self {
+        self.executor = Arc::from(executor);
+        self
+    }
+
+    /// Number of scans currently running or waiting for a slot.
+    pub fn running_count(&self) -> usize {
+        self.running.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Cancel every running scan, e.g. on shutdown. Returns how many were
+    /// running.
+    pub fn cancel_all(&self) -> usize {
+        let running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        for (scan_id, token) in running.iter() {
+            tracing::info!(scan_id, "Cancelling scan");
+            token.cancel();
+        }
+        running.len()
+    }
+
+    /// Wait until no scan is running, including the status update a
+    /// cancelled scan makes on its way out. Use after
+    /// [`cancel_all`](Self::cancel_all) before closing the pool.
+    pub async fn wait_idle(&self) {
+        let mut in_progress = self.in_progress.subscribe();
+        let _ = in_progress.wait_for(|n| *n == 0).await;
+    }
+
+    /// Cancel a running scan: its executor is stopped and the scan is marked
+    /// `cancelled`. Returns `false` if no scan with that ID is executing.
+    pub fn cancel(&self, scan_id: &str) -> bool {
+        let running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        match running.get(scan_id) {
+            Some(token) => {
+                tracing::info!(scan_id, "Cancelling scan");
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Exclude known-legitimate MACs (e.g. multi-homed hosts, VM bridges)
+    /// from duplicate MAC alerts.
+    pub fn with_mac_allowlist(mut self, macs: Vec<String>) -> Self {
+        self.mac_allowlist = macs;
+        self
     }
 
     /// Persist discovered hosts to the database.
     ///
-    /// For each host:
-    /// 1. Upsert device (by IP)
-    /// 2. Upsert each port
-    /// 3. Classify device based on ports, OS, and vendor
-    /// 4. Publish `DeviceDiscovered` (new) or `DeviceUpdated` (existing) event
+    /// Each host is written in its own transaction:
+    /// 1. Upsert its device (matched by IP), taking the vendor from the MAC's
+    ///    OUI when nmap didn't report one
+    /// 2. Upsert its ports in one batch
+    /// 3. Classify the device based on its ports, OS, and vendor
+    /// 4. After commit, publish `DeviceDiscovered` (new) or `DeviceUpdated`
+    ///    (existing)
+    ///
+    /// A host that fails to persist is rolled back, logged and skipped, so it
+    /// leaves no device without its ports or classification; the other hosts
+    /// are still stored. Returns the stored devices.
+    ///
+    /// Afterwards, MACs bound to several active IPs raise an informational
+    /// alert (see [`ActiveScanner::check_duplicate_macs`]).
     pub async fn persist_hosts(
         &self,
         hosts: &[DiscoveredHost],
     ) -> ScannerResult<Vec<Device>> {
-        let mut devices = Vec::new();
-        let now = Utc::now().to_rfc3339();
+        let now = now_rfc3339();
+
+        let ips: Vec<String> = hosts.iter().map(|h| h.ip.clone()).collect();
+        let mut existing: HashMap<String, Device> = HashMap::new();
+        for device in netsec_db::repo::devices::list_by_ips(&self.pool, &ips).await? {
+            existing.entry(device.ip.clone()).or_insert(device);
+        }
 
+        // Merge every host into its device; a host repeated in `hosts`
+        // updates the same device.
+        let mut merged: Vec<(Device, bool, Vec<Port>)> = Vec::new();
+        let mut by_ip: HashMap<&str, usize> = HashMap::new();
         for host in hosts {
-            let is_new;
-            let mut device =
-                match netsec_db::repo::devices::get_by_ip(&self.pool, &host.ip).await? {
-                    Some(mut existing) => {
-                        is_new = false;
-                        existing.last_seen = now.clone();
-                        existing.status = DeviceStatus::Online.as_str().to_string();
-                        if let Some(ref mac) = host.mac {
-                            existing.mac = Some(mac.clone());
-                        }
-                        if existing.hostname.is_none() {
-                            existing.hostname = host.hostname.clone();
-                        }
-                        if let Some(ref vendor) = host.vendor {
-                            existing.vendor = Some(vendor.clone());
-                        }
-                        if let Some(ref os) = host.os_info {
-                            existing.os_family = Some(os.clone());
+            let idx = match by_ip.get(host.ip.as_str()) {
+                Some(&idx) => idx,
+                None => {
+                    let (device, new) = match existing.remove(&host.ip) {
+                        Some(device) => (device, false),
+                        None => {
+                            let mut d = Device::new(host.ip.clone());
+                            d.first_seen = now.clone();
+                            d.discovery_method =
+                                Some(DiscoveryMethod::Active.as_str().to_string());
+                            (d, true)
                         }
-                        existing
-                    }
-                    None => {
-                        is_new = true;
-                        let mut d = Device::new(host.ip.clone());
-                        d.mac = host.mac.clone();
-                        d.hostname = host.hostname.clone();
-                        d.vendor = host.vendor.clone();
-                        d.os_family = host.os_info.clone();
-                        d.status = DeviceStatus::Online.as_str().to_string();
-                        d.last_seen = now.clone();
-                        d.first_seen = now.clone();
-                        d
-                    }
-                };
-
-            // Persist device first (ports have FK to device)
-            if is_new {
-                netsec_db::repo::devices::insert(&self.pool, &device).await?;
-            } else {
-                netsec_db::repo::devices::update(&self.pool, &device).await?;
-            }
-
-            // Upsert ports (device must exist for FK)
-            for dp in &host.ports {
+                    };
+                    by_ip.insert(&host.ip, merged.len());
+                    merged.push((device, new, Vec::new()));
+                    merged.len() - 1
+                }
+            };
+            let (device, _, ports) = &mut merged[idx];
+            merge_host(device, host, &now);
+            ports.extend(host.ports.iter().map(|dp| {
                 let mut port = Port::new(device.id.clone(), dp.port, dp.protocol.clone());
                 port.state = dp.state.clone();
                 port.service_name = dp.service_name.clone();
                 port.service_version = dp.service_version.clone();
-                netsec_db::repo::ports::upsert(&self.pool, &port).await?;
-            }
+                port.http_title = dp.http_title.clone();
+                port.server_header = dp.server_header.clone();
+                port
+            }));
+        }
 
-            // Classify device based on current ports, then update
-            let db_ports =
-                netsec_db::repo::ports::list_by_device(&self.pool, &device.id).await?;
-            let (device_type, confidence) = fingerprint::classify_device(
-                &db_ports,
-                device.os_family.as_deref(),
-                device.vendor.as_deref(),
-            );
-            device.device_type = device_type.as_str().to_string();
-            device.classification_confidence = confidence;
-            netsec_db::repo::devices::update(&self.pool, &device).await?;
+        let mut devices = Vec::with_capacity(merged.len());
+        for (mut device, new, ports) in merged {
+            if let Err(e) = self.persist_host(&mut device, &ports).await {
+                tracing::warn!(ip = %device.ip, "Skipping host that failed to persist: {e}");
+                continue;
+            }
 
-            // Publish event
-            let event_type = if is_new {
+            // Only announce devices once they're durably stored
+            let event_type = if new {
                 EventType::DeviceDiscovered
             } else {
                 EventType::DeviceUpdated
@@ -282,29 +698,107 @@ impl ActiveScanner {
             );
             // Ignore send errors (no subscribers is fine)
             let _ = self.event_bus.publish(event);
-
             devices.push(device);
         }
 
+        self.check_duplicate_macs(&devices).await?;
+
         Ok(devices)
     }
 
+    /// Write one device with its ports and classification in a transaction,
+    /// upserting the ports in one batch. Dropping the transaction on error
+    /// rolls everything back.
+    async fn persist_host(&self, device: &mut Device, ports: &[Port]) -> ScannerResult<()> {
+        use netsec_db::repo::{devices as device_repo, ports as port_repo};
+
+        let mut tx = self.pool.begin().await?;
+        // Device first (ports have FK to device), then all ports in one batch
+        device_repo::bulk_upsert(&mut *tx, std::slice::from_ref(device)).await?;
+        port_repo::bulk_upsert(&mut *tx, ports).await?;
+
+        // Classify based on all the device's current ports, then update
+        let db_ports = port_repo::list_by_device(&mut *tx, &device.id).await?;
+        let (device_type, confidence) = fingerprint::classify_device(
+            &db_ports,
+            device.os_family.as_deref(),
+            device.vendor.as_deref(),
+        );
+        device.device_type = device_type.as_str().to_string();
+        device.classification_confidence = confidence;
+        device_repo::update(&mut *tx, device).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Detect MACs of the given devices that are bound to multiple active IPs
+    /// and record an informational alert per duplicate.
+    ///
+    /// Existing alerts for the same MAC are bumped rather than duplicated.
+    pub async fn check_duplicate_macs(
+        &self,
+        devices: &[Device],
+    ) -> ScannerResult<Vec<DuplicateMac>> {
+        let mut macs: Vec<String> = devices
+            .iter()
+            .filter_map(|d| d.mac.as_deref().map(mac_conflict::normalize_mac))
+            .collect();
+        macs.sort();
+        macs.dedup();
+
+        let mut candidates = Vec::new();
+        for mac in &macs {
+            candidates.extend(netsec_db::repo::devices::list_by_mac(&self.pool, mac).await?);
+        }
+
+        let duplicates = mac_conflict::find_duplicate_macs(&candidates, &self.mac_allowlist);
+        for dup in &duplicates {
+            tracing::warn!(mac = %dup.mac, ips = ?dup.ips, "MAC bound to multiple IPs");
+
+            let alert = mac_conflict::duplicate_mac_alert(dup);
+            match netsec_db::repo::alerts::get_by_fingerprint(&self.pool, &alert.fingerprint)
+                .await?
+            {
+                Some(existing) => {
+                    netsec_db::repo::alerts::increment_count(
+                        &self.pool,
+                        &existing.id,
+                        &now_rfc3339(),
+                    )
+                    .await?;
+                }
+                None => {
+                    netsec_db::repo::alerts::insert(&self.pool, &alert).await?;
+                    let payload = serde_json::to_value(&alert)
+                        .map_err(|e| ScannerError::EventBus(e.to_string()))?;
+                    let _ = self
+                        .event_bus
+                        .publish(NetsecEvent::new(EventType::AlertCreated, payload));
+                }
+            }
+        }
+
+        Ok(duplicates)
+    }
+
     /// Create a scan record in the database with status=running.
     pub async fn create_scan_record(
         &self,
         config: &ScanConfig,
     ) -> ScannerResult<Scan> {
         let mut scan = Scan::new(
-            "nmap".to_string(),
+            config.scanner.as_str().to_string(),
             config.target.clone(),
             config.scan_type.clone(),
         );
         scan.status = ScanStatus::Running.as_str().to_string();
-        scan.started_at = Some(Utc::now().to_rfc3339());
+        scan.started_at = Some(now_rfc3339());
         scan.parameters = serde_json::json!({
             "timing": config.timing,
             "ports": config.ports,
             "scan_type": config.scan_type.as_str(),
+            "dry_run": config.dry_run,
+            "scanner": config.scanner.as_str(),
         })
         .to_string();
 
@@ -312,26 +806,78 @@ impl ActiveScanner {
         Ok(scan)
     }
 
-    /// Run a full scan: create record, execute nmap, persist results.
+    /// Run a full scan: create record, execute the scanner, persist results.
     ///
     /// 1. Creates a scan record in the database (status=running).
-    /// 2. Executes nmap via `crate::executor::execute_nmap`.
+    /// 2. Waits for a [`ScanLimiter`] slot, then executes the scan via the
+    ///    configured [`ScanExecutor`] (by default nmap or masscan, as
+    ///    `config.scanner` selects), storing its progress and publishing
+    ///    `ScanProgress` events as it runs.
     /// 3. On success: processes results, persists hosts, marks scan complete.
     /// 4. On failure: marks scan as failed in the database.
+    /// 5. If [`cancel`](Self::cancel)led: marks scan as cancelled and returns
+    ///    [`ScannerError::Cancelled`].
+    ///
+    /// With `dry_run` set, the scanner is never spawned: the command line is stored
+    /// in the scan's results and the scan is marked completed with no hosts.
     pub async fn run_scan(&self, config: &ScanConfig) -> ScannerResult<Vec<Device>> {
         let scan = self.create_scan_record(config).await?;
-        match crate::executor::execute_nmap(config).await {
+        if config.dry_run {
+            if let Err(e) = config.validate() {
+                let _ = netsec_db::repo::scans::update_status(&self.pool, &scan.id, "failed", 0.0)
+                    .await;
+                return Err(e);
+            }
+            let command = format_scan_command(config)?;
+            tracing::info!(scan_id = %scan.id, %command, "Dry run; scanner not executed");
+            let results = serde_json::json!({
+                "dry_run": true,
+                "command": command,
+                "hosts_found": 0,
+                "total_ports": 0,
+            })
+            .to_string();
+            netsec_db::repo::scans::set_results(&self.pool, &scan.id, &results, &now_rfc3339())
+                .await?;
+            return Ok(Vec::new());
+        }
+        let _in_progress = InProgress::enter(&self.in_progress);
+        let cancel = CancellationToken::new();
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(scan.id.clone(), cancel.clone());
+        // Cancellable while still waiting for a slot
+        let permit = tokio::select! {
+            permit = self.limiter.acquire() => Some(permit),
+            _ = cancel.cancelled() => None,
+        };
+        let outcome = match permit {
+            Some(_permit) => self.execute(&scan.id, config, cancel).await,
+            None => Err(ScannerError::Cancelled),
+        };
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&scan.id);
+
+        match outcome {
             Ok(result) => {
                 let hosts = process_nmap_results(&result);
                 let devices = self.persist_hosts(&hosts).await?;
                 self.complete_scan(&scan.id, &hosts).await?;
+                self.publish_progress(&scan.id, 100);
                 Ok(devices)
             }
             Err(e) => {
+                let status = match e {
+                    ScannerError::Cancelled => ScanStatus::Cancelled,
+                    _ => ScanStatus::Failed,
+                };
                 let _ = netsec_db::repo::scans::update_status(
                     &self.pool,
                     &scan.id,
-                    "failed",
+                    status.as_str(),
                     0.0,
                 )
                 .await;
@@ -340,6 +886,59 @@ impl ActiveScanner {
         }
     }
 
+    /// Run the executor, storing and publishing its progress as it goes.
+    async fn execute(
+        &self,
+        scan_id: &str,
+        config: &ScanConfig,
+        cancel: CancellationToken,
+    ) -> ScannerResult<NmapScanResult> {
+        let (progress, mut progress_updates) = mpsc::unbounded_channel();
+        let execute = self.executor.execute(config, cancel, progress);
+        let report = async {
+            // nmap restarts at 0 for each phase, so only ever move forward,
+            // and hold 100 back for completion
+            let mut last = 0;
+            while let Some(percent) = progress_updates.recv().await {
+                let percent = percent.clamp(0.0, 99.0) as u8;
+                if percent > last {
+                    last = percent;
+                    self.report_progress(scan_id, percent).await;
+                }
+            }
+        };
+        let (outcome, ()) = tokio::join!(execute, report);
+        outcome
+    }
+
+    /// Store a running scan's progress and publish a `ScanProgress` event.
+    /// Failures are only logged; they never fail the scan.
+    async fn report_progress(&self, scan_id: &str, percent: u8) {
+        let stored = netsec_db::repo::scans::update_status(
+            &self.pool,
+            scan_id,
+            ScanStatus::Running.as_str(),
+            f64::from(percent) / 100.0,
+        )
+        .await;
+        if let Err(e) = stored {
+            tracing::warn!(scan_id, error = %e, "Failed to store scan progress");
+        }
+        self.publish_progress(scan_id, percent);
+    }
+
+    fn publish_progress(&self, scan_id: &str, percent: u8) {
+        let event = NetsecEvent::new(
+            EventType::ScanProgress,
+            serde_json::json!({
+                "scan_id": scan_id,
+                "progress": percent,
+            }),
+        );
+        // Ignore send errors (no subscribers is fine)
+        let _ = self.event_bus.publish(event);
+    }
+
     /// Mark a scan as completed and store a results summary.
     pub async fn complete_scan(
         &self,
@@ -352,7 +951,7 @@ impl ActiveScanner {
         })
         .to_string();
 
-        let completed_at = Utc::now().to_rfc3339();
+        let completed_at = now_rfc3339();
         netsec_db::repo::scans::set_results(&self.pool, scan_id, &results, &completed_at).await?;
         Ok(())
     }
@@ -363,6 +962,23 @@ mod tests {
     use super::*;
     use netsec_parsers::nmap::{NmapHost, NmapPort, NmapScanResult};
     use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_scan_limiter_permits() {
+        assert_eq!(ScanLimiter::new(0).max(), 1);
+
+        let limiter = ScanLimiter::new(2);
+        let first = limiter.acquire().await;
+        let _second = limiter.clone().acquire().await;
+        let waiting = tokio::time::timeout(Duration::from_millis(20), limiter.acquire()).await;
+        assert!(waiting.is_err(), "third scan should wait for a slot");
+
+        drop(first);
+        let _third = tokio::time::timeout(Duration::from_secs(1), limiter.acquire())
+            .await
+            .expect("released slot should be reusable");
+    }
 
     #[test]
     fn test_build_args_discovery() {
@@ -370,7 +986,7 @@ mod tests {
             target: "192.168.1.0/24".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         let args = build_nmap_args(&config);
         assert_eq!(args, vec!["-sn", "-T4", "192.168.1.0/24"]);
@@ -383,6 +999,7 @@ mod tests {
             scan_type: ScanType::Port,
             timing: 3,
             ports: Some("22,80,443".to_string()),
+            ..Default::default()
         };
         let args = build_nmap_args(&config);
         assert_eq!(
@@ -391,18 +1008,154 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_args_udp_scan() {
+        let config = ScanConfig {
+            target: "10.0.0.1".to_string(),
+            scan_type: ScanType::Udp,
+            timing: 4,
+            ports: Some("53,123,161".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            build_nmap_args(&config),
+            vec!["-sU", "-T4", "-p", "53,123,161", "-oX", "-", "10.0.0.1"]
+        );
+
+        let combined = ScanConfig {
+            scan_type: ScanType::TcpUdp,
+            ..config
+        };
+        assert_eq!(
+            build_nmap_args(&combined),
+            vec!["-sS", "-sU", "-T4", "-p", "53,123,161", "-oX", "-", "10.0.0.1"]
+        );
+    }
+
+    #[test]
+    fn test_udp_scan_without_ports() {
+        let config = ScanConfig {
+            target: "10.0.0.1".to_string(),
+            scan_type: ScanType::Udp,
+            timing: 3,
+            ..Default::default()
+        };
+        // Args still build, but validation refuses a full sweep
+        assert_eq!(build_nmap_args(&config), vec!["-sU", "-T3", "-oX", "-", "10.0.0.1"]);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("require a port list"));
+    }
+
+    #[test]
+    fn test_udp_scan_port_cap() {
+        let config = ScanConfig {
+            target: "10.0.0.1".to_string(),
+            scan_type: ScanType::TcpUdp,
+            timing: 3,
+            ports: Some("1-1024".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let too_many = ScanConfig {
+            ports: Some("1-1024,5353".to_string()),
+            ..config.clone()
+        };
+        let err = too_many.validate().unwrap_err();
+        assert!(err.to_string().contains("limited to 1024 ports, got 1025"));
+
+        let reversed = ScanConfig {
+            ports: Some("100-1".to_string()),
+            ..config
+        };
+        assert!(reversed.validate().is_err());
+
+        // TCP-only scans are not capped
+        let tcp = ScanConfig {
+            scan_type: ScanType::Port,
+            ports: Some("1-65535".to_string()),
+            ..too_many
+        };
+        assert!(tcp.validate().is_ok());
+    }
+
+    #[test]
+    fn test_build_args_exclude() {
+        let config = ScanConfig {
+            target: "192.168.1.0/24".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            exclude: vec!["192.168.1.1".to_string(), "192.168.1.128/28".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            build_nmap_args(&config),
+            vec![
+                "-sn",
+                "-T4",
+                "--exclude",
+                "192.168.1.1,192.168.1.128/28",
+                "192.168.1.0/24"
+            ]
+        );
+
+        let port = ScanConfig {
+            scan_type: ScanType::Port,
+            exclude: vec!["fe80::1".to_string()],
+            ..config
+        };
+        assert_eq!(
+            build_nmap_args(&port),
+            vec!["-sS", "-T4", "-oX", "-", "--exclude", "fe80::1", "192.168.1.0/24"]
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_exclude() {
+        for bad in [
+            "192.168.1",
+            "10.0.0.1/33",
+            "fe80::1/129",
+            "host.local",
+            "10.0.0.1,10.0.0.2",
+            "",
+        ] {
+            let config = ScanConfig {
+                target: "10.0.0.0/24".to_string(),
+                scan_type: ScanType::Discovery,
+                timing: 4,
+                exclude: vec!["10.0.0.254".to_string(), bad.to_string()],
+                ..Default::default()
+            };
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains("not an IP or CIDR"), "{bad}: {err}");
+        }
+    }
+
     #[test]
     fn test_build_args_full_scan() {
         let config = ScanConfig {
             target: "10.0.0.0/24".to_string(),
             scan_type: ScanType::Full,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         let args = build_nmap_args(&config);
         assert_eq!(
             args,
-            vec!["-sS", "-sV", "-O", "-T4", "-oX", "-", "10.0.0.0/24"]
+            vec![
+                "-sS",
+                "-sV",
+                "-O",
+                "--script",
+                "http-title,http-server-header",
+                "-T4",
+                "-oX",
+                "-",
+                "10.0.0.0/24"
+            ]
         );
     }
 
@@ -413,13 +1166,151 @@ mod tests {
                 target: "1.2.3.4".to_string(),
                 scan_type: ScanType::Discovery,
                 timing: t,
-                ports: None,
+                ..Default::default()
             };
             let args = build_nmap_args(&config);
             assert_eq!(args[1], format!("-T{t}"));
         }
     }
 
+    #[test]
+    fn test_format_nmap_command() {
+        let config = ScanConfig {
+            target: "10.0.0.1".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            dry_run: true,
+            ..Default::default()
+        };
+        assert_eq!(format_nmap_command(&config), "nmap -sn -T4 10.0.0.1");
+
+        let full = ScanConfig {
+            scan_type: ScanType::Full,
+            ..config
+        };
+        let command = format_nmap_command(&full);
+        assert!(command.starts_with("nmap -sS -sV -O --script "));
+        assert!(command.ends_with(" -oX - 10.0.0.1"));
+    }
+
+    #[test]
+    fn test_build_masscan_args() {
+        let config = ScanConfig {
+            target: "10.0.0.0/8".to_string(),
+            scan_type: ScanType::Port,
+            timing: 4,
+            ports: Some("22,80,8000-8100".to_string()),
+            exclude: vec!["10.0.0.1".to_string(), "10.1.0.0/16".to_string()],
+            scanner: ScannerEngine::Masscan,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_masscan_args(&config).unwrap(),
+            vec![
+                "-p", "22,80,8000-8100", "--rate", "50000", "-oJ", "-",
+                "--exclude", "10.0.0.1,10.1.0.0/16", "10.0.0.0/8",
+            ]
+        );
+
+        let discovery = ScanConfig {
+            scan_type: ScanType::Discovery,
+            timing: 9,
+            exclude: Vec::new(),
+            ..config.clone()
+        };
+        assert_eq!(
+            build_masscan_args(&discovery).unwrap(),
+            vec!["--ping", "--rate", "100000", "-oJ", "-", "10.0.0.0/8"]
+        );
+
+        let full = ScanConfig {
+            scan_type: ScanType::Full,
+            ports: None,
+            exclude: Vec::new(),
+            ..config.clone()
+        };
+        let args = build_masscan_args(&full).unwrap();
+        assert_eq!(args[..3], ["-p", MASSCAN_DEFAULT_PORTS, "--banners"]);
+
+        let tcp_udp = ScanConfig {
+            scan_type: ScanType::TcpUdp,
+            ports: Some("53,1000-1010".to_string()),
+            ..config.clone()
+        };
+        assert_eq!(build_masscan_args(&tcp_udp).unwrap()[1], "53,1000-1010,U:53,U:1000-1010");
+        let udp = ScanConfig {
+            scan_type: ScanType::Udp,
+            ..tcp_udp
+        };
+        assert_eq!(build_masscan_args(&udp).unwrap()[1], "U:53,U:1000-1010");
+
+        // Masscan needs both ends of a range; nmap's short form is rejected
+        let full_range = ScanConfig {
+            target: "10.0.0.1-10.0.0.50".to_string(),
+            ..config.clone()
+        };
+        assert_eq!(build_masscan_args(&full_range).unwrap().last().unwrap(), "10.0.0.1-10.0.0.50");
+        assert!(full_range.validate().is_ok());
+        for short in ["10.0.0.1-50", "10.0.0-3.1"] {
+            let config = ScanConfig {
+                target: short.to_string(),
+                ..config.clone()
+            };
+            assert!(matches!(build_masscan_args(&config), Err(ScannerError::Validation(_))));
+            assert!(config.validate().is_err(), "{short} should be rejected");
+            // nmap takes them
+            let nmap = ScanConfig {
+                scanner: ScannerEngine::Nmap,
+                ..config
+            };
+            assert!(nmap.validate().is_ok(), "{short} should be valid for nmap");
+        }
+    }
+
+    #[test]
+    fn test_format_scan_command_by_engine() {
+        let config = ScanConfig {
+            target: "10.0.0.1".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 3,
+            dry_run: true,
+            ..Default::default()
+        };
+        assert_eq!(format_scan_command(&config).unwrap(), format_nmap_command(&config));
+        let masscan = ScanConfig {
+            scanner: ScannerEngine::Masscan,
+            ..config
+        };
+        assert_eq!(
+            format_scan_command(&masscan).unwrap(),
+            "masscan --ping --rate 10000 -oJ - 10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn test_process_masscan_results() {
+        let json = include_str!("../../../tests/fixtures/masscan_sample.json");
+        let records = netsec_parsers::masscan::parse_masscan_json(json).unwrap();
+        let hosts = process_masscan_results(&records);
+
+        let ips: Vec<&str> = hosts.iter().map(|h| h.ip.as_str()).collect();
+        assert_eq!(ips, vec!["192.168.1.10", "192.168.1.20", "192.168.1.30"]);
+
+        let router = &hosts[0];
+        assert_eq!(router.mac, None);
+        assert_eq!(router.os_info, None);
+        assert_eq!(router.ports.len(), 2);
+        let http = &router.ports[0];
+        assert_eq!((http.port, http.protocol.as_str()), (80, "tcp"));
+        assert_eq!(http.state, "open");
+        assert_eq!(http.service_name.as_deref(), Some("http"));
+        assert_eq!(http.http_title.as_deref(), Some("Router Login"));
+        assert_eq!(router.ports[1].service_name, None);
+
+        assert_eq!(hosts[1].ports[0].protocol, "udp");
+        assert!(hosts[2].ports.is_empty());
+    }
+
     #[test]
     fn test_build_args_custom_ports() {
         let config = ScanConfig {
@@ -427,6 +1318,7 @@ mod tests {
             scan_type: ScanType::Port,
             timing: 3,
             ports: Some("1-1024".to_string()),
+            ..Default::default()
         };
         let args = build_nmap_args(&config);
         assert!(args.contains(&"-p".to_string()));
@@ -483,6 +1375,7 @@ mod tests {
             protocol: "tcp".to_string(),
             state: state.to_string(),
             service,
+            ..Default::default()
         }
     }
 
@@ -510,6 +1403,25 @@ mod tests {
         assert_eq!(hosts[0].ports[0].port, 80);
     }
 
+    #[test]
+    fn test_process_results_web_scripts() {
+        let mut port = make_nmap_port(80, "open", Some("http"));
+        port.scripts
+            .insert("http-title".to_string(), "Welcome to nginx!".to_string());
+        port.scripts.insert(
+            "http-server-header".to_string(),
+            "nginx/1.18.0 (Ubuntu)".to_string(),
+        );
+        let scan = NmapScanResult {
+            scan_info: HashMap::new(),
+            hosts: vec![make_nmap_host("10.0.0.8", "up", None, None, None, None, vec![port])],
+        };
+        let hosts = process_nmap_results(&scan);
+        let dp = &hosts[0].ports[0];
+        assert_eq!(dp.http_title.as_deref(), Some("Welcome to nginx!"));
+        assert_eq!(dp.server_header.as_deref(), Some("nginx/1.18.0 (Ubuntu)"));
+    }
+
     #[test]
     fn test_process_results_multiple_hosts() {
         let scan = NmapScanResult {
@@ -557,6 +1469,90 @@ mod tests {
         assert_eq!(hosts[1].ip, "10.0.0.3");
     }
 
+    #[test]
+    fn test_process_results_ipv6_only_host() {
+        let mut host = make_nmap_host(
+            "unused",
+            "up",
+            None,
+            None,
+            Some("printer.lan"),
+            None,
+            vec![make_nmap_port(631, "open", Some("ipp"))],
+        );
+        host.addresses.clear();
+        host.addresses
+            .insert("ipv6".to_string(), "FE80:0:0:0::0001".to_string());
+        let scan = NmapScanResult {
+            scan_info: HashMap::new(),
+            hosts: vec![host],
+        };
+
+        let hosts = process_nmap_results(&scan);
+        assert_eq!(hosts.len(), 1);
+        assert_eq!(hosts[0].ip, "fe80::1");
+        assert_eq!(hosts[0].hostname.as_deref(), Some("printer.lan"));
+        assert_eq!(hosts[0].ports.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_persist_ipv6_only_host() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let scanner = ActiveScanner::new(pool.clone(), EventBus::new());
+
+        let mut host = make_nmap_host(
+            "unused",
+            "up",
+            None,
+            None,
+            None,
+            None,
+            vec![
+                make_nmap_port(22, "open", Some("ssh")),
+                make_nmap_port(80, "open", Some("http")),
+            ],
+        );
+        host.addresses.clear();
+        host.addresses
+            .insert("ipv6".to_string(), "2001:db8::10".to_string());
+        let scan = NmapScanResult {
+            scan_info: HashMap::new(),
+            hosts: vec![host],
+        };
+
+        let devices = scanner
+            .persist_hosts(&process_nmap_results(&scan))
+            .await
+            .unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].ip, "2001:db8::10");
+        assert_ne!(devices[0].device_type, "unknown");
+
+        let from_db = netsec_db::repo::devices::get_by_ip(&pool, "2001:db8::10")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(from_db.id, devices[0].id);
+        assert_eq!(from_db.device_type, devices[0].device_type);
+        let ports = netsec_db::repo::ports::list_by_device(&pool, &from_db.id)
+            .await
+            .unwrap();
+        assert_eq!(ports.len(), 2);
+
+        // A rescan reporting the address in another spelling updates the same device
+        let again = vec![DiscoveredHost {
+            ip: canonical_ip("2001:DB8:0::10"),
+            mac: None,
+            hostname: None,
+            vendor: None,
+            os_info: None,
+            ports: Vec::new(),
+        }];
+        let devices = scanner.persist_hosts(&again).await.unwrap();
+        assert_eq!(devices[0].id, from_db.id);
+    }
+
     #[tokio::test]
     async fn test_persist_inserts_new_device() {
         let pool = netsec_db::pool::create_test_pool().await.unwrap();
@@ -577,6 +1573,8 @@ mod tests {
                     state: "open".to_string(),
                     service_name: Some("ssh".to_string()),
                     service_version: None,
+                    http_title: None,
+                    server_header: None,
                 },
                 DiscoveredPort {
                     port: 80,
@@ -584,6 +1582,8 @@ mod tests {
                     state: "open".to_string(),
                     service_name: Some("http".to_string()),
                     service_version: None,
+                    http_title: None,
+                    server_header: None,
                 },
             ],
         }];
@@ -607,6 +1607,107 @@ mod tests {
         assert_eq!(ports.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_persist_fills_vendor_from_oui() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let scanner = ActiveScanner::new(pool.clone(), EventBus::new());
+
+        let host = |ip: &str, mac: &str, vendor: Option<&str>| DiscoveredHost {
+            ip: ip.to_string(),
+            mac: Some(mac.to_string()),
+            hostname: None,
+            vendor: vendor.map(str::to_string),
+            os_info: None,
+            ports: Vec::new(),
+        };
+        let devices = scanner
+            .persist_hosts(&[
+                host("10.0.0.5", "b8:27:eb:01:02:03", None),
+                host("10.0.0.6", "00:50:56:01:02:03", Some("VMware, Inc.")),
+                host("10.0.0.7", "02:00:00:01:02:03", None),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(devices[0].vendor.as_deref(), Some("Raspberry Pi Foundation"));
+        // nmap's vendor wins
+        assert_eq!(devices[1].vendor.as_deref(), Some("VMware, Inc."));
+        assert_eq!(devices[2].vendor, None);
+
+        // Rescans without an nmap vendor fill a missing one
+        let mut existing = Device::new("10.0.0.8".to_string());
+        existing.mac = Some("DC:A6:32:00:00:01".to_string());
+        netsec_db::repo::devices::insert(&pool, &existing).await.unwrap();
+        let devices = scanner
+            .persist_hosts(&[host("10.0.0.8", "DC:A6:32:00:00:01", None)])
+            .await
+            .unwrap();
+        assert_eq!(devices[0].vendor.as_deref(), Some("Raspberry Pi Foundation"));
+    }
+
+    #[tokio::test]
+    async fn test_persist_flags_duplicate_mac() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = EventBus::new();
+        let scanner = ActiveScanner::new(pool.clone(), bus);
+
+        let host = |ip: &str, mac: &str| DiscoveredHost {
+            ip: ip.to_string(),
+            mac: Some(mac.to_string()),
+            hostname: None,
+            vendor: None,
+            os_info: None,
+            ports: vec![],
+        };
+        let hosts = vec![
+            host("10.0.0.1", "AA:BB:CC:00:00:01"),
+            host("10.0.0.2", "AA:BB:CC:00:00:01"),
+            host("10.0.0.3", "AA:BB:CC:00:00:03"),
+        ];
+
+        scanner.persist_hosts(&hosts).await.unwrap();
+        let alert = netsec_db::repo::alerts::get_by_fingerprint(&pool, "duplicate-mac:aa:bb:cc:00:00:01")
+            .await
+            .unwrap()
+            .expect("duplicate MAC alert");
+        assert_eq!(alert.severity, "info");
+        assert!(alert.description.contains("10.0.0.1, 10.0.0.2"));
+
+        // A rescan bumps the existing alert instead of creating another
+        scanner.persist_hosts(&hosts).await.unwrap();
+        assert_eq!(netsec_db::repo::alerts::count(&pool).await.unwrap(), 1);
+        let alert = netsec_db::repo::alerts::get_by_id(&pool, &alert.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alert.count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_persist_skips_allowlisted_mac() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = EventBus::new();
+        let scanner = ActiveScanner::new(pool.clone(), bus)
+            .with_mac_allowlist(vec!["aa:bb:cc:00:00:01".to_string()]);
+
+        let hosts: Vec<DiscoveredHost> = ["10.0.0.1", "10.0.0.2"]
+            .iter()
+            .map(|ip| DiscoveredHost {
+                ip: ip.to_string(),
+                mac: Some("AA:BB:CC:00:00:01".to_string()),
+                hostname: None,
+                vendor: None,
+                os_info: None,
+                ports: vec![],
+            })
+            .collect();
+
+        scanner.persist_hosts(&hosts).await.unwrap();
+        assert_eq!(netsec_db::repo::alerts::count(&pool).await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_persist_updates_existing() {
         let pool = netsec_db::pool::create_test_pool().await.unwrap();
@@ -657,6 +1758,8 @@ mod tests {
                 state: "open".to_string(),
                 service_name: Some("http".to_string()),
                 service_version: None,
+                http_title: Some("Dashboard".to_string()),
+                server_header: Some("nginx/1.24".to_string()),
             }],
         }];
         let devices1 = scanner.persist_hosts(&hosts1).await.unwrap();
@@ -675,6 +1778,8 @@ mod tests {
                 state: "open".to_string(),
                 service_name: Some("http".to_string()),
                 service_version: Some("nginx/1.24".to_string()),
+                http_title: None,
+                server_header: None,
             }],
         }];
         scanner.persist_hosts(&hosts2).await.unwrap();
@@ -685,6 +1790,9 @@ mod tests {
             .unwrap();
         assert_eq!(ports.len(), 1);
         assert_eq!(ports[0].service_version.as_deref(), Some("nginx/1.24"));
+        // Web findings from the first scan survive a scan without NSE output
+        assert_eq!(ports[0].http_title.as_deref(), Some("Dashboard"));
+        assert_eq!(ports[0].server_header.as_deref(), Some("nginx/1.24"));
     }
 
     #[tokio::test]
@@ -707,6 +1815,8 @@ mod tests {
                     state: "open".to_string(),
                     service_name: Some("ssh".to_string()),
                     service_version: None,
+                    http_title: None,
+                    server_header: None,
                 },
                 DiscoveredPort {
                     port: 80,
@@ -714,6 +1824,8 @@ mod tests {
                     state: "open".to_string(),
                     service_name: Some("http".to_string()),
                     service_version: None,
+                    http_title: None,
+                    server_header: None,
                 },
                 DiscoveredPort {
                     port: 443,
@@ -721,6 +1833,8 @@ mod tests {
                     state: "open".to_string(),
                     service_name: Some("https".to_string()),
                     service_version: None,
+                    http_title: None,
+                    server_header: None,
                 },
             ],
         }];
@@ -753,6 +1867,61 @@ mod tests {
         assert_eq!(event.event_type, EventType::DeviceDiscovered);
     }
 
+    #[tokio::test]
+    async fn test_persist_rolls_back_on_port_failure() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TRIGGER fail_port_666 BEFORE INSERT ON ports
+             WHEN NEW.port_number = 666
+             BEGIN SELECT RAISE(ABORT, 'injected port failure'); END;",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        let scanner = ActiveScanner::new(pool.clone(), bus);
+
+        let port = |port: u16| DiscoveredPort {
+            port,
+            protocol: "tcp".to_string(),
+            state: "open".to_string(),
+            service_name: None,
+            service_version: None,
+            http_title: None,
+            server_header: None,
+        };
+        let host = |ip: &str, ports: Vec<DiscoveredPort>| DiscoveredHost {
+            ip: ip.to_string(),
+            mac: None,
+            hostname: None,
+            vendor: None,
+            os_info: None,
+            ports,
+        };
+        let hosts = vec![
+            host("10.0.0.66", vec![port(22), port(666)]),
+            host("10.0.0.65", vec![port(22)]),
+        ];
+
+        // The failing host is skipped; the other one is still stored
+        let devices = scanner.persist_hosts(&hosts).await.unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].ip, "10.0.0.65");
+        assert!(netsec_db::repo::devices::get_by_ip(&pool, "10.0.0.66")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(netsec_db::repo::devices::count(&pool).await.unwrap(), 1);
+        let stored = netsec_db::repo::ports::list_by_device(&pool, &devices[0].id).await.unwrap();
+        assert_eq!(stored.len(), 1);
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.payload["ip"], "10.0.0.65");
+        assert!(rx.try_recv().is_err(), "no event for a rolled-back device");
+    }
+
     // ── ScanConfig::validate tests ──────────────────────────────────────
 
     #[test]
@@ -761,7 +1930,7 @@ mod tests {
             target: "192.168.1.0/24".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         assert!(config.validate().is_ok());
     }
@@ -772,7 +1941,7 @@ mod tests {
             target: "fe80::1".to_string(),
             scan_type: ScanType::Discovery,
             timing: 3,
-            ports: None,
+            ..Default::default()
         };
         assert!(config.validate().is_ok());
     }
@@ -783,7 +1952,7 @@ mod tests {
             target: "".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("target must not be empty"));
@@ -796,7 +1965,7 @@ mod tests {
             target: "192.168.1.1; rm -rf /".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("invalid character"));
@@ -806,7 +1975,7 @@ mod tests {
             target: "`whoami`".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
         assert!(config2.validate().is_err());
     }
@@ -818,6 +1987,7 @@ mod tests {
             scan_type: ScanType::Port,
             timing: 3,
             ports: Some("22,80,443".to_string()),
+            ..Default::default()
         };
         assert!(config.validate().is_ok());
     }
@@ -829,6 +1999,7 @@ mod tests {
             scan_type: ScanType::Port,
             timing: 3,
             ports: Some("22,http,443".to_string()),
+            ..Default::default()
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("invalid character"));
@@ -841,7 +2012,7 @@ mod tests {
                 target: "10.0.0.1".to_string(),
                 scan_type: ScanType::Discovery,
                 timing: t,
-                ports: None,
+                ..Default::default()
             };
             assert!(config.validate().is_ok(), "timing {t} should be valid");
         }
@@ -853,7 +2024,7 @@ mod tests {
             target: "10.0.0.1".to_string(),
             scan_type: ScanType::Discovery,
             timing: 6,
-            ports: None,
+            ..Default::default()
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("timing must be 0-5"));
@@ -0,0 +1,146 @@
+//! Link-layer frame sources for the passive ARP/DHCP listener.
+//!
+//! [`PacketSource`] abstracts where frames come from so the listener can be
+//! driven by a raw socket on a real interface or by canned frames in tests.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::ScannerResult;
+
+/// The future returned by [`PacketSource::next_frame`].
+pub type FrameFuture<'a> =
+    Pin<Box<dyn Future<Output = ScannerResult<Option<Vec<u8>>>> + Send + 'a>>;
+
+/// A stream of raw Ethernet frames.
+pub trait PacketSource: Send {
+    /// The next captured frame, or `None` once the source is exhausted.
+    fn next_frame(&mut self) -> FrameFuture<'_>;
+}
+
+/// Frames from an in-memory list, in order.
+#[derive(Debug, Clone, Default)]
+pub struct VecSource {
+    frames: std::collections::VecDeque<Vec<u8>>,
+}
+
+impl VecSource {
+    pub fn new(frames: Vec<Vec<u8>>) -> Self {
+        Self {
+            frames: frames.into(),
+        }
+    }
+}
+
+impl PacketSource for VecSource {
+    fn next_frame(&mut self) -> FrameFuture<'_> {
+        let frame = self.frames.pop_front();
+        Box::pin(async move { Ok(frame) })
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use raw::RawSocketSource;
+
+#[cfg(target_os = "linux")]
+mod raw {
+    use std::io::Read;
+
+    use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+    use tokio::io::unix::AsyncFd;
+
+    use super::*;
+    use crate::ScannerError;
+
+    /// `AF_PACKET` address family.
+    const AF_PACKET: u16 = 17;
+    /// `ETH_P_ALL`: every protocol.
+    const ETH_P_ALL: u16 = 0x0003;
+    /// Largest frame read: a standard MTU plus Ethernet and VLAN headers.
+    const MAX_FRAME: usize = 1522;
+
+    fn capture_err(iface: &str, what: &str, e: std::io::Error) -> ScannerError {
+        ScannerError::PassiveParse(format!("capture on {iface}: {what} failed: {e}"))
+    }
+
+    /// An `AF_PACKET` raw socket bound to one interface.
+    ///
+    /// Opening it needs `CAP_NET_RAW` (or root).
+    pub struct RawSocketSource {
+        socket: AsyncFd<Socket>,
+        buf: Vec<u8>,
+    }
+
+    impl RawSocketSource {
+        /// Open a raw socket capturing every frame on `iface`.
+        pub fn open(iface: &str) -> ScannerResult<Self> {
+            let ifindex = interface_index(iface)?;
+            let protocol = Protocol::from(i32::from(ETH_P_ALL.to_be()));
+            let socket = Socket::new(Domain::PACKET, Type::RAW, Some(protocol))
+                .map_err(|e| capture_err(iface, "socket creation", e))?;
+            socket
+                .bind(&link_addr(ifindex))
+                .map_err(|e| capture_err(iface, "bind", e))?;
+            socket
+                .set_nonblocking(true)
+                .map_err(|e| capture_err(iface, "set_nonblocking", e))?;
+            let socket = AsyncFd::new(socket).map_err(|e| capture_err(iface, "register", e))?;
+            Ok(Self {
+                socket,
+                buf: vec![0; MAX_FRAME],
+            })
+        }
+    }
+
+    impl PacketSource for RawSocketSource {
+        fn next_frame(&mut self) -> FrameFuture<'_> {
+            Box::pin(async move {
+                loop {
+                    let mut guard = self.socket.readable().await.map_err(|e| {
+                        ScannerError::PassiveParse(format!("capture wait failed: {e}"))
+                    })?;
+                    match guard.try_io(|s| s.get_ref().read(&mut self.buf)) {
+                        Ok(Ok(len)) => return Ok(Some(self.buf[..len].to_vec())),
+                        Ok(Err(e)) => {
+                            return Err(ScannerError::PassiveParse(format!(
+                                "capture read failed: {e}"
+                            )))
+                        }
+                        Err(_would_block) => continue,
+                    }
+                }
+            })
+        }
+    }
+
+    /// The kernel index of `iface`, from sysfs.
+    fn interface_index(iface: &str) -> ScannerResult<i32> {
+        let path = format!("/sys/class/net/{iface}/ifindex");
+        std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or_else(|| ScannerError::Validation(format!("unknown interface: {iface}")))
+    }
+
+    /// A `sockaddr_ll` selecting every protocol on interface `ifindex`.
+    fn link_addr(ifindex: i32) -> SockAddr {
+        // struct sockaddr_ll { u16 family, u16 protocol (BE), i32 ifindex, u16 hatype,
+        // u8 pkttype, u8 halen, u8 addr[8] }
+        let mut ll = [0u8; 20];
+        ll[0..2].copy_from_slice(&AF_PACKET.to_ne_bytes());
+        ll[2..4].copy_from_slice(&ETH_P_ALL.to_be_bytes());
+        ll[4..8].copy_from_slice(&ifindex.to_ne_bytes());
+        // SAFETY: `try_init` hands out a zeroed sockaddr_storage, which is
+        // larger than and suitably aligned for the 20-byte sockaddr_ll written
+        // into it.
+        let ((), addr) = unsafe {
+            SockAddr::try_init(|storage, len| {
+                std::ptr::copy_nonoverlapping(ll.as_ptr(), storage.cast::<u8>(), ll.len());
+                *len = ll.len() as _;
+                Ok(())
+            })
+        }
+        .expect("sockaddr_ll fits in sockaddr_storage");
+        addr
+    }
+}
@@ -1,71 +1,55 @@
-//! Nmap executor: binary discovery, privilege checking, and scan execution.
+//! Nmap and masscan executors: binary discovery, privilege checking, and scan
+//! execution.
 
+use std::future::Future;
 use std::path::PathBuf;
+use std::pin::Pin;
 
 use netsec_models::scan::ScanType;
 use netsec_parsers::nmap::NmapScanResult;
+use netsec_platform::detect::BinaryResolver;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
-use crate::active::{build_nmap_args, ScanConfig};
+use crate::active::{build_masscan_args, build_nmap_args, ScanConfig, ScannerEngine};
 use crate::{ScannerError, ScannerResult};
 
 /// Find the nmap binary on the system.
 ///
-/// 1. Tries `where nmap` (Windows) or `which nmap` (Unix) via `std::process::Command`.
-/// 2. Falls back to common installation paths.
-/// 3. Returns `None` if nmap cannot be found.
+/// Resolved through the shared [`BinaryResolver`], which checks `PATH` and
+/// common installation paths once and caches the hit. Returns `None` if
+/// nmap cannot be found.
 pub fn find_nmap_binary() -> Option<PathBuf> {
-    // Try the system PATH lookup command
-    #[cfg(windows)]
-    let which_result = std::process::Command::new("where")
-        .arg("nmap")
-        .output();
-
-    #[cfg(not(windows))]
-    let which_result = std::process::Command::new("which")
-        .arg("nmap")
-        .output();
-
-    if let Ok(output) = which_result {
-        if output.status.success() {
-            let path_str = String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .next()
-                .unwrap_or("")
-                .trim()
-                .to_string();
-            if !path_str.is_empty() {
-                let path = PathBuf::from(&path_str);
-                if path.exists() {
-                    return Some(path);
-                }
-            }
-        }
-    }
+    BinaryResolver::shared().resolve("nmap")
+}
 
-    // Check common fallback paths
-    let fallback_paths = [
-        "/usr/bin/nmap",
-        "/usr/local/bin/nmap",
-        "/opt/homebrew/bin/nmap",
-        r"C:\Program Files (x86)\Nmap\nmap.exe",
-        r"C:\Program Files\Nmap\nmap.exe",
-    ];
+/// Message of the error [`preflight_nmap`] returns.
+pub const NMAP_NOT_FOUND: &str = "nmap not found in PATH; install it or configure a path";
 
-    for path_str in &fallback_paths {
-        let path = PathBuf::from(path_str);
-        if path.exists() {
-            return Some(path);
-        }
-    }
+/// Resolve the nmap binary through `resolver` before anything is spawned.
+///
+/// A missing nmap, or a configured path that doesn't exist or isn't
+/// executable, fails here with [`NMAP_NOT_FOUND`] instead of as an OS error
+/// from the spawn.
+pub fn preflight_nmap(resolver: &BinaryResolver) -> ScannerResult<PathBuf> {
+    resolver
+        .resolve("nmap")
+        .ok_or_else(|| ScannerError::NmapExecution(NMAP_NOT_FOUND.to_string()))
+}
 
-    None
+/// Find the masscan binary on the system, the same way as
+/// [`find_nmap_binary`].
+pub fn find_masscan_binary() -> Option<PathBuf> {
+    BinaryResolver::shared().resolve("masscan")
 }
 
 /// Check if current privileges are sufficient for the given scan type.
 ///
 /// - `Discovery` scans need no elevation (uses `-sn` ping scan).
 /// - `Port`, `Full`, `Vulnerability`, and `Custom` scans require elevation
-///   because they use SYN scan (`-sS`) which needs raw sockets.
+///   because they use SYN scan (`-sS`) which needs raw sockets; UDP scans
+///   (`-sU`) need raw sockets as well.
 pub fn check_scan_privileges(scan_type: &ScanType) -> ScannerResult<()> {
     match scan_type {
         ScanType::Discovery => Ok(()),
@@ -73,56 +57,314 @@ pub fn check_scan_privileges(scan_type: &ScanType) -> ScannerResult<()> {
             if netsec_platform::privileges::is_elevated() {
                 Ok(())
             } else {
-                Err(ScannerError::NmapExecution(
-                    "requires elevated privileges for SYN scan (-sS)".to_string(),
-                ))
+                let flag = if scan_type.includes_udp() { "UDP scan (-sU)" } else { "SYN scan (-sS)" };
+                Err(ScannerError::NmapExecution(format!(
+                    "requires elevated privileges for {flag}"
+                )))
             }
         }
     }
 }
 
-/// Execute nmap with the given scan configuration and return parsed results.
+/// Receives scan progress from a [`ScanExecutor`], as a percentage.
+pub type ProgressSender = mpsc::UnboundedSender<f64>;
+
+/// How often nmap prints runtime statistics (`--stats-every`).
+const STATS_INTERVAL: &str = "5s";
+
+/// Parse the completion percentage from one line of nmap output.
 ///
-/// 1. Finds the nmap binary.
+/// Recognizes both the text status lines nmap prints with `--stats-every`
+/// (`SYN Stealth Scan Timing: About 12.34% done; ETC: 10:00 (0:01:02 remaining)`)
+/// and their XML form (`<taskprogress task="SYN Stealth Scan" percent="12.34" .../>`).
+/// The percentage covers the current scan phase only, not the whole scan.
+pub fn parse_progress_line(line: &str) -> Option<f64> {
+    let raw = if line.contains("<taskprogress") {
+        line.split_once("percent=\"")?.1.split_once('"')?.0
+    } else {
+        line.split_once("About ")?.1.split_once("% done")?.0
+    };
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|percent| (0.0..=100.0).contains(percent))
+}
+
+/// Parse the completion percentage from one masscan status line
+/// (`rate:  0.10-kpps, 12.34% done,   0:01:02 remaining, found=3`).
+pub fn parse_masscan_progress_line(line: &str) -> Option<f64> {
+    line.split_once("% done")?
+        .0
+        .rsplit(|c: char| c == ',' || c.is_whitespace())
+        .next()?
+        .parse::<f64>()
+        .ok()
+        .filter(|percent| (0.0..=100.0).contains(percent))
+}
+
+/// Runs a scan for [`ActiveScanner`](crate::active::ActiveScanner).
+///
+/// Implementations report progress through `progress` as they go, and must
+/// return [`ScannerError::Cancelled`] promptly once `cancel` fires, stopping
+/// any work still in flight.
+pub trait ScanExecutor: Send + Sync {
+    fn execute<'a>(
+        &'a self,
+        config: &'a ScanConfig,
+        cancel: CancellationToken,
+        progress: ProgressSender,
+    ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>>;
+}
+
+/// Runs the system nmap via [`execute_nmap_with`].
+#[derive(Debug, Clone, Default)]
+pub struct NmapExecutor {
+    /// Finds nmap; the shared resolver if `None`.
+    resolver: Option<BinaryResolver>,
+}
+
+impl NmapExecutor {
+    /// An executor finding nmap through `resolver` rather than
+    /// [`BinaryResolver::shared`].
+    pub fn with_resolver(resolver: BinaryResolver) -> Self {
+        Self {
+            resolver: Some(resolver),
+        }
+    }
+}
+
+impl ScanExecutor for NmapExecutor {
+    fn execute<'a>(
+        &'a self,
+        config: &'a ScanConfig,
+        cancel: CancellationToken,
+        progress: ProgressSender,
+    ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+        let resolver = self.resolver.as_ref().unwrap_or(BinaryResolver::shared());
+        Box::pin(execute_nmap_with(resolver, config, cancel, progress))
+    }
+}
+
+/// Runs masscan via [`execute_masscan`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MasscanExecutor;
+
+impl ScanExecutor for MasscanExecutor {
+    fn execute<'a>(
+        &'a self,
+        config: &'a ScanConfig,
+        cancel: CancellationToken,
+        progress: ProgressSender,
+    ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+        Box::pin(execute_masscan(config, cancel, progress))
+    }
+}
+
+/// The executor [`ActiveScanner`](crate::active::ActiveScanner) uses unless
+/// given another: runs the system nmap or masscan, as `config.scanner`
+/// selects.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemExecutor;
+
+impl ScanExecutor for SystemExecutor {
+    fn execute<'a>(
+        &'a self,
+        config: &'a ScanConfig,
+        cancel: CancellationToken,
+        progress: ProgressSender,
+    ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+        match config.scanner {
+            ScannerEngine::Nmap => Box::pin(execute_nmap(config, cancel, progress)),
+            ScannerEngine::Masscan => MasscanExecutor.execute(config, cancel, progress),
+        }
+    }
+}
+
+/// Execute nmap with the given scan configuration and return parsed results,
+/// finding nmap through the shared [`BinaryResolver`].
+pub async fn execute_nmap(
+    config: &ScanConfig,
+    cancel: CancellationToken,
+    progress: ProgressSender,
+) -> ScannerResult<NmapScanResult> {
+    execute_nmap_with(BinaryResolver::shared(), config, cancel, progress).await
+}
+
+/// Execute nmap found through `resolver` and return parsed results.
+///
+/// 1. Finds the nmap binary ([`preflight_nmap`]).
 /// 2. Checks privileges for the scan type.
 /// 3. Builds nmap arguments.
-/// 4. Spawns nmap as a subprocess, capturing stdout.
+/// 4. Spawns nmap as a subprocess, reading stdout line by line and sending
+///    the percentage of each runtime status line to `progress`.
 /// 5. Parses XML output via `netsec_parsers::nmap::parse_nmap_xml()`.
-pub async fn execute_nmap(config: &ScanConfig) -> ScannerResult<NmapScanResult> {
+///
+/// If `cancel` fires while nmap runs, the process is killed and
+/// [`ScannerError::Cancelled`] is returned.
+pub async fn execute_nmap_with(
+    resolver: &BinaryResolver,
+    config: &ScanConfig,
+    cancel: CancellationToken,
+    progress: ProgressSender,
+) -> ScannerResult<NmapScanResult> {
     config.validate()?;
 
-    let nmap_path = find_nmap_binary().ok_or_else(|| {
-        ScannerError::NmapExecution(
-            "nmap binary not found; install nmap or add it to PATH".to_string(),
-        )
-    })?;
+    let nmap_path = preflight_nmap(resolver)?;
 
     check_scan_privileges(&config.scan_type)?;
 
-    let args = build_nmap_args(config);
+    // Runtime-only flag, so it isn't part of `build_nmap_args` (or dry runs)
+    let mut args = vec!["--stats-every".to_string(), STATS_INTERVAL.to_string()];
+    args.extend(build_nmap_args(config));
 
-    let output = tokio::process::Command::new(&nmap_path)
+    // Killed when dropped, i.e. on cancellation or any early return
+    let mut child = tokio::process::Command::new(&nmap_path)
         .args(&args)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| ScannerError::NmapExecution(format!("failed to spawn nmap: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            // The cached path may be stale (nmap moved or uninstalled)
+            resolver.invalidate("nmap");
+            ScannerError::NmapExecution(format!("failed to spawn nmap: {e}"))
+        })?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let run = async {
+        let mut reader = BufReader::new(stdout);
+        let mut output = String::new();
+        let read_stdout = async {
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                if reader.read_until(b'\n', &mut line).await? == 0 {
+                    return Ok::<_, std::io::Error>(());
+                }
+                let line = String::from_utf8_lossy(&line);
+                if let Some(percent) = parse_progress_line(&line) {
+                    let _ = progress.send(percent);
+                }
+                output.push_str(&line);
+            }
+        };
+        let mut errors = Vec::new();
+        tokio::try_join!(read_stdout, stderr.read_to_end(&mut errors))?;
+        let status = child.wait().await?;
+        Ok::<_, std::io::Error>((status, output, errors))
+    };
+    let (status, stdout, stderr) = tokio::select! {
+        result = run => result
+            .map_err(|e| ScannerError::NmapExecution(format!("failed to run nmap: {e}")))?,
+        _ = cancel.cancelled() => return Err(ScannerError::Cancelled),
+    };
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
         return Err(ScannerError::NmapExecution(format!(
             "nmap exited with status {}: {}",
-            output.status,
+            status,
             stderr.trim()
         )));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
     netsec_parsers::nmap::parse_nmap_xml(&stdout)
         .map_err(|e| ScannerError::NmapParse(e.to_string()))
 }
 
+/// Execute masscan with the given scan configuration and return its results.
+///
+/// Mirrors [`execute_nmap`], except that:
+/// - masscan sends raw packets for every scan type, so it always needs
+///   elevated privileges;
+/// - progress comes from the status line masscan redraws on stderr;
+/// - the `-oJ` output is parsed by
+///   `netsec_parsers::masscan::parse_masscan_json()` and grouped per host
+///   into an [`NmapScanResult`], so hosts persist exactly like nmap's.
+///
+/// If `cancel` fires while masscan runs, the process is killed and
+/// [`ScannerError::Cancelled`] is returned.
+pub async fn execute_masscan(
+    config: &ScanConfig,
+    cancel: CancellationToken,
+    progress: ProgressSender,
+) -> ScannerResult<NmapScanResult> {
+    config.validate()?;
+
+    let masscan_path = find_masscan_binary().ok_or_else(|| {
+        ScannerError::MasscanExecution(
+            "masscan binary not found; install masscan or add it to PATH".to_string(),
+        )
+    })?;
+
+    if !netsec_platform::privileges::is_elevated() {
+        return Err(ScannerError::MasscanExecution(
+            "requires elevated privileges for raw packet scanning".to_string(),
+        ));
+    }
+
+    // Killed when dropped, i.e. on cancellation or any early return
+    let mut child = tokio::process::Command::new(&masscan_path)
+        .args(build_masscan_args(config)?)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            BinaryResolver::shared().invalidate("masscan");
+            ScannerError::MasscanExecution(format!("failed to spawn masscan: {e}"))
+        })?;
+
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let run = async {
+        let mut output = Vec::new();
+        let mut errors = String::new();
+        let read_stderr = async {
+            // The status line is redrawn in place, ending in `\r`
+            let mut reader = BufReader::new(stderr);
+            let mut chunk = Vec::new();
+            loop {
+                chunk.clear();
+                if reader.read_until(b'\r', &mut chunk).await? == 0 {
+                    return Ok::<_, std::io::Error>(());
+                }
+                for line in String::from_utf8_lossy(&chunk).split(['\r', '\n']) {
+                    if let Some(percent) = parse_masscan_progress_line(line) {
+                        let _ = progress.send(percent);
+                    } else if !line.trim().is_empty() {
+                        errors.push_str(line.trim());
+                        errors.push('\n');
+                    }
+                }
+            }
+        };
+        tokio::try_join!(stdout.read_to_end(&mut output), read_stderr)?;
+        let status = child.wait().await?;
+        Ok::<_, std::io::Error>((status, output, errors))
+    };
+
+    let (status, stdout, stderr) = tokio::select! {
+        result = run => result
+            .map_err(|e| ScannerError::MasscanExecution(format!("failed to run masscan: {e}")))?,
+        _ = cancel.cancelled() => return Err(ScannerError::Cancelled),
+    };
+
+    if !status.success() {
+        return Err(ScannerError::MasscanExecution(format!(
+            "masscan exited with status {}: {}",
+            status,
+            stderr.trim()
+        )));
+    }
+
+    let records = netsec_parsers::masscan::parse_masscan_json(&String::from_utf8_lossy(&stdout))
+        .map_err(|e| ScannerError::MasscanParse(e.to_string()))?;
+    Ok(netsec_parsers::masscan::to_nmap_result(&records))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,18 +411,61 @@ mod tests {
             target: "127.0.0.1".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
 
         if find_nmap_binary().is_none() {
-            let result = execute_nmap(&config).await;
+            let (progress, _) = mpsc::unbounded_channel();
+            let result = execute_nmap(&config, CancellationToken::new(), progress).await;
             assert!(result.is_err());
             let err = result.unwrap_err().to_string();
-            assert!(err.contains("nmap binary not found"));
+            assert_eq!(err, format!("nmap execution error: {NMAP_NOT_FOUND}"));
         }
         // If nmap exists, this test is a no-op (we don't want to actually scan)
     }
 
+    #[tokio::test]
+    async fn test_preflight_reports_missing_nmap() {
+        let dir = std::env::temp_dir().join(format!("netsec-preflight-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let lookup: netsec_platform::detect::FileLookup =
+            std::sync::Arc::new(netsec_platform::detect::is_executable);
+        let resolver = BinaryResolver::with_lookup(vec![dir.clone()], lookup);
+
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let scanner = crate::active::ActiveScanner::new(pool.clone(), bus)
+            .with_executor(Box::new(NmapExecutor::with_resolver(resolver.clone())));
+        let config = ScanConfig {
+            target: "192.168.99.0/24".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            ..Default::default()
+        };
+        let expect_not_found = |result: ScannerResult<()>| match result {
+            Err(ScannerError::NmapExecution(message)) => assert_eq!(message, NMAP_NOT_FOUND),
+            other => panic!("expected the preflight error, got {other:?}"),
+        };
+
+        // Nothing in the search path
+        expect_not_found(scanner.run_scan(&config).await.map(|_| ()));
+        let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+        assert_eq!(scans[0].status, "failed");
+
+        // A file named nmap that can't be executed
+        let fake = dir.join("nmap");
+        std::fs::write(&fake, "not a binary").unwrap();
+        #[cfg(unix)]
+        expect_not_found(preflight_nmap(&resolver).map(|_| ()));
+
+        // A configured path that doesn't exist
+        resolver.set_override("nmap", dir.join("missing/nmap"));
+        expect_not_found(scanner.run_scan(&config).await.map(|_| ()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[tokio::test]
     async fn test_run_scan_creates_record() {
         let pool = netsec_db::pool::create_test_pool().await.unwrap();
@@ -192,7 +477,7 @@ mod tests {
             target: "192.168.99.0/24".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
 
         let scan = scanner.create_scan_record(&config).await.unwrap();
@@ -218,7 +503,7 @@ mod tests {
             target: "192.168.99.0/24".to_string(),
             scan_type: ScanType::Discovery,
             timing: 4,
-            ports: None,
+            ..Default::default()
         };
 
         // run_scan will fail because nmap probably isn't installed in test env
@@ -232,4 +517,299 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_masscan_progress_line() {
+        let line = "rate:  0.10-kpps, 12.34% done,   0:01:02 remaining, found=3       ";
+        assert_eq!(parse_masscan_progress_line(line), Some(12.34));
+        let done = "rate:  9.98-kpps, 100.00% done, found=7";
+        assert_eq!(parse_masscan_progress_line(done), Some(100.0));
+        assert_eq!(parse_masscan_progress_line("Scanning 256 hosts [1 port/host]"), None);
+        assert_eq!(parse_masscan_progress_line("rate: 1-kpps, lots% done"), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_masscan_no_binary() {
+        let config = ScanConfig {
+            target: "127.0.0.1".to_string(),
+            scan_type: ScanType::Port,
+            timing: 3,
+            ports: Some("80".to_string()),
+            scanner: ScannerEngine::Masscan,
+            ..Default::default()
+        };
+        if find_masscan_binary().is_none() {
+            // The default executor picks masscan from the config
+            let (progress, _) = mpsc::unbounded_channel();
+            let err = SystemExecutor
+                .execute(&config, CancellationToken::new(), progress)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("masscan binary not found"));
+        }
+    }
+
+    #[test]
+    fn test_parse_progress_line() {
+        let text = "SYN Stealth Scan Timing: About 12.34% done; ETC: 10:00 (0:01:02 remaining)";
+        assert_eq!(parse_progress_line(text), Some(12.34));
+        let xml = r#"<taskprogress task="Ping Scan" time="1700000000" percent="50.00"
+            remaining="3" etc="1700000003"/>"#;
+        assert_eq!(parse_progress_line(xml), Some(50.0));
+
+        assert_eq!(parse_progress_line("Stats: 0:00:05 elapsed; 0 hosts completed (1 up)"), None);
+        assert_eq!(parse_progress_line("<taskbegin task=\"Ping Scan\" time=\"1\"/>"), None);
+        assert_eq!(parse_progress_line("About 250% done"), None);
+        assert_eq!(parse_progress_line("About lots% done"), None);
+    }
+
+    /// Replays canned nmap output through the progress parser.
+    struct ScriptedExecutor {
+        output: &'static str,
+    }
+
+    impl ScanExecutor for ScriptedExecutor {
+        fn execute<'a>(
+            &'a self,
+            _config: &'a ScanConfig,
+            _cancel: CancellationToken,
+            progress: ProgressSender,
+        ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+            Box::pin(async move {
+                for line in self.output.lines() {
+                    if let Some(percent) = parse_progress_line(line) {
+                        progress.send(percent).unwrap();
+                    }
+                    tokio::task::yield_now().await;
+                }
+                Ok(NmapScanResult::default())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_publishes_progress() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let mut events =
+            bus.subscribe_filtered(vec![netsec_models::event::EventType::ScanProgress]);
+        let executor = ScriptedExecutor {
+            output: "\
+Stats: 0:00:02 elapsed; 0 hosts completed (1 up), 1 undergoing SYN Stealth Scan
+SYN Stealth Scan Timing: About 10.50% done; ETC: 10:00 (0:00:20 remaining)
+SYN Stealth Scan Timing: About 10.90% done; ETC: 10:00 (0:00:19 remaining)
+<taskprogress task=\"SYN Stealth Scan\" time=\"1\" percent=\"42.00\" remaining=\"9\"/>
+Service scan Timing: About 5.00% done; ETC: 10:01 (0:01:00 remaining)
+SYN Stealth Scan Timing: About 100.00% done; ETC: 10:00 (0:00:00 remaining)
+",
+        };
+        let scanner = crate::active::ActiveScanner::new(pool.clone(), bus)
+            .with_executor(Box::new(executor));
+
+        let config = ScanConfig {
+            target: "192.0.2.1".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            ..Default::default()
+        };
+        scanner.run_scan(&config).await.unwrap();
+
+        let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+        let mut progress = Vec::new();
+        while let Ok(Ok(event)) =
+            tokio::time::timeout(std::time::Duration::from_millis(100), events.recv()).await
+        {
+            assert_eq!(event.payload["scan_id"], scans[0].id.as_str());
+            progress.push(event.payload["progress"].as_u64().unwrap());
+        }
+        // Per-phase restarts are skipped and 100 is left for completion
+        assert_eq!(progress, vec![10, 42, 99, 100]);
+        assert_eq!(scans[0].status, "completed");
+        assert_eq!(scans[0].progress, 1.0);
+    }
+
+    /// Tracks how many executions overlap.
+    #[derive(Default)]
+    struct CountingExecutor {
+        current: std::sync::atomic::AtomicUsize,
+        peak: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl ScanExecutor for CountingExecutor {
+        fn execute<'a>(
+            &'a self,
+            _config: &'a ScanConfig,
+            _cancel: CancellationToken,
+            _progress: ProgressSender,
+        ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+            use std::sync::atomic::Ordering;
+            Box::pin(async move {
+                let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                self.current.fetch_sub(1, Ordering::SeqCst);
+                Ok(NmapScanResult::default())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_respects_max_concurrency() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let executor = CountingExecutor::default();
+        let peak = executor.peak.clone();
+        let scanner = std::sync::Arc::new(
+            crate::active::ActiveScanner::with_max_concurrency(pool.clone(), bus, 2)
+                .with_executor(Box::new(executor)),
+        );
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..6 {
+            let scanner = scanner.clone();
+            tasks.spawn(async move {
+                let config = ScanConfig {
+                    target: format!("192.0.2.{i}"),
+                    scan_type: ScanType::Discovery,
+                    timing: 4,
+                    ..Default::default()
+                };
+                scanner.run_scan(&config).await
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result.unwrap().unwrap();
+        }
+
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+        assert_eq!(scans.len(), 6);
+        assert!(scans.iter().all(|s| s.status == "completed"));
+    }
+
+    /// Never finishes on its own; only returns once cancelled.
+    struct HangingExecutor;
+
+    impl ScanExecutor for HangingExecutor {
+        fn execute<'a>(
+            &'a self,
+            _config: &'a ScanConfig,
+            cancel: CancellationToken,
+            _progress: ProgressSender,
+        ) -> Pin<Box<dyn Future<Output = ScannerResult<NmapScanResult>> + Send + 'a>> {
+            Box::pin(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(3600)) => {
+                        Ok(NmapScanResult::default())
+                    }
+                    _ = cancel.cancelled() => Err(ScannerError::Cancelled),
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_scan() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let scanner = std::sync::Arc::new(
+            crate::active::ActiveScanner::new(pool.clone(), bus)
+                .with_executor(Box::new(HangingExecutor)),
+        );
+        assert!(!scanner.cancel("no-such-scan"));
+
+        let config = ScanConfig {
+            target: "192.0.2.0/24".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            ..Default::default()
+        };
+        let task = {
+            let scanner = scanner.clone();
+            tokio::spawn(async move { scanner.run_scan(&config).await })
+        };
+
+        // Wait for the scan to start executing
+        let scan_id = loop {
+            let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+            if let Some(scan) = scans.first() {
+                if scanner.cancel(&scan.id) {
+                    break scan.id.clone();
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        };
+
+        // Idle only once the cancelled scan has recorded its status
+        tokio::time::timeout(std::time::Duration::from_secs(5), scanner.wait_idle())
+            .await
+            .expect("cancelled scan should stop");
+        let scan = netsec_db::repo::scans::get_by_id(&pool, &scan_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(scan.status, "cancelled");
+
+        let result = task.await.unwrap();
+        assert!(matches!(result, Err(ScannerError::Cancelled)));
+        // No longer running, so there is nothing left to cancel
+        assert!(!scanner.cancel(&scan_id));
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_dry_run_records_command() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let scanner = crate::active::ActiveScanner::new(pool.clone(), bus);
+
+        let config = ScanConfig {
+            target: "192.0.2.0/24".to_string(),
+            scan_type: ScanType::Port,
+            timing: 3,
+            ports: Some("22,80".to_string()),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        // A real run would fail here (no nmap binary or no privileges for -sS),
+        // so success shows the executor was never reached.
+        let devices = scanner.run_scan(&config).await.unwrap();
+        assert!(devices.is_empty());
+
+        let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].status, "completed");
+        let results: serde_json::Value =
+            serde_json::from_str(&scans[0].results).unwrap();
+        assert_eq!(results["dry_run"], true);
+        assert_eq!(
+            results["command"],
+            "nmap -sS -T3 -p 22,80 -oX - 192.0.2.0/24"
+        );
+        assert_eq!(results["hosts_found"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_dry_run_rejects_invalid_config() {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = netsec_events::EventBus::new();
+        let scanner = crate::active::ActiveScanner::new(pool.clone(), bus);
+
+        let config = ScanConfig {
+            target: "10.0.0.1; rm -rf /".to_string(),
+            scan_type: ScanType::Discovery,
+            timing: 4,
+            dry_run: true,
+            ..Default::default()
+        };
+        assert!(scanner.run_scan(&config).await.is_err());
+
+        let scans = netsec_db::repo::scans::list(&pool, 10, 0).await.unwrap();
+        assert_eq!(scans[0].status, "failed");
+    }
 }
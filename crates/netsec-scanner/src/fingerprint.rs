@@ -2,69 +2,128 @@
 
 use netsec_models::device::DeviceType;
 use netsec_models::port::Port;
-use std::collections::HashMap;
-use std::sync::LazyLock;
-
-/// Static OUI table mapping normalized MAC prefixes (first 8 chars, "XX:XX:XX")
-/// to vendor names. This is a curated subset for demo/testing purposes, not a
-/// full IEEE OUI database. See `data/oui_reference.json` for the canonical list.
-static OUI_TABLE: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
-    let mut m = HashMap::new();
-    m.insert("00:00:0C", "Cisco");
-    m.insert("00:1A:2B", "Ayecom");
-    m.insert("00:1B:63", "Apple");
-    m.insert("00:1E:C2", "Apple");
-    m.insert("00:50:56", "VMware");
-    m.insert("00:0C:29", "VMware");
-    m.insert("00:15:5D", "Microsoft");
-    m.insert("00:1A:A0", "Dell");
-    m.insert("00:14:22", "Dell");
-    m.insert("00:25:B5", "Intel");
-    m.insert("00:1B:21", "Intel");
-    m.insert("3C:D9:2B", "HP");
-    m.insert("00:1A:4B", "HP");
-    m.insert("B8:27:EB", "Raspberry Pi Foundation");
-    m.insert("DC:A6:32", "Raspberry Pi Foundation");
-    m.insert("AC:DE:48", "Samsung");
-    m.insert("00:1A:8A", "Samsung");
-    m.insert("F8:1A:67", "TP-Link");
-    m.insert("00:1D:7E", "Cisco");
-    m.insert("00:26:CB", "Cisco");
-    m.insert("00:17:88", "Signify N.V.");
-    m.insert("44:D9:E7", "Ubiquiti");
-    m.insert("80:2A:A8", "Ubiquiti");
-    m.insert("00:1B:44", "SanDisk");
-    m.insert("2C:F0:5D", "Juniper");
-    m
-});
 
 /// Look up the vendor name for a MAC address via the OUI table.
 ///
-/// Normalizes the MAC to uppercase and extracts the first 8 characters (XX:XX:XX).
-/// Returns `None` if the MAC is too short or the prefix is not in the table.
+/// Returns `None` if the MAC is malformed or the prefix is not in the table.
+/// See [`crate::oui`].
 pub fn lookup_oui(mac: &str) -> Option<&'static str> {
-    let normalized = mac.to_uppercase();
-    if normalized.len() < 8 {
-        return None;
-    }
-    let prefix = &normalized[..8];
-    OUI_TABLE.get(prefix).copied()
+    crate::oui::vendor(mac)
+}
+
+/// Server header substrings (lowercase) that identify a device class.
+const SERVER_HEADER_HINTS: &[(&str, DeviceType, f64)] = &[
+    ("hp http server", DeviceType::Printer, 0.7),
+    ("cups", DeviceType::Printer, 0.7),
+    ("xerox", DeviceType::Printer, 0.7),
+    ("epson", DeviceType::Printer, 0.7),
+    ("mikrotik", DeviceType::Router, 0.7),
+    ("routeros", DeviceType::Router, 0.7),
+    ("goahead", DeviceType::IoT, 0.6),
+    ("rompager", DeviceType::IoT, 0.6),
+    ("boa/", DeviceType::IoT, 0.6),
+    ("mini_httpd", DeviceType::IoT, 0.6),
+    ("uc-httpd", DeviceType::IoT, 0.6),
+    ("hikvision", DeviceType::IoT, 0.6),
+    ("microsoft-iis", DeviceType::Server, 0.7),
+];
+
+/// Map an HTTP `Server` header to a device class, if it is distinctive.
+///
+/// Generic servers (Apache, nginx, lighttpd) run on everything from routers
+/// to data-center hosts, so they return `None`.
+pub fn classify_server_header(header: &str) -> Option<(DeviceType, f64)> {
+    let lower = header.to_lowercase();
+    SERVER_HEADER_HINTS
+        .iter()
+        .find(|(needle, _, _)| lower.contains(needle))
+        .map(|(_, dt, conf)| (dt.clone(), *conf))
 }
 
-/// Classify a device based on its open ports, OS hint, and vendor.
+/// Service banner substrings (lowercase) that identify a device class.
+const BANNER_HINTS: &[(&str, DeviceType)] = &[
+    ("routeros", DeviceType::Router),
+    ("mikrotik", DeviceType::Router),
+    ("dd-wrt", DeviceType::Router),
+    ("openwrt", DeviceType::Router),
+    ("edgeos", DeviceType::Router),
+    ("junos", DeviceType::Router),
+    ("cisco ios", DeviceType::Router),
+    ("hikvision", DeviceType::IoT),
+    ("dahua", DeviceType::IoT),
+    ("ip camera", DeviceType::IoT),
+    ("onvif", DeviceType::IoT),
+    ("tasmota", DeviceType::IoT),
+    ("esphome", DeviceType::IoT),
+    ("jetdirect", DeviceType::Printer),
+    ("lexmark", DeviceType::Printer),
+    ("postgresql", DeviceType::Server),
+    ("mysql", DeviceType::Server),
+    ("mariadb", DeviceType::Server),
+    ("microsoft sql server", DeviceType::Server),
+    ("mongodb", DeviceType::Server),
+    ("redis", DeviceType::Server),
+    ("elasticsearch", DeviceType::Server),
+];
+
+/// Confidence of a banner match. Port and header heuristics below this are
+/// overridden by a recognized banner; an OS-based mobile match is not.
+pub const BANNER_CONFIDENCE: f64 = 0.75;
+
+/// Map service banners (or nmap product/version strings) to a device class.
+///
+/// The first banner containing a known product name wins. Generic daemons
+/// (OpenSSH, Apache, nginx) run on every class of device and are ignored.
+pub fn classify_from_banner(service_banners: &[String]) -> Option<DeviceType> {
+    service_banners.iter().find_map(|banner| {
+        let lower = banner.to_lowercase();
+        BANNER_HINTS
+            .iter()
+            .find(|(needle, _)| lower.contains(needle))
+            .map(|(_, dt)| dt.clone())
+    })
+}
+
+/// Classify a device based on its open ports, OS hint, vendor and service
+/// banners.
 ///
 /// Returns a `(DeviceType, confidence)` tuple. Rules are checked in priority order:
 /// 1. OS hint contains "iOS"/"Android" -> Mobile (0.8)
 /// 2. Vendor contains "Cisco"/"Juniper"/"Ubiquiti" -> Router (0.7)
-/// 3. Port 631 or 9100 present -> Printer (0.7)
-/// 4. Port 1883 (MQTT) or 5353 (mDNS) + no HTTP ports -> IoT (0.6)
-/// 5. Multiple server ports (22, 80, 443, 8080, 3306, 5432) -> Server (0.7)
-/// 6. Port 3389 (RDP) -> Workstation (0.6)
-/// 7. Default -> Unknown (0.0)
+/// 3. A port's HTTP `Server` header is distinctive -> see [`classify_server_header`]
+/// 4. Port 631 or 9100 present -> Printer (0.7)
+/// 5. Port 1883 (MQTT) or 5353 (mDNS) + no HTTP ports -> IoT (0.6)
+/// 6. Multiple server ports (22, 80, 443, 8080, 3306, 5432) -> Server (0.7)
+/// 7. Port 3389 (RDP) -> Workstation (0.6)
+/// 8. Default -> Unknown (0.0)
+///
+/// A recognized port banner or service version (see [`classify_from_banner`])
+/// then replaces any result less confident than [`BANNER_CONFIDENCE`].
 pub fn classify_device(
     ports: &[Port],
     os_hint: Option<&str>,
     vendor: Option<&str>,
+) -> (DeviceType, f64) {
+    let guess = classify_by_hints(ports, os_hint, vendor);
+    if guess.1 >= BANNER_CONFIDENCE {
+        return guess;
+    }
+    let banners: Vec<String> = ports
+        .iter()
+        .flat_map(|p| [&p.banner, &p.service_version])
+        .filter_map(|b| b.clone())
+        .collect();
+    match classify_from_banner(&banners) {
+        Some(dt) => (dt, BANNER_CONFIDENCE),
+        None => guess,
+    }
+}
+
+/// Rules 1-8 of [`classify_device`].
+fn classify_by_hints(
+    ports: &[Port],
+    os_hint: Option<&str>,
+    vendor: Option<&str>,
 ) -> (DeviceType, f64) {
     // Rule 1: Mobile by OS hint
     if let Some(os) = os_hint {
@@ -85,12 +144,21 @@ pub fn classify_device(
 
     let port_numbers: Vec<i64> = ports.iter().map(|p| p.port_number).collect();
 
-    // Rule 3: Printer
+    // Rule 3: Distinctive HTTP Server header
+    if let Some(hint) = ports
+        .iter()
+        .filter_map(|p| p.server_header.as_deref())
+        .find_map(classify_server_header)
+    {
+        return hint;
+    }
+
+    // Rule 4: Printer
     if port_numbers.contains(&631) || port_numbers.contains(&9100) {
         return (DeviceType::Printer, 0.7);
     }
 
-    // Rule 4: IoT — MQTT or mDNS without HTTP
+    // Rule 5: IoT — MQTT or mDNS without HTTP
     let has_http = port_numbers.contains(&80)
         || port_numbers.contains(&443)
         || port_numbers.contains(&8080);
@@ -98,7 +166,7 @@ pub fn classify_device(
         return (DeviceType::IoT, 0.6);
     }
 
-    // Rule 5: Server — multiple server ports
+    // Rule 6: Server — multiple server ports
     let server_ports: &[i64] = &[22, 80, 443, 8080, 3306, 5432];
     let server_count = port_numbers
         .iter()
@@ -108,12 +176,12 @@ pub fn classify_device(
         return (DeviceType::Server, 0.7);
     }
 
-    // Rule 6: Workstation by RDP
+    // Rule 7: Workstation by RDP
     if port_numbers.contains(&3389) {
         return (DeviceType::Workstation, 0.6);
     }
 
-    // Rule 7: Default
+    // Rule 8: Default
     (DeviceType::Unknown, 0.0)
 }
 
@@ -191,6 +259,79 @@ mod tests {
         assert!((conf - 0.7).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_classify_server_header() {
+        assert_eq!(
+            classify_server_header("HP HTTP Server; HP LaserJet"),
+            Some((DeviceType::Printer, 0.7))
+        );
+        assert_eq!(
+            classify_server_header("GoAhead-Webs"),
+            Some((DeviceType::IoT, 0.6))
+        );
+        assert_eq!(classify_server_header("nginx/1.18.0 (Ubuntu)"), None);
+    }
+
+    #[test]
+    fn test_classify_by_server_header() {
+        let mut port = make_port(80);
+        port.server_header = Some("RomPager/4.07 UPnP/1.0".into());
+        let (dt, conf) = classify_device(&[port], None, None);
+        assert_eq!(dt, DeviceType::IoT);
+        assert!((conf - 0.6).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_classify_from_banner() {
+        let banners = |b: &[&str]| b.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            classify_from_banner(&banners(&["MikroTik RouterOS sshd"])),
+            Some(DeviceType::Router)
+        );
+        assert_eq!(
+            classify_from_banner(&banners(&["OpenSSH 8.9p1", "DD-WRT v3.0 httpd"])),
+            Some(DeviceType::Router)
+        );
+        assert_eq!(
+            classify_from_banner(&banners(&["PostgreSQL DB 14.2"])),
+            Some(DeviceType::Server)
+        );
+        assert_eq!(
+            classify_from_banner(&banners(&["OpenSSH 8.9p1 Ubuntu", "nginx"])),
+            None
+        );
+        assert_eq!(classify_from_banner(&[]), None);
+    }
+
+    #[test]
+    fn test_banner_reclassifies_ambiguous_host() {
+        // RDP alone looks like a workstation...
+        let mut rdp = make_port(3389);
+        let (dt, _) = classify_device(std::slice::from_ref(&rdp), None, None);
+        assert_eq!(dt, DeviceType::Workstation);
+
+        // ...until a camera banner shows up on another port
+        let mut rtsp = make_port(554);
+        rtsp.service_version = Some("Hikvision IP camera rtspd".into());
+        let (dt, conf) = classify_device(&[rdp.clone(), rtsp], None, None);
+        assert_eq!(dt, DeviceType::IoT);
+        assert!((conf - BANNER_CONFIDENCE).abs() < f64::EPSILON);
+
+        // Unknown hosts are promoted too
+        rdp.port_number = 8000;
+        rdp.banner = Some("220 ProFTPD on RouterOS".into());
+        assert_eq!(classify_device(&[rdp], None, None).0, DeviceType::Router);
+    }
+
+    #[test]
+    fn test_banner_does_not_override_confident_match() {
+        let mut db = make_port(5432);
+        db.banner = Some("PostgreSQL".into());
+        let (dt, conf) = classify_device(&[db], Some("Android 13"), None);
+        assert_eq!(dt, DeviceType::Mobile);
+        assert!((conf - 0.8).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_classify_unknown() {
         let ports = vec![];
@@ -0,0 +1,286 @@
+//! Offline IP-to-country lookup for traffic flow enrichment.
+//!
+//! [`GeoDb::embedded`] is a curated subset of well-known allocations, enough
+//! for tests and a rough first pass; load a full range table with
+//! [`GeoDb::from_csv`] (the `start,end,country` layout of the DB-IP and
+//! IP2Location "lite" country CSVs) for real coverage.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use netsec_models::traffic::TrafficFlow;
+
+use crate::{ScannerError, ScannerResult};
+
+/// Embedded `(start, end, country)` ranges. Keep sorted by start address.
+const EMBEDDED_RANGES: &[(&str, &str, &str)] = &[
+    ("8.8.4.0", "8.8.4.255", "US"),
+    ("8.8.8.0", "8.8.8.255", "US"),
+    ("9.0.0.0", "9.255.255.255", "US"),
+    ("17.0.0.0", "17.255.255.255", "US"),
+    ("133.0.0.0", "133.255.255.255", "JP"),
+    ("193.0.0.0", "193.0.7.255", "NL"),
+    ("2001:4860::", "2001:4860:ffff:ffff:ffff:ffff:ffff:ffff", "US"),
+];
+
+/// A sorted table of address ranges and their ISO 3166-1 alpha-2 country codes.
+///
+/// IPv4 and IPv6 ranges are kept apart; IPv4-mapped IPv6 addresses are looked
+/// up as IPv4.
+#[derive(Debug, Clone, Default)]
+pub struct GeoDb {
+    v4: Vec<(u32, u32, String)>,
+    v6: Vec<(u128, u128, String)>,
+}
+
+impl GeoDb {
+    /// The built-in table.
+    pub fn embedded() -> Self {
+        let mut db = Self::default();
+        for (start, end, country) in EMBEDDED_RANGES {
+            db.insert_range(start, end, country)
+                .expect("embedded geo ranges are valid");
+        }
+        db.sort();
+        db
+    }
+
+    /// Parse `start,end,country` lines. Blank lines and `#` comments are
+    /// skipped and fields may be quoted; a line with a malformed address,
+    /// a reversed range or a country that isn't a two-letter code is an error.
+    pub fn from_csv(data: &str) -> ScannerResult<Self> {
+        let mut db = Self::default();
+        for (lineno, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> =
+                line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+            let [start, end, country, ..] = fields[..] else {
+                return Err(geo_err(lineno, "expected start,end,country"));
+            };
+            db.insert_range(start, end, country)
+                .map_err(|e| geo_err(lineno, &e))?;
+        }
+        db.sort();
+        Ok(db)
+    }
+
+    /// Load a CSV range table from disk.
+    pub fn load(path: impl AsRef<std::path::Path>) -> ScannerResult<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path).map_err(|e| {
+            ScannerError::Validation(format!("geo database {}: {e}", path.display()))
+        })?;
+        Self::from_csv(&data)
+    }
+
+    fn insert_range(&mut self, start: &str, end: &str, country: &str) -> Result<(), String> {
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("invalid country code {country:?}"));
+        }
+        let country = country.to_ascii_uppercase();
+        let parse = |s: &str| s.parse::<IpAddr>().map_err(|_| format!("invalid address {s:?}"));
+        match (parse(start)?, parse(end)?) {
+            (IpAddr::V4(a), IpAddr::V4(b)) if a <= b => {
+                self.v4.push((a.into(), b.into(), country));
+            }
+            (IpAddr::V6(a), IpAddr::V6(b)) if a <= b => {
+                self.v6.push((a.into(), b.into(), country));
+            }
+            _ => return Err(format!("invalid range {start} - {end}")),
+        }
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        self.v4.sort_by_key(|r| r.0);
+        self.v6.sort_by_key(|r| r.0);
+    }
+
+    /// Number of ranges in the table.
+    pub fn len(&self) -> usize {
+        self.v4.len() + self.v6.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Country code for a public address, or `None` for non-routable
+    /// addresses and addresses outside every range.
+    pub fn lookup(&self, ip: IpAddr) -> Option<&str> {
+        if !is_public(ip) {
+            return None;
+        }
+        match ip {
+            IpAddr::V4(v4) => find(&self.v4, u32::from(v4)),
+            IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+                Some(v4) => find(&self.v4, u32::from(v4)),
+                None => find(&self.v6, u128::from(v6)),
+            },
+        }
+    }
+}
+
+fn geo_err(lineno: usize, message: &str) -> ScannerError {
+    ScannerError::Validation(format!("geo database line {}: {message}", lineno + 1))
+}
+
+/// Binary search for the last range starting at or before `addr`.
+fn find<T: Ord + Copy>(ranges: &[(T, T, String)], addr: T) -> Option<&str> {
+    let idx = ranges.partition_point(|r| r.0 <= addr).checked_sub(1)?;
+    let (_, end, country) = &ranges[idx];
+    (addr <= *end).then_some(country.as_str())
+}
+
+/// Whether `ip` is globally routable: not private (RFC 1918 / unique local),
+/// loopback, link-local, CGNAT shared space, documentation, multicast,
+/// broadcast, unspecified or otherwise reserved.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(v6),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // 100.64.0.0/10 carrier-grade NAT
+        || (a == 100 && (64..128).contains(&b))
+        // 198.18.0.0/15 benchmarking
+        || (a == 198 && (b == 18 || b == 19))
+        // 240.0.0.0/4 reserved
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7 unique local
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10 link-local
+        || (first & 0xffc0) == 0xfe80
+        // 2001:db8::/32 documentation
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// Fill in `country_src` / `country_dst` from `db`.
+///
+/// Private, reserved and unparseable addresses, and public addresses the
+/// table doesn't cover, are set to `None`.
+pub fn enrich_geo(flow: &mut TrafficFlow, db: &GeoDb) {
+    let country = |ip: &str| {
+        let addr = ip.trim().parse::<IpAddr>().ok()?;
+        db.lookup(addr).map(str::to_string)
+    };
+    flow.country_src = country(&flow.src_ip);
+    flow.country_dst = country(&flow.dst_ip);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flow(src: &str, dst: &str) -> TrafficFlow {
+        TrafficFlow::new(src.into(), 51234, dst.into(), 443, "tcp".into())
+    }
+
+    #[test]
+    fn test_enrich_public_and_private() {
+        let db = GeoDb::embedded();
+        let mut f = flow("192.168.1.20", "8.8.8.8");
+        enrich_geo(&mut f, &db);
+        assert_eq!(f.country_src, None);
+        assert_eq!(f.country_dst.as_deref(), Some("US"));
+
+        let mut f = flow("2001:4860:4860::8888", "193.0.6.139");
+        enrich_geo(&mut f, &db);
+        assert_eq!(f.country_src.as_deref(), Some("US"));
+        assert_eq!(f.country_dst.as_deref(), Some("NL"));
+    }
+
+    #[test]
+    fn test_enrich_invalid_and_unknown() {
+        let db = GeoDb::embedded();
+        let mut f = flow("not-an-ip", "203.0.113.999");
+        f.country_src = Some("XX".into());
+        enrich_geo(&mut f, &db);
+        assert_eq!(f.country_src, None);
+        assert_eq!(f.country_dst, None);
+
+        // Public, but outside the embedded table
+        let mut f = flow("10.0.0.1", "45.33.32.156");
+        enrich_geo(&mut f, &db);
+        assert_eq!(f.country_dst, None);
+    }
+
+    #[test]
+    fn test_non_routable_ranges() {
+        for ip in [
+            "10.1.2.3",
+            "172.16.0.1",
+            "172.31.255.255",
+            "192.168.0.1",
+            "127.0.0.1",
+            "169.254.10.20",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "224.0.0.251",
+            "192.0.2.10",
+            "::1",
+            "fe80::1",
+            "fd12:3456::1",
+            "2001:db8::1",
+            "::ffff:192.168.1.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["8.8.8.8", "172.32.0.1", "100.128.0.1", "2001:4860::1", "::ffff:8.8.8.8"] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+        // A range covering private space still never matches it
+        let db = GeoDb::from_csv("10.0.0.0,10.255.255.255,US").unwrap();
+        assert_eq!(db.lookup("10.0.0.1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_from_csv() {
+        let db = GeoDb::from_csv(
+            "# start,end,country\n\
+             \"81.2.69.0\",\"81.2.69.255\",\"gb\"\n\
+             \n\
+             1.0.0.0,1.0.0.255,AU\n",
+        )
+        .unwrap();
+        assert_eq!(db.len(), 2);
+        assert_eq!(db.lookup("81.2.69.160".parse().unwrap()), Some("GB"));
+        assert_eq!(db.lookup("1.0.0.1".parse().unwrap()), Some("AU"));
+        assert_eq!(db.lookup("1.0.1.1".parse().unwrap()), None);
+
+        for bad in [
+            "1.0.0.0,1.0.0.255",
+            "1.0.0.9,1.0.0.0,AU",
+            "1.0.0.0,::1,AU",
+            "x,y,AU",
+            "1.0.0.0,1.0.0.1,USA",
+        ] {
+            let err = GeoDb::from_csv(bad).unwrap_err();
+            assert!(err.to_string().contains("line 1"), "{bad}: {err}");
+        }
+    }
+}
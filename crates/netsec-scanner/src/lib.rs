@@ -1,11 +1,18 @@
-//! Network scanning engine: active discovery, passive listeners, OUI lookup,
-//! device classification and fingerprinting.
+//! Network scanning engine: active discovery, passive listeners (multicast and
+//! ARP/DHCP capture), OUI lookup, device classification and fingerprinting,
+//! web-service findings, duplicate MAC detection, and traffic flow
+//! geolocation.
 
 pub mod active;
+pub mod capture;
 pub mod executor;
 pub mod fingerprint;
+pub mod geo;
 pub mod listener;
+pub mod mac_conflict;
+pub mod oui;
 pub mod passive;
+pub mod web;
 
 use thiserror::Error;
 
@@ -17,12 +24,18 @@ pub enum ScannerError {
     NmapExecution(String),
     #[error("nmap parse error: {0}")]
     NmapParse(String),
+    #[error("masscan execution error: {0}")]
+    MasscanExecution(String),
+    #[error("masscan parse error: {0}")]
+    MasscanParse(String),
     #[error("passive parse error: {0}")]
     PassiveParse(String),
     #[error("event bus error: {0}")]
     EventBus(String),
     #[error("validation error: {0}")]
     Validation(String),
+    #[error("scan cancelled")]
+    Cancelled,
 }
 
 pub type ScannerResult<T> = Result<T, ScannerError>;
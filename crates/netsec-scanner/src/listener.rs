@@ -1,14 +1,17 @@
-//! Passive network discovery: mDNS and SSDP multicast listeners.
+//! Passive network discovery: mDNS and SSDP multicast listeners, and an
+//! ARP/DHCP listener over a link-layer [`PacketSource`].
 //!
 //! Uses `socket2` to create multicast UDP sockets, then converts them
 //! to `tokio::net::UdpSocket` for async I/O.
 
+use netsec_db::DbPool;
 use netsec_events::EventBus;
-use sqlx::SqlitePool;
+use netsec_models::event::{EventType, NetsecEvent};
 use std::net::{Ipv4Addr, SocketAddrV4};
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
+use crate::capture::PacketSource;
 use crate::passive::{self, MdnsRecord};
 use crate::ScannerResult;
 
@@ -22,9 +25,10 @@ const SSDP_MULTICAST_ADDR: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
 /// SSDP port.
 const SSDP_PORT: u16 = 1900;
 
-/// Passive scanner that listens for mDNS and SSDP multicast traffic.
+/// Passive scanner that listens for mDNS and SSDP multicast traffic, and
+/// for ARP replies and DHCP requests on an interface.
 pub struct PassiveScanner {
-    pool: SqlitePool,
+    pool: DbPool,
     event_bus: EventBus,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
@@ -32,7 +36,7 @@ pub struct PassiveScanner {
 
 impl PassiveScanner {
     /// Create a new passive scanner.
-    pub fn new(pool: SqlitePool, event_bus: EventBus) -> Self {
+    pub fn new(pool: DbPool, event_bus: EventBus) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
         Self {
             pool,
@@ -134,6 +138,65 @@ impl PassiveScanner {
         Ok(handle)
     }
 
+    /// Learn devices from ARP replies and DHCP requests seen on `iface`.
+    ///
+    /// Opens a raw capture socket (needs `CAP_NET_RAW`) and runs
+    /// [`PassiveScanner::run_with_source`] on it until shutdown.
+    #[cfg(target_os = "linux")]
+    pub async fn run(&self, iface: &str) -> ScannerResult<()> {
+        let mut source = crate::capture::RawSocketSource::open(iface)?;
+        tracing::info!("Passive ARP/DHCP listener started on {iface}");
+        self.run_with_source(&mut source).await
+    }
+
+    /// Read frames from `source` until it is exhausted or the scanner is shut
+    /// down, upserting a device for every ARP reply and DHCP request.
+    ///
+    /// Publishes `DeviceDiscovered` for new devices and `DeviceUpdated` for
+    /// known ones. A frame that fails to store is logged and skipped; a
+    /// source error ends the run.
+    pub async fn run_with_source(&self, source: &mut dyn PacketSource) -> ScannerResult<()> {
+        let mut shutdown_rx = self.shutdown_rx.clone();
+        loop {
+            if *shutdown_rx.borrow() {
+                tracing::info!("Passive ARP/DHCP listener shutting down");
+                return Ok(());
+            }
+            let frame = tokio::select! {
+                _ = shutdown_rx.changed() => continue,
+                frame = source.next_frame() => frame?,
+            };
+            let Some(frame) = frame else {
+                return Ok(());
+            };
+            let Some(binding) = passive::parse_frame(&frame) else {
+                continue;
+            };
+            match passive::process_link_binding(&self.pool, &binding).await {
+                Ok((device, new)) => {
+                    let event_type = if new {
+                        EventType::DeviceDiscovered
+                    } else {
+                        EventType::DeviceUpdated
+                    };
+                    let event = NetsecEvent::new(
+                        event_type,
+                        serde_json::json!({
+                            "device_id": device.id,
+                            "ip": device.ip,
+                            "device_type": device.device_type,
+                        }),
+                    );
+                    // Ignore send errors (no subscribers is fine)
+                    let _ = self.event_bus.publish(event);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to process {} binding: {e}", binding.protocol);
+                }
+            }
+        }
+    }
+
     /// Signal all listener tasks to shut down.
     pub fn shutdown(&self) {
         let _ = self.shutdown_tx.send(true);
@@ -291,6 +354,125 @@ mod tests {
         assert!(*scanner.shutdown_rx.borrow());
     }
 
+    fn ethernet(src_mac: [u8; 6], ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0xFF; 6];
+        frame.extend_from_slice(&src_mac);
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn arp_reply(mac: [u8; 6], ip: [u8; 4]) -> Vec<u8> {
+        let mut arp = vec![0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x02];
+        arp.extend_from_slice(&mac);
+        arp.extend_from_slice(&ip);
+        arp.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        arp.extend_from_slice(&[192, 168, 1, 1]);
+        ethernet(mac, 0x0806, &arp)
+    }
+
+    fn dhcp_request(mac: [u8; 6], requested: [u8; 4], hostname: &str) -> Vec<u8> {
+        let mut bootp = vec![0u8; 236];
+        bootp[0..3].copy_from_slice(&[1, 1, 6]);
+        bootp[28..34].copy_from_slice(&mac);
+        bootp.extend_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+        bootp.extend_from_slice(&[53, 1, 3]);
+        bootp.extend_from_slice(&[50, 4]);
+        bootp.extend_from_slice(&requested);
+        bootp.extend_from_slice(&[12, hostname.len() as u8]);
+        bootp.extend_from_slice(hostname.as_bytes());
+        bootp.push(255);
+
+        let mut udp = vec![0, 68, 0, 67];
+        udp.extend_from_slice(&((8 + bootp.len()) as u16).to_be_bytes());
+        udp.extend_from_slice(&[0, 0]);
+        udp.extend_from_slice(&bootp);
+
+        let mut ip = vec![0x45, 0, 0, 0, 0, 0, 0, 0, 64, 17, 0, 0];
+        ip[2..4].copy_from_slice(&((20 + udp.len()) as u16).to_be_bytes());
+        ip.extend_from_slice(&[0, 0, 0, 0, 255, 255, 255, 255]);
+        ip.extend_from_slice(&udp);
+        ethernet(mac, 0x0800, &ip)
+    }
+
+    async fn scanner_with_frames(
+        frames: Vec<Vec<u8>>,
+    ) -> (DbPool, tokio::sync::broadcast::Receiver<NetsecEvent>) {
+        let pool = netsec_db::pool::create_test_pool().await.unwrap();
+        netsec_db::run_migrations(&pool).await.unwrap();
+        let bus = EventBus::new();
+        let rx = bus.subscribe();
+        let scanner = PassiveScanner::new(pool.clone(), bus);
+        let mut source = crate::capture::VecSource::new(frames);
+        scanner.run_with_source(&mut source).await.unwrap();
+        (pool, rx)
+    }
+
+    #[tokio::test]
+    async fn test_arp_reply_creates_passive_device() {
+        let mac = [0x00, 0x0C, 0x29, 0xAB, 0xCD, 0xEF];
+        let (pool, mut rx) = scanner_with_frames(vec![arp_reply(mac, [192, 168, 1, 42])]).await;
+
+        let device = netsec_db::repo::devices::get_by_ip(&pool, "192.168.1.42")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(device.mac.as_deref(), Some("00:0C:29:AB:CD:EF"));
+        assert_eq!(device.vendor.as_deref(), Some("VMware"));
+        assert_eq!(device.discovery_method.as_deref(), Some("passive"));
+        assert_eq!(device.status, "online");
+        let obs = netsec_db::repo::observations::list_by_device(&pool, &device.id, 10)
+            .await
+            .unwrap();
+        assert_eq!(obs[0].protocol, "arp");
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.event_type, EventType::DeviceDiscovered);
+        assert_eq!(event.payload["device_id"], device.id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_dhcp_request_learns_hostname() {
+        let mac = [0x02, 0x11, 0x22, 0x33, 0x44, 0x55];
+        let frames = vec![
+            dhcp_request(mac, [192, 168, 1, 77], "alice-laptop"),
+            // The same client answering ARP later updates, not duplicates
+            arp_reply(mac, [192, 168, 1, 77]),
+        ];
+        let (pool, mut rx) = scanner_with_frames(frames).await;
+
+        let devices = netsec_db::repo::devices::list(&pool, 10, 0).await.unwrap();
+        assert_eq!(devices.len(), 1);
+        let device = &devices[0];
+        assert_eq!(device.ip, "192.168.1.77");
+        assert_eq!(device.mac.as_deref(), Some("02:11:22:33:44:55"));
+        assert_eq!(device.hostname.as_deref(), Some("alice-laptop"));
+        assert_eq!(device.discovery_method.as_deref(), Some("passive"));
+
+        assert_eq!(rx.try_recv().unwrap().event_type, EventType::DeviceDiscovered);
+        assert_eq!(rx.try_recv().unwrap().event_type, EventType::DeviceUpdated);
+    }
+
+    #[test]
+    fn test_parse_frame_ignores_other_traffic() {
+        let mac = [0x02, 0, 0, 0, 0, 1];
+        // ARP request (oper 1)
+        let mut request = arp_reply(mac, [10, 0, 0, 1]);
+        request[21] = 1;
+        assert_eq!(passive::parse_frame(&request), None);
+        // ARP probe from 0.0.0.0
+        assert_eq!(passive::parse_frame(&arp_reply(mac, [0, 0, 0, 0])), None);
+        // DHCPDISCOVER (message type 1)
+        let mut discover = dhcp_request(mac, [10, 0, 0, 2], "host");
+        let opt = 14 + 20 + 8 + 240 + 2;
+        discover[opt] = 1;
+        assert_eq!(passive::parse_frame(&discover), None);
+        // Truncated frames
+        assert_eq!(passive::parse_frame(&[0u8; 13]), None);
+        let dhcp = dhcp_request(mac, [10, 0, 0, 2], "host");
+        assert_eq!(passive::parse_frame(&dhcp[..200]), None);
+    }
+
     #[test]
     fn test_ssdp_response_integration() {
         let msearch = build_ssdp_msearch();
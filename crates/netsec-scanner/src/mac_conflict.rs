@@ -0,0 +1,153 @@
+//! Duplicate MAC detection.
+//!
+//! The same MAC address bound to several active IPs can indicate ARP
+//! spoofing, a multi-homed host, or virtualization/NAT. Detection is a pure
+//! function over device records; known-legitimate MACs are excluded via an
+//! allowlist.
+
+use std::collections::BTreeMap;
+
+use netsec_models::alert::{Alert, AlertCategory, Severity};
+use netsec_models::device::{Device, DeviceStatus};
+
+/// A MAC address observed on more than one active IP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateMac {
+    /// Normalized MAC (lowercase, colon-separated).
+    pub mac: String,
+    /// Distinct IPs the MAC is bound to, sorted.
+    pub ips: Vec<String>,
+}
+
+/// Normalize a MAC for comparison: lowercase, `-` separators become `:`.
+pub fn normalize_mac(mac: &str) -> String {
+    mac.trim().to_ascii_lowercase().replace('-', ":")
+}
+
+/// Find MACs bound to more than one online device IP.
+///
+/// Offline devices and MACs in `allowlist` (any separator/case) are ignored.
+/// Results are sorted by MAC.
+pub fn find_duplicate_macs(devices: &[Device], allowlist: &[String]) -> Vec<DuplicateMac> {
+    let allowed: Vec<String> = allowlist.iter().map(|m| normalize_mac(m)).collect();
+    let online = DeviceStatus::Online.as_str();
+
+    let mut by_mac: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for device in devices.iter().filter(|d| d.status == online) {
+        let Some(mac) = device.mac.as_deref().map(normalize_mac) else {
+            continue;
+        };
+        if mac.is_empty() || allowed.contains(&mac) {
+            continue;
+        }
+        let ips = by_mac.entry(mac).or_default();
+        if !ips.contains(&device.ip) {
+            ips.push(device.ip.clone());
+        }
+    }
+
+    by_mac
+        .into_iter()
+        .filter(|(_, ips)| ips.len() > 1)
+        .map(|(mac, mut ips)| {
+            ips.sort();
+            DuplicateMac { mac, ips }
+        })
+        .collect()
+}
+
+/// Build the informational alert for a duplicate MAC.
+///
+/// The fingerprint depends only on the MAC so repeated sightings deduplicate
+/// into a single alert.
+pub fn duplicate_mac_alert(dup: &DuplicateMac) -> Alert {
+    let mut alert = Alert::new(
+        format!("MAC {} seen on {} IPs", dup.mac, dup.ips.len()),
+        "scanner".to_string(),
+        format!("duplicate-mac:{}", dup.mac),
+    );
+    alert.severity = Severity::Info.as_str().to_string();
+    alert.category = AlertCategory::Anomaly.as_str().to_string();
+    alert.description = format!(
+        "MAC address {} is bound to multiple active IPs ({}). This may indicate \
+         ARP spoofing, a multi-homed host, or virtualization/NAT.",
+        dup.mac,
+        dup.ips.join(", ")
+    );
+    alert.device_ip = dup.ips.first().cloned();
+    alert.raw_data = Some(serde_json::json!({ "mac": dup.mac, "ips": dup.ips }).to_string());
+    alert
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(ip: &str, mac: Option<&str>, status: DeviceStatus) -> Device {
+        let mut d = Device::new(ip.to_string());
+        d.mac = mac.map(String::from);
+        d.status = status.as_str().to_string();
+        d
+    }
+
+    #[test]
+    fn test_unique_macs_have_no_duplicates() {
+        let devices = vec![
+            device("10.0.0.1", Some("AA:BB:CC:00:00:01"), DeviceStatus::Online),
+            device("10.0.0.2", Some("AA:BB:CC:00:00:02"), DeviceStatus::Online),
+            device("10.0.0.3", None, DeviceStatus::Online),
+            device("10.0.0.4", None, DeviceStatus::Online),
+        ];
+        assert!(find_duplicate_macs(&devices, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_mac_detected() {
+        let devices = vec![
+            device("10.0.0.9", Some("AA:BB:CC:00:00:01"), DeviceStatus::Online),
+            device("10.0.0.1", Some("aa-bb-cc-00-00-01"), DeviceStatus::Online),
+            device("10.0.0.2", Some("AA:BB:CC:00:00:02"), DeviceStatus::Online),
+        ];
+        let dups = find_duplicate_macs(&devices, &[]);
+        assert_eq!(
+            dups,
+            vec![DuplicateMac {
+                mac: "aa:bb:cc:00:00:01".to_string(),
+                ips: vec!["10.0.0.1".to_string(), "10.0.0.9".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_offline_devices_ignored() {
+        let devices = vec![
+            device("10.0.0.1", Some("AA:BB:CC:00:00:01"), DeviceStatus::Online),
+            device("10.0.0.2", Some("AA:BB:CC:00:00:01"), DeviceStatus::Offline),
+        ];
+        assert!(find_duplicate_macs(&devices, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_allowlisted_mac_ignored() {
+        let devices = vec![
+            device("10.0.0.1", Some("AA:BB:CC:00:00:01"), DeviceStatus::Online),
+            device("10.0.0.2", Some("AA:BB:CC:00:00:01"), DeviceStatus::Online),
+        ];
+        let allowlist = vec!["aa-bb-cc-00-00-01".to_string()];
+        assert!(find_duplicate_macs(&devices, &allowlist).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_mac_alert() {
+        let dup = DuplicateMac {
+            mac: "aa:bb:cc:00:00:01".to_string(),
+            ips: vec!["10.0.0.1".to_string(), "10.0.0.9".to_string()],
+        };
+        let alert = duplicate_mac_alert(&dup);
+        assert_eq!(alert.severity, "info");
+        assert_eq!(alert.category, "anomaly");
+        assert_eq!(alert.fingerprint, "duplicate-mac:aa:bb:cc:00:00:01");
+        assert!(alert.description.contains("10.0.0.1, 10.0.0.9"));
+        assert_eq!(alert.device_ip.as_deref(), Some("10.0.0.1"));
+    }
+}
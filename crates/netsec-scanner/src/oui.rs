@@ -0,0 +1,118 @@
+//! Offline MAC vendor lookup by OUI (the first three octets of a MAC).
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Static OUI table mapping normalized MAC prefixes ("XX:XX:XX") to vendor
+/// names. This is a curated subset of common prefixes, not a full IEEE OUI
+/// database; keep it in sync with `data/oui_reference.json`.
+static OUI_TABLE: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    let mut m = HashMap::new();
+    m.insert("00:00:0C", "Cisco");
+    m.insert("00:01:42", "Cisco Systems");
+    m.insert("00:0C:29", "VMware");
+    m.insert("00:10:DB", "Juniper Networks");
+    m.insert("00:12:5A", "Microsoft Corporation");
+    m.insert("00:14:22", "Dell");
+    m.insert("00:15:5D", "Microsoft");
+    m.insert("00:17:88", "Signify N.V.");
+    m.insert("00:1A:2B", "Ayecom");
+    m.insert("00:1A:4B", "HP");
+    m.insert("00:1A:8A", "Samsung");
+    m.insert("00:1A:A0", "Dell");
+    m.insert("00:1B:21", "Intel");
+    m.insert("00:1B:44", "SanDisk");
+    m.insert("00:1B:63", "Apple");
+    m.insert("00:1D:7E", "Cisco");
+    m.insert("00:1E:C2", "Apple");
+    m.insert("00:25:B5", "Intel");
+    m.insert("00:26:CB", "Cisco");
+    m.insert("00:50:56", "VMware");
+    m.insert("04:D4:C4", "ASUS (RT-AX88U)");
+    m.insert("18:B4:30", "Nest Labs");
+    m.insert("18:D6:C7", "TP-Link");
+    m.insert("24:F5:AA", "Samsung");
+    m.insert("2C:F0:5D", "Juniper");
+    m.insert("3C:5A:B4", "Google");
+    m.insert("3C:D9:2B", "HP");
+    m.insert("44:D9:E7", "Ubiquiti");
+    m.insert("54:BF:64", "Dell Technologies");
+    m.insert("74:83:C2", "Ubiquiti (UniFi)");
+    m.insert("74:C2:46", "Amazon Technologies");
+    m.insert("80:2A:A8", "Ubiquiti");
+    m.insert("AC:3C:0B", "Apple Inc.");
+    m.insert("AC:DE:48", "Samsung");
+    m.insert("B8:27:EB", "Raspberry Pi Foundation");
+    m.insert("D8:28:C9", "August Home");
+    m.insert("DC:A6:32", "Raspberry Pi Foundation");
+    m.insert("E0:D5:5E", "Generic Hardware");
+    m.insert("F0:BF:97", "Sony Corporation");
+    m.insert("F8:1A:67", "TP-Link");
+    m
+});
+
+/// The "XX:XX:XX" OUI prefix of a MAC address.
+///
+/// Accepts `:`, `-` or `.` separators (or none) in any case, but the address
+/// must have exactly 12 hex digits.
+pub fn oui_prefix(mac: &str) -> Option<String> {
+    let digits: Vec<char> = mac
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.'))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if digits.len() != 12 || !digits.iter().all(char::is_ascii_hexdigit) {
+        return None;
+    }
+    Some(format!(
+        "{}{}:{}{}:{}{}",
+        digits[0], digits[1], digits[2], digits[3], digits[4], digits[5]
+    ))
+}
+
+/// Vendor name for a MAC address, or `None` if the MAC is malformed or its
+/// prefix isn't in the table.
+pub fn lookup_vendor(mac: &str) -> Option<String> {
+    vendor(mac).map(str::to_string)
+}
+
+pub(crate) fn vendor(mac: &str) -> Option<&'static str> {
+    OUI_TABLE.get(oui_prefix(mac)?.as_str()).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_known_prefix() {
+        assert_eq!(
+            lookup_vendor("B8:27:EB:12:34:56").as_deref(),
+            Some("Raspberry Pi Foundation")
+        );
+        assert_eq!(lookup_vendor("00-50-56-c0-00-08").as_deref(), Some("VMware"));
+        assert_eq!(lookup_vendor("3c5a.b400.0001").as_deref(), Some("Google"));
+        assert_eq!(lookup_vendor("f81a67aabbcc").as_deref(), Some("TP-Link"));
+    }
+
+    #[test]
+    fn test_lookup_unknown_prefix() {
+        assert_eq!(lookup_vendor("FF:FF:FF:00:00:00"), None);
+        assert_eq!(lookup_vendor("02:00:00:00:00:01"), None);
+    }
+
+    #[test]
+    fn test_lookup_malformed_mac() {
+        for mac in [
+            "",
+            "B8:27:EB",
+            "B8:27:EB:12:34",
+            "B8:27:EB:12:34:56:78",
+            "G8:27:EB:12:34:56",
+            "not a mac",
+        ] {
+            assert_eq!(oui_prefix(mac), None, "{mac}");
+            assert_eq!(lookup_vendor(mac), None, "{mac}");
+        }
+    }
+}
@@ -1,11 +1,14 @@
-//! Passive discovery: mDNS and SSDP response parsing, device upsert logic.
+//! Passive discovery: mDNS and SSDP response parsing, ARP reply and DHCP
+//! request frame parsing, device upsert logic.
 
-use chrono::Utc;
-use netsec_models::device::{Device, DeviceStatus};
+use std::net::Ipv4Addr;
+
+use netsec_db::DbPool;
+use netsec_models::device::{Device, DeviceStatus, DiscoveryMethod};
 use netsec_models::event::Observation;
-use sqlx::SqlitePool;
+use netsec_models::time::now_rfc3339;
 
-use crate::ScannerResult;
+use crate::{oui, ScannerResult};
 
 /// A parsed mDNS record.
 #[derive(Debug, Clone)]
@@ -94,11 +97,11 @@ pub fn parse_ssdp_response(response: &str) -> Option<SsdpDevice> {
 /// optionally merges the hostname. Otherwise inserts a new device.
 /// Records an `Observation` with protocol "mdns".
 pub async fn process_mdns_discovery(
-    pool: &SqlitePool,
+    pool: &DbPool,
     record: &MdnsRecord,
     source_ip: &str,
 ) -> ScannerResult<Device> {
-    let now = Utc::now().to_rfc3339();
+    let now = now_rfc3339();
     let ip = record.ip.as_deref().unwrap_or(source_ip);
 
     let device = match netsec_db::repo::devices::get_by_ip(pool, ip).await? {
@@ -117,6 +120,7 @@ pub async fn process_mdns_discovery(
             device.status = DeviceStatus::Online.as_str().to_string();
             device.last_seen = now.clone();
             device.first_seen = now.clone();
+            device.discovery_method = Some(DiscoveryMethod::Passive.as_str().to_string());
             netsec_db::repo::devices::insert(pool, &device).await?;
             device
         }
@@ -144,11 +148,11 @@ pub async fn process_mdns_discovery(
 /// optionally merges server info into hostname. Otherwise inserts a new device.
 /// Records an `Observation` with protocol "ssdp".
 pub async fn process_ssdp_discovery(
-    pool: &SqlitePool,
+    pool: &DbPool,
     ssdp: &SsdpDevice,
     source_ip: &str,
 ) -> ScannerResult<Device> {
-    let now = Utc::now().to_rfc3339();
+    let now = now_rfc3339();
 
     let device = match netsec_db::repo::devices::get_by_ip(pool, source_ip).await? {
         Some(mut existing) => {
@@ -166,6 +170,7 @@ pub async fn process_ssdp_discovery(
             device.status = DeviceStatus::Online.as_str().to_string();
             device.last_seen = now.clone();
             device.first_seen = now.clone();
+            device.discovery_method = Some(DiscoveryMethod::Passive.as_str().to_string());
             netsec_db::repo::devices::insert(pool, &device).await?;
             device
         }
@@ -187,6 +192,204 @@ pub async fn process_ssdp_discovery(
     Ok(device)
 }
 
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_ARP: u16 = 0x0806;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const DHCP_MAGIC_COOKIE: [u8; 4] = [0x63, 0x82, 0x53, 0x63];
+const DHCP_OPT_HOSTNAME: u8 = 12;
+const DHCP_OPT_REQUESTED_IP: u8 = 50;
+const DHCP_OPT_MESSAGE_TYPE: u8 = 53;
+const DHCP_REQUEST: u8 = 3;
+
+/// An IP/MAC (and possibly hostname) binding learned from a captured frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkBinding {
+    /// Observation protocol: "arp" or "dhcp".
+    pub protocol: &'static str,
+    pub ip: String,
+    /// Uppercase, colon-separated, as nmap reports it.
+    pub mac: String,
+    pub hostname: Option<String>,
+}
+
+/// Parse an Ethernet frame carrying an ARP reply or a DHCP request.
+///
+/// Returns `None` for any other traffic.
+pub fn parse_frame(frame: &[u8]) -> Option<LinkBinding> {
+    let (ethertype, payload) = ethernet_payload(frame)?;
+    match ethertype {
+        ETHERTYPE_ARP => parse_arp_reply(payload),
+        ETHERTYPE_IPV4 => parse_dhcp_request(payload),
+        _ => None,
+    }
+}
+
+/// Split an Ethernet II frame into its ethertype and payload, skipping a
+/// single 802.1Q tag.
+fn ethernet_payload(frame: &[u8]) -> Option<(u16, &[u8])> {
+    let ethertype = u16::from_be_bytes([*frame.get(12)?, *frame.get(13)?]);
+    if ethertype == ETHERTYPE_VLAN {
+        let inner = u16::from_be_bytes([*frame.get(16)?, *frame.get(17)?]);
+        return Some((inner, &frame[18..]));
+    }
+    Some((ethertype, &frame[14..]))
+}
+
+/// Sender MAC and IP of an Ethernet/IPv4 ARP reply.
+///
+/// Replies from the unspecified address (ARP probes) are ignored.
+pub fn parse_arp_reply(arp: &[u8]) -> Option<LinkBinding> {
+    if arp.len() < 28 {
+        return None;
+    }
+    // htype 1 (Ethernet), ptype IPv4, hlen 6, plen 4, oper 2 (reply)
+    if arp[0..8] != [0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x02] {
+        return None;
+    }
+    let ip = ipv4_at(arp, 14)?;
+    if ip.is_unspecified() {
+        return None;
+    }
+    Some(LinkBinding {
+        protocol: "arp",
+        ip: ip.to_string(),
+        mac: format_mac(&arp[8..14])?,
+        hostname: None,
+    })
+}
+
+/// Client MAC, requested IP and hostname of a DHCPREQUEST in an IPv4 packet.
+///
+/// The IP comes from the requested-address option (50), falling back to
+/// `ciaddr` for renewals; a request with neither is ignored.
+pub fn parse_dhcp_request(ipv4: &[u8]) -> Option<LinkBinding> {
+    let ihl = usize::from(*ipv4.first()? & 0x0F) * 4;
+    // Version 4, UDP
+    if ipv4[0] >> 4 != 4 || ihl < 20 || *ipv4.get(9)? != 17 {
+        return None;
+    }
+    let udp = ipv4.get(ihl..)?;
+    if udp.len() < 8 || udp[0..4] != [0, 68, 0, 67] {
+        return None;
+    }
+    let bootp = &udp[8..];
+    // op 1 (BOOTREQUEST), htype 1, hlen 6
+    if bootp.len() < 240 || bootp[0..3] != [1, 1, 6] || bootp[236..240] != DHCP_MAGIC_COOKIE {
+        return None;
+    }
+
+    let mut message_type = None;
+    let mut requested_ip = None;
+    let mut hostname = None;
+    let mut options = &bootp[240..];
+    while let Some((&code, rest)) = options.split_first() {
+        match code {
+            0 => {
+                options = rest;
+                continue;
+            }
+            255 => break,
+            _ => {}
+        }
+        let (&len, rest) = rest.split_first()?;
+        let value = rest.get(..usize::from(len))?;
+        match code {
+            DHCP_OPT_MESSAGE_TYPE => message_type = value.first().copied(),
+            DHCP_OPT_REQUESTED_IP => requested_ip = ipv4_at(value, 0),
+            DHCP_OPT_HOSTNAME => {
+                let name = String::from_utf8_lossy(value).trim().to_string();
+                hostname = (!name.is_empty()).then_some(name);
+            }
+            _ => {}
+        }
+        options = &rest[usize::from(len)..];
+    }
+    if message_type != Some(DHCP_REQUEST) {
+        return None;
+    }
+
+    let ip = requested_ip
+        .or_else(|| ipv4_at(bootp, 12))
+        .filter(|ip| !ip.is_unspecified())?;
+    Some(LinkBinding {
+        protocol: "dhcp",
+        ip: ip.to_string(),
+        mac: format_mac(&bootp[28..34])?,
+        hostname,
+    })
+}
+
+fn ipv4_at(data: &[u8], offset: usize) -> Option<Ipv4Addr> {
+    let octets: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    Some(Ipv4Addr::from(octets))
+}
+
+/// Format a MAC, rejecting all-zero and broadcast addresses.
+fn format_mac(bytes: &[u8]) -> Option<String> {
+    if bytes.iter().all(|&b| b == 0) || bytes.iter().all(|&b| b == 0xFF) {
+        return None;
+    }
+    let parts: Vec<String> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+    Some(parts.join(":"))
+}
+
+/// Upsert a device from an ARP or DHCP binding.
+///
+/// An existing device (matched by IP) is marked online and gets the MAC,
+/// OUI vendor and hostname filled in where they were unknown; otherwise a
+/// new device is inserted with `discovery_method = "passive"`. Records an
+/// `Observation` with the binding's protocol. Returns the device and whether
+/// it was newly inserted.
+pub async fn process_link_binding(
+    pool: &DbPool,
+    binding: &LinkBinding,
+) -> ScannerResult<(Device, bool)> {
+    let now = now_rfc3339();
+
+    let (device, new) = match netsec_db::repo::devices::get_by_ip(pool, &binding.ip).await? {
+        Some(mut existing) => {
+            existing.last_seen = now.clone();
+            existing.status = DeviceStatus::Online.as_str().to_string();
+            if existing.mac.is_none() {
+                existing.mac = Some(binding.mac.clone());
+            }
+            if existing.vendor.is_none() {
+                existing.vendor = oui::lookup_vendor(&binding.mac);
+            }
+            if existing.hostname.is_none() {
+                existing.hostname = binding.hostname.clone();
+            }
+            netsec_db::repo::devices::update(pool, &existing).await?;
+            (existing, false)
+        }
+        None => {
+            let mut device = Device::new(binding.ip.clone());
+            device.mac = Some(binding.mac.clone());
+            device.vendor = oui::lookup_vendor(&binding.mac);
+            device.hostname = binding.hostname.clone();
+            device.status = DeviceStatus::Online.as_str().to_string();
+            device.last_seen = now.clone();
+            device.first_seen = now.clone();
+            device.discovery_method = Some(DiscoveryMethod::Passive.as_str().to_string());
+            netsec_db::repo::devices::insert(pool, &device).await?;
+            (device, true)
+        }
+    };
+
+    let obs = Observation::new(
+        device.id.clone(),
+        binding.protocol.to_string(),
+        serde_json::json!({
+            "ip": binding.ip,
+            "mac": binding.mac,
+            "hostname": binding.hostname,
+        }),
+    );
+    netsec_db::repo::observations::insert(pool, &obs).await?;
+
+    Ok((device, new))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
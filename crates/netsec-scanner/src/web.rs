@@ -0,0 +1,101 @@
+//! Structured findings for web services from NSE script output.
+//!
+//! Nmap's `http-title` and `http-server-header` scripts report the page title
+//! and `Server` header of HTTP(S) ports. These helpers turn the raw script
+//! output captured by the nmap parser into structured port metadata.
+
+use std::collections::HashMap;
+
+/// NSE script ids requested for web-service findings.
+pub const WEB_SCRIPTS: &[&str] = &["http-title", "http-server-header"];
+
+/// Structured web-service metadata extracted from a port's scripts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebServiceInfo {
+    pub http_title: Option<String>,
+    pub server_header: Option<String>,
+}
+
+/// Extract web-service findings from a port's script outputs (keyed by script id).
+pub fn extract_web_info(scripts: &HashMap<String, String>) -> WebServiceInfo {
+    WebServiceInfo {
+        http_title: scripts.get("http-title").and_then(|o| parse_http_title(o)),
+        server_header: scripts
+            .get("http-server-header")
+            .and_then(|o| parse_http_server_header(o)),
+    }
+}
+
+/// Parse `http-title` script output into a page title.
+///
+/// The title is the first non-empty line. Returns `None` for nmap's
+/// "no title" and "did not follow redirect" placeholders.
+pub fn parse_http_title(output: &str) -> Option<String> {
+    let line = first_line(output)?;
+    if line.starts_with("Site doesn't have a title") || line.starts_with("Did not follow redirect") {
+        return None;
+    }
+    Some(line.to_string())
+}
+
+/// Parse `http-server-header` script output into the `Server` header value.
+///
+/// When nmap reports several headers (one per line) the first is used.
+pub fn parse_http_server_header(output: &str) -> Option<String> {
+    first_line(output).map(str::to_string)
+}
+
+fn first_line(output: &str) -> Option<&str> {
+    output.lines().map(str::trim).find(|l| !l.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_title() {
+        assert_eq!(parse_http_title("Welcome to nginx!").as_deref(), Some("Welcome to nginx!"));
+        assert_eq!(
+            parse_http_title("\n  Login Page\n  Requested resource was /login").as_deref(),
+            Some("Login Page")
+        );
+    }
+
+    #[test]
+    fn test_parse_http_title_placeholders() {
+        assert_eq!(parse_http_title("Site doesn't have a title (text/html)."), None);
+        assert_eq!(
+            parse_http_title("Did not follow redirect to https://10.0.0.1/"),
+            None
+        );
+        assert_eq!(parse_http_title("   \n"), None);
+    }
+
+    #[test]
+    fn test_parse_http_server_header() {
+        assert_eq!(
+            parse_http_server_header("nginx/1.18.0 (Ubuntu)").as_deref(),
+            Some("nginx/1.18.0 (Ubuntu)")
+        );
+        assert_eq!(
+            parse_http_server_header("\n  Apache/2.4.41\n  Apache").as_deref(),
+            Some("Apache/2.4.41")
+        );
+        assert_eq!(parse_http_server_header(""), None);
+    }
+
+    #[test]
+    fn test_extract_web_info() {
+        let mut scripts = HashMap::new();
+        scripts.insert("http-title".to_string(), "Router Admin".to_string());
+        scripts.insert("http-server-header".to_string(), "GoAhead-Webs".to_string());
+        scripts.insert("ssl-cert".to_string(), "Subject: commonName=router".to_string());
+
+        let info = extract_web_info(&scripts);
+        assert_eq!(info.http_title.as_deref(), Some("Router Admin"));
+        assert_eq!(info.server_header.as_deref(), Some("GoAhead-Webs"));
+
+        assert_eq!(extract_web_info(&HashMap::new()), WebServiceInfo::default());
+    }
+}
@@ -16,7 +16,7 @@ async fn test_run_scan_end_to_end_no_nmap() {
         target: "192.168.99.0/24".to_string(),
         scan_type: ScanType::Discovery,
         timing: 4,
-        ports: None,
+        ..Default::default()
     };
 
     let result = scanner.run_scan(&config).await;
@@ -45,7 +45,7 @@ async fn test_scan_record_lifecycle() {
         target: "10.0.0.0/24".to_string(),
         scan_type: ScanType::Full,
         timing: 3,
-        ports: None,
+        ..Default::default()
     };
 
     // Create scan record
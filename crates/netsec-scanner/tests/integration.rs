@@ -18,7 +18,7 @@ async fn test_full_scan_pipeline() {
         target: "192.168.1.0/24".to_string(),
         scan_type: ScanType::Full,
         timing: 4,
-        ports: None,
+        ..Default::default()
     };
     let args = build_nmap_args(&config);
     assert!(args.contains(&"-sV".to_string()));
@@ -90,6 +90,8 @@ async fn test_passive_then_active_merge() {
                 state: "open".to_string(),
                 service_name: Some("ipp".to_string()),
                 service_version: None,
+                http_title: None,
+                server_header: None,
             },
             DiscoveredPort {
                 port: 9100,
@@ -97,6 +99,8 @@ async fn test_passive_then_active_merge() {
                 state: "open".to_string(),
                 service_name: Some("jetdirect".to_string()),
                 service_version: None,
+                http_title: None,
+                server_header: None,
             },
         ],
     }];
@@ -145,6 +149,8 @@ async fn test_classification_after_scan() {
                 state: "open".to_string(),
                 service_name: Some("ssh".to_string()),
                 service_version: Some("OpenSSH 9.0".to_string()),
+                http_title: None,
+                server_header: None,
             },
             DiscoveredPort {
                 port: 5432,
@@ -152,6 +158,8 @@ async fn test_classification_after_scan() {
                 state: "open".to_string(),
                 service_name: Some("postgresql".to_string()),
                 service_version: Some("15.4".to_string()),
+                http_title: None,
+                server_header: None,
             },
         ],
     }];
@@ -0,0 +1,261 @@
+//! Five-field cron expression parsing.
+//!
+//! Supports `*`, single values, comma lists (`1,15,30`), ranges (`9-17`) and
+//! steps (`*/5`, `0-30/10`, `5/15`) in every field:
+//!
+//! ```text
+//! minute hour day-of-month month day-of-week
+//! 0-59   0-23 1-31         1-12  0-7 (0 and 7 are Sunday)
+//! ```
+//!
+//! As in classic cron, when both day-of-month and day-of-week are restricted
+//! a time matches if *either* does.
+//!
+//! Fields are compared against the wall-clock time of whatever timezone the
+//! given time is in. The rule is "fire once per matching minute": each real
+//! minute is evaluated once by its local time, so across a DST transition a
+//! repeated local hour fires twice and a skipped local hour never fires.
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
+
+use crate::{SchedulerError, SchedulerResult};
+
+/// Allowed values in one field, as a bit set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    bits: u64,
+    /// The field was `*` (unrestricted), which matters for the day fields.
+    any: bool,
+}
+
+impl Field {
+    fn contains(&self, value: u32) -> bool {
+        value < 64 && self.bits & (1 << value) != 0
+    }
+}
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: Field,
+    hours: Field,
+    days_of_month: Field,
+    months: Field,
+    days_of_week: Field,
+}
+
+impl CronSchedule {
+    /// Parse a five-field cron expression.
+    pub fn parse(expr: &str) -> SchedulerResult<Self> {
+        let parts: Vec<&str> = expr.split_whitespace().collect();
+        if parts.len() != 5 {
+            return Err(invalid(expr, "expected 5 fields"));
+        }
+
+        let mut days_of_week = parse_field(parts[4], 0, 7).map_err(|e| invalid(expr, &e))?;
+        // 7 is an alias for Sunday
+        if days_of_week.contains(7) {
+            days_of_week.bits |= 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(parts[0], 0, 59).map_err(|e| invalid(expr, &e))?,
+            hours: parse_field(parts[1], 0, 23).map_err(|e| invalid(expr, &e))?,
+            days_of_month: parse_field(parts[2], 1, 31).map_err(|e| invalid(expr, &e))?,
+            months: parse_field(parts[3], 1, 12).map_err(|e| invalid(expr, &e))?,
+            days_of_week,
+        })
+    }
+
+    /// Whether the schedule fires in the minute containing `time`, using
+    /// `time`'s local wall clock.
+    pub fn matches<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> bool {
+        let dom = self.days_of_month.contains(time.day());
+        let dow = self.days_of_week.contains(time.weekday().num_days_from_sunday());
+        let day = match (self.days_of_month.any, self.days_of_week.any) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        };
+
+        self.minutes.contains(time.minute())
+            && self.hours.contains(time.hour())
+            && self.months.contains(time.month())
+            && day
+    }
+
+    /// The first minute strictly after `after` at which the schedule fires,
+    /// searching at most [`MAX_SEARCH_MINUTES`] ahead.
+    ///
+    /// Minutes are stepped on the absolute timeline and matched by `after`'s
+    /// wall clock, following the same DST rule as [`matches`](Self::matches).
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let minute_start = after.with_second(0)?.with_nanosecond(0)?;
+        (1..=MAX_SEARCH_MINUTES)
+            .map(|m| minute_start.clone() + Duration::minutes(m))
+            .find(|candidate| self.matches(candidate))
+    }
+}
+
+/// How far [`CronSchedule::next_after`] searches: one (leap) year of minutes.
+pub const MAX_SEARCH_MINUTES: i64 = 366 * 24 * 60;
+
+fn invalid(expr: &str, reason: &str) -> SchedulerError {
+    SchedulerError::InvalidTrigger(format!("invalid cron expression '{expr}': {reason}"))
+}
+
+/// Parse one field whose values lie in `min..=max`.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Field, String> {
+    let mut bits = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step '{step}'"))?;
+                if step == 0 {
+                    return Err("step must be positive".to_string());
+                }
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            // `5/15` means "from 5 to the end in steps of 15"
+            (value, if step.is_some() { max } else { value })
+        };
+        if start > end {
+            return Err(format!("range '{range}' is reversed"));
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+
+    Ok(Field {
+        bits,
+        any: field == "*",
+    })
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(v) if (min..=max).contains(&v) => Ok(v),
+        _ => Err(format!("'{value}' is not in {min}-{max}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2024-06-17 is a Monday
+        Utc.with_ymd_and_hms(2024, month, day, hour, minute, 0).unwrap()
+    }
+
+    fn due(expr: &str, time: DateTime<Utc>) -> bool {
+        CronSchedule::parse(expr).unwrap().matches(&time)
+    }
+
+    #[test]
+    fn test_business_hours_weekdays() {
+        let expr = "0 9-17 * * 1-5";
+        assert!(due(expr, at(6, 17, 9, 0))); // Monday 09:00
+        assert!(due(expr, at(6, 21, 17, 0))); // Friday 17:00
+        assert!(!due(expr, at(6, 17, 18, 0))); // after hours
+        assert!(!due(expr, at(6, 17, 9, 30))); // not on the hour
+        assert!(!due(expr, at(6, 22, 12, 0))); // Saturday
+        assert!(!due(expr, at(6, 23, 12, 0))); // Sunday
+    }
+
+    #[test]
+    fn test_minute_steps() {
+        assert!(due("*/15 * * * *", at(6, 17, 3, 45)));
+        assert!(!due("*/15 * * * *", at(6, 17, 3, 50)));
+        assert!(due("0-30/10 * * * *", at(6, 17, 3, 30)));
+        assert!(!due("0-30/10 * * * *", at(6, 17, 3, 40)));
+        assert!(due("5/20 * * * *", at(6, 17, 3, 45)));
+        assert!(!due("5/20 * * * *", at(6, 17, 3, 0)));
+    }
+
+    #[test]
+    fn test_hour_steps_and_lists() {
+        assert!(due("0 */6 * * *", at(6, 17, 18, 0)));
+        assert!(!due("0 */6 * * *", at(6, 17, 19, 0)));
+        assert!(due("1,15,30 * * * *", at(6, 17, 3, 15)));
+        assert!(!due("1,15,30 * * * *", at(6, 17, 3, 16)));
+    }
+
+    #[test]
+    fn test_day_of_month_and_month() {
+        assert!(due("0 0 1 * *", at(7, 1, 0, 0)));
+        assert!(!due("0 0 1 * *", at(7, 2, 0, 0)));
+        assert!(due("0 0 */10 * *", at(7, 11, 0, 0)));
+        assert!(!due("0 0 */10 * *", at(7, 10, 0, 0)));
+        assert!(due("0 0 1 1-3,12 *", at(12, 1, 0, 0)));
+        assert!(!due("0 0 1 1-3,12 *", at(6, 1, 0, 0)));
+        assert!(due("0 0 * */3 *", at(4, 9, 0, 0)));
+        assert!(!due("0 0 * */3 *", at(5, 9, 0, 0)));
+    }
+
+    #[test]
+    fn test_day_of_week_sunday_aliases_and_steps() {
+        let sunday = at(6, 23, 0, 0);
+        assert!(due("0 0 * * 0", sunday));
+        assert!(due("0 0 * * 7", sunday));
+        // Every other day starting Sunday: Sun, Tue, Thu, Sat
+        assert!(due("0 0 * * */2", at(6, 18, 0, 0))); // Tuesday
+        assert!(!due("0 0 * * */2", at(6, 17, 0, 0))); // Monday
+    }
+
+    #[test]
+    fn test_restricted_day_fields_match_either() {
+        // 13th of the month or any Friday
+        let expr = "0 0 13 * 5";
+        assert!(due(expr, at(6, 13, 0, 0))); // Thursday the 13th
+        assert!(due(expr, at(6, 21, 0, 0))); // Friday the 21st
+        assert!(!due(expr, at(6, 20, 0, 0)));
+    }
+
+    #[test]
+    fn test_next_after() {
+        let schedule = CronSchedule::parse("0 9-17 * * 1-5").unwrap();
+        // Friday 17:30 -> Monday 09:00
+        let next = schedule.next_after(&at(6, 21, 17, 30)).unwrap();
+        assert_eq!(next, at(6, 24, 9, 0));
+
+        // Strictly after: a matching minute itself is skipped
+        let next = schedule.next_after(&at(6, 24, 9, 0)).unwrap();
+        assert_eq!(next, at(6, 24, 10, 0));
+
+        // February 30th never happens
+        let never = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert!(never.next_after(&at(6, 17, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "10-5 * * * *",
+            "a * * * *",
+            "1,,2 * * * *",
+        ] {
+            assert!(CronSchedule::parse(expr).is_err(), "{expr} should be invalid");
+        }
+    }
+}
@@ -1,19 +1,30 @@
 //! Job scheduler with SQLite persistence.
 //!
 //! Provides a tick-based scheduler that queries enabled jobs from the database
-//! and dispatches them based on interval or cron triggers.
+//! and dispatches them based on interval or cron triggers. Each job's last run
+//! is stored in the database, so interval timing survives restarts.
 
-use std::collections::HashMap;
+pub mod cron;
+pub mod offline;
+
+use std::collections::HashSet;
+use std::sync::Mutex;
 use std::time::Duration;
 
-use chrono::{DateTime, Utc};
-use netsec_events::EventBus;
+use chrono::{DateTime, DurationRound, Utc};
+use chrono_tz::Tz;
+use netsec_db::DbPool;
+use netsec_events::{EventBus, FilteredSubscriber};
 use netsec_models::event::{EventType, NetsecEvent};
-use sqlx::SqlitePool;
+use netsec_models::plugin::ScheduledJob;
+use netsec_models::time::{parse_rfc3339, to_rfc3339};
 use thiserror::Error;
-use tokio::sync::watch;
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
 use tokio::task::JoinHandle;
 
+pub use cron::CronSchedule;
+pub use offline::mark_stale_devices_offline;
+
 #[derive(Debug, Error)]
 pub enum SchedulerError {
     #[error("database error: {0}")]
@@ -22,6 +33,8 @@ pub enum SchedulerError {
     InvalidTrigger(String),
     #[error("job not found: {0}")]
     JobNotFound(String),
+    #[error("scheduler is not running")]
+    NotRunning,
 }
 
 pub type SchedulerResult<T> = Result<T, SchedulerError>;
@@ -46,14 +59,26 @@ pub fn parse_interval_args(args: &str) -> SchedulerResult<Duration> {
 /// Returns `true` if:
 /// - `last_run` is `None` (first run)
 /// - The elapsed time since `last_run` exceeds the interval
+///
+/// An unparseable `last_run` is logged and the job is skipped rather than
+/// fired, so a corrupt timestamp cannot cause a job to run on every tick.
 pub fn is_interval_due(last_run: Option<&str>, interval: Duration) -> bool {
+    is_interval_due_at(last_run, interval, &Utc::now())
+}
+
+/// [`is_interval_due`] evaluated at `now` instead of the current time.
+pub fn is_interval_due_at(last_run: Option<&str>, interval: Duration, now: &DateTime<Utc>) -> bool {
     match last_run {
         None => true,
         Some(last) => {
-            let Ok(last_dt) = DateTime::parse_from_rfc3339(last) else {
-                return true; // Can't parse -> treat as overdue
+            let last_dt = match parse_rfc3339(last) {
+                Ok(dt) => dt,
+                Err(e) => {
+                    tracing::warn!("Skipping interval job: {}", e);
+                    return false;
+                }
             };
-            let elapsed = Utc::now().signed_duration_since(last_dt.with_timezone(&Utc));
+            let elapsed = now.signed_duration_since(last_dt);
             elapsed.to_std().unwrap_or(Duration::ZERO) >= interval
         }
     }
@@ -73,88 +98,447 @@ pub fn parse_cron_args(args: &str) -> SchedulerResult<String> {
         })
 }
 
-/// Simplified cron check: is the expression due at the given time?
-///
-/// Supports common patterns:
-/// - `* * * * *` — every minute (always true)
-/// - `0 * * * *` — hourly (minute == 0)
-/// - `0 0 * * *` — daily (minute == 0, hour == 0)
-/// - `0 0 * * 0` — weekly on Sunday (minute == 0, hour == 0, weekday == Sun)
+/// Parse once trigger_args JSON: `{"run_at": "2024-06-15T14:00:00Z"}` -> run time.
+pub fn parse_once_args(args: &str) -> SchedulerResult<DateTime<Utc>> {
+    let parsed: serde_json::Value =
+        serde_json::from_str(args).map_err(|e| SchedulerError::InvalidTrigger(e.to_string()))?;
+
+    let run_at = parsed
+        .get("run_at")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            SchedulerError::InvalidTrigger("missing or invalid 'run_at' field".to_string())
+        })?;
+
+    parse_rfc3339(run_at).map_err(|e| SchedulerError::InvalidTrigger(e.to_string()))
+}
+
+/// Check if a one-time job is due: `now` has reached `run_at` and the job
+/// has never run.
+pub fn is_once_due(run_at: &DateTime<Utc>, last_run: Option<&str>, now: &DateTime<Utc>) -> bool {
+    last_run.is_none() && now >= run_at
+}
+
+/// Cron check: is the expression due at the given time?
 ///
-/// For unrecognized patterns, returns `false`.
+/// Accepts full five-field expressions (see [`CronSchedule`]). Invalid
+/// expressions are logged and never fire.
 pub fn is_cron_due(cron_expr: &str, now: &DateTime<Utc>) -> bool {
-    let parts: Vec<&str> = cron_expr.split_whitespace().collect();
-    if parts.len() != 5 {
-        return false;
-    }
+    is_cron_due_tz(cron_expr, now, Tz::UTC)
+}
 
-    let minute = now.format("%M").to_string().parse::<u32>().unwrap_or(0);
-    let hour = now.format("%H").to_string().parse::<u32>().unwrap_or(0);
-    let weekday = now.format("%w").to_string().parse::<u32>().unwrap_or(0);
+/// Cron check against the wall-clock time in `tz`.
+///
+/// `now_utc` is converted into `tz` before the fields are compared, so
+/// `0 9 * * *` in `America/New_York` fires at 09:00 New York time. Across DST
+/// transitions the job fires once per matching minute: twice during a
+/// repeated local hour and not at all during a skipped one.
+pub fn is_cron_due_tz(cron_expr: &str, now_utc: &DateTime<Utc>, tz: Tz) -> bool {
+    match CronSchedule::parse(cron_expr) {
+        Ok(schedule) => schedule.matches(&now_utc.with_timezone(&tz)),
+        Err(e) => {
+            tracing::warn!("Skipping cron job: {}", e);
+            false
+        }
+    }
+}
 
-    // Check minute field
-    let minute_match = match parts[0] {
-        "*" => true,
-        val => val.parse::<u32>().map(|v| v == minute).unwrap_or(false),
+/// Whether `last_run` falls in the same minute as `now` on `tz`'s clock, i.e.
+/// a cron job already fired for this matching minute.
+pub fn cron_ran_this_minute(last_run: &DateTime<Utc>, now: &DateTime<Utc>, tz: Tz) -> bool {
+    let minute = |t: &DateTime<Utc>| {
+        t.with_timezone(&tz)
+            .duration_trunc(chrono::Duration::minutes(1))
+            .ok()
     };
+    minute(last_run).is_some() && minute(last_run) == minute(now)
+}
+
+/// When a trigger fires next, strictly after `after`.
+///
+/// - `interval`: `after` plus the interval.
+/// - `cron`: the next matching minute (UTC), searched up to a year ahead;
+///   `None` if the expression never matches in that window.
+/// - `once`: the run time, or `None` if it is not after `after`.
+///
+/// Jitter and per-job timezones are not applied.
+pub fn next_run_after(
+    trigger_type: &str,
+    trigger_args: &str,
+    after: DateTime<Utc>,
+) -> SchedulerResult<Option<DateTime<Utc>>> {
+    match trigger_type {
+        "interval" => {
+            let interval = parse_interval_args(trigger_args)?;
+            let interval = chrono::Duration::from_std(interval)
+                .map_err(|e| SchedulerError::InvalidTrigger(e.to_string()))?;
+            Ok(after.checked_add_signed(interval))
+        }
+        "cron" => {
+            let expr = parse_cron_args(trigger_args)?;
+            Ok(CronSchedule::parse(&expr)?.next_after(&after))
+        }
+        "once" => {
+            let run_at = parse_once_args(trigger_args)?;
+            Ok((run_at > after).then_some(run_at))
+        }
+        other => Err(SchedulerError::InvalidTrigger(format!(
+            "unknown trigger type '{other}'"
+        ))),
+    }
+}
+
+/// Scheduler tuning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    /// How often the jobs table is checked.
+    pub tick_interval: Duration,
+    /// Spread dispatches over up to this many seconds: each job gets a fixed
+    /// per-job offset (see [`jitter_offset`]) that shifts its phase, not its
+    /// period (see [`jittered_due_at`]). 0 disables.
+    pub jitter_secs: u64,
+    /// Mark devices unseen for this long offline on every tick (see
+    /// [`mark_stale_devices_offline`]). `None` disables the sweep.
+    pub offline_after: Option<Duration>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval: Duration::from_secs(60),
+            jitter_secs: 0,
+            offline_after: None,
+        }
+    }
+}
+
+impl From<Duration> for SchedulerConfig {
+    fn from(tick_interval: Duration) -> Self {
+        Self {
+            tick_interval,
+            ..Self::default()
+        }
+    }
+}
 
-    // Check hour field
-    let hour_match = match parts[1] {
-        "*" => true,
-        val => val.parse::<u32>().map(|v| v == hour).unwrap_or(false),
+/// Deterministic dispatch delay for a job: a hash of its id modulo
+/// `jitter_secs`.
+///
+/// Uses FNV-1a so the offset is the same on every tick and across restarts.
+pub fn jitter_offset(job_id: &str, jitter_secs: u64) -> Duration {
+    if jitter_secs == 0 {
+        return Duration::ZERO;
+    }
+    let hash = job_id.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    Duration::from_secs(hash % jitter_secs)
+}
+
+/// When an interval job with jitter `offset` is next due.
+///
+/// The offset is a fixed phase rather than an extra delay. A job that has
+/// never run is first due `offset` after `created_at`. After that it runs on
+/// the grid `offset + k * interval` (from the Unix epoch), at the grid point
+/// nearest to `last_run + interval`. The period stays `interval`, and jobs
+/// whose runs coincided drift apart onto their own phases.
+pub fn jittered_due_at(
+    last_run: Option<&DateTime<Utc>>,
+    created_at: &DateTime<Utc>,
+    interval: Duration,
+    offset: Duration,
+) -> DateTime<Utc> {
+    let offset_ms = offset.as_millis() as i64;
+    let Some(last_run) = last_run else {
+        return *created_at + chrono::Duration::milliseconds(offset_ms);
     };
+    let interval_ms = interval.as_millis() as i64;
+    if interval_ms == 0 {
+        return *last_run;
+    }
+    let phase = offset_ms % interval_ms;
+    let target = last_run.timestamp_millis() + interval_ms;
+    let slot = (target - phase + interval_ms / 2).div_euclid(interval_ms);
+    DateTime::from_timestamp_millis(phase + slot * interval_ms).unwrap_or(*last_run)
+}
+
+/// Whether an interval `job` is due at `now` with jitter `offset` applied.
+///
+/// Unparseable timestamps are logged and the job is skipped, as in
+/// [`is_interval_due`].
+fn is_jittered_interval_due(
+    job: &ScheduledJob,
+    interval: Duration,
+    offset: Duration,
+    now: &DateTime<Utc>,
+) -> bool {
+    let parsed = (
+        job.last_run.as_deref().map(parse_rfc3339).transpose(),
+        parse_rfc3339(&job.created_at),
+    );
+    match parsed {
+        (Ok(last_run), Ok(created_at)) => {
+            *now >= jittered_due_at(last_run.as_ref(), &created_at, interval, offset)
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::warn!("Skipping interval job {}: {}", job.id, e);
+            false
+        }
+    }
+}
 
-    // Check day of month field (simplified: just wildcard)
-    let dom_match = parts[2] == "*";
+/// What to do when a job comes due while its previous run is still in flight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Dispatch every time the job is due.
+    #[default]
+    Allow,
+    /// Skip dispatch until a `ScanCompleted` or `ScanFailed` event with the
+    /// job's `job_id` has been seen.
+    Skip,
+}
 
-    // Check month field (simplified: just wildcard)
-    let month_match = parts[3] == "*";
+/// Remove the job referenced by a completion event from the in-flight set.
+fn clear_in_flight(in_flight: &mut HashSet<String>, event: &NetsecEvent) {
+    if let Some(job_id) = event.payload.get("job_id").and_then(|v| v.as_str()) {
+        if in_flight.remove(job_id) {
+            tracing::debug!("Job {job_id} finished");
+        }
+    }
+}
 
-    // Check day of week field
-    let dow_match = match parts[4] {
-        "*" => true,
-        val => val.parse::<u32>().map(|v| v == weekday).unwrap_or(false),
+/// Read enabled jobs and dispatch those that are due.
+///
+/// Each job is shifted by its [`jitter_offset`]. With `in_flight`
+/// set (overlap prevention), jobs in the set are skipped and dispatched jobs
+/// are added to it.
+async fn run_tick(
+    pool: &DbPool,
+    event_bus: &EventBus,
+    jitter_secs: u64,
+    mut in_flight: Option<&mut HashSet<String>>,
+) {
+    let jobs = match netsec_db::repo::scheduled_jobs::list_enabled(pool).await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::error!("Failed to query jobs: {e}");
+            return;
+        }
     };
 
-    minute_match && hour_match && dom_match && month_match && dow_match
+    let now = Utc::now();
+
+    for job in &jobs {
+        if in_flight.as_deref().is_some_and(|set| set.contains(&job.id)) {
+            tracing::debug!("Skipping job {}: previous run still in flight", job.id);
+            continue;
+        }
+
+        let offset = jitter_offset(&job.id, jitter_secs);
+        // Cron and one-time triggers are evaluated as of `offset` ago
+        let shifted = now - chrono::Duration::from_std(offset).unwrap_or_default();
+
+        let is_due = match job.trigger_type.as_str() {
+            "interval" => {
+                match parse_interval_args(&job.trigger_args) {
+                    Ok(interval) if jitter_secs == 0 => {
+                        is_interval_due(job.last_run.as_deref(), interval)
+                    }
+                    Ok(interval) => is_jittered_interval_due(job, interval, offset, &now),
+                    Err(e) => {
+                        tracing::warn!("Invalid interval args for job {}: {e}", job.id);
+                        false
+                    }
+                }
+            }
+            "cron" => {
+                match (parse_cron_args(&job.trigger_args), job.timezone.parse::<Tz>()) {
+                    (Ok(expr), Ok(tz)) => {
+                        // A reload within a matching minute must not fire the
+                        // job again. `last_run` is recorded unshifted.
+                        let ran = job
+                            .last_run
+                            .as_deref()
+                            .and_then(|last| parse_rfc3339(last).ok())
+                            .is_some_and(|last| {
+                                cron_ran_this_minute(&(last - (now - shifted)), &shifted, tz)
+                            });
+                        !ran && is_cron_due_tz(&expr, &shifted, tz)
+                    }
+                    (_, Err(e)) => {
+                        tracing::warn!("Invalid timezone for job {}: {e}", job.id);
+                        false
+                    }
+                    (Err(e), _) => {
+                        tracing::warn!("Invalid cron args for job {}: {e}", job.id);
+                        false
+                    }
+                }
+            }
+            "once" => {
+                match parse_once_args(&job.trigger_args) {
+                    Ok(run_at) => is_once_due(&run_at, job.last_run.as_deref(), &shifted),
+                    Err(e) => {
+                        tracing::warn!("Invalid once args for job {}: {e}", job.id);
+                        false
+                    }
+                }
+            }
+            _ => false,
+        };
+
+        if is_due {
+            dispatch_job(pool, event_bus, job, &now).await;
+            if let Some(set) = in_flight.as_deref_mut() {
+                set.insert(job.id.clone());
+            }
+        }
+    }
+}
+
+/// Publish a job's `ScanStarted` event and record the run.
+///
+/// One-time jobs are disabled afterwards so they never repeat.
+async fn dispatch_job(
+    pool: &DbPool,
+    event_bus: &EventBus,
+    job: &ScheduledJob,
+    now: &DateTime<Utc>,
+) {
+    let event = NetsecEvent::new(
+        EventType::ScanStarted,
+        serde_json::json!({
+            "job_id": job.id,
+            "task_type": job.task_type,
+            "task_params": job.task_params,
+        }),
+    );
+    let _ = event_bus.publish(event);
+    if let Err(e) =
+        netsec_db::repo::scheduled_jobs::update_last_run(pool, &job.id, &to_rfc3339(now)).await
+    {
+        tracing::error!("Failed to record last run for job {}: {e}", job.id);
+    }
+    if job.trigger_type == "once" {
+        // One-time jobs never repeat
+        if let Err(e) =
+            netsec_db::repo::scheduled_jobs::set_enabled(pool, &job.id, false, &to_rfc3339(now)).await
+        {
+            tracing::error!("Failed to disable one-time job {}: {e}", job.id);
+        }
+    }
+    tracing::info!("Dispatched job {}: {}", job.id, job.task_type);
+}
+
+/// Dispatch a job immediately, bypassing its trigger.
+async fn run_now(
+    pool: &DbPool,
+    event_bus: &EventBus,
+    job_id: &str,
+    in_flight: Option<&mut HashSet<String>>,
+) -> SchedulerResult<()> {
+    let job = netsec_db::repo::scheduled_jobs::get_by_id(pool, job_id)
+        .await?
+        .filter(|job| job.enabled)
+        .ok_or_else(|| SchedulerError::JobNotFound(job_id.to_string()))?;
+
+    tracing::info!("Manual run requested for job {}", job.id);
+    dispatch_job(pool, event_bus, &job, &Utc::now()).await;
+    if let Some(set) = in_flight {
+        set.insert(job.id);
+    }
+    Ok(())
+}
+
+/// Requests sent to the running tick loop.
+enum SchedulerCommand {
+    RunNow {
+        job_id: String,
+        reply: oneshot::Sender<SchedulerResult<()>>,
+    },
 }
 
 /// Job scheduler with tick-based dispatch.
 pub struct Scheduler {
-    pool: SqlitePool,
+    pool: DbPool,
     event_bus: EventBus,
-    tick_interval: Duration,
+    config: SchedulerConfig,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
+    reload_tx: watch::Sender<u64>,
+    reload_rx: watch::Receiver<u64>,
+    overlap_policy: OverlapPolicy,
+    command_tx: mpsc::UnboundedSender<SchedulerCommand>,
+    /// Taken by the tick loop in [`Scheduler::start`].
+    command_rx: Mutex<Option<mpsc::UnboundedReceiver<SchedulerCommand>>>,
 }
 
 impl Scheduler {
-    /// Create a new scheduler.
-    pub fn new(pool: SqlitePool, event_bus: EventBus, tick_interval: Duration) -> Self {
+    /// Create a new scheduler. Accepts a [`SchedulerConfig`] or just a tick
+    /// interval.
+    pub fn new(pool: DbPool, event_bus: EventBus, config: impl Into<SchedulerConfig>) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (reload_tx, reload_rx) = watch::channel(0);
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
         Self {
             pool,
             event_bus,
-            tick_interval,
+            config: config.into(),
             shutdown_tx,
             shutdown_rx,
+            reload_tx,
+            reload_rx,
+            overlap_policy: OverlapPolicy::Allow,
+            command_tx,
+            command_rx: Mutex::new(Some(command_rx)),
         }
     }
 
+    /// Set how jobs that are still running are treated when they come due
+    /// again. Defaults to [`OverlapPolicy::Allow`].
+    pub fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap_policy = policy;
+        self
+    }
+
     /// Start the scheduler tick loop in a background task.
     ///
     /// Every `tick_interval`, queries enabled jobs from the database and
     /// checks if each is due. For due jobs, publishes a `ScanStarted` event.
+    /// With `offline_after` set, each tick also runs the offline sweep.
+    /// A [`Scheduler::reload`] forces an immediate tick. Ticks keep a fixed
+    /// cadence: reloads, manual runs and completions do not postpone them.
     pub fn start(&self) -> JoinHandle<()> {
         let pool = self.pool.clone();
         let event_bus = self.event_bus.clone();
-        let tick_interval = self.tick_interval;
+        let SchedulerConfig {
+            tick_interval,
+            jitter_secs,
+            offline_after,
+        } = self.config;
         let mut shutdown_rx = self.shutdown_rx.clone();
+        let mut reload_rx = self.reload_rx.clone();
+        let mut command_rx = self.command_rx.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if command_rx.is_none() {
+            tracing::warn!("Scheduler started more than once; manual runs go to the first loop");
+        }
+        // Subscribe before the first dispatch so no completion is missed
+        let mut completions: Option<FilteredSubscriber> = match self.overlap_policy {
+            OverlapPolicy::Allow => None,
+            OverlapPolicy::Skip => Some(
+                event_bus.subscribe_filtered(vec![EventType::ScanCompleted, EventType::ScanFailed]),
+            ),
+        };
 
         tokio::spawn(async move {
-            let mut last_runs: HashMap<String, String> = HashMap::new();
+            let mut in_flight: HashSet<String> = HashSet::new();
+            // Created once so the other branches can't restart the countdown
+            let first_tick = tokio::time::Instant::now() + tick_interval;
+            let mut ticker = tokio::time::interval_at(first_tick, tick_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
             loop {
+                let skip_overlap = completions.is_some();
                 tokio::select! {
                     _ = shutdown_rx.changed() => {
                         if *shutdown_rx.borrow() {
@@ -162,55 +546,58 @@ impl Scheduler {
                             break;
                         }
                     }
-                    _ = tokio::time::sleep(tick_interval) => {
-                        let jobs = match netsec_db::repo::scheduled_jobs::list_enabled(&pool).await {
-                            Ok(jobs) => jobs,
-                            Err(e) => {
-                                tracing::error!("Failed to query jobs: {e}");
-                                continue;
+                    changed = reload_rx.changed() => {
+                        if changed.is_err() {
+                            // Scheduler dropped; nothing can signal us any more
+                            break;
+                        }
+                        tracing::info!("Scheduler reload requested");
+                        run_tick(&pool, &event_bus, jitter_secs, skip_overlap.then_some(&mut in_flight)).await;
+                    }
+                    Some(command) = async {
+                        match command_rx.as_mut() {
+                            Some(rx) => rx.recv().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        match command {
+                            SchedulerCommand::RunNow { job_id, reply } => {
+                                let result = run_now(
+                                    &pool,
+                                    &event_bus,
+                                    &job_id,
+                                    skip_overlap.then_some(&mut in_flight),
+                                )
+                                .await;
+                                let _ = reply.send(result);
                             }
-                        };
-
-                        let now = Utc::now();
-
-                        for job in &jobs {
-                            let is_due = match job.trigger_type.as_str() {
-                                "interval" => {
-                                    match parse_interval_args(&job.trigger_args) {
-                                        Ok(interval) => {
-                                            let last = last_runs.get(&job.id).map(|s| s.as_str());
-                                            is_interval_due(last, interval)
-                                        }
-                                        Err(e) => {
-                                            tracing::warn!("Invalid interval args for job {}: {e}", job.id);
-                                            false
-                                        }
-                                    }
-                                }
-                                "cron" => {
-                                    match parse_cron_args(&job.trigger_args) {
-                                        Ok(expr) => is_cron_due(&expr, &now),
-                                        Err(e) => {
-                                            tracing::warn!("Invalid cron args for job {}: {e}", job.id);
-                                            false
-                                        }
-                                    }
-                                }
-                                _ => false,
-                            };
-
-                            if is_due {
-                                let event = NetsecEvent::new(
-                                    EventType::ScanStarted,
-                                    serde_json::json!({
-                                        "job_id": job.id,
-                                        "task_type": job.task_type,
-                                        "task_params": job.task_params,
-                                    }),
-                                );
-                                let _ = event_bus.publish(event);
-                                last_runs.insert(job.id.clone(), now.to_rfc3339());
-                                tracing::info!("Dispatched job {}: {}", job.id, job.task_type);
+                        }
+                    }
+                    completed = async {
+                        match completions.as_mut() {
+                            Some(subscriber) => subscriber.recv().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        match completed {
+                            Ok(event) => clear_in_flight(&mut in_flight, &event),
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                // Completions may have been dropped; better a
+                                // possible overlap than a job stuck forever
+                                tracing::warn!("Scheduler missed {n} events, clearing in-flight jobs");
+                                in_flight.clear();
+                            }
+                            Err(broadcast::error::RecvError::Closed) => {
+                                completions = None;
+                                in_flight.clear();
+                            }
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        run_tick(&pool, &event_bus, jitter_secs, skip_overlap.then_some(&mut in_flight)).await;
+                        if let Some(threshold) = offline_after {
+                            if let Err(e) = mark_stale_devices_offline(&pool, &event_bus, threshold).await {
+                                tracing::error!("Offline sweep failed: {e}");
                             }
                         }
                     }
@@ -219,6 +606,35 @@ impl Scheduler {
         })
     }
 
+    /// Re-read the jobs table immediately instead of waiting for the next tick.
+    ///
+    /// Call after jobs are added, edited, or deleted so changes take effect
+    /// promptly.
+    pub fn reload(&self) {
+        self.reload_tx.send_modify(|generation| *generation = generation.wrapping_add(1));
+    }
+
+    /// Dispatch a job right away, regardless of its trigger state.
+    ///
+    /// Publishes the same `ScanStarted` event as a scheduled run and updates
+    /// the job's `last_run` (a one-time job is used up). Returns
+    /// [`SchedulerError::JobNotFound`] if the job does not exist or is
+    /// disabled, and [`SchedulerError::NotRunning`] if the tick loop is not
+    /// running.
+    pub async fn trigger_now(&self, job_id: &str) -> SchedulerResult<()> {
+        if self.command_rx.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+            return Err(SchedulerError::NotRunning);
+        }
+        let (reply, response) = oneshot::channel();
+        self.command_tx
+            .send(SchedulerCommand::RunNow {
+                job_id: job_id.to_string(),
+                reply,
+            })
+            .map_err(|_| SchedulerError::NotRunning)?;
+        response.await.map_err(|_| SchedulerError::NotRunning)?
+    }
+
     /// Signal the scheduler to shut down.
     pub fn shutdown(&self) {
         let _ = self.shutdown_tx.send(true);
@@ -228,7 +644,7 @@ impl Scheduler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use chrono::{Datelike, TimeZone};
 
     #[test]
     fn test_parse_interval_args_valid() {
@@ -258,20 +674,164 @@ mod tests {
     #[test]
     fn test_is_interval_due_not_yet() {
         // Last run is "now" -> not yet due
-        let now = Utc::now().to_rfc3339();
+        let now = netsec_models::time::now_rfc3339();
         assert!(!is_interval_due(Some(&now), Duration::from_secs(3600)));
     }
 
     #[test]
     fn test_is_interval_due_past() {
         // Last run was 2 hours ago, interval is 1 hour -> due
-        let two_hours_ago = (Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+        let two_hours_ago = to_rfc3339(&(Utc::now() - chrono::Duration::hours(2)));
         assert!(is_interval_due(
             Some(&two_hours_ago),
             Duration::from_secs(3600)
         ));
     }
 
+    #[test]
+    fn test_is_interval_due_at() {
+        let now = parse_rfc3339("2024-06-15T12:00:00Z").unwrap();
+        let hour = Duration::from_secs(3600);
+        assert!(is_interval_due_at(Some("2024-06-15T11:00:00Z"), hour, &now));
+        assert!(!is_interval_due_at(Some("2024-06-15T11:00:01Z"), hour, &now));
+        assert!(is_interval_due_at(None, hour, &now));
+    }
+
+    #[test]
+    fn test_is_interval_due_unparseable_skips() {
+        // A corrupt timestamp must not be treated as overdue
+        assert!(!is_interval_due(Some("not-a-timestamp"), Duration::from_secs(3600)));
+        assert!(!is_interval_due(Some(""), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_once_args() {
+        let run_at = parse_once_args(r#"{"run_at": "2024-06-15T14:00:00Z"}"#).unwrap();
+        assert_eq!(run_at, Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap());
+
+        assert!(parse_once_args("{}").is_err());
+        assert!(parse_once_args(r#"{"run_at": "tomorrow"}"#).is_err());
+    }
+
+    #[test]
+    fn test_is_once_due_not_yet() {
+        let run_at = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let before = Utc.with_ymd_and_hms(2024, 6, 15, 13, 59, 0).unwrap();
+        assert!(!is_once_due(&run_at, None, &before));
+    }
+
+    #[test]
+    fn test_is_once_due_unfired() {
+        let run_at = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        assert!(is_once_due(&run_at, None, &run_at));
+        // Missed while the scheduler was down: still fires
+        let later = Utc.with_ymd_and_hms(2024, 6, 16, 9, 0, 0).unwrap();
+        assert!(is_once_due(&run_at, None, &later));
+    }
+
+    #[test]
+    fn test_is_once_due_already_fired() {
+        let run_at = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let later = Utc.with_ymd_and_hms(2024, 6, 15, 15, 0, 0).unwrap();
+        assert!(!is_once_due(&run_at, Some("2024-06-15T14:00:05+00:00"), &later));
+    }
+
+    #[test]
+    fn test_clear_in_flight() {
+        let mut in_flight: HashSet<String> = ["job-1", "job-2"].map(String::from).into();
+        clear_in_flight(
+            &mut in_flight,
+            &NetsecEvent::new(EventType::ScanCompleted, serde_json::json!({"job_id": "job-1"})),
+        );
+        // Events without a job id are ignored
+        clear_in_flight(
+            &mut in_flight,
+            &NetsecEvent::new(EventType::ScanFailed, serde_json::json!({"scan_id": "s"})),
+        );
+        assert_eq!(in_flight, HashSet::from(["job-2".to_string()]));
+    }
+
+    #[test]
+    fn test_jitter_offset_is_stable_and_bounded() {
+        assert_eq!(jitter_offset("job-a", 0), Duration::ZERO);
+        let offset = jitter_offset("job-a", 300);
+        assert!(offset < Duration::from_secs(300));
+        assert_eq!(jitter_offset("job-a", 300), offset);
+    }
+
+    #[test]
+    fn test_jitter_spreads_jobs_with_same_interval() {
+        let interval = Duration::from_secs(3600);
+        let a = jitter_offset("job-a", 600);
+        let b = jitter_offset("job-b", 600);
+        assert_ne!(a, b);
+
+        // Created together: first runs are spread by the offsets
+        let created = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let first_a = jittered_due_at(None, &created, interval, a);
+        let first_b = jittered_due_at(None, &created, interval, b);
+        assert_ne!(first_a, first_b);
+
+        // Ran together anyway (e.g. before jitter was enabled): the next runs
+        // move onto different phases
+        let next_a = jittered_due_at(Some(&created), &created, interval, a);
+        let next_b = jittered_due_at(Some(&created), &created, interval, b);
+        assert_ne!(next_a, next_b);
+    }
+
+    #[test]
+    fn test_jitter_keeps_period() {
+        let interval = Duration::from_secs(3600);
+        let offset = Duration::from_secs(137);
+        let created = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let tick_delay = chrono::Duration::seconds(3);
+
+        let first = jittered_due_at(None, &created, interval, offset);
+        assert_eq!(first, created + chrono::Duration::seconds(137));
+
+        // Each run happens a little after its due time, at the next tick
+        let second = jittered_due_at(Some(&(first + tick_delay)), &created, interval, offset);
+        let third = jittered_due_at(Some(&(second + tick_delay)), &created, interval, offset);
+        let hour = chrono::Duration::hours(1);
+        assert_eq!(third - second, hour);
+        assert!(second - first <= hour);
+        // The offset is a phase on the interval grid, not added per period
+        assert_eq!(second.timestamp() % 3600, 137);
+    }
+
+    #[test]
+    fn test_next_run_after_interval() {
+        let after = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let next = next_run_after("interval", r#"{"interval_secs": 5400}"#, after).unwrap();
+        assert_eq!(next, Some(Utc.with_ymd_and_hms(2024, 6, 15, 15, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_next_run_after_hourly_cron() {
+        let after = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        let next = next_run_after("cron", r#"{"cron": "0 * * * *"}"#, after).unwrap();
+        assert_eq!(next, Some(Utc.with_ymd_and_hms(2024, 6, 15, 15, 0, 0).unwrap()));
+
+        let mid_hour = Utc.with_ymd_and_hms(2024, 6, 15, 23, 42, 17).unwrap();
+        let next = next_run_after("cron", r#"{"cron": "0 * * * *"}"#, mid_hour).unwrap();
+        assert_eq!(next, Some(Utc.with_ymd_and_hms(2024, 6, 16, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_next_run_after_once_and_errors() {
+        let args = r#"{"run_at": "2024-06-15T14:00:00Z"}"#;
+        let before = Utc.with_ymd_and_hms(2024, 6, 15, 13, 0, 0).unwrap();
+        assert_eq!(
+            next_run_after("once", args, before).unwrap(),
+            Some(Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap())
+        );
+        let past = Utc.with_ymd_and_hms(2024, 6, 15, 14, 0, 0).unwrap();
+        assert_eq!(next_run_after("once", args, past).unwrap(), None);
+
+        assert!(next_run_after("cron", r#"{"cron": "bogus"}"#, past).is_err());
+        assert!(next_run_after("weekly", "{}", past).is_err());
+    }
+
     #[test]
     fn test_parse_cron_args_valid() {
         let result = parse_cron_args(r#"{"cron": "0 * * * *"}"#).unwrap();
@@ -284,6 +844,58 @@ mod tests {
         assert!(is_cron_due("* * * * *", &now));
     }
 
+    #[test]
+    fn test_is_cron_due_tz_negative_offset() {
+        // 09:00 in New York is 13:00 UTC in summer (EDT, UTC-4)
+        let ny = chrono_tz::America::New_York;
+        let summer = Utc.with_ymd_and_hms(2024, 6, 15, 13, 0, 0).unwrap();
+        assert!(is_cron_due_tz("0 9 * * *", &summer, ny));
+        assert!(!is_cron_due("0 9 * * *", &summer));
+
+        // ... and 14:00 UTC in winter (EST, UTC-5)
+        let winter = Utc.with_ymd_and_hms(2024, 1, 15, 14, 0, 0).unwrap();
+        assert!(is_cron_due_tz("0 9 * * *", &winter, ny));
+        assert!(!is_cron_due_tz("0 9 * * *", &summer.with_month(1).unwrap(), ny));
+    }
+
+    #[test]
+    fn test_is_cron_due_tz_positive_offset_crosses_date() {
+        // 08:00 Monday in Tokyo (UTC+9) is 23:00 Sunday UTC
+        let tokyo = chrono_tz::Asia::Tokyo;
+        let sunday_utc = Utc.with_ymd_and_hms(2024, 6, 16, 23, 0, 0).unwrap();
+        assert!(is_cron_due_tz("0 8 * * 1", &sunday_utc, tokyo));
+        assert!(!is_cron_due("0 8 * * 1", &sunday_utc));
+    }
+
+    #[test]
+    fn test_is_cron_due_tz_dst_transitions() {
+        let ny = chrono_tz::America::New_York;
+        // 2024-03-10: 02:00-02:59 local does not exist; nothing in that UTC
+        // window maps to 02:30
+        let fires_on = |expr: &str, day: DateTime<Utc>| {
+            (0..24 * 60)
+                .map(|m| day + chrono::Duration::minutes(m))
+                .filter(|t| is_cron_due_tz(expr, t, ny))
+                .count()
+        };
+        let skipped = fires_on("30 2 * * *", Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap());
+        assert_eq!(skipped, 0);
+
+        // 2024-11-03: 01:00-01:59 local happens twice; 01:30 fires twice
+        let repeated = fires_on("30 1 * * *", Utc.with_ymd_and_hms(2024, 11, 3, 0, 0, 0).unwrap());
+        assert_eq!(repeated, 2);
+    }
+
+    #[test]
+    fn test_cron_ran_this_minute() {
+        let ny = chrono_tz::America::New_York;
+        let at = |h, m, s| Utc.with_ymd_and_hms(2024, 11, 3, h, m, s).unwrap();
+        assert!(cron_ran_this_minute(&at(14, 30, 2), &at(14, 30, 59), ny));
+        assert!(!cron_ran_this_minute(&at(14, 29, 59), &at(14, 30, 0), ny));
+        // 01:30 local happens twice on the fall-back day; both runs count
+        assert!(!cron_ran_this_minute(&at(5, 30, 0), &at(6, 30, 0), ny));
+    }
+
     #[test]
     fn test_is_cron_due_hourly() {
         // Create a time where minute == 0
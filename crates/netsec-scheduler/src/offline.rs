@@ -0,0 +1,95 @@
+//! Offline sweep: devices that stop answering are marked `offline`.
+//!
+//! Scans only ever mark devices `online`, so without this sweep a device that
+//! leaves the network keeps its old status. The scheduler runs the sweep every
+//! tick when [`SchedulerConfig::offline_after`](crate::SchedulerConfig) is set.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use netsec_db::DbPool;
+use netsec_events::EventBus;
+use netsec_models::event::{EventType, NetsecEvent};
+use netsec_models::time::to_rfc3339;
+
+use crate::SchedulerResult;
+
+/// Mark `online` devices whose `last_seen` is more than `threshold` ago as
+/// `offline`, publishing a `DeviceOffline` event for each. Returns the number
+/// of devices that went offline.
+pub async fn mark_stale_devices_offline(
+    pool: &DbPool,
+    event_bus: &EventBus,
+    threshold: Duration,
+) -> SchedulerResult<u64> {
+    let cutoff = chrono::Duration::from_std(threshold)
+        .ok()
+        .and_then(|age| Utc::now().checked_sub_signed(age));
+    let Some(cutoff) = cutoff else {
+        return Ok(0);
+    };
+
+    let devices = netsec_db::repo::devices::mark_offline_before(pool, &to_rfc3339(&cutoff)).await?;
+    for device in &devices {
+        let event = NetsecEvent::new(
+            EventType::DeviceOffline,
+            serde_json::json!({
+                "device_id": device.id,
+                "ip": device.ip,
+                "last_seen": device.last_seen,
+            }),
+        );
+        let _ = event_bus.publish(event);
+    }
+    if !devices.is_empty() {
+        tracing::info!("Marked {} devices offline", devices.len());
+    }
+    Ok(devices.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netsec_db::{pool::create_test_pool, run_migrations};
+    use netsec_models::device::{Device, DeviceStatus};
+
+    async fn insert_device(pool: &DbPool, ip: &str, last_seen: &str) -> Device {
+        let mut device = Device::new(ip.to_string());
+        device.status = DeviceStatus::Online.as_str().to_string();
+        device.last_seen = last_seen.to_string();
+        netsec_db::repo::devices::insert(pool, &device).await.unwrap();
+        device
+    }
+
+    async fn status(pool: &DbPool, id: &str) -> String {
+        let device = netsec_db::repo::devices::get_by_id(pool, id).await.unwrap();
+        device.unwrap().status
+    }
+
+    #[tokio::test]
+    async fn test_sweep_marks_only_stale_devices() {
+        let pool = create_test_pool().await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        let fresh = insert_device(&pool, "10.0.0.1", &to_rfc3339(&Utc::now())).await;
+        let stale = insert_device(&pool, "10.0.0.2", "2020-01-01T00:00:00+00:00").await;
+
+        let threshold = Duration::from_secs(3600);
+        assert_eq!(mark_stale_devices_offline(&pool, &bus, threshold).await.unwrap(), 1);
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.event_type, EventType::DeviceOffline);
+        assert_eq!(event.payload["device_id"], stale.id.as_str());
+        assert_eq!(event.payload["ip"], "10.0.0.2");
+        assert!(rx.try_recv().is_err(), "expected exactly one event");
+
+        assert_eq!(status(&pool, &stale.id).await, "offline");
+        assert_eq!(status(&pool, &fresh.id).await, "online");
+
+        // Already offline: no repeat event
+        assert_eq!(mark_stale_devices_offline(&pool, &bus, threshold).await.unwrap(), 0);
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -3,7 +3,7 @@
 use std::time::Duration;
 
 use netsec_events::EventBus;
-use netsec_models::event::EventType;
+use netsec_models::event::{EventType, NetsecEvent};
 use netsec_models::plugin::{ScheduledJob, TriggerType};
 
 /// Insert an enabled interval job with 0s interval -> start scheduler -> receive event.
@@ -41,6 +41,8 @@ async fn test_scheduler_dispatches_due_job() {
     let payload = &event.payload;
     assert_eq!(payload["job_id"].as_str().unwrap(), job.id);
     assert_eq!(payload["task_type"].as_str().unwrap(), "discovery_scan");
+    // The dispatched payload passes event validation
+    assert!(NetsecEvent::new_validated(event.event_type.clone(), payload.clone()).is_ok());
 
     scheduler.shutdown();
     let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
@@ -81,3 +83,333 @@ async fn test_scheduler_skips_disabled_job() {
     scheduler.shutdown();
     let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
 }
+
+/// A long tick interval plus `reload()` after inserting a due job -> dispatch
+/// happens right away rather than at the next scheduled tick.
+#[tokio::test]
+async fn test_scheduler_reload_dispatches_before_next_tick() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe();
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_secs(3600),
+    );
+    let handle = scheduler.start();
+
+    // Job added "via the API" after the scheduler is already running
+    let mut job = ScheduledJob::new(TriggerType::Interval, "discovery_scan".to_string());
+    job.trigger_args = r#"{"interval_secs": 0}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    // Without a reload, nothing happens until the hourly tick
+    let idle = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await;
+    assert!(idle.is_err(), "no dispatch expected before reload");
+
+    scheduler.reload();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for reload dispatch")
+        .expect("recv error");
+    assert_eq!(event.event_type, EventType::ScanStarted);
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), job.id);
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+}
+
+/// A cron job dispatched by a tick is not fired again by a reload (e.g. after
+/// the job is saved in the GUI) or a later tick within the same minute.
+#[tokio::test]
+async fn test_scheduler_cron_fires_once_per_minute_across_reload() {
+    use chrono::Timelike;
+
+    // Leave room to finish before the minute turns over
+    let second = chrono::Utc::now().second();
+    if second > 45 {
+        tokio::time::sleep(Duration::from_secs(u64::from(61 - second))).await;
+    }
+
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let mut job = ScheduledJob::new(TriggerType::Cron, "discovery_scan".to_string());
+    job.trigger_args = r#"{"cron": "* * * * *"}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe();
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_millis(100),
+    );
+    let handle = scheduler.start();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for tick dispatch")
+        .expect("recv error");
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), job.id);
+
+    scheduler.reload();
+    let again = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+    assert!(again.is_err(), "cron job dispatched twice in one minute");
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+}
+
+/// A job that ran recently (e.g. before a restart) is not re-fired on the
+/// first tick; a due job is dispatched and its last run persisted.
+#[tokio::test]
+async fn test_scheduler_respects_persisted_last_run() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let mut recent = ScheduledJob::new(TriggerType::Interval, "recent_scan".to_string());
+    recent.trigger_args = r#"{"interval_secs": 3600}"#.to_string();
+    recent.last_run = Some(netsec_models::time::now_rfc3339());
+    netsec_db::repo::scheduled_jobs::insert(&pool, &recent)
+        .await
+        .unwrap();
+
+    let mut never_run = ScheduledJob::new(TriggerType::Interval, "first_scan".to_string());
+    never_run.trigger_args = r#"{"interval_secs": 3600}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &never_run)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe();
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_millis(50),
+    );
+    let handle = scheduler.start();
+
+    // Only the never-run job fires, once, over several ticks
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for scheduler event")
+        .expect("recv error");
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), never_run.id);
+
+    let more = tokio::time::timeout(Duration::from_millis(300), rx.recv()).await;
+    assert!(more.is_err(), "no further dispatch expected");
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+
+    let stored = netsec_db::repo::scheduled_jobs::get_by_id(&pool, &never_run.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.last_run.is_some());
+}
+
+/// A due one-time job fires once and is then disabled.
+#[tokio::test]
+async fn test_scheduler_once_job_fires_once_and_disables() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let mut job = ScheduledJob::new(TriggerType::Once, "full_scan".to_string());
+    job.trigger_args = r#"{"run_at": "2024-06-15T14:00:00Z"}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe();
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_millis(50),
+    );
+    let handle = scheduler.start();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for scheduler event")
+        .expect("recv error");
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), job.id);
+
+    let more = tokio::time::timeout(Duration::from_millis(300), rx.recv()).await;
+    assert!(more.is_err(), "one-time job must not repeat");
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+
+    let stored = netsec_db::repo::scheduled_jobs::get_by_id(&pool, &job.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!stored.enabled);
+    assert!(stored.last_run.is_some());
+}
+
+/// With `OverlapPolicy::Skip`, an always-due job is not re-dispatched while
+/// in flight, and is dispatched again once its completion is observed.
+#[tokio::test]
+async fn test_scheduler_skips_in_flight_job() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let mut job = ScheduledJob::new(TriggerType::Interval, "discovery_scan".to_string());
+    job.trigger_args = r#"{"interval_secs": 0}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe_filtered(vec![EventType::ScanStarted]);
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_millis(50),
+    )
+    .with_overlap_policy(netsec_scheduler::OverlapPolicy::Skip);
+    let handle = scheduler.start();
+
+    let first = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for first dispatch")
+        .expect("recv error");
+    assert_eq!(first.payload["job_id"].as_str().unwrap(), job.id);
+
+    // Several ticks pass while the job is still running
+    let overlap = tokio::time::timeout(Duration::from_millis(300), rx.recv()).await;
+    assert!(overlap.is_err(), "in-flight job must not be re-dispatched");
+
+    bus.publish(NetsecEvent::new(
+        EventType::ScanCompleted,
+        serde_json::json!({"job_id": job.id}),
+    ))
+    .unwrap();
+
+    let again = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for dispatch after completion")
+        .expect("recv error");
+    assert_eq!(again.payload["job_id"].as_str().unwrap(), job.id);
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+}
+
+/// `trigger_now` dispatches a job whose trigger is not due, and rejects
+/// unknown or disabled jobs.
+#[tokio::test]
+async fn test_scheduler_trigger_now() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    // Ran just now with an hourly interval: not due for a long time
+    let mut job = ScheduledJob::new(TriggerType::Interval, "discovery_scan".to_string());
+    job.trigger_args = r#"{"interval_secs": 3600}"#.to_string();
+    job.last_run = Some(netsec_models::time::now_rfc3339());
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    let mut disabled = ScheduledJob::new(TriggerType::Interval, "full_scan".to_string());
+    disabled.enabled = false;
+    netsec_db::repo::scheduled_jobs::insert(&pool, &disabled)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe();
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_secs(3600),
+    );
+
+    // Not started yet
+    assert!(matches!(
+        scheduler.trigger_now(&job.id).await,
+        Err(netsec_scheduler::SchedulerError::NotRunning)
+    ));
+
+    let handle = scheduler.start();
+    scheduler.trigger_now(&job.id).await.unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timeout waiting for manual dispatch")
+        .expect("recv error");
+    assert_eq!(event.event_type, EventType::ScanStarted);
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), job.id);
+
+    for id in ["missing", disabled.id.as_str()] {
+        assert!(matches!(
+            scheduler.trigger_now(id).await,
+            Err(netsec_scheduler::SchedulerError::JobNotFound(_))
+        ));
+    }
+
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+}
+
+/// Completions arriving faster than the tick interval must not postpone ticks.
+#[tokio::test]
+async fn test_scheduler_ticks_under_steady_completions() {
+    let pool = netsec_db::pool::create_test_pool().await.unwrap();
+    netsec_db::run_migrations(&pool).await.unwrap();
+
+    let mut job = ScheduledJob::new(TriggerType::Interval, "discovery_scan".to_string());
+    job.trigger_args = r#"{"interval_secs": 0}"#.to_string();
+    netsec_db::repo::scheduled_jobs::insert(&pool, &job)
+        .await
+        .unwrap();
+
+    let bus = EventBus::new();
+    let mut rx = bus.subscribe_filtered(vec![EventType::ScanStarted]);
+
+    let scheduler = netsec_scheduler::Scheduler::new(
+        pool.clone(),
+        bus.clone(),
+        Duration::from_millis(200),
+    )
+    .with_overlap_policy(netsec_scheduler::OverlapPolicy::Skip);
+    let handle = scheduler.start();
+
+    // Completions of some other job every 20ms
+    let flood_bus = bus.clone();
+    let flood = tokio::spawn(async move {
+        loop {
+            let _ = flood_bus.publish(NetsecEvent::new(
+                EventType::ScanCompleted,
+                serde_json::json!({"job_id": "other"}),
+            ));
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    });
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("tick postponed by completions")
+        .expect("recv error");
+    assert_eq!(event.payload["job_id"].as_str().unwrap(), job.id);
+
+    flood.abort();
+    scheduler.shutdown();
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+}
@@ -4,11 +4,14 @@
 //! and detects:
 //! - MAC address changes for a known IP (potential MITM)
 //! - Multiple IPs claiming the same MAC (potential gateway spoof)
+//!
+//! [`ArpBindingTable`] does the same for a stream of bindings seen on the wire,
+//! flagging MAC flapping and gateway takeovers within a time window.
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::sync::Mutex;
 use tracing::warn;
 
@@ -22,6 +25,221 @@ pub struct ArpEntry {
     pub device: String,
 }
 
+/// An IP↔MAC binding seen on the wire (ARP reply, gratuitous ARP, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArpEvent {
+    pub ip: String,
+    pub mac: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// What kind of spoofing an [`ArpSpoofFinding`] reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArpSpoofKind {
+    /// The IP alternated between MACs within the flap window.
+    MacFlapping,
+    /// A configured gateway IP is now answered by a different MAC.
+    GatewayMacChanged,
+    /// One MAC is answering for several gateway IPs at once.
+    GatewayClaim { mac: String, gateways: Vec<String> },
+}
+
+/// A suspected ARP spoof with the competing MACs and the time span it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArpSpoofFinding {
+    pub kind: ArpSpoofKind,
+    pub ip: String,
+    /// Every MAC involved, oldest binding first (for `GatewayClaim`, the
+    /// claiming MAC first).
+    pub macs: Vec<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Tuning for [`ArpBindingTable`].
+#[derive(Debug, Clone)]
+pub struct ArpSpoofConfig {
+    /// Bindings kept per IP; older ones are forgotten.
+    pub history_depth: usize,
+    /// How far back MAC changes count towards flapping.
+    pub flap_window: Duration,
+    /// MAC changes within the window that make an IP flapping (A→B→A is 2).
+    pub flap_min_changes: usize,
+    /// Gateway IPs whose MAC should never change.
+    pub gateways: Vec<String>,
+}
+
+impl Default for ArpSpoofConfig {
+    fn default() -> Self {
+        Self {
+            history_depth: 16,
+            flap_window: Duration::seconds(60),
+            flap_min_changes: 2,
+            gateways: Vec::new(),
+        }
+    }
+}
+
+/// A run of consecutive sightings of one MAC for an IP.
+#[derive(Debug, Clone)]
+struct ArpBinding {
+    mac: String,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+/// IP → recent MAC bindings, fed from a stream of [`ArpEvent`]s.
+pub struct ArpBindingTable {
+    config: ArpSpoofConfig,
+    bindings: HashMap<String, VecDeque<ArpBinding>>,
+}
+
+impl ArpBindingTable {
+    pub fn new(config: ArpSpoofConfig) -> Self {
+        Self {
+            config,
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Record a batch of events (in time order) and return what they reveal.
+    ///
+    /// Each IP yields at most one finding of each kind per batch.
+    pub fn observe(&mut self, events: &[ArpEvent]) -> Vec<ArpSpoofFinding> {
+        // Gateway IP → (MAC before the first change, first change, last change)
+        let mut gateway_changes: BTreeMap<String, (String, DateTime<Utc>, DateTime<Utc>)> =
+            BTreeMap::new();
+        let mut touched = BTreeSet::new();
+
+        for event in events {
+            let mac = event.mac.to_lowercase();
+            let history = self.bindings.entry(event.ip.clone()).or_default();
+            match history.back_mut() {
+                Some(last) if last.mac == mac => last.last_seen = event.timestamp,
+                last => {
+                    if let Some(last) = last {
+                        if self.config.gateways.contains(&event.ip) {
+                            gateway_changes
+                                .entry(event.ip.clone())
+                                .and_modify(|c| c.2 = event.timestamp)
+                                .or_insert((last.mac.clone(), last.last_seen, event.timestamp));
+                        }
+                    }
+                    history.push_back(ArpBinding {
+                        mac,
+                        first_seen: event.timestamp,
+                        last_seen: event.timestamp,
+                    });
+                    while history.len() > self.config.history_depth.max(1) {
+                        history.pop_front();
+                    }
+                }
+            }
+            touched.insert(event.ip.clone());
+        }
+
+        let mut findings = Vec::new();
+        for ip in &touched {
+            findings.extend(self.flapping(ip));
+            if let Some((old_mac, first, last)) = gateway_changes.remove(ip) {
+                let mut macs = vec![old_mac];
+                for b in self.history_since(ip, first) {
+                    if !macs.contains(&b.mac) {
+                        macs.push(b.mac.clone());
+                    }
+                }
+                findings.push(ArpSpoofFinding {
+                    kind: ArpSpoofKind::GatewayMacChanged,
+                    ip: ip.clone(),
+                    macs,
+                    first_seen: first,
+                    last_seen: last,
+                });
+            }
+        }
+        findings.extend(self.gateway_claims(&touched));
+        findings
+    }
+
+    /// Bindings for `ip` still current at or after `since`.
+    fn history_since<'a>(
+        &'a self,
+        ip: &str,
+        since: DateTime<Utc>,
+    ) -> impl Iterator<Item = &'a ArpBinding> + 'a {
+        self.bindings
+            .get(ip)
+            .into_iter()
+            .flatten()
+            .filter(move |b| b.last_seen >= since)
+    }
+
+    fn flapping(&self, ip: &str) -> Option<ArpSpoofFinding> {
+        let latest = self.bindings.get(ip)?.back()?.last_seen;
+        let recent: Vec<&ArpBinding> =
+            self.history_since(ip, latest - self.config.flap_window).collect();
+        if recent.len() - 1 < self.config.flap_min_changes.max(1) {
+            return None;
+        }
+        let mut macs: Vec<String> = Vec::new();
+        for b in &recent {
+            if !macs.contains(&b.mac) {
+                macs.push(b.mac.clone());
+            }
+        }
+        Some(ArpSpoofFinding {
+            kind: ArpSpoofKind::MacFlapping,
+            ip: ip.to_string(),
+            macs,
+            first_seen: recent[0].first_seen,
+            last_seen: latest,
+        })
+    }
+
+    /// MACs currently bound to more than one gateway, where one of those
+    /// gateways was seen in this batch.
+    fn gateway_claims(&self, touched: &BTreeSet<String>) -> Vec<ArpSpoofFinding> {
+        let mut by_mac: BTreeMap<&str, Vec<(&String, &ArpBinding)>> = BTreeMap::new();
+        for gateway in &self.config.gateways {
+            if let Some(current) = self.bindings.get(gateway).and_then(|h| h.back()) {
+                by_mac.entry(&current.mac).or_default().push((gateway, current));
+            }
+        }
+
+        by_mac
+            .into_iter()
+            .filter(|(_, claims)| claims.len() > 1)
+            .filter(|(_, claims)| claims.iter().any(|(ip, _)| touched.contains(*ip)))
+            .map(|(mac, claims)| {
+                let mut macs = vec![mac.to_string()];
+                for (ip, _) in &claims {
+                    for b in self.bindings.get(*ip).into_iter().flatten() {
+                        if !macs.contains(&b.mac) {
+                            macs.push(b.mac.clone());
+                        }
+                    }
+                }
+                ArpSpoofFinding {
+                    kind: ArpSpoofKind::GatewayClaim {
+                        mac: mac.to_string(),
+                        gateways: claims.iter().map(|(ip, _)| (*ip).clone()).collect(),
+                    },
+                    ip: claims[0].0.clone(),
+                    macs,
+                    first_seen: claims.iter().map(|(_, b)| b.first_seen).min().unwrap_or_default(),
+                    last_seen: claims.iter().map(|(_, b)| b.last_seen).max().unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Detect ARP spoofing in a stream of bindings using the default
+/// [`ArpSpoofConfig`] (flapping only, since no gateways are configured).
+pub fn detect_arp_spoofing(events: &[ArpEvent]) -> Vec<ArpSpoofFinding> {
+    ArpBindingTable::new(ArpSpoofConfig::default()).observe(events)
+}
+
 /// Detector that watches the ARP table for spoofing indicators.
 pub struct ArpSpoofDetector {
     /// Previous snapshot: IP → MAC
@@ -287,6 +505,102 @@ mypc (192.168.1.50) at aa:bb:cc:dd:ee:02 on en0 ifscope [ethernet]
         assert!(alerts.is_empty());
     }
 
+    fn ev(ip: &str, mac: &str, secs: i64) -> ArpEvent {
+        ArpEvent {
+            ip: ip.into(),
+            mac: mac.into(),
+            timestamp: DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_detect_mac_flapping() {
+        let findings = detect_arp_spoofing(&[
+            ev("10.0.0.7", "aa:aa:aa:aa:aa:01", 0),
+            ev("10.0.0.7", "AA:AA:AA:AA:AA:01", 5),
+            ev("10.0.0.7", "bb:bb:bb:bb:bb:02", 10),
+            ev("10.0.0.7", "aa:aa:aa:aa:aa:01", 15),
+            ev("10.0.0.8", "cc:cc:cc:cc:cc:03", 15),
+        ]);
+        assert_eq!(findings.len(), 1);
+        let f = &findings[0];
+        assert_eq!(f.kind, ArpSpoofKind::MacFlapping);
+        assert_eq!(f.ip, "10.0.0.7");
+        assert_eq!(f.macs, vec!["aa:aa:aa:aa:aa:01", "bb:bb:bb:bb:bb:02"]);
+        assert_eq!(f.first_seen, ev("", "", 0).timestamp);
+        assert_eq!(f.last_seen, ev("", "", 15).timestamp);
+    }
+
+    #[test]
+    fn test_no_flapping_outside_window() {
+        // One change a minute apart twice is a slow move, not a flap
+        let findings = detect_arp_spoofing(&[
+            ev("10.0.0.7", "aa:aa:aa:aa:aa:01", 0),
+            ev("10.0.0.7", "bb:bb:bb:bb:bb:02", 90),
+            ev("10.0.0.7", "cc:cc:cc:cc:cc:03", 180),
+        ]);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_detect_gateway_takeover() {
+        let mut table = ArpBindingTable::new(ArpSpoofConfig {
+            gateways: vec!["192.168.1.1".into(), "192.168.2.1".into()],
+            ..Default::default()
+        });
+        let learned = table.observe(&[
+            ev("192.168.1.1", "aa:bb:cc:dd:ee:01", 0),
+            ev("192.168.2.1", "aa:bb:cc:dd:ee:02", 0),
+        ]);
+        assert!(learned.is_empty());
+
+        // The attacker answers for both gateways
+        let findings = table.observe(&[
+            ev("192.168.1.1", "66:66:66:66:66:66", 300),
+            ev("192.168.2.1", "66:66:66:66:66:66", 301),
+        ]);
+        let changed: Vec<_> = findings
+            .iter()
+            .filter(|f| f.kind == ArpSpoofKind::GatewayMacChanged)
+            .collect();
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0].ip, "192.168.1.1");
+        assert_eq!(changed[0].macs, vec!["aa:bb:cc:dd:ee:01", "66:66:66:66:66:66"]);
+        assert_eq!(changed[0].first_seen, ev("", "", 0).timestamp);
+        assert_eq!(changed[0].last_seen, ev("", "", 300).timestamp);
+
+        let claim = findings
+            .iter()
+            .find(|f| matches!(f.kind, ArpSpoofKind::GatewayClaim { .. }))
+            .expect("gateway claim finding");
+        assert_eq!(
+            claim.kind,
+            ArpSpoofKind::GatewayClaim {
+                mac: "66:66:66:66:66:66".into(),
+                gateways: vec!["192.168.1.1".into(), "192.168.2.1".into()],
+            }
+        );
+        assert_eq!(claim.macs[0], "66:66:66:66:66:66");
+        assert_eq!(findings.len(), 3);
+    }
+
+    #[test]
+    fn test_history_depth_bounds_table() {
+        let mut table = ArpBindingTable::new(ArpSpoofConfig {
+            history_depth: 2,
+            flap_min_changes: 2,
+            ..Default::default()
+        });
+        let findings = table.observe(&[
+            ev("10.0.0.7", "aa:aa:aa:aa:aa:01", 0),
+            ev("10.0.0.7", "bb:bb:bb:bb:bb:02", 1),
+            ev("10.0.0.7", "aa:aa:aa:aa:aa:01", 2),
+        ]);
+        // Only two bindings are remembered, so just one change is visible
+        assert!(findings.is_empty());
+        assert_eq!(table.bindings["10.0.0.7"].len(), 2);
+    }
+
     #[test]
     fn test_name() {
         let d = ArpSpoofDetector::new();
@@ -8,9 +8,10 @@
 use async_trait::async_trait;
 use chrono::Utc;
 use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::warn;
 
+use crate::entropy::shannon_entropy;
 use crate::{ThreatDetector, ThreatResult};
 
 /// A DNS query record (from Zeek dns.log or system DNS logs).
@@ -23,6 +24,51 @@ pub struct DnsQueryRecord {
     pub answer: Option<String>,
 }
 
+/// A DNS query, as consumed by [`detect_dns_tunneling`]. `timestamp` is
+/// seconds since the epoch (Zeek's `ts`).
+pub type DnsQuery = DnsQueryRecord;
+
+/// A DNS tunneling heuristic that fired for a domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelIndicator {
+    LongLabels,
+    HighSubdomainRate,
+    HighLabelEntropy,
+    TxtNullRatio,
+}
+
+impl TunnelIndicator {
+    /// Contribution to a finding's score; all indicators sum to 1.0.
+    fn weight(self) -> f64 {
+        match self {
+            TunnelIndicator::LongLabels => 0.3,
+            TunnelIndicator::HighSubdomainRate => 0.25,
+            TunnelIndicator::HighLabelEntropy => 0.3,
+            TunnelIndicator::TxtNullRatio => 0.15,
+        }
+    }
+}
+
+/// A registered domain whose queries look like a DNS tunnel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovertChannelFinding {
+    pub domain: String,
+    /// Likelihood of tunneling, 0.0–1.0 (sum of indicator weights).
+    pub score: f64,
+    pub indicators: Vec<TunnelIndicator>,
+    pub query_count: usize,
+    pub unique_subdomains: usize,
+    /// Average length of the leftmost label, where the payload usually sits.
+    pub avg_label_len: usize,
+    pub subdomains_per_sec: f64,
+    pub label_entropy: f64,
+    pub txt_ratio: f64,
+}
+
+/// Score at or above which a domain is reported as a tunnel; no single
+/// indicator is enough on its own.
+const TUNNEL_SCORE_THRESHOLD: f64 = 0.5;
+
 /// Configuration for covert channel detection thresholds.
 #[derive(Debug, Clone)]
 pub struct CovertChannelConfig {
@@ -34,6 +80,14 @@ pub struct CovertChannelConfig {
     pub icmp_max_payload_bytes: usize,
     /// Path to Zeek dns.log (if available).
     pub zeek_dns_log: Option<String>,
+    /// Queries a domain needs before [`detect_dns_tunneling`] scores it.
+    pub dns_min_queries: usize,
+    /// Max distinct subdomains per second under one registered domain.
+    pub dns_max_subdomains_per_sec: f64,
+    /// Max average Shannon entropy (bits/char) of the leftmost label.
+    pub dns_max_label_entropy: f64,
+    /// Max share of TXT/NULL queries to a domain.
+    pub dns_max_txt_ratio: f64,
 }
 
 impl Default for CovertChannelConfig {
@@ -43,6 +97,10 @@ impl Default for CovertChannelConfig {
             dns_max_queries_per_domain: 100,
             icmp_max_payload_bytes: 64,
             zeek_dns_log: None,
+            dns_min_queries: 10,
+            dns_max_subdomains_per_sec: 2.0,
+            dns_max_label_entropy: 3.5,
+            dns_max_txt_ratio: 0.5,
         }
     }
 }
@@ -175,6 +233,97 @@ impl CovertChannelDetector {
         alerts
     }
 
+    /// Score each registered domain for DNS tunneling and return those at or
+    /// above [`TUNNEL_SCORE_THRESHOLD`], highest score first.
+    pub fn find_dns_tunnels(&self, queries: &[DnsQuery]) -> Vec<CovertChannelFinding> {
+        let mut by_domain: HashMap<String, Vec<&DnsQuery>> = HashMap::new();
+        for q in queries {
+            by_domain.entry(extract_base_domain(&q.query)).or_default().push(q);
+        }
+
+        let mut findings: Vec<CovertChannelFinding> = by_domain
+            .into_iter()
+            .filter(|(_, group)| group.len() >= self.config.dns_min_queries)
+            .filter_map(|(domain, group)| self.score_domain(domain, &group))
+            .collect();
+        findings.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.domain.cmp(&b.domain)));
+        findings
+    }
+
+    fn score_domain(&self, domain: String, group: &[&DnsQuery]) -> Option<CovertChannelFinding> {
+        let subdomains: HashSet<&str> = group
+            .iter()
+            .filter_map(|q| subdomain_part(&q.query))
+            .collect();
+
+        // Without parseable timestamps the whole batch counts as one second
+        let times: Vec<f64> = group.iter().filter_map(|q| q.timestamp.parse().ok()).collect();
+        let span = match (
+            times.iter().copied().reduce(f64::min),
+            times.iter().copied().reduce(f64::max),
+        ) {
+            (Some(first), Some(last)) => (last - first).max(1.0),
+            _ => 1.0,
+        };
+        let subdomains_per_sec = subdomains.len() as f64 / span;
+
+        let leftmost: Vec<&str> = group
+            .iter()
+            .filter_map(|q| subdomain_part(&q.query))
+            .filter_map(|s| s.split('.').next())
+            .collect();
+        let label_entropy = if leftmost.is_empty() {
+            0.0
+        } else {
+            leftmost.iter().map(|l| shannon_entropy(l.as_bytes())).sum::<f64>()
+                / leftmost.len() as f64
+        };
+
+        let txt = group
+            .iter()
+            .filter(|q| matches!(q.qtype.as_str(), "TXT" | "16" | "NULL" | "10"))
+            .count();
+        let txt_ratio = txt as f64 / group.len() as f64;
+        let avg_label_len = leftmost
+            .iter()
+            .map(|l| l.len())
+            .sum::<usize>()
+            .checked_div(leftmost.len())
+            .unwrap_or(0);
+
+        let indicators: Vec<TunnelIndicator> = [
+            (avg_label_len > self.config.dns_max_avg_label_len, TunnelIndicator::LongLabels),
+            (
+                subdomains_per_sec > self.config.dns_max_subdomains_per_sec,
+                TunnelIndicator::HighSubdomainRate,
+            ),
+            (
+                label_entropy > self.config.dns_max_label_entropy,
+                TunnelIndicator::HighLabelEntropy,
+            ),
+            (txt_ratio > self.config.dns_max_txt_ratio, TunnelIndicator::TxtNullRatio),
+        ]
+        .into_iter()
+        .filter_map(|(hit, indicator)| hit.then_some(indicator))
+        .collect();
+        let score: f64 = indicators.iter().map(|i| i.weight()).sum();
+        if score < TUNNEL_SCORE_THRESHOLD {
+            return None;
+        }
+
+        Some(CovertChannelFinding {
+            domain,
+            score,
+            indicators,
+            query_count: group.len(),
+            unique_subdomains: subdomains.len(),
+            avg_label_len,
+            subdomains_per_sec,
+            label_entropy,
+            txt_ratio,
+        })
+    }
+
     /// Read and analyze Zeek DNS logs if available.
     fn analyze_zeek_logs(&self) -> Vec<NormalizedAlert> {
         let path = match &self.config.zeek_dns_log {
@@ -220,6 +369,22 @@ impl ThreatDetector for CovertChannelDetector {
     }
 }
 
+/// Detect DNS tunneling with the default [`CovertChannelConfig`].
+///
+/// Queries are aggregated per registered domain (last two labels) and scored
+/// on label length, distinct subdomains per second, leftmost-label entropy
+/// and the share of TXT/NULL queries.
+pub fn detect_dns_tunneling(queries: &[DnsQuery]) -> Vec<CovertChannelFinding> {
+    CovertChannelDetector::new().find_dns_tunnels(queries)
+}
+
+/// The labels in front of the base domain, if any.
+fn subdomain_part(fqdn: &str) -> Option<&str> {
+    let fqdn = fqdn.trim_end_matches('.');
+    let base = extract_base_domain(fqdn);
+    fqdn.strip_suffix(base.as_str())?.strip_suffix('.')
+}
+
 /// Extract the base domain (last 2 labels) from a FQDN.
 fn extract_base_domain(fqdn: &str) -> String {
     let labels: Vec<&str> = fqdn.trim_end_matches('.').split('.').collect();
@@ -369,6 +534,66 @@ mod tests {
         assert_eq!(records[0].qtype, "A");
     }
 
+    fn query(ts: f64, name: &str, qtype: &str) -> DnsQuery {
+        DnsQuery {
+            timestamp: format!("{ts:.6}"),
+            src_ip: "192.168.1.100".into(),
+            query: name.into(),
+            qtype: qtype.into(),
+            answer: None,
+        }
+    }
+
+    #[test]
+    fn test_subdomain_part() {
+        assert_eq!(subdomain_part("a.b.example.com."), Some("a.b"));
+        assert_eq!(subdomain_part("example.com"), None);
+    }
+
+    #[test]
+    fn test_detect_dns_tunneling_only_flags_tunnel() {
+        let mut queries = Vec::new();
+        // iodine/dnscat-style: hex-encoded chunks under one apex, 10/s, mostly TXT
+        let mut x: u64 = 0x1234_5678_9ABC_DEF0;
+        for i in 0..200 {
+            let mut label = String::new();
+            for _ in 0..4 {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                label.push_str(&format!("{:010x}", x & 0xFF_FFFF_FFFF));
+            }
+            let qtype = if i % 4 == 0 { "A" } else { "TXT" };
+            let ts = 1_700_000_000.0 + i as f64 / 10.0;
+            queries.push(query(ts, &format!("{label}.t.evil.com"), qtype));
+        }
+        // Ordinary browsing over the same 20 seconds
+        let sites = ["www.google.com", "mail.google.com", "www.github.com", "api.github.com"];
+        for i in 0..80 {
+            let name = sites[i % sites.len()];
+            let qtype = if i % 2 == 0 { "A" } else { "AAAA" };
+            queries.push(query(1_700_000_000.0 + i as f64 / 4.0, name, qtype));
+        }
+
+        let findings = detect_dns_tunneling(&queries);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        let f = &findings[0];
+        assert_eq!(f.domain, "evil.com");
+        assert_eq!(f.query_count, 200);
+        assert_eq!(f.unique_subdomains, 200);
+        assert!(f.label_entropy > 3.5);
+        assert!(f.indicators.contains(&TunnelIndicator::LongLabels));
+        assert!(f.indicators.contains(&TunnelIndicator::HighSubdomainRate));
+        assert!(f.indicators.contains(&TunnelIndicator::TxtNullRatio));
+        assert!((f.score - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_detect_dns_tunneling_ignores_few_queries() {
+        let queries = vec![query(0.0, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.evil.com", "TXT")];
+        assert!(detect_dns_tunneling(&queries).is_empty());
+    }
+
     #[test]
     fn test_name() {
         let d = CovertChannelDetector::new();
@@ -2,11 +2,12 @@
 //!
 //! Detects DNS poisoning by resolving known-good domains and comparing
 //! results against expected IPs or trusted resolvers (Quad9, Cloudflare).
+//! [`detect_dns_hijack`] compares captured answers against a trusted baseline.
 
 use async_trait::async_trait;
 use chrono::Utc;
 use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::net::ToSocketAddrs;
 use tracing::warn;
 
@@ -21,6 +22,113 @@ pub struct DnsCanary {
     pub expected_prefixes: Vec<String>,
 }
 
+/// A DNS response as seen by a resolver: the answers for one query name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsAnswer {
+    pub query: String,
+    /// Resolved addresses; empty for NXDOMAIN or a no-data answer.
+    pub answers: Vec<String>,
+    /// Smallest TTL across the answer records, in seconds.
+    pub ttl: u32,
+}
+
+/// Why an observed answer diverges from the trusted baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DnsHijackKind {
+    /// The name resolved to addresses the trusted resolver never returned.
+    AnswerSubstitution,
+    /// The trusted resolver says NXDOMAIN but an address came back instead,
+    /// as captive portals and ad-injecting resolvers do.
+    NxdomainRewrite,
+    /// A normally long-lived record came back with a very short TTL.
+    LowTtl,
+}
+
+/// A query whose observed answers diverge from the baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsHijackFinding {
+    pub query: String,
+    pub kind: DnsHijackKind,
+    /// Observed answers outside the baseline (every observed answer for
+    /// `LowTtl`).
+    pub divergent: Vec<String>,
+    /// What the trusted resolver returned.
+    pub baseline: Vec<String>,
+    pub observed_ttl: u32,
+    pub baseline_ttl: u32,
+}
+
+/// Baseline TTL (seconds) from which a domain counts as normally stable.
+const STABLE_TTL_SECS: u32 = 300;
+
+/// Observed TTL (seconds) at or below which a stable domain is suspicious.
+const LOW_TTL_SECS: u32 = 30;
+
+/// Answers for one query name, merged across responses.
+struct MergedAnswer {
+    answers: BTreeSet<String>,
+    ttl: u32,
+}
+
+fn merge_answers(answers: &[DnsAnswer]) -> BTreeMap<String, MergedAnswer> {
+    let mut merged: BTreeMap<String, MergedAnswer> = BTreeMap::new();
+    for a in answers {
+        let query = a.query.trim_end_matches('.').to_lowercase();
+        let entry = merged.entry(query).or_insert(MergedAnswer {
+            answers: BTreeSet::new(),
+            ttl: a.ttl,
+        });
+        entry.answers.extend(a.answers.iter().cloned());
+        entry.ttl = entry.ttl.min(a.ttl);
+    }
+    merged
+}
+
+/// Compare answers seen on the network against those from a trusted resolver.
+///
+/// Query names are matched case-insensitively, ignoring a trailing dot, and
+/// names absent from `baseline` are skipped. Each name yields at most one
+/// finding per [`DnsHijackKind`].
+pub fn detect_dns_hijack(observed: &[DnsAnswer], baseline: &[DnsAnswer]) -> Vec<DnsHijackFinding> {
+    let baseline = merge_answers(baseline);
+    let mut findings = Vec::new();
+
+    for (query, seen) in merge_answers(observed) {
+        let Some(expected) = baseline.get(&query) else {
+            continue;
+        };
+        let finding = |kind, divergent: Vec<String>| DnsHijackFinding {
+            query: query.clone(),
+            kind,
+            divergent,
+            baseline: expected.answers.iter().cloned().collect(),
+            observed_ttl: seen.ttl,
+            baseline_ttl: expected.ttl,
+        };
+
+        if expected.answers.is_empty() {
+            if !seen.answers.is_empty() {
+                let divergent = seen.answers.iter().cloned().collect();
+                findings.push(finding(DnsHijackKind::NxdomainRewrite, divergent));
+            }
+            continue;
+        }
+
+        let divergent: Vec<String> = seen.answers.difference(&expected.answers).cloned().collect();
+        if !divergent.is_empty() {
+            findings.push(finding(DnsHijackKind::AnswerSubstitution, divergent));
+        }
+        if !seen.answers.is_empty()
+            && expected.ttl >= STABLE_TTL_SECS
+            && seen.ttl <= LOW_TTL_SECS
+        {
+            let answers = seen.answers.iter().cloned().collect();
+            findings.push(finding(DnsHijackKind::LowTtl, answers));
+        }
+    }
+    findings
+}
+
 /// DNS hijack detector configuration.
 pub struct DnsHijackDetector {
     canaries: Vec<DnsCanary>,
@@ -244,6 +352,64 @@ mod tests {
         assert!(canaries.iter().any(|c| c.domain == "dns.google"));
     }
 
+    fn answer(query: &str, answers: &[&str], ttl: u32) -> DnsAnswer {
+        DnsAnswer {
+            query: query.into(),
+            answers: answers.iter().map(|a| a.to_string()).collect(),
+            ttl,
+        }
+    }
+
+    #[test]
+    fn test_detect_answer_substitution() {
+        let baseline = vec![
+            answer("bank.example", &["203.0.113.10", "203.0.113.11"], 3600),
+            answer("news.example", &["198.51.100.5"], 600),
+        ];
+        let observed = vec![
+            answer("BANK.example.", &["203.0.113.10", "192.168.1.66"], 3600),
+            answer("news.example", &["198.51.100.5"], 600),
+            answer("unknown.example", &["10.0.0.1"], 60),
+        ];
+        let findings = detect_dns_hijack(&observed, &baseline);
+        assert_eq!(findings.len(), 1);
+        let f = &findings[0];
+        assert_eq!(f.query, "bank.example");
+        assert_eq!(f.kind, DnsHijackKind::AnswerSubstitution);
+        assert_eq!(f.divergent, vec!["192.168.1.66"]);
+        assert_eq!(f.baseline, vec!["203.0.113.10", "203.0.113.11"]);
+    }
+
+    #[test]
+    fn test_detect_nxdomain_rewrite() {
+        let baseline = vec![answer("no-such-host.example", &[], 900)];
+        let observed = vec![answer("no-such-host.example", &["10.10.10.10"], 0)];
+        let findings = detect_dns_hijack(&observed, &baseline);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, DnsHijackKind::NxdomainRewrite);
+        assert_eq!(findings[0].divergent, vec!["10.10.10.10"]);
+        assert!(findings[0].baseline.is_empty());
+
+        // Both saying NXDOMAIN is fine
+        let observed = vec![answer("no-such-host.example", &[], 900)];
+        assert!(detect_dns_hijack(&observed, &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_detect_low_ttl() {
+        let baseline = vec![answer("cdn.example", &["203.0.113.7"], 86400)];
+        let observed = vec![answer("cdn.example", &["203.0.113.7"], 5)];
+        let findings = detect_dns_hijack(&observed, &baseline);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, DnsHijackKind::LowTtl);
+        assert_eq!(findings[0].observed_ttl, 5);
+        assert_eq!(findings[0].baseline_ttl, 86400);
+
+        // A domain that's short-lived anyway isn't flagged
+        let baseline = vec![answer("cdn.example", &["203.0.113.7"], 20)];
+        assert!(detect_dns_hijack(&observed, &baseline).is_empty());
+    }
+
     #[test]
     fn test_name() {
         let d = DnsHijackDetector::new();
@@ -4,10 +4,14 @@
 //! - Encrypted C2 channels (high entropy, regular intervals)
 //! - Compressed data exfiltration
 //! - Cryptojacking / mining traffic (Stratum protocol on known ports)
+//!
+//! The entropy helpers ([`shannon_entropy`], [`analyze_payload_entropy`],
+//! [`window_entropy`]) are usable on their own for tunneling detection.
 
 use async_trait::async_trait;
 use chrono::Utc;
 use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
+use std::ops::Range;
 use tracing::warn;
 
 use crate::{ThreatDetector, ThreatResult};
@@ -60,6 +64,103 @@ impl Default for EntropyConfig {
     }
 }
 
+/// Calculate Shannon entropy of a byte slice in bits per byte (0.0 - 8.0).
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    let mut freq = [0u64; 256];
+    for &b in data {
+        freq[b as usize] += 1;
+    }
+    entropy_from_counts(&freq, data.len())
+}
+
+fn entropy_from_counts(freq: &[u64; 256], len: usize) -> f64 {
+    if len == 0 {
+        return 0.0;
+    }
+    let len = len as f64;
+    let mut entropy = 0.0;
+    for &count in freq {
+        if count > 0 {
+            let p = count as f64 / len;
+            entropy -= p * p.log2();
+        }
+    }
+    entropy
+}
+
+/// A payload whose entropy exceeded the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntropyFinding {
+    /// Position of the payload in the analyzed slice.
+    pub index: usize,
+    pub entropy: f64,
+    pub len: usize,
+}
+
+/// Flag payloads whose per-byte entropy is above `threshold`.
+///
+/// Encrypted or compressed data sits near 8.0; plain protocols are well below
+/// 6.0, so a threshold around 7.0 singles out likely tunnels. Note that a
+/// payload shorter than 256 bytes can't exceed `log2(len)`.
+pub fn analyze_payload_entropy(payloads: &[Vec<u8>], threshold: f64) -> Vec<EntropyFinding> {
+    payloads
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.is_empty())
+        .filter_map(|(index, p)| {
+            let entropy = shannon_entropy(p);
+            (entropy > threshold).then_some(EntropyFinding {
+                index,
+                entropy,
+                len: p.len(),
+            })
+        })
+        .collect()
+}
+
+/// Entropy of each `window`-byte slice of `data`, advancing `step` bytes at a
+/// time, as `(offset, entropy)` pairs.
+///
+/// Counts are updated incrementally, so large buffers are cheap to scan.
+/// Returns nothing if `data` is shorter than one window or either size is 0.
+pub fn window_entropy(data: &[u8], window: usize, step: usize) -> Vec<(usize, f64)> {
+    if window == 0 || step == 0 || data.len() < window {
+        return Vec::new();
+    }
+    let mut freq = [0u64; 256];
+    for &b in &data[..window] {
+        freq[b as usize] += 1;
+    }
+
+    let mut out = vec![(0, entropy_from_counts(&freq, window))];
+    let mut start = 0;
+    while start + step + window <= data.len() {
+        for i in start..start + step {
+            freq[data[i] as usize] -= 1;
+            freq[data[i + window] as usize] += 1;
+        }
+        start += step;
+        out.push((start, entropy_from_counts(&freq, window)));
+    }
+    out
+}
+
+/// Byte ranges of `data` covered by `window`-byte windows (half-overlapping)
+/// whose entropy exceeds `threshold`, with touching ranges merged.
+pub fn high_entropy_regions(data: &[u8], window: usize, threshold: f64) -> Vec<Range<usize>> {
+    let mut regions: Vec<Range<usize>> = Vec::new();
+    for (offset, entropy) in window_entropy(data, window, (window / 2).max(1)) {
+        if entropy <= threshold {
+            continue;
+        }
+        match regions.last_mut() {
+            Some(last) if last.end >= offset => last.end = offset + window,
+            _ => regions.push(offset..offset + window),
+        }
+    }
+    regions
+}
+
 /// Detector for encrypted C2, data exfiltration, and cryptojacking.
 pub struct EntropyDetector {
     config: EntropyConfig,
@@ -78,22 +179,7 @@ impl EntropyDetector {
 
     /// Calculate Shannon entropy of a byte slice (0.0 - 8.0).
     pub fn shannon_entropy(data: &[u8]) -> f64 {
-        if data.is_empty() {
-            return 0.0;
-        }
-        let mut freq = [0u64; 256];
-        for &b in data {
-            freq[b as usize] += 1;
-        }
-        let len = data.len() as f64;
-        let mut entropy = 0.0;
-        for &count in &freq {
-            if count > 0 {
-                let p = count as f64 / len;
-                entropy -= p * p.log2();
-            }
-        }
-        entropy
+        shannon_entropy(data)
     }
 
     /// Check if a destination port is a known mining port.
@@ -386,6 +472,69 @@ mod tests {
         assert!(alerts.iter().any(|a| a.title.contains("Beaconing")));
     }
 
+    /// Deterministic pseudo-random bytes (xorshift64).
+    fn random_bytes(n: usize) -> Vec<u8> {
+        let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+        (0..n)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                (x >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_entropy_constant_and_random() {
+        assert!(shannon_entropy(&[0x41; 4096]) < 1e-9);
+        let e = shannon_entropy(&random_bytes(65536));
+        assert!((e - 8.0).abs() < 0.01, "expected ~8.0, got {e}");
+    }
+
+    #[test]
+    fn test_analyze_payload_entropy_threshold() {
+        let payloads = vec![
+            b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n".repeat(20),
+            random_bytes(2048),
+            Vec::new(),
+            vec![7u8; 512],
+            random_bytes(4096),
+        ];
+        let findings = analyze_payload_entropy(&payloads, 7.0);
+        let flagged: Vec<usize> = findings.iter().map(|f| f.index).collect();
+        assert_eq!(flagged, vec![1, 4]);
+        assert_eq!(findings[1].len, 4096);
+        assert!(findings.iter().all(|f| f.entropy > 7.0));
+
+        // Nothing passes an impossible threshold
+        assert!(analyze_payload_entropy(&payloads, 8.0).is_empty());
+    }
+
+    #[test]
+    fn test_window_entropy_finds_region() {
+        let mut data = vec![0u8; 4096];
+        data.extend(random_bytes(4096));
+        data.extend(vec![0u8; 4096]);
+
+        let windows = window_entropy(&data, 1024, 1024);
+        assert_eq!(windows.len(), 12);
+        // Incremental counts agree with a fresh calculation
+        for (offset, e) in &windows {
+            assert!((e - shannon_entropy(&data[*offset..offset + 1024])).abs() < 1e-9);
+        }
+
+        let regions = high_entropy_regions(&data, 1024, 7.0);
+        assert_eq!(regions, vec![4096..8192]);
+    }
+
+    #[test]
+    fn test_window_entropy_short_input() {
+        assert!(window_entropy(&[1, 2, 3], 8, 1).is_empty());
+        assert!(window_entropy(&[1, 2, 3], 0, 1).is_empty());
+        assert_eq!(window_entropy(&[1, 2, 3], 3, 1).len(), 1);
+    }
+
     #[test]
     fn test_name() {
         let d = EntropyDetector::new();
@@ -2,12 +2,14 @@
 //!
 //! Scans for nearby WiFi networks and detects:
 //! - Duplicate SSIDs with different BSSIDs (potential evil twin)
-//! - Known SSID appearing on unexpected channels or with different security
+//! - The same SSID advertised with conflicting encryption (downgrade clone)
+//! - Known SSID appearing on an untrusted BSSID, especially one louder than
+//!   the trusted APs
 
 use async_trait::async_trait;
 use chrono::Utc;
 use netsec_models::alert::{AlertCategory, NormalizedAlert, Severity};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use tracing::warn;
 
 use crate::{ThreatDetector, ThreatError, ThreatResult};
@@ -22,6 +24,124 @@ pub struct AccessPoint {
     pub encryption: String,
 }
 
+/// A single access point observation fed to [`detect_evil_twins`].
+pub type ApObservation = AccessPoint;
+
+/// What made an SSID look like an evil twin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvilTwinKind {
+    /// Several BSSIDs advertise the SSID and none are known-good.
+    DuplicateSsid,
+    /// The SSID is advertised with conflicting encryption.
+    EncryptionMismatch { downgrade_to_open: bool },
+    /// A known SSID is advertised by a BSSID outside its trusted list.
+    UntrustedBssid { bssid: String },
+    /// Like `UntrustedBssid`, but the rogue BSSID is louder than every trusted
+    /// BSSID in view, which is how an evil twin lures clients away.
+    StrongerThanTrusted { bssid: String },
+}
+
+/// A suspected evil twin, with the observations that conflict.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvilTwinFinding {
+    pub ssid: String,
+    pub kind: EvilTwinKind,
+    /// How likely this is an attack rather than a legitimate setup, 0.0–1.0.
+    pub confidence: f64,
+    /// For untrusted BSSIDs the rogue AP comes first, followed by the trusted
+    /// APs in view; otherwise every observation of the SSID.
+    pub observations: Vec<ApObservation>,
+}
+
+impl EvilTwinFinding {
+    fn to_alert(&self) -> Option<NormalizedAlert> {
+        let ssid = &self.ssid;
+        let aps: Vec<serde_json::Value> = self
+            .observations
+            .iter()
+            .map(|ap| {
+                serde_json::json!({
+                    "bssid": ap.bssid,
+                    "channel": ap.channel,
+                    "signal_dbm": ap.signal_dbm,
+                    "encryption": ap.encryption,
+                })
+            })
+            .collect();
+
+        let (severity, title, description, fingerprint) = match &self.kind {
+            EvilTwinKind::DuplicateSsid => return None,
+            EvilTwinKind::EncryptionMismatch { downgrade_to_open } => {
+                warn!(ssid = %ssid, "Suspicious: same SSID with mixed encryption");
+                let (severity, detail) = if *downgrade_to_open {
+                    (
+                        Severity::High,
+                        "(both open and encrypted). An open clone of an encrypted network \
+                         is a common evil twin technique.",
+                    )
+                } else {
+                    (
+                        Severity::Medium,
+                        "(different encryption levels). A weaker clone of the network \
+                         may be attempting a downgrade attack.",
+                    )
+                };
+                (
+                    severity,
+                    format!("Suspicious AP: '{}' seen with mixed encryption", ssid),
+                    format!(
+                        "SSID '{}' is broadcast by {} access points with mixed security {}",
+                        ssid,
+                        self.observations.len(),
+                        detail
+                    ),
+                    format!("evil-twin-mixed-enc-{}", ssid),
+                )
+            }
+            EvilTwinKind::UntrustedBssid { bssid }
+            | EvilTwinKind::StrongerThanTrusted { bssid } => {
+                warn!(
+                    ssid = %ssid,
+                    rogue_bssid = %bssid,
+                    "Evil twin detected: untrusted BSSID for known SSID"
+                );
+                let ap = &self.observations[0];
+                let louder = if matches!(self.kind, EvilTwinKind::StrongerThanTrusted { .. }) {
+                    " It is stronger than every trusted BSSID in range."
+                } else {
+                    ""
+                };
+                (
+                    Severity::Critical,
+                    format!("Evil twin AP: '{}' on untrusted BSSID {}", ssid, bssid),
+                    format!(
+                        "Access point with SSID '{}' detected on BSSID {} (channel {}, signal {} dBm, {}). \
+                         This BSSID is not in the trusted list.{} Possible evil twin attack.",
+                        ssid, bssid, ap.channel, ap.signal_dbm, ap.encryption, louder
+                    ),
+                    format!("evil-twin-{}-{}", ssid, bssid),
+                )
+            }
+        };
+
+        Some(NormalizedAlert {
+            source_tool: "netsec-threat".into(),
+            severity,
+            category: AlertCategory::NetworkThreat,
+            title,
+            description,
+            device_ip: None,
+            fingerprint,
+            raw_data: serde_json::json!({
+                "ssid": ssid,
+                "confidence": self.confidence,
+                "access_points": aps,
+            }),
+            timestamp: Utc::now(),
+        })
+    }
+}
+
 /// Detector for evil twin (rogue) access points.
 pub struct EvilTwinDetector {
     /// SSIDs we trust and their expected BSSIDs.
@@ -168,100 +288,125 @@ impl EvilTwinDetector {
         aps
     }
 
-    /// Analyze a list of access points for evil twin indicators.
-    pub fn analyze(&self, aps: &[AccessPoint]) -> Vec<NormalizedAlert> {
-        let mut alerts = Vec::new();
-
-        // Group by SSID
-        let mut ssid_groups: HashMap<&str, Vec<&AccessPoint>> = HashMap::new();
-        for ap in aps {
-            ssid_groups.entry(&ap.ssid).or_default().push(ap);
-        }
-
-        for (ssid, group) in &ssid_groups {
-            if group.len() < 2 {
-                continue;
+    /// Find evil twin indicators among the observed access points, checking
+    /// SSIDs with a trusted list against it.
+    pub fn find_evil_twins(&self, observations: &[ApObservation]) -> Vec<EvilTwinFinding> {
+        let mut by_ssid: BTreeMap<&str, Vec<&ApObservation>> = BTreeMap::new();
+        for ap in observations {
+            if !ap.ssid.is_empty() {
+                by_ssid.entry(&ap.ssid).or_default().push(ap);
             }
+        }
 
-            // Multiple APs with the same SSID — check if any are untrusted
-            if let Some(trusted_bssids) = self.trusted_ssids.get(*ssid) {
-                for ap in group {
-                    if !trusted_bssids.contains(&ap.bssid) {
-                        warn!(
-                            ssid = %ssid,
-                            rogue_bssid = %ap.bssid,
-                            "Evil twin detected: untrusted BSSID for known SSID"
-                        );
-                        alerts.push(NormalizedAlert {
-                            source_tool: "netsec-threat".into(),
-                            severity: Severity::Critical,
-                            category: AlertCategory::NetworkThreat,
-                            title: format!(
-                                "Evil twin AP: '{}' on untrusted BSSID {}",
-                                ssid, ap.bssid
-                            ),
-                            description: format!(
-                                "Access point with SSID '{}' detected on BSSID {} (channel {}, signal {} dBm, {}). \
-                                 This BSSID is not in the trusted list. Possible evil twin attack.",
-                                ssid, ap.bssid, ap.channel, ap.signal_dbm, ap.encryption
-                            ),
-                            device_ip: None,
-                            fingerprint: format!("evil-twin-{}-{}", ssid, ap.bssid),
-                            raw_data: serde_json::json!({
-                                "ssid": ssid,
-                                "rogue_bssid": ap.bssid,
-                                "channel": ap.channel,
-                                "signal_dbm": ap.signal_dbm,
-                                "encryption": ap.encryption,
-                                "trusted_bssids": trusted_bssids,
-                            }),
-                            timestamp: Utc::now(),
-                        });
-                    }
-                }
-            } else {
-                // No trusted BSSIDs configured — just flag duplicate SSIDs
-                // Check for different encryption levels (open clone of encrypted network)
-                let has_open = group.iter().any(|ap| ap.encryption.to_lowercase().contains("open") || ap.encryption.is_empty());
-                let has_encrypted = group.iter().any(|ap| !ap.encryption.to_lowercase().contains("open") && !ap.encryption.is_empty());
-
-                if has_open && has_encrypted {
-                    warn!(ssid = %ssid, "Suspicious: same SSID with mixed encryption");
-                    alerts.push(NormalizedAlert {
-                        source_tool: "netsec-threat".into(),
-                        severity: Severity::High,
-                        category: AlertCategory::NetworkThreat,
-                        title: format!("Suspicious AP: '{}' seen with mixed encryption", ssid),
-                        description: format!(
-                            "SSID '{}' is broadcast by {} access points with mixed security \
-                             (both open and encrypted). An open clone of an encrypted network \
-                             is a common evil twin technique.",
-                            ssid,
-                            group.len()
-                        ),
-                        device_ip: None,
-                        fingerprint: format!("evil-twin-mixed-enc-{}", ssid),
-                        raw_data: serde_json::json!({
-                            "ssid": ssid,
-                            "access_points": group.iter().map(|ap| {
-                                serde_json::json!({
-                                    "bssid": ap.bssid,
-                                    "channel": ap.channel,
-                                    "signal_dbm": ap.signal_dbm,
-                                    "encryption": ap.encryption,
-                                })
-                            }).collect::<Vec<_>>(),
-                        }),
-                        timestamp: Utc::now(),
+        let mut findings = Vec::new();
+        for (ssid, group) in by_ssid {
+            let trusted = self.trusted_ssids.get(ssid);
+            let mismatch = encryption_mismatch(ssid, &group);
+            let bssids: BTreeSet<String> = group.iter().map(|ap| ap.bssid.to_uppercase()).collect();
+
+            match trusted {
+                Some(trusted) => findings.extend(untrusted_bssids(ssid, &group, trusted)),
+                None if bssids.len() > 1 && mismatch.is_none() => {
+                    findings.push(EvilTwinFinding {
+                        ssid: ssid.to_string(),
+                        kind: EvilTwinKind::DuplicateSsid,
+                        confidence: (0.2 + 0.1 * bssids.len() as f64).min(0.6),
+                        observations: group.iter().map(|ap| (*ap).clone()).collect(),
                     });
                 }
+                None => {}
             }
+            findings.extend(mismatch);
         }
+        findings
+    }
 
-        alerts
+    /// Analyze a list of access points for evil twin indicators.
+    ///
+    /// Every finding except a bare [`EvilTwinKind::DuplicateSsid`] becomes an
+    /// alert; several BSSIDs sharing an SSID with matching security is normal
+    /// for multi-AP networks and would be noise on its own.
+    pub fn analyze(&self, aps: &[AccessPoint]) -> Vec<NormalizedAlert> {
+        self.find_evil_twins(aps)
+            .iter()
+            .filter_map(EvilTwinFinding::to_alert)
+            .collect()
     }
 }
 
+/// Detect evil twin access points without a trusted-BSSID list.
+///
+/// See [`EvilTwinDetector::find_evil_twins`] to also flag known SSIDs seen on
+/// unexpected BSSIDs.
+pub fn detect_evil_twins(observations: &[ApObservation]) -> Vec<EvilTwinFinding> {
+    EvilTwinDetector::new().find_evil_twins(observations)
+}
+
+/// Rough strength of an advertised security mode: open < WEP < WPA < WPA2 < WPA3.
+fn security_rank(encryption: &str) -> u8 {
+    let enc = encryption.to_lowercase();
+    if enc.contains("wpa3") || enc.contains("sae") {
+        4
+    } else if enc.contains("wpa2") || enc.contains("rsn") {
+        3
+    } else if enc.contains("wpa") {
+        2
+    } else if enc.contains("wep") {
+        1
+    } else {
+        // "Open", "--" (nmcli) and an empty field
+        0
+    }
+}
+
+/// A finding when the SSID is advertised with more than one security level.
+fn encryption_mismatch(ssid: &str, group: &[&ApObservation]) -> Option<EvilTwinFinding> {
+    let ranks: BTreeSet<u8> = group.iter().map(|ap| security_rank(&ap.encryption)).collect();
+    if ranks.len() < 2 {
+        return None;
+    }
+    let downgrade_to_open = ranks.contains(&0);
+    Some(EvilTwinFinding {
+        ssid: ssid.to_string(),
+        kind: EvilTwinKind::EncryptionMismatch { downgrade_to_open },
+        confidence: if downgrade_to_open { 0.9 } else { 0.7 },
+        observations: group.iter().map(|ap| (*ap).clone()).collect(),
+    })
+}
+
+/// Findings for BSSIDs advertising a known SSID that aren't in its trusted list.
+fn untrusted_bssids(
+    ssid: &str,
+    group: &[&ApObservation],
+    trusted: &[String],
+) -> Vec<EvilTwinFinding> {
+    let is_trusted = |ap: &ApObservation| trusted.iter().any(|b| b.eq_ignore_ascii_case(&ap.bssid));
+    let (good, rogue): (Vec<&ApObservation>, Vec<&ApObservation>) =
+        group.iter().copied().partition(|ap| is_trusted(ap));
+    let strongest_good = good.iter().map(|ap| ap.signal_dbm).max();
+
+    rogue
+        .into_iter()
+        .map(|ap| {
+            let bssid = ap.bssid.clone();
+            let (kind, confidence) = match strongest_good {
+                Some(signal) if ap.signal_dbm > signal => {
+                    (EvilTwinKind::StrongerThanTrusted { bssid }, 0.95)
+                }
+                _ => (EvilTwinKind::UntrustedBssid { bssid }, 0.8),
+            };
+            let mut observations = vec![ap.clone()];
+            observations.extend(good.iter().map(|ap| (*ap).clone()));
+            EvilTwinFinding {
+                ssid: ssid.to_string(),
+                kind,
+                confidence,
+                observations,
+            }
+        })
+        .collect()
+}
+
 impl Default for EvilTwinDetector {
     fn default() -> Self {
         Self::new()
@@ -427,6 +572,81 @@ wlan0     Scan completed :
         assert!(alerts.is_empty());
     }
 
+    fn ap(ssid: &str, bssid: &str, signal_dbm: i32, encryption: &str) -> ApObservation {
+        ApObservation {
+            ssid: ssid.into(),
+            bssid: bssid.into(),
+            channel: 6,
+            signal_dbm,
+            encryption: encryption.into(),
+        }
+    }
+
+    #[test]
+    fn test_detect_duplicate_bssids() {
+        let findings = detect_evil_twins(&[
+            ap("Office", "AA:AA:AA:00:00:01", -50, "WPA2"),
+            ap("Office", "BB:BB:BB:00:00:02", -55, "WPA2"),
+            ap("Office", "aa:aa:aa:00:00:01", -52, "WPA2"),
+            ap("Lobby", "CC:CC:CC:00:00:03", -60, "WPA2"),
+        ]);
+        assert_eq!(findings.len(), 1);
+        let f = &findings[0];
+        assert_eq!(f.ssid, "Office");
+        assert_eq!(f.kind, EvilTwinKind::DuplicateSsid);
+        assert_eq!(f.observations.len(), 3);
+        // Two distinct BSSIDs — the repeated sighting isn't counted twice
+        assert!((f.confidence - 0.4).abs() < 1e-9);
+
+        // Matching security across BSSIDs is common, so it isn't alerted on
+        assert!(EvilTwinDetector::new().analyze(&f.observations).is_empty());
+    }
+
+    #[test]
+    fn test_detect_encryption_downgrade() {
+        let findings = detect_evil_twins(&[
+            ap("CoffeeShop", "AA:AA:AA:00:00:01", -50, "WPA2"),
+            ap("CoffeeShop", "BB:BB:BB:00:00:01", -45, "--"),
+        ]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].kind,
+            EvilTwinKind::EncryptionMismatch { downgrade_to_open: true }
+        );
+        assert!(findings[0].confidence >= 0.9);
+
+        let findings = detect_evil_twins(&[
+            ap("Corp", "AA:AA:AA:00:00:01", -50, "WPA3"),
+            ap("Corp", "BB:BB:BB:00:00:01", -45, "WPA2"),
+        ]);
+        assert_eq!(
+            findings[0].kind,
+            EvilTwinKind::EncryptionMismatch { downgrade_to_open: false }
+        );
+        let alerts = EvilTwinDetector::new().analyze(&findings[0].observations);
+        assert_eq!(alerts[0].severity, Severity::Medium);
+    }
+
+    #[test]
+    fn test_detect_stronger_than_trusted() {
+        let mut trusted = HashMap::new();
+        trusted.insert("HomeBase".into(), vec!["AA:BB:CC:DD:EE:01".into()]);
+        let detector = EvilTwinDetector::with_trusted(trusted);
+
+        let findings = detector.find_evil_twins(&[
+            ap("HomeBase", "aa:bb:cc:dd:ee:01", -70, "WPA2"),
+            ap("HomeBase", "FF:FF:FF:00:00:99", -40, "WPA2"),
+        ]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].kind,
+            EvilTwinKind::StrongerThanTrusted { bssid: "FF:FF:FF:00:00:99".into() }
+        );
+        assert_eq!(findings[0].observations[0].bssid, "FF:FF:FF:00:00:99");
+        assert_eq!(findings[0].observations[1].bssid, "aa:bb:cc:dd:ee:01");
+        assert!(findings[0].confidence > 0.9);
+    }
+
     #[test]
     fn test_name() {
         let d = EvilTwinDetector::new();
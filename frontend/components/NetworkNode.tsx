@@ -82,6 +82,11 @@ export const NetworkNode: React.FC<NetworkNodeProps> = ({ node, isSelected, onMo
          </>
        )}
 
+       {/* Recently Changed */}
+       {node.highlighted && !isSelected && (
+         <circle r="30" fill="none" stroke="#22d3ee" strokeWidth="2" opacity="0.8" className="animate-pulse-ring" />
+       )}
+
        {/* Compromised Effect */}
        {isCompromised && (
          <>
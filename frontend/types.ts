@@ -232,6 +232,7 @@ export interface Node {
   apiData?: ApiDevice; // Full API data reference
   vulnerabilities?: string[]; // Vulnerabilities found (simple list)
   detailedVulnerabilities?: Vulnerability[]; // Structured list
+  highlighted?: boolean; // Recently changed by a refresh
 }
 
 export interface Connection {
@@ -49,6 +49,7 @@ interface NodeJson {
   parent_id?: string;
   width?: number;
   height?: number;
+  highlighted?: boolean;
 }
 
 interface PortJson {
@@ -116,6 +117,7 @@ function jsonToNode(json: NodeJson): Node {
       description: v.description,
       references: v.references || [],
     })),
+    highlighted: json.highlighted,
   };
 }
 
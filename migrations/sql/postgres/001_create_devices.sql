@@ -0,0 +1,18 @@
+-- Discovered network devices
+CREATE TABLE IF NOT EXISTS devices (
+    id TEXT PRIMARY KEY NOT NULL,
+    ip TEXT NOT NULL,
+    mac TEXT,
+    hostname TEXT,
+    vendor TEXT,
+    os_family TEXT,
+    device_type TEXT NOT NULL DEFAULT 'unknown',
+    classification_confidence DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    status TEXT NOT NULL DEFAULT 'unknown',
+    first_seen TEXT NOT NULL,
+    last_seen TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_devices_ip ON devices(ip);
+CREATE INDEX IF NOT EXISTS idx_devices_mac ON devices(mac);
+CREATE INDEX IF NOT EXISTS idx_devices_status ON devices(status);
@@ -0,0 +1,2 @@
+-- Add notes field to alerts table for user comments
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS notes TEXT;
@@ -0,0 +1,3 @@
+-- Add os_version and notes fields to devices table
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS os_version TEXT;
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS notes TEXT;
@@ -0,0 +1,10 @@
+-- Add tracking fields to alerts table
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS source_event_id TEXT;
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS device_id TEXT;
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS raw_data TEXT;
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS first_seen TEXT;
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS last_seen TEXT;
+
+-- Backfill first_seen/last_seen from created_at for existing rows
+UPDATE alerts SET first_seen = created_at WHERE first_seen IS NULL;
+UPDATE alerts SET last_seen = updated_at WHERE last_seen IS NULL;
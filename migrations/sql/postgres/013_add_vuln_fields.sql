@@ -0,0 +1,5 @@
+-- Add service, device_ip, status, and references fields to vulnerabilities table
+ALTER TABLE vulnerabilities ADD COLUMN IF NOT EXISTS service TEXT;
+ALTER TABLE vulnerabilities ADD COLUMN IF NOT EXISTS device_ip TEXT;
+ALTER TABLE vulnerabilities ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'open';
+ALTER TABLE vulnerabilities ADD COLUMN IF NOT EXISTS references_json TEXT;
@@ -0,0 +1,3 @@
+-- Add structured web-service findings (from NSE http-title / http-server-header) to ports
+ALTER TABLE ports ADD COLUMN IF NOT EXISTS http_title TEXT;
+ALTER TABLE ports ADD COLUMN IF NOT EXISTS server_header TEXT;
@@ -0,0 +1,2 @@
+-- Persist scheduler run history so interval timing survives restarts
+ALTER TABLE scheduled_jobs ADD COLUMN IF NOT EXISTS last_run TEXT;
@@ -0,0 +1,2 @@
+-- IANA timezone that cron expressions are evaluated in
+ALTER TABLE scheduled_jobs ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'UTC';
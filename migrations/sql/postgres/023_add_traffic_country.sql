@@ -0,0 +1,4 @@
+-- ISO 3166-1 alpha-2 country codes from geolocation enrichment, NULL for
+-- private, reserved or unknown addresses
+ALTER TABLE traffic_flows ADD COLUMN IF NOT EXISTS country_src TEXT;
+ALTER TABLE traffic_flows ADD COLUMN IF NOT EXISTS country_dst TEXT;
@@ -0,0 +1,11 @@
+-- Append-only analyst note history per alert
+CREATE TABLE IF NOT EXISTS alert_notes (
+    id TEXT PRIMARY KEY NOT NULL,
+    alert_id TEXT NOT NULL REFERENCES alerts(id) ON DELETE CASCADE,
+    seq BIGINT NOT NULL,
+    author TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_alert_notes_alert_seq ON alert_notes(alert_id, seq);
@@ -0,0 +1,11 @@
+-- Device lifecycle events
+CREATE TABLE IF NOT EXISTS device_events (
+    id TEXT PRIMARY KEY NOT NULL,
+    device_id TEXT NOT NULL REFERENCES devices(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    details TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_events_device_id ON device_events(device_id);
+CREATE INDEX IF NOT EXISTS idx_device_events_type ON device_events(event_type);
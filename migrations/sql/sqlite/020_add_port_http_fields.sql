@@ -0,0 +1,3 @@
+-- Add structured web-service findings (from NSE http-title / http-server-header) to ports
+ALTER TABLE ports ADD COLUMN http_title TEXT;
+ALTER TABLE ports ADD COLUMN server_header TEXT;
@@ -0,0 +1,2 @@
+-- Persist scheduler run history so interval timing survives restarts
+ALTER TABLE scheduled_jobs ADD COLUMN last_run TEXT;
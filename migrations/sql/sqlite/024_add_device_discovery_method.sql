@@ -0,0 +1,2 @@
+-- How a device was first discovered ('active' or 'passive')
+ALTER TABLE devices ADD COLUMN discovery_method TEXT;
@@ -53,12 +53,15 @@ class DispatchConfig(BaseModel):
 class AlertsConfig(BaseModel):
     dedup_window_seconds: int = 300
     max_alerts_per_minute: int = 100
+    auto_resolve_after_hours: int = 0
     dispatch: DispatchConfig = DispatchConfig()
 
 
 class ToolsConfig(BaseModel):
     scan_timeout: int = 300
     max_concurrent_scans: int = 3
+    mac_allowlist: list[str] = []
+    scan_cooldown_secs: int = 60
 
 
 class SentinelProcessConfig(BaseModel):
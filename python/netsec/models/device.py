@@ -38,5 +38,7 @@ class Port(Base):
     service_name: Mapped[Optional[str]] = mapped_column(String(100))
     service_version: Mapped[Optional[str]] = mapped_column(String(255))
     banner: Mapped[Optional[str]] = mapped_column(Text)
+    http_title: Mapped[Optional[str]] = mapped_column(Text)
+    server_header: Mapped[Optional[str]] = mapped_column(String(255))
 
     device: Mapped[Device] = relationship(back_populates="ports")
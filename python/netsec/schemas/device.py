@@ -15,6 +15,8 @@ class PortOut(BaseModel):
     service_name: Optional[str] = None
     service_version: Optional[str] = None
     banner: Optional[str] = None
+    http_title: Optional[str] = None
+    server_header: Optional[str] = None
 
     model_config = {"from_attributes": True}
 
@@ -0,0 +1,38 @@
+"""Test the Rust EventBus bindings in netsec_core."""
+import asyncio
+
+import pytest
+
+netsec_core = pytest.importorskip("netsec_core")
+
+
+def test_publish_and_receive_one_event():
+    bus = netsec_core.EventBus()
+    receiver = bus.subscribe()
+    assert bus.subscriber_count() == 1
+
+    reached = bus.publish("scan_completed", {"scan_id": "abc", "hosts": [1, 2]})
+    assert reached == 1
+
+    event = receiver.recv(timeout=1.0)
+    assert event["event_type"] == "scan_completed"
+    assert event["payload"] == {"scan_id": "abc", "hosts": [1, 2]}
+    assert event["id"] and event["timestamp"]
+    assert receiver.recv(timeout=0.05) is None
+
+
+def test_publish_unknown_event_type():
+    bus = netsec_core.EventBus()
+    with pytest.raises(ValueError):
+        bus.publish("not_an_event", {})
+
+
+async def test_recv_from_asyncio():
+    bus = netsec_core.EventBus()
+    receiver = bus.subscribe()
+    pending = asyncio.create_task(asyncio.to_thread(receiver.recv, 2.0))
+    await asyncio.sleep(0.05)
+    bus.publish("alert_created")
+    event = await pending
+    assert event["event_type"] == "alert_created"
+    assert event["payload"] == {}
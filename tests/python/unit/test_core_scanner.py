@@ -0,0 +1,28 @@
+"""Test the Rust active scan bindings in netsec_core."""
+import asyncio
+
+import pytest
+
+netsec_core = pytest.importorskip("netsec_core")
+
+
+def test_build_nmap_args():
+    args = netsec_core.build_nmap_args("192.168.1.0/24", "port", 4, "22,80")
+    assert args == ["-sS", "-T4", "-p", "22,80", "-oX", "-", "192.168.1.0/24"]
+
+
+def test_invalid_config_raises_scanner_error():
+    with pytest.raises(netsec_core.ScannerError):
+        netsec_core.build_nmap_args("10.0.0.1; rm -rf /", "port")
+    with pytest.raises(netsec_core.ScannerError):
+        netsec_core.build_nmap_args("10.0.0.1", "bogus")
+    with pytest.raises(netsec_core.ScannerError):
+        netsec_core.build_nmap_args("10.0.0.1", "port", timing=9)
+
+
+async def test_dry_run_scan(tmp_path):
+    scanner = netsec_core.Scanner(str(tmp_path / "netsec.db"))
+    devices = await asyncio.to_thread(
+        scanner.run_scan, "10.0.0.0/24", "port", 3, "22", dry_run=True
+    )
+    assert devices == []
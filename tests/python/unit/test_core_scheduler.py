@@ -0,0 +1,71 @@
+"""Test the Rust scheduler bindings in netsec_core against known cases."""
+import json
+
+import pytest
+
+netsec_core = pytest.importorskip("netsec_core")
+scheduler = netsec_core.scheduler
+
+
+@pytest.mark.parametrize(
+    "expr, now, expected",
+    [
+        ("0 * * * *", "2024-06-15T12:00:00Z", True),
+        ("0 * * * *", "2024-06-15T12:01:00Z", False),
+        ("*/15 * * * *", "2024-06-15T12:30:00Z", True),
+        ("*/15 * * * *", "2024-06-15T12:31:00Z", False),
+        ("0 9 * * 1-5", "2024-06-17T09:00:00Z", True),  # Monday
+        ("0 9 * * 1-5", "2024-06-15T09:00:00Z", False),  # Saturday
+        ("not a cron", "2024-06-15T12:00:00Z", False),
+    ],
+)
+def test_cron_is_due(expr, now, expected):
+    assert scheduler.cron_is_due(expr, now) is expected
+
+
+def test_cron_is_due_in_timezone():
+    # 13:00 UTC is 09:00 in New York during daylight saving time
+    assert scheduler.cron_is_due("0 9 * * *", "2024-06-15T13:00:00Z", "America/New_York")
+    assert not scheduler.cron_is_due("0 9 * * *", "2024-06-15T09:00:00Z", "America/New_York")
+
+
+@pytest.mark.parametrize(
+    "last_run, interval, now, expected",
+    [
+        (None, 60, "2024-06-15T12:00:00Z", True),
+        ("2024-06-15T11:00:00Z", 3600, "2024-06-15T12:00:00Z", True),
+        ("2024-06-15T11:30:00Z", 3600, "2024-06-15T12:00:00Z", False),
+        ("garbage", 60, "2024-06-15T12:00:00Z", False),
+    ],
+)
+def test_interval_is_due(last_run, interval, now, expected):
+    assert scheduler.interval_is_due(last_run, interval, now) is expected
+
+
+def test_parse_trigger_args():
+    assert scheduler.parse_interval_args(json.dumps({"interval_secs": 300})) == 300
+    assert scheduler.parse_cron_args(json.dumps({"cron": "0 * * * *"})) == "0 * * * *"
+    with pytest.raises(ValueError):
+        scheduler.parse_interval_args("{}")
+    with pytest.raises(ValueError):
+        scheduler.parse_cron_args(json.dumps({"interval_secs": 60}))
+
+
+def test_next_run_after():
+    cron = json.dumps({"cron": "0 9 * * 1"})
+    assert scheduler.next_run_after("cron", cron, "2024-06-15T12:00:00Z") == (
+        "2024-06-17T09:00:00+00:00"
+    )
+    interval = json.dumps({"interval_secs": 60})
+    assert scheduler.next_run_after("interval", interval, "2024-06-15T12:00:00Z") == (
+        "2024-06-15T12:01:00+00:00"
+    )
+
+
+def test_invalid_input_raises_value_error():
+    with pytest.raises(ValueError):
+        scheduler.cron_is_due("* * * * *", "2024-06-15T12:00:00")  # naive time
+    with pytest.raises(ValueError):
+        scheduler.cron_is_due("* * * * *", "2024-06-15T12:00:00Z", "Mars/Olympus")
+    with pytest.raises(ValueError):
+        scheduler.interval_is_due(None, 60, "yesterday")